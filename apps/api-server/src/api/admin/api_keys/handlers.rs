@@ -0,0 +1,187 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use domain::models::{
+    ApiKeyCreatedDto, ApiKeyDto, CreateApiKeyPayload, CreateServicePrincipalPayload,
+    RotateApiKeyPayload, ServicePrincipalDto, UpdateServicePrincipalPayload,
+};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    extractors::current_user::CurrentUser,
+    infra::{errors::AppError, state::AppState},
+};
+
+/// GET /admin/service-principals
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/service-principals",
+    tag = "Admin",
+    responses(
+        (status = 200, description = "Service principals cadastrados", body = Vec<ServicePrincipalDto>)
+    )
+)]
+pub async fn list_principals(
+    State(state): State<AppState>,
+) -> Result<Json<Vec<ServicePrincipalDto>>, AppError> {
+    let principals = state.api_key_service.list_principals().await?;
+    Ok(Json(principals))
+}
+
+/// POST /admin/service-principals
+///
+/// Identidade de uma integração. A `role` define o teto de permissões de
+/// todas as chaves do principal.
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/service-principals",
+    tag = "Admin",
+    request_body = CreateServicePrincipalPayload,
+    responses(
+        (status = 201, description = "Service principal criado", body = ServicePrincipalDto),
+        (status = 409, description = "Nome já utilizado")
+    )
+)]
+pub async fn create_principal(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Json(payload): Json<CreateServicePrincipalPayload>,
+) -> Result<(StatusCode, Json<ServicePrincipalDto>), AppError> {
+    payload.validate().map_err(AppError::Validation)?;
+
+    let principal = state
+        .api_key_service
+        .create_principal(payload, current_user.id)
+        .await?;
+    Ok((StatusCode::CREATED, Json(principal)))
+}
+
+/// PATCH /admin/service-principals/{id}
+///
+/// Desativar o principal invalida imediatamente todas as suas chaves.
+#[utoipa::path(
+    patch,
+    path = "/api/v1/admin/service-principals/{id}",
+    tag = "Admin",
+    params(("id" = Uuid, Path, description = "ID do service principal")),
+    request_body = UpdateServicePrincipalPayload,
+    responses(
+        (status = 200, description = "Service principal atualizado", body = ServicePrincipalDto),
+        (status = 404, description = "Service principal não encontrado")
+    )
+)]
+pub async fn update_principal(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpdateServicePrincipalPayload>,
+) -> Result<Json<ServicePrincipalDto>, AppError> {
+    payload.validate().map_err(AppError::Validation)?;
+
+    let principal = state.api_key_service.update_principal(id, payload).await?;
+    Ok(Json(principal))
+}
+
+/// GET /admin/service-principals/{id}/api-keys
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/service-principals/{id}/api-keys",
+    tag = "Admin",
+    params(("id" = Uuid, Path, description = "ID do service principal")),
+    responses(
+        (status = 200, description = "Chaves do principal (sem os segredos)", body = Vec<ApiKeyDto>),
+        (status = 404, description = "Service principal não encontrado")
+    )
+)]
+pub async fn list_keys(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<ApiKeyDto>>, AppError> {
+    let keys = state.api_key_service.list_keys(id).await?;
+    Ok(Json(keys))
+}
+
+/// POST /admin/service-principals/{id}/api-keys
+///
+/// A chave completa só é exibida nesta resposta.
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/service-principals/{id}/api-keys",
+    tag = "Admin",
+    params(("id" = Uuid, Path, description = "ID do service principal")),
+    request_body = CreateApiKeyPayload,
+    responses(
+        (status = 201, description = "Chave criada", body = ApiKeyCreatedDto),
+        (status = 400, description = "Escopo, IP ou validade inválidos"),
+        (status = 404, description = "Service principal não encontrado")
+    )
+)]
+pub async fn create_key(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<CreateApiKeyPayload>,
+) -> Result<(StatusCode, Json<ApiKeyCreatedDto>), AppError> {
+    payload.validate().map_err(AppError::Validation)?;
+
+    let created = state
+        .api_key_service
+        .create_key(id, payload, current_user.id)
+        .await?;
+    Ok((StatusCode::CREATED, Json(created)))
+}
+
+/// POST /admin/api-keys/{id}/rotate
+///
+/// Emite a chave substituta; a atual continua válida durante o período de
+/// transição (`grace_period_secs`, padrão 24h).
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/api-keys/{id}/rotate",
+    tag = "Admin",
+    params(("id" = Uuid, Path, description = "ID da chave")),
+    request_body = RotateApiKeyPayload,
+    responses(
+        (status = 201, description = "Chave substituta criada", body = ApiKeyCreatedDto),
+        (status = 400, description = "Chave revogada ou expirada"),
+        (status = 404, description = "Chave não encontrada")
+    )
+)]
+pub async fn rotate_key(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Path(id): Path<Uuid>,
+    payload: Option<Json<RotateApiKeyPayload>>,
+) -> Result<(StatusCode, Json<ApiKeyCreatedDto>), AppError> {
+    let payload = payload.map(|Json(p)| p).unwrap_or_default();
+    payload.validate().map_err(AppError::Validation)?;
+
+    let created = state
+        .api_key_service
+        .rotate_key(id, payload, current_user.id)
+        .await?;
+    Ok((StatusCode::CREATED, Json(created)))
+}
+
+/// DELETE /admin/api-keys/{id}
+///
+/// Revogação imediata, sem período de transição.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/admin/api-keys/{id}",
+    tag = "Admin",
+    params(("id" = Uuid, Path, description = "ID da chave")),
+    responses(
+        (status = 204, description = "Chave revogada"),
+        (status = 404, description = "Chave não encontrada ou já revogada")
+    )
+)]
+pub async fn revoke_key(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, AppError> {
+    state.api_key_service.revoke_key(id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
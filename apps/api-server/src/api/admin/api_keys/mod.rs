@@ -0,0 +1,22 @@
+pub mod handlers;
+
+use crate::infra::state::AppState;
+use axum::{
+    routing::{delete, get, patch, post},
+    Router,
+};
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/service-principals",
+            get(handlers::list_principals).post(handlers::create_principal),
+        )
+        .route("/service-principals/{id}", patch(handlers::update_principal))
+        .route(
+            "/service-principals/{id}/api-keys",
+            get(handlers::list_keys).post(handlers::create_key),
+        )
+        .route("/api-keys/{id}/rotate", post(handlers::rotate_key))
+        .route("/api-keys/{id}", delete(handlers::revoke_key))
+}
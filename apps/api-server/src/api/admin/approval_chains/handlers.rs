@@ -0,0 +1,144 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use domain::models::{
+    ApprovalChainDto, ApprovalDocumentType, CreateApprovalChainPayload, UpdateApprovalChainPayload,
+};
+use serde::Deserialize;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    extractors::current_user::CurrentUser,
+    infra::{errors::AppError, state::AppState},
+};
+
+#[derive(Debug, Deserialize)]
+pub struct ApprovalChainListQuery {
+    pub document_type: Option<ApprovalDocumentType>,
+}
+
+/// GET /admin/approval-chains
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/approval-chains",
+    tag = "Admin",
+    params(("document_type" = Option<ApprovalDocumentType>, Query, description = "Tipo de documento")),
+    responses(
+        (status = 200, description = "Cadeias de aprovação", body = Vec<ApprovalChainDto>)
+    )
+)]
+pub async fn list_chains(
+    State(state): State<AppState>,
+    Query(query): Query<ApprovalChainListQuery>,
+) -> Result<Json<Vec<ApprovalChainDto>>, AppError> {
+    let chains = state
+        .approval_chain_service
+        .list_chains(query.document_type)
+        .await?;
+    Ok(Json(chains))
+}
+
+/// POST /admin/approval-chains
+///
+/// Cria uma cadeia de aprovação para um tipo de documento. A cadeia se
+/// aplica aos documentos que atendem a todas as condições informadas
+/// (valor mínimo, prioridades, almoxarifado e unidade de destino); entre
+/// as aplicáveis, vale a de maior precedência. Etapas com a mesma ordem
+/// correm em paralelo.
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/approval-chains",
+    tag = "Admin",
+    request_body = CreateApprovalChainPayload,
+    responses(
+        (status = 201, description = "Cadeia criada", body = ApprovalChainDto),
+        (status = 400, description = "Condições ou etapas inválidas")
+    )
+)]
+pub async fn create_chain(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Json(payload): Json<CreateApprovalChainPayload>,
+) -> Result<(StatusCode, Json<ApprovalChainDto>), AppError> {
+    payload.validate().map_err(AppError::Validation)?;
+
+    let chain = state
+        .approval_chain_service
+        .create_chain(payload, current_user.id)
+        .await?;
+    Ok((StatusCode::CREATED, Json(chain)))
+}
+
+/// GET /admin/approval-chains/{id}
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/approval-chains/{id}",
+    tag = "Admin",
+    params(("id" = Uuid, Path, description = "ID da cadeia")),
+    responses(
+        (status = 200, description = "Cadeia de aprovação", body = ApprovalChainDto),
+        (status = 404, description = "Cadeia não encontrada")
+    )
+)]
+pub async fn get_chain(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApprovalChainDto>, AppError> {
+    let chain = state.approval_chain_service.get_chain(id).await?;
+    Ok(Json(chain))
+}
+
+/// PUT /admin/approval-chains/{id}
+///
+/// Substitui condições e etapas. Documentos já em aprovação seguem com as
+/// etapas com que foram iniciados.
+#[utoipa::path(
+    put,
+    path = "/api/v1/admin/approval-chains/{id}",
+    tag = "Admin",
+    params(("id" = Uuid, Path, description = "ID da cadeia")),
+    request_body = UpdateApprovalChainPayload,
+    responses(
+        (status = 200, description = "Cadeia atualizada", body = ApprovalChainDto),
+        (status = 400, description = "Condições ou etapas inválidas"),
+        (status = 404, description = "Cadeia não encontrada")
+    )
+)]
+pub async fn update_chain(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpdateApprovalChainPayload>,
+) -> Result<Json<ApprovalChainDto>, AppError> {
+    payload.validate().map_err(AppError::Validation)?;
+
+    let chain = state
+        .approval_chain_service
+        .update_chain(id, payload)
+        .await?;
+    Ok(Json(chain))
+}
+
+/// DELETE /admin/approval-chains/{id}
+///
+/// Desativa a cadeia para novos documentos.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/admin/approval-chains/{id}",
+    tag = "Admin",
+    params(("id" = Uuid, Path, description = "ID da cadeia")),
+    responses(
+        (status = 204, description = "Cadeia desativada"),
+        (status = 404, description = "Cadeia não encontrada"),
+        (status = 409, description = "Cadeia já desativada")
+    )
+)]
+pub async fn deactivate_chain(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, AppError> {
+    state.approval_chain_service.deactivate_chain(id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,18 @@
+pub mod handlers;
+
+use crate::infra::state::AppState;
+use axum::{routing::get, Router};
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/approval-chains",
+            get(handlers::list_chains).post(handlers::create_chain),
+        )
+        .route(
+            "/approval-chains/{id}",
+            get(handlers::get_chain)
+                .put(handlers::update_chain)
+                .delete(handlers::deactivate_chain),
+        )
+}
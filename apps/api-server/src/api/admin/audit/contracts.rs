@@ -1,4 +1,4 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use domain::models::AuditLogEntry;
 use serde::{Deserialize, Serialize};
 
@@ -33,6 +33,13 @@ pub struct CleanupRequest {
     pub retention_days: i64,
 }
 
+/// Day range for chain verification and checkpoint listing (UTC dates)
+#[derive(Debug, Deserialize)]
+pub struct ChainRangeQuery {
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+}
+
 #[derive(Debug, Serialize)]
 pub struct ActionCountDto {
     pub action: String,
@@ -1,13 +1,16 @@
 use super::contracts::{
-    ActionCountDto, AuditLogFilter, AuditLogListResponse, AuditLogStatsResponse, CleanupRequest,
-    ResourceCountDto, SuspiciousIpDto,
+    ActionCountDto, AuditLogFilter, AuditLogListResponse, AuditLogStatsResponse, ChainRangeQuery,
+    CleanupRequest, ResourceCountDto, SuspiciousIpDto,
 };
 use crate::infra::{errors::AppError, state::AppState};
 use axum::{
     extract::{Path, Query, State},
     Json,
 };
-use domain::models::AuditLogEntry;
+use domain::models::{
+    AuditArchiveResult, AuditChainVerification, AuditCheckpointDto, AuditLogEntry, AuditSealResult,
+    EntityChangeDto,
+};
 use persistence::repositories::audit_logs_repository::AuditLogRepository;
 use serde::Deserialize;
 use uuid::Uuid;
@@ -92,6 +95,34 @@ pub async fn get_log(
     Ok(Json(map_log_entry(log)))
 }
 
+/// GET /admin/audit-logs/:id/changes
+///
+/// Entity changes made by the request that produced the audit entry
+/// (linked by `request_id`).
+pub async fn get_log_changes(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<EntityChangeDto>>, AppError> {
+    let repo = AuditLogRepository::new(&state.db_pool_logs);
+
+    let log = repo
+        .find_by_id(id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Audit log {} not found", id)))?;
+
+    let changes = match log.request_id {
+        Some(request_id) => {
+            state
+                .entity_history_service
+                .list_by_request(request_id)
+                .await?
+        }
+        None => Vec::new(),
+    };
+
+    Ok(Json(changes))
+}
+
 /// GET /admin/audit-logs/stats
 pub async fn get_stats(
     State(state): State<AppState>,
@@ -194,15 +225,55 @@ pub async fn get_user_logs(
 }
 
 /// POST /admin/audit-logs/cleanup
+///
+/// Entries older than the retention period are exported to sealed archive
+/// files before leaving the database; nothing is deleted silently.
 pub async fn cleanup_logs(
     State(state): State<AppState>,
     Json(payload): Json<CleanupRequest>,
-) -> Result<Json<serde_json::Value>, AppError> {
-    let repo = AuditLogRepository::new(&state.db_pool_logs);
-    let deleted = repo.cleanup_old_logs(payload.retention_days).await?;
+) -> Result<Json<AuditArchiveResult>, AppError> {
+    let result = state
+        .audit_integrity_service
+        .archive(payload.retention_days)
+        .await?;
+
+    Ok(Json(result))
+}
+
+/// GET /admin/audit-logs/verify
+pub async fn verify_chain(
+    State(state): State<AppState>,
+    Query(query): Query<ChainRangeQuery>,
+) -> Result<Json<AuditChainVerification>, AppError> {
+    let report = state
+        .audit_integrity_service
+        .verify(query.from, query.to)
+        .await?;
+
+    Ok(Json(report))
+}
+
+/// GET /admin/audit-logs/checkpoints
+pub async fn list_checkpoints(
+    State(state): State<AppState>,
+    Query(query): Query<ChainRangeQuery>,
+) -> Result<Json<Vec<AuditCheckpointDto>>, AppError> {
+    let checkpoints = state
+        .audit_integrity_service
+        .list_checkpoints(query.from, query.to)
+        .await?;
+
+    Ok(Json(checkpoints))
+}
+
+/// POST /admin/audit-logs/checkpoints
+///
+/// Seals every closed day that has no checkpoint yet (the background job
+/// does the same hourly).
+pub async fn seal_checkpoints(
+    State(state): State<AppState>,
+) -> Result<Json<AuditSealResult>, AppError> {
+    let result = state.audit_integrity_service.seal_closed_days().await?;
 
-    Ok(Json(serde_json::json!({
-        "deleted_count": deleted,
-        "message": format!("Deleted {} logs older than {} days", deleted, payload.retention_days)
-    })))
+    Ok(Json(result))
 }
@@ -20,9 +20,15 @@ pub fn router() -> Router<AppState> {
             get(handlers::get_suspicious_ips),
         )
         .route("/audit-logs/cleanup", post(handlers::cleanup_logs))
+        .route("/audit-logs/verify", get(handlers::verify_chain))
+        .route(
+            "/audit-logs/checkpoints",
+            get(handlers::list_checkpoints).post(handlers::seal_checkpoints),
+        )
         // Dynamic routes next
         .route("/audit-logs/user/{user_id}", get(handlers::get_user_logs))
         .route("/audit-logs/{id}", get(handlers::get_log))
+        .route("/audit-logs/{id}/changes", get(handlers::get_log_changes))
         // Root collection route last
         .route("/audit-logs", get(handlers::list_logs))
 }
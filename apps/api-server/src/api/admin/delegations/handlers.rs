@@ -0,0 +1,102 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use domain::models::{ApprovalDelegationDto, CreateDelegationPayload};
+use serde::Deserialize;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    extractors::current_user::CurrentUser,
+    infra::{errors::AppError, state::AppState},
+};
+
+#[derive(Debug, Deserialize)]
+pub struct DelegationListQuery {
+    /// Somente delegações vigentes (não revogadas e dentro do período)
+    #[serde(default)]
+    pub active_only: bool,
+}
+
+/// GET /admin/delegations
+///
+/// Delegações concedidas e recebidas pelo usuário autenticado.
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/delegations",
+    tag = "Admin",
+    params(("active_only" = Option<bool>, Query, description = "Somente delegações vigentes")),
+    responses(
+        (status = 200, description = "Delegações do usuário", body = Vec<ApprovalDelegationDto>)
+    )
+)]
+pub async fn list_delegations(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Query(query): Query<DelegationListQuery>,
+) -> Result<Json<Vec<ApprovalDelegationDto>>, AppError> {
+    let delegations = state
+        .delegation_service
+        .list_delegations(current_user.id, query.active_only)
+        .await?;
+    Ok(Json(delegations))
+}
+
+/// POST /admin/delegations
+///
+/// Delega a outro usuário, durante a vigência, a competência de aprovação
+/// do usuário autenticado nos escopos informados. A delegação expira
+/// sozinha ao fim do período.
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/delegations",
+    tag = "Admin",
+    request_body = CreateDelegationPayload,
+    responses(
+        (status = 201, description = "Delegação criada", body = ApprovalDelegationDto),
+        (status = 400, description = "Período ou escopos inválidos"),
+        (status = 404, description = "Usuário delegado não encontrado")
+    )
+)]
+pub async fn create_delegation(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Json(payload): Json<CreateDelegationPayload>,
+) -> Result<(StatusCode, Json<ApprovalDelegationDto>), AppError> {
+    payload.validate().map_err(AppError::Validation)?;
+
+    let delegation = state
+        .delegation_service
+        .create_delegation(current_user.id, payload)
+        .await?;
+    Ok((StatusCode::CREATED, Json(delegation)))
+}
+
+/// DELETE /admin/delegations/{id}
+///
+/// Encerra a delegação imediatamente. Pode ser feito pelo titular ou pelo
+/// delegado.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/admin/delegations/{id}",
+    tag = "Admin",
+    params(("id" = Uuid, Path, description = "ID da delegação")),
+    responses(
+        (status = 204, description = "Delegação revogada"),
+        (status = 404, description = "Delegação não encontrada"),
+        (status = 409, description = "Delegação já revogada")
+    )
+)]
+pub async fn revoke_delegation(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, AppError> {
+    state
+        .delegation_service
+        .revoke_delegation(id, current_user.id)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
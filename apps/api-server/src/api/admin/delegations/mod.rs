@@ -0,0 +1,16 @@
+pub mod handlers;
+
+use crate::infra::state::AppState;
+use axum::{
+    routing::{delete, get},
+    Router,
+};
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/delegations",
+            get(handlers::list_delegations).post(handlers::create_delegation),
+        )
+        .route("/delegations/{id}", delete(handlers::revoke_delegation))
+}
@@ -0,0 +1,19 @@
+use domain::models::{EntityChangeDto, HistoryEntity};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Deserialize)]
+pub struct HistoryQuery {
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct EntityHistoryResponse {
+    pub entity: HistoryEntity,
+    pub entity_id: String,
+    pub data: Vec<EntityChangeDto>,
+    pub total: i64,
+    pub limit: i64,
+    pub offset: i64,
+}
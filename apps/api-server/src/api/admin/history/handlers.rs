@@ -0,0 +1,50 @@
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use domain::models::HistoryEntity;
+
+use super::contracts::{EntityHistoryResponse, HistoryQuery};
+use crate::infra::{errors::AppError, state::AppState};
+
+/// GET /admin/history/{entity}/{id}
+///
+/// Alterações campo a campo da entidade, da mais recente para a mais
+/// antiga, com o autor e o request ID de cada uma.
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/history/{entity}/{id}",
+    tag = "Admin",
+    params(
+        ("entity" = HistoryEntity, Path, description = "Tipo da entidade (ex.: warehouse, supplier, policy)"),
+        ("id" = String, Path, description = "ID da entidade (sujeito da regra para policy)"),
+        ("limit" = Option<i64>, Query, description = "Máximo de registros (padrão 50, até 100)"),
+        ("offset" = Option<i64>, Query, description = "Deslocamento")
+    ),
+    responses(
+        (status = 200, description = "Histórico da entidade", body = EntityHistoryResponse),
+        (status = 400, description = "Entidade ou identificador inválido")
+    )
+)]
+pub async fn get_entity_history(
+    State(state): State<AppState>,
+    Path((entity, entity_id)): Path<(HistoryEntity, String)>,
+    Query(query): Query<HistoryQuery>,
+) -> Result<Json<EntityHistoryResponse>, AppError> {
+    let limit = query.limit.unwrap_or(50).clamp(1, 100);
+    let offset = query.offset.unwrap_or(0).max(0);
+
+    let (data, total) = state
+        .entity_history_service
+        .list(entity, &entity_id, limit, offset)
+        .await?;
+
+    Ok(Json(EntityHistoryResponse {
+        entity,
+        entity_id,
+        data,
+        total,
+        limit,
+        offset,
+    }))
+}
@@ -0,0 +1,9 @@
+pub mod contracts;
+pub mod handlers;
+
+use crate::infra::state::AppState;
+use axum::{routing::get, Router};
+
+pub fn router() -> Router<AppState> {
+    Router::new().route("/history/{entity}/{id}", get(handlers::get_entity_history))
+}
@@ -0,0 +1,28 @@
+use serde::Deserialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+/// Titular por ID de usuário e/ou CPF (o CPF também localiza o motorista)
+#[derive(Debug, Deserialize)]
+pub struct DataSubjectQuery {
+    pub user_id: Option<Uuid>,
+    pub cpf: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct AnonymizeRequest {
+    /// User account of the subject
+    pub user_id: Option<Uuid>,
+
+    /// CPF of the subject; also matches the driver record
+    pub cpf: Option<String>,
+
+    /// Why the data is being anonymized (e.g., the data-subject request number)
+    #[validate(length(
+        min = 5,
+        max = 500,
+        message = "Reason must be between 5 and 500 characters"
+    ))]
+    pub reason: String,
+}
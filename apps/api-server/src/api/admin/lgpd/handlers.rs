@@ -0,0 +1,139 @@
+use axum::{
+    extract::{Query, State},
+    http::{header::USER_AGENT, HeaderMap},
+    Json,
+};
+use casbin::{CoreApi, MgmtApi};
+use domain::models::{AnonymizationResult, DataSubjectExport};
+use validator::Validate;
+
+use super::contracts::{AnonymizeRequest, DataSubjectQuery};
+use crate::api::auth::session_handlers::extract_client_ip;
+use crate::extractors::current_user::CurrentUser;
+use crate::infra::{errors::AppError, state::AppState};
+
+fn user_agent(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(USER_AGENT)
+        .and_then(|v| v.to_str().ok())
+        .map(|s| s.to_string())
+}
+
+/// GET /admin/lgpd/export
+///
+/// Tudo o que a base guarda sobre o titular (LGPD art. 18, II): conta de
+/// usuário, motorista, registros vinculados e entradas de auditoria.
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/lgpd/export",
+    tag = "Admin",
+    params(
+        ("user_id" = Option<uuid::Uuid>, Query, description = "ID do usuário titular"),
+        ("cpf" = Option<String>, Query, description = "CPF do titular (localiza também o motorista)")
+    ),
+    responses(
+        (status = 200, description = "Dados pessoais do titular", body = DataSubjectExport),
+        (status = 400, description = "Titular não informado ou CPF inválido"),
+        (status = 404, description = "Nenhum dado encontrado para o titular")
+    )
+)]
+pub async fn export_personal_data(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    headers: HeaderMap,
+    Query(query): Query<DataSubjectQuery>,
+) -> Result<Json<DataSubjectExport>, AppError> {
+    let export = state
+        .lgpd_service
+        .export(query.user_id, query.cpf.as_deref())
+        .await?;
+
+    state
+        .audit_service
+        .log_lgpd_export(
+            current_user.id,
+            &current_user.username,
+            export.user.as_ref().map(|u| u.id),
+            export.driver.as_ref().map(|d| d.id),
+            extract_client_ip(&headers),
+            user_agent(&headers),
+        )
+        .await;
+
+    Ok(Json(export))
+}
+
+/// POST /admin/lgpd/anonymize
+///
+/// Troca os dados pessoais do titular por tokens irreversíveis. Viagens,
+/// multas, abastecimentos e requisições mantêm as referências; a conta de
+/// usuário fica bloqueada e perde sessões, credenciais e políticas.
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/lgpd/anonymize",
+    tag = "Admin",
+    request_body = AnonymizeRequest,
+    responses(
+        (status = 200, description = "Titular anonimizado", body = AnonymizationResult),
+        (status = 400, description = "Titular não informado ou CPF inválido"),
+        (status = 403, description = "Tentativa de anonimizar a própria conta"),
+        (status = 404, description = "Nenhum dado encontrado para o titular"),
+        (status = 409, description = "Titular já anonimizado")
+    )
+)]
+pub async fn anonymize_personal_data(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    headers: HeaderMap,
+    Json(payload): Json<AnonymizeRequest>,
+) -> Result<Json<AnonymizationResult>, AppError> {
+    payload.validate().map_err(AppError::Validation)?;
+
+    let result = state
+        .lgpd_service
+        .anonymize(payload.user_id, payload.cpf.as_deref(), current_user.id)
+        .await?;
+
+    if let Some(user_id) = result.user_id {
+        let mut enforcer = state.enforcer.write().await;
+        let policies = enforcer.get_filtered_policy(0, vec![user_id.to_string()]);
+        let groupings = enforcer.get_filtered_grouping_policy(0, vec![user_id.to_string()]);
+        enforcer
+            .remove_filtered_policy(0, vec![user_id.to_string()])
+            .await
+            .ok();
+        enforcer
+            .remove_filtered_grouping_policy(0, vec![user_id.to_string()])
+            .await
+            .ok();
+        if let Err(e) = enforcer.save_policy().await {
+            tracing::error!("Failed to save policy after anonymization: {:?}", e);
+        }
+
+        let removed = policies
+            .iter()
+            .map(|rule| ("p", rule))
+            .chain(groupings.iter().map(|rule| ("g", rule)));
+        for (ptype, rule) in removed {
+            state
+                .entity_history_service
+                .record_policy_change(ptype, rule, false)
+                .await;
+        }
+    }
+
+    state
+        .audit_service
+        .log_lgpd_anonymization(
+            current_user.id,
+            &current_user.username,
+            result.user_id,
+            result.driver_id,
+            &payload.reason,
+            extract_client_ip(&headers),
+            user_agent(&headers),
+        )
+        .await;
+
+    Ok(Json(result))
+}
@@ -0,0 +1,14 @@
+pub mod contracts;
+pub mod handlers;
+
+use crate::infra::state::AppState;
+use axum::{
+    routing::{get, post},
+    Router,
+};
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/lgpd/export", get(handlers::export_personal_data))
+        .route("/lgpd/anonymize", post(handlers::anonymize_personal_data))
+}
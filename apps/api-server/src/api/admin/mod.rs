@@ -1,7 +1,13 @@
+pub mod api_keys;
+pub mod approval_chains;
 pub mod audit;
 pub mod batches;
+pub mod delegations;
+pub mod history;
+pub mod lgpd;
 pub mod policies;
 pub mod requisitions;
+pub mod security;
 pub mod transfers;
 pub mod users;
 
@@ -30,6 +36,12 @@ pub fn router() -> Router<AppState> {
         .merge(users::router())
         .merge(policies::router())
         .merge(audit::router())
+        .merge(security::router())
+        .merge(api_keys::router())
+        .merge(delegations::router())
+        .merge(approval_chains::router())
+        .merge(history::router())
+        .merge(lgpd::router())
         .merge(requisitions::router())
         .nest("/geo_regions", geo_regions::router())
         .nest("/budget-classifications", budget_classifications::router())
@@ -1,7 +1,8 @@
 use lazy_static::lazy_static;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
 use validator::Validate;
 
 lazy_static! {
@@ -58,3 +59,37 @@ pub struct PolicyListResponse {
     /// List of policies as [subject, object, action] tuples
     pub policies: Vec<Vec<String>>,
 }
+
+/// Assigns (or revokes) a role that applies only within an organizational unit.
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct UnitRoleRequest {
+    /// User receiving the role
+    pub user_id: Uuid,
+
+    /// Role name whose policies apply inside the unit (e.g., "warehouse_clerk")
+    #[validate(length(min = 1, max = 100, message = "Role must be between 1 and 100 characters"))]
+    #[validate(regex(path = *SUBJECT_REGEX, message = "Role contains invalid characters"))]
+    pub role: String,
+
+    /// Organizational unit where the role applies
+    pub unit_id: Uuid,
+
+    /// Whether the role also applies to every unit below `unit_id`
+    #[serde(default)]
+    pub include_subtree: bool,
+}
+
+/// A role assigned within an organizational unit.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UnitRoleAssignment {
+    pub user_id: String,
+    pub role: String,
+    pub unit_id: Uuid,
+    pub include_subtree: bool,
+}
+
+/// Filters for listing unit role assignments.
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct UnitRoleListQuery {
+    pub user_id: Option<Uuid>,
+}
@@ -1,14 +1,27 @@
-use axum::{extract::State, http::StatusCode, Json};
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    Json,
+};
 use casbin::{CoreApi, MgmtApi};
 use domain::ports::UserRepositoryPort;
 use persistence::repositories::user_repository::UserRepository;
 use validator::Validate;
 
-use super::contracts::{PolicyListResponse, PolicyRequest, PolicyResponse};
-use crate::infra::{errors::AppError, state::AppState};
+use super::contracts::{
+    PolicyListResponse, PolicyRequest, PolicyResponse, UnitRoleAssignment, UnitRoleListQuery,
+    UnitRoleRequest,
+};
+use crate::infra::{
+    casbin_setup::{parse_unit_domain, unit_domain},
+    errors::AppError,
+    state::AppState,
+};
+use crate::utils::constants::UNIT_ROLE_PTYPE;
 
 async fn clear_policy_cache(state: &AppState) {
     state.policy_cache.invalidate_all();
+    state.unit_scope_cache.invalidate_all();
     tracing::debug!("Policy cache cleared");
 }
 
@@ -31,7 +44,7 @@ pub async fn add_policy(
     // Verifica se o Subject (sub) existe no banco de dados antes de criar a policy
     if let Ok(user_id) = uuid::Uuid::parse_str(&payload.sub) {
         // Caso 1: É um UUID
-        let user_repo = UserRepository::new(state.db_pool_auth.clone(), state.field_encryption_key);
+        let user_repo = UserRepository::new(state.db_pool_auth.clone(), state.field_keyring.clone());
         if user_repo.find_by_id(user_id).await?.is_none() {
             return Err(AppError::NotFound("User not found (UUID)".to_string()));
         }
@@ -40,7 +53,7 @@ pub async fn add_policy(
         // Tenta converter String -> Username (Value Object)
 
         if let Ok(username) = domain::value_objects::Username::try_from(payload.sub.as_str()) {
-            let user_repo = UserRepository::new(state.db_pool_auth.clone(), state.field_encryption_key);
+            let user_repo = UserRepository::new(state.db_pool_auth.clone(), state.field_keyring.clone());
 
             // Agora passamos &username (que é do tipo correto), não &payload.sub
             if user_repo.find_by_username(&username).await?.is_none() {
@@ -81,13 +94,12 @@ pub async fn add_policy(
         ));
     }
 
-    let result = enforcer
-        .add_policy(vec![
-            payload.sub.clone(),
-            payload.obj.clone(),
-            payload.act.clone(),
-        ])
-        .await;
+    let rule = vec![
+        payload.sub.clone(),
+        payload.obj.clone(),
+        payload.act.clone(),
+    ];
+    let result = enforcer.add_policy(rule.clone()).await;
 
     match result {
         Ok(true) => {
@@ -111,6 +123,10 @@ pub async fn add_policy(
             }
 
             clear_policy_cache(&state).await;
+            state
+                .entity_history_service
+                .record_policy_change("p", &rule, true)
+                .await;
 
             Ok((
                 StatusCode::CREATED,
@@ -141,9 +157,10 @@ pub async fn remove_policy(
     State(state): State<AppState>,
     Json(payload): Json<PolicyRequest>,
 ) -> Result<StatusCode, AppError> {
+    let rule = vec![payload.sub, payload.obj, payload.act];
     let mut enforcer = state.enforcer.write().await;
     let removed = enforcer
-        .remove_policy(vec![payload.sub, payload.obj, payload.act])
+        .remove_policy(rule.clone())
         .await
         .map_err(|e| AppError::Anyhow(anyhow::anyhow!(e)))?;
 
@@ -156,9 +173,138 @@ pub async fn remove_policy(
 
         clear_policy_cache(&state).await;
         tracing::info!("Policy cache cleared after removal");
+        state
+            .entity_history_service
+            .record_policy_change("p", &rule, false)
+            .await;
 
         Ok(StatusCode::NO_CONTENT)
     } else {
         Err(AppError::NotFound("Policy not found".to_string()))
     }
 }
+
+pub async fn list_unit_roles(
+    State(state): State<AppState>,
+    Query(query): Query<UnitRoleListQuery>,
+) -> Result<Json<Vec<UnitRoleAssignment>>, AppError> {
+    let enforcer = state.enforcer.read().await;
+    let groupings = match query.user_id {
+        Some(user_id) => enforcer.get_filtered_named_grouping_policy(
+            UNIT_ROLE_PTYPE,
+            0,
+            vec![user_id.to_string()],
+        ),
+        None => enforcer.get_named_grouping_policy(UNIT_ROLE_PTYPE),
+    };
+
+    let assignments = groupings
+        .into_iter()
+        .filter_map(|g| match g.as_slice() {
+            [user_id, role, domain] => {
+                parse_unit_domain(domain).map(|(unit_id, include_subtree)| UnitRoleAssignment {
+                    user_id: user_id.clone(),
+                    role: role.clone(),
+                    unit_id,
+                    include_subtree,
+                })
+            }
+            _ => None,
+        })
+        .collect();
+
+    Ok(Json(assignments))
+}
+
+pub async fn add_unit_role(
+    State(state): State<AppState>,
+    Json(payload): Json<UnitRoleRequest>,
+) -> Result<(StatusCode, Json<PolicyResponse>), AppError> {
+    payload.validate().map_err(AppError::Validation)?;
+
+    let user_repo = UserRepository::new(state.db_pool_auth.clone(), state.field_keyring.clone());
+    if user_repo.find_by_id(payload.user_id).await?.is_none() {
+        return Err(AppError::NotFound("User not found (UUID)".to_string()));
+    }
+    state
+        .organizational_unit_service
+        .get(payload.unit_id)
+        .await?;
+
+    let rule = vec![
+        payload.user_id.to_string(),
+        payload.role.clone(),
+        unit_domain(payload.unit_id, payload.include_subtree),
+    ];
+    let mut enforcer = state.enforcer.write().await;
+    let added = enforcer
+        .add_named_grouping_policy(UNIT_ROLE_PTYPE, rule.clone())
+        .await
+        .map_err(|e| AppError::Anyhow(anyhow::anyhow!(e)))?;
+
+    if !added {
+        return Ok((
+            StatusCode::OK,
+            Json(PolicyResponse {
+                success: true,
+                message: "Unit role already assigned".to_string(),
+            }),
+        ));
+    }
+
+    if let Err(e) = enforcer.save_policy().await {
+        tracing::error!("Failed to save unit role: {:?}", e);
+    }
+    clear_policy_cache(&state).await;
+    state
+        .entity_history_service
+        .record_policy_change(UNIT_ROLE_PTYPE, &rule, true)
+        .await;
+
+    tracing::info!(
+        "Unit role assigned: user={}, role={}, unit={}, subtree={}",
+        payload.user_id,
+        payload.role,
+        payload.unit_id,
+        payload.include_subtree
+    );
+
+    Ok((
+        StatusCode::CREATED,
+        Json(PolicyResponse {
+            success: true,
+            message: "Unit role assigned successfully".to_string(),
+        }),
+    ))
+}
+
+pub async fn remove_unit_role(
+    State(state): State<AppState>,
+    Json(payload): Json<UnitRoleRequest>,
+) -> Result<StatusCode, AppError> {
+    let rule = vec![
+        payload.user_id.to_string(),
+        payload.role,
+        unit_domain(payload.unit_id, payload.include_subtree),
+    ];
+    let mut enforcer = state.enforcer.write().await;
+    let removed = enforcer
+        .remove_named_grouping_policy(UNIT_ROLE_PTYPE, rule.clone())
+        .await
+        .map_err(|e| AppError::Anyhow(anyhow::anyhow!(e)))?;
+
+    if !removed {
+        return Err(AppError::NotFound("Unit role not found".to_string()));
+    }
+
+    if let Err(e) = enforcer.save_policy().await {
+        tracing::error!("Failed to save policy: {:?}", e);
+    }
+    clear_policy_cache(&state).await;
+    state
+        .entity_history_service
+        .record_policy_change(UNIT_ROLE_PTYPE, &rule, false)
+        .await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
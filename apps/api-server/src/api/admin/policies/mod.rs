@@ -12,4 +12,10 @@ pub fn router() -> Router<AppState> {
         .route("/policies", get(handlers::list_policies))
         .route("/policies", post(handlers::add_policy))
         .route("/policies", delete(handlers::remove_policy))
+        .route(
+            "/policies/unit-roles",
+            get(handlers::list_unit_roles)
+                .post(handlers::add_unit_role)
+                .delete(handlers::remove_unit_role),
+        )
 }
@@ -100,6 +100,8 @@ pub struct RequisitionResponse {
     pub needed_by: Option<chrono::NaiveDate>,
     pub approved_by: Option<Uuid>,
     pub approved_at: Option<DateTime<Utc>>,
+    /// Approval holder when the decision was taken by a delegate
+    pub approved_on_behalf_of: Option<Uuid>,
     pub fulfilled_by: Option<Uuid>,
     pub fulfilled_at: Option<DateTime<Utc>>,
     pub rejection_reason: Option<String>,
@@ -188,6 +190,7 @@ impl From<domain::models::requisition::RequisitionDto> for RequisitionResponse {
             needed_by: dto.needed_by,
             approved_by: dto.approved_by,
             approved_at: dto.approved_at,
+            approved_on_behalf_of: dto.approved_on_behalf_of,
             fulfilled_by: dto.fulfilled_by,
             fulfilled_at: dto.fulfilled_at,
             rejection_reason: dto.rejection_reason,
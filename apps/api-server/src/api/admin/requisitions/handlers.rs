@@ -6,8 +6,9 @@ use crate::{
 use axum::{
     extract::{Path, Query, State},
     http::HeaderMap,
-    Json,
+    Extension, Json,
 };
+use domain::models::{DelegatedAuthority, DelegationScope, DocumentApprovalStepDto, UnitScope};
 use domain::models::requisition::{
     ApproveRequisitionPayload, AuditContext, CancelRequisitionPayload, CreateRequisitionItemPayload,
     FulfillItemInput, FulfillRequisitionPayload, RejectRequisitionPayload, RollbackPayload,
@@ -54,6 +55,30 @@ fn create_audit_context(user: &CurrentUser, headers: &HeaderMap) -> AuditContext
         .with_user_agent(extract_user_agent(headers))
 }
 
+/// Records "decided by `user` on behalf of `on_behalf_of`" in the audit log
+async fn log_delegated_decision(
+    state: &AppState,
+    user: &CurrentUser,
+    ctx: &AuditContext,
+    on_behalf_of: Uuid,
+    decision: &str,
+    id: Uuid,
+) {
+    state
+        .audit_service
+        .log_delegated_approval(
+            user.id,
+            &user.username,
+            on_behalf_of,
+            DelegationScope::Requisition,
+            decision,
+            &format!("/api/admin/requisitions/{}/{}", id, decision),
+            ctx.ip_address.clone(),
+            ctx.user_agent.clone(),
+        )
+        .await;
+}
+
 // ============================================================================
 // REQUISITION HANDLERS
 // ============================================================================
@@ -63,6 +88,7 @@ fn create_audit_context(user: &CurrentUser, headers: &HeaderMap) -> AuditContext
 pub async fn list_requisitions(
     State(state): State<AppState>,
     _user: CurrentUser,
+    Extension(scope): Extension<UnitScope>,
     Query(query): Query<RequisitionListQuery>,
 ) -> Result<Json<RequisitionListResponse>, AppError> {
     let limit = query.limit.unwrap_or(20).clamp(1, 100);
@@ -70,7 +96,14 @@ pub async fn list_requisitions(
 
     let (requisitions, total) = state
         .requisition_service
-        .list_requisitions(limit, offset, query.status, query.requester_id, query.warehouse_id)
+        .list_requisitions(
+            limit,
+            offset,
+            query.status,
+            query.requester_id,
+            query.warehouse_id,
+            &scope,
+        )
         .await?;
 
     Ok(Json(RequisitionListResponse {
@@ -86,17 +119,38 @@ pub async fn list_requisitions(
 pub async fn get_requisition(
     State(state): State<AppState>,
     _user: CurrentUser,
+    Extension(scope): Extension<UnitScope>,
     Path(id): Path<Uuid>,
 ) -> Result<Json<RequisitionResponse>, AppError> {
-    let requisition = state.requisition_service.get_requisition(id).await?;
+    let requisition = state
+        .requisition_service
+        .get_requisition_in_scope(id, &scope)
+        .await?;
     Ok(Json(requisition.into()))
 }
 
+/// GET /api/admin/requisitions/:id/approval-steps
+/// Steps of the approval chain applied to the requisition
+pub async fn get_requisition_approval_steps(
+    State(state): State<AppState>,
+    _user: CurrentUser,
+    Extension(scope): Extension<UnitScope>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<DocumentApprovalStepDto>>, AppError> {
+    let steps = state
+        .requisition_service
+        .get_approval_steps(id, &scope)
+        .await?;
+    Ok(Json(steps))
+}
+
 /// POST /api/admin/requisitions/:id/approve
-/// Approve a pending requisition
+/// Approve a pending requisition, or the caller's step of its approval chain
 pub async fn approve_requisition(
     State(state): State<AppState>,
     user: CurrentUser,
+    Extension(scope): Extension<UnitScope>,
+    delegated: Option<Extension<DelegatedAuthority>>,
     headers: HeaderMap,
     Path(id): Path<Uuid>,
     Json(payload): Json<ApproveRequest>,
@@ -111,9 +165,15 @@ pub async fn approve_requisition(
             ApproveRequisitionPayload {
                 notes: payload.notes,
             },
+            &scope,
+            delegated.as_ref().map(|Extension(d)| d),
         )
         .await?;
 
+    if let Some(on_behalf_of) = requisition.approved_on_behalf_of {
+        log_delegated_decision(&state, &user, &ctx, on_behalf_of, "approve", id).await;
+    }
+
     Ok(Json(requisition.into()))
 }
 
@@ -122,6 +182,8 @@ pub async fn approve_requisition(
 pub async fn reject_requisition(
     State(state): State<AppState>,
     user: CurrentUser,
+    Extension(scope): Extension<UnitScope>,
+    delegated: Option<Extension<DelegatedAuthority>>,
     headers: HeaderMap,
     Path(id): Path<Uuid>,
     Json(payload): Json<RejectRequest>,
@@ -136,9 +198,15 @@ pub async fn reject_requisition(
             RejectRequisitionPayload {
                 reason: payload.reason,
             },
+            &scope,
+            delegated.as_ref().map(|Extension(d)| d),
         )
         .await?;
 
+    if let Some(on_behalf_of) = requisition.approved_on_behalf_of {
+        log_delegated_decision(&state, &user, &ctx, on_behalf_of, "reject", id).await;
+    }
+
     Ok(Json(requisition.into()))
 }
 
@@ -147,6 +215,7 @@ pub async fn reject_requisition(
 pub async fn cancel_requisition(
     State(state): State<AppState>,
     user: CurrentUser,
+    Extension(scope): Extension<UnitScope>,
     headers: HeaderMap,
     Path(id): Path<Uuid>,
     Json(payload): Json<CancelRequest>,
@@ -161,6 +230,7 @@ pub async fn cancel_requisition(
             CancelRequisitionPayload {
                 reason: payload.reason,
             },
+            &scope,
         )
         .await?;
 
@@ -176,12 +246,13 @@ pub async fn cancel_requisition(
 pub async fn get_requisition_history(
     State(state): State<AppState>,
     _user: CurrentUser,
+    Extension(scope): Extension<UnitScope>,
     Path(id): Path<Uuid>,
     Query(query): Query<HistoryQuery>,
 ) -> Result<Json<HistoryListResponse>, AppError> {
     let history = state
         .requisition_service
-        .get_requisition_history(id, query.limit)
+        .get_requisition_history(id, query.limit, &scope)
         .await?;
 
     Ok(Json(HistoryListResponse {
@@ -195,12 +266,13 @@ pub async fn get_requisition_history(
 pub async fn get_rollback_points(
     State(state): State<AppState>,
     _user: CurrentUser,
+    Extension(scope): Extension<UnitScope>,
     Path(id): Path<Uuid>,
     Query(query): Query<RollbackPointsQuery>,
 ) -> Result<Json<RollbackPointsResponse>, AppError> {
     let points = state
         .requisition_service
-        .get_rollback_points(id, query.limit)
+        .get_rollback_points(id, query.limit, &scope)
         .await?;
 
     Ok(Json(RollbackPointsResponse {
@@ -214,6 +286,7 @@ pub async fn get_rollback_points(
 pub async fn rollback_requisition(
     State(state): State<AppState>,
     user: CurrentUser,
+    Extension(scope): Extension<UnitScope>,
     headers: HeaderMap,
     Path(id): Path<Uuid>,
     Json(payload): Json<RollbackRequest>,
@@ -229,6 +302,7 @@ pub async fn rollback_requisition(
                 history_id: payload.history_id,
                 reason: payload.reason,
             },
+            &scope,
         )
         .await?;
 
@@ -244,11 +318,12 @@ pub async fn rollback_requisition(
 pub async fn get_requisition_items(
     State(state): State<AppState>,
     _user: CurrentUser,
+    Extension(scope): Extension<UnitScope>,
     Path(id): Path<Uuid>,
 ) -> Result<Json<Vec<ItemResponse>>, AppError> {
     let items = state
         .requisition_service
-        .get_requisition_items(id)
+        .get_requisition_items(id, &scope)
         .await?;
 
     Ok(Json(items.into_iter().map(Into::into).collect()))
@@ -259,15 +334,16 @@ pub async fn get_requisition_items(
 pub async fn delete_requisition_item(
     State(state): State<AppState>,
     user: CurrentUser,
+    Extension(scope): Extension<UnitScope>,
     headers: HeaderMap,
-    Path((_, item_id)): Path<(Uuid, Uuid)>,
+    Path((id, item_id)): Path<(Uuid, Uuid)>,
     Json(payload): Json<DeleteItemRequest>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     let ctx = create_audit_context(&user, &headers);
 
     state
         .requisition_service
-        .soft_delete_item(item_id, &ctx, &payload.reason)
+        .soft_delete_item(id, item_id, &ctx, &payload.reason, &scope)
         .await?;
 
     Ok(Json(serde_json::json!({
@@ -281,14 +357,15 @@ pub async fn delete_requisition_item(
 pub async fn restore_requisition_item(
     State(state): State<AppState>,
     user: CurrentUser,
+    Extension(scope): Extension<UnitScope>,
     headers: HeaderMap,
-    Path((_, item_id)): Path<(Uuid, Uuid)>,
+    Path((id, item_id)): Path<(Uuid, Uuid)>,
 ) -> Result<Json<ItemResponse>, AppError> {
     let ctx = create_audit_context(&user, &headers);
 
     let item = state
         .requisition_service
-        .restore_item(item_id, &ctx)
+        .restore_item(id, item_id, &ctx, &scope)
         .await?;
 
     Ok(Json(item.into()))
@@ -299,6 +376,7 @@ pub async fn restore_requisition_item(
 pub async fn start_processing_requisition(
     State(state): State<AppState>,
     user: CurrentUser,
+    Extension(scope): Extension<UnitScope>,
     headers: HeaderMap,
     Path(id): Path<Uuid>,
     Json(payload): Json<StartProcessingRequest>,
@@ -307,7 +385,12 @@ pub async fn start_processing_requisition(
 
     let requisition = state
         .requisition_service
-        .start_processing(id, &ctx, StartProcessingPayload { notes: payload.notes })
+        .start_processing(
+            id,
+            &ctx,
+            StartProcessingPayload { notes: payload.notes },
+            &scope,
+        )
         .await?;
 
     Ok(Json(requisition.into()))
@@ -318,6 +401,7 @@ pub async fn start_processing_requisition(
 pub async fn fulfill_requisition(
     State(state): State<AppState>,
     user: CurrentUser,
+    Extension(scope): Extension<UnitScope>,
     headers: HeaderMap,
     Path(id): Path<Uuid>,
     Json(payload): Json<FulfillRequisitionRequest>,
@@ -339,7 +423,7 @@ pub async fn fulfill_requisition(
 
     let requisition = state
         .requisition_service
-        .fulfill_requisition(id, &ctx, domain_payload)
+        .fulfill_requisition(id, &ctx, domain_payload, &scope)
         .await?;
 
     Ok(Json(requisition.into()))
@@ -349,6 +433,7 @@ pub async fn fulfill_requisition(
 pub async fn add_requisition_item(
     user: CurrentUser,
     State(state): State<AppState>,
+    Extension(scope): Extension<UnitScope>,
     Path(id): Path<Uuid>,
     headers: HeaderMap,
     Json(payload): Json<CreateRequisitionItemPayload>,
@@ -356,7 +441,7 @@ pub async fn add_requisition_item(
     let _ctx = create_audit_context(&user, &headers);
     let item = state
         .requisition_service
-        .add_item_to_requisition(id, payload, user.id)
+        .add_item_to_requisition(id, payload, user.id, &scope)
         .await?;
 
     Ok((axum::http::StatusCode::CREATED, Json(item.into())))
@@ -15,6 +15,7 @@ pub fn router() -> Router<AppState> {
         .route("/requisitions/{id}", get(handlers::get_requisition))
         .route("/requisitions/{id}/approve", post(handlers::approve_requisition))
         .route("/requisitions/{id}/reject", post(handlers::reject_requisition))
+        .route("/requisitions/{id}/approval-steps", get(handlers::get_requisition_approval_steps))
         .route("/requisitions/{id}/cancel", post(handlers::cancel_requisition))
         .route("/requisitions/{id}/start-processing", post(handlers::start_processing_requisition))
         .route("/requisitions/{id}/fulfill", post(handlers::fulfill_requisition))
@@ -0,0 +1,269 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use core_services::request_context;
+use domain::models::{AccountLockoutDto, FieldKeyRotationDto, JwtSigningKeyDto};
+use serde::{Deserialize, Serialize};
+use tracing::{error, info};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::{
+    extractors::current_user::CurrentUser,
+    infra::{errors::AppError, state::AppState},
+};
+
+/// GET /admin/security/lockouts
+///
+/// Contas bloqueadas ou com falhas de login pendentes.
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/security/lockouts",
+    tag = "Admin",
+    responses(
+        (status = 200, description = "Bloqueios ativos e falhas pendentes", body = Vec<AccountLockoutDto>)
+    )
+)]
+pub async fn list_lockouts(
+    State(state): State<AppState>,
+) -> Result<Json<Vec<AccountLockoutDto>>, AppError> {
+    let lockouts = state.login_throttle_service.list_lockouts().await?;
+    Ok(Json(lockouts))
+}
+
+/// DELETE /admin/security/lockouts/{user_id}
+///
+/// Desbloqueia a conta e zera a contagem de falhas.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/admin/security/lockouts/{user_id}",
+    tag = "Admin",
+    params(("user_id" = Uuid, Path, description = "ID do usuário")),
+    responses(
+        (status = 204, description = "Bloqueio removido"),
+        (status = 404, description = "Nenhum bloqueio registrado")
+    )
+)]
+pub async fn clear_lockout(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Path(user_id): Path<Uuid>,
+) -> Result<StatusCode, AppError> {
+    state.login_throttle_service.clear_lockout(user_id).await?;
+
+    info!(admin_id = %current_user.id, user_id = %user_id, "Bloqueio de login removido pelo administrador");
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct ListFieldKeyRotationsParams {
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+    #[serde(default)]
+    pub offset: i64,
+}
+
+fn default_limit() -> i64 {
+    50
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FieldKeyRotationsListResponse {
+    /// Id of the key new values are encrypted with
+    pub current_key_id: u32,
+    /// Every key accepted for decryption
+    pub key_ids: Vec<u32>,
+    pub data: Vec<FieldKeyRotationDto>,
+    pub total: i64,
+    pub limit: i64,
+    pub offset: i64,
+}
+
+/// Executa a recriptografia em segundo plano, mantendo o autor da requisição
+fn spawn_rotation(state: &AppState, rotation: FieldKeyRotationDto) {
+    let service = state.field_key_rotation_service.clone();
+    let job = async move {
+        if let Err(e) = service.execute(rotation).await {
+            error!("Falha ao registrar o resultado da recriptografia: {}", e);
+        }
+    };
+    match request_context::current() {
+        Some(ctx) => tokio::spawn(request_context::scope(ctx, job)),
+        None => tokio::spawn(job),
+    };
+}
+
+/// POST /admin/security/field-key-rotations
+///
+/// Recriptografa com a chave atual os campos cifrados e reconstrói os blind
+/// indexes. Acompanhe o progresso em `/field-key-rotations/{id}`.
+#[utoipa::path(
+    post,
+    path = "/api/admin/security/field-key-rotations",
+    tag = "Admin",
+    responses(
+        (status = 202, description = "Recriptografia iniciada", body = FieldKeyRotationDto),
+        (status = 409, description = "Já existe uma rotação em andamento")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn start_field_key_rotation(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+) -> Result<(StatusCode, Json<FieldKeyRotationDto>), AppError> {
+    let rotation = state
+        .field_key_rotation_service
+        .start(Some(current_user.id))
+        .await?;
+
+    info!(
+        admin_id = %current_user.id,
+        rotation_id = %rotation.id,
+        target_key_id = rotation.target_key_id,
+        "Rotação da chave de criptografia de campos iniciada"
+    );
+    spawn_rotation(&state, rotation.clone());
+    Ok((StatusCode::ACCEPTED, Json(rotation)))
+}
+
+/// GET /admin/security/field-key-rotations
+///
+/// Chaves do keyring e rotações executadas, da mais recente à mais antiga.
+#[utoipa::path(
+    get,
+    path = "/api/admin/security/field-key-rotations",
+    tag = "Admin",
+    params(ListFieldKeyRotationsParams),
+    responses(
+        (status = 200, description = "Rotações de chave", body = FieldKeyRotationsListResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_field_key_rotations(
+    State(state): State<AppState>,
+    Query(params): Query<ListFieldKeyRotationsParams>,
+) -> Result<Json<FieldKeyRotationsListResponse>, AppError> {
+    let (data, total) = state
+        .field_key_rotation_service
+        .list_runs(params.limit, params.offset)
+        .await?;
+    Ok(Json(FieldKeyRotationsListResponse {
+        current_key_id: state.field_keyring.current_id(),
+        key_ids: state.field_keyring.key_ids(),
+        data,
+        total,
+        limit: params.limit,
+        offset: params.offset,
+    }))
+}
+
+/// GET /admin/security/field-key-rotations/{id}
+///
+/// Progresso de uma rotação.
+#[utoipa::path(
+    get,
+    path = "/api/admin/security/field-key-rotations/{id}",
+    tag = "Admin",
+    params(("id" = Uuid, Path, description = "ID da rotação")),
+    responses(
+        (status = 200, description = "Rotação de chave", body = FieldKeyRotationDto),
+        (status = 404, description = "Rotação não encontrada")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_field_key_rotation(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<FieldKeyRotationDto>, AppError> {
+    let rotation = state.field_key_rotation_service.get_run(id).await?;
+    Ok(Json(rotation))
+}
+
+/// POST /admin/security/field-key-rotations/{id}/resume
+///
+/// Retoma uma rotação com falha a partir do último registro processado.
+#[utoipa::path(
+    post,
+    path = "/api/admin/security/field-key-rotations/{id}/resume",
+    tag = "Admin",
+    params(("id" = Uuid, Path, description = "ID da rotação")),
+    responses(
+        (status = 202, description = "Recriptografia retomada", body = FieldKeyRotationDto),
+        (status = 400, description = "Rotação não falhou ou a chave atual mudou"),
+        (status = 404, description = "Rotação não encontrada"),
+        (status = 409, description = "Já existe uma rotação em andamento")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn resume_field_key_rotation(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Path(id): Path<Uuid>,
+) -> Result<(StatusCode, Json<FieldKeyRotationDto>), AppError> {
+    let rotation = state.field_key_rotation_service.resume(id).await?;
+
+    info!(admin_id = %current_user.id, rotation_id = %id, "Rotação da chave de criptografia de campos retomada");
+    spawn_rotation(&state, rotation.clone());
+    Ok((StatusCode::ACCEPTED, Json(rotation)))
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct JwtSigningKeysResponse {
+    /// `kid` of the key signing new tokens on this instance
+    pub signing_kid: String,
+    /// Keys registered by rotations, most recent first. Before the first
+    /// rotation the configured key signs and the list is empty.
+    pub data: Vec<JwtSigningKeyDto>,
+}
+
+/// GET /admin/security/jwt-keys
+///
+/// Chaves de assinatura dos JWTs e o período de carência das aposentadas.
+#[utoipa::path(
+    get,
+    path = "/api/admin/security/jwt-keys",
+    tag = "Admin",
+    responses(
+        (status = 200, description = "Keyring JWT", body = JwtSigningKeysResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_jwt_signing_keys(
+    State(state): State<AppState>,
+) -> Result<Json<JwtSigningKeysResponse>, AppError> {
+    let data = state.jwt_key_service.list_keys().await?;
+    Ok(Json(JwtSigningKeysResponse {
+        signing_kid: state.jwt_service.signing_kid(),
+        data,
+    }))
+}
+
+/// POST /admin/security/jwt-keys/rotate
+///
+/// Gera uma nova chave de assinatura. A anterior continua verificando tokens
+/// até o fim do período de carência; as demais instâncias passam a assinar
+/// com a nova chave na próxima releitura do keyring.
+#[utoipa::path(
+    post,
+    path = "/api/admin/security/jwt-keys/rotate",
+    tag = "Admin",
+    responses(
+        (status = 201, description = "Nova chave ativa", body = JwtSigningKeyDto),
+        (status = 409, description = "Rotação concorrente")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn rotate_jwt_signing_key(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+) -> Result<(StatusCode, Json<JwtSigningKeyDto>), AppError> {
+    let key = state
+        .jwt_key_service
+        .rotate(Some(current_user.id))
+        .await?;
+
+    info!(admin_id = %current_user.id, kid = %key.kid, "Chave de assinatura JWT rotacionada pelo administrador");
+    Ok((StatusCode::CREATED, Json(key)))
+}
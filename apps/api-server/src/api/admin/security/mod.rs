@@ -0,0 +1,30 @@
+pub mod handlers;
+
+use crate::infra::state::AppState;
+use axum::{
+    routing::{delete, get, post},
+    Router,
+};
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/security/lockouts", get(handlers::list_lockouts))
+        .route("/security/lockouts/{user_id}", delete(handlers::clear_lockout))
+        .route(
+            "/security/field-key-rotations",
+            get(handlers::list_field_key_rotations).post(handlers::start_field_key_rotation),
+        )
+        .route(
+            "/security/field-key-rotations/{id}",
+            get(handlers::get_field_key_rotation),
+        )
+        .route(
+            "/security/field-key-rotations/{id}/resume",
+            post(handlers::resume_field_key_rotation),
+        )
+        .route("/security/jwt-keys", get(handlers::list_jwt_signing_keys))
+        .route(
+            "/security/jwt-keys/rotate",
+            post(handlers::rotate_jwt_signing_key),
+        )
+}
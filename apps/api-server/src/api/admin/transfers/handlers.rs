@@ -1,9 +1,14 @@
+use crate::api::auth::session_handlers::extract_client_ip;
 use crate::extractors::current_user::CurrentUser;
 use crate::infra::{errors::AppError, state::AppState};
 use axum::{
     extract::{Path, Query, State},
-    http::StatusCode,
-    Json,
+    http::{header::USER_AGENT, HeaderMap, StatusCode},
+    Extension, Json,
+};
+use domain::models::{
+    ApprovalStepDecisionPayload, DelegatedAuthority, DelegationScope, DocumentApprovalStepDto,
+    UnitScope,
 };
 use domain::models::warehouse::{
     CancelTransferPayload, ConfirmGovbrSignatureTransferPayload, ConfirmTransferPayload,
@@ -11,6 +16,7 @@ use domain::models::warehouse::{
 };
 use serde::Deserialize;
 use uuid::Uuid;
+use validator::Validate;
 
 #[derive(Debug, Deserialize)]
 pub struct TransferListQuery {
@@ -75,19 +81,96 @@ pub async fn initiate_transfer(
     Ok((StatusCode::CREATED, Json(serde_json::json!(transfer))))
 }
 
+/// GET /api/admin/transfers/:id/approval-steps
+/// Steps of the approval chain applied to the transfer
+pub async fn get_transfer_approval_steps(
+    _user: CurrentUser,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<DocumentApprovalStepDto>>, AppError> {
+    let steps = state.stock_transfer_service.get_approval_steps(id).await?;
+    Ok(Json(steps))
+}
+
+/// POST /api/admin/transfers/:id/approve
+/// Approves the caller's step of the transfer's approval chain
+pub async fn approve_transfer_step(
+    user: CurrentUser,
+    State(state): State<AppState>,
+    Extension(scope): Extension<UnitScope>,
+    delegated: Option<Extension<DelegatedAuthority>>,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<ApprovalStepDecisionPayload>,
+) -> Result<Json<Vec<DocumentApprovalStepDto>>, AppError> {
+    payload.validate().map_err(AppError::Validation)?;
+
+    let steps = state
+        .stock_transfer_service
+        .approve_transfer_step(id, payload, user.id, &scope, delegated.as_ref().map(|Extension(d)| d))
+        .await?;
+
+    if let Some(on_behalf_of) = steps
+        .iter()
+        .filter(|s| s.decided_by == Some(user.id))
+        .find_map(|s| s.decided_on_behalf_of)
+    {
+        state
+            .audit_service
+            .log_delegated_approval(
+                user.id,
+                &user.username,
+                on_behalf_of,
+                DelegationScope::StockTransfer,
+                "approve",
+                &format!("/api/admin/transfers/{}/approve", id),
+                extract_client_ip(&headers),
+                headers
+                    .get(USER_AGENT)
+                    .and_then(|v| v.to_str().ok())
+                    .map(|s| s.to_string()),
+            )
+            .await;
+    }
+
+    Ok(Json(steps))
+}
+
 /// POST /api/admin/transfers/:id/confirm
 /// RF-018 Step 2a: Destination confirms receipt
 pub async fn confirm_transfer(
     user: CurrentUser,
     State(state): State<AppState>,
+    Extension(scope): Extension<UnitScope>,
+    delegated: Option<Extension<DelegatedAuthority>>,
+    headers: HeaderMap,
     Path(id): Path<Uuid>,
     Json(payload): Json<ConfirmTransferPayload>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     let transfer = state
         .stock_transfer_service
-        .confirm_transfer(id, payload, user.id)
+        .confirm_transfer(id, payload, user.id, &scope, delegated.as_ref().map(|Extension(d)| d))
         .await?;
 
+    if let Some(on_behalf_of) = transfer.transfer.confirmed_on_behalf_of {
+        state
+            .audit_service
+            .log_delegated_approval(
+                user.id,
+                &user.username,
+                on_behalf_of,
+                DelegationScope::StockTransfer,
+                "confirm",
+                &format!("/api/admin/transfers/{}/confirm", id),
+                extract_client_ip(&headers),
+                headers
+                    .get(USER_AGENT)
+                    .and_then(|v| v.to_str().ok())
+                    .map(|s| s.to_string()),
+            )
+            .await;
+    }
+
     Ok(Json(serde_json::json!(transfer)))
 }
 
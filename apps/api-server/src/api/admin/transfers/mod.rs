@@ -13,6 +13,8 @@ pub fn router() -> Router<AppState> {
         // Transfer lifecycle
         .route("/transfers", get(handlers::list_transfers))
         .route("/transfers/{id}", get(handlers::get_transfer))
+        .route("/transfers/{id}/approval-steps", get(handlers::get_transfer_approval_steps))
+        .route("/transfers/{id}/approve", post(handlers::approve_transfer_step))
         .route("/transfers/{id}/confirm", post(handlers::confirm_transfer))
         .route("/transfers/{id}/reject", post(handlers::reject_transfer))
         .route("/transfers/{id}/cancel", post(handlers::cancel_transfer))
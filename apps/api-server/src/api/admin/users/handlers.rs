@@ -71,7 +71,7 @@ pub async fn list_users(
     State(state): State<AppState>,
     Query(params): Query<ListUsersQuery>,
 ) -> Result<Json<Paginated<UserListItem>>, AppError> {
-    let user_repo = UserRepository::new(state.db_pool_auth, state.field_encryption_key);
+    let user_repo = UserRepository::new(state.db_pool_auth, state.field_keyring.clone());
 
     let limit = params.limit.unwrap_or(10);
     let offset = params.offset.unwrap_or(0);
@@ -125,7 +125,7 @@ pub async fn create_user(
         return Err(AppError::Validation(e));
     }
 
-    let user_repo = UserRepository::new(state.db_pool_auth, state.field_encryption_key);
+    let user_repo = UserRepository::new(state.db_pool_auth, state.field_keyring.clone());
 
     // CORREÇÃO: payload.username é um Username, passamos referência &Username
     if user_repo.exists_by_username(&payload.username).await? {
@@ -157,12 +157,19 @@ pub async fn create_user(
 
     // Sync with Casbin
     {
+        let rule = vec![created.id.to_string(), role.clone()];
         let mut enforcer = state.enforcer.write().await;
-        enforcer
-            .add_grouping_policy(vec![created.id.to_string(), role.clone()])
+        let added = enforcer
+            .add_grouping_policy(rule.clone())
             .await
-            .ok();
+            .unwrap_or(false);
         let _ = enforcer.save_policy().await;
+        if added {
+            state
+                .entity_history_service
+                .record_policy_change("g", &rule, true)
+                .await;
+        }
     }
 
     let user_extended = user_repo
@@ -195,7 +202,7 @@ pub async fn get_user(
     State(state): State<AppState>,
     Path(user_id): Path<Uuid>,
 ) -> Result<Json<UserDetailDto>, AppError> {
-    let user_repo = UserRepository::new(state.db_pool_auth, state.field_encryption_key);
+    let user_repo = UserRepository::new(state.db_pool_auth, state.field_keyring.clone());
     let user = user_repo
         .find_extended_by_id(user_id)
         .await?
@@ -235,7 +242,7 @@ pub async fn update_user(
     }
 
     let auth_repo = AuthRepository::new(state.db_pool_auth.clone());
-    let user_repo = UserRepository::new(state.db_pool_auth.clone(), state.field_encryption_key);
+    let user_repo = UserRepository::new(state.db_pool_auth.clone(), state.field_keyring.clone());
 
     if user_repo.find_by_id(user_id).await?.is_none() {
         return Err(AppError::NotFound("User not found".to_string()));
@@ -268,16 +275,29 @@ pub async fn update_user(
         user_repo.update_role(user_id, role).await?;
         auth_repo.revoke_all_user_tokens(user_id).await?;
 
+        let rule = vec![user_id.to_string(), role.clone()];
         let mut enforcer = state.enforcer.write().await;
+        let previous = enforcer.get_filtered_grouping_policy(0, vec![user_id.to_string()]);
         enforcer
             .remove_filtered_grouping_policy(0, vec![user_id.to_string()])
             .await
             .ok();
-        enforcer
-            .add_grouping_policy(vec![user_id.to_string(), role.clone()])
-            .await
-            .ok();
+        enforcer.add_grouping_policy(rule.clone()).await.ok();
         let _ = enforcer.save_policy().await;
+
+        // Papel mantido não entra no histórico
+        for old in previous.iter().filter(|g| **g != rule) {
+            state
+                .entity_history_service
+                .record_policy_change("g", old, false)
+                .await;
+        }
+        if !previous.contains(&rule) {
+            state
+                .entity_history_service
+                .record_policy_change("g", &rule, true)
+                .await;
+        }
     }
 
     // 4. Password
@@ -332,7 +352,7 @@ pub async fn delete_user(
     }
 
     let auth_repo = AuthRepository::new(state.db_pool_auth.clone());
-    let user_repo = UserRepository::new(state.db_pool_auth.clone(), state.field_encryption_key);
+    let user_repo = UserRepository::new(state.db_pool_auth.clone(), state.field_keyring.clone());
 
     auth_repo.revoke_all_user_tokens(user_id).await?;
 
@@ -343,6 +363,8 @@ pub async fn delete_user(
 
     {
         let mut enforcer = state.enforcer.write().await;
+        let policies = enforcer.get_filtered_policy(0, vec![user_id.to_string()]);
+        let groupings = enforcer.get_filtered_grouping_policy(0, vec![user_id.to_string()]);
         enforcer
             .remove_filtered_policy(0, vec![user_id.to_string()])
             .await
@@ -355,6 +377,17 @@ pub async fn delete_user(
         if let Err(e) = enforcer.save_policy().await {
             tracing::error!("Failed to save policy after user deletion: {:?}", e);
         }
+
+        let removed = policies
+            .iter()
+            .map(|rule| ("p", rule))
+            .chain(groupings.iter().map(|rule| ("g", rule)));
+        for (ptype, rule) in removed {
+            state
+                .entity_history_service
+                .record_policy_change(ptype, rule, false)
+                .await;
+        }
     }
 
     Ok(StatusCode::NO_CONTENT)
@@ -391,7 +424,7 @@ pub async fn ban_user(
     }
 
     let auth_repo = AuthRepository::new(state.db_pool_auth.clone());
-    let user_repo = UserRepository::new(state.db_pool_auth.clone(), state.field_encryption_key);
+    let user_repo = UserRepository::new(state.db_pool_auth.clone(), state.field_keyring.clone());
 
     // Ban the user
     user_repo
@@ -437,7 +470,7 @@ pub async fn unban_user(
     State(state): State<AppState>,
     Path(user_id): Path<Uuid>,
 ) -> Result<Json<UserActionResponse>, AppError> {
-    let user_repo = UserRepository::new(state.db_pool_auth.clone(), state.field_encryption_key);
+    let user_repo = UserRepository::new(state.db_pool_auth.clone(), state.field_keyring.clone());
 
     // Unban the user
     user_repo.unban_user(user_id).await.map_err(|e| match e {
@@ -4,8 +4,9 @@ use crate::state::AppState;
 use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
-    Json,
+    Extension, Json,
 };
+use domain::models::{ApprovalStepDecisionPayload, UnitScope};
 use domain::models::warehouse::{
     CancelDisposalRequestPayload, CreateDisposalRequestPayload, DisposalRequestStatus,
     ManualExitPayload, ReturnEntryPayload, StandaloneEntryPayload, StockMovementDto,
@@ -13,6 +14,7 @@ use domain::models::warehouse::{
 use serde::Deserialize;
 use utoipa::IntoParams;
 use uuid::Uuid;
+use validator::Validate;
 
 #[derive(Debug, Deserialize, IntoParams)]
 pub struct DisposalListQuery {
@@ -71,11 +73,12 @@ fn default_limit() -> i64 {
 pub async fn create_warehouse(
     _user: CurrentUser,
     State(state): State<AppState>,
+    Extension(scope): Extension<UnitScope>,
     Json(payload): Json<CreateWarehousePayload>,
 ) -> Result<(StatusCode, Json<WarehouseWithDetailsDto>), (StatusCode, String)> {
     state
         .warehouse_service
-        .create_warehouse(payload)
+        .create_warehouse(payload, &scope)
         .await
         .map(|w| (StatusCode::CREATED, Json(w)))
         .map_err(|e| (StatusCode::from(&e), e.to_string()))
@@ -84,11 +87,12 @@ pub async fn create_warehouse(
 pub async fn get_warehouse(
     _user: CurrentUser,
     State(state): State<AppState>,
+    Extension(scope): Extension<UnitScope>,
     Path(id): Path<Uuid>,
 ) -> Result<Json<WarehouseWithDetailsDto>, (StatusCode, String)> {
     state
         .warehouse_service
-        .get_warehouse(id)
+        .get_warehouse_in_scope(id, &scope)
         .await
         .map(Json)
         .map_err(|e| (StatusCode::from(&e), e.to_string()))
@@ -97,12 +101,13 @@ pub async fn get_warehouse(
 pub async fn update_warehouse(
     _user: CurrentUser,
     State(state): State<AppState>,
+    Extension(scope): Extension<UnitScope>,
     Path(id): Path<Uuid>,
     Json(payload): Json<UpdateWarehousePayload>,
 ) -> Result<Json<WarehouseWithDetailsDto>, (StatusCode, String)> {
     state
         .warehouse_service
-        .update_warehouse(id, payload)
+        .update_warehouse(id, payload, &scope)
         .await
         .map(Json)
         .map_err(|e| (StatusCode::from(&e), e.to_string()))
@@ -111,11 +116,12 @@ pub async fn update_warehouse(
 pub async fn delete_warehouse(
     _user: CurrentUser,
     State(state): State<AppState>,
+    Extension(scope): Extension<UnitScope>,
     Path(id): Path<Uuid>,
 ) -> Result<StatusCode, (StatusCode, String)> {
     state
         .warehouse_service
-        .delete_warehouse(id)
+        .delete_warehouse(id, &scope)
         .await
         .map(|_| StatusCode::NO_CONTENT)
         .map_err(|e| (StatusCode::from(&e), e.to_string()))
@@ -124,6 +130,7 @@ pub async fn delete_warehouse(
 pub async fn list_warehouses(
     _user: CurrentUser,
     State(state): State<AppState>,
+    Extension(scope): Extension<UnitScope>,
     Query(query): Query<WarehouseListQuery>,
 ) -> Result<Json<WarehousesListResponse>, (StatusCode, String)> {
     state
@@ -135,6 +142,7 @@ pub async fn list_warehouses(
             query.warehouse_type,
             query.city_id,
             query.is_active,
+            &scope,
         )
         .await
         .map(|(warehouses, total)| {
@@ -155,11 +163,12 @@ pub async fn list_warehouses(
 pub async fn get_stock(
     _user: CurrentUser,
     State(state): State<AppState>,
+    Extension(scope): Extension<UnitScope>,
     Path(stock_id): Path<Uuid>,
 ) -> Result<Json<WarehouseStockWithDetailsDto>, (StatusCode, String)> {
     state
         .warehouse_service
-        .get_stock(stock_id)
+        .get_stock(stock_id, &scope)
         .await
         .map(Json)
         .map_err(|e| (StatusCode::from(&e), e.to_string()))
@@ -168,6 +177,7 @@ pub async fn get_stock(
 pub async fn list_warehouse_stocks(
     _user: CurrentUser,
     State(state): State<AppState>,
+    Extension(scope): Extension<UnitScope>,
     Path(warehouse_id): Path<Uuid>,
     Query(query): Query<StockListQuery>,
 ) -> Result<Json<WarehouseStocksListResponse>, (StatusCode, String)> {
@@ -179,6 +189,7 @@ pub async fn list_warehouse_stocks(
             query.offset,
             query.search,
             query.is_blocked,
+            &scope,
         )
         .await
         .map(|(stocks, total)| {
@@ -195,12 +206,13 @@ pub async fn list_warehouse_stocks(
 pub async fn update_stock_params(
     _user: CurrentUser,
     State(state): State<AppState>,
+    Extension(scope): Extension<UnitScope>,
     Path(stock_id): Path<Uuid>,
     Json(payload): Json<UpdateStockParamsPayload>,
 ) -> Result<Json<WarehouseStockDto>, (StatusCode, String)> {
     state
         .warehouse_service
-        .update_stock_params(stock_id, payload)
+        .update_stock_params(stock_id, payload, &scope)
         .await
         .map(Json)
         .map_err(|e| (StatusCode::from(&e), e.to_string()))
@@ -209,12 +221,13 @@ pub async fn update_stock_params(
 pub async fn block_stock(
     user: CurrentUser,
     State(state): State<AppState>,
+    Extension(scope): Extension<UnitScope>,
     Path(stock_id): Path<Uuid>,
     Json(payload): Json<BlockStockPayload>,
 ) -> Result<Json<WarehouseStockDto>, (StatusCode, String)> {
     state
         .warehouse_service
-        .block_stock(stock_id, payload, user.id)
+        .block_stock(stock_id, payload, user.id, &scope)
         .await
         .map(Json)
         .map_err(|e| (StatusCode::from(&e), e.to_string()))
@@ -223,11 +236,12 @@ pub async fn block_stock(
 pub async fn unblock_stock(
     _user: CurrentUser,
     State(state): State<AppState>,
+    Extension(scope): Extension<UnitScope>,
     Path(stock_id): Path<Uuid>,
 ) -> Result<Json<WarehouseStockDto>, (StatusCode, String)> {
     state
         .warehouse_service
-        .unblock_stock(stock_id)
+        .unblock_stock(stock_id, &scope)
         .await
         .map(Json)
         .map_err(|e| (StatusCode::from(&e), e.to_string()))
@@ -242,6 +256,7 @@ pub async fn unblock_stock(
 pub async fn list_stock_movements(
     _user: CurrentUser,
     State(state): State<AppState>,
+    Extension(scope): Extension<UnitScope>,
     Path(warehouse_id): Path<Uuid>,
     Query(query): Query<MovementListQuery>,
 ) -> Result<Json<MovementsListResponse>, (StatusCode, String)> {
@@ -253,6 +268,7 @@ pub async fn list_stock_movements(
             query.offset,
             query.catalog_item_id,
             query.movement_type,
+            &scope,
         )
         .await
         .map(|(movements, total)| {
@@ -272,12 +288,13 @@ pub async fn list_stock_movements(
 pub async fn create_standalone_entry(
     user: CurrentUser,
     State(state): State<AppState>,
+    Extension(scope): Extension<UnitScope>,
     Path(warehouse_id): Path<Uuid>,
     Json(payload): Json<StandaloneEntryPayload>,
 ) -> Result<(StatusCode, Json<serde_json::Value>), (StatusCode, String)> {
     state
         .warehouse_service
-        .create_standalone_entry(warehouse_id, payload, user.id)
+        .create_standalone_entry(warehouse_id, payload, user.id, &scope)
         .await
         .map(|r| {
             (
@@ -298,12 +315,13 @@ pub async fn create_standalone_entry(
 pub async fn create_return_entry(
     user: CurrentUser,
     State(state): State<AppState>,
+    Extension(scope): Extension<UnitScope>,
     Path(warehouse_id): Path<Uuid>,
     Json(payload): Json<ReturnEntryPayload>,
 ) -> Result<(StatusCode, Json<serde_json::Value>), (StatusCode, String)> {
     state
         .warehouse_service
-        .create_return_entry(warehouse_id, payload, user.id)
+        .create_return_entry(warehouse_id, payload, user.id, &scope)
         .await
         .map(|r| {
             (
@@ -323,12 +341,13 @@ pub async fn create_return_entry(
 pub async fn create_disposal_request(
     user: CurrentUser,
     State(state): State<AppState>,
+    Extension(scope): Extension<UnitScope>,
     Path(warehouse_id): Path<Uuid>,
     Json(payload): Json<CreateDisposalRequestPayload>,
 ) -> Result<(StatusCode, Json<serde_json::Value>), (StatusCode, String)> {
     state
         .warehouse_service
-        .create_disposal_request(warehouse_id, payload, user.id)
+        .create_disposal_request(warehouse_id, payload, user.id, &scope)
         .await
         .map(|r| (StatusCode::CREATED, Json(serde_json::json!(r))))
         .map_err(|e| (StatusCode::from(&e), e.to_string()))
@@ -338,12 +357,19 @@ pub async fn create_disposal_request(
 pub async fn list_disposal_requests(
     _user: CurrentUser,
     State(state): State<AppState>,
+    Extension(scope): Extension<UnitScope>,
     Path(warehouse_id): Path<Uuid>,
     Query(query): Query<DisposalListQuery>,
 ) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
     state
         .warehouse_service
-        .list_disposal_requests(warehouse_id, query.limit, query.offset, query.status)
+        .list_disposal_requests(
+            warehouse_id,
+            query.limit,
+            query.offset,
+            query.status,
+            &scope,
+        )
         .await
         .map(|(data, total)| {
             Json(serde_json::json!({
@@ -360,11 +386,48 @@ pub async fn list_disposal_requests(
 pub async fn get_disposal_request(
     _user: CurrentUser,
     State(state): State<AppState>,
+    Extension(scope): Extension<UnitScope>,
     Path(request_id): Path<Uuid>,
 ) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
     state
         .warehouse_service
-        .get_disposal_request(request_id)
+        .get_disposal_request_in_scope(request_id, &scope)
+        .await
+        .map(|r| Json(serde_json::json!(r)))
+        .map_err(|e| (StatusCode::from(&e), e.to_string()))
+}
+
+/// GET /api/admin/disposal-requests/:id/approval-steps
+pub async fn get_disposal_approval_steps(
+    _user: CurrentUser,
+    State(state): State<AppState>,
+    Extension(scope): Extension<UnitScope>,
+    Path(request_id): Path<Uuid>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    state
+        .warehouse_service
+        .get_disposal_approval_steps(request_id, &scope)
+        .await
+        .map(|r| Json(serde_json::json!(r)))
+        .map_err(|e| (StatusCode::from(&e), e.to_string()))
+}
+
+/// POST /api/admin/disposal-requests/:id/approve
+/// Aprova a etapa do usuário na cadeia de aprovação do pedido
+pub async fn approve_disposal_step(
+    user: CurrentUser,
+    State(state): State<AppState>,
+    Extension(scope): Extension<UnitScope>,
+    Path(request_id): Path<Uuid>,
+    Json(payload): Json<ApprovalStepDecisionPayload>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    payload
+        .validate()
+        .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+
+    state
+        .warehouse_service
+        .approve_disposal_step(request_id, payload, user.id, &scope)
         .await
         .map(|r| Json(serde_json::json!(r)))
         .map_err(|e| (StatusCode::from(&e), e.to_string()))
@@ -375,11 +438,12 @@ pub async fn get_disposal_request(
 pub async fn confirm_disposal_signature(
     user: CurrentUser,
     State(state): State<AppState>,
+    Extension(scope): Extension<UnitScope>,
     Path(request_id): Path<Uuid>,
 ) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
     state
         .warehouse_service
-        .confirm_disposal_signature(request_id, user.id)
+        .confirm_disposal_signature(request_id, user.id, &scope)
         .await
         .map(|r| Json(serde_json::json!(r)))
         .map_err(|e| (StatusCode::from(&e), e.to_string()))
@@ -389,12 +453,13 @@ pub async fn confirm_disposal_signature(
 pub async fn cancel_disposal_request(
     user: CurrentUser,
     State(state): State<AppState>,
+    Extension(scope): Extension<UnitScope>,
     Path(request_id): Path<Uuid>,
     Json(payload): Json<CancelDisposalRequestPayload>,
 ) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
     state
         .warehouse_service
-        .cancel_disposal_request(request_id, payload, user.id)
+        .cancel_disposal_request(request_id, payload, user.id, &scope)
         .await
         .map(|r| Json(serde_json::json!(r)))
         .map_err(|e| (StatusCode::from(&e), e.to_string()))
@@ -405,12 +470,13 @@ pub async fn cancel_disposal_request(
 pub async fn create_manual_exit(
     user: CurrentUser,
     State(state): State<AppState>,
+    Extension(scope): Extension<UnitScope>,
     Path(warehouse_id): Path<Uuid>,
     Json(payload): Json<ManualExitPayload>,
 ) -> Result<(StatusCode, Json<serde_json::Value>), (StatusCode, String)> {
     state
         .warehouse_service
-        .create_manual_exit(warehouse_id, payload, user.id)
+        .create_manual_exit(warehouse_id, payload, user.id, &scope)
         .await
         .map(|r| {
             (
@@ -10,6 +10,8 @@ use axum::{
 pub fn disposal_requests_router() -> Router<AppState> {
     Router::new()
         .route("/{id}", get(handlers::get_disposal_request))
+        .route("/{id}/approval-steps", get(handlers::get_disposal_approval_steps))
+        .route("/{id}/approve", post(handlers::approve_disposal_step))
         .route("/{id}/confirm-signature", post(handlers::confirm_disposal_signature))
         .route("/{id}/cancel", post(handlers::cancel_disposal_request))
 }
@@ -61,6 +61,13 @@ pub struct ResetPasswordRequest {
     pub new_password: String,
 }
 
+#[derive(Deserialize, Validate, ToSchema)]
+pub struct UnlockAccountRequest {
+    /// Token recebido no email de conta bloqueada
+    #[validate(length(min = 1))]
+    pub token: String,
+}
+
 // RESPONSES
 
 #[derive(Serialize, ToSchema)]
@@ -196,6 +203,20 @@ impl Default for ResetPasswordResponse {
     }
 }
 
+#[derive(Serialize, ToSchema)]
+pub struct UnlockAccountResponse {
+    /// Mensagem de confirmação
+    pub message: String,
+}
+
+impl Default for UnlockAccountResponse {
+    fn default() -> Self {
+        Self {
+            message: "Conta desbloqueada. Você já pode fazer login.".to_string(),
+        }
+    }
+}
+
 #[derive(Serialize, ToSchema)]
 pub struct MfaRequiredResponse {
     /// Indica que MFA é necessário
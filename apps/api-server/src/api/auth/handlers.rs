@@ -5,9 +5,13 @@
 
 use crate::api::email_verification::create_verification_token;
 use anyhow::Context;
-use axum::{extract::State, http::StatusCode, Json};
+use axum::{
+    extract::State,
+    http::StatusCode,
+    Json,
+};
 use chrono::{Duration, Utc};
-use core_services::security::{hash_password, validate_password_strength, verify_password};
+use core_services::security::{hash_password, validate_password_strength};
 use domain::models::TokenType;
 use domain::ports::AuthRepositoryPort;
 use domain::ports::UserRepositoryPort;
@@ -17,12 +21,15 @@ use tracing::{error, info, warn};
 use uuid::Uuid;
 use validator::Validate;
 
+use super::session_handlers::authenticate_password;
+use crate::extractors::client_ip::ClientIp;
 use crate::infra::{errors::AppError, state::AppState};
 
 use super::contracts::{
     ForgotPasswordRequest, ForgotPasswordResponse, LoginRequest, LoginResponse, LogoutRequest,
     LogoutResponse, MfaRequiredResponse, RefreshTokenRequest, RefreshTokenResponse,
     RegisterRequest, RegisterResponse, ResetPasswordRequest, ResetPasswordResponse,
+    UnlockAccountRequest, UnlockAccountResponse,
 };
 
 // =============================================================================
@@ -109,6 +116,7 @@ fn generate_mfa_challenge_token(state: &AppState, user_id: Uuid) -> anyhow::Resu
 )]
 pub async fn login(
     State(state): State<AppState>,
+    ClientIp(ip): ClientIp,
     Json(payload): Json<LoginRequest>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     // 1. Validar payload
@@ -117,30 +125,15 @@ pub async fn login(
         AppError::Validation(e)
     })?;
 
-    // 2. Buscar usuário por username ou email (via repositório)
-    let user_repo = UserRepository::new(state.db_pool_auth.clone(), state.field_encryption_key);
-    let user_info = user_repo
-        .find_for_login(&payload.username)
-        .await?
-        .ok_or(AppError::InvalidPassword)?;
+    // 2-3. Buscar usuário e verificar senha (com bloqueio progressivo e
+    // limites por IP/sub-rede)
+    let user_info =
+        authenticate_password(&state, ip, &payload.username, &payload.password).await?;
 
     let user_id = user_info.id;
     let username = user_info.username;
-    let password_hash = user_info.password_hash;
     let mfa_enabled = user_info.mfa_enabled;
 
-    // 3. Verificar senha com Argon2id
-    let password_valid =
-        tokio::task::spawn_blocking(move || verify_password(&payload.password, &password_hash))
-            .await
-            .context("Falha na task de verificação de senha")?
-            .map_err(|_| AppError::InvalidPassword)?;
-
-    if !password_valid {
-        warn!(username = %payload.username, "Tentativa de login com senha inválida");
-        return Err(AppError::InvalidPassword);
-    }
-
     // 4. Verificar se MFA está habilitado
     if mfa_enabled {
         let mfa_token =
@@ -195,7 +188,7 @@ pub async fn register(
 
     validate_password_strength(&payload.password).map_err(AppError::BadRequest)?;
 
-    let user_repo = UserRepository::new(state.db_pool_auth.clone(), state.field_encryption_key);
+    let user_repo = UserRepository::new(state.db_pool_auth.clone(), state.field_keyring.clone());
 
     if user_repo.exists_by_username(&payload.username).await? {
         return Err(AppError::Conflict("Username já está em uso".to_string()));
@@ -446,7 +439,7 @@ pub async fn forgot_password(
         AppError::Validation(e)
     })?;
 
-    let user_repo = UserRepository::new(state.db_pool_auth, state.field_encryption_key);
+    let user_repo = UserRepository::new(state.db_pool_auth, state.field_keyring.clone());
 
     // 2. Buscar usuário pelo email
     let user = user_repo.find_by_email(&payload.email).await?;
@@ -526,7 +519,7 @@ pub async fn reset_password(
         .context("Erro ao gerar hash da senha")?;
 
     // 5. Atualizar senha no banco
-    let user_repo = UserRepository::new(state.db_pool_auth.clone(), state.field_encryption_key);
+    let user_repo = UserRepository::new(state.db_pool_auth.clone(), state.field_keyring.clone());
     user_repo
         .update_password(user_id, &password_hash)
         .await
@@ -539,11 +532,45 @@ pub async fn reset_password(
         .await
         .context("Falha ao revogar tokens")?;
 
+    // 7. Nova senha encerra qualquer bloqueio por tentativas falhas
+    if let Err(e) = state.login_throttle_service.clear_lockout(user_id).await {
+        if !matches!(e, application::errors::ServiceError::NotFound(_)) {
+            warn!(user_id = %user_id, error = %e, "Falha ao limpar bloqueio de login");
+        }
+    }
+
     info!(user_id = %user_id, "Senha redefinida com sucesso");
 
     Ok(Json(ResetPasswordResponse::default()))
 }
 
+/// POST /unlock-account
+///
+/// Desbloqueia a conta pelo link enviado no email de bloqueio.
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/unlock-account",
+    tag = "Auth",
+    request_body = UnlockAccountRequest,
+    responses(
+        (status = 200, description = "Conta desbloqueada", body = UnlockAccountResponse),
+        (status = 400, description = "Token inválido ou expirado")
+    )
+)]
+pub async fn unlock_account(
+    State(state): State<AppState>,
+    Json(payload): Json<UnlockAccountRequest>,
+) -> Result<Json<UnlockAccountResponse>, AppError> {
+    payload.validate().map_err(AppError::Validation)?;
+
+    state
+        .login_throttle_service
+        .unlock_with_token(&payload.token)
+        .await?;
+
+    Ok(Json(UnlockAccountResponse::default()))
+}
+
 // =============================================================================
 // INTERNAL TYPES
 // =============================================================================
@@ -6,6 +6,7 @@
 //! - Refresh de tokens
 //! - Reset de senha
 //! - Session management (HttpOnly cookies)
+//! - Single sign-on OpenID Connect (gov.br)
 //!
 //! # Arquitetura
 //!
@@ -14,6 +15,8 @@
 //! ├── mod.rs              # Router + re-exports (este arquivo)
 //! ├── handlers.rs         # Handlers HTTP (JWT-based)
 //! ├── session_handlers.rs # Handlers HTTP (Cookie-based)
+//! ├── oidc_handlers.rs    # Handlers HTTP (SSO OpenID Connect)
+//! ├── passkey_handlers.rs # Handlers HTTP (login sem senha com passkey)
 //! └── contracts.rs        # DTOs (Request/Response)
 //! ```
 //!
@@ -31,6 +34,8 @@
 
 pub mod contracts;
 pub mod handlers;
+pub mod oidc_handlers;
+pub mod passkey_handlers;
 pub mod session_handlers;
 
 use axum::{
@@ -80,6 +85,14 @@ pub use contracts::{
 /// | GET    | /session/list         | list_sessions     | Lista sessões do usuário     |
 /// | DELETE | /session/{id}         | revoke_session    | Revoga sessão específica     |
 ///
+/// # Rotas OIDC (SSO)
+///
+/// | Método | Path                      | Handler        | Descrição                         |
+/// |--------|---------------------------|----------------|-----------------------------------|
+/// | GET    | /oidc/providers           | list_providers | Provedores configurados           |
+/// | GET    | /oidc/{provider}/login    | oidc_login     | Redireciona ao IdP (PKCE)         |
+/// | GET    | /oidc/{provider}/callback | oidc_callback  | Valida retorno e cria a sessão    |
+///
 /// # Exemplo
 ///
 /// ```rust,ignore
@@ -97,7 +110,8 @@ pub fn router() -> Router<AppState> {
         .route("/refresh-token", post(handlers::refresh_token))
         .route("/logout", post(handlers::logout))
         .route("/forgot-password", post(handlers::forgot_password))
-        .route("/reset-password", post(handlers::reset_password));
+        .route("/reset-password", post(handlers::reset_password))
+        .route("/unlock-account", post(handlers::unlock_account));
 
     let session_routes = Router::new()
         // Autenticação Session (Cookie-Based)
@@ -109,8 +123,21 @@ pub fn router() -> Router<AppState> {
         .route("/session/list", get(session_handlers::list_sessions))
         .route("/session/{session_id}", delete(session_handlers::revoke_session));
 
+    let oidc_routes = Router::new()
+        // Single sign-on (OpenID Connect)
+        .route("/oidc/providers", get(oidc_handlers::list_providers))
+        .route("/oidc/{provider}/login", get(oidc_handlers::oidc_login))
+        .route("/oidc/{provider}/callback", get(oidc_handlers::oidc_callback));
+
+    let passkey_routes = Router::new()
+        // Login sem senha com passkey (WebAuthn)
+        .route("/session/passkey/start", post(passkey_handlers::passkey_login_start))
+        .route("/session/passkey/finish", post(passkey_handlers::passkey_login_finish));
+
     jwt_routes
         .merge(session_routes)
+        .merge(oidc_routes)
+        .merge(passkey_routes)
         .layer(login_rate_limiter())
 }
 
@@ -0,0 +1,179 @@
+//! OpenID Connect (SSO) Handlers
+//!
+//! Authorization Code + PKCE login against external identity providers
+//! (gov.br and others). A successful callback creates the same HttpOnly
+//! session as `session_login`.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::{
+        header::{LOCATION, SET_COOKIE},
+        HeaderMap, StatusCode,
+    },
+    response::{IntoResponse, Redirect, Response},
+    Json,
+};
+use casbin::{CoreApi, MgmtApi};
+use chrono::Utc;
+use core_services::session::{
+    build_oidc_state_cookie, build_removal_cookie, config, hash_token,
+};
+use domain::models::{OidcCallbackQuery, OidcLoginQuery, OidcProviderDto};
+use tower_cookies::Cookies;
+use tracing::warn;
+
+use super::session_handlers::create_session;
+use crate::infra::{errors::AppError, state::AppState};
+
+/// GET /api/v1/auth/oidc/providers
+///
+/// Lists the configured identity providers (for the login page).
+#[utoipa::path(
+    get,
+    path = "/api/v1/auth/oidc/providers",
+    tag = "Session Auth",
+    responses(
+        (status = 200, description = "Configured providers", body = Vec<OidcProviderDto>)
+    )
+)]
+pub async fn list_providers(State(state): State<AppState>) -> Json<Vec<OidcProviderDto>> {
+    Json(state.oidc_service.list_providers())
+}
+
+/// GET /api/v1/auth/oidc/{provider}/login
+///
+/// Redirects the browser to the provider's authorization endpoint and binds
+/// the login `state` to this browser with a short-lived HttpOnly cookie.
+#[utoipa::path(
+    get,
+    path = "/api/v1/auth/oidc/{provider}/login",
+    tag = "Session Auth",
+    params(
+        ("provider" = String, Path, description = "Provider name (e.g. govbr)"),
+        ("redirect_to" = Option<String>, Query, description = "Relative path to return to after login")
+    ),
+    responses(
+        (status = 303, description = "Redirect to the identity provider"),
+        (status = 400, description = "Invalid redirect_to"),
+        (status = 404, description = "Provider not configured")
+    )
+)]
+pub async fn oidc_login(
+    State(state): State<AppState>,
+    Path(provider): Path<String>,
+    Query(query): Query<OidcLoginQuery>,
+) -> Result<Response, AppError> {
+    let authorization = state
+        .oidc_service
+        .begin_login(&provider, query.redirect_to)
+        .await?;
+
+    let lifetime = (authorization.expires_at - Utc::now())
+        .to_std()
+        .unwrap_or_default();
+    let state_cookie = build_oidc_state_cookie(&hash_token(&authorization.state), lifetime);
+
+    let mut response = Redirect::to(&authorization.authorization_url).into_response();
+    response
+        .headers_mut()
+        .append(SET_COOKIE, state_cookie.to_string().parse().unwrap());
+    Ok(response)
+}
+
+/// GET /api/v1/auth/oidc/{provider}/callback
+///
+/// Redirect URI registered at the provider. Validates state/nonce (the state
+/// must match the cookie set at login), links or provisions the local account
+/// and starts a cookie session. Redirects to the `redirect_to` given at login,
+/// or returns the session JSON otherwise.
+#[utoipa::path(
+    get,
+    path = "/api/v1/auth/oidc/{provider}/callback",
+    tag = "Session Auth",
+    params(
+        ("provider" = String, Path, description = "Provider name (e.g. govbr)"),
+        ("code" = Option<String>, Query, description = "Authorization code"),
+        ("state" = Option<String>, Query, description = "State issued at login"),
+        ("error" = Option<String>, Query, description = "Error returned by the provider")
+    ),
+    responses(
+        (status = 200, description = "Login successful", body = super::session_handlers::SessionLoginResponse),
+        (status = 303, description = "Login successful, redirect to redirect_to"),
+        (status = 400, description = "Invalid or expired state, or state not issued to this browser"),
+        (status = 401, description = "Identity rejected or not linked to any account")
+    )
+)]
+pub async fn oidc_callback(
+    State(state): State<AppState>,
+    Path(provider): Path<String>,
+    Query(query): Query<OidcCallbackQuery>,
+    cookies: Cookies,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    if let Some(error) = query.error {
+        warn!(provider = %provider, error = %error, description = ?query.error_description, "OIDC provider returned an error");
+        return Err(AppError::Unauthorized(format!(
+            "Identity provider denied the login: {}",
+            error
+        )));
+    }
+
+    let (Some(code), Some(login_state)) = (query.code, query.state) else {
+        return Err(AppError::BadRequest(
+            "Missing 'code' or 'state' parameter".to_string(),
+        ));
+    };
+
+    // Login CSRF: the callback must come from the browser that started the login
+    let bound_to_browser = cookies
+        .get(config::OIDC_STATE_COOKIE_NAME)
+        .is_some_and(|c| c.value() == hash_token(&login_state));
+    if !bound_to_browser {
+        warn!(provider = %provider, "OIDC callback without a matching state cookie");
+        return Err(AppError::BadRequest(
+            "Login state was not issued to this browser".to_string(),
+        ));
+    }
+
+    let outcome = state
+        .oidc_service
+        .complete_login(&provider, &code, &login_state)
+        .await?;
+
+    // Just-in-time account: register the default role with Casbin
+    if let Some(role) = &outcome.provisioned_role {
+        let rule = vec![outcome.user_id.to_string(), role.clone()];
+        let mut enforcer = state.enforcer.write().await;
+        let added = enforcer
+            .add_grouping_policy(rule.clone())
+            .await
+            .map_err(|e| AppError::Internal(format!("Falha ao atribuir o papel: {}", e)))?;
+        enforcer
+            .save_policy()
+            .await
+            .map_err(|e| AppError::Internal(format!("Falha ao salvar o papel: {}", e)))?;
+        if added {
+            state
+                .entity_history_service
+                .record_policy_change("g", &rule, true)
+                .await;
+        }
+    }
+
+    let issued = create_session(&state, &headers, outcome.user_id, &outcome.username, false).await?;
+
+    let response = match &outcome.redirect_to {
+        Some(path) => (StatusCode::SEE_OTHER, [(LOCATION, path.as_str())]).into_response(),
+        None => Json(&issued.body).into_response(),
+    };
+
+    let mut response = issued.attach_cookies(response);
+    response.headers_mut().append(
+        SET_COOKIE,
+        build_removal_cookie(config::OIDC_STATE_COOKIE_NAME)
+            .to_string()
+            .parse()
+            .unwrap(),
+    );
+    Ok(response)
+}
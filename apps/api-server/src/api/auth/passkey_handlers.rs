@@ -0,0 +1,69 @@
+//! Passwordless Login Handlers
+//!
+//! Login com passkey (credencial descoberta pelo navegador, sem usuário e
+//! senha). Uma asserção válida cria a mesma sessão HttpOnly de `session_login`.
+
+use axum::{
+    extract::State,
+    http::HeaderMap,
+    response::{IntoResponse, Response},
+    Json,
+};
+use domain::models::{FinishPasskeyCeremonyPayload, WebauthnChallengeDto};
+use domain::ports::UserRepositoryPort;
+use persistence::repositories::user_repository::UserRepository;
+use tracing::warn;
+
+use super::session_handlers::create_session;
+use crate::infra::{errors::AppError, state::AppState};
+
+/// POST /api/v1/auth/session/passkey/start
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/session/passkey/start",
+    tag = "Session Auth",
+    responses(
+        (status = 200, description = "Options for navigator.credentials.get()", body = WebauthnChallengeDto)
+    )
+)]
+pub async fn passkey_login_start(
+    State(state): State<AppState>,
+) -> Result<Json<WebauthnChallengeDto>, AppError> {
+    let challenge = state.webauthn_service.start_passwordless().await?;
+    Ok(Json(challenge))
+}
+
+/// POST /api/v1/auth/session/passkey/finish
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/session/passkey/finish",
+    tag = "Session Auth",
+    request_body = FinishPasskeyCeremonyPayload,
+    responses(
+        (status = 200, description = "Login successful", body = super::session_handlers::SessionLoginResponse),
+        (status = 400, description = "Invalid or expired challenge"),
+        (status = 401, description = "Assertion rejected")
+    )
+)]
+pub async fn passkey_login_finish(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(payload): Json<FinishPasskeyCeremonyPayload>,
+) -> Result<Response, AppError> {
+    let assertion = state.webauthn_service.finish_passwordless(payload).await?;
+
+    let user_repo = UserRepository::new(state.db_pool_auth.clone(), state.field_keyring.clone());
+    let user = user_repo
+        .find_extended_by_id(assertion.user_id)
+        .await?
+        .ok_or(AppError::InvalidPassword)?;
+
+    if user.is_banned {
+        warn!(user_id = %user.id, "Passkey login by banned user");
+        return Err(AppError::InvalidPassword);
+    }
+
+    let issued = create_session(&state, &headers, user.id, user.username.as_str(), false).await?;
+
+    Ok(issued.attach_cookies(Json(&issued.body).into_response()))
+}
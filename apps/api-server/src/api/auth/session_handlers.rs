@@ -10,25 +10,27 @@ use axum::{
     Json,
 };
 use chrono::{Duration, Utc};
+use application::errors::ServiceError;
 use core_services::{
-    security::verify_password,
     session::{
         build_csrf_cookie, build_removal_cookie, build_session_cookie, config, encryption,
         generate_csrf_token, generate_session_token, hash_token,
     },
 };
-use domain::models::{CreateSession, SessionRevocationReason};
+use domain::models::{CreateSession, SessionRevocationReason, UserLoginInfo};
 use domain::ports::{SessionRepositoryPort, UserRepositoryPort};
 use persistence::repositories::{
     session_repository::SessionRepository, user_repository::UserRepository,
 };
 use serde::{Deserialize, Serialize};
-use tower_cookies::Cookies;
+use tower_cookies::{Cookie, Cookies};
 use tracing::{error, info, warn};
 use utoipa::ToSchema;
+use std::net::IpAddr;
 use uuid::Uuid;
 use validator::Validate;
 
+use crate::extractors::client_ip::ClientIp;
 use crate::infra::{errors::AppError, state::AppState};
 
 // =============================================================================
@@ -102,7 +104,7 @@ pub struct SessionSummaryDto {
 // =============================================================================
 
 /// Extracts client IP from headers (X-Forwarded-For, X-Real-IP, or direct)
-fn extract_client_ip(headers: &HeaderMap) -> Option<String> {
+pub(crate) fn extract_client_ip(headers: &HeaderMap) -> Option<String> {
     // Try X-Forwarded-For first (may have multiple IPs)
     if let Some(xff) = headers.get("X-Forwarded-For") {
         if let Ok(xff_str) = xff.to_str() {
@@ -158,101 +160,108 @@ async fn get_encryption_key(state: &AppState) -> Result<Vec<u8>, AppError> {
     Ok(key.key_material)
 }
 
-// =============================================================================
-// HANDLERS
-// =============================================================================
-
-/// POST /api/v1/auth/session/login
-///
-/// Session-based login with HttpOnly cookies.
-/// Sets session cookie (HttpOnly, Secure, SameSite=Strict) and CSRF cookie.
-#[utoipa::path(
-    post,
-    path = "/api/v1/auth/session/login",
-    tag = "Session Auth",
-    request_body = SessionLoginRequest,
-    responses(
-        (status = 200, description = "Login successful", body = SessionLoginResponse),
-        (status = 400, description = "Invalid request"),
-        (status = 401, description = "Invalid credentials")
-    )
-)]
-pub async fn session_login(
-    State(state): State<AppState>,
-    headers: HeaderMap,
-    Json(payload): Json<SessionLoginRequest>,
-) -> Result<Response, AppError> {
-    // 1. Validate payload
-    payload.validate().map_err(|e| {
-        warn!(validation_errors = ?e, "Session login validation failed");
-        AppError::Validation(e)
-    })?;
-
-    // 2. Find user
-    let user_repo = UserRepository::new(state.db_pool_auth.clone(), state.field_encryption_key);
-    let user_info = user_repo
-        .find_for_login(&payload.username)
-        .await?
-        .ok_or(AppError::InvalidPassword)?;
+/// Session just created for an authenticated user: response body plus the
+/// session/CSRF cookies that must accompany it.
+pub(crate) struct IssuedSession {
+    pub body: SessionLoginResponse,
+    session_cookie: Cookie<'static>,
+    csrf_cookie: Cookie<'static>,
+}
 
-    let user_id = user_info.id;
-    let username = user_info.username.clone();
-    let password_hash = user_info.password_hash.clone();
-
-    // 3. Verify password
-    let password_valid =
-        tokio::task::spawn_blocking(move || verify_password(&payload.password, &password_hash))
-            .await
-            .map_err(|e| AppError::Internal(format!("Password verification task failed: {}", e)))?
-            .map_err(|_| AppError::InvalidPassword)?;
-
-    if !password_valid {
-        warn!(username = %payload.username, "Invalid password attempt");
-        return Err(AppError::InvalidPassword);
+impl IssuedSession {
+    /// Appends the Set-Cookie headers to any response (JSON or redirect)
+    pub(crate) fn attach_cookies(&self, mut response: Response) -> Response {
+        response.headers_mut().append(
+            SET_COOKIE,
+            self.session_cookie.to_string().parse().unwrap(),
+        );
+        response.headers_mut().append(
+            SET_COOKIE,
+            self.csrf_cookie.to_string().parse().unwrap(),
+        );
+        response
     }
+}
 
-    // 4. Check MFA (for now, return error if MFA is enabled - should use JWT flow)
-    if user_info.mfa_enabled {
-        return Err(AppError::BadRequest(
-            "MFA is enabled. Please use the JWT login endpoint.".to_string(),
-        ));
-    }
+/// Checks a username/email + password pair through the login throttle.
+///
+/// Shared by JWT and cookie login: applies the per-IP/subnet limits and the
+/// progressive account lockout, and costs the same whether or not the
+/// account exists.
+pub(crate) async fn authenticate_password(
+    state: &AppState,
+    ip: Option<IpAddr>,
+    identifier: &str,
+    password: &str,
+) -> Result<UserLoginInfo, AppError> {
+    let user_repo = UserRepository::new(state.db_pool_auth.clone(), state.field_keyring.clone());
+    let user_info = user_repo.find_for_login(identifier).await?;
+
+    let candidate = user_info
+        .as_ref()
+        .map(|u| (u.id, u.password_hash.clone()));
+
+    state
+        .login_throttle_service
+        .verify_password(identifier, password, candidate, ip)
+        .await
+        .map_err(|e| match e {
+            ServiceError::InvalidCredentials => {
+                warn!(username = %identifier, "Invalid password attempt");
+                AppError::InvalidPassword
+            }
+            other => AppError::Service(other),
+        })?;
 
-    // 5. Generate session tokens
+    user_info.ok_or(AppError::InvalidPassword)
+}
+
+/// Creates a server-side session for an already authenticated user.
+///
+/// Shared by password login and external identity providers (OIDC), so every
+/// login path issues identical cookies.
+pub(crate) async fn create_session(
+    state: &AppState,
+    headers: &HeaderMap,
+    user_id: Uuid,
+    username: &str,
+    remember_me: bool,
+) -> Result<IssuedSession, AppError> {
+    // 1. Generate session tokens
     let session_token = generate_session_token();
     let session_token_hash = hash_token(&session_token);
     let csrf_token = generate_csrf_token();
     let csrf_token_hash = hash_token(&csrf_token);
 
-    // 6. Generate access token (JWT) for internal API calls
+    // 2. Generate access token (JWT) for internal API calls
     let access_token = state
         .jwt_service
-        .generate_token(user_id, &username, domain::models::TokenType::Access, 3600)
+        .generate_token(user_id, username, domain::models::TokenType::Access, 3600)
         .map_err(|e| {
             error!("Failed to generate access token: {:?}", e);
             AppError::Internal("Failed to generate access token".to_string())
         })?;
 
-    // 7. Encrypt access token for storage
-    let encryption_key = get_encryption_key(&state).await?;
+    // 3. Encrypt access token for storage
+    let encryption_key = get_encryption_key(state).await?;
     let access_token_encrypted = encryption::encrypt(&access_token, &encryption_key)
         .map_err(|e| AppError::Internal(format!("Encryption failed: {}", e)))?;
 
-    // 8. Calculate session duration
-    let session_duration = if payload.remember_me {
+    // 4. Calculate session duration
+    let session_duration = if remember_me {
         config::EXTENDED_SESSION_DURATION
     } else {
         config::SESSION_DURATION
     };
     let expires_at = Utc::now() + Duration::from_std(session_duration).unwrap();
 
-    // 9. Create session in database
+    // 5. Create session in database
     let session_repo = SessionRepository::new(state.db_pool_auth.clone());
     let create_session = CreateSession {
         session_token_hash: session_token_hash.clone(),
         user_id,
-        user_agent: extract_user_agent(&headers),
-        ip_address: extract_client_ip(&headers),
+        user_agent: extract_user_agent(headers),
+        ip_address: extract_client_ip(headers),
         access_token_encrypted,
         refresh_token_id: None,
         expires_at,
@@ -261,34 +270,73 @@ pub async fn session_login(
 
     let session = session_repo.create_session(create_session).await?;
 
-    // 10. Build cookies
-    let session_cookie = build_session_cookie(&session_token, session_duration);
-    let csrf_cookie = build_csrf_cookie(&csrf_token, session_duration);
-
     info!(user_id = %user_id, session_id = %session.id, "Session login successful");
 
-    // 11. Build response with Set-Cookie headers
-    let response_body = SessionLoginResponse {
-        success: true,
-        message: "Login successful".to_string(),
-        user_id,
-        username: username.clone(),
-        csrf_token: csrf_token.clone(),
-        expires_at: expires_at.timestamp(),
-    };
+    // 6. Build cookies
+    Ok(IssuedSession {
+        body: SessionLoginResponse {
+            success: true,
+            message: "Login successful".to_string(),
+            user_id,
+            username: username.to_string(),
+            csrf_token: csrf_token.clone(),
+            expires_at: expires_at.timestamp(),
+        },
+        session_cookie: build_session_cookie(&session_token, session_duration),
+        csrf_cookie: build_csrf_cookie(&csrf_token, session_duration),
+    })
+}
 
-    let mut response = Json(response_body).into_response();
+// =============================================================================
+// HANDLERS
+// =============================================================================
 
-    response.headers_mut().insert(
-        SET_COOKIE,
-        session_cookie.to_string().parse().unwrap(),
-    );
-    response.headers_mut().append(
-        SET_COOKIE,
-        csrf_cookie.to_string().parse().unwrap(),
-    );
+/// POST /api/v1/auth/session/login
+///
+/// Session-based login with HttpOnly cookies.
+/// Sets session cookie (HttpOnly, Secure, SameSite=Strict) and CSRF cookie.
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/session/login",
+    tag = "Session Auth",
+    request_body = SessionLoginRequest,
+    responses(
+        (status = 200, description = "Login successful", body = SessionLoginResponse),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Invalid credentials")
+    )
+)]
+pub async fn session_login(
+    State(state): State<AppState>,
+    ClientIp(ip): ClientIp,
+    headers: HeaderMap,
+    Json(payload): Json<SessionLoginRequest>,
+) -> Result<Response, AppError> {
+    // 1. Validate payload
+    payload.validate().map_err(|e| {
+        warn!(validation_errors = ?e, "Session login validation failed");
+        AppError::Validation(e)
+    })?;
 
-    Ok(response)
+    // 2-3. Find user and verify password (throttled)
+    let user_info =
+        authenticate_password(&state, ip, &payload.username, &payload.password).await?;
+
+    let user_id = user_info.id;
+    let username = user_info.username.clone();
+
+    // 4. Check MFA (for now, return error if MFA is enabled - should use JWT flow)
+    if user_info.mfa_enabled {
+        return Err(AppError::BadRequest(
+            "MFA is enabled. Please use the JWT login endpoint.".to_string(),
+        ));
+    }
+
+    // 5. Create session and set cookies
+    let issued = create_session(&state, &headers, user_id, &username, payload.remember_me).await?;
+    let response = Json(&issued.body).into_response();
+
+    Ok(issued.attach_cookies(response))
 }
 
 /// POST /api/v1/auth/session/logout
@@ -443,7 +491,7 @@ pub async fn session_info(
         .ok_or_else(|| AppError::Unauthorized("Invalid or expired session".to_string()))?;
 
     // 2. Get username
-    let user_repo = UserRepository::new(state.db_pool_auth.clone(), state.field_encryption_key);
+    let user_repo = UserRepository::new(state.db_pool_auth.clone(), state.field_keyring.clone());
     let user = user_repo
         .find_by_id(session.user_id)
         .await?
@@ -0,0 +1,160 @@
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    Json,
+};
+use domain::models::{
+    CatalogDuplicateFilter, CatalogDuplicateReport, CatalogItemMergeResult, CatalogItemRedirectDto,
+    MergeCatalogItemsPayload,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::extractors::current_user::CurrentUser;
+use crate::infra::state::AppState;
+
+// ============================================================================
+// Contracts
+// ============================================================================
+
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct CatmatDuplicatesParams {
+    /// Minimum description similarity, from 0.3 to 1
+    #[serde(default = "default_min_similarity")]
+    pub min_similarity: f32,
+    /// Only pair items of the same PDM. Legacy items each have their own
+    /// placeholder PDM, so turn it off to find them.
+    #[serde(default = "default_true")]
+    pub same_pdm: bool,
+    #[serde(default = "default_true")]
+    pub same_unit: bool,
+    pub pdm_id: Option<Uuid>,
+    #[serde(default)]
+    pub include_inactive: bool,
+}
+
+fn default_min_similarity() -> f32 {
+    0.6
+}
+
+fn default_true() -> bool {
+    true
+}
+
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct CatalogItemRedirectsParams {
+    /// Only redirects to this item
+    pub new_item_id: Option<Uuid>,
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+    #[serde(default)]
+    pub offset: i64,
+}
+
+fn default_limit() -> i64 {
+    50
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CatalogItemRedirectsListResponse {
+    pub data: Vec<CatalogItemRedirectDto>,
+    pub total: i64,
+    pub limit: i64,
+    pub offset: i64,
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// Clusters of CATMAT items that look like the same product, with the
+/// suggested surviving item of each cluster
+#[utoipa::path(
+    get,
+    path = "/api/admin/catalog/catmat/items/duplicates",
+    tag = "CATMAT - Items",
+    params(CatmatDuplicatesParams),
+    responses(
+        (status = 200, description = "Duplicate clusters", body = CatalogDuplicateReport),
+        (status = 400, description = "Similarity out of range"),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_catmat_duplicates(
+    _user: CurrentUser,
+    State(state): State<AppState>,
+    Query(params): Query<CatmatDuplicatesParams>,
+) -> Result<Json<CatalogDuplicateReport>, (StatusCode, String)> {
+    let filter = CatalogDuplicateFilter {
+        min_similarity: params.min_similarity,
+        same_pdm: params.same_pdm,
+        same_unit: params.same_unit,
+        pdm_id: params.pdm_id,
+        include_inactive: params.include_inactive,
+    };
+    state
+        .catalog_merge_service
+        .duplicate_report(&filter)
+        .await
+        .map(Json)
+        .map_err(|e| (StatusCode::from(&e), e.to_string()))
+}
+
+/// Merges duplicate CATMAT items into the surviving one, in a single
+/// transaction: every reference moves to it, stock balances are added up
+/// with a weighted-average value and the old IDs redirect to it
+#[utoipa::path(
+    post,
+    path = "/api/admin/catalog/catmat/items/merge",
+    tag = "CATMAT - Items",
+    request_body = MergeCatalogItemsPayload,
+    responses(
+        (status = 200, description = "Items merged", body = CatalogItemMergeResult),
+        (status = 400, description = "Invalid item list"),
+        (status = 404, description = "Item not found"),
+        (status = 409, description = "A document lists more than one of the items"),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn merge_catmat_items(
+    user: CurrentUser,
+    State(state): State<AppState>,
+    Json(payload): Json<MergeCatalogItemsPayload>,
+) -> Result<Json<CatalogItemMergeResult>, (StatusCode, String)> {
+    state
+        .catalog_merge_service
+        .merge_items(payload, Some(user.id))
+        .await
+        .map(Json)
+        .map_err(|e| (StatusCode::from(&e), e.to_string()))
+}
+
+/// IDs of CATMAT items retired by merges and the items that replaced them
+#[utoipa::path(
+    get,
+    path = "/api/admin/catalog/catmat/items/redirects",
+    tag = "CATMAT - Items",
+    params(CatalogItemRedirectsParams),
+    responses(
+        (status = 200, description = "Redirects", body = CatalogItemRedirectsListResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_catmat_redirects(
+    _user: CurrentUser,
+    State(state): State<AppState>,
+    Query(params): Query<CatalogItemRedirectsParams>,
+) -> Result<Json<CatalogItemRedirectsListResponse>, (StatusCode, String)> {
+    let (data, total) = state
+        .catalog_merge_service
+        .list_redirects(params.new_item_id, params.limit, params.offset)
+        .await
+        .map_err(|e| (StatusCode::from(&e), e.to_string()))?;
+    Ok(Json(CatalogItemRedirectsListResponse {
+        data,
+        total,
+        limit: params.limit,
+        offset: params.offset,
+    }))
+}
@@ -1,8 +1,15 @@
 pub mod contracts;
 pub mod handlers;
+pub mod merge_handlers;
+pub mod official_handlers;
+pub mod search_handlers;
+pub mod sync_handlers;
 
 use crate::state::AppState;
-use axum::{routing::get, Router};
+use axum::{
+    routing::{get, post},
+    Router,
+};
 
 /// Creates the catalog router with all CRUD routes for CATMAT, CATSER, units, and conversions
 pub fn router() -> Router<AppState> {
@@ -78,6 +85,10 @@ pub fn router() -> Router<AppState> {
             "/",
             get(handlers::list_catmat_items).post(handlers::create_catmat_item),
         )
+        .route("/search", get(search_handlers::search_catmat_items))
+        .route("/duplicates", get(merge_handlers::list_catmat_duplicates))
+        .route("/merge", post(merge_handlers::merge_catmat_items))
+        .route("/redirects", get(merge_handlers::list_catmat_redirects))
         .route(
             "/{id}",
             get(handlers::get_catmat_item)
@@ -145,6 +156,7 @@ pub fn router() -> Router<AppState> {
             "/",
             get(handlers::list_catser_items).post(handlers::create_catser_item),
         )
+        .route("/search", get(search_handlers::search_catser_items))
         .route(
             "/{id}",
             get(handlers::get_catser_item)
@@ -164,4 +176,24 @@ pub fn router() -> Router<AppState> {
         .nest("/catser/groups", catser_groups_router)
         .nest("/catser/classes", catser_classes_router)
         .nest("/catser/items", catser_items_router)
+        .route("/autocomplete", get(search_handlers::autocomplete))
+        .route("/sync", post(sync_handlers::start_sync))
+        .route("/sync/runs", get(sync_handlers::list_sync_runs))
+        .route("/sync/runs/{id}", get(sync_handlers::get_sync_run))
+        .route(
+            "/official/catmat/items",
+            get(official_handlers::search_official_catmat_items),
+        )
+        .route(
+            "/official/catmat/items/{code}/import",
+            post(official_handlers::import_catmat_item),
+        )
+        .route(
+            "/official/catser/items",
+            get(official_handlers::search_official_catser_items),
+        )
+        .route(
+            "/official/catser/items/{code}/import",
+            post(official_handlers::import_catser_item),
+        )
 }
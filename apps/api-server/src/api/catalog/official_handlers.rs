@@ -0,0 +1,186 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use domain::models::{
+    CatmatImportResult, CatserImportResult, OfficialCatmatItem, OfficialCatserItem,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::extractors::current_user::CurrentUser;
+use crate::infra::state::AppState;
+
+// ============================================================================
+// Contracts
+// ============================================================================
+
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct SearchOfficialCatmatParams {
+    pub item_code: Option<i64>,
+    pub pdm_code: Option<i64>,
+    pub class_code: Option<i64>,
+    pub group_code: Option<i64>,
+    #[serde(default = "default_page")]
+    pub page: i64,
+}
+
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct SearchOfficialCatserParams {
+    pub item_code: Option<i64>,
+    pub class_code: Option<i64>,
+    pub group_code: Option<i64>,
+    #[serde(default = "default_page")]
+    pub page: i64,
+}
+
+fn default_page() -> i64 {
+    1
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct OfficialCatmatSearchResponse {
+    pub data: Vec<OfficialCatmatItem>,
+    pub total: i64,
+    pub page: i64,
+    pub total_pages: i64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct OfficialCatserSearchResponse {
+    pub data: Vec<OfficialCatserItem>,
+    pub total: i64,
+    pub page: i64,
+    pub total_pages: i64,
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// Searches items of the official CATMAT (one page of the ComprasGov API),
+/// flagging the ones already registered locally
+#[utoipa::path(
+    get,
+    path = "/api/admin/catalog/official/catmat/items",
+    tag = "Catalog - Sync",
+    params(SearchOfficialCatmatParams),
+    responses(
+        (status = 200, description = "Official items", body = OfficialCatmatSearchResponse),
+        (status = 500, description = "ComprasGov unavailable"),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn search_official_catmat_items(
+    _user: CurrentUser,
+    State(state): State<AppState>,
+    Query(params): Query<SearchOfficialCatmatParams>,
+) -> Result<Json<OfficialCatmatSearchResponse>, (StatusCode, String)> {
+    let page = params.page.max(1);
+    let response = state
+        .catalog_sync_service
+        .search_official_catmat_items(
+            params.item_code,
+            params.pdm_code,
+            params.class_code,
+            params.group_code,
+            page,
+        )
+        .await
+        .map_err(|e| (StatusCode::from(&e), e.to_string()))?;
+    Ok(Json(OfficialCatmatSearchResponse {
+        data: response.resultado,
+        total: response.total_registros,
+        page,
+        total_pages: response.total_paginas,
+    }))
+}
+
+/// Searches items of the official CATSER (one page of the ComprasGov API),
+/// flagging the ones already registered locally
+#[utoipa::path(
+    get,
+    path = "/api/admin/catalog/official/catser/items",
+    tag = "Catalog - Sync",
+    params(SearchOfficialCatserParams),
+    responses(
+        (status = 200, description = "Official items", body = OfficialCatserSearchResponse),
+        (status = 500, description = "ComprasGov unavailable"),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn search_official_catser_items(
+    _user: CurrentUser,
+    State(state): State<AppState>,
+    Query(params): Query<SearchOfficialCatserParams>,
+) -> Result<Json<OfficialCatserSearchResponse>, (StatusCode, String)> {
+    let page = params.page.max(1);
+    let response = state
+        .catalog_sync_service
+        .search_official_catser_items(params.item_code, params.class_code, params.group_code, page)
+        .await
+        .map_err(|e| (StatusCode::from(&e), e.to_string()))?;
+    Ok(Json(OfficialCatserSearchResponse {
+        data: response.resultado,
+        total: response.total_registros,
+        page,
+        total_pages: response.total_paginas,
+    }))
+}
+
+/// Imports a CATMAT item from ComprasGov with the group, class and PDM
+/// missing locally, all marked as verified
+#[utoipa::path(
+    post,
+    path = "/api/admin/catalog/official/catmat/items/{code}/import",
+    tag = "Catalog - Sync",
+    params(("code" = String, Path, description = "Official CATMAT item code")),
+    responses(
+        (status = 201, description = "Item imported", body = CatmatImportResult),
+        (status = 400, description = "Invalid code or inactive item"),
+        (status = 404, description = "Item not found in the official catalog"),
+        (status = 409, description = "Item already registered locally"),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn import_catmat_item(
+    _user: CurrentUser,
+    State(state): State<AppState>,
+    Path(code): Path<String>,
+) -> Result<(StatusCode, Json<CatmatImportResult>), (StatusCode, String)> {
+    state
+        .catalog_service
+        .import_catmat_item(&code)
+        .await
+        .map(|r| (StatusCode::CREATED, Json(r)))
+        .map_err(|e| (StatusCode::from(&e), e.to_string()))
+}
+
+/// Imports a CATSER item from ComprasGov with the section, division, group
+/// and class missing locally, all marked as verified
+#[utoipa::path(
+    post,
+    path = "/api/admin/catalog/official/catser/items/{code}/import",
+    tag = "Catalog - Sync",
+    params(("code" = String, Path, description = "Official CATSER item code")),
+    responses(
+        (status = 201, description = "Item imported", body = CatserImportResult),
+        (status = 400, description = "Invalid code or inactive item"),
+        (status = 404, description = "Item not found in the official catalog"),
+        (status = 409, description = "Item already registered locally"),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn import_catser_item(
+    _user: CurrentUser,
+    State(state): State<AppState>,
+    Path(code): Path<String>,
+) -> Result<(StatusCode, Json<CatserImportResult>), (StatusCode, String)> {
+    state
+        .catalog_service
+        .import_catser_item(&code)
+        .await
+        .map(|r| (StatusCode::CREATED, Json(r)))
+        .map_err(|e| (StatusCode::from(&e), e.to_string()))
+}
@@ -0,0 +1,181 @@
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    Json,
+};
+use domain::models::{
+    CatalogKind, CatalogSuggestion, CatmatItemSearchFilter, CatmatItemSearchHit,
+    CatserItemSearchFilter, CatserItemSearchHit, MaterialClassification,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::extractors::current_user::CurrentUser;
+use crate::infra::state::AppState;
+
+// ============================================================================
+// Contracts
+// ============================================================================
+
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct SearchCatmatItemsParams {
+    /// Search text; typos fall back to similarity matching
+    pub q: String,
+    pub material_classification: Option<MaterialClassification>,
+    pub code_ncm: Option<String>,
+    pub is_sustainable: Option<bool>,
+    pub budget_classification_id: Option<Uuid>,
+    pub is_active: Option<bool>,
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+    #[serde(default)]
+    pub offset: i64,
+}
+
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct SearchCatserItemsParams {
+    /// Search text; typos fall back to similarity matching
+    pub q: String,
+    pub code_cpc: Option<String>,
+    pub budget_classification_id: Option<Uuid>,
+    pub is_active: Option<bool>,
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+    #[serde(default)]
+    pub offset: i64,
+}
+
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct AutocompleteParams {
+    /// Text typed so far
+    pub q: String,
+    /// Both catalogs when omitted
+    pub catalog: Option<CatalogKind>,
+    #[serde(default = "default_suggestions")]
+    pub limit: i64,
+}
+
+fn default_limit() -> i64 {
+    50
+}
+
+fn default_suggestions() -> i64 {
+    10
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CatmatItemSearchResponse {
+    pub data: Vec<CatmatItemSearchHit>,
+    pub total: i64,
+    pub limit: i64,
+    pub offset: i64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CatserItemSearchResponse {
+    pub data: Vec<CatserItemSearchHit>,
+    pub total: i64,
+    pub limit: i64,
+    pub offset: i64,
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// Ranked full-text search over CATMAT items, with the matched terms highlighted
+#[utoipa::path(
+    get,
+    path = "/api/admin/catalog/catmat/items/search",
+    tag = "CATMAT - Items",
+    params(SearchCatmatItemsParams),
+    responses(
+        (status = 200, description = "Ranked items", body = CatmatItemSearchResponse),
+        (status = 400, description = "Empty search text"),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn search_catmat_items(
+    _user: CurrentUser,
+    State(state): State<AppState>,
+    Query(params): Query<SearchCatmatItemsParams>,
+) -> Result<Json<CatmatItemSearchResponse>, (StatusCode, String)> {
+    let filter = CatmatItemSearchFilter {
+        material_classification: params.material_classification,
+        code_ncm: params.code_ncm,
+        is_sustainable: params.is_sustainable,
+        budget_classification_id: params.budget_classification_id,
+        is_active: params.is_active,
+    };
+    let (data, total) = state
+        .catalog_search_service
+        .search_catmat_items(&params.q, &filter, params.limit, params.offset)
+        .await
+        .map_err(|e| (StatusCode::from(&e), e.to_string()))?;
+    Ok(Json(CatmatItemSearchResponse {
+        data,
+        total,
+        limit: params.limit,
+        offset: params.offset,
+    }))
+}
+
+/// Ranked full-text search over CATSER items, with the matched terms highlighted
+#[utoipa::path(
+    get,
+    path = "/api/admin/catalog/catser/items/search",
+    tag = "CATSER - Items",
+    params(SearchCatserItemsParams),
+    responses(
+        (status = 200, description = "Ranked items", body = CatserItemSearchResponse),
+        (status = 400, description = "Empty search text"),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn search_catser_items(
+    _user: CurrentUser,
+    State(state): State<AppState>,
+    Query(params): Query<SearchCatserItemsParams>,
+) -> Result<Json<CatserItemSearchResponse>, (StatusCode, String)> {
+    let filter = CatserItemSearchFilter {
+        code_cpc: params.code_cpc,
+        budget_classification_id: params.budget_classification_id,
+        is_active: params.is_active,
+    };
+    let (data, total) = state
+        .catalog_search_service
+        .search_catser_items(&params.q, &filter, params.limit, params.offset)
+        .await
+        .map_err(|e| (StatusCode::from(&e), e.to_string()))?;
+    Ok(Json(CatserItemSearchResponse {
+        data,
+        total,
+        limit: params.limit,
+        offset: params.offset,
+    }))
+}
+
+/// Suggestions of active CATMAT and CATSER items while typing
+#[utoipa::path(
+    get,
+    path = "/api/admin/catalog/autocomplete",
+    tag = "Catalog - Search",
+    params(AutocompleteParams),
+    responses(
+        (status = 200, description = "Suggestions", body = Vec<CatalogSuggestion>),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn autocomplete(
+    _user: CurrentUser,
+    State(state): State<AppState>,
+    Query(params): Query<AutocompleteParams>,
+) -> Result<Json<Vec<CatalogSuggestion>>, (StatusCode, String)> {
+    state
+        .catalog_search_service
+        .suggest(&params.q, params.catalog, params.limit)
+        .await
+        .map(Json)
+        .map_err(|e| (StatusCode::from(&e), e.to_string()))
+}
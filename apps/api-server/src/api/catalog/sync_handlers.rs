@@ -0,0 +1,157 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use core_services::request_context;
+use domain::models::{CatalogKind, CatalogSyncRunDto, CatalogSyncScope};
+use serde::{Deserialize, Serialize};
+use tracing::error;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::extractors::current_user::CurrentUser;
+use crate::infra::state::AppState;
+
+// ============================================================================
+// Contracts
+// ============================================================================
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct StartCatalogSyncRequest {
+    pub catalog: CatalogKind,
+    pub scope: CatalogSyncScope,
+    /// Official code of the group or PDM (omitted for `FULL`)
+    pub code: Option<String>,
+    /// Computes the report without writing to the catalog
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct ListCatalogSyncRunsParams {
+    pub catalog: Option<CatalogKind>,
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+    #[serde(default)]
+    pub offset: i64,
+}
+
+fn default_limit() -> i64 {
+    50
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CatalogSyncRunsListResponse {
+    pub data: Vec<CatalogSyncRunDto>,
+    pub total: i64,
+    pub limit: i64,
+    pub offset: i64,
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// Starts a synchronization of the catalog with ComprasGov. The run executes
+/// in the background; poll `/sync/runs/{id}` for the report.
+#[utoipa::path(
+    post,
+    path = "/api/admin/catalog/sync",
+    tag = "Catalog - Sync",
+    request_body = StartCatalogSyncRequest,
+    responses(
+        (status = 202, description = "Sync started", body = CatalogSyncRunDto),
+        (status = 400, description = "Invalid scope or code"),
+        (status = 409, description = "A sync of the same catalog is already running"),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn start_sync(
+    user: CurrentUser,
+    State(state): State<AppState>,
+    Json(payload): Json<StartCatalogSyncRequest>,
+) -> Result<(StatusCode, Json<CatalogSyncRunDto>), (StatusCode, String)> {
+    let service = state.catalog_sync_service.clone();
+    let run = service
+        .start(
+            payload.catalog,
+            payload.scope,
+            payload.code.as_deref(),
+            payload.dry_run,
+            Some(user.id),
+        )
+        .await
+        .map_err(|e| (StatusCode::from(&e), e.to_string()))?;
+
+    // Keeps the actor of the request on the audited writes of the run
+    let job_run = run.clone();
+    let job = async move {
+        if let Err(e) = service.execute(job_run).await {
+            error!(
+                "Falha ao registrar o resultado da sincronização do catálogo: {}",
+                e
+            );
+        }
+    };
+    match request_context::current() {
+        Some(ctx) => tokio::spawn(request_context::scope(ctx, job)),
+        None => tokio::spawn(job),
+    };
+
+    Ok((StatusCode::ACCEPTED, Json(run)))
+}
+
+/// Lists sync runs, most recent first (without the report)
+#[utoipa::path(
+    get,
+    path = "/api/admin/catalog/sync/runs",
+    tag = "Catalog - Sync",
+    params(ListCatalogSyncRunsParams),
+    responses(
+        (status = 200, description = "Sync runs", body = CatalogSyncRunsListResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_sync_runs(
+    _user: CurrentUser,
+    State(state): State<AppState>,
+    Query(params): Query<ListCatalogSyncRunsParams>,
+) -> Result<Json<CatalogSyncRunsListResponse>, (StatusCode, String)> {
+    let (data, total) = state
+        .catalog_sync_service
+        .list_runs(params.catalog, params.limit, params.offset)
+        .await
+        .map_err(|e| (StatusCode::from(&e), e.to_string()))?;
+    Ok(Json(CatalogSyncRunsListResponse {
+        data,
+        total,
+        limit: params.limit,
+        offset: params.offset,
+    }))
+}
+
+/// Gets a sync run with its report of added, changed and deactivated entries
+#[utoipa::path(
+    get,
+    path = "/api/admin/catalog/sync/runs/{id}",
+    tag = "Catalog - Sync",
+    params(("id" = Uuid, Path, description = "Sync run ID")),
+    responses(
+        (status = 200, description = "Sync run", body = CatalogSyncRunDto),
+        (status = 404, description = "Not found"),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_sync_run(
+    _user: CurrentUser,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<CatalogSyncRunDto>, (StatusCode, String)> {
+    state
+        .catalog_sync_service
+        .get_run(id)
+        .await
+        .map(Json)
+        .map_err(|e| (StatusCode::from(&e), e.to_string()))
+}
@@ -115,7 +115,7 @@ pub async fn resend_verification(
     payload.validate().map_err(AppError::Validation)?;
 
     let verification_repo = EmailVerificationRepository::new(&state.db_pool_auth);
-    let user_repo = UserRepository::new(state.db_pool_auth.clone(), state.field_encryption_key);
+    let user_repo = UserRepository::new(state.db_pool_auth.clone(), state.field_keyring.clone());
 
     // 1. Encontrar usuário
     let user = match user_repo.find_by_email(&payload.email).await? {
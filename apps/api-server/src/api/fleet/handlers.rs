@@ -5,10 +5,11 @@ use crate::state::AppState;
 use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
-    Json,
+    Extension, Json,
 };
 use application::errors::ServiceError;
 use domain::models::vehicle::VehicleStatus;
+use domain::models::UnitScope;
 use domain::models::odometer::{
     CreateOdometerReadingPayload, ResolveQuarantinePayload, StatusLeitura,
 };
@@ -539,11 +540,12 @@ pub async fn create_vehicle(
 pub async fn get_vehicle(
     _user: CurrentUser,
     State(state): State<AppState>,
+    Extension(scope): Extension<UnitScope>,
     Path(id): Path<Uuid>,
 ) -> Result<Json<VehicleWithDetailsDto>, (StatusCode, String)> {
     state
         .vehicle_service
-        .get_vehicle(id)
+        .get_vehicle_in_scope(id, &scope)
         .await
         .map(Json)
         .map_err(|e| (StatusCode::from(&e), e.to_string()))
@@ -552,6 +554,7 @@ pub async fn get_vehicle(
 pub async fn list_vehicles(
     _user: CurrentUser,
     State(state): State<AppState>,
+    Extension(scope): Extension<UnitScope>,
     Query(query): Query<VehicleListQuery>,
 ) -> Result<Json<VehiclesListResponse>, (StatusCode, String)> {
     let (vehicles, total) = state
@@ -565,6 +568,7 @@ pub async fn list_vehicles(
             query.fuel_type_id,
             query.department_id,
             query.include_deleted,
+            &scope,
         )
         .await
         .map_err(|e| (StatusCode::from(&e), e.to_string()))?;
@@ -579,12 +583,13 @@ pub async fn list_vehicles(
 pub async fn update_vehicle(
     user: CurrentUser,
     State(state): State<AppState>,
+    Extension(scope): Extension<UnitScope>,
     Path(id): Path<Uuid>,
     Json(payload): Json<UpdateVehiclePayload>,
 ) -> Result<Json<VehicleWithDetailsDto>, (StatusCode, String)> {
     state
         .vehicle_service
-        .update_vehicle(id, payload, Some(user.id))
+        .update_vehicle(id, payload, Some(user.id), &scope)
         .await
         .map(Json)
         .map_err(|e| (StatusCode::from(&e), e.to_string()))
@@ -593,11 +598,12 @@ pub async fn update_vehicle(
 pub async fn delete_vehicle(
     user: CurrentUser,
     State(state): State<AppState>,
+    Extension(scope): Extension<UnitScope>,
     Path(id): Path<Uuid>,
 ) -> Result<StatusCode, (StatusCode, String)> {
     state
         .vehicle_service
-        .delete_vehicle(id, Some(user.id))
+        .delete_vehicle(id, Some(user.id), &scope)
         .await
         .map(|_| StatusCode::NO_CONTENT)
         .map_err(|e| (StatusCode::from(&e), e.to_string()))
@@ -606,12 +612,13 @@ pub async fn delete_vehicle(
 pub async fn change_vehicle_status(
     user: CurrentUser,
     State(state): State<AppState>,
+    Extension(scope): Extension<UnitScope>,
     Path(id): Path<Uuid>,
     Json(payload): Json<ChangeVehicleStatusPayload>,
 ) -> Result<Json<VehicleWithDetailsDto>, (StatusCode, String)> {
     state
         .vehicle_service
-        .change_vehicle_status(id, payload, Some(user.id))
+        .change_vehicle_status(id, payload, Some(user.id), &scope)
         .await
         .map(Json)
         .map_err(|e| (StatusCode::from(&e), e.to_string()))
@@ -620,11 +627,12 @@ pub async fn change_vehicle_status(
 pub async fn change_operational_status(
     user: CurrentUser,
     State(state): State<AppState>,
+    Extension(scope): Extension<UnitScope>,
     Path(id): Path<Uuid>,
     Json(payload): Json<ChangeOperationalStatusPayload>,
 ) -> axum::response::Response {
     use axum::response::IntoResponse;
-    match state.vehicle_service.change_operational_status(id, payload, Some(user.id)).await {
+    match state.vehicle_service.change_operational_status(id, payload, Some(user.id), &scope).await {
         Ok(vehicle) => (StatusCode::OK, Json(vehicle)).into_response(),
         Err(ServiceError::OptimisticLockConflict(msg)) => (
             StatusCode::CONFLICT,
@@ -653,11 +661,12 @@ pub async fn change_operational_status(
 pub async fn search_vehicles(
     _user: CurrentUser,
     State(state): State<AppState>,
+    Extension(scope): Extension<UnitScope>,
     Query(query): Query<SearchQuery>,
 ) -> Result<Json<VehicleSearchResponse>, (StatusCode, String)> {
     let vehicles = state
         .vehicle_service
-        .search_vehicles(&query.q, query.limit)
+        .search_vehicles(&query.q, query.limit, &scope)
         .await
         .map_err(|e| (StatusCode::from(&e), e.to_string()))?;
     Ok(Json(VehicleSearchResponse { vehicles }))
@@ -666,11 +675,12 @@ pub async fn search_vehicles(
 pub async fn get_vehicle_status_history(
     _user: CurrentUser,
     State(state): State<AppState>,
+    Extension(scope): Extension<UnitScope>,
     Path(id): Path<Uuid>,
 ) -> Result<Json<Vec<VehicleStatusHistoryDto>>, (StatusCode, String)> {
     state
         .vehicle_service
-        .get_vehicle_status_history(id)
+        .get_vehicle_status_history(id, &scope)
         .await
         .map(Json)
         .map_err(|e| (StatusCode::from(&e), e.to_string()))
@@ -862,11 +872,12 @@ pub async fn resolve_odometer_quarantine(
 pub async fn register_department_transfer(
     user: CurrentUser,
     State(state): State<AppState>,
+    Extension(scope): Extension<UnitScope>,
     Path(id): Path<Uuid>,
     Json(payload): Json<CreateVehicleDepartmentTransferPayload>,
 ) -> axum::response::Response {
     use axum::response::IntoResponse;
-    match state.asset_management_service.transfer_department(id, payload, Some(user.id)).await {
+    match state.asset_management_service.transfer_department(id, payload, Some(user.id), &scope).await {
         Ok(t) => (StatusCode::CREATED, Json(t)).into_response(),
         Err(e) => (StatusCode::from(&e), e.to_string()).into_response(),
     }
@@ -875,11 +886,12 @@ pub async fn register_department_transfer(
 pub async fn list_department_transfers(
     _user: CurrentUser,
     State(state): State<AppState>,
+    Extension(scope): Extension<UnitScope>,
     Path(id): Path<Uuid>,
 ) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
     let transfers = state
         .asset_management_service
-        .list_transfers(id)
+        .list_transfers(id, &scope)
         .await
         .map_err(|e| (StatusCode::from(&e), e.to_string()))?;
     Ok(Json(serde_json::json!({ "data": transfers })))
@@ -2,7 +2,12 @@ use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
 pub use domain::models::fueling::{
-    CreateFuelingPayload, FuelingDto, FuelingWithDetailsDto, UpdateFuelingPayload,
+    CreateFuelingPayload, FuelingAnomaly, FuelingAnomalyKind, FuelingDto,
+    FuelingReviewStatus, FuelingWithDetailsDto, ReviewFuelingPayload, UpdateFuelingPayload,
+};
+pub use domain::models::fuel_card::{
+    FuelCardImportQuery, FuelCardImportReportDto, FuelCardMatchStatus,
+    FuelCardTransactionDto, FuelCardTransactionFilters,
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
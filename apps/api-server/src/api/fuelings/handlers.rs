@@ -25,6 +25,15 @@ fn default_limit() -> i64 {
     50
 }
 
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct FuelingReviewQueueQuery {
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+    #[serde(default)]
+    pub offset: i64,
+    pub vehicle_id: Option<Uuid>,
+}
+
 pub async fn create_fueling(
     user: CurrentUser,
     State(state): State<AppState>,
@@ -103,3 +112,80 @@ pub async fn list_fuelings(
         })
         .map_err(|e| (StatusCode::from(&e), e.to_string()))
 }
+
+pub async fn list_review_queue(
+    _user: CurrentUser,
+    State(state): State<AppState>,
+    Query(query): Query<FuelingReviewQueueQuery>,
+) -> Result<Json<FuelingsListResponse>, (StatusCode, String)> {
+    state
+        .fueling_service
+        .list_review_queue(query.limit, query.offset, query.vehicle_id)
+        .await
+        .map(|(data, total)| {
+            Json(FuelingsListResponse {
+                data,
+                total,
+                limit: query.limit,
+                offset: query.offset,
+            })
+        })
+        .map_err(|e| (StatusCode::from(&e), e.to_string()))
+}
+
+pub async fn review_fueling(
+    user: CurrentUser,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<ReviewFuelingPayload>,
+) -> Result<Json<FuelingWithDetailsDto>, (StatusCode, String)> {
+    state
+        .fueling_service
+        .review_fueling(id, payload, user.id)
+        .await
+        .map(Json)
+        .map_err(|e| (StatusCode::from(&e), e.to_string()))
+}
+
+// ── Fuel card import and reconciliation ──────────────────────────────────
+
+pub async fn import_fuel_card(
+    user: CurrentUser,
+    State(state): State<AppState>,
+    Query(query): Query<FuelCardImportQuery>,
+    body: String,
+) -> Result<(StatusCode, Json<FuelCardImportReportDto>), (StatusCode, String)> {
+    state
+        .fuel_card_service
+        .import_csv(&query.operator, &body, Some(user.id))
+        .await
+        .map(|r| (StatusCode::CREATED, Json(r)))
+        .map_err(|e| (StatusCode::from(&e), e.to_string()))
+}
+
+pub async fn get_fuel_card_import(
+    _user: CurrentUser,
+    State(state): State<AppState>,
+    Path(job_id): Path<Uuid>,
+) -> Result<Json<FuelCardImportReportDto>, (StatusCode, String)> {
+    state
+        .fuel_card_service
+        .get_report(job_id)
+        .await
+        .map(Json)
+        .map_err(|e| (StatusCode::from(&e), e.to_string()))
+}
+
+pub async fn list_fuel_card_transactions(
+    _user: CurrentUser,
+    State(state): State<AppState>,
+    Path(job_id): Path<Uuid>,
+    Query(filters): Query<FuelCardTransactionFilters>,
+) -> Result<Json<Vec<FuelCardTransactionDto>>, (StatusCode, String)> {
+    state
+        .fuel_card_service
+        .list_transactions(job_id, filters)
+        .await
+        .map(Json)
+        .map_err(|e| (StatusCode::from(&e), e.to_string()))
+}
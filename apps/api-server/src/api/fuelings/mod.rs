@@ -3,14 +3,22 @@ pub mod handlers;
 
 use crate::infra::state::AppState;
 use axum::{
-    routing::get,
+    routing::{get, post, put},
     Router,
 };
 
 pub fn router() -> Router<AppState> {
     Router::new()
         .route("/", get(handlers::list_fuelings).post(handlers::create_fueling))
+        .route("/review-queue", get(handlers::list_review_queue))
+        .route("/card-imports", post(handlers::import_fuel_card))
+        .route("/card-imports/{job_id}", get(handlers::get_fuel_card_import))
+        .route(
+            "/card-imports/{job_id}/transactions",
+            get(handlers::list_fuel_card_transactions),
+        )
         .route("/{id}", get(handlers::get_fueling)
             .put(handlers::update_fueling)
             .delete(handlers::delete_fueling))
+        .route("/{id}/review", put(handlers::review_fueling))
 }
@@ -1,9 +1,10 @@
 pub mod contracts;
 pub mod handlers;
+pub mod passkey_handlers;
 
 use crate::infra::state::AppState;
 use axum::{
-    routing::{get, post},
+    routing::{get, patch, post},
     Router,
 };
 
@@ -12,7 +13,10 @@ pub use contracts::*;
 /// Router para rota de verificação (parte do login)
 pub fn router() -> Router<AppState> {
     // CORREÇÃO: handlers::verify -> handlers::verify_login
-    Router::new().route("/auth/mfa/verify", post(handlers::verify_login))
+    Router::new()
+        .route("/auth/mfa/verify", post(handlers::verify_login))
+        .route("/auth/mfa/passkey/start", post(passkey_handlers::start_mfa_passkey))
+        .route("/auth/mfa/passkey/verify", post(passkey_handlers::verify_mfa_passkey))
 }
 
 /// Router para rotas protegidas (gerenciamento)
@@ -29,4 +33,21 @@ pub fn protected_router() -> Router<AppState> {
         )
         // CORREÇÃO: handlers::status -> handlers::get_status
         .route("/auth/mfa/status", get(handlers::get_status))
+        // Passkeys / chaves de segurança (WebAuthn)
+        .route(
+            "/auth/webauthn/register/start",
+            post(passkey_handlers::start_registration),
+        )
+        .route(
+            "/auth/webauthn/register/finish",
+            post(passkey_handlers::finish_registration),
+        )
+        .route(
+            "/auth/webauthn/credentials",
+            get(passkey_handlers::list_credentials),
+        )
+        .route(
+            "/auth/webauthn/credentials/{id}",
+            patch(passkey_handlers::rename_credential).delete(passkey_handlers::delete_credential),
+        )
 }
@@ -0,0 +1,223 @@
+//! Passkey / Security Key Handlers
+//!
+//! Cadastro e gerenciamento de credenciais WebAuthn do usuário logado e
+//! uso da passkey como segundo fator no desafio MFA do login.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use domain::models::{
+    FinishPasskeyCeremonyPayload, MfaVerifyResponse, PasskeyMfaFinishPayload,
+    PasskeyMfaStartPayload, RenamePasskeyPayload, StartPasskeyRegistrationPayload,
+    WebauthnChallengeDto, WebauthnCredentialDto,
+};
+use tracing::instrument;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::extractors::current_user::CurrentUser;
+use crate::infra::{errors::AppError, state::AppState};
+
+// --- Gerenciamento (rotas protegidas) ---
+
+/// POST /api/v1/auth/webauthn/register/start
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/webauthn/register/start",
+    tag = "MFA",
+    request_body = StartPasskeyRegistrationPayload,
+    responses(
+        (status = 200, description = "Options for navigator.credentials.create()", body = WebauthnChallengeDto),
+        (status = 400, description = "Invalid name"),
+        (status = 401, description = "Not authenticated"),
+        (status = 409, description = "Name already in use")
+    )
+)]
+#[instrument(skip_all)]
+pub async fn start_registration(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Json(payload): Json<StartPasskeyRegistrationPayload>,
+) -> Result<Json<WebauthnChallengeDto>, AppError> {
+    payload.validate().map_err(AppError::Validation)?;
+
+    let challenge = state
+        .webauthn_service
+        .start_registration(current_user.id, &payload.name)
+        .await?;
+
+    Ok(Json(challenge))
+}
+
+/// POST /api/v1/auth/webauthn/register/finish
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/webauthn/register/finish",
+    tag = "MFA",
+    request_body = FinishPasskeyCeremonyPayload,
+    responses(
+        (status = 201, description = "Credential registered", body = WebauthnCredentialDto),
+        (status = 400, description = "Invalid or expired challenge / attestation"),
+        (status = 401, description = "Not authenticated"),
+        (status = 409, description = "Credential already registered")
+    )
+)]
+#[instrument(skip_all)]
+pub async fn finish_registration(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Json(payload): Json<FinishPasskeyCeremonyPayload>,
+) -> Result<(StatusCode, Json<WebauthnCredentialDto>), AppError> {
+    let credential = state
+        .webauthn_service
+        .finish_registration(current_user.id, payload)
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(credential)))
+}
+
+/// GET /api/v1/auth/webauthn/credentials
+#[utoipa::path(
+    get,
+    path = "/api/v1/auth/webauthn/credentials",
+    tag = "MFA",
+    responses(
+        (status = 200, description = "Registered credentials", body = Vec<WebauthnCredentialDto>),
+        (status = 401, description = "Not authenticated")
+    )
+)]
+#[instrument(skip_all)]
+pub async fn list_credentials(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+) -> Result<Json<Vec<WebauthnCredentialDto>>, AppError> {
+    let credentials = state
+        .webauthn_service
+        .list_credentials(current_user.id)
+        .await?;
+
+    Ok(Json(credentials))
+}
+
+/// PATCH /api/v1/auth/webauthn/credentials/{id}
+#[utoipa::path(
+    patch,
+    path = "/api/v1/auth/webauthn/credentials/{id}",
+    tag = "MFA",
+    params(("id" = Uuid, Path, description = "Credential ID")),
+    request_body = RenamePasskeyPayload,
+    responses(
+        (status = 200, description = "Credential renamed", body = WebauthnCredentialDto),
+        (status = 404, description = "Credential not found"),
+        (status = 409, description = "Name already in use")
+    )
+)]
+#[instrument(skip_all)]
+pub async fn rename_credential(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<RenamePasskeyPayload>,
+) -> Result<Json<WebauthnCredentialDto>, AppError> {
+    payload.validate().map_err(AppError::Validation)?;
+
+    let credential = state
+        .webauthn_service
+        .rename_credential(current_user.id, id, &payload.name)
+        .await?;
+
+    Ok(Json(credential))
+}
+
+/// DELETE /api/v1/auth/webauthn/credentials/{id}
+#[utoipa::path(
+    delete,
+    path = "/api/v1/auth/webauthn/credentials/{id}",
+    tag = "MFA",
+    params(("id" = Uuid, Path, description = "Credential ID")),
+    responses(
+        (status = 204, description = "Credential removed"),
+        (status = 404, description = "Credential not found")
+    )
+)]
+#[instrument(skip_all)]
+pub async fn delete_credential(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, AppError> {
+    state
+        .webauthn_service
+        .delete_credential(current_user.id, id)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// --- Segundo fator no login (rotas públicas) ---
+
+/// POST /api/v1/auth/mfa/passkey/start
+///
+/// Gera o desafio WebAuthn para o `mfa_token` devolvido pelo login.
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/mfa/passkey/start",
+    tag = "MFA",
+    request_body = PasskeyMfaStartPayload,
+    responses(
+        (status = 200, description = "Options for navigator.credentials.get()", body = WebauthnChallengeDto),
+        (status = 400, description = "No passkey registered"),
+        (status = 401, description = "Invalid or expired MFA token")
+    )
+)]
+#[instrument(skip_all)]
+pub async fn start_mfa_passkey(
+    State(state): State<AppState>,
+    Json(payload): Json<PasskeyMfaStartPayload>,
+) -> Result<Json<WebauthnChallengeDto>, AppError> {
+    payload.validate().map_err(AppError::Validation)?;
+
+    let challenge = state
+        .mfa_service
+        .start_passkey_verification(&payload.mfa_token)
+        .await?;
+
+    Ok(Json(challenge))
+}
+
+/// POST /api/v1/auth/mfa/passkey/verify
+///
+/// Conclui o login com a assinatura da passkey no lugar do código TOTP.
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/mfa/passkey/verify",
+    tag = "MFA",
+    request_body = PasskeyMfaFinishPayload,
+    responses(
+        (status = 200, description = "Login completed (access and refresh tokens)"),
+        (status = 400, description = "Invalid or expired challenge"),
+        (status = 401, description = "Assertion rejected or invalid MFA token")
+    )
+)]
+#[instrument(skip_all)]
+pub async fn verify_mfa_passkey(
+    State(state): State<AppState>,
+    Json(payload): Json<PasskeyMfaFinishPayload>,
+) -> Result<Json<MfaVerifyResponse>, AppError> {
+    payload.validate().map_err(AppError::Validation)?;
+
+    let response = state
+        .mfa_service
+        .verify_login_with_passkey(
+            &payload.mfa_token,
+            FinishPasskeyCeremonyPayload {
+                challenge_id: payload.challenge_id,
+                credential: payload.credential,
+            },
+        )
+        .await?;
+
+    Ok(Json(response))
+}
@@ -11,6 +11,7 @@ use axum::{
     http::StatusCode,
     Json,
 };
+use chrono::NaiveDate;
 use domain::models::organizational::ActivityArea;
 use serde::Deserialize;
 use serde_json::json;
@@ -40,6 +41,18 @@ pub struct OrganizationsListQuery {
     pub is_active: Option<bool>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct UnitTreeQuery {
+    pub organization_id: Option<Uuid>,
+    /// Structure in effect on this date (current one when omitted)
+    pub as_of: Option<NaiveDate>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AsOfQuery {
+    pub as_of: Option<NaiveDate>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct UnitCategoriesListQuery {
     #[serde(default = "default_limit")]
@@ -763,6 +776,7 @@ pub async fn list_organizational_units(
     tag = "Organization - Organizational Units",
     params(
         ("organization_id" = Option<Uuid>, Query, description = "Filter by organization"),
+        ("as_of" = Option<NaiveDate>, Query, description = "Structure in effect on this date"),
     ),
     responses(
         (status = 200, description = "Tree retrieved successfully", body = Vec<OrganizationalUnitTreeNode>),
@@ -771,13 +785,12 @@ pub async fn list_organizational_units(
 pub async fn get_organizational_units_tree(
     _user: CurrentUser,
     State(state): State<AppState>,
-    Query(_query): Query<OrganizationsListQuery>,
+    Query(query): Query<UnitTreeQuery>,
 ) -> Result<Json<Vec<OrganizationalUnitTreeNode>>, (StatusCode, String)> {
     let service = get_unit_service(&state);
 
-    // Reusing organization_id from the query (if provided)
     service
-        .get_tree(None)
+        .get_tree(query.organization_id, query.as_of)
         .await
         .map(Json)
         .map_err(|e| (e.status_code(), e.to_string()))
@@ -815,6 +828,7 @@ pub async fn get_organizational_unit(
     tag = "Organization - Organizational Units",
     params(
         ("id" = Uuid, Path, description = "Parent unit ID"),
+        ("as_of" = Option<NaiveDate>, Query, description = "Structure in effect on this date"),
     ),
     responses(
         (status = 200, description = "Children retrieved successfully", body = Vec<OrganizationalUnitDto>),
@@ -824,11 +838,12 @@ pub async fn get_organizational_unit_children(
     _user: CurrentUser,
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
+    Query(query): Query<AsOfQuery>,
 ) -> Result<Json<Vec<OrganizationalUnitDto>>, (StatusCode, String)> {
     let service = get_unit_service(&state);
 
     service
-        .get_children(id)
+        .get_children(id, query.as_of)
         .await
         .map(Json)
         .map_err(|e| (e.status_code(), e.to_string()))
@@ -840,20 +855,23 @@ pub async fn get_organizational_unit_children(
     tag = "Organization - Organizational Units",
     params(
         ("id" = Uuid, Path, description = "Unit ID"),
+        ("as_of" = Option<NaiveDate>, Query, description = "Structure in effect on this date"),
     ),
     responses(
         (status = 200, description = "Path to root retrieved successfully", body = Vec<OrganizationalUnitDto>),
+        (status = 404, description = "Unit not found (or did not exist on the date)"),
     )
 )]
 pub async fn get_organizational_unit_path(
     _user: CurrentUser,
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
+    Query(query): Query<AsOfQuery>,
 ) -> Result<Json<Vec<OrganizationalUnitDto>>, (StatusCode, String)> {
     let service = get_unit_service(&state);
 
     service
-        .get_path_to_root(id)
+        .get_path_to_root(id, query.as_of)
         .await
         .map(Json)
         .map_err(|e| (e.status_code(), e.to_string()))
@@ -1091,6 +1109,7 @@ pub async fn sync_all_from_db(
         "created": summary.created,
         "updated": summary.updated,
         "failed": summary.failed,
+        "pending_review": summary.pending_review,
         "errors": summary.errors
     })))
 }
@@ -1,5 +1,7 @@
 pub mod contracts;
 pub mod handlers;
+pub mod preview_handlers;
+pub mod restructuring_handlers;
 pub mod stats_handlers;
 pub mod sync_handlers;
 
@@ -73,7 +75,17 @@ pub fn router() -> Router<AppState> {
         .route("/{id}/children", get(handlers::get_organizational_unit_children))
         .route("/{id}/path", get(handlers::get_organizational_unit_path))
         .route("/{id}/deactivate", post(handlers::deactivate_organizational_unit))
-        .route("/{id}/activate", post(handlers::activate_organizational_unit));
+        .route("/{id}/activate", post(handlers::activate_organizational_unit))
+        .route("/{id}/lineage", get(restructuring_handlers::get_unit_lineage));
+
+    // Unit restructurings (merge/split with reference migration)
+    let restructurings_router = Router::new()
+        .route(
+            "/",
+            get(restructuring_handlers::list_unit_restructurings)
+                .post(restructuring_handlers::create_unit_restructuring),
+        )
+        .route("/{id}", get(restructuring_handlers::get_unit_restructuring));
 
     // SIORG Sync routes (immediate operations)
     let sync_router = Router::new()
@@ -113,6 +125,16 @@ pub fn router() -> Router<AppState> {
             get(sync_handlers::get_entity_history),
         );
 
+    // Change preview routes (dry-run + selective approval)
+    let previews_router = Router::new()
+        .route(
+            "/",
+            get(preview_handlers::list_siorg_previews).post(preview_handlers::create_siorg_preview),
+        )
+        .route("/{id}", get(preview_handlers::get_siorg_preview))
+        .route("/{id}/apply", post(preview_handlers::apply_siorg_preview))
+        .route("/{id}/discard", post(preview_handlers::discard_siorg_preview));
+
     // Statistics routes
     let stats_router = Router::new()
         .route("/detailed", get(stats_handlers::get_detailed_stats))
@@ -163,6 +185,7 @@ pub fn router() -> Router<AppState> {
         .nest("/unit-categories", unit_categories_router)
         .nest("/unit-types", unit_types_router)
         .nest("/units", units_router)
+        .nest("/restructurings", restructurings_router)
         .nest("/natureza-juridica", natureza_juridica_router)
         .nest("/poder", poder_router)
         .nest("/esfera", esfera_router)
@@ -170,5 +193,6 @@ pub fn router() -> Router<AppState> {
         .nest("/sync/queue", queue_router)
         .nest("/sync/conflicts", conflicts_router)
         .nest("/sync/history", history_router)
+        .nest("/sync/previews", previews_router)
         .nest("/sync/stats", stats_router)
 }
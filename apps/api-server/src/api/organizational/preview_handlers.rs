@@ -0,0 +1,191 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use domain::models::{ApplySiorgPreviewPayload, SiorgChangePreviewDto, SiorgPreviewStatus};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::extractors::current_user::CurrentUser;
+use crate::infra::state::AppState;
+
+// ============================================================================
+// Contracts
+// ============================================================================
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateSiorgPreviewRequest {
+    /// UUID local da organização
+    pub organization_id: Uuid,
+}
+
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct ListSiorgPreviewsParams {
+    pub organization_id: Option<Uuid>,
+    pub status: Option<SiorgPreviewStatus>,
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+    #[serde(default)]
+    pub offset: i64,
+}
+
+fn default_limit() -> i64 {
+    50
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SiorgPreviewsListResponse {
+    pub data: Vec<SiorgChangePreviewDto>,
+    pub total: i64,
+    pub limit: i64,
+    pub offset: i64,
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// Calcula as alterações publicadas pelo SIORG desde a última sincronização,
+/// sem gravar nada nas unidades. Substitui a prévia pendente da organização.
+#[utoipa::path(
+    post,
+    path = "/api/admin/organizational/sync/previews",
+    tag = "Organization - SIORG Sync",
+    request_body = CreateSiorgPreviewRequest,
+    responses(
+        (status = 201, description = "Prévia gerada", body = SiorgChangePreviewDto),
+        (status = 400, description = "Organização sem versão sincronizada ou já atualizada"),
+        (status = 404, description = "Organização não encontrada"),
+        (status = 502, description = "Falha na API do SIORG"),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn create_siorg_preview(
+    user: CurrentUser,
+    State(state): State<AppState>,
+    Json(payload): Json<CreateSiorgPreviewRequest>,
+) -> Result<(StatusCode, Json<SiorgChangePreviewDto>), (StatusCode, String)> {
+    state
+        .siorg_sync_service
+        .preview_changes(payload.organization_id, Some(user.id))
+        .await
+        .map(|preview| (StatusCode::CREATED, Json(preview)))
+        .map_err(|e| (StatusCode::from(&e), e.to_string()))
+}
+
+/// Prévias de alterações do SIORG, sem as entradas, mais recentes primeiro
+#[utoipa::path(
+    get,
+    path = "/api/admin/organizational/sync/previews",
+    tag = "Organization - SIORG Sync",
+    params(ListSiorgPreviewsParams),
+    responses(
+        (status = 200, description = "Prévias", body = SiorgPreviewsListResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_siorg_previews(
+    _user: CurrentUser,
+    State(state): State<AppState>,
+    Query(params): Query<ListSiorgPreviewsParams>,
+) -> Result<Json<SiorgPreviewsListResponse>, (StatusCode, String)> {
+    let (data, total) = state
+        .siorg_sync_service
+        .list_previews(
+            params.organization_id,
+            params.status,
+            params.limit,
+            params.offset,
+        )
+        .await
+        .map_err(|e| (StatusCode::from(&e), e.to_string()))?;
+    Ok(Json(SiorgPreviewsListResponse {
+        data,
+        total,
+        limit: params.limit,
+        offset: params.offset,
+    }))
+}
+
+/// Prévia com a diferença de cada unidade e as referências afetadas
+#[utoipa::path(
+    get,
+    path = "/api/admin/organizational/sync/previews/{id}",
+    tag = "Organization - SIORG Sync",
+    params(("id" = Uuid, Path, description = "ID da prévia")),
+    responses(
+        (status = 200, description = "Prévia", body = SiorgChangePreviewDto),
+        (status = 404, description = "Prévia não encontrada"),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_siorg_preview(
+    _user: CurrentUser,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<SiorgChangePreviewDto>, (StatusCode, String)> {
+    state
+        .siorg_sync_service
+        .get_preview(id)
+        .await
+        .map(Json)
+        .map_err(|e| (StatusCode::from(&e), e.to_string()))
+}
+
+/// Aplica as alterações aprovadas (todas ou as unidades informadas) e
+/// rejeita as demais
+#[utoipa::path(
+    post,
+    path = "/api/admin/organizational/sync/previews/{id}/apply",
+    tag = "Organization - SIORG Sync",
+    params(("id" = Uuid, Path, description = "ID da prévia")),
+    request_body = ApplySiorgPreviewPayload,
+    responses(
+        (status = 200, description = "Prévia aplicada", body = SiorgChangePreviewDto),
+        (status = 400, description = "Unidade fora da prévia ou dependente de criação rejeitada"),
+        (status = 404, description = "Prévia não encontrada"),
+        (status = 409, description = "Prévia já decidida ou estrutura sincronizada depois dela"),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn apply_siorg_preview(
+    user: CurrentUser,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<ApplySiorgPreviewPayload>,
+) -> Result<Json<SiorgChangePreviewDto>, (StatusCode, String)> {
+    state
+        .siorg_sync_service
+        .apply_preview(id, payload, Some(user.id))
+        .await
+        .map(Json)
+        .map_err(|e| (StatusCode::from(&e), e.to_string()))
+}
+
+/// Descarta a prévia sem aplicar nada
+#[utoipa::path(
+    post,
+    path = "/api/admin/organizational/sync/previews/{id}/discard",
+    tag = "Organization - SIORG Sync",
+    params(("id" = Uuid, Path, description = "ID da prévia")),
+    responses(
+        (status = 200, description = "Prévia descartada", body = SiorgChangePreviewDto),
+        (status = 404, description = "Prévia não encontrada"),
+        (status = 409, description = "Prévia já decidida"),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn discard_siorg_preview(
+    user: CurrentUser,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<SiorgChangePreviewDto>, (StatusCode, String)> {
+    state
+        .siorg_sync_service
+        .discard_preview(id, Some(user.id))
+        .await
+        .map(Json)
+        .map_err(|e| (StatusCode::from(&e), e.to_string()))
+}
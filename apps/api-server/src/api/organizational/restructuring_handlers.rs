@@ -0,0 +1,159 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use casbin::CoreApi;
+use domain::models::{CreateUnitRestructuringPayload, UnitLineageResponse, UnitRestructuringDto};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::extractors::current_user::CurrentUser;
+use crate::infra::state::AppState;
+
+// ============================================================================
+// Contracts
+// ============================================================================
+
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct ListUnitRestructuringsParams {
+    pub organization_id: Option<Uuid>,
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+    #[serde(default)]
+    pub offset: i64,
+}
+
+fn default_limit() -> i64 {
+    50
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UnitRestructuringsListResponse {
+    pub data: Vec<UnitRestructuringDto>,
+    pub total: i64,
+    pub limit: i64,
+    pub offset: i64,
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// Funde ou cinde unidades organizacionais, migrando as referências das
+/// unidades sucedidas e registrando a linhagem
+#[utoipa::path(
+    post,
+    path = "/api/admin/organizational/restructurings",
+    tag = "Organization - Organizational Units",
+    request_body = CreateUnitRestructuringPayload,
+    responses(
+        (status = 201, description = "Reestruturação registrada", body = UnitRestructuringDto),
+        (status = 400, description = "Unidades, sucessora principal ou direcionamentos inválidos"),
+        (status = 404, description = "Unidade não encontrada"),
+        (status = 409, description = "Unidade sucedida já reestruturada"),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn create_unit_restructuring(
+    user: CurrentUser,
+    State(state): State<AppState>,
+    Json(payload): Json<CreateUnitRestructuringPayload>,
+) -> Result<(StatusCode, Json<UnitRestructuringDto>), (StatusCode, String)> {
+    let restructuring = state
+        .unit_restructuring_service
+        .restructure(payload, Some(user.id))
+        .await
+        .map_err(|e| (StatusCode::from(&e), e.to_string()))?;
+
+    // Os papéis por unidade foram movidos direto no banco
+    if restructuring.migrated.unit_roles > 0 {
+        if let Err(e) = state.enforcer.write().await.load_policy().await {
+            tracing::error!("Falha ao recarregar políticas após reestruturação: {:?}", e);
+        }
+    }
+    state.policy_cache.invalidate_all();
+    state.unit_scope_cache.invalidate_all();
+
+    Ok((StatusCode::CREATED, Json(restructuring)))
+}
+
+/// Reestruturações registradas, mais recentes primeiro
+#[utoipa::path(
+    get,
+    path = "/api/admin/organizational/restructurings",
+    tag = "Organization - Organizational Units",
+    params(ListUnitRestructuringsParams),
+    responses(
+        (status = 200, description = "Reestruturações", body = UnitRestructuringsListResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_unit_restructurings(
+    _user: CurrentUser,
+    State(state): State<AppState>,
+    Query(params): Query<ListUnitRestructuringsParams>,
+) -> Result<Json<UnitRestructuringsListResponse>, (StatusCode, String)> {
+    let (data, total) = state
+        .unit_restructuring_service
+        .list(params.organization_id, params.limit, params.offset)
+        .await
+        .map_err(|e| (StatusCode::from(&e), e.to_string()))?;
+    Ok(Json(UnitRestructuringsListResponse {
+        data,
+        total,
+        limit: params.limit,
+        offset: params.offset,
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/organizational/restructurings/{id}",
+    tag = "Organization - Organizational Units",
+    params(("id" = Uuid, Path, description = "ID da reestruturação")),
+    responses(
+        (status = 200, description = "Reestruturação", body = UnitRestructuringDto),
+        (status = 404, description = "Reestruturação não encontrada"),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_unit_restructuring(
+    _user: CurrentUser,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<UnitRestructuringDto>, (StatusCode, String)> {
+    state
+        .unit_restructuring_service
+        .get(id)
+        .await
+        .map(Json)
+        .map_err(|e| (StatusCode::from(&e), e.to_string()))
+}
+
+/// Linhagem da unidade: de quais unidades veio, quais a sucederam e qual a
+/// substitui hoje
+#[utoipa::path(
+    get,
+    path = "/api/admin/organizational/units/{id}/lineage",
+    tag = "Organization - Organizational Units",
+    params(("id" = Uuid, Path, description = "ID da unidade")),
+    responses(
+        (status = 200, description = "Linhagem da unidade", body = UnitLineageResponse),
+        (status = 404, description = "Unidade não encontrada"),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_unit_lineage(
+    _user: CurrentUser,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<UnitLineageResponse>, (StatusCode, String)> {
+    state
+        .unit_restructuring_service
+        .lineage(id)
+        .await
+        .map(Json)
+        .map_err(|e| (StatusCode::from(&e), e.to_string()))
+}
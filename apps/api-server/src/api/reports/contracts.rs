@@ -1 +1,15 @@
+use chrono::NaiveDate;
 pub use domain::models::report::{FuelConsumptionDto, FleetSummaryDto, VehicleDashboardDto, ReportDateFilter};
+pub use domain::models::report::{UnitCostNode, UnitCostReport};
+use serde::Deserialize;
+use utoipa::IntoParams;
+use uuid::Uuid;
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct UnitCostQuery {
+    pub organization_id: Option<Uuid>,
+    pub start_date: Option<NaiveDate>,
+    pub end_date: Option<NaiveDate>,
+    /// Structure used for the roll-up (defaults to `end_date`, then today)
+    pub as_of: Option<NaiveDate>,
+}
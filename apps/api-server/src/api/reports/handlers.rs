@@ -1,3 +1,4 @@
+use super::contracts::{UnitCostQuery, UnitCostReport};
 use crate::infra::state::AppState;
 use axum::{
     extract::{Path, Query, State},
@@ -74,3 +75,53 @@ pub async fn get_fleet_summary(
         Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
     }
 }
+
+/// Valor das requisições atendidas agregado pela estrutura organizacional
+/// vigente em `as_of`
+#[utoipa::path(
+    get,
+    path = "/api/admin/reports/requisition-cost-by-unit",
+    tag = "Reports",
+    params(UnitCostQuery),
+    responses(
+        (status = 200, description = "Custos por unidade", body = UnitCostReport),
+        (status = 400, description = "Período inválido"),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_requisition_cost_by_unit(
+    State(state): State<AppState>,
+    Query(q): Query<UnitCostQuery>,
+) -> Result<Json<UnitCostReport>, (StatusCode, String)> {
+    state
+        .unit_cost_report_service
+        .requisition_cost_by_unit(q.organization_id, q.start_date, q.end_date, q.as_of)
+        .await
+        .map(Json)
+        .map_err(|e| (StatusCode::from(&e), e.to_string()))
+}
+
+/// Custos de abastecimento e manutenção agregados pela estrutura
+/// organizacional vigente em `as_of`
+#[utoipa::path(
+    get,
+    path = "/api/admin/reports/fleet-cost-by-unit",
+    tag = "Reports",
+    params(UnitCostQuery),
+    responses(
+        (status = 200, description = "Custos por unidade", body = UnitCostReport),
+        (status = 400, description = "Período inválido"),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_fleet_cost_by_unit(
+    State(state): State<AppState>,
+    Query(q): Query<UnitCostQuery>,
+) -> Result<Json<UnitCostReport>, (StatusCode, String)> {
+    state
+        .unit_cost_report_service
+        .fleet_cost_by_unit(q.organization_id, q.start_date, q.end_date, q.as_of)
+        .await
+        .map(Json)
+        .map_err(|e| (StatusCode::from(&e), e.to_string()))
+}
@@ -9,4 +9,6 @@ pub fn router() -> Router<AppState> {
         .route("/fuel-consumption", get(handlers::get_fuel_consumption))
         .route("/fleet-summary", get(handlers::get_fleet_summary))
         .route("/vehicles/{id}/dashboard", get(handlers::get_vehicle_dashboard))
+        .route("/requisition-cost-by-unit", get(handlers::get_requisition_cost_by_unit))
+        .route("/fleet-cost-by-unit", get(handlers::get_fleet_cost_by_unit))
 }
@@ -4,4 +4,7 @@ pub use domain::models::trip::{
     CheckoutPayload, CheckinPayload,
     FinalizeTripPayload, SetConflictPayload, CancelTripPayload,
     TripListFilters,
+    TripBookingDto, TripCalendarQuery, VehicleScheduleDto, DriverScheduleDto,
+    AvailableVehiclesQuery, AvailableVehicleDto,
+    TripAllocationSuggestionDto,
 };
@@ -1,12 +1,14 @@
 use super::contracts::*;
+use crate::api::auth::session_handlers::extract_client_ip;
 use crate::extractors::current_user::CurrentUser;
 use crate::state::AppState;
 use application::errors::ServiceError;
 use axum::{
     extract::{Path, Query, State},
-    http::StatusCode,
-    Json,
+    http::{header::USER_AGENT, HeaderMap, StatusCode},
+    Extension, Json,
 };
+use domain::models::{DelegatedAuthority, DelegationScope, UnitScope};
 use uuid::Uuid;
 
 fn occ_response(msg: String) -> axum::response::Response {
@@ -28,11 +30,12 @@ fn occ_response(msg: String) -> axum::response::Response {
 pub async fn list_trips(
     _user: CurrentUser,
     State(state): State<AppState>,
+    Extension(scope): Extension<UnitScope>,
     Query(filters): Query<TripListFilters>,
 ) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
     let (trips, total) = state
         .trip_service
-        .list_trips(filters)
+        .list_trips(filters, &scope)
         .await
         .map_err(|e| (StatusCode::from(&e), e.to_string()))?;
     Ok(Json(serde_json::json!({ "data": trips, "total": total })))
@@ -54,11 +57,12 @@ pub async fn create_trip(
 pub async fn get_trip(
     _user: CurrentUser,
     State(state): State<AppState>,
+    Extension(scope): Extension<UnitScope>,
     Path(id): Path<Uuid>,
 ) -> Result<Json<VehicleTripDto>, (StatusCode, String)> {
     state
         .trip_service
-        .get_trip(id)
+        .get_trip_in_scope(id, &scope)
         .await
         .map(Json)
         .map_err(|e| (StatusCode::from(&e), e.to_string()))
@@ -69,12 +73,37 @@ pub async fn get_trip(
 pub async fn review_trip(
     user: CurrentUser,
     State(state): State<AppState>,
+    Extension(scope): Extension<UnitScope>,
+    delegated: Option<Extension<DelegatedAuthority>>,
+    headers: HeaderMap,
     Path(id): Path<Uuid>,
     Json(payload): Json<ReviewTripPayload>,
 ) -> axum::response::Response {
     use axum::response::IntoResponse;
-    match state.trip_service.review_trip(id, payload, user.id).await {
-        Ok(t) => (StatusCode::OK, Json(t)).into_response(),
+    let approved = payload.approved;
+    let delegated = delegated.as_ref().map(|Extension(d)| d);
+    match state.trip_service.review_trip(id, payload, user.id, &scope, delegated).await {
+        Ok(t) => {
+            if let Some(on_behalf_of) = t.approved_on_behalf_of {
+                state
+                    .audit_service
+                    .log_delegated_approval(
+                        user.id,
+                        &user.username,
+                        on_behalf_of,
+                        DelegationScope::Trip,
+                        if approved { "approve" } else { "reject" },
+                        &format!("/api/admin/trips/{}/review", id),
+                        extract_client_ip(&headers),
+                        headers
+                            .get(USER_AGENT)
+                            .and_then(|v| v.to_str().ok())
+                            .map(|s| s.to_string()),
+                    )
+                    .await;
+            }
+            (StatusCode::OK, Json(t)).into_response()
+        }
         Err(ServiceError::OptimisticLockConflict(msg)) => occ_response(msg),
         Err(e) => (StatusCode::from(&e), e.to_string()).into_response(),
     }
@@ -85,11 +114,16 @@ pub async fn review_trip(
 pub async fn allocate_trip(
     user: CurrentUser,
     State(state): State<AppState>,
+    Extension(scope): Extension<UnitScope>,
     Path(id): Path<Uuid>,
     Json(payload): Json<AllocateTripPayload>,
 ) -> axum::response::Response {
     use axum::response::IntoResponse;
-    match state.trip_service.allocate_trip(id, payload, user.id).await {
+    match state
+        .trip_service
+        .allocate_trip(id, payload, user.id, &scope)
+        .await
+    {
         Ok(t) => (StatusCode::OK, Json(t)).into_response(),
         Err(ServiceError::OptimisticLockConflict(msg)) => occ_response(msg),
         Err(e) => (StatusCode::from(&e), e.to_string()).into_response(),
@@ -101,11 +135,16 @@ pub async fn allocate_trip(
 pub async fn checkout(
     user: CurrentUser,
     State(state): State<AppState>,
+    Extension(scope): Extension<UnitScope>,
     Path(id): Path<Uuid>,
     Json(payload): Json<CheckoutPayload>,
 ) -> axum::response::Response {
     use axum::response::IntoResponse;
-    match state.trip_service.checkout(id, payload, user.id).await {
+    match state
+        .trip_service
+        .checkout(id, payload, user.id, &scope)
+        .await
+    {
         Ok(t) => (StatusCode::OK, Json(t)).into_response(),
         Err(ServiceError::OptimisticLockConflict(msg)) => occ_response(msg),
         Err(e) => (StatusCode::from(&e), e.to_string()).into_response(),
@@ -117,11 +156,16 @@ pub async fn checkout(
 pub async fn checkin(
     user: CurrentUser,
     State(state): State<AppState>,
+    Extension(scope): Extension<UnitScope>,
     Path(id): Path<Uuid>,
     Json(payload): Json<CheckinPayload>,
 ) -> axum::response::Response {
     use axum::response::IntoResponse;
-    match state.trip_service.checkin(id, payload, user.id).await {
+    match state
+        .trip_service
+        .checkin(id, payload, user.id, &scope)
+        .await
+    {
         Ok(t) => (StatusCode::OK, Json(t)).into_response(),
         Err(ServiceError::OptimisticLockConflict(msg)) => occ_response(msg),
         Err(e) => (StatusCode::from(&e), e.to_string()).into_response(),
@@ -133,11 +177,16 @@ pub async fn checkin(
 pub async fn finalize_trip(
     user: CurrentUser,
     State(state): State<AppState>,
+    Extension(scope): Extension<UnitScope>,
     Path(id): Path<Uuid>,
     Json(payload): Json<FinalizeTripPayload>,
 ) -> axum::response::Response {
     use axum::response::IntoResponse;
-    match state.trip_service.finalize_trip(id, payload, user.id).await {
+    match state
+        .trip_service
+        .finalize_trip(id, payload, user.id, &scope)
+        .await
+    {
         Ok(t) => (StatusCode::OK, Json(t)).into_response(),
         Err(ServiceError::OptimisticLockConflict(msg)) => occ_response(msg),
         Err(e) => (StatusCode::from(&e), e.to_string()).into_response(),
@@ -149,11 +198,16 @@ pub async fn finalize_trip(
 pub async fn set_conflict(
     user: CurrentUser,
     State(state): State<AppState>,
+    Extension(scope): Extension<UnitScope>,
     Path(id): Path<Uuid>,
     Json(payload): Json<SetConflictPayload>,
 ) -> axum::response::Response {
     use axum::response::IntoResponse;
-    match state.trip_service.set_conflict(id, payload, user.id).await {
+    match state
+        .trip_service
+        .set_conflict(id, payload, user.id, &scope)
+        .await
+    {
         Ok(t) => (StatusCode::OK, Json(t)).into_response(),
         Err(ServiceError::OptimisticLockConflict(msg)) => occ_response(msg),
         Err(e) => (StatusCode::from(&e), e.to_string()).into_response(),
@@ -165,13 +219,88 @@ pub async fn set_conflict(
 pub async fn cancel_trip(
     user: CurrentUser,
     State(state): State<AppState>,
+    Extension(scope): Extension<UnitScope>,
     Path(id): Path<Uuid>,
     Json(payload): Json<CancelTripPayload>,
 ) -> axum::response::Response {
     use axum::response::IntoResponse;
-    match state.trip_service.cancel_trip(id, payload, user.id).await {
+    match state
+        .trip_service
+        .cancel_trip(id, payload, user.id, &scope)
+        .await
+    {
         Ok(t) => (StatusCode::OK, Json(t)).into_response(),
         Err(ServiceError::OptimisticLockConflict(msg)) => occ_response(msg),
         Err(e) => (StatusCode::from(&e), e.to_string()).into_response(),
     }
 }
+
+// ── RF-VIG-03: Calendar and availability ─────────────────────────────────
+
+pub async fn vehicle_calendar(
+    _user: CurrentUser,
+    State(state): State<AppState>,
+    Query(query): Query<TripCalendarQuery>,
+) -> Result<Json<Vec<VehicleScheduleDto>>, (StatusCode, String)> {
+    state
+        .trip_service
+        .vehicle_calendar(query)
+        .await
+        .map(Json)
+        .map_err(|e| (StatusCode::from(&e), e.to_string()))
+}
+
+pub async fn driver_calendar(
+    _user: CurrentUser,
+    State(state): State<AppState>,
+    Query(query): Query<TripCalendarQuery>,
+) -> Result<Json<Vec<DriverScheduleDto>>, (StatusCode, String)> {
+    state
+        .trip_service
+        .driver_calendar(query)
+        .await
+        .map(Json)
+        .map_err(|e| (StatusCode::from(&e), e.to_string()))
+}
+
+pub async fn available_vehicles(
+    _user: CurrentUser,
+    State(state): State<AppState>,
+    Query(query): Query<AvailableVehiclesQuery>,
+) -> Result<Json<Vec<AvailableVehicleDto>>, (StatusCode, String)> {
+    state
+        .trip_service
+        .find_available_vehicles(query)
+        .await
+        .map(Json)
+        .map_err(|e| (StatusCode::from(&e), e.to_string()))
+}
+
+// ── RF-VIG-05: Allocation suggestions ────────────────────────────────────
+
+pub async fn list_suggestions(
+    _user: CurrentUser,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<TripAllocationSuggestionDto>>, (StatusCode, String)> {
+    state
+        .trip_service
+        .list_suggestions(id)
+        .await
+        .map(Json)
+        .map_err(|e| (StatusCode::from(&e), e.to_string()))
+}
+
+pub async fn generate_suggestions(
+    user: CurrentUser,
+    State(state): State<AppState>,
+    Extension(scope): Extension<UnitScope>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<TripAllocationSuggestionDto>>, (StatusCode, String)> {
+    state
+        .trip_service
+        .suggest_vehicles(id, Some(user.id), &scope)
+        .await
+        .map(Json)
+        .map_err(|e| (StatusCode::from(&e), e.to_string()))
+}
@@ -10,8 +10,12 @@ use axum::{routing::get, Router};
 pub fn router() -> Router<AppState> {
     Router::new()
         .route("/", get(handlers::list_trips).post(handlers::create_trip))
+        .route("/calendar/vehicles", get(handlers::vehicle_calendar))
+        .route("/calendar/drivers",  get(handlers::driver_calendar))
+        .route("/available-vehicles", get(handlers::available_vehicles))
         .route("/{id}", get(handlers::get_trip))
         .route("/{id}/review",    axum::routing::put(handlers::review_trip))
+        .route("/{id}/suggestions", get(handlers::list_suggestions).post(handlers::generate_suggestions))
         .route("/{id}/allocate",  axum::routing::put(handlers::allocate_trip))
         .route("/{id}/checkout",  axum::routing::put(handlers::checkout))
         .route("/{id}/checkin",   axum::routing::put(handlers::checkin))
@@ -2,8 +2,11 @@ use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
 pub use domain::models::vehicle_fine::{
-    ChangeFineStatusPayload, CreateVehicleFinePayload, CreateVehicleFineTypePayload, FineSeverity,
-    FineStatus, UpdateVehicleFinePayload, UpdateVehicleFineTypePayload, VehicleFineDto,
+    ChangeFineStatusPayload, CreateVehicleFinePayload, CreateVehicleFineTypePayload,
+    DriverPointsAlertDto, DriverPointsAlertLevel, DriverPointsAlertQuery, DriverPointsQuery,
+    DriverPointsSummaryDto, FineDeadlineDto, FineDeadlineKind, FineDeadlineQuery, FineDriverSource,
+    FineIdentificationFormDto, FineSeverity, FineStatus, IdentifyFineDriverPayload,
+    UpdateVehicleFinePayload, UpdateVehicleFineTypePayload, VehicleFineDto,
     VehicleFineStatusHistoryDto, VehicleFineTypeDto, VehicleFineWithDetailsDto,
 };
 
@@ -250,3 +250,103 @@ pub async fn list_fines(
         })
         .map_err(|e| (StatusCode::from(&e), e.to_string()))
 }
+
+// ============================
+// Driver identification handlers
+// ============================
+
+pub async fn identify_fine_driver(
+    user: CurrentUser,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<IdentifyFineDriverPayload>,
+) -> Result<Json<VehicleFineWithDetailsDto>, (StatusCode, String)> {
+    state
+        .vehicle_fine_service
+        .identify_driver(id, payload, Some(user.id))
+        .await
+        .map(Json)
+        .map_err(|e| (StatusCode::from(&e), e.to_string()))
+}
+
+pub async fn get_identification_form(
+    _user: CurrentUser,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<FineIdentificationFormDto>, (StatusCode, String)> {
+    state
+        .vehicle_fine_service
+        .get_identification_form(id)
+        .await
+        .map(Json)
+        .map_err(|e| (StatusCode::from(&e), e.to_string()))
+}
+
+pub async fn list_fine_deadlines(
+    _user: CurrentUser,
+    State(state): State<AppState>,
+    Query(query): Query<FineDeadlineQuery>,
+) -> Result<Json<Vec<FineDeadlineDto>>, (StatusCode, String)> {
+    state
+        .vehicle_fine_service
+        .list_upcoming_deadlines(query)
+        .await
+        .map(Json)
+        .map_err(|e| (StatusCode::from(&e), e.to_string()))
+}
+
+// ============================
+// Driver points handlers
+// ============================
+
+pub async fn list_driver_points(
+    _user: CurrentUser,
+    State(state): State<AppState>,
+    Query(query): Query<DriverPointsQuery>,
+) -> Result<Json<Vec<DriverPointsSummaryDto>>, (StatusCode, String)> {
+    state
+        .vehicle_fine_service
+        .list_driver_points(query)
+        .await
+        .map(Json)
+        .map_err(|e| (StatusCode::from(&e), e.to_string()))
+}
+
+pub async fn get_driver_points(
+    _user: CurrentUser,
+    State(state): State<AppState>,
+    Path(driver_id): Path<Uuid>,
+) -> Result<Json<DriverPointsSummaryDto>, (StatusCode, String)> {
+    state
+        .vehicle_fine_service
+        .get_driver_points(driver_id)
+        .await
+        .map(Json)
+        .map_err(|e| (StatusCode::from(&e), e.to_string()))
+}
+
+pub async fn list_driver_points_alerts(
+    _user: CurrentUser,
+    State(state): State<AppState>,
+    Query(query): Query<DriverPointsAlertQuery>,
+) -> Result<Json<Vec<DriverPointsAlertDto>>, (StatusCode, String)> {
+    state
+        .vehicle_fine_service
+        .list_points_alerts(query)
+        .await
+        .map(Json)
+        .map_err(|e| (StatusCode::from(&e), e.to_string()))
+}
+
+pub async fn acknowledge_driver_points_alert(
+    user: CurrentUser,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<DriverPointsAlertDto>, (StatusCode, String)> {
+    state
+        .vehicle_fine_service
+        .acknowledge_points_alert(id, user.id)
+        .await
+        .map(Json)
+        .map_err(|e| (StatusCode::from(&e), e.to_string()))
+}
@@ -16,14 +16,24 @@ pub fn router() -> Router<AppState> {
 
     let fines_router = Router::new()
         .route("/", get(handlers::list_fines).post(handlers::create_fine))
+        .route("/deadlines", get(handlers::list_fine_deadlines))
         .route("/{id}", get(handlers::get_fine)
             .put(handlers::update_fine)
             .delete(handlers::delete_fine))
         .route("/{id}/restore", axum::routing::put(handlers::restore_fine))
         .route("/{id}/status", axum::routing::put(handlers::change_fine_status))
-        .route("/{id}/history", get(handlers::get_fine_status_history));
+        .route("/{id}/history", get(handlers::get_fine_status_history))
+        .route("/{id}/identify-driver", axum::routing::post(handlers::identify_fine_driver))
+        .route("/{id}/identification-form", get(handlers::get_identification_form));
+
+    let driver_points_router = Router::new()
+        .route("/", get(handlers::list_driver_points))
+        .route("/alerts", get(handlers::list_driver_points_alerts))
+        .route("/alerts/{id}/acknowledge", axum::routing::post(handlers::acknowledge_driver_points_alert))
+        .route("/{driver_id}", get(handlers::get_driver_points));
 
     Router::new()
         .nest("/fine-types", fine_types_router)
         .nest("/fines", fines_router)
+        .nest("/driver-points", driver_points_router)
 }
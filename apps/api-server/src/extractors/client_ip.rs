@@ -0,0 +1,230 @@
+//! Client IP Extractor
+//!
+//! Endereço de origem usado em decisões de segurança (throttling de login,
+//! allowlist de API keys). X-Forwarded-For e X-Real-IP só são considerados
+//! quando a conexão TCP vem de um proxy confiável (WS_TRUSTED_PROXIES);
+//! fora disso vale o endereço da própria conexão.
+
+use std::convert::Infallible;
+use std::net::{IpAddr, SocketAddr};
+
+use axum::{
+    extract::{connect_info::MockConnectInfo, ConnectInfo, FromRequestParts},
+    http::{request::Parts, Extensions, HeaderMap},
+};
+
+use crate::infra::state::AppState;
+
+/// Redes (CIDR) dos proxies reversos cujos cabeçalhos de encaminhamento são
+/// aceitos.
+#[derive(Debug, Clone, Default)]
+pub struct TrustedProxies(Vec<(IpAddr, u8)>);
+
+impl TrustedProxies {
+    /// Lê uma lista separada por vírgulas (`10.0.0.0/8,127.0.0.1`); um IP sem
+    /// prefixo vale como /32 ou /128.
+    pub fn parse(list: &str) -> Result<Self, String> {
+        list.split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .map(|entry| {
+                let (addr, prefix) = match entry.split_once('/') {
+                    Some((addr, prefix)) => (addr, Some(prefix)),
+                    None => (entry, None),
+                };
+                let addr: IpAddr = addr
+                    .parse()
+                    .map_err(|_| format!("Proxy confiável inválido: {}", entry))?;
+                let max = if addr.is_ipv4() { 32 } else { 128 };
+                let prefix = match prefix {
+                    Some(p) => p
+                        .parse::<u8>()
+                        .ok()
+                        .filter(|p| *p <= max)
+                        .ok_or_else(|| format!("Prefixo inválido: {}", entry))?,
+                    None => max,
+                };
+                Ok((addr, prefix))
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(Self)
+    }
+
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        let ip = ip.to_canonical();
+        self.0.iter().any(|(network, prefix)| match (network, ip) {
+            (IpAddr::V4(net), IpAddr::V4(ip)) => {
+                let mask = u32::MAX.checked_shl(32 - *prefix as u32).unwrap_or(0);
+                u32::from(*net) & mask == u32::from(ip) & mask
+            }
+            (IpAddr::V6(net), IpAddr::V6(ip)) => {
+                let mask = u128::MAX.checked_shl(128 - *prefix as u32).unwrap_or(0);
+                u128::from(*net) & mask == u128::from(ip) & mask
+            }
+            _ => false,
+        })
+    }
+}
+
+/// Resolve o IP do cliente a partir do endereço da conexão.
+///
+/// Com a conexão vinda de um proxy confiável, percorre o X-Forwarded-For da
+/// direita para a esquerda e devolve o primeiro salto não confiável (os
+/// anteriores podem ter sido forjados pelo próprio cliente); sem o cabeçalho,
+/// usa o X-Real-IP definido pelo proxy.
+pub fn resolve_client_ip(
+    peer: Option<IpAddr>,
+    headers: &HeaderMap,
+    trusted: &TrustedProxies,
+) -> Option<IpAddr> {
+    let peer = peer?.to_canonical();
+    if !trusted.contains(peer) {
+        return Some(peer);
+    }
+
+    let hops: Vec<&str> = headers
+        .get_all("X-Forwarded-For")
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .collect();
+
+    if !hops.is_empty() {
+        let mut client = peer;
+        for hop in hops.iter().rev() {
+            // Um salto ilegível encerra a cadeia: dali para trás nada é confiável
+            let Ok(ip) = hop.parse::<IpAddr>() else {
+                break;
+            };
+            client = ip.to_canonical();
+            if !trusted.contains(client) {
+                break;
+            }
+        }
+        return Some(client);
+    }
+
+    headers
+        .get("X-Real-IP")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<IpAddr>().ok())
+        .map(|ip| ip.to_canonical())
+        .or(Some(peer))
+}
+
+/// IP do cliente resolvido por `resolve_client_ip`; `None` só quando o
+/// servidor não expõe o endereço da conexão.
+///
+/// ```rust,ignore
+/// pub async fn handler(ClientIp(ip): ClientIp) { ... }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct ClientIp(pub Option<IpAddr>);
+
+impl ClientIp {
+    /// Resolve a partir das extensões e cabeçalhos de uma requisição; usado
+    /// também por middlewares, que recebem a `Request` inteira.
+    pub fn resolve(extensions: &Extensions, headers: &HeaderMap, trusted: &TrustedProxies) -> Self {
+        let peer = extensions
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(addr)| addr.ip())
+            .or_else(|| {
+                extensions
+                    .get::<MockConnectInfo<SocketAddr>>()
+                    .map(|MockConnectInfo(addr)| addr.ip())
+            });
+        Self(resolve_client_ip(peer, headers, trusted))
+    }
+}
+
+impl FromRequestParts<AppState> for ClientIp {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        Ok(Self::resolve(
+            &parts.extensions,
+            &parts.headers,
+            &state.config.trusted_proxies,
+        ))
+    }
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(pairs: &[(&'static str, &str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.append(*name, value.parse().unwrap());
+        }
+        headers
+    }
+
+    fn ip(s: &str) -> Option<IpAddr> {
+        Some(s.parse().unwrap())
+    }
+
+    #[test]
+    fn test_trusted_proxies_parse_and_contains() {
+        let trusted = TrustedProxies::parse("10.0.0.0/8, 127.0.0.1 ,fd00::/8").unwrap();
+        assert!(trusted.contains("10.1.2.3".parse().unwrap()));
+        assert!(trusted.contains("127.0.0.1".parse().unwrap()));
+        assert!(!trusted.contains("127.0.0.2".parse().unwrap()));
+        assert!(trusted.contains("fd12::1".parse().unwrap()));
+        assert!(trusted.contains("::ffff:10.0.0.1".parse().unwrap()));
+
+        assert!(TrustedProxies::parse("10.0.0.0/33").is_err());
+        assert!(TrustedProxies::parse("proxy.local").is_err());
+        assert!(!TrustedProxies::parse("")
+            .unwrap()
+            .contains("10.0.0.1".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_untrusted_peer_ignores_forwarding_headers() {
+        let trusted = TrustedProxies::default();
+        let spoofed = headers(&[("X-Forwarded-For", "1.2.3.4"), ("X-Real-IP", "5.6.7.8")]);
+        assert_eq!(
+            resolve_client_ip(ip("203.0.113.9"), &spoofed, &trusted),
+            ip("203.0.113.9")
+        );
+    }
+
+    #[test]
+    fn test_trusted_peer_uses_rightmost_untrusted_hop() {
+        let trusted = TrustedProxies::parse("10.0.0.0/8").unwrap();
+        // O cliente forjou 1.2.3.4; o proxy acrescentou o endereço real
+        let forwarded = headers(&[("X-Forwarded-For", "1.2.3.4, 198.51.100.7, 10.0.0.2")]);
+        assert_eq!(
+            resolve_client_ip(ip("10.0.0.1"), &forwarded, &trusted),
+            ip("198.51.100.7")
+        );
+
+        let garbage = headers(&[("X-Forwarded-For", "unknown, 198.51.100.7")]);
+        assert_eq!(
+            resolve_client_ip(ip("10.0.0.1"), &garbage, &trusted),
+            ip("198.51.100.7")
+        );
+
+        let real_ip = headers(&[("X-Real-IP", "198.51.100.8")]);
+        assert_eq!(
+            resolve_client_ip(ip("10.0.0.1"), &real_ip, &trusted),
+            ip("198.51.100.8")
+        );
+
+        assert_eq!(
+            resolve_client_ip(ip("10.0.0.1"), &HeaderMap::new(), &trusted),
+            ip("10.0.0.1")
+        );
+        assert_eq!(resolve_client_ip(None, &forwarded, &trusted), None);
+    }
+}
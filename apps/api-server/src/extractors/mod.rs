@@ -1,2 +1,3 @@
 pub mod client_info;
+pub mod client_ip;
 pub mod current_user;
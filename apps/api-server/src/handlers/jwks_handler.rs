@@ -0,0 +1,24 @@
+use axum::{extract::State, http::header, response::IntoResponse, Json};
+
+use crate::state::AppState;
+
+/// GET /.well-known/jwks.json
+///
+/// Chaves públicas que verificam os JWTs emitidos: a ativa e as que estão no
+/// período de carência após uma rotação. Os serviços internos selecionam a
+/// chave pelo `kid` do token e devem buscar o JWKS de novo ao encontrar um
+/// `kid` desconhecido.
+#[utoipa::path(
+    get,
+    path = "/.well-known/jwks.json",
+    tag = "Public",
+    responses(
+        (status = 200, description = "JWK Set (RFC 7517)", content_type = "application/json")
+    )
+)]
+pub async fn handler_jwks(State(state): State<AppState>) -> impl IntoResponse {
+    (
+        [(header::CACHE_CONTROL, "public, max-age=60")],
+        Json(state.jwt_service.jwks()),
+    )
+}
@@ -1,5 +1,6 @@
 pub mod handlers_documented;
 pub mod health_handler;
+pub mod jwks_handler;
 pub mod metrics_handler;
 pub mod protected_handler;
 pub mod public_handler;
@@ -1,6 +1,6 @@
 pub use crate::extractors::current_user::CurrentUser;
 use crate::state::SharedEnforcer;
-use crate::utils::constants::{ROLE_ADMIN, ROLE_USER};
+use crate::utils::constants::{ROLE_ADMIN, ROLE_USER, UNIT_ROLE_PTYPE, UNIT_SUBTREE_SUFFIX};
 use anyhow::{Context, Result};
 use axum::http::request::Parts;
 use casbin::{CoreApi, DefaultModel, Enforcer, MgmtApi};
@@ -49,12 +49,22 @@ pub async fn setup_casbin(pool: PgPool) -> Result<SharedEnforcer> {
 }
 
 async fn seed_policies(enforcer: &mut Enforcer, pool: &PgPool) -> Result<()> {
+    // Papéis por unidade são atribuídos em tempo de execução e sobrevivem ao reinício
+    let unit_roles = enforcer.get_named_grouping_policy(UNIT_ROLE_PTYPE);
+
     // Limpa políticas antigas para evitar duplicação no reinício
     enforcer.clear_policy().await?;
 
     // Carrega todas as políticas de cada domínio
     policies::seed_all_policies(enforcer).await?;
 
+    if !unit_roles.is_empty() {
+        info!("Restaurando {} papéis por unidade organizacional", unit_roles.len());
+        enforcer
+            .add_named_grouping_policies(UNIT_ROLE_PTYPE, unit_roles)
+            .await?;
+    }
+
     match enforcer.save_policy().await {
         Ok(_) => {
             info!("Políticas do Casbin carregadas e salvas no banco.");
@@ -159,6 +169,24 @@ async fn seed_policies(enforcer: &mut Enforcer, pool: &PgPool) -> Result<()> {
     Ok(())
 }
 
+/// Domínio Casbin (`g2`) de um papel exercido na unidade, opcionalmente
+/// estendido à sua subárvore
+pub fn unit_domain(unit_id: Uuid, include_subtree: bool) -> String {
+    if include_subtree {
+        format!("{}{}", unit_id, UNIT_SUBTREE_SUFFIX)
+    } else {
+        unit_id.to_string()
+    }
+}
+
+/// Inverso de [`unit_domain`]: `(unidade, inclui subárvore)`
+pub fn parse_unit_domain(domain: &str) -> Option<(Uuid, bool)> {
+    match domain.strip_suffix(UNIT_SUBTREE_SUFFIX) {
+        Some(unit) => Uuid::parse_str(unit).ok().map(|id| (id, true)),
+        None => Uuid::parse_str(domain).ok().map(|id| (id, false)),
+    }
+}
+
 pub fn casbin_subject_getter(parts: &Parts) -> String {
     if let Some(user) = parts.extensions.get::<CurrentUser>() {
         user.id.to_string()
@@ -166,3 +194,25 @@ pub fn casbin_subject_getter(parts: &Parts) -> String {
         "anonymous".to_string()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unit_domain_roundtrip() {
+        let unit_id = Uuid::new_v4();
+
+        assert_eq!(unit_domain(unit_id, false), unit_id.to_string());
+        assert_eq!(unit_domain(unit_id, true), format!("{}/*", unit_id));
+        assert_eq!(parse_unit_domain(&unit_domain(unit_id, false)), Some((unit_id, false)));
+        assert_eq!(parse_unit_domain(&unit_domain(unit_id, true)), Some((unit_id, true)));
+    }
+
+    #[test]
+    fn test_parse_unit_domain_rejects_garbage() {
+        assert_eq!(parse_unit_domain("*"), None);
+        assert_eq!(parse_unit_domain("almoxarifado/*"), None);
+        assert_eq!(parse_unit_domain(""), None);
+    }
+}
@@ -1,35 +1,197 @@
+use application::services::login_throttle_service::LoginThrottleSettings;
+use application::services::oidc_service::OidcProviderSettings;
+use core_services::secrets::{Secret, SecretStore};
 use dotenvy::dotenv;
 use serde::Deserialize;
 
+use crate::extractors::client_ip::TrustedProxies;
+
 #[derive(Deserialize, Debug, Clone)]
 pub struct DatabaseConfig {
     pub url: String,
 }
 
+pub const MAIN_DATABASE_URL: Secret = Secret::new("main_database_url", "WS_MAIN_DATABASE_URL");
+pub const LOGS_DATABASE_URL: Secret = Secret::new("logs_database_url", "WS_LOGS_DATABASE_URL");
+pub const JWT_PRIVATE_KEY: Secret = Secret::new("jwt_private_key", "WS_JWT_PRIVATE_KEY");
+pub const JWT_PUBLIC_KEY: Secret = Secret::new("jwt_public_key", "WS_JWT_PUBLIC_KEY");
+pub const FIELD_ENCRYPTION_KEY: Secret =
+    Secret::new("field_encryption_key", "WS_FIELD_ENCRYPTION_KEY");
+pub const SMTP_USER: Secret = Secret::new("smtp_user", "EMAIL_SMTP_USER");
+pub const SMTP_PASS: Secret = Secret::new("smtp_pass", "EMAIL_SMTP_PASS");
+pub const SIORG_API_TOKEN: Secret = Secret::new("siorg_api_token", "SIORG_API_TOKEN");
+pub const COMPRASNET_EMPENHO_API_TOKEN: Secret =
+    Secret::new("comprasnet_empenho_api_token", "COMPRASNET_EMPENHO_API_TOKEN");
+
+/// Segredos da API, lidos do provedor escolhido por SECRETS_PROVIDER
+/// (ver `SecretStore::load_from_env`).
+pub static SECRETS: &[Secret] = &[
+    MAIN_DATABASE_URL,
+    LOGS_DATABASE_URL,
+    JWT_PRIVATE_KEY,
+    JWT_PUBLIC_KEY,
+    FIELD_ENCRYPTION_KEY,
+    SMTP_USER,
+    SMTP_PASS,
+    SIORG_API_TOKEN,
+    COMPRASNET_EMPENHO_API_TOKEN,
+];
+
 #[derive(Deserialize, Debug, Clone)]
 pub struct Config {
-    #[serde(rename = "main_database_url")]
+    /// Os campos sensíveis abaixo também podem vir do provedor de segredos,
+    /// que tem precedência sobre as variáveis WS_*.
+    #[serde(rename = "main_database_url", default)]
     pub main_db: String,
 
-    #[serde(rename = "logs_database_url")]
+    #[serde(rename = "logs_database_url", default)]
     pub logs_db: String,
 
-    #[serde(rename = "jwt_private_key")]
+    #[serde(rename = "jwt_private_key", default)]
     pub jwt_private_key: String,
 
-    #[serde(rename = "jwt_public_key")]
+    #[serde(rename = "jwt_public_key", default)]
     pub jwt_public_key: String,
 
+    /// Tempo em que a chave JWT substituída por uma rotação ainda verifica
+    /// tokens (WS_JWT_KEY_GRACE_PERIOD_SECS). Deve cobrir o token mais longo.
+    #[serde(default = "default_jwt_key_grace_period_secs")]
+    pub jwt_key_grace_period_secs: i64,
+
+    /// Intervalo de releitura do keyring JWT, para que rotações feitas em
+    /// outra instância sejam aplicadas (WS_JWT_KEYRING_REFRESH_SECS).
+    #[serde(default = "default_jwt_keyring_refresh_secs")]
+    pub jwt_keyring_refresh_secs: u64,
+
     /// 64-character hex-encoded 32-byte key used for field-level AES-256-GCM
     /// encryption (email, MFA secrets). Set via WS_FIELD_ENCRYPTION_KEY.
     /// Generate with: `openssl rand -hex 32`
-    #[serde(rename = "field_encryption_key")]
+    #[serde(rename = "field_encryption_key", default)]
     pub field_encryption_key: String,
+
+    /// Versão de WS_FIELD_ENCRYPTION_KEY gravada em cada valor cifrado
+    /// (WS_FIELD_ENCRYPTION_KEY_ID). Incrementada a cada rotação.
+    #[serde(default = "default_field_encryption_key_id")]
+    pub field_encryption_key_id: u32,
+
+    /// Chaves anteriores ainda aceitas na decriptação, até a recriptografia
+    /// terminar (WS_FIELD_ENCRYPTION_PREVIOUS_KEYS=1:<hex>,2:<hex>).
+    #[serde(default)]
+    pub field_encryption_previous_keys: String,
+
+    /// Provedores OpenID Connect habilitados (WS_OIDC_PROVIDERS=govbr,...).
+    /// Cada provedor é lido de WS_OIDC_<NOME>_* — ver `load_oidc_providers`.
+    #[serde(skip)]
+    pub oidc_providers: Vec<OidcProviderSettings>,
+
+    /// WebAuthn Relying Party ID: domínio ao qual as passkeys ficam vinculadas.
+    /// Set via WS_WEBAUTHN_RP_ID.
+    #[serde(default = "default_webauthn_rp_id")]
+    pub webauthn_rp_id: String,
+
+    /// Origem do frontend que executa as cerimônias (WS_WEBAUTHN_RP_ORIGIN).
+    #[serde(default = "default_webauthn_rp_origin")]
+    pub webauthn_rp_origin: String,
+
+    /// Nome exibido pelo autenticador (WS_WEBAUTHN_RP_NAME).
+    #[serde(default = "default_webauthn_rp_name")]
+    pub webauthn_rp_name: String,
+
+    /// Falhas consecutivas que bloqueiam a conta (WS_LOGIN_MAX_FAILURES).
+    #[serde(default = "default_login_max_failures")]
+    pub login_max_failures: i32,
+
+    /// Primeiro bloqueio em segundos; dobra a cada reincidência
+    /// (WS_LOGIN_LOCKOUT_BASE_SECS).
+    #[serde(default = "default_login_lockout_base_secs")]
+    pub login_lockout_base_secs: i64,
+
+    /// Teto da duração do bloqueio (WS_LOGIN_LOCKOUT_MAX_SECS).
+    #[serde(default = "default_login_lockout_max_secs")]
+    pub login_lockout_max_secs: i64,
+
+    /// Falhas por IP em 15 minutos antes do 429 (WS_LOGIN_IP_MAX_FAILURES).
+    #[serde(default = "default_login_ip_max_failures")]
+    pub login_ip_max_failures: i64,
+
+    /// Falhas por sub-rede /24 (IPv4) ou /64 (IPv6) em 15 minutos
+    /// (WS_LOGIN_SUBNET_MAX_FAILURES).
+    #[serde(default = "default_login_subnet_max_failures")]
+    pub login_subnet_max_failures: i64,
+
+    /// Proxies reversos (CIDR, separados por vírgula) cujos X-Forwarded-For
+    /// e X-Real-IP são aceitos (WS_TRUSTED_PROXIES). Vazio: vale o IP da
+    /// conexão.
+    #[serde(skip)]
+    pub trusted_proxies: TrustedProxies,
+
+    /// Diretório dos arquivos selados gerados pela retenção dos registros de
+    /// auditoria (WS_AUDIT_ARCHIVE_DIR).
+    #[serde(default = "default_audit_archive_dir")]
+    pub audit_archive_dir: String,
+
+    /// Provedor de segredos; sem carga explícita, lê apenas o ambiente.
+    #[serde(skip)]
+    pub secrets: SecretStore,
+}
+
+fn default_jwt_key_grace_period_secs() -> i64 {
+    86400
+}
+
+fn default_jwt_keyring_refresh_secs() -> u64 {
+    60
+}
+
+fn default_field_encryption_key_id() -> u32 {
+    1
+}
+
+fn default_webauthn_rp_id() -> String {
+    "localhost".to_string()
+}
+
+fn default_webauthn_rp_origin() -> String {
+    "http://localhost:3000".to_string()
+}
+
+fn default_webauthn_rp_name() -> String {
+    "Waterswamp".to_string()
+}
+
+fn default_login_max_failures() -> i32 {
+    LoginThrottleSettings::default().max_failures
+}
+
+fn default_login_lockout_base_secs() -> i64 {
+    LoginThrottleSettings::default().base_lockout_secs
+}
+
+fn default_login_lockout_max_secs() -> i64 {
+    LoginThrottleSettings::default().max_lockout_secs
+}
+
+fn default_login_ip_max_failures() -> i64 {
+    LoginThrottleSettings::default().ip_max_failures
+}
+
+fn default_login_subnet_max_failures() -> i64 {
+    LoginThrottleSettings::default().subnet_max_failures
+}
+
+fn default_audit_archive_dir() -> String {
+    "./audit-archive".to_string()
 }
 
 impl Config {
     /// Carrega a configuração do ambiente
     pub fn from_env() -> Result<Self, config::ConfigError> {
+        Self::with_secrets(SecretStore::default())
+    }
+
+    /// Carrega a configuração do ambiente, com os campos sensíveis lidos de
+    /// `secrets`
+    pub fn with_secrets(secrets: SecretStore) -> Result<Self, config::ConfigError> {
         dotenv().ok();
         // Inicializa o builder de configuração
         let cfg = config::Config::builder()
@@ -45,6 +207,105 @@ impl Config {
             .build()?;
 
         // Deserializa na nossa struct Config
-        cfg.try_deserialize()
+        let mut config: Config = cfg.try_deserialize()?;
+        config.oidc_providers = load_oidc_providers()?;
+        config.trusted_proxies =
+            TrustedProxies::parse(&std::env::var("WS_TRUSTED_PROXIES").unwrap_or_default())
+                .map_err(config::ConfigError::Message)?;
+        config.secrets = secrets;
+        config.apply_secrets()?;
+        Ok(config)
     }
+
+    fn apply_secrets(&mut self) -> Result<(), config::ConfigError> {
+        let fields = [
+            (MAIN_DATABASE_URL, &mut self.main_db),
+            (LOGS_DATABASE_URL, &mut self.logs_db),
+            (JWT_PRIVATE_KEY, &mut self.jwt_private_key),
+            (JWT_PUBLIC_KEY, &mut self.jwt_public_key),
+            (FIELD_ENCRYPTION_KEY, &mut self.field_encryption_key),
+        ];
+        for (secret, field) in fields {
+            if let Some(value) = self.secrets.get(&secret) {
+                *field = value;
+            }
+        }
+
+        // As chaves JWT podem vir de private.pem/public.pem
+        for (secret, value) in [
+            (MAIN_DATABASE_URL, &self.main_db),
+            (LOGS_DATABASE_URL, &self.logs_db),
+            (FIELD_ENCRYPTION_KEY, &self.field_encryption_key),
+        ] {
+            if value.is_empty() {
+                return Err(config::ConfigError::NotFound(secret.env.to_string()));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Lê os provedores OIDC do ambiente.
+///
+/// Para cada nome em `WS_OIDC_PROVIDERS` (separados por vírgula):
+///
+/// | Variável                          | Obrigatória | Padrão                  |
+/// |-----------------------------------|-------------|-------------------------|
+/// | WS_OIDC_<NOME>_ISSUER             | sim         |                         |
+/// | WS_OIDC_<NOME>_CLIENT_ID          | sim         |                         |
+/// | WS_OIDC_<NOME>_REDIRECT_URI       | sim         |                         |
+/// | WS_OIDC_<NOME>_CLIENT_SECRET      | não         | (cliente público, PKCE) |
+/// | WS_OIDC_<NOME>_SCOPES             | não         | `openid email profile`  |
+/// | WS_OIDC_<NOME>_CPF_CLAIM          | não         | `sub` para `govbr`      |
+/// | WS_OIDC_<NOME>_JIT_PROVISIONING   | não         | `false`                 |
+/// | WS_OIDC_<NOME>_DEFAULT_ROLE       | não         | `user`                  |
+/// | WS_OIDC_<NOME>_DISPLAY_NAME       | não         | nome do provedor        |
+fn load_oidc_providers() -> Result<Vec<OidcProviderSettings>, config::ConfigError> {
+    let Ok(names) = std::env::var("WS_OIDC_PROVIDERS") else {
+        return Ok(Vec::new());
+    };
+
+    names
+        .split(',')
+        .map(|n| n.trim().to_lowercase())
+        .filter(|n| !n.is_empty())
+        .map(|name| {
+            let prefix = format!("WS_OIDC_{}_", name.to_uppercase());
+            let optional = |key: &str| {
+                std::env::var(format!("{}{}", prefix, key))
+                    .ok()
+                    .map(|v| v.trim().to_string())
+                    .filter(|v| !v.is_empty())
+            };
+            let required = |key: &str| {
+                optional(key).ok_or_else(|| {
+                    config::ConfigError::NotFound(format!("{}{}", prefix, key))
+                })
+            };
+
+            let scopes = optional("SCOPES")
+                .unwrap_or_else(|| "openid email profile".to_string())
+                .split([' ', ','])
+                .filter(|s| !s.is_empty())
+                .map(str::to_string)
+                .collect();
+            let cpf_claim = optional("CPF_CLAIM")
+                .or_else(|| (name == "govbr").then(|| "sub".to_string()));
+
+            Ok(OidcProviderSettings {
+                display_name: optional("DISPLAY_NAME").unwrap_or_else(|| name.clone()),
+                issuer: required("ISSUER")?,
+                client_id: required("CLIENT_ID")?,
+                client_secret: optional("CLIENT_SECRET"),
+                redirect_uri: required("REDIRECT_URI")?,
+                scopes,
+                cpf_claim,
+                jit_provisioning: optional("JIT_PROVISIONING")
+                    .map(|v| matches!(v.to_lowercase().as_str(), "1" | "true" | "yes"))
+                    .unwrap_or(false),
+                default_role: optional("DEFAULT_ROLE").unwrap_or_else(|| "user".to_string()),
+                name,
+            })
+        })
+        .collect()
 }
@@ -1,6 +1,6 @@
 use application::errors::ServiceError;
 use axum::{
-    http::StatusCode,
+    http::{header, StatusCode},
     response::{IntoResponse, Response},
     Json,
 };
@@ -63,6 +63,13 @@ impl IntoResponse for AppError {
             _ => tracing::info!("Erro cliente na requisição: {:?}", self),
         }
 
+        let retry_after = match &self {
+            AppError::Service(ServiceError::TooManyRequests {
+                retry_after_secs, ..
+            }) => Some(*retry_after_secs),
+            _ => None,
+        };
+
         // 2. Determine status code and safe message for client
         let (status, error_message) = match self {
             AppError::Database(_) => (
@@ -102,6 +109,7 @@ impl IntoResponse for AppError {
                 ),
                 ServiceError::BadRequest(msg) => (StatusCode::BAD_REQUEST, msg),
                 ServiceError::NotFound(msg) => (StatusCode::NOT_FOUND, msg),
+                ServiceError::Forbidden(msg) => (StatusCode::FORBIDDEN, msg),
                 ServiceError::Conflict(msg) => (StatusCode::CONFLICT, msg),
                 ServiceError::Repository(msg) | ServiceError::RepositoryError(msg) => (
                     StatusCode::INTERNAL_SERVER_ERROR,
@@ -112,6 +120,9 @@ impl IntoResponse for AppError {
                     "Internal service error.".to_string(),
                 ),
                 ServiceError::OptimisticLockConflict(msg) => (StatusCode::CONFLICT, msg),
+                ServiceError::TooManyRequests { message, .. } => {
+                    (StatusCode::TOO_MANY_REQUESTS, message)
+                }
             },
             AppError::Unauthorized(msg) => (StatusCode::UNAUTHORIZED, msg),
             AppError::InvalidPassword => (
@@ -135,6 +146,9 @@ impl IntoResponse for AppError {
             "error": error_message,
         }));
 
-        (status, body).into_response()
+        match retry_after {
+            Some(secs) => (status, [(header::RETRY_AFTER, secs.to_string())], body).into_response(),
+            None => (status, body).into_response(),
+        }
     }
 }
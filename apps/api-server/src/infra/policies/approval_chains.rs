@@ -0,0 +1,46 @@
+use anyhow::Result;
+use casbin::{Enforcer, MgmtApi};
+
+use crate::utils::*;
+
+pub async fn seed(enforcer: &mut Enforcer) -> Result<()> {
+    // Configuração das cadeias de aprovação
+    //
+    // GET    /approval-chains       — lista (filtro por tipo de documento)
+    // POST   /approval-chains       — cria cadeia
+    // GET    /approval-chains/:id   — detalhe com etapas
+    // PUT    /approval-chains/:id   — substitui condições e etapas
+    // DELETE /approval-chains/:id   — desativa
+    let by_id = format!("{}/{{id}}", RESOURCE_ADMIN_APPROVAL_CHAINS);
+    for (path, method) in [
+        (RESOURCE_ADMIN_APPROVAL_CHAINS, ACTION_GET),
+        (RESOURCE_ADMIN_APPROVAL_CHAINS, ACTION_POST),
+        (by_id.as_str(), ACTION_GET),
+        (by_id.as_str(), ACTION_PUT),
+        (by_id.as_str(), ACTION_DELETE),
+    ] {
+        enforcer
+            .add_policy(str_vec![ROLE_ADMIN, path, method])
+            .await?;
+    }
+
+    // Etapas por documento. Requisições são aprovadas etapa a etapa pela
+    // própria rota /approve; quem decide cada etapa é verificado no serviço.
+    for (path, method) in [
+        ("/api/admin/requisitions/{id}/approval-steps", ACTION_GET),
+        ("/api/admin/transfers/{id}/approval-steps", ACTION_GET),
+        ("/api/admin/transfers/{id}/approve", ACTION_POST),
+        (
+            "/api/admin/disposal-requests/{id}/approval-steps",
+            ACTION_GET,
+        ),
+        ("/api/admin/disposal-requests/{id}/approve", ACTION_POST),
+    ] {
+        enforcer
+            .add_policy(str_vec![ROLE_ADMIN, path, method])
+            .await?;
+    }
+
+    tracing::info!("Políticas de Cadeias de Aprovação carregadas");
+    Ok(())
+}
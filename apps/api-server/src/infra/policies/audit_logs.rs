@@ -40,6 +40,13 @@ pub async fn seed(enforcer: &mut Enforcer) -> Result<()> {
             ACTION_GET
         ])
         .await?;
+    enforcer
+        .add_policy(str_vec![
+            ROLE_ADMIN,
+            format!("{}/{{id}}/changes", base),
+            ACTION_GET
+        ])
+        .await?;
     enforcer
         .add_policy(str_vec![
             ROLE_ADMIN,
@@ -47,6 +54,27 @@ pub async fn seed(enforcer: &mut Enforcer) -> Result<()> {
             ACTION_POST
         ])
         .await?;
+    enforcer
+        .add_policy(str_vec![
+            ROLE_ADMIN,
+            format!("{}/verify", base),
+            ACTION_GET
+        ])
+        .await?;
+    enforcer
+        .add_policy(str_vec![
+            ROLE_ADMIN,
+            format!("{}/checkpoints", base),
+            ACTION_GET
+        ])
+        .await?;
+    enforcer
+        .add_policy(str_vec![
+            ROLE_ADMIN,
+            format!("{}/checkpoints", base),
+            ACTION_POST
+        ])
+        .await?;
 
     tracing::info!("Políticas de Audit Logs carregadas");
     Ok(())
@@ -1,8 +1,8 @@
 use anyhow::Result;
-use casbin::Enforcer;
+use casbin::{Enforcer, MgmtApi};
 
 use super::{add_crud_policies, add_crud_tree_policies};
-use crate::utils::ROLE_ADMIN;
+use crate::utils::*;
 
 pub async fn seed(enforcer: &mut Enforcer) -> Result<()> {
     let base = "/api/admin/catalog";
@@ -35,6 +35,43 @@ pub async fn seed(enforcer: &mut Enforcer) -> Result<()> {
     add_crud_policies(enforcer, ROLE_ADMIN, &format!("{}/catser/classes", base)).await?;
     add_crud_policies(enforcer, ROLE_ADMIN, &format!("{}/catser/items", base)).await?;
 
+    // --- Busca textual ---
+    for path in &[
+        format!("{}/catmat/items/search", base),
+        format!("{}/catser/items/search", base),
+        format!("{}/autocomplete", base),
+    ] {
+        enforcer
+            .add_policy(str_vec![ROLE_ADMIN, path, ACTION_GET])
+            .await?;
+    }
+
+    // --- Fusão de itens duplicados ---
+    for (path, method) in &[
+        (format!("{}/catmat/items/duplicates", base), ACTION_GET),
+        (format!("{}/catmat/items/merge", base), ACTION_POST),
+        (format!("{}/catmat/items/redirects", base), ACTION_GET),
+    ] {
+        enforcer
+            .add_policy(str_vec![ROLE_ADMIN, path, method])
+            .await?;
+    }
+
+    // --- Sincronização com o ComprasGov ---
+    for (path, method) in &[
+        (format!("{}/sync", base), ACTION_POST),
+        (format!("{}/sync/runs", base), ACTION_GET),
+        (format!("{}/sync/runs/{{id}}", base), ACTION_GET),
+        (format!("{}/official/catmat/items", base), ACTION_GET),
+        (format!("{}/official/catmat/items/{{code}}/import", base), ACTION_POST),
+        (format!("{}/official/catser/items", base), ACTION_GET),
+        (format!("{}/official/catser/items/{{code}}/import", base), ACTION_POST),
+    ] {
+        enforcer
+            .add_policy(str_vec![ROLE_ADMIN, path, method])
+            .await?;
+    }
+
     tracing::info!("Políticas de Catalog carregadas (incluindo CATMAT e CATSER)");
     Ok(())
 }
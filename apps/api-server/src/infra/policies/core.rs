@@ -27,6 +27,13 @@ pub async fn seed(enforcer: &mut Enforcer) -> Result<()> {
         .add_policy(str_vec![ROLE_ADMIN, RESOURCE_ADMIN_POLICIES, ACTION_DELETE])
         .await?;
 
+    // Papéis por unidade organizacional
+    for method in [ACTION_GET, ACTION_POST, ACTION_DELETE] {
+        enforcer
+            .add_policy(str_vec![ROLE_ADMIN, RESOURCE_ADMIN_UNIT_ROLES, method])
+            .await?;
+    }
+
     // Users CRUD
     add_crud_policies(enforcer, ROLE_ADMIN, "/api/admin/users").await?;
 
@@ -0,0 +1,28 @@
+use anyhow::Result;
+use casbin::{Enforcer, MgmtApi};
+
+use crate::utils::*;
+
+pub async fn seed(enforcer: &mut Enforcer) -> Result<()> {
+    // Delegação de competência: cada usuário gerencia as próprias
+    // delegações (o serviço restringe ao titular e ao delegado)
+    //
+    // GET    /delegations       — concedidas e recebidas
+    // POST   /delegations       — delega a própria competência
+    // DELETE /delegations/:id   — revogação imediata
+    let by_id = format!("{}/{{id}}", RESOURCE_ADMIN_DELEGATIONS);
+    for role in [ROLE_ADMIN, ROLE_USER] {
+        for (path, method) in [
+            (RESOURCE_ADMIN_DELEGATIONS, ACTION_GET),
+            (RESOURCE_ADMIN_DELEGATIONS, ACTION_POST),
+            (by_id.as_str(), ACTION_DELETE),
+        ] {
+            enforcer
+                .add_policy(str_vec![role, path, method])
+                .await?;
+        }
+    }
+
+    tracing::info!("Políticas de Delegações carregadas");
+    Ok(())
+}
@@ -1,12 +1,35 @@
 use anyhow::Result;
-use casbin::Enforcer;
+use casbin::{Enforcer, MgmtApi};
 
 use super::add_crud_policies;
-use crate::utils::ROLE_ADMIN;
+use crate::utils::*;
 
 pub async fn seed(enforcer: &mut Enforcer) -> Result<()> {
     add_crud_policies(enforcer, ROLE_ADMIN, "/api/admin/fuelings").await?;
 
+    // Review queue (anomaly quarantine)
+    enforcer
+        .add_policy(str_vec![ROLE_ADMIN, "/api/admin/fuelings/review-queue", ACTION_GET])
+        .await?;
+    enforcer
+        .add_policy(str_vec![ROLE_ADMIN, "/api/admin/fuelings/{id}/review", ACTION_PUT])
+        .await?;
+
+    // Fuel card import and reconciliation
+    enforcer
+        .add_policy(str_vec![ROLE_ADMIN, "/api/admin/fuelings/card-imports", ACTION_POST])
+        .await?;
+    enforcer
+        .add_policy(str_vec![ROLE_ADMIN, "/api/admin/fuelings/card-imports/{id}", ACTION_GET])
+        .await?;
+    enforcer
+        .add_policy(str_vec![
+            ROLE_ADMIN,
+            "/api/admin/fuelings/card-imports/{id}/transactions",
+            ACTION_GET
+        ])
+        .await?;
+
     tracing::info!("Políticas de Fueling Management carregadas");
     Ok(())
 }
@@ -0,0 +1,18 @@
+use anyhow::Result;
+use casbin::{Enforcer, MgmtApi};
+
+use crate::utils::*;
+
+pub async fn seed(enforcer: &mut Enforcer) -> Result<()> {
+    // GET /history/:entity/:id — alterações campo a campo de uma entidade
+    enforcer
+        .add_policy(str_vec![
+            ROLE_ADMIN,
+            format!("{}/{{entity}}/{{id}}", RESOURCE_ADMIN_HISTORY),
+            ACTION_GET
+        ])
+        .await?;
+
+    tracing::info!("Políticas de Histórico de Alterações carregadas");
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use anyhow::Result;
+use casbin::{Enforcer, MgmtApi};
+
+use crate::utils::*;
+
+pub async fn seed(enforcer: &mut Enforcer) -> Result<()> {
+    // GET /lgpd/export — dados pessoais do titular
+    enforcer
+        .add_policy(str_vec![
+            ROLE_ADMIN,
+            format!("{}/export", RESOURCE_ADMIN_LGPD),
+            ACTION_GET
+        ])
+        .await?;
+
+    // POST /lgpd/anonymize — anonimização irreversível
+    enforcer
+        .add_policy(str_vec![
+            ROLE_ADMIN,
+            format!("{}/anonymize", RESOURCE_ADMIN_LGPD),
+            ACTION_POST
+        ])
+        .await?;
+
+    tracing::info!("Políticas de LGPD carregadas");
+    Ok(())
+}
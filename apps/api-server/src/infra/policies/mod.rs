@@ -11,15 +11,21 @@ mod audit_logs;
 mod budget_classifications;
 mod catalog;
 mod core;
+mod delegations;
+mod approval_chains;
 mod drivers;
 mod fleet;
 mod fuelings;
 mod geo_regions;
+mod history;
+mod lgpd;
 mod invoices;
 mod locations;
 mod organizational;
+mod reports;
 mod requisitions;
 mod suppliers;
+mod trips;
 mod vehicle_fines;
 mod warehouse;
 mod batches;
@@ -27,6 +33,7 @@ mod alerts;
 mod dashboard;
 mod abc_analysis;
 mod legacy_import;
+mod security;
 
 use crate::utils::*;
 
@@ -77,6 +84,7 @@ pub async fn seed_all_policies(enforcer: &mut Enforcer) -> Result<()> {
     warehouse::seed(enforcer).await?;
     requisitions::seed(enforcer).await?;
     fleet::seed(enforcer).await?;
+    trips::seed(enforcer).await?;
     suppliers::seed(enforcer).await?;
     drivers::seed(enforcer).await?;
     fuelings::seed(enforcer).await?;
@@ -87,5 +95,11 @@ pub async fn seed_all_policies(enforcer: &mut Enforcer) -> Result<()> {
     dashboard::seed(enforcer).await?;
     abc_analysis::seed(enforcer).await?;
     legacy_import::seed(enforcer).await?;
+    security::seed(enforcer).await?;
+    delegations::seed(enforcer).await?;
+    approval_chains::seed(enforcer).await?;
+    history::seed(enforcer).await?;
+    lgpd::seed(enforcer).await?;
+    reports::seed(enforcer).await?;
     Ok(())
 }
@@ -90,6 +90,36 @@ pub async fn seed(enforcer: &mut Enforcer) -> Result<()> {
             ACTION_POST
         ])
         .await?;
+    enforcer
+        .add_policy(str_vec![
+            ROLE_ADMIN,
+            format!("{}/units/{{id}}/lineage", base),
+            ACTION_GET
+        ])
+        .await?;
+
+    // Unit Restructurings (merge/split)
+    enforcer
+        .add_policy(str_vec![
+            ROLE_ADMIN,
+            format!("{}/restructurings", base),
+            ACTION_GET
+        ])
+        .await?;
+    enforcer
+        .add_policy(str_vec![
+            ROLE_ADMIN,
+            format!("{}/restructurings", base),
+            ACTION_POST
+        ])
+        .await?;
+    enforcer
+        .add_policy(str_vec![
+            ROLE_ADMIN,
+            format!("{}/restructurings/{{id}}", base),
+            ACTION_GET
+        ])
+        .await?;
 
     // SIORG Sync
     let sync = format!("{}/sync", base);
@@ -226,6 +256,43 @@ pub async fn seed(enforcer: &mut Enforcer) -> Result<()> {
         ])
         .await?;
 
+    // Change Previews
+    enforcer
+        .add_policy(str_vec![
+            ROLE_ADMIN,
+            format!("{}/previews", sync),
+            ACTION_GET
+        ])
+        .await?;
+    enforcer
+        .add_policy(str_vec![
+            ROLE_ADMIN,
+            format!("{}/previews", sync),
+            ACTION_POST
+        ])
+        .await?;
+    enforcer
+        .add_policy(str_vec![
+            ROLE_ADMIN,
+            format!("{}/previews/*", sync),
+            ACTION_GET
+        ])
+        .await?;
+    enforcer
+        .add_policy(str_vec![
+            ROLE_ADMIN,
+            format!("{}/previews/*/apply", sync),
+            ACTION_POST
+        ])
+        .await?;
+    enforcer
+        .add_policy(str_vec![
+            ROLE_ADMIN,
+            format!("{}/previews/*/discard", sync),
+            ACTION_POST
+        ])
+        .await?;
+
     // Sync Statistics
     enforcer
         .add_policy(str_vec![
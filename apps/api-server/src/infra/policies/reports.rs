@@ -0,0 +1,26 @@
+use anyhow::Result;
+use casbin::{Enforcer, MgmtApi};
+
+use crate::utils::*;
+
+pub async fn seed(enforcer: &mut Enforcer) -> Result<()> {
+    let base = "/api/admin/reports";
+
+    // GET /reports/requisition-cost-by-unit — custos agregados pela estrutura vigente em uma data
+    // GET /reports/fleet-cost-by-unit
+    // GET /reports/fuel-consumption, /reports/fleet-summary e /reports/vehicles/{id}/dashboard
+    for path in &[
+        format!("{}/requisition-cost-by-unit", base),
+        format!("{}/fleet-cost-by-unit", base),
+        format!("{}/fuel-consumption", base),
+        format!("{}/fleet-summary", base),
+        format!("{}/vehicles/{{id}}/dashboard", base),
+    ] {
+        enforcer
+            .add_policy(str_vec![ROLE_ADMIN, path, ACTION_GET])
+            .await?;
+    }
+
+    tracing::info!("Políticas de Relatórios carregadas");
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use anyhow::Result;
+use casbin::{Enforcer, MgmtApi};
+
+use crate::utils::*;
+
+pub async fn seed(enforcer: &mut Enforcer) -> Result<()> {
+    let base = "/api/admin/security";
+
+    // Segurança de autenticação: somente ROLE_ADMIN
+    //
+    // GET    /security/lockouts                     — contas bloqueadas / com falhas
+    // DELETE /security/lockouts/:user_id            — desbloqueio manual
+    // GET    /security/field-key-rotations          — keyring e rotações executadas
+    // POST   /security/field-key-rotations          — recriptografa com a chave atual
+    // GET    /security/field-key-rotations/:id      — progresso
+    // POST   /security/field-key-rotations/:id/resume — retoma rotação com falha
+    // GET    /security/jwt-keys                     — keyring de assinatura dos JWTs
+    // POST   /security/jwt-keys/rotate              — nova chave de assinatura
+    // GET    /service-principals                    — integrações cadastradas
+    // POST   /service-principals                    — nova integração
+    // PATCH  /service-principals/:id                — altera role / ativa / desativa
+    // GET    /service-principals/:id/api-keys       — chaves da integração
+    // POST   /service-principals/:id/api-keys       — emite chave
+    // POST   /api-keys/:id/rotate                   — rotação com período de transição
+    // DELETE /api-keys/:id                          — revogação imediata
+    for (path, method) in &[
+        (format!("{}/lockouts", base), ACTION_GET),
+        (format!("{}/lockouts/{{user_id}}", base), ACTION_DELETE),
+        (format!("{}/field-key-rotations", base), ACTION_GET),
+        (format!("{}/field-key-rotations", base), ACTION_POST),
+        (format!("{}/field-key-rotations/{{id}}", base), ACTION_GET),
+        (format!("{}/field-key-rotations/{{id}}/resume", base), ACTION_POST),
+        (format!("{}/jwt-keys", base), ACTION_GET),
+        (format!("{}/jwt-keys/rotate", base), ACTION_POST),
+        ("/api/admin/service-principals".to_string(), ACTION_GET),
+        ("/api/admin/service-principals".to_string(), ACTION_POST),
+        ("/api/admin/service-principals/{id}".to_string(), ACTION_PATCH),
+        ("/api/admin/service-principals/{id}/api-keys".to_string(), ACTION_GET),
+        ("/api/admin/service-principals/{id}/api-keys".to_string(), ACTION_POST),
+        ("/api/admin/api-keys/{id}/rotate".to_string(), ACTION_POST),
+        ("/api/admin/api-keys/{id}".to_string(), ACTION_DELETE),
+    ] {
+        enforcer
+            .add_policy(str_vec![ROLE_ADMIN, path, method])
+            .await?;
+    }
+
+    tracing::info!("Políticas de Segurança carregadas");
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use anyhow::Result;
+use casbin::{Enforcer, MgmtApi};
+
+use crate::utils::*;
+
+pub async fn seed(enforcer: &mut Enforcer) -> Result<()> {
+    let base = "/api/admin/trips";
+
+    enforcer
+        .add_policy(str_vec![ROLE_ADMIN, base, ACTION_GET])
+        .await?;
+    enforcer
+        .add_policy(str_vec![ROLE_ADMIN, base, ACTION_POST])
+        .await?;
+    enforcer
+        .add_policy(str_vec![ROLE_ADMIN, format!("{}/{{id}}", base), ACTION_GET])
+        .await?;
+
+    // Agenda e disponibilidade (RF-VIG-03)
+    for path in [
+        "calendar/vehicles",
+        "calendar/drivers",
+        "available-vehicles",
+    ] {
+        enforcer
+            .add_policy(str_vec![
+                ROLE_ADMIN,
+                format!("{}/{}", base, path),
+                ACTION_GET
+            ])
+            .await?;
+    }
+
+    // Sugestões de alocação (RF-VIG-05)
+    let suggestions = format!("{}/{{id}}/suggestions", base);
+    enforcer
+        .add_policy(str_vec![ROLE_ADMIN, &suggestions, ACTION_GET])
+        .await?;
+    enforcer
+        .add_policy(str_vec![ROLE_ADMIN, &suggestions, ACTION_POST])
+        .await?;
+
+    // Transições da FSM
+    for action in [
+        "review", "allocate", "checkout", "checkin", "finalize", "conflict", "cancel",
+    ] {
+        enforcer
+            .add_policy(str_vec![
+                ROLE_ADMIN,
+                format!("{}/{{id}}/{}", base, action),
+                ACTION_PUT
+            ])
+            .await?;
+    }
+
+    tracing::info!("Políticas de Trips carregadas");
+    Ok(())
+}
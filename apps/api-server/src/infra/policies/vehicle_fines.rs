@@ -34,6 +34,47 @@ pub async fn seed(enforcer: &mut Enforcer) -> Result<()> {
         ])
         .await?;
 
+    // Driver identification and deadlines
+    enforcer
+        .add_policy(str_vec![ROLE_ADMIN, format!("{}/fines/deadlines", base), ACTION_GET])
+        .await?;
+    enforcer
+        .add_policy(str_vec![
+            ROLE_ADMIN,
+            format!("{}/fines/{{id}}/identify-driver", base),
+            ACTION_POST
+        ])
+        .await?;
+    enforcer
+        .add_policy(str_vec![
+            ROLE_ADMIN,
+            format!("{}/fines/{{id}}/identification-form", base),
+            ACTION_GET
+        ])
+        .await?;
+
+    // Driver points (CNH)
+    enforcer
+        .add_policy(str_vec![ROLE_ADMIN, format!("{}/driver-points", base), ACTION_GET])
+        .await?;
+    enforcer
+        .add_policy(str_vec![
+            ROLE_ADMIN,
+            format!("{}/driver-points/{{id}}", base),
+            ACTION_GET
+        ])
+        .await?;
+    enforcer
+        .add_policy(str_vec![ROLE_ADMIN, format!("{}/driver-points/alerts", base), ACTION_GET])
+        .await?;
+    enforcer
+        .add_policy(str_vec![
+            ROLE_ADMIN,
+            format!("{}/driver-points/alerts/{{id}}/acknowledge", base),
+            ACTION_POST
+        ])
+        .await?;
+
     tracing::info!("Políticas de Vehicle Fines carregadas");
     Ok(())
 }
@@ -2,15 +2,29 @@ use crate::infra::config::Config;
 use application::services::audit_services::AuditService;
 use application::services::auth_service::AuthService;
 use application::services::budget_classifications_service::BudgetClassificationsService;
+use application::services::catalog_merge_service::CatalogMergeService;
+use application::services::catalog_search_service::CatalogSearchService;
 use application::services::catalog_service::CatalogService;
 use application::services::geo_regions_service::GeoRegionsService;
 use application::services::mfa_service::MfaService;
+use application::services::oidc_service::OidcService;
+use application::services::webauthn_service::WebauthnService;
+use application::services::login_throttle_service::LoginThrottleService;
+use application::services::api_key_service::ApiKeyService;
+use application::services::field_key_rotation_service::FieldKeyRotationService;
+use application::services::jwt_key_service::JwtKeyService;
+use application::services::delegation_service::DelegationService;
+use application::services::approval_chain_service::ApprovalChainService;
+use application::services::audit_integrity_service::AuditIntegrityService;
+use application::services::entity_history_service::EntityHistoryService;
+use application::services::lgpd_service::LgpdService;
 use application::services::organizational_service::{
     OrganizationService, OrganizationalUnitCategoryService, OrganizationalUnitService,
     OrganizationalUnitTypeService, SiorgEsferaService, SiorgNaturezaJuridicaService,
     SiorgPoderService, SystemSettingsService,
 };
 use application::services::requisition_service::RequisitionService;
+use application::services::unit_restructuring_service::UnitRestructuringService;
 use application::services::user_service::UserService;
 use application::services::supplier_service::SupplierService;
 use application::services::vehicle_service::VehicleService;
@@ -30,14 +44,19 @@ use application::services::alert_service::AlertService;
 use application::services::dashboard_service::DashboardService;
 use application::services::abc_analysis_service::AbcAnalysisService;
 use application::services::legacy_import_service::LegacyImportService;
+use application::services::fuel_card_service::FuelCardService;
 use application::external::CircuitBreakerRegistry;
 use application::services::asset_management_service::AssetManagementService;
 use application::services::trip_service::TripService;
 use application::services::maintenance_service::MaintenanceService;
 use application::services::fleet_report_service::FleetReportService;
+use application::services::unit_cost_report_service::UnitCostReportService;
 use application::external::SiorgSyncService;
+use application::external::CatalogSyncService;
 use casbin::Enforcer;
+use core_services::field_encryption::FieldKeyring;
 use core_services::jwt::JwtService;
+use domain::models::UnitScope;
 use domain::ports::{
     BuildingRepositoryPort, BuildingTypeRepositoryPort, FloorRepositoryPort, SiteRepositoryPort,
     SiorgHistoryRepositoryPort, SiorgSyncQueueRepositoryPort, SpaceRepositoryPort,
@@ -51,27 +70,45 @@ use tokio::sync::RwLock;
 
 pub type SharedEnforcer = Arc<RwLock<Enforcer>>;
 pub type PolicyCache = Cache<String, bool>;
+/// Escopo por unidade resolvido para `sub:obj:act` (`None` = nenhum papel por unidade permite)
+pub type UnitScopeCache = Cache<String, Option<UnitScope>>;
 
 #[derive(Clone)]
 pub struct AppState {
     pub enforcer: SharedEnforcer,
     pub policy_cache: PolicyCache,
+    pub unit_scope_cache: UnitScopeCache,
     pub db_pool_auth: PgPool,
     pub db_pool_logs: PgPool,
     pub jwt_service: Arc<JwtService>,
     pub email_service: Arc<dyn EmailSender + Send + Sync>,
     pub audit_service: AuditService,
+    pub audit_integrity_service: Arc<AuditIntegrityService>,
+    pub entity_history_service: Arc<EntityHistoryService>,
+    pub lgpd_service: Arc<LgpdService>,
     pub auth_service: Arc<AuthService>,
+    pub login_throttle_service: Arc<LoginThrottleService>,
+    pub api_key_service: Arc<ApiKeyService>,
+    pub field_key_rotation_service: Arc<FieldKeyRotationService>,
+    pub jwt_key_service: Arc<JwtKeyService>,
+    pub delegation_service: Arc<DelegationService>,
+    pub approval_chain_service: Arc<ApprovalChainService>,
     pub user_service: Arc<UserService>,
     pub mfa_service: Arc<MfaService>,
+    pub webauthn_service: Arc<WebauthnService>,
+    pub oidc_service: Arc<OidcService>,
     pub location_service: Arc<GeoRegionsService>,
     pub budget_classifications_service: Arc<BudgetClassificationsService>,
     pub catalog_service: Arc<CatalogService>,
+    pub catalog_merge_service: Arc<CatalogMergeService>,
+    pub catalog_search_service: Arc<CatalogSearchService>,
+    pub catalog_sync_service: Arc<CatalogSyncService>,
     pub system_settings_service: Arc<SystemSettingsService>,
     pub organization_service: Arc<OrganizationService>,
     pub organizational_unit_category_service: Arc<OrganizationalUnitCategoryService>,
     pub organizational_unit_type_service: Arc<OrganizationalUnitTypeService>,
     pub organizational_unit_service: Arc<OrganizationalUnitService>,
+    pub unit_restructuring_service: Arc<UnitRestructuringService>,
     pub siorg_sync_service: Arc<SiorgSyncService>,
     pub siorg_sync_queue_repository: Arc<dyn SiorgSyncQueueRepositoryPort>,
     pub siorg_history_repository: Arc<dyn SiorgHistoryRepositoryPort>,
@@ -98,13 +135,15 @@ pub struct AppState {
     pub trip_service: Arc<TripService>,
     pub maintenance_service: Arc<MaintenanceService>,
     pub fleet_report_service: Arc<FleetReportService>,
+    pub unit_cost_report_service: Arc<UnitCostReportService>,
     pub alert_service: Arc<AlertService>,
     pub dashboard_service: Arc<DashboardService>,
     pub abc_analysis_service: Arc<AbcAnalysisService>,
     pub legacy_import_service: Arc<LegacyImportService>,
+    pub fuel_card_service: Arc<FuelCardService>,
     pub config: Arc<Config>,
-    /// AES-256-GCM key for field-level encryption (email, MFA secrets).
-    pub field_encryption_key: [u8; 32],
+    /// Versioned AES-256-GCM keys for field-level encryption (email, MFA secrets).
+    pub field_keyring: FieldKeyring,
     // Repositories for direct access in public handlers
     pub site_repository: Arc<dyn SiteRepositoryPort>,
     pub building_repository: Arc<dyn BuildingRepositoryPort>,
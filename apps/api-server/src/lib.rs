@@ -1,17 +1,21 @@
 use anyhow::{Context, Result};
 use application::services::audit_services::AuditService;
 use moka::future::Cache;
+use sqlx::postgres::PgConnectOptions;
 use sqlx::PgPool;
 use std::net::SocketAddr;
+use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::net::TcpListener;
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
 // Imports de Portas e Serviços
 use application::services::{
     auth_service::AuthService,
     budget_classifications_service::BudgetClassificationsService,
+    catalog_merge_service::CatalogMergeService,
+    catalog_search_service::CatalogSearchService,
     catalog_service::CatalogService,
     driver_service::DriverService,
     fueling_service::FuelingService,
@@ -32,6 +36,7 @@ use application::services::{
     trip_service::TripService,
     maintenance_service::MaintenanceService,
     fleet_report_service::FleetReportService,
+    unit_cost_report_service::UnitCostReportService,
     inventory_service::InventoryService,
     stock_movement_service::StockMovementService,
     stock_transfer_service::StockTransferService,
@@ -44,28 +49,43 @@ use application::services::{
     dashboard_service::DashboardService,
     abc_analysis_service::AbcAnalysisService,
     legacy_import_service::LegacyImportService,
+    fuel_card_service::FuelCardService,
+    oidc_service::OidcService,
+    webauthn_service::{WebauthnService, WebauthnSettings},
+    login_throttle_service::{LoginThrottleService, LoginThrottleSettings},
+    api_key_service::ApiKeyService,
+    field_key_rotation_service::FieldKeyRotationService,
+    jwt_key_service::JwtKeyService,
+    delegation_service::DelegationService,
+    approval_chain_service::ApprovalChainService,
+    audit_integrity_service::AuditIntegrityService,
+    entity_history_service::EntityHistoryService,
+    lgpd_service::LgpdService,
 };
 use domain::ports::{
     AuthRepositoryPort, BudgetClassificationRepositoryPort, BuildingRepositoryPort,
     OdometerReadingRepositoryPort, VehicleTripRepositoryPort, MaintenanceOrderRepositoryPort,
-    FleetReportRepositoryPort,
+    FleetReportRepositoryPort, UnitCostReportRepositoryPort,
     VehicleDepartmentTransferRepositoryPort, DepreciationConfigRepositoryPort,
     VehicleIncidentRepositoryPort, VehicleDisposalRepositoryPort,
     FleetFuelCatalogRepositoryPort, FleetMaintenanceServiceRepositoryPort,
     FleetSystemParamRepositoryPort, FleetChecklistTemplateRepositoryPort,
-    BuildingTypeRepositoryPort, CatmatClassRepositoryPort, CatmatGroupRepositoryPort,
+    BuildingTypeRepositoryPort, CatalogMergeRepositoryPort, CatalogSearchRepositoryPort, CatalogSyncRepositoryPort, FieldKeyRotationRepositoryPort, JwtSigningKeyRepositoryPort, CatmatClassRepositoryPort,
+    CatmatGroupRepositoryPort,
     CatmatItemRepositoryPort, CatmatPdmRepositoryPort, CatserClassRepositoryPort,
     CatserDivisionRepositoryPort, CatserGroupRepositoryPort, CatserItemRepositoryPort,
     CatserSectionRepositoryPort, CityRepositoryPort, CountryRepositoryPort, DriverRepositoryPort,
     EmailServicePort, FloorRepositoryPort, FuelingRepositoryPort, InvoiceAdjustmentRepositoryPort,
-    InvoiceItemRepositoryPort, InvoiceRepositoryPort, MfaRepositoryPort,
+    InvoiceItemRepositoryPort, InvoiceRepositoryPort, MfaRepositoryPort, OidcRepositoryPort, WebauthnRepositoryPort, LoginThrottleRepositoryPort, ApiKeyRepositoryPort,
+    ApprovalDelegationRepositoryPort, ApprovalChainRepositoryPort, AuditIntegrityRepositoryPort,
+    EntityHistoryRepositoryPort, LgpdRepositoryPort,
     OrganizationRepositoryPort, OrganizationalUnitCategoryRepositoryPort,
     OrganizationalUnitRepositoryPort, OrganizationalUnitTypeRepositoryPort,
     RequisitionItemRepositoryPort, RequisitionRepositoryPort, SiorgEsferaRepositoryPort,
-    SiorgHistoryRepositoryPort, SiorgNaturezaJuridicaRepositoryPort, SiorgPoderRepositoryPort,
+    SiorgChangePreviewRepositoryPort, SiorgHistoryRepositoryPort, SiorgNaturezaJuridicaRepositoryPort, SiorgPoderRepositoryPort,
     SiorgSyncQueueRepositoryPort, SiteRepositoryPort, SpaceRepositoryPort, SpaceTypeRepositoryPort,
     StateRepositoryPort, SupplierRepositoryPort, SystemSettingsRepositoryPort,
-    UnitConversionRepositoryPort, UnitOfMeasureRepositoryPort, UserRepositoryPort,
+    UnitConversionRepositoryPort, UnitOfMeasureRepositoryPort, UnitRestructuringRepositoryPort, UserRepositoryPort,
     VehicleCategoryRepositoryPort, VehicleColorRepositoryPort, VehicleDocumentRepositoryPort,
     VehicleFineRepositoryPort, VehicleFineStatusHistoryRepositoryPort,
     VehicleFineTypeRepositoryPort, VehicleFuelTypeRepositoryPort, VehicleMakeRepositoryPort,
@@ -79,6 +99,7 @@ use domain::ports::alert::StockAlertRepositoryPort;
 use domain::ports::dashboard::DashboardRepositoryPort;
 use domain::ports::abc_analysis::AbcAnalysisRepositoryPort;
 use domain::ports::legacy_import::LegacyImportRepositoryPort;
+use domain::ports::fuel_card::FuelCardRepositoryPort;
 use persistence::repositories::{
     auth_repository::AuthRepository,
     budget_classifications_repository::BudgetClassificationRepository,
@@ -88,6 +109,11 @@ use persistence::repositories::{
         CatserItemRepository, CatserSectionRepository, UnitConversionRepository,
         UnitOfMeasureRepository,
     },
+    catalog_merge_repository::CatalogMergeRepository,
+    catalog_search_repository::CatalogSearchRepository,
+    catalog_sync_repository::CatalogSyncRepository,
+    field_key_rotation_repository::FieldKeyRotationRepository,
+    jwt_signing_key_repository::JwtSigningKeyRepository,
     driver_repository::DriverRepository,
     facilities_repository::{
         BuildingRepository, BuildingTypeRepository, FloorRepository, SiteRepository,
@@ -98,6 +124,15 @@ use persistence::repositories::{
     invoice_adjustment_repository::InvoiceAdjustmentRepository,
     invoice_repository::{InvoiceItemRepository, InvoiceRepository},
     mfa_repository::MfaRepository,
+    oidc_repository::OidcRepository,
+    webauthn_repository::WebauthnRepository,
+    login_throttle_repository::LoginThrottleRepository,
+    api_key_repository::ApiKeyRepository,
+    delegation_repository::ApprovalDelegationRepository,
+    approval_chain_repository::ApprovalChainRepository,
+    audit_integrity_repository::AuditIntegrityRepository,
+    entity_history_repository::EntityHistoryRepository,
+    lgpd_repository::LgpdRepository,
     organizational_repository::{
         OrganizationRepository, OrganizationalUnitCategoryRepository, OrganizationalUnitRepository,
         OrganizationalUnitTypeRepository, SiorgEsferaRepository, SiorgNaturezaJuridicaRepository,
@@ -120,13 +155,14 @@ use persistence::repositories::{
     odometer_repository::OdometerReadingRepository,
     trip_repository::VehicleTripRepository,
     maintenance_repository::MaintenanceOrderRepository,
-    report_repository::FleetReportRepository,
+    report_repository::{FleetReportRepository, UnitCostReportRepository},
     financial_event_repository::FinancialEventRepository,
     batch_repository::{WarehouseBatchStockRepository, BatchQualityOccurrenceRepository},
     alert_repository::StockAlertRepository,
     dashboard_repository::DashboardRepository,
     abc_analysis_repository::AbcAnalysisRepository,
     legacy_import_repository::LegacyImportRepository,
+    fuel_card_repository::FuelCardRepository,
     asset_management_repository::{
         VehicleDepartmentTransferRepository,
         DepreciationConfigRepository,
@@ -140,8 +176,9 @@ use persistence::repositories::{
 };
 
 // Core & Infra
-use core_services::field_encryption;
+use core_services::field_encryption::FieldKeyring;
 use core_services::jwt::JwtService;
+use core_services::secrets::SecretStore;
 use email_service::{EmailConfig, EmailSender, EmailService}; // Removido MockEmailService
 
 use crate::infra::config::Config;
@@ -170,24 +207,106 @@ pub fn build_application_state(
 ) -> state::AppState {
     let audit_service = AuditService::new(pool_logs.clone());
 
-    let enc_key = field_encryption::parse_key(&config.field_encryption_key).expect(
-        "WS_FIELD_ENCRYPTION_KEY must be a valid 64-char hex string (openssl rand -hex 32)",
+    // Hash-chained audit log: signed daily checkpoints and sealed archives
+    let audit_integrity_repo_port: Arc<dyn AuditIntegrityRepositoryPort> =
+        Arc::new(AuditIntegrityRepository::new(pool_logs.clone()));
+    let audit_integrity_service = Arc::new(AuditIntegrityService::new(
+        audit_integrity_repo_port,
+        jwt_service.clone(),
+        config.audit_archive_dir.clone(),
+    ));
+
+    // Field-level change history (main database)
+    let entity_history_repo_port: Arc<dyn EntityHistoryRepositoryPort> =
+        Arc::new(EntityHistoryRepository::new(pool_auth.clone()));
+    let entity_history_service = Arc::new(EntityHistoryService::new(entity_history_repo_port));
+
+    let keyring = FieldKeyring::from_config(
+        &config.field_encryption_key,
+        config.field_encryption_key_id,
+        &config.field_encryption_previous_keys,
+    )
+    .expect(
+        "WS_FIELD_ENCRYPTION_KEY must be a valid 64-char hex string (openssl rand -hex 32) \
+         and WS_FIELD_ENCRYPTION_PREVIOUS_KEYS a list of <id>:<hex key>",
     );
 
     let user_repo_port: Arc<dyn UserRepositoryPort> =
-        Arc::new(UserRepository::new(pool_auth.clone(), enc_key));
+        Arc::new(UserRepository::new(pool_auth.clone(), keyring.clone()));
+
+    // LGPD data-subject export and anonymization (audit log read from the logs database)
+    let lgpd_repo_port: Arc<dyn LgpdRepositoryPort> = Arc::new(LgpdRepository::new(
+        pool_auth.clone(),
+        pool_logs.clone(),
+        keyring.clone(),
+    ));
+    let lgpd_service = Arc::new(LgpdService::new(
+        lgpd_repo_port,
+        audit_integrity_service.clone(),
+    ));
 
     let auth_repo_port: Arc<dyn AuthRepositoryPort> =
         Arc::new(AuthRepository::new(pool_auth.clone()));
 
     let mfa_repo_port: Arc<dyn MfaRepositoryPort> =
-        Arc::new(MfaRepository::new(pool_auth.clone(), enc_key));
+        Arc::new(MfaRepository::new(pool_auth.clone(), keyring.clone()));
+
+    // Recriptografia dos campos cifrados após a rotação da chave
+    let field_key_rotation_repo_port: Arc<dyn FieldKeyRotationRepositoryPort> =
+        Arc::new(FieldKeyRotationRepository::new(pool_auth.clone(), keyring.clone()));
+    let field_key_rotation_service = Arc::new(FieldKeyRotationService::new(
+        field_key_rotation_repo_port,
+        keyring.current_id(),
+    ));
+
+    // Keyring de assinatura dos JWTs (chaves privadas cifradas com a chave de campos)
+    let jwt_signing_key_repo_port: Arc<dyn JwtSigningKeyRepositoryPort> =
+        Arc::new(JwtSigningKeyRepository::new(pool_auth.clone(), keyring.clone()));
+    let jwt_key_service = Arc::new(JwtKeyService::new(
+        jwt_signing_key_repo_port,
+        jwt_service.clone(),
+        config.jwt_key_grace_period_secs,
+    ));
+
+    let login_throttle_repo_port: Arc<dyn LoginThrottleRepositoryPort> =
+        Arc::new(LoginThrottleRepository::new(pool_auth.clone()));
+    let login_throttle_service = Arc::new(LoginThrottleService::new(
+        login_throttle_repo_port,
+        user_repo_port.clone(),
+        email_service_port.clone(),
+        LoginThrottleSettings {
+            max_failures: config.login_max_failures,
+            base_lockout_secs: config.login_lockout_base_secs,
+            max_lockout_secs: config.login_lockout_max_secs,
+            ip_max_failures: config.login_ip_max_failures,
+            subnet_max_failures: config.login_subnet_max_failures,
+            ..Default::default()
+        },
+    ));
+
+    let api_key_repo_port: Arc<dyn ApiKeyRepositoryPort> =
+        Arc::new(ApiKeyRepository::new(pool_auth.clone()));
+    let api_key_service = Arc::new(ApiKeyService::new(api_key_repo_port));
+
+    // Approval delegations (honored by requisition, trip and transfer approvals)
+    let delegation_repo_port: Arc<dyn ApprovalDelegationRepositoryPort> =
+        Arc::new(ApprovalDelegationRepository::new(pool_auth.clone()));
+    let delegation_service = Arc::new(DelegationService::new(
+        delegation_repo_port,
+        user_repo_port.clone(),
+    ));
+
+    // Multi-level approval chains (requisitions, stock transfers, disposal requests)
+    let approval_chain_repo_port: Arc<dyn ApprovalChainRepositoryPort> =
+        Arc::new(ApprovalChainRepository::new(pool_auth.clone()));
+    let approval_chain_service = Arc::new(ApprovalChainService::new(approval_chain_repo_port));
 
     let auth_service = Arc::new(AuthService::new(
         user_repo_port.clone(),
         auth_repo_port.clone(),
         email_service_port.clone(),
         jwt_service.clone(),
+        login_throttle_service.clone(),
     ));
 
     let user_service = Arc::new(UserService::new(
@@ -197,12 +316,40 @@ pub fn build_application_state(
         jwt_service.clone(),
     ));
 
+    let webauthn_repo_port: Arc<dyn WebauthnRepositoryPort> =
+        Arc::new(WebauthnRepository::new(pool_auth.clone()));
+    let webauthn_service = Arc::new(
+        WebauthnService::new(
+            webauthn_repo_port,
+            user_repo_port.clone(),
+            &WebauthnSettings {
+                rp_id: config.webauthn_rp_id.clone(),
+                rp_origin: config.webauthn_rp_origin.clone(),
+                rp_name: config.webauthn_rp_name.clone(),
+            },
+        )
+        .expect("Invalid WebAuthn configuration (WS_WEBAUTHN_RP_ID / WS_WEBAUTHN_RP_ORIGIN)"),
+    );
+
     let mfa_service = Arc::new(MfaService::new(
         mfa_repo_port.clone(),
         user_repo_port.clone(),
         auth_repo_port.clone(),
         email_service_port.clone(),
         jwt_service.clone(),
+        webauthn_service.clone(),
+    ));
+
+    let oidc_repo_port: Arc<dyn OidcRepositoryPort> =
+        Arc::new(OidcRepository::new(pool_auth.clone(), keyring.clone()));
+    let oidc_client = Arc::new(
+        application::external::OidcClient::new().expect("Failed to create OIDC client"),
+    );
+    let oidc_service = Arc::new(OidcService::new(
+        oidc_repo_port,
+        user_repo_port.clone(),
+        oidc_client,
+        config.oidc_providers.clone(),
     ));
 
     let country_repo_port: Arc<dyn CountryRepositoryPort> =
@@ -261,8 +408,8 @@ pub fn build_application_state(
     let catser_item_repo_port: Arc<dyn CatserItemRepositoryPort> =
         Arc::new(CatserItemRepository::new(pool_auth.clone()));
 
-    let catalog_service = Arc::new(CatalogService::new(
-        unit_repo_port,
+    let catalog_svc_builder = CatalogService::new(
+        unit_repo_port.clone(),
         conversion_repo_port,
         catmat_group_repo_port,
         catmat_class_repo_port,
@@ -273,7 +420,7 @@ pub fn build_application_state(
         catser_group_repo_port,
         catser_class_repo_port,
         catser_item_repo_port,
-    ));
+    );
 
     // Organizational repositories
     let pool_auth_arc = Arc::new(pool_auth.clone());
@@ -331,7 +478,7 @@ pub fn build_application_state(
     // SIORG Sync Service
     let siorg_base_url = std::env::var("SIORG_API_URL")
         .unwrap_or_else(|_| "https://estruturaorganizacional.dados.gov.br/doc".to_string());
-    let siorg_token = std::env::var("SIORG_API_TOKEN").ok();
+    let siorg_token = config.secrets.handle(&infra::config::SIORG_API_TOKEN);
 
     let siorg_client = Arc::new(
         application::external::SiorgClient::new(siorg_base_url, siorg_token)
@@ -344,19 +491,40 @@ pub fn build_application_state(
     let siorg_poder_service = Arc::new(SiorgPoderService::new(poder_repo_port.clone()));
     let siorg_esfera_service = Arc::new(SiorgEsferaService::new(esfera_repo_port.clone()));
 
-    let siorg_sync_service = Arc::new(application::external::SiorgSyncService::new(
-        siorg_client,
-        organization_repo_port,
-        organizational_unit_repo_port,
-        unit_category_repo_port,
-        unit_type_repo_port,
-        system_settings_repo_port.clone(),
-        natureza_juridica_repo_port,
-        poder_repo_port,
-        esfera_repo_port,
-        siorg_history_repo_port.clone(),
-        pool_auth.clone(),
-    ));
+    // Prévia das alterações de estrutura, aprovada antes de ser aplicada
+    let siorg_change_preview_repo_port: Arc<dyn SiorgChangePreviewRepositoryPort> = Arc::new(
+        persistence::repositories::siorg_preview_repository::SiorgChangePreviewRepository::new(
+            pool_auth.clone(),
+        ),
+    );
+    let siorg_sync_service = Arc::new(
+        application::external::SiorgSyncService::new(
+            siorg_client,
+            organization_repo_port,
+            organizational_unit_repo_port,
+            unit_category_repo_port,
+            unit_type_repo_port,
+            system_settings_repo_port.clone(),
+            natureza_juridica_repo_port,
+            poder_repo_port,
+            esfera_repo_port,
+            siorg_history_repo_port.clone(),
+            pool_auth.clone(),
+        )
+        .with_change_previews(siorg_change_preview_repo_port),
+    );
+
+    // Fusão e cisão de unidades, com migração das referências e linhagem
+    let unit_restructuring_repo_port: Arc<dyn UnitRestructuringRepositoryPort> = Arc::new(
+        persistence::repositories::unit_restructuring_repository::UnitRestructuringRepository::new(
+            pool_auth.clone(),
+        ),
+    );
+    let unit_restructuring_service = Arc::new(
+        application::services::unit_restructuring_service::UnitRestructuringService::new(
+            unit_restructuring_repo_port,
+        ),
+    );
 
     // Stock movement service (needed by requisition, invoice, and adjustment services)
     let stock_movement_service = Arc::new(StockMovementService::new(pool_auth.clone()));
@@ -372,6 +540,8 @@ pub fn build_application_state(
         requisition_repo_port,
         requisition_item_repo_port,
         stock_movement_service.clone(),
+        delegation_service.clone(),
+        approval_chain_service.clone(),
     ));
 
     // Supplier repository and service
@@ -387,7 +557,9 @@ pub fn build_application_state(
     // Fueling repository and service
     let fueling_repo: Arc<dyn FuelingRepositoryPort> =
         Arc::new(FuelingRepository::new(pool_auth.clone()));
-    let fueling_service = Arc::new(FuelingService::new(fueling_repo));
+    let vehicle_repo_for_fuelings: Arc<dyn VehicleRepositoryPort> =
+        Arc::new(VehicleRepository::new(pool_auth.clone()));
+    let fueling_service = Arc::new(FuelingService::new(fueling_repo, vehicle_repo_for_fuelings));
 
     // Vehicle fleet repositories and service
     let vehicle_category_repo: Arc<dyn VehicleCategoryRepositoryPort> =
@@ -428,10 +600,13 @@ pub fn build_application_state(
         Arc::new(VehicleFineRepository::new(pool_auth.clone()));
     let vehicle_fine_status_history_repo: Arc<dyn VehicleFineStatusHistoryRepositoryPort> =
         Arc::new(VehicleFineStatusHistoryRepository::new(pool_auth.clone()));
+    let driver_repo_for_fines: Arc<dyn DriverRepositoryPort> =
+        Arc::new(DriverRepository::new(pool_auth.clone()));
     let vehicle_fine_service = Arc::new(VehicleFineService::new(
         vehicle_fine_type_repo,
         vehicle_fine_repo,
         vehicle_fine_status_history_repo,
+        driver_repo_for_fines,
     ));
 
     // Financial event repository and publisher (RF-028)
@@ -444,7 +619,9 @@ pub fn build_application_state(
         use application::external::ComprasnetEmpenhoClient;
         let base_url = std::env::var("COMPRASNET_EMPENHO_API_URL")
             .unwrap_or_default();
-        let api_token = std::env::var("COMPRASNET_EMPENHO_API_TOKEN").ok();
+        let api_token = config
+            .secrets
+            .handle(&infra::config::COMPRASNET_EMPENHO_API_TOKEN);
         if !base_url.is_empty() {
             match ComprasnetEmpenhoClient::new(base_url, api_token) {
                 Ok(client) => {
@@ -507,6 +684,7 @@ pub fn build_application_state(
         stock_repo,
         stock_movement_service.clone(),
         disposal_request_repo,
+        approval_chain_service.clone(),
     ));
     let inventory_service = Arc::new(InventoryService::new(
         pool_auth.clone(),
@@ -519,6 +697,8 @@ pub fn build_application_state(
     let stock_transfer_service = Arc::new(StockTransferService::new(
         pool_auth.clone(),
         stock_movement_service.clone(),
+        delegation_service.clone(),
+        approval_chain_service.clone(),
     ));
 
     // Batch service: FEFO (RF-021) + Quality Occurrences (RF-043)
@@ -536,20 +716,46 @@ pub fn build_application_state(
     // Circuit breaker registry (RF-035 — Modo Degradado)
     use application::external::{CircuitBreaker, CircuitBreakerRegistry};
     let cb_registry = Arc::new(CircuitBreakerRegistry::new());
+    let comprasnet_failures: u32 = 5;
+    let comprasnet_recovery: u64 = 60;
+    let cb_comprasnet = Arc::new(CircuitBreaker::new(
+        "comprasnet",
+        comprasnet_failures,
+        comprasnet_recovery,
+    ));
     {
-        let comprasnet_failures: u32 = 5;
-        let comprasnet_recovery: u64 = 60;
-        let cb_comprasnet = Arc::new(CircuitBreaker::new(
-            "comprasnet",
-            comprasnet_failures,
-            comprasnet_recovery,
-        ));
         let siorg_cb = Arc::new(CircuitBreaker::new("siorg", 3, 120));
-        cb_registry.register(cb_comprasnet);
+        cb_registry.register(cb_comprasnet.clone());
         cb_registry.register(siorg_cb);
     }
     let circuit_breaker_registry = cb_registry;
 
+    // Sincronização do catálogo com o ComprasGov (protegida pelo circuito comprasnet)
+    let catalog_sync_repo_port: Arc<dyn CatalogSyncRepositoryPort> =
+        Arc::new(CatalogSyncRepository::new(pool_auth.clone()));
+    let catalog_sync_service = Arc::new(application::external::CatalogSyncService::new(
+        catalog_sync_repo_port,
+        unit_repo_port,
+        system_settings_repo_port.clone(),
+        cb_comprasnet,
+    ));
+    // Fusão de itens duplicados; IDs fundidos continuam resolvendo pelo redirecionamento
+    let catalog_merge_repo_port: Arc<dyn CatalogMergeRepositoryPort> =
+        Arc::new(CatalogMergeRepository::new(pool_auth.clone()));
+    let catalog_merge_service = Arc::new(CatalogMergeService::new(catalog_merge_repo_port.clone()));
+
+    // Importação sob demanda de itens oficiais
+    let catalog_service = Arc::new(
+        catalog_svc_builder
+            .with_catalog_sync(catalog_sync_service.clone())
+            .with_item_redirects(catalog_merge_repo_port),
+    );
+
+    // Busca textual e autocompletar do catálogo
+    let catalog_search_repo_port: Arc<dyn CatalogSearchRepositoryPort> =
+        Arc::new(CatalogSearchRepository::new(pool_auth.clone()));
+    let catalog_search_service = Arc::new(CatalogSearchService::new(catalog_search_repo_port));
+
     // Asset management service (RF-AST-06/09/10/11/12 + RF-ADM-01/02/07/08)
     let transfer_repo: Arc<dyn VehicleDepartmentTransferRepositoryPort> =
         Arc::new(VehicleDepartmentTransferRepository::new(pool_auth.clone()));
@@ -594,7 +800,27 @@ pub fn build_application_state(
         Arc::new(VehicleRepository::new(pool_auth.clone()));
     let odometer_service = Arc::new(OdometerService::new(odometer_repo.clone(), vehicle_repo_for_odometer));
 
-    // Trip service (RF-USO-01/02/03/04 + RF-VIG-04)
+    // Fleet report service (RF-REL-01/02/03)
+    let report_repo: Arc<dyn FleetReportRepositoryPort> =
+        Arc::new(FleetReportRepository::new(pool_auth.clone()));
+    let vehicle_repo_for_reports: Arc<dyn VehicleRepositoryPort> =
+        Arc::new(VehicleRepository::new(pool_auth.clone()));
+    let fleet_report_service = Arc::new(FleetReportService::new(
+        report_repo,
+        vehicle_repo_for_reports,
+    ));
+
+    // Custos por unidade na estrutura vigente em uma data
+    let unit_cost_report_repo: Arc<dyn UnitCostReportRepositoryPort> =
+        Arc::new(UnitCostReportRepository::new(pool_auth.clone()));
+    let unit_repo_for_reports: Arc<dyn OrganizationalUnitRepositoryPort> =
+        Arc::new(OrganizationalUnitRepository::new(pool_auth_arc.clone()));
+    let unit_cost_report_service = Arc::new(UnitCostReportService::new(
+        unit_cost_report_repo,
+        unit_repo_for_reports,
+    ));
+
+    // Trip service (RF-USO-01/02/03/04 + RF-VIG-04/05)
     let trip_repo: Arc<dyn VehicleTripRepositoryPort> =
         Arc::new(VehicleTripRepository::new(pool_auth.clone()));
     let vehicle_repo_for_trips: Arc<dyn VehicleRepositoryPort> =
@@ -609,6 +835,8 @@ pub fn build_application_state(
         driver_repo_for_trips,
         odometer_repo,
         status_history_for_trips,
+        fleet_report_service.clone(),
+        delegation_service.clone(),
     ));
 
     // Maintenance service (RF-MNT-01/02/03/04)
@@ -624,16 +852,6 @@ pub fn build_application_state(
         status_history_for_maint,
     ));
 
-    // Fleet report service (RF-REL-01/02/03)
-    let report_repo: Arc<dyn FleetReportRepositoryPort> =
-        Arc::new(FleetReportRepository::new(pool_auth.clone()));
-    let vehicle_repo_for_reports: Arc<dyn VehicleRepositoryPort> =
-        Arc::new(VehicleRepository::new(pool_auth.clone()));
-    let fleet_report_service = Arc::new(FleetReportService::new(
-        report_repo,
-        vehicle_repo_for_reports,
-    ));
-
     // ÉPICO 4: Alertas, Dashboard, ABC, Legacy Import
     let alert_repo: Arc<dyn StockAlertRepositoryPort> =
         Arc::new(StockAlertRepository::new(pool_auth.clone()));
@@ -651,31 +869,62 @@ pub fn build_application_state(
         Arc::new(LegacyImportRepository::new(pool_auth.clone()));
     let legacy_import_service = Arc::new(LegacyImportService::new(legacy_import_repo));
 
+    let fuel_card_repo: Arc<dyn FuelCardRepositoryPort> =
+        Arc::new(FuelCardRepository::new(pool_auth.clone()));
+    let import_repo_for_fuel_cards: Arc<dyn LegacyImportRepositoryPort> =
+        Arc::new(LegacyImportRepository::new(pool_auth.clone()));
+    let fuel_card_service = Arc::new(FuelCardService::new(
+        fuel_card_repo,
+        import_repo_for_fuel_cards,
+    ));
+
     // Cache com TTL e tamanho máximo para políticas do Casbin
     let policy_cache = Cache::builder()
         .max_capacity(10_000) // Máximo 10k entries
         .time_to_live(Duration::from_secs(300)) // TTL de 5 minutos
         .build();
 
+    // Mesmo TTL: mudanças na árvore de unidades refletem em até 5 minutos
+    let unit_scope_cache = Cache::builder()
+        .max_capacity(10_000)
+        .time_to_live(Duration::from_secs(300))
+        .build();
+
     state::AppState {
         enforcer,
         policy_cache,
+        unit_scope_cache,
         db_pool_auth: pool_auth,
         db_pool_logs: pool_logs,
         jwt_service,
         email_service: email_service_legacy,
         audit_service,
+        audit_integrity_service,
+        entity_history_service,
+        lgpd_service,
         auth_service,
+        login_throttle_service,
+        api_key_service,
+        field_key_rotation_service,
+        jwt_key_service,
+        delegation_service,
+        approval_chain_service,
         user_service,
         mfa_service,
+        webauthn_service,
+        oidc_service,
         location_service,
         budget_classifications_service,
         catalog_service,
+        catalog_merge_service,
+        catalog_search_service,
+        catalog_sync_service,
         system_settings_service,
         organization_service,
         organizational_unit_category_service,
         organizational_unit_type_service,
         organizational_unit_service,
+        unit_restructuring_service,
         siorg_sync_service,
         siorg_sync_queue_repository: siorg_sync_queue_repo_port,
         siorg_history_repository: siorg_history_repo_port,
@@ -702,12 +951,14 @@ pub fn build_application_state(
         trip_service,
         maintenance_service,
         fleet_report_service,
+        unit_cost_report_service,
         alert_service,
         dashboard_service,
         abc_analysis_service,
         legacy_import_service,
+        fuel_card_service,
         config,
-        field_encryption_key: enc_key,
+        field_keyring: keyring,
 
         site_repository: site_repo_port,
         building_repository: building_repo_port,
@@ -726,7 +977,9 @@ pub async fn run(addr: SocketAddr) -> Result<()> {
 
     info!("🚀 Iniciando Waterswamp API (lib run)...");
 
-    let config = Config::from_env()?;
+    let secrets = SecretStore::load_from_env(infra::config::SECRETS).await?;
+    info!(provider = secrets.provider_name(), "🔒 Segredos carregados");
+    let config = Config::with_secrets(secrets.clone())?;
     let config_arc = Arc::new(config.clone());
 
     info!("🔌 Conectando aos bancos de dados...");
@@ -761,10 +1014,53 @@ pub async fn run(addr: SocketAddr) -> Result<()> {
     );
 
     info!("📧 Inicializando serviço de email...");
-    let email_config = EmailConfig::from_env().context("Config email")?;
+    let mut email_config = EmailConfig::from_env().context("Config email")?;
+    if let Some(user) = secrets.get(&infra::config::SMTP_USER) {
+        email_config.smtp_user = user;
+    }
+    if let Some(pass) = secrets.get(&infra::config::SMTP_PASS) {
+        email_config.smtp_pass = pass;
+    }
     let email_service =
         Arc::new(EmailService::new(email_config).context("Falha ao criar serviço de email")?);
 
+    // Segredos rotacionados passam a valer sem reinício: novas conexões usam
+    // a URL atual e o SMTP é reconectado com as novas credenciais
+    let (refresh_pool_auth, refresh_pool_logs) = (pool_auth.clone(), pool_logs.clone());
+    let refresh_email = email_service.clone();
+    secrets.spawn_refresh(move |store, changed| {
+        use infra::config::{
+            FIELD_ENCRYPTION_KEY, JWT_PRIVATE_KEY, JWT_PUBLIC_KEY, LOGS_DATABASE_URL,
+            MAIN_DATABASE_URL, SMTP_PASS, SMTP_USER,
+        };
+        for (secret, pool) in [
+            (MAIN_DATABASE_URL, &refresh_pool_auth),
+            (LOGS_DATABASE_URL, &refresh_pool_logs),
+        ] {
+            if !changed.contains(&secret.name) {
+                continue;
+            }
+            match store.get(&secret).map(|url| PgConnectOptions::from_str(&url)) {
+                Some(Ok(options)) => pool.set_connect_options(options),
+                Some(Err(e)) => error!("{} inválida: {}", secret.name, e),
+                None => error!("{} removida do provedor de segredos", secret.name),
+            }
+        }
+        if changed.contains(&SMTP_USER.name) || changed.contains(&SMTP_PASS.name) {
+            let user = store.get(&SMTP_USER).unwrap_or_default();
+            let pass = store.get(&SMTP_PASS).unwrap_or_default();
+            if let Err(e) = refresh_email.update_credentials(user, pass) {
+                error!("Falha ao aplicar as novas credenciais SMTP: {}", e);
+            }
+        }
+        // Têm rotação própria (keyring JWT e recriptografia dos campos)
+        for secret in [JWT_PRIVATE_KEY, JWT_PUBLIC_KEY, FIELD_ENCRYPTION_KEY] {
+            if changed.contains(&secret.name) {
+                warn!("{} alterada; a nova chave só é usada após reinício", secret.name);
+            }
+        }
+    });
+
     let app_state = build_application_state(
         config_arc.clone(),
         pool_auth.clone(),
@@ -826,6 +1122,79 @@ pub async fn run(addr: SocketAddr) -> Result<()> {
         info!("⏭️  Worker embutido desabilitado (ENABLE_EMBEDDED_WORKER=false)");
     }
 
+    // Sincronizações de catálogo interrompidas por um reinício não voltam a executar
+    match app_state.catalog_sync_service.fail_interrupted_runs().await {
+        Ok(0) => {}
+        Ok(failed) => info!(failed, "Sincronizações de catálogo interrompidas marcadas como falhas"),
+        Err(e) => error!("Falha ao encerrar sincronizações de catálogo interrompidas: {}", e),
+    }
+
+    // Recriptografia interrompida por um reinício continua do ponto salvo
+    match app_state.field_key_rotation_service.take_interrupted_run().await {
+        Ok(Some(rotation)) => {
+            info!(rotation_id = %rotation.id, "Retomando recriptografia dos campos cifrados");
+            let service = app_state.field_key_rotation_service.clone();
+            tokio::spawn(async move {
+                if let Err(e) = service.execute(rotation).await {
+                    error!("Falha ao registrar o resultado da recriptografia: {}", e);
+                }
+            });
+        }
+        Ok(None) => {}
+        Err(e) => error!("Falha ao verificar recriptografia interrompida: {}", e),
+    }
+
+    // Keyring JWT: assina com a chave ativa do banco e relê periodicamente
+    // as rotações feitas por outras instâncias
+    app_state
+        .jwt_key_service
+        .refresh()
+        .await
+        .context("Falha ao carregar o keyring JWT")?;
+    let jwt_keys = app_state.jwt_key_service.clone();
+    let keyring_refresh = Duration::from_secs(config.jwt_keyring_refresh_secs.max(1));
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(keyring_refresh);
+        interval.tick().await;
+        loop {
+            interval.tick().await;
+            if let Err(e) = jwt_keys.refresh().await {
+                error!("Falha ao recarregar o keyring JWT: {}", e);
+            }
+        }
+    });
+
+    // Limpeza periódica do histórico de tentativas de login (retenção de 30 dias)
+    let login_throttle = app_state.login_throttle_service.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(3600));
+        loop {
+            interval.tick().await;
+            match login_throttle.purge_attempts(30).await {
+                Ok(0) => {}
+                Ok(purged) => info!(purged, "Tentativas de login antigas removidas"),
+                Err(e) => error!("Falha ao limpar tentativas de login: {}", e),
+            }
+        }
+    });
+
+    // Sela os dias encerrados da cadeia de auditoria (checkpoints assinados)
+    let audit_integrity = app_state.audit_integrity_service.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(3600));
+        loop {
+            interval.tick().await;
+            match audit_integrity.seal_closed_days().await {
+                Ok(result) if !result.skipped_days.is_empty() => error!(
+                    days = ?result.skipped_days,
+                    "Cadeia de auditoria inválida: dias não selados"
+                ),
+                Ok(_) => {}
+                Err(e) => error!("Falha ao selar registros de auditoria: {}", e),
+            }
+        }
+    });
+
     info!("📡 Construindo rotas...");
     let app = routes::build(app_state);
 
@@ -835,7 +1204,8 @@ pub async fn run(addr: SocketAddr) -> Result<()> {
 
     info!("✨ Waterswamp API ouvindo em {}", addr);
 
-    axum::serve(listener, app.into_make_service())
+    // O IP da conexão alimenta o throttling de login e a allowlist de API keys
+    axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
         .with_graceful_shutdown(shutdown_signal())
         .await?;
 
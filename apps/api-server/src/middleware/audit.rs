@@ -4,6 +4,7 @@ use axum::{
     middleware::Next,
     response::Response,
 };
+use core_services::request_context::{self, RequestContext};
 use std::time::Instant;
 use uuid::Uuid;
 
@@ -30,8 +31,10 @@ pub async fn mw_audit(State(state): State<AppState>, req: Request, next: Next) -
     let ip_address = extract_ip_address(&headers);
     let user_agent = extract_user_agent(&headers);
 
-    // Execute the request
-    let response = next.run(req).await;
+    // Execute the request; inner layers read the request ID and origin from
+    // the context to attribute captured entity changes
+    let context = RequestContext::new(request_id, ip_address.clone(), user_agent.clone());
+    let response = request_context::scope(context, next.run(req)).await;
 
     // Calculate duration
     let duration_ms = start_time.elapsed().as_millis() as i32;
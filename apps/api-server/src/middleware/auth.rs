@@ -1,17 +1,37 @@
 pub use crate::extractors::current_user::CurrentUser;
-use crate::{infra::errors::AppError, state::AppState};
+use crate::{
+    extractors::client_ip::ClientIp,
+    infra::{casbin_setup::parse_unit_domain, errors::AppError},
+    state::AppState,
+    utils::constants::UNIT_ROLE_PTYPE,
+};
+use application::errors::ServiceError;
+use application::services::api_key_service::parse_key;
 use axum::{
     extract::{Request, State},
-    http::{HeaderMap, Method},
+    http::{header::USER_AGENT, HeaderMap, Method},
     middleware::Next,
     response::Response,
 };
-use casbin::CoreApi;
+use casbin::{function_map::key_match4, CoreApi, MgmtApi};
+use core_services::request_context;
 use core_services::session::{config as session_config, encryption, hash_token, validate_csrf};
-use domain::models::TokenType;
+use domain::models::{
+    AuthenticatedApiKey, DelegatedAuthority, DelegationScope, TokenType, UnitScope,
+};
 use domain::ports::SessionRepositoryPort;
 use persistence::repositories::session_repository::SessionRepository;
 use tower_cookies::Cookies;
+use uuid::Uuid;
+
+/// Decisões que podem ser tomadas por delegação de competência
+const DELEGABLE_ROUTES: &[(&str, &str, DelegationScope)] = &[
+    ("/api/admin/requisitions/{id}/approve", "POST", DelegationScope::Requisition),
+    ("/api/admin/requisitions/{id}/reject", "POST", DelegationScope::Requisition),
+    ("/api/admin/trips/{id}/review", "PUT", DelegationScope::Trip),
+    ("/api/admin/transfers/{id}/approve", "POST", DelegationScope::StockTransfer),
+    ("/api/admin/transfers/{id}/confirm", "POST", DelegationScope::StockTransfer),
+];
 
 fn extract_token(headers: &HeaderMap) -> Option<String> {
     headers
@@ -21,6 +41,89 @@ fn extract_token(headers: &HeaderMap) -> Option<String> {
         .map(|s| s.to_string())
 }
 
+fn extract_api_key(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get("Authorization")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|s| s.strip_prefix("ApiKey "))
+        .map(|s| s.to_string())
+}
+
+/// API key authentication middleware (service principals / integrations)
+///
+/// Authenticates `Authorization: ApiKey <key>` requests; anything else passes
+/// through to `mw_session_authenticate`. Every request made with a key is
+/// written to the audit log, with the principal as the actor, and so is
+/// every rejected key.
+pub async fn mw_api_key_authenticate(
+    State(state): State<AppState>,
+    mut req: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    let Some(key) = extract_api_key(req.headers()) else {
+        return Ok(next.run(req).await);
+    };
+
+    // Allowlist por IP: só o endereço da conexão ou de um proxy confiável
+    let ClientIp(client_ip) =
+        ClientIp::resolve(req.extensions(), req.headers(), &state.config.trusted_proxies);
+    let ip_address = client_ip.map(|ip| ip.to_string());
+    let user_agent = req
+        .headers()
+        .get(USER_AGENT)
+        .and_then(|v| v.to_str().ok())
+        .map(|s| s.to_string());
+
+    let resource = req.uri().path().to_string();
+    let method = req.method().to_string();
+
+    let api_key = match state.api_key_service.authenticate(&key, client_ip).await {
+        Ok(api_key) => api_key,
+        Err(ServiceError::InvalidCredentials) => {
+            state
+                .audit_service
+                .log_api_key_failed(
+                    parse_key(&key).map(|(prefix, _)| prefix),
+                    &resource,
+                    &method,
+                    ip_address,
+                    user_agent,
+                )
+                .await;
+            return Err(AppError::Unauthorized(
+                "Invalid or expired API key".to_string(),
+            ));
+        }
+        Err(other) => return Err(other.into()),
+    };
+
+    request_context::set_actor(api_key.principal_id);
+    req.extensions_mut().insert(CurrentUser {
+        id: api_key.principal_id,
+        username: api_key.principal_name.clone(),
+    });
+    req.extensions_mut().insert(api_key.clone());
+
+    let response = next.run(req).await;
+
+    state
+        .audit_service
+        .log_api_key_use(
+            api_key.principal_id,
+            &api_key.principal_name,
+            api_key.key_id,
+            &api_key.prefix,
+            &resource,
+            &method,
+            response.status().as_u16(),
+            ip_address,
+            user_agent,
+        )
+        .await;
+
+    Ok(response)
+}
+
 /// Session-based authentication middleware
 ///
 /// Authenticates using HttpOnly session cookies. Falls back to JWT if no session cookie.
@@ -31,6 +134,11 @@ pub async fn mw_session_authenticate(
     mut req: Request,
     next: Next,
 ) -> Result<Response, AppError> {
+    // Already authenticated by mw_api_key_authenticate
+    if req.extensions().get::<AuthenticatedApiKey>().is_some() {
+        return Ok(next.run(req).await);
+    }
+
     // Try session cookie first
     if let Some(session_cookie) = cookies.get(session_config::SESSION_COOKIE_NAME) {
         let session_token = session_cookie.value().to_string();
@@ -66,6 +174,7 @@ pub async fn mw_session_authenticate(
                 .touch_session(session.id, Some(session_config::SLIDING_WINDOW_MINUTES))
                 .await;
 
+            request_context::set_actor(current_user.id);
             req.extensions_mut().insert(claims);
             req.extensions_mut().insert(current_user);
             req.extensions_mut().insert(session.id); // Store session ID for later use
@@ -88,6 +197,7 @@ pub async fn mw_session_authenticate(
         username: claims.username.clone(),
     };
 
+    request_context::set_actor(current_user.id);
     req.extensions_mut().insert(claims);
     req.extensions_mut().insert(current_user);
 
@@ -129,42 +239,66 @@ async fn get_encryption_key(state: &AppState) -> Result<Vec<u8>, AppError> {
 /// Middleware de Autorização usando Casbin
 pub async fn mw_authorize(
     State(state): State<AppState>,
-    req: Request,
+    mut req: Request,
     next: Next,
 ) -> Result<Response, AppError> {
-    let user = req
+    let user_id = req
         .extensions()
         .get::<CurrentUser>()
+        .map(|user| user.id)
         .ok_or_else(|| anyhow::anyhow!("CurrentUser not found in request extensions"))?;
 
-    let subject = user.id.to_string();
     let object = req.uri().path().to_string();
     let action = req.method().to_string();
 
+    // Chave de API: o escopo da chave restringe e a role do principal decide
+    let api_key = req.extensions().get::<AuthenticatedApiKey>();
+    let is_api_key = api_key.is_some();
+    let subject = match api_key {
+        Some(api_key) => {
+            let in_scope = api_key
+                .scopes
+                .iter()
+                .any(|scope| scope.act == action && key_match4(&object, &scope.obj));
+            if !in_scope {
+                tracing::warn!(
+                    "Acesso negado (fora do escopo da chave {}): obj={}, act={}",
+                    api_key.prefix,
+                    object,
+                    action
+                );
+                return Err(AppError::Forbidden("Access denied".to_string()));
+            }
+            api_key.role.clone()
+        }
+        None => user_id.to_string(),
+    };
+
     let cache_key = format!("{}:{}:{}", subject, object, action);
+    let allowed = enforce_cached(&state, &cache_key, &subject, &object, &action).await?;
 
-    // Tentar obter do cache (moka)
-    let allowed = if let Some(decision) = state.policy_cache.get(&cache_key).await {
-        tracing::debug!("Cache hit para: {}", cache_key);
-        decision
+    // Papel global vale em todas as unidades; sem ele, valem os papéis
+    // atribuídos por unidade somados às delegações recebidas
+    // (service principals só têm papel global). O escopo inserido é sempre
+    // o próprio — vazio para quem só age por delegação —, de modo que os
+    // serviços decidam em nome próprio quando o documento está nele e
+    // recorram à delegação apenas fora dele
+    let mut delegated = None;
+    let scope = if allowed {
+        Some(UnitScope::All)
+    } else if is_api_key {
+        None
     } else {
-        tracing::debug!("Cache miss para: {}", cache_key);
-
-        // Consultar Casbin
-        let decision = {
-            let enforcer_guard = state.enforcer.read().await;
-            enforcer_guard
-                .enforce(vec![subject.clone(), object.clone(), action.clone()])
-                .map_err(|e| anyhow::anyhow!("Erro no Casbin Enforcer: {}", e))?
-        };
-
-        // Inserir no cache (moka insere automaticamente com TTL)
-        state.policy_cache.insert(cache_key.clone(), decision).await;
-
-        decision
+        let own = resolve_unit_scope(&state, &cache_key, &subject, &object, &action).await?;
+        delegated = resolve_delegated_authority(&state, user_id, &object, &action).await?;
+        match (own, &delegated) {
+            (Some(own), _) => Some(own),
+            (None, Some(_)) => Some(UnitScope::Units(Vec::new())),
+            (None, None) => None,
+        }
     };
 
-    if !allowed {
+    let Some(scope) = scope else {
         tracing::warn!(
             "Acesso negado: sub={}, obj={}, act={}",
             subject,
@@ -172,13 +306,147 @@ pub async fn mw_authorize(
             action
         );
         return Err(AppError::Forbidden("Access denied".to_string()));
-    }
+    };
 
     tracing::debug!(
-        "Acesso permitido: sub={}, obj={}, act={}",
+        "Acesso permitido: sub={}, obj={}, act={}, escopo={:?}",
         subject,
         object,
-        action
+        action,
+        scope
     );
+    req.extensions_mut().insert(scope);
+    if let Some(delegated) = delegated {
+        req.extensions_mut().insert(delegated);
+    }
     Ok(next.run(req).await)
 }
+
+/// Decisão do Casbin para o papel global, com cache (moka)
+async fn enforce_cached(
+    state: &AppState,
+    cache_key: &str,
+    subject: &str,
+    object: &str,
+    action: &str,
+) -> Result<bool, AppError> {
+    if let Some(decision) = state.policy_cache.get(cache_key).await {
+        tracing::debug!("Cache hit para: {}", cache_key);
+        return Ok(decision);
+    }
+    tracing::debug!("Cache miss para: {}", cache_key);
+
+    let decision = {
+        let enforcer_guard = state.enforcer.read().await;
+        enforcer_guard
+            .enforce(vec![subject.to_string(), object.to_string(), action.to_string()])
+            .map_err(|e| anyhow::anyhow!("Erro no Casbin Enforcer: {}", e))?
+    };
+
+    // Inserir no cache (moka insere automaticamente com TTL)
+    state.policy_cache.insert(cache_key.to_string(), decision).await;
+    Ok(decision)
+}
+
+/// Competência que o usuário exerce em nome de quem lhe delegou a decisão
+/// de `act` sobre `obj`: para cada delegação em vigor, as unidades em que o
+/// titular pode decidir. Delegações não se encadeiam — vale apenas a
+/// autorização própria do titular.
+async fn resolve_delegated_authority(
+    state: &AppState,
+    delegate_id: Uuid,
+    object: &str,
+    action: &str,
+) -> Result<Option<DelegatedAuthority>, AppError> {
+    let Some(scope) = DELEGABLE_ROUTES
+        .iter()
+        .find(|(obj, act, _)| *act == action && key_match4(object, obj))
+        .map(|(_, _, scope)| *scope)
+    else {
+        return Ok(None);
+    };
+
+    let mut grants = Vec::new();
+    for delegator_id in state
+        .delegation_service
+        .active_delegators(delegate_id, scope)
+        .await?
+    {
+        let subject = delegator_id.to_string();
+        let cache_key = format!("{}:{}:{}", subject, object, action);
+        let unit_scope = if enforce_cached(state, &cache_key, &subject, object, action).await? {
+            Some(UnitScope::All)
+        } else {
+            resolve_unit_scope(state, &cache_key, &subject, object, action).await?
+        };
+        if let Some(unit_scope) = unit_scope {
+            grants.push((delegator_id, unit_scope));
+        }
+    }
+
+    Ok((!grants.is_empty()).then_some(DelegatedAuthority { scope, grants }))
+}
+
+/// Unidades em que o usuário pode executar `act` sobre `obj` por meio de
+/// papéis atribuídos por unidade (`g2`). `None` quando nenhum papel permite.
+async fn resolve_unit_scope(
+    state: &AppState,
+    cache_key: &str,
+    subject: &str,
+    object: &str,
+    action: &str,
+) -> Result<Option<UnitScope>, AppError> {
+    if let Some(scope) = state.unit_scope_cache.get(cache_key).await {
+        return Ok(scope);
+    }
+
+    let domains: Vec<String> = {
+        let enforcer = state.enforcer.read().await;
+        let mut domains = Vec::new();
+        for assignment in
+            enforcer.get_filtered_named_grouping_policy(UNIT_ROLE_PTYPE, 0, vec![subject.to_string()])
+        {
+            let [_, role, domain] = assignment.as_slice() else {
+                continue;
+            };
+            let permitted = enforcer
+                .enforce(vec![role.clone(), object.to_string(), action.to_string()])
+                .map_err(|e| anyhow::anyhow!("Erro no Casbin Enforcer: {}", e))?;
+            if permitted {
+                domains.push(domain.clone());
+            }
+        }
+        domains
+    };
+
+    let mut unit_ids = Vec::new();
+    for domain in domains {
+        let Some((unit_id, include_subtree)) = parse_unit_domain(&domain) else {
+            tracing::warn!("Domínio de papel por unidade inválido: {}", domain);
+            continue;
+        };
+        if include_subtree {
+            match state
+                .organizational_unit_service
+                .get_subtree_ids(unit_id)
+                .await
+            {
+                Ok(ids) => unit_ids.extend(ids),
+                // Unidade removida: o papel deixa de valer
+                Err(ServiceError::NotFound(_)) => {}
+                Err(e) => return Err(e.into()),
+            }
+        } else {
+            unit_ids.push(unit_id);
+        }
+    }
+    unit_ids.sort();
+    unit_ids.dedup();
+
+    let scope = (!unit_ids.is_empty()).then_some(UnitScope::Units(unit_ids));
+    state
+        .unit_scope_cache
+        .insert(cache_key.to_string(), scope.clone())
+        .await;
+    Ok(scope)
+}
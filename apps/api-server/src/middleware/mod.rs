@@ -3,5 +3,5 @@ pub mod auth;
 pub mod idempotency;
 pub mod rate_limit;
 
-pub use auth::{mw_authorize, mw_session_authenticate};
+pub use auth::{mw_api_key_authenticate, mw_authorize, mw_session_authenticate};
 pub use rate_limit::login_rate_limiter;
@@ -41,18 +41,22 @@ use utoipa::OpenApi;
         (name = "CATSER - Groups", description = "Grupos do Catálogo de Serviços (CATSER)"),
         (name = "CATSER - Classes", description = "Classes do Catálogo de Serviços (CATSER)"),
         (name = "CATSER - Items", description = "Itens do Catálogo de Serviços (CATSER)"),
+        (name = "Catalog - Sync", description = "Sincronização do catálogo com o ComprasGov"),
+        (name = "Catalog - Search", description = "Busca textual e autocompletar do catálogo"),
         (name = "Organization - System Settings", description = "Configurações globais do sistema"),
         (name = "Organization - Organizations", description = "Gerenciamento de organizações (CNPJ, SIORG)"),
         (name = "Organization - Unit Categories", description = "Categorias de unidades organizacionais"),
         (name = "Organization - Unit Types", description = "Tipos de unidades organizacionais"),
         (name = "Organization - Organizational Units", description = "Unidades organizacionais hierárquicas com sincronização SIORG"),
         (name = "Organization - SIORG Sync", description = "Sincronização bidirecional com API do SIORG"),
+        (name = "Reports", description = "Relatórios de custos e consumo"),
     ),
     paths(
         // Health
         crate::handlers::health_handler::handler_health,
         crate::handlers::health_handler::handler_ready,
         crate::handlers::health_handler::handler_liveness,
+        crate::handlers::jwks_handler::handler_jwks,
 
         // Auth
         crate::api::auth::handlers::login,
@@ -75,6 +79,12 @@ use utoipa::OpenApi;
         crate::api::admin::users::handlers::delete_user,
         crate::api::admin::users::handlers::ban_user,
         crate::api::admin::users::handlers::unban_user,
+        crate::api::admin::security::handlers::start_field_key_rotation,
+        crate::api::admin::security::handlers::list_field_key_rotations,
+        crate::api::admin::security::handlers::get_field_key_rotation,
+        crate::api::admin::security::handlers::resume_field_key_rotation,
+        crate::api::admin::security::handlers::list_jwt_signing_keys,
+        crate::api::admin::security::handlers::rotate_jwt_signing_key,
 
         // Admin - Locations (Countries)
         crate::api::geo_regions::handlers::list_countries,
@@ -185,6 +195,25 @@ use utoipa::OpenApi;
         crate::api::catalog::handlers::update_catser_item,
         crate::api::catalog::handlers::delete_catser_item,
 
+        // CATMAT - Items (duplicates and merge)
+        crate::api::catalog::merge_handlers::list_catmat_duplicates,
+        crate::api::catalog::merge_handlers::merge_catmat_items,
+        crate::api::catalog::merge_handlers::list_catmat_redirects,
+
+        // Catalog - Search
+        crate::api::catalog::search_handlers::search_catmat_items,
+        crate::api::catalog::search_handlers::search_catser_items,
+        crate::api::catalog::search_handlers::autocomplete,
+
+        // Catalog - Sync (ComprasGov)
+        crate::api::catalog::sync_handlers::start_sync,
+        crate::api::catalog::sync_handlers::list_sync_runs,
+        crate::api::catalog::sync_handlers::get_sync_run,
+        crate::api::catalog::official_handlers::search_official_catmat_items,
+        crate::api::catalog::official_handlers::search_official_catser_items,
+        crate::api::catalog::official_handlers::import_catmat_item,
+        crate::api::catalog::official_handlers::import_catser_item,
+
         // Organization - System Settings
         crate::api::organizational::handlers::create_system_setting,
         crate::api::organizational::handlers::list_system_settings,
@@ -224,6 +253,10 @@ use utoipa::OpenApi;
         crate::api::organizational::handlers::delete_organizational_unit,
         crate::api::organizational::handlers::deactivate_organizational_unit,
         crate::api::organizational::handlers::activate_organizational_unit,
+        crate::api::organizational::restructuring_handlers::get_unit_lineage,
+        crate::api::organizational::restructuring_handlers::create_unit_restructuring,
+        crate::api::organizational::restructuring_handlers::list_unit_restructurings,
+        crate::api::organizational::restructuring_handlers::get_unit_restructuring,
 
         // Organization - SIORG Sync
         crate::api::organizational::handlers::sync_all_from_db,
@@ -233,6 +266,15 @@ use utoipa::OpenApi;
         crate::api::organizational::handlers::sync_unit,
         crate::api::organizational::handlers::sync_organization_units,
         crate::api::organizational::handlers::check_siorg_health,
+        crate::api::organizational::preview_handlers::create_siorg_preview,
+        crate::api::organizational::preview_handlers::list_siorg_previews,
+        crate::api::organizational::preview_handlers::get_siorg_preview,
+        crate::api::organizational::preview_handlers::apply_siorg_preview,
+        crate::api::organizational::preview_handlers::discard_siorg_preview,
+
+        // Reports - custos por unidade (estrutura vigente em uma data)
+        crate::api::reports::handlers::get_requisition_cost_by_unit,
+        crate::api::reports::handlers::get_fleet_cost_by_unit,
     ),
     components(
         schemas(
@@ -267,6 +309,13 @@ use utoipa::OpenApi;
             crate::api::admin::users::contracts::BanUserRequest,
             crate::api::admin::users::contracts::UserActionResponse,
             crate::api::admin::users::contracts::PaginationParams,
+            crate::api::admin::security::handlers::FieldKeyRotationsListResponse,
+            domain::models::field_key_rotation::FieldKeyRotationDto,
+            domain::models::field_key_rotation::FieldKeyRotationStatus,
+            domain::models::field_key_rotation::FieldKeyRotationStage,
+            crate::api::admin::security::handlers::JwtSigningKeysResponse,
+            domain::models::jwt_signing_key::JwtSigningKeyDto,
+            domain::models::jwt_signing_key::JwtSigningKeyStatus,
 
             // Locations
             crate::api::geo_regions::contracts::CountryResponse,
@@ -347,6 +396,35 @@ use utoipa::OpenApi;
             crate::api::catalog::contracts::CatserGroupsListResponse,
             crate::api::catalog::contracts::CatserClassesListResponse,
             crate::api::catalog::contracts::CatserItemsListResponse,
+            crate::api::catalog::merge_handlers::CatalogItemRedirectsListResponse,
+            domain::models::catalog_merge::CatalogDuplicateCandidate,
+            domain::models::catalog_merge::CatalogDuplicateCluster,
+            domain::models::catalog_merge::CatalogDuplicateReport,
+            domain::models::catalog_merge::MergeCatalogItemsPayload,
+            domain::models::catalog_merge::CatalogMergeReference,
+            domain::models::catalog_merge::CatalogItemMergeResult,
+            domain::models::catalog_merge::CatalogItemRedirectDto,
+            crate::api::catalog::search_handlers::CatmatItemSearchResponse,
+            crate::api::catalog::search_handlers::CatserItemSearchResponse,
+            domain::models::catalog_search::CatalogMatchKind,
+            domain::models::catalog_search::CatmatItemSearchHit,
+            domain::models::catalog_search::CatserItemSearchHit,
+            domain::models::catalog_search::CatalogSuggestion,
+            crate::api::catalog::sync_handlers::StartCatalogSyncRequest,
+            crate::api::catalog::sync_handlers::CatalogSyncRunsListResponse,
+            crate::api::catalog::official_handlers::OfficialCatmatSearchResponse,
+            crate::api::catalog::official_handlers::OfficialCatserSearchResponse,
+            domain::models::catalog_sync::CatalogKind,
+            domain::models::catalog_sync::CatalogSyncScope,
+            domain::models::catalog_sync::CatalogSyncStatus,
+            domain::models::catalog_sync::CatalogLevel,
+            domain::models::catalog_sync::CatalogSyncEntry,
+            domain::models::catalog_sync::CatalogSyncReport,
+            domain::models::catalog_sync::CatalogSyncRunDto,
+            domain::models::catalog_sync::CatmatImportResult,
+            domain::models::catalog_sync::CatserImportResult,
+            domain::models::catalog_sync::OfficialCatmatItem,
+            domain::models::catalog_sync::OfficialCatserItem,
 
             // Organization - Domain Models
             domain::models::organizational::ActivityArea,
@@ -376,6 +454,32 @@ use utoipa::OpenApi;
             crate::api::organizational::contracts::OrganizationalUnitTypesListResponse,
             crate::api::organizational::contracts::OrganizationalUnitsListResponse,
 
+            // Organization - SIORG change previews
+            domain::models::siorg_preview::SiorgPreviewStatus,
+            domain::models::siorg_preview::SiorgDiffKind,
+            domain::models::siorg_preview::SiorgDiffDecision,
+            domain::models::siorg_preview::SiorgUnitReferences,
+            domain::models::siorg_preview::SiorgUnitDiff,
+            domain::models::siorg_preview::SiorgChangePreviewDto,
+            domain::models::siorg_preview::ApplySiorgPreviewPayload,
+            crate::api::organizational::preview_handlers::CreateSiorgPreviewRequest,
+            crate::api::organizational::preview_handlers::SiorgPreviewsListResponse,
+
+            // Organization - Unit restructurings (merge/split)
+            domain::models::unit_restructuring::UnitRestructuringKind,
+            domain::models::unit_restructuring::RestructuringReference,
+            domain::models::unit_restructuring::RestructuringOverride,
+            domain::models::unit_restructuring::CreateUnitRestructuringPayload,
+            domain::models::unit_restructuring::RestructuringMigrated,
+            domain::models::unit_restructuring::UnitLineageDto,
+            domain::models::unit_restructuring::UnitRestructuringDto,
+            domain::models::unit_restructuring::UnitLineageResponse,
+            crate::api::organizational::restructuring_handlers::UnitRestructuringsListResponse,
+
+            // Reports - custos por unidade
+            domain::models::report::UnitCostNode,
+            domain::models::report::UnitCostReport,
+
         )
     ),
     modifiers(&SecurityAddon)
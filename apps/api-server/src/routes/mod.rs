@@ -11,7 +11,7 @@ use crate::{
     infra::{cors, telemetry},
     middleware::audit,
     middleware::rate_limit::api_rate_limiter,
-    middleware::{mw_authorize, mw_session_authenticate},
+    middleware::{mw_api_key_authenticate, mw_authorize, mw_session_authenticate},
     openapi::ApiDoc,
     state::AppState,
 };
@@ -38,7 +38,7 @@ pub fn build(app_state: AppState) -> Router {
             mw_session_authenticate,
         ));
 
-    // 3. ROTAS ADMINISTRATIVAS E PROTEGIDAS (Sessão/JWT + Casbin RBAC)
+    // 3. ROTAS ADMINISTRATIVAS E PROTEGIDAS (Chave de API ou Sessão/JWT + Casbin RBAC)
     // Aqui incluímos geo_regions explicitamente como parte do admin ou rotas protegidas
     let admin_protected_routes = Router::new()
         .nest("/api/admin", admin::router()) // O admin::router já deve conter geo_regions internamente
@@ -50,6 +50,10 @@ pub fn build(app_state: AppState) -> Router {
         .layer(middleware::from_fn_with_state(
             app_state.clone(),
             mw_session_authenticate,
+        ))
+        .layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            mw_api_key_authenticate,
         ));
 
     // 4. MONTAGEM DO ROUTER PRINCIPAL
@@ -1,5 +1,5 @@
 use crate::api::locations;
-use crate::handlers::{health_handler, public_handler};
+use crate::handlers::{health_handler, jwks_handler, public_handler};
 use crate::{infra::telemetry, state::AppState};
 use axum::{routing::get, Router};
 
@@ -10,6 +10,8 @@ pub fn router() -> Router<AppState> {
         .route("/health/live", get(health_handler::handler_liveness))
         .route("/health/ready", get(health_handler::handler_ready))
         .route("/metrics", get(telemetry::handler_metrics))
+        // Chaves públicas de verificação dos JWTs
+        .route("/.well-known/jwks.json", get(jwks_handler::handler_jwks))
         // Public locations API (no authentication required)
         .nest("/api/locations/public", locations::public_router())
 }
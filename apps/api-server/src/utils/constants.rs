@@ -26,6 +26,31 @@ pub const RESOURCE_USER_PROFILE: &str = "/users/profile";
 /// Gestão de políticas (admin)
 pub const RESOURCE_ADMIN_POLICIES: &str = "/api/admin/policies";
 
+/// Gestão de papéis por unidade organizacional (admin)
+pub const RESOURCE_ADMIN_UNIT_ROLES: &str = "/api/admin/policies/unit-roles";
+
+/// Delegações de competência para aprovação
+pub const RESOURCE_ADMIN_DELEGATIONS: &str = "/api/admin/delegations";
+
+/// Cadeias de aprovação multinível
+pub const RESOURCE_ADMIN_APPROVAL_CHAINS: &str = "/api/admin/approval-chains";
+
+/// Histórico de alterações por entidade
+pub const RESOURCE_ADMIN_HISTORY: &str = "/api/admin/history";
+
+/// Direitos do titular (LGPD): exportação e anonimização de dados pessoais
+pub const RESOURCE_ADMIN_LGPD: &str = "/api/admin/lgpd";
+
+// =============================================================================
+// PAPÉIS POR UNIDADE ORGANIZACIONAL (CASBIN g2)
+// =============================================================================
+
+/// Tipo de agrupamento Casbin que associa usuário, papel e unidade
+pub const UNIT_ROLE_PTYPE: &str = "g2";
+
+/// Sufixo do domínio que estende o papel à subárvore da unidade
+pub const UNIT_SUBTREE_SUFFIX: &str = "/*";
+
 // =============================================================================
 // AÇÕES (CASBIN)
 // =============================================================================
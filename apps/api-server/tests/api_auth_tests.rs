@@ -99,7 +99,7 @@ async fn test_api_auth_login_success() {
 
     // Create test user using existing helper (1 argument)
     let (username, _email, password) =
-        create_test_user(&state.db_pool_auth, &state.field_encryption_key)
+        create_test_user(&state.db_pool_auth, &state.field_keyring)
             .await
             .expect("Failed to create test user");
 
@@ -202,7 +202,7 @@ async fn test_api_auth_login_with_mfa_enabled() {
 
     // Create user
     let (username, _email, password) =
-        create_test_user(&state.db_pool_auth, &state.field_encryption_key)
+        create_test_user(&state.db_pool_auth, &state.field_keyring)
             .await
             .expect("Failed to create test user");
 
@@ -305,11 +305,7 @@ async fn test_api_auth_register_success() {
 
     assert_eq!(row.get::<String, _>("username"), username);
     let encrypted_email = row.get::<String, _>("email");
-    let decrypted_email = core_services::field_encryption::decrypt_field(
-        &encrypted_email,
-        &state.field_encryption_key,
-    )
-    .unwrap();
+    let decrypted_email = state.field_keyring.decrypt(&encrypted_email).unwrap();
     assert_eq!(decrypted_email, email);
 
     cleanup_test_users(&state.db_pool_auth).await.ok();
@@ -325,7 +321,7 @@ async fn test_api_auth_register_duplicate_username() {
 
     // Create first user
     let (username, _email, _password) =
-        create_test_user(&state.db_pool_auth, &state.field_encryption_key)
+        create_test_user(&state.db_pool_auth, &state.field_keyring)
             .await
             .expect("Failed to create test user");
 
@@ -361,7 +357,7 @@ async fn test_api_auth_register_duplicate_email() {
 
     // Create first user
     let (username, user_email, _password) =
-        create_test_user(&state.db_pool_auth, &state.field_encryption_key)
+        create_test_user(&state.db_pool_auth, &state.field_keyring)
             .await
             .expect("Failed to create test user");
 
@@ -527,7 +523,7 @@ async fn test_api_auth_refresh_token_success() {
 
     // Create and login user
     let (username, _email, password) =
-        create_test_user(&state.db_pool_auth, &state.field_encryption_key)
+        create_test_user(&state.db_pool_auth, &state.field_keyring)
             .await
             .expect("Failed to create test user");
     let (_, refresh_token) = login_user(&server, &username, &password).await;
@@ -564,7 +560,7 @@ async fn test_api_auth_refresh_token_reuse_detection() {
 
     // Create and login user
     let (username, _email, password) =
-        create_test_user(&state.db_pool_auth, &state.field_encryption_key)
+        create_test_user(&state.db_pool_auth, &state.field_keyring)
             .await
             .expect("Failed to create test user");
     let (_, refresh_token) = login_user(&server, &username, &password).await;
@@ -636,7 +632,7 @@ async fn test_api_auth_logout_success() {
 
     // Create and login user
     let (username, _email, password) =
-        create_test_user(&state.db_pool_auth, &state.field_encryption_key)
+        create_test_user(&state.db_pool_auth, &state.field_keyring)
             .await
             .expect("Failed to create test user");
     let (_, refresh_token) = login_user(&server, &username, &password).await;
@@ -682,7 +678,7 @@ async fn test_api_auth_forgot_password_existing_email() {
     // Create user
 
     let (username, user_email, _password) =
-        create_test_user(&state.db_pool_auth, &state.field_encryption_key)
+        create_test_user(&state.db_pool_auth, &state.field_keyring)
             .await
             .expect("Failed to create test user");
 
@@ -757,7 +753,7 @@ async fn test_api_auth_reset_password_success() {
 
     // Create user
     let (username, user_email, old_password) =
-        create_test_user(&state.db_pool_auth, &state.field_encryption_key)
+        create_test_user(&state.db_pool_auth, &state.field_keyring)
             .await
             .expect("Failed to create test user");
 
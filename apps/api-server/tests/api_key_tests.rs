@@ -0,0 +1,391 @@
+use serde_json::{json, Value};
+use std::time::Duration;
+use uuid::Uuid;
+use waterswamp::extractors::client_ip::TrustedProxies;
+
+mod common;
+
+const LOCKOUTS: &str = "/api/admin/security/lockouts";
+
+async fn create_principal(app: &common::TestApp, role: &str) -> Value {
+    let response = app
+        .api
+        .post("/api/admin/service-principals")
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .json(&json!({
+            "name": format!("integration-{}", Uuid::new_v4().simple()),
+            "description": "Ponte SIAFI",
+            "role": role
+        }))
+        .await;
+    assert_eq!(response.status_code(), 201);
+    response.json()
+}
+
+async fn create_key(app: &common::TestApp, principal_id: &str, body: Value) -> Value {
+    let response = app
+        .api
+        .post(&format!("/api/admin/service-principals/{}/api-keys", principal_id))
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .json(&body)
+        .await;
+    assert_eq!(response.status_code(), 201, "{}", response.text());
+    response.json()
+}
+
+fn lockouts_key_body() -> Value {
+    json!({
+        "name": "leitura de bloqueios",
+        "scopes": [{ "obj": LOCKOUTS, "act": "GET" }],
+        "expires_at": (chrono::Utc::now() + chrono::Duration::days(30)).to_rfc3339()
+    })
+}
+
+async fn get_with_key(app: &common::TestApp, path: &str, key: &str) -> u16 {
+    app.api
+        .get(path)
+        .add_header("Authorization", format!("ApiKey {}", key))
+        .await
+        .status_code()
+        .as_u16()
+}
+
+#[tokio::test]
+async fn test_api_key_is_limited_to_its_scopes() {
+    let app = common::spawn_app().await;
+    let principal = create_principal(&app, "admin").await;
+    let created = create_key(&app, principal["id"].as_str().unwrap(), lockouts_key_body()).await;
+    let key = created["key"].as_str().unwrap();
+
+    assert!(key.starts_with("wsk_"));
+    assert!(created["api_key"].get("secret_hash").is_none());
+
+    assert_eq!(get_with_key(&app, LOCKOUTS, key).await, 200);
+
+    // Mesmo com role admin, fora do escopo da chave é negado
+    assert_eq!(get_with_key(&app, "/api/admin/service-principals", key).await, 403);
+    let status = app
+        .api
+        .delete(&format!("{}/{}", LOCKOUTS, Uuid::new_v4()))
+        .add_header("Authorization", format!("ApiKey {}", key))
+        .await
+        .status_code();
+    assert_eq!(status, 403);
+}
+
+#[tokio::test]
+async fn test_api_key_cannot_exceed_principal_role() {
+    let app = common::spawn_app().await;
+    let principal = create_principal(&app, "user").await;
+    let created = create_key(&app, principal["id"].as_str().unwrap(), lockouts_key_body()).await;
+
+    assert_eq!(get_with_key(&app, LOCKOUTS, created["key"].as_str().unwrap()).await, 403);
+}
+
+#[tokio::test]
+async fn test_invalid_and_revoked_keys_are_rejected() {
+    let app = common::spawn_app().await;
+    let principal = create_principal(&app, "admin").await;
+    let created = create_key(&app, principal["id"].as_str().unwrap(), lockouts_key_body()).await;
+    let key = created["key"].as_str().unwrap();
+    let prefix = key.split('.').next().unwrap();
+
+    assert_eq!(get_with_key(&app, LOCKOUTS, "garbage").await, 401);
+    assert_eq!(get_with_key(&app, LOCKOUTS, &format!("{}.wrong-secret", prefix)).await, 401);
+
+    let response = app
+        .api
+        .delete(&format!("/api/admin/api-keys/{}", created["api_key"]["id"].as_str().unwrap()))
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .await;
+    assert_eq!(response.status_code(), 204);
+
+    assert_eq!(get_with_key(&app, LOCKOUTS, key).await, 401);
+
+    // Chaves recusadas também são auditadas
+    let mut audited = None;
+    for _ in 0..20 {
+        audited = sqlx::query_scalar::<_, String>(
+            "SELECT resource FROM audit_logs \
+             WHERE action = 'api_key_failed' AND details->>'api_key_prefix' = $1",
+        )
+        .bind(prefix)
+        .fetch_optional(&app.db_logs)
+        .await
+        .unwrap();
+        if audited.is_some() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    assert_eq!(audited.as_deref(), Some(LOCKOUTS));
+}
+
+#[tokio::test]
+async fn test_api_key_writes_record_the_principal() {
+    let app = common::spawn_app().await;
+    let principal = create_principal(&app, "admin").await;
+    let principal_id = principal["id"].as_str().unwrap();
+    let imports = "/api/admin/fuelings/card-imports";
+    let created = create_key(
+        &app,
+        principal_id,
+        json!({
+            "name": "importação do cartão combustível",
+            "scopes": [{ "obj": imports, "act": "POST" }],
+            "expires_at": (chrono::Utc::now() + chrono::Duration::days(30)).to_rfc3339()
+        }),
+    )
+    .await;
+
+    let key = created["key"].as_str().unwrap();
+    let csv = format!(
+        "data;placa;cpf;litros;valor;id_transacao;posto\n\
+         11/02/2026 07:30;ZZZ9Z99;00000000000;10,000;62,50;TX-{};Posto Central\n",
+        Uuid::new_v4().simple()
+    );
+    let response = app
+        .api
+        .post(&format!("{}?operator=Ticket%20Log", imports))
+        .add_header("Authorization", format!("ApiKey {}", key))
+        .text(csv)
+        .await;
+    assert_eq!(response.status_code(), 201, "{}", response.text());
+    let report: Value = response.json();
+    let job_id: Uuid = report["job"]["id"].as_str().unwrap().parse().unwrap();
+
+    let submitted_by: Option<Uuid> =
+        sqlx::query_scalar("SELECT submitted_by FROM legacy_import_jobs WHERE id = $1")
+            .bind(job_id)
+            .fetch_one(&app.db_auth)
+            .await
+            .unwrap();
+    assert_eq!(submitted_by, Some(principal_id.parse().unwrap()));
+}
+
+#[tokio::test]
+async fn test_disabling_principal_disables_its_keys() {
+    let app = common::spawn_app().await;
+    let principal = create_principal(&app, "admin").await;
+    let principal_id = principal["id"].as_str().unwrap();
+    let created = create_key(&app, principal_id, lockouts_key_body()).await;
+    let key = created["key"].as_str().unwrap();
+
+    let response = app
+        .api
+        .patch(&format!("/api/admin/service-principals/{}", principal_id))
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .json(&json!({ "is_active": false }))
+        .await;
+    response.assert_status_ok();
+
+    assert_eq!(get_with_key(&app, LOCKOUTS, key).await, 401);
+}
+
+#[tokio::test]
+async fn test_api_key_ip_allowlist() {
+    let app = common::spawn_app().await;
+    let principal = create_principal(&app, "admin").await;
+    let mut body = lockouts_key_body();
+    body["allowed_ips"] = json!(["203.0.113.0/24"]);
+    let created = create_key(&app, principal["id"].as_str().unwrap(), body).await;
+    let key = created["key"].as_str().unwrap();
+
+    assert_eq!(created["api_key"]["allowed_ips"], json!(["203.0.113.0/24"]));
+
+    let allowed = app
+        .api
+        .get(LOCKOUTS)
+        .add_header("Authorization", format!("ApiKey {}", key))
+        .add_header("X-Forwarded-For", "203.0.113.77")
+        .await;
+    allowed.assert_status_ok();
+
+    let denied = app
+        .api
+        .get(LOCKOUTS)
+        .add_header("Authorization", format!("ApiKey {}", key))
+        .add_header("X-Forwarded-For", "198.51.100.1")
+        .await;
+    assert_eq!(denied.status_code(), 401);
+
+    // Sem X-Forwarded-For vale o IP da conexão, fora da allowlist
+    assert_eq!(get_with_key(&app, LOCKOUTS, key).await, 401);
+}
+
+#[tokio::test]
+async fn test_api_key_allowlist_ignores_forwarded_for_from_untrusted_peer() {
+    let app = common::spawn_app_with(|c| c.trusted_proxies = TrustedProxies::default()).await;
+    let principal = create_principal(&app, "admin").await;
+    let mut body = lockouts_key_body();
+    body["allowed_ips"] = json!(["203.0.113.0/24"]);
+    let created = create_key(&app, principal["id"].as_str().unwrap(), body).await;
+    let key = created["key"].as_str().unwrap();
+
+    let spoofed = app
+        .api
+        .get(LOCKOUTS)
+        .add_header("Authorization", format!("ApiKey {}", key))
+        .add_header("X-Forwarded-For", "203.0.113.77")
+        .add_header("X-Real-IP", "203.0.113.77")
+        .await;
+    assert_eq!(spoofed.status_code(), 401);
+}
+
+#[tokio::test]
+async fn test_rotation_keeps_old_key_during_grace_period() {
+    let app = common::spawn_app().await;
+    let principal = create_principal(&app, "admin").await;
+    let principal_id = principal["id"].as_str().unwrap();
+    let created = create_key(&app, principal_id, lockouts_key_body()).await;
+    let old_key = created["key"].as_str().unwrap();
+    let old_id = created["api_key"]["id"].as_str().unwrap();
+
+    let response = app
+        .api
+        .post(&format!("/api/admin/api-keys/{}/rotate", old_id))
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .json(&json!({ "grace_period_secs": 3600 }))
+        .await;
+    assert_eq!(response.status_code(), 201);
+    let rotated: Value = response.json();
+    let new_key = rotated["key"].as_str().unwrap();
+
+    assert_ne!(new_key, old_key);
+    assert_eq!(rotated["api_key"]["rotated_from_id"], json!(old_id));
+    assert_eq!(rotated["api_key"]["scopes"], created["api_key"]["scopes"]);
+
+    // Ambas funcionam durante a transição
+    assert_eq!(get_with_key(&app, LOCKOUTS, old_key).await, 200);
+    assert_eq!(get_with_key(&app, LOCKOUTS, new_key).await, 200);
+
+    // Rotação sem período de transição invalida a chave substituída na hora
+    let response = app
+        .api
+        .post(&format!(
+            "/api/admin/api-keys/{}/rotate",
+            rotated["api_key"]["id"].as_str().unwrap()
+        ))
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .json(&json!({ "grace_period_secs": 0 }))
+        .await;
+    assert_eq!(response.status_code(), 201);
+    let newest: Value = response.json();
+
+    assert_eq!(get_with_key(&app, LOCKOUTS, new_key).await, 401);
+    assert_eq!(get_with_key(&app, LOCKOUTS, newest["key"].as_str().unwrap()).await, 200);
+}
+
+#[tokio::test]
+async fn test_api_key_use_is_audited_and_tracked() {
+    let app = common::spawn_app().await;
+    let principal = create_principal(&app, "admin").await;
+    let principal_id = principal["id"].as_str().unwrap();
+    let created = create_key(&app, principal_id, lockouts_key_body()).await;
+    let key = created["key"].as_str().unwrap();
+    let key_id = created["api_key"]["id"].as_str().unwrap().to_string();
+
+    let response = app
+        .api
+        .get(LOCKOUTS)
+        .add_header("Authorization", format!("ApiKey {}", key))
+        .add_header("X-Forwarded-For", "192.0.2.10")
+        .await;
+    response.assert_status_ok();
+
+    let keys: Value = app
+        .api
+        .get(&format!("/api/admin/service-principals/{}/api-keys", principal_id))
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .await
+        .json();
+    let listed = keys
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|k| k["id"] == json!(key_id))
+        .unwrap();
+    assert!(listed["last_used_at"].is_string());
+    assert_eq!(listed["last_used_ip"], "192.0.2.10");
+
+    // O log de auditoria é gravado em background
+    let mut audited = None;
+    for _ in 0..20 {
+        audited = sqlx::query_as::<_, (String, Value)>(
+            "SELECT resource, details FROM audit_logs \
+             WHERE action = 'api_key_used' AND details->>'api_key_id' = $1",
+        )
+        .bind(&key_id)
+        .fetch_optional(&app.db_logs)
+        .await
+        .unwrap();
+        if audited.is_some() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    let (resource, details) = audited.expect("Uso da chave não auditado");
+    assert_eq!(resource, LOCKOUTS);
+    assert_eq!(details["status_code"], 200);
+    assert_eq!(details["method"], "GET");
+}
+
+#[tokio::test]
+async fn test_api_key_is_not_accepted_on_user_routes() {
+    let app = common::spawn_app().await;
+    let principal = create_principal(&app, "admin").await;
+    let created = create_key(&app, principal["id"].as_str().unwrap(), lockouts_key_body()).await;
+
+    assert_eq!(
+        get_with_key(&app, "/users/profile", created["key"].as_str().unwrap()).await,
+        401
+    );
+}
+
+#[tokio::test]
+async fn test_key_creation_validates_scopes_and_expiry() {
+    let app = common::spawn_app().await;
+    let principal = create_principal(&app, "admin").await;
+    let path = format!(
+        "/api/admin/service-principals/{}/api-keys",
+        principal["id"].as_str().unwrap()
+    );
+
+    let expired = json!({
+        "name": "expirada",
+        "scopes": [{ "obj": LOCKOUTS, "act": "GET" }],
+        "expires_at": (chrono::Utc::now() - chrono::Duration::days(1)).to_rfc3339()
+    });
+    let bad_action = json!({
+        "name": "ação inválida",
+        "scopes": [{ "obj": LOCKOUTS, "act": "TRACE" }],
+        "expires_at": (chrono::Utc::now() + chrono::Duration::days(1)).to_rfc3339()
+    });
+    let no_scopes = json!({
+        "name": "sem escopo",
+        "scopes": [],
+        "expires_at": (chrono::Utc::now() + chrono::Duration::days(1)).to_rfc3339()
+    });
+
+    for body in [expired, bad_action, no_scopes] {
+        let response = app
+            .api
+            .post(&path)
+            .add_header("Authorization", format!("Bearer {}", app.admin_token))
+            .json(&body)
+            .await;
+        assert_eq!(response.status_code(), 400, "{}", body);
+    }
+}
+
+#[tokio::test]
+async fn test_service_principals_require_admin() {
+    let app = common::spawn_app().await;
+
+    let response = app
+        .api
+        .get("/api/admin/service-principals")
+        .add_header("Authorization", format!("Bearer {}", app.user_token))
+        .await;
+    assert_eq!(response.status_code(), 403);
+}
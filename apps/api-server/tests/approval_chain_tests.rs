@@ -0,0 +1,571 @@
+mod common;
+
+use common::{admin_post, random_suffix, TestApp};
+use http::StatusCode;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+const CHAINS: &str = "/api/admin/approval-chains";
+
+// ============================
+// HELPERS
+// ============================
+
+async fn user_id(app: &TestApp, username: &str) -> Uuid {
+    sqlx::query_scalar("SELECT id FROM users WHERE username = $1")
+        .bind(username)
+        .fetch_one(&app.db_auth)
+        .await
+        .unwrap()
+}
+
+/// Unidade organizacional avulsa
+async fn create_unit(app: &TestApp) -> Uuid {
+    let organization = admin_post(
+        app,
+        "/api/admin/organizational/organizations",
+        json!({
+            "acronym": random_suffix(),
+            "name": format!("Org Cadeia {}", random_suffix()),
+            "cnpj": format!("{:014}", rand::random::<u64>() % 100000000000000),
+            "ug_code": rand::random::<u32>() % 1000000,
+            "siorg_code": rand::random::<i32>() % 1000000,
+            "is_main_organization": false,
+            "is_active": true
+        }),
+    )
+    .await;
+    let category = admin_post(
+        app,
+        "/api/admin/organizational/unit-categories",
+        json!({
+            "name": format!("Categoria {}", random_suffix()),
+            "is_active": true,
+            "is_siorg_managed": false
+        }),
+    )
+    .await;
+    let unit_type = admin_post(
+        app,
+        "/api/admin/organizational/unit-types",
+        json!({
+            "code": random_suffix(),
+            "name": format!("Tipo {}", random_suffix()),
+            "is_active": true,
+            "is_siorg_managed": false
+        }),
+    )
+    .await;
+    let unit = admin_post(
+        app,
+        "/api/admin/organizational/units",
+        json!({
+            "organization_id": organization["id"],
+            "category_id": category["id"],
+            "unit_type_id": unit_type["id"],
+            "name": format!("Unidade {}", random_suffix()),
+            "activity_area": "Support",
+            "is_active": true
+        }),
+    )
+    .await;
+    unit["id"].as_str().unwrap().parse().unwrap()
+}
+
+async fn create_warehouse(app: &TestApp) -> Uuid {
+    let uid = Uuid::new_v4().simple().to_string();
+    let city_id: Uuid = sqlx::query_scalar("SELECT id FROM cities LIMIT 1")
+        .fetch_one(&app.db_auth)
+        .await
+        .expect("Nenhuma cidade cadastrada");
+    sqlx::query_scalar(
+        "INSERT INTO warehouses (name, code, warehouse_type, city_id, is_active)
+         VALUES ($1, $2, 'SECTOR', $3, true)
+         RETURNING id",
+    )
+    .bind(format!("Almoxarifado {}", &uid[..8]))
+    .bind(format!("WC{}", &uid[..14]))
+    .bind(city_id)
+    .fetch_one(&app.db_auth)
+    .await
+    .unwrap()
+}
+
+async fn create_pending_requisition(
+    app: &TestApp,
+    warehouse_id: Uuid,
+    destination_unit_id: Uuid,
+    priority: &str,
+    total_value: i64,
+) -> Uuid {
+    let uid = Uuid::new_v4().simple().to_string();
+    let requester_id = user_id(app, "vinicius").await;
+    sqlx::query_scalar(
+        "INSERT INTO requisitions (
+            requisition_number, warehouse_id, destination_unit_id, requester_id,
+            status, priority, total_value, request_date
+         )
+         VALUES ($1, $2, $3, $4, 'PENDING'::requisition_status_enum,
+                 $5::requisition_priority_enum, $6, CURRENT_DATE)
+         RETURNING id",
+    )
+    .bind(format!("REQ{}", &uid[..12]))
+    .bind(warehouse_id)
+    .bind(destination_unit_id)
+    .bind(requester_id)
+    .bind(priority)
+    .bind(rust_decimal::Decimal::from(total_value))
+    .fetch_one(&app.db_auth)
+    .await
+    .unwrap()
+}
+
+/// Diretor do almoxarifado: usuário sem papel global, com um papel na
+/// unidade que só pode decidir requisições
+async fn create_director(app: &TestApp, unit_id: Uuid) -> (Uuid, String, String) {
+    let (username, _, _) = common::create_test_user(&app.db_auth, &app.field_keyring)
+        .await
+        .unwrap();
+    let id = user_id(app, &username).await;
+
+    let role = format!("role:diretor_{}", random_suffix());
+    for (obj, act) in [
+        ("/api/admin/requisitions/*/approve", "POST"),
+        ("/api/admin/requisitions/*/reject", "POST"),
+    ] {
+        admin_post(
+            app,
+            "/api/admin/policies",
+            json!({ "sub": role, "obj": obj, "act": act }),
+        )
+        .await;
+    }
+    admin_post(
+        app,
+        "/api/admin/policies/unit-roles",
+        json!({ "user_id": id, "role": role, "unit_id": unit_id, "include_subtree": false }),
+    )
+    .await;
+
+    (id, common::generate_test_token(id), role)
+}
+
+async fn create_chain(app: &TestApp, body: Value) -> Value {
+    let response = app
+        .api
+        .post(CHAINS)
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .json(&body)
+        .await;
+    assert_eq!(
+        response.status_code(),
+        StatusCode::CREATED,
+        "{}",
+        response.text()
+    );
+    response.json()
+}
+
+async fn decide(
+    app: &TestApp,
+    token: &str,
+    requisition_id: Uuid,
+    action: &str,
+    body: Value,
+) -> axum_test::TestResponse {
+    app.api
+        .post(&format!(
+            "/api/admin/requisitions/{}/{}",
+            requisition_id, action
+        ))
+        .add_header("Authorization", format!("Bearer {}", token))
+        .json(&body)
+        .await
+}
+
+async fn approval_steps(app: &TestApp, path: &str) -> Vec<Value> {
+    let response = app
+        .api
+        .get(path)
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .await;
+    assert_eq!(
+        response.status_code(),
+        StatusCode::OK,
+        "{}",
+        response.text()
+    );
+    response.json::<Value>().as_array().unwrap().clone()
+}
+
+// ============================
+// TESTES
+// ============================
+
+#[tokio::test]
+async fn test_chain_crud() {
+    let app = common::spawn_app().await;
+    let warehouse_id = create_warehouse(&app).await;
+
+    let chain = create_chain(
+        &app,
+        json!({
+            "document_type": "REQUISITION",
+            "name": "Acima de R$ 5.000",
+            "min_value": "5000",
+            "warehouse_id": warehouse_id,
+            "steps": [
+                { "step_order": 1, "name": "Gestor", "approver_role": "ROLE_ADMIN" },
+                { "step_order": 2, "name": "Financeiro", "approver_role": "ROLE_FINANCE" },
+                { "step_order": 2, "name": "Diretor", "approver_role": "ROLE_DIRECTOR" }
+            ]
+        }),
+    )
+    .await;
+    assert_eq!(chain["is_active"], true);
+    assert_eq!(chain["steps"].as_array().unwrap().len(), 3);
+    let id = chain["id"].as_str().unwrap();
+
+    let response = app
+        .api
+        .get(&format!("{}?document_type=REQUISITION", CHAINS))
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    assert!(response
+        .json::<Value>()
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|c| c["id"] == chain["id"]));
+
+    let response = app
+        .api
+        .put(&format!("{}/{}", CHAINS, id))
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .json(&json!({
+            "name": "Acima de R$ 10.000",
+            "min_value": "10000",
+            "warehouse_id": warehouse_id,
+            "is_active": true,
+            "steps": [{ "step_order": 1, "name": "Diretor", "approver_role": "ROLE_DIRECTOR" }]
+        }))
+        .await;
+    assert_eq!(
+        response.status_code(),
+        StatusCode::OK,
+        "{}",
+        response.text()
+    );
+    let updated: Value = response.json();
+    assert_eq!(updated["name"], "Acima de R$ 10.000");
+    assert_eq!(updated["steps"].as_array().unwrap().len(), 1);
+
+    let response = app
+        .api
+        .delete(&format!("{}/{}", CHAINS, id))
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::NO_CONTENT);
+
+    let response = app
+        .api
+        .delete(&format!("{}/{}", CHAINS, id))
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::CONFLICT);
+
+    let response = app
+        .api
+        .get(&format!("{}/{}", CHAINS, Uuid::new_v4()))
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_chain_validation() {
+    let app = common::spawn_app().await;
+
+    for body in [
+        // Sem etapas
+        json!({ "document_type": "REQUISITION", "name": "Vazia", "steps": [] }),
+        // Etapa sem aprovador
+        json!({
+            "document_type": "STOCK_TRANSFER",
+            "name": "Sem aprovador",
+            "steps": [{ "step_order": 1, "name": "Ninguém" }]
+        }),
+        // Valor mínimo negativo
+        json!({
+            "document_type": "DISPOSAL_REQUEST",
+            "name": "Negativa",
+            "min_value": "-1",
+            "steps": [{ "step_order": 1, "name": "Diretor", "approver_role": "ROLE_ADMIN" }]
+        }),
+    ] {
+        let response = app
+            .api
+            .post(CHAINS)
+            .add_header("Authorization", format!("Bearer {}", app.admin_token))
+            .json(&body)
+            .await;
+        assert_eq!(response.status_code(), StatusCode::BAD_REQUEST, "{}", body);
+    }
+
+    // Somente administradores configuram cadeias
+    let response = app
+        .api
+        .get(CHAINS)
+        .add_header("Authorization", format!("Bearer {}", app.user_token))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn test_urgent_requisition_needs_second_approver() {
+    let app = common::spawn_app().await;
+    let admin_id = user_id(&app, "vinicius").await;
+    let unit_id = create_unit(&app).await;
+    let warehouse_id = create_warehouse(&app).await;
+    let (director_id, director_token, director_role) = create_director(&app, unit_id).await;
+
+    create_chain(
+        &app,
+        json!({
+            "document_type": "REQUISITION",
+            "name": "Urgentes",
+            "priorities": ["URGENT"],
+            "warehouse_id": warehouse_id,
+            "steps": [
+                { "step_order": 1, "name": "Gestor", "approver_user_id": admin_id },
+                { "step_order": 2, "name": "Diretor do almoxarifado", "approver_role": director_role }
+            ]
+        }),
+    )
+    .await;
+    let requisition_id =
+        create_pending_requisition(&app, warehouse_id, unit_id, "URGENT", 100).await;
+
+    // Consultar as etapas antes da primeira decisão não grava nada
+    let steps = approval_steps(
+        &app,
+        &format!("/api/admin/requisitions/{}/approval-steps", requisition_id),
+    )
+    .await;
+    assert_eq!(steps.len(), 2);
+    assert!(steps.iter().all(|s| s["status"] == "PENDING"));
+    let stored: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM document_approval_steps WHERE document_id = $1")
+            .bind(requisition_id)
+            .fetch_one(&app.db_auth)
+            .await
+            .unwrap();
+    assert_eq!(stored, 0);
+
+    // O diretor só decide depois do gestor
+    let response = decide(&app, &director_token, requisition_id, "approve", json!({})).await;
+    assert_eq!(
+        response.status_code(),
+        StatusCode::FORBIDDEN,
+        "{}",
+        response.text()
+    );
+
+    // Primeira etapa: a requisição segue pendente
+    let response = decide(&app, &app.admin_token, requisition_id, "approve", json!({})).await;
+    assert_eq!(
+        response.status_code(),
+        StatusCode::OK,
+        "{}",
+        response.text()
+    );
+    assert_eq!(response.json::<Value>()["status"], "Pending");
+
+    // O mesmo aprovador não decide duas etapas
+    let response = decide(&app, &app.admin_token, requisition_id, "approve", json!({})).await;
+    assert_eq!(response.status_code(), StatusCode::CONFLICT);
+
+    let steps = approval_steps(
+        &app,
+        &format!("/api/admin/requisitions/{}/approval-steps", requisition_id),
+    )
+    .await;
+    assert_eq!(steps.len(), 2);
+    assert_eq!(steps[0]["status"], "APPROVED");
+    assert_eq!(steps[0]["decided_by"], json!(admin_id));
+    assert_eq!(steps[1]["status"], "PENDING");
+
+    // Última etapa conclui a aprovação
+    let response = decide(&app, &director_token, requisition_id, "approve", json!({})).await;
+    assert_eq!(
+        response.status_code(),
+        StatusCode::OK,
+        "{}",
+        response.text()
+    );
+    let body: Value = response.json();
+    assert_eq!(body["status"], "Approved");
+    assert_eq!(body["approved_by"], json!(director_id));
+}
+
+#[tokio::test]
+async fn test_requisition_outside_chain_conditions_keeps_single_step() {
+    let app = common::spawn_app().await;
+    let warehouse_id = create_warehouse(&app).await;
+    let unit_id = create_unit(&app).await;
+
+    create_chain(
+        &app,
+        json!({
+            "document_type": "REQUISITION",
+            "name": "Alto valor",
+            "min_value": "1000",
+            "warehouse_id": warehouse_id,
+            "steps": [{ "step_order": 1, "name": "Diretor", "approver_role": "ROLE_DIRECTOR" }]
+        }),
+    )
+    .await;
+    let requisition_id = create_pending_requisition(&app, warehouse_id, unit_id, "NORMAL", 999).await;
+
+    let steps = approval_steps(
+        &app,
+        &format!("/api/admin/requisitions/{}/approval-steps", requisition_id),
+    )
+    .await;
+    assert!(steps.is_empty());
+
+    let response = decide(&app, &app.admin_token, requisition_id, "approve", json!({})).await;
+    assert_eq!(
+        response.status_code(),
+        StatusCode::OK,
+        "{}",
+        response.text()
+    );
+    assert_eq!(response.json::<Value>()["status"], "Approved");
+}
+
+#[tokio::test]
+async fn test_rejection_in_chain_rejects_requisition() {
+    let app = common::spawn_app().await;
+    let unit_id = create_unit(&app).await;
+    let warehouse_id = create_warehouse(&app).await;
+    let (_, director_token, director_role) = create_director(&app, unit_id).await;
+
+    create_chain(
+        &app,
+        json!({
+            "document_type": "REQUISITION",
+            "name": "Diretor",
+            "warehouse_id": warehouse_id,
+            "steps": [{ "step_order": 1, "name": "Diretor", "approver_role": director_role }]
+        }),
+    )
+    .await;
+    let requisition_id =
+        create_pending_requisition(&app, warehouse_id, unit_id, "HIGH", 50).await;
+
+    // O administrador não é aprovador desta cadeia
+    let response = decide(
+        &app,
+        &app.admin_token,
+        requisition_id,
+        "reject",
+        json!({ "reason": "Sem orçamento" }),
+    )
+    .await;
+    assert_eq!(response.status_code(), StatusCode::FORBIDDEN);
+
+    let response = decide(
+        &app,
+        &director_token,
+        requisition_id,
+        "reject",
+        json!({ "reason": "Sem orçamento" }),
+    )
+    .await;
+    assert_eq!(
+        response.status_code(),
+        StatusCode::OK,
+        "{}",
+        response.text()
+    );
+    assert_eq!(response.json::<Value>()["status"], "Rejected");
+
+    let steps = approval_steps(
+        &app,
+        &format!("/api/admin/requisitions/{}/approval-steps", requisition_id),
+    )
+    .await;
+    assert_eq!(steps[0]["status"], "REJECTED");
+    assert_eq!(steps[0]["comments"], "Sem orçamento");
+}
+
+#[tokio::test]
+async fn test_transfer_confirmation_waits_for_chain() {
+    let app = common::spawn_app().await;
+    let admin_id = user_id(&app, "vinicius").await;
+    let source_id = create_warehouse(&app).await;
+    let destination_id = create_warehouse(&app).await;
+
+    create_chain(
+        &app,
+        json!({
+            "document_type": "STOCK_TRANSFER",
+            "name": "Saídas do almoxarifado",
+            "warehouse_id": source_id,
+            "steps": [{ "step_order": 1, "name": "Gestor", "approver_user_id": admin_id }]
+        }),
+    )
+    .await;
+    let transfer_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO stock_transfers (transfer_number, source_warehouse_id, destination_warehouse_id, initiated_by)
+         VALUES ($1, $2, $3, $4)
+         RETURNING id",
+    )
+    .bind(format!("TRF{}", random_suffix()))
+    .bind(source_id)
+    .bind(destination_id)
+    .bind(admin_id)
+    .fetch_one(&app.db_auth)
+    .await
+    .unwrap();
+
+    let confirm = |app: &TestApp| {
+        app.api
+            .post(&format!("/api/admin/transfers/{}/confirm", transfer_id))
+            .add_header("Authorization", format!("Bearer {}", app.admin_token))
+            .json(&json!({ "items": [] }))
+    };
+
+    let response = confirm(&app).await;
+    assert_eq!(
+        response.status_code(),
+        StatusCode::BAD_REQUEST,
+        "{}",
+        response.text()
+    );
+
+    let response = app
+        .api
+        .post(&format!("/api/admin/transfers/{}/approve", transfer_id))
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .json(&json!({ "comments": "De acordo" }))
+        .await;
+    assert_eq!(
+        response.status_code(),
+        StatusCode::OK,
+        "{}",
+        response.text()
+    );
+    let steps: Value = response.json();
+    assert_eq!(steps[0]["status"], "APPROVED");
+
+    let response = confirm(&app).await;
+    assert_eq!(
+        response.status_code(),
+        StatusCode::OK,
+        "{}",
+        response.text()
+    );
+}
@@ -5,6 +5,7 @@
 
 mod common;
 
+use chrono::{Duration, NaiveDate, Utc};
 use serde_json::{json, Value};
 use uuid::Uuid;
 
@@ -430,6 +431,22 @@ async fn test_cleanup_old_logs() {
 
     assert_eq!(count, 0, "Old log should be deleted");
 
+    // ...but only after being exported to a sealed archive
+    let body: Value = response.json();
+    assert!(body["archived_count"].as_i64().unwrap() >= 1);
+    let archived: bool = sqlx::query_scalar(
+        r#"
+        SELECT EXISTS(
+            SELECT 1 FROM audit_log_archives
+            WHERE chain_date = ((NOW() - INTERVAL '100 days') AT TIME ZONE 'UTC')::DATE
+        )
+        "#,
+    )
+    .fetch_one(&app.db_logs)
+    .await
+    .unwrap();
+    assert!(archived, "Old log should be archived");
+
     // Verify recent log still exists - use parameterized query
     let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM audit_logs WHERE action = $1")
         .bind(&recent_action)
@@ -606,3 +623,195 @@ async fn test_middleware_logs_login_attempt() {
         "Login should be logged by middleware"
     );
 }
+
+// =============================================================================
+// HASH CHAIN
+// =============================================================================
+
+/// Day of year 2999 derived from a fresh UUID: never sealed nor archived,
+/// and practically never shared with another test run
+fn isolated_future_day() -> NaiveDate {
+    let offset = (Uuid::new_v4().as_u128() % 365) as i64;
+    NaiveDate::from_ymd_opt(2999, 1, 1).unwrap() + Duration::days(offset)
+}
+
+async fn insert_chained(app: &common::TestApp, day: NaiveDate, count: usize) -> Vec<(Uuid, i64)> {
+    let mut entries = Vec::new();
+    for i in 0..count {
+        let entry: (Uuid, i64) = sqlx::query_as(
+            r#"
+            INSERT INTO audit_logs (action, resource, created_at)
+            VALUES ($1, '/chain', ($2::DATE + TIME '12:00') AT TIME ZONE 'UTC')
+            RETURNING id, seq
+            "#,
+        )
+        .bind(format!("chain_test_{}", i))
+        .bind(day)
+        .fetch_one(&app.db_logs)
+        .await
+        .unwrap();
+        entries.push(entry);
+    }
+    entries
+}
+
+async fn verify_day(app: &common::TestApp, day: NaiveDate) -> Value {
+    let response = app
+        .api
+        .get("/api/admin/audit-logs/verify")
+        .add_query_param("from", day.to_string())
+        .add_query_param("to", day.to_string())
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .await;
+    response.assert_status_ok();
+
+    let body: Value = response.json();
+    body["days"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|d| d["chain_date"] == day.to_string())
+        .cloned()
+        .expect("day missing from verification")
+}
+
+fn has_issue(day: &Value, kind: &str, seq: i64) -> bool {
+    day["issues"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|i| i["kind"] == kind && i["seq"] == seq)
+}
+
+#[tokio::test]
+async fn test_audit_entries_are_chained() {
+    let app = common::spawn_app().await;
+    let day = isolated_future_day();
+    let entries = insert_chained(&app, day, 3).await;
+
+    let links: Vec<(String, String)> = sqlx::query_as(
+        "SELECT prev_hash::TEXT, entry_hash::TEXT FROM audit_logs WHERE id = ANY($1) ORDER BY seq",
+    )
+    .bind(entries.iter().map(|e| e.0).collect::<Vec<_>>())
+    .fetch_all(&app.db_logs)
+    .await
+    .unwrap();
+
+    assert_eq!(links.len(), 3);
+    assert_eq!(links[1].0, links[0].1);
+    assert_eq!(links[2].0, links[1].1);
+}
+
+#[tokio::test]
+async fn test_audit_logs_reject_update_and_delete() {
+    let app = common::spawn_app().await;
+    let day = isolated_future_day();
+    let entries = insert_chained(&app, day, 1).await;
+
+    let update = sqlx::query("UPDATE audit_logs SET action = 'forged' WHERE id = $1")
+        .bind(entries[0].0)
+        .execute(&app.db_logs)
+        .await;
+    assert!(update.is_err());
+
+    let delete = sqlx::query("DELETE FROM audit_logs WHERE id = $1")
+        .bind(entries[0].0)
+        .execute(&app.db_logs)
+        .await;
+    assert!(delete.is_err());
+}
+
+#[tokio::test]
+async fn test_verify_detects_edited_entry() {
+    let app = common::spawn_app().await;
+    let day = isolated_future_day();
+    let entries = insert_chained(&app, day, 3).await;
+
+    // Edição direta por quem tem acesso ao banco, contornando o guard
+    let mut tx = app.db_logs.begin().await.unwrap();
+    sqlx::query("ALTER TABLE audit_logs DISABLE TRIGGER trg_audit_log_guard")
+        .execute(&mut *tx)
+        .await
+        .unwrap();
+    sqlx::query("UPDATE audit_logs SET username = 'someone_else' WHERE id = $1")
+        .bind(entries[1].0)
+        .execute(&mut *tx)
+        .await
+        .unwrap();
+    sqlx::query("ALTER TABLE audit_logs ENABLE TRIGGER trg_audit_log_guard")
+        .execute(&mut *tx)
+        .await
+        .unwrap();
+    tx.commit().await.unwrap();
+
+    let report = verify_day(&app, day).await;
+    assert_eq!(report["valid"], false);
+    assert!(has_issue(&report, "HASH_MISMATCH", entries[1].1));
+}
+
+#[tokio::test]
+async fn test_verify_detects_gap() {
+    let app = common::spawn_app().await;
+    let day = isolated_future_day();
+    let entries = insert_chained(&app, day, 3).await;
+
+    let mut tx = app.db_logs.begin().await.unwrap();
+    sqlx::query("SET LOCAL audit.archiving = 'on'")
+        .execute(&mut *tx)
+        .await
+        .unwrap();
+    sqlx::query("DELETE FROM audit_logs WHERE id = $1")
+        .bind(entries[1].0)
+        .execute(&mut *tx)
+        .await
+        .unwrap();
+    tx.commit().await.unwrap();
+
+    let report = verify_day(&app, day).await;
+    assert_eq!(report["valid"], false);
+    assert!(has_issue(&report, "GAP", entries[1].1));
+}
+
+#[tokio::test]
+async fn test_seal_closed_days_signs_checkpoint() {
+    let app = common::spawn_app().await;
+    let offset = 30 + (Uuid::new_v4().as_u128() % 55) as i64;
+    let day = Utc::now().date_naive() - Duration::days(offset);
+    insert_chained(&app, day, 2).await;
+
+    let response = app
+        .api
+        .post("/api/admin/audit-logs/checkpoints")
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .await;
+    response.assert_status_ok();
+
+    let report = verify_day(&app, day).await;
+    assert_eq!(report["checkpointed"], true);
+    assert_eq!(report["valid"], true);
+
+    let response = app
+        .api
+        .get("/api/admin/audit-logs/checkpoints")
+        .add_query_param("from", day.to_string())
+        .add_query_param("to", day.to_string())
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .await;
+    response.assert_status_ok();
+    let checkpoints: Vec<Value> = response.json();
+    assert_eq!(checkpoints.len(), 1);
+    assert!(!checkpoints[0]["signature"].as_str().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_verify_requires_admin() {
+    let app = common::spawn_app().await;
+
+    let response = app
+        .api
+        .get("/api/admin/audit-logs/verify")
+        .add_header("Authorization", format!("Bearer {}", app.user_token))
+        .await;
+
+    response.assert_status(axum::http::StatusCode::FORBIDDEN);
+}
@@ -0,0 +1,422 @@
+mod common;
+
+use common::TestApp;
+use http::StatusCode;
+use rust_decimal::Decimal;
+use serde_json::{json, Value};
+use std::str::FromStr;
+use uuid::Uuid;
+
+// ============================
+// HELPERS
+// ============================
+
+fn random_code() -> String {
+    Uuid::new_v4()
+        .simple()
+        .to_string()
+        .chars()
+        .take(8)
+        .collect()
+}
+
+async fn post(app: &TestApp, path: &str, body: Value) -> Value {
+    let response = app
+        .api
+        .post(path)
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .json(&body)
+        .await;
+    assert_eq!(
+        response.status_code(),
+        StatusCode::CREATED,
+        "{} -> {}",
+        path,
+        response.text()
+    );
+    response.json()
+}
+
+async fn get(app: &TestApp, path: &str) -> Value {
+    let response = app
+        .api
+        .get(path)
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .await;
+    assert_eq!(
+        response.status_code(),
+        StatusCode::OK,
+        "{} -> {}",
+        path,
+        response.text()
+    );
+    response.json()
+}
+
+/// PDM e dois itens do mesmo produto com descrições diferentes: (pdm, a, b)
+async fn create_duplicates(app: &TestApp) -> (Value, Value, Value) {
+    let unit = post(
+        app,
+        "/api/admin/catalog/units-of-measure",
+        json!({ "name": random_code(), "symbol": random_code(), "is_base_unit": true }),
+    )
+    .await;
+    let group = post(
+        app,
+        "/api/admin/catalog/catmat/groups",
+        json!({ "code": random_code(), "name": "Grupo de fusão", "is_active": true }),
+    )
+    .await;
+    let class = post(
+        app,
+        "/api/admin/catalog/catmat/classes",
+        json!({ "group_id": group["id"], "code": random_code(), "name": "Classe de fusão", "is_active": true }),
+    )
+    .await;
+    let pdm = post(
+        app,
+        "/api/admin/catalog/catmat/pdms",
+        json!({ "class_id": class["id"], "code": random_code(), "description": "Caneta esferográfica", "is_active": true }),
+    )
+    .await;
+
+    let mut items = Vec::new();
+    for description in [
+        "CANETA ESFEROGRÁFICA AZUL PONTA MÉDIA 1.0MM",
+        "Caneta esferografica azul ponta media 1,0 mm",
+    ] {
+        items.push(
+            post(
+                app,
+                "/api/admin/catalog/catmat/items",
+                json!({
+                    "pdm_id": pdm["id"],
+                    "unit_of_measure_id": unit["id"],
+                    "code": random_code(),
+                    "description": description,
+                    "is_sustainable": false,
+                    "is_active": true
+                }),
+            )
+            .await,
+        );
+    }
+    let b = items.pop().unwrap();
+    let a = items.pop().unwrap();
+    (pdm, a, b)
+}
+
+fn uuid(value: &Value) -> Uuid {
+    Uuid::parse_str(value.as_str().unwrap()).unwrap()
+}
+
+async fn create_warehouse(app: &TestApp) -> Uuid {
+    let uid = Uuid::new_v4().simple().to_string();
+    let city_id: Uuid = sqlx::query_scalar("SELECT id FROM cities LIMIT 1")
+        .fetch_one(&app.db_auth)
+        .await
+        .expect("Nenhuma cidade cadastrada");
+    sqlx::query_scalar(
+        "INSERT INTO warehouses (name, code, warehouse_type, city_id, is_active)
+         VALUES ($1, $2, 'SECTOR', $3, true)
+         RETURNING id",
+    )
+    .bind(format!("Almoxarifado {}", &uid[..8]))
+    .bind(format!("WM{}", &uid[..14]))
+    .bind(city_id)
+    .fetch_one(&app.db_auth)
+    .await
+    .unwrap()
+}
+
+async fn insert_stock(
+    app: &TestApp,
+    warehouse_id: Uuid,
+    item_id: Uuid,
+    quantity: &str,
+    value: &str,
+) {
+    sqlx::query(
+        "INSERT INTO warehouse_stocks (warehouse_id, catalog_item_id, quantity, average_unit_value)
+         VALUES ($1, $2, $3, $4)",
+    )
+    .bind(warehouse_id)
+    .bind(item_id)
+    .bind(Decimal::from_str(quantity).unwrap())
+    .bind(Decimal::from_str(value).unwrap())
+    .execute(&app.db_auth)
+    .await
+    .unwrap();
+}
+
+async fn merge(app: &TestApp, survivor_id: &Value, duplicate_id: &Value) -> http::StatusCode {
+    app.api
+        .post("/api/admin/catalog/catmat/items/merge")
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .json(&json!({ "survivor_id": survivor_id, "duplicate_ids": [duplicate_id] }))
+        .await
+        .status_code()
+}
+
+// ============================
+// TESTS
+// ============================
+
+#[tokio::test]
+async fn test_duplicate_report_clusters_similar_items() {
+    let app = common::spawn_app().await;
+    let (pdm, a, b) = create_duplicates(&app).await;
+
+    let body = get(
+        &app,
+        &format!(
+            "/api/admin/catalog/catmat/items/duplicates?pdm_id={}&min_similarity=0.5",
+            pdm["id"].as_str().unwrap()
+        ),
+    )
+    .await;
+
+    let cluster = body["clusters"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|c| {
+            c["items"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .any(|i| i["id"] == a["id"])
+        })
+        .expect("grupo de duplicados não encontrado");
+    let ids: Vec<&Value> = cluster["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|i| &i["id"])
+        .collect();
+    assert!(ids.contains(&&b["id"]));
+    assert!(cluster["max_similarity"].as_f64().unwrap() >= 0.5);
+    assert!(ids.contains(&&cluster["suggested_survivor_id"]));
+}
+
+#[tokio::test]
+async fn test_duplicate_report_rejects_low_similarity() {
+    let app = common::spawn_app().await;
+
+    let response = app
+        .api
+        .get("/api/admin/catalog/catmat/items/duplicates?min_similarity=0.1")
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .await;
+
+    assert_eq!(response.status_code(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_merge_combines_stock_and_redirects_old_id() {
+    let app = common::spawn_app().await;
+    let (_, survivor, duplicate) = create_duplicates(&app).await;
+    let (survivor_id, duplicate_id) = (uuid(&survivor["id"]), uuid(&duplicate["id"]));
+
+    // Mesmo almoxarifado nos dois itens, e um almoxarifado só com o duplicado
+    let shared = create_warehouse(&app).await;
+    let only_duplicate = create_warehouse(&app).await;
+    insert_stock(&app, shared, survivor_id, "10", "2.0000").await;
+    insert_stock(&app, shared, duplicate_id, "30", "4.0000").await;
+    insert_stock(&app, only_duplicate, duplicate_id, "5", "1.5000").await;
+
+    let response = app
+        .api
+        .post("/api/admin/catalog/catmat/items/merge")
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .json(&json!({
+            "survivor_id": survivor_id,
+            "duplicate_ids": [duplicate_id],
+            "reason": "Duplicado da importação legada"
+        }))
+        .await;
+    assert_eq!(
+        response.status_code(),
+        StatusCode::OK,
+        "{}",
+        response.text()
+    );
+    let result: Value = response.json();
+    assert_eq!(result["combined_stocks"], 1);
+
+    let stocks: Vec<(Uuid, Decimal, Decimal)> = sqlx::query_as(
+        "SELECT warehouse_id, quantity, average_unit_value FROM warehouse_stocks
+         WHERE catalog_item_id = $1 AND warehouse_id = ANY($2)",
+    )
+    .bind(survivor_id)
+    .bind(vec![shared, only_duplicate])
+    .fetch_all(&app.db_auth)
+    .await
+    .unwrap();
+    assert_eq!(stocks.len(), 2);
+    let (_, quantity, value) = stocks.iter().find(|s| s.0 == shared).unwrap();
+    assert_eq!(*quantity, Decimal::from(40));
+    assert_eq!(*value, Decimal::from_str("3.5").unwrap());
+    let (_, quantity, value) = stocks.iter().find(|s| s.0 == only_duplicate).unwrap();
+    assert_eq!(*quantity, Decimal::from(5));
+    assert_eq!(*value, Decimal::from_str("1.5").unwrap());
+
+    // O ID antigo resolve para o sobrevivente
+    let item = get(
+        &app,
+        &format!("/api/admin/catalog/catmat/items/{}", duplicate_id),
+    )
+    .await;
+    assert_eq!(item["id"], survivor["id"]);
+
+    let redirects = get(
+        &app,
+        &format!(
+            "/api/admin/catalog/catmat/items/redirects?new_item_id={}",
+            survivor_id
+        ),
+    )
+    .await;
+    assert_eq!(redirects["total"], 1);
+    assert_eq!(redirects["data"][0]["old_item_id"], duplicate["id"]);
+    assert_eq!(redirects["data"][0]["old_code"], duplicate["code"]);
+}
+
+#[tokio::test]
+async fn test_merge_rejects_survivor_among_duplicates() {
+    let app = common::spawn_app().await;
+    let (_, a, b) = create_duplicates(&app).await;
+
+    let response = app
+        .api
+        .post("/api/admin/catalog/catmat/items/merge")
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .json(&json!({ "survivor_id": a["id"], "duplicate_ids": [b["id"], a["id"]] }))
+        .await;
+
+    assert_eq!(response.status_code(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_merge_unknown_item_returns_404() {
+    let app = common::spawn_app().await;
+    let (_, a, _) = create_duplicates(&app).await;
+
+    let response = app
+        .api
+        .post("/api/admin/catalog/catmat/items/merge")
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .json(&json!({ "survivor_id": a["id"], "duplicate_ids": [Uuid::new_v4()] }))
+        .await;
+
+    assert_eq!(response.status_code(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_merge_requires_admin() {
+    let app = common::spawn_app().await;
+
+    let response = app
+        .api
+        .post("/api/admin/catalog/catmat/items/merge")
+        .add_header("Authorization", format!("Bearer {}", app.user_token))
+        .json(&json!({ "survivor_id": Uuid::new_v4(), "duplicate_ids": [Uuid::new_v4()] }))
+        .await;
+
+    assert_eq!(response.status_code(), StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn test_merge_keeps_duplicate_as_inactive_redirect() {
+    let app = common::spawn_app().await;
+    let (_, survivor, duplicate) = create_duplicates(&app).await;
+    // Código numérico, como os do catálogo oficial
+    let code = (Uuid::new_v4().as_u128() % 900_000_000 + 100_000_000).to_string();
+    sqlx::query("UPDATE catmat_items SET code = $1 WHERE id = $2")
+        .bind(&code)
+        .bind(uuid(&duplicate["id"]))
+        .execute(&app.db_auth)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        merge(&app, &survivor["id"], &duplicate["id"]).await,
+        StatusCode::OK
+    );
+
+    let (is_active, merged_into_id): (bool, Option<Uuid>) =
+        sqlx::query_as("SELECT is_active, merged_into_id FROM catmat_items WHERE id = $1")
+            .bind(uuid(&duplicate["id"]))
+            .fetch_one(&app.db_auth)
+            .await
+            .unwrap();
+    assert!(!is_active);
+    assert_eq!(merged_into_id, Some(uuid(&survivor["id"])));
+
+    // Não pode participar de nova fusão nem ser reativado
+    assert_eq!(
+        merge(&app, &survivor["id"], &duplicate["id"]).await,
+        StatusCode::BAD_REQUEST
+    );
+    let response = app
+        .api
+        .put(&format!(
+            "/api/admin/catalog/catmat/items/{}",
+            duplicate["id"].as_str().unwrap()
+        ))
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .json(&json!({ "is_active": true }))
+        .await;
+    assert_eq!(
+        response.status_code(),
+        StatusCode::BAD_REQUEST,
+        "{}",
+        response.text()
+    );
+
+    // O código oficial continua ocupado: a importação não recria o item
+    let response = app
+        .api
+        .post(&format!(
+            "/api/admin/catalog/official/catmat/items/{}/import",
+            code
+        ))
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .await;
+    assert_eq!(
+        response.status_code(),
+        StatusCode::CONFLICT,
+        "{}",
+        response.text()
+    );
+}
+
+#[tokio::test]
+async fn test_merge_rejects_different_units_of_measure() {
+    let app = common::spawn_app().await;
+    let (_, a, b) = create_duplicates(&app).await;
+    let unit = post(
+        &app,
+        "/api/admin/catalog/units-of-measure",
+        json!({ "name": random_code(), "symbol": random_code(), "is_base_unit": true }),
+    )
+    .await;
+    sqlx::query("UPDATE catmat_items SET unit_of_measure_id = $1 WHERE id = $2")
+        .bind(uuid(&unit["id"]))
+        .bind(uuid(&b["id"]))
+        .execute(&app.db_auth)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        merge(&app, &a["id"], &b["id"]).await,
+        StatusCode::BAD_REQUEST
+    );
+
+    let is_active: bool = sqlx::query_scalar("SELECT is_active FROM catmat_items WHERE id = $1")
+        .bind(uuid(&b["id"]))
+        .fetch_one(&app.db_auth)
+        .await
+        .unwrap();
+    assert!(is_active);
+}
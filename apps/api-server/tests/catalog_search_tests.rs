@@ -0,0 +1,211 @@
+mod common;
+
+use common::TestApp;
+use http::StatusCode;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+// ============================
+// HELPERS
+// ============================
+
+/// Palavra inventada (só consoantes) para não colidir com outros itens
+fn random_word() -> String {
+    const LETTERS: &[u8] = b"bcdfghjklmnpqrstvwxz";
+    Uuid::new_v4()
+        .as_bytes()
+        .iter()
+        .map(|b| LETTERS[*b as usize % LETTERS.len()] as char)
+        .collect()
+}
+
+fn random_code() -> String {
+    Uuid::new_v4()
+        .simple()
+        .to_string()
+        .chars()
+        .take(8)
+        .collect()
+}
+
+async fn post(app: &TestApp, path: &str, body: Value) -> Value {
+    let response = app
+        .api
+        .post(path)
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .json(&body)
+        .await;
+    assert_eq!(
+        response.status_code(),
+        StatusCode::CREATED,
+        "{} -> {}",
+        path,
+        response.text()
+    );
+    response.json()
+}
+
+/// Item CATMAT ativo com a descrição informada
+async fn create_catmat_item(app: &TestApp, description: &str) -> Value {
+    let unit = post(
+        app,
+        "/api/admin/catalog/units-of-measure",
+        json!({ "name": random_code(), "symbol": random_code(), "is_base_unit": true }),
+    )
+    .await;
+    let group = post(
+        app,
+        "/api/admin/catalog/catmat/groups",
+        json!({ "code": random_code(), "name": "Grupo de busca", "is_active": true }),
+    )
+    .await;
+    let class = post(
+        app,
+        "/api/admin/catalog/catmat/classes",
+        json!({ "group_id": group["id"], "code": random_code(), "name": "Classe de busca", "is_active": true }),
+    )
+    .await;
+    let pdm = post(
+        app,
+        "/api/admin/catalog/catmat/pdms",
+        json!({ "class_id": class["id"], "code": random_code(), "description": "PDM de busca", "is_active": true }),
+    )
+    .await;
+    post(
+        app,
+        "/api/admin/catalog/catmat/items",
+        json!({
+            "pdm_id": pdm["id"],
+            "unit_of_measure_id": unit["id"],
+            "code": random_code(),
+            "description": description,
+            "is_sustainable": false,
+            "is_active": true
+        }),
+    )
+    .await
+}
+
+async fn get(app: &TestApp, path: &str) -> Value {
+    let response = app
+        .api
+        .get(path)
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .await;
+    assert_eq!(
+        response.status_code(),
+        StatusCode::OK,
+        "{} -> {}",
+        path,
+        response.text()
+    );
+    response.json()
+}
+
+fn find<'a>(hits: &'a Value, id: &Value) -> Option<&'a Value> {
+    hits.as_array().unwrap().iter().find(|h| h["id"] == *id)
+}
+
+// ============================
+// TESTS
+// ============================
+
+#[tokio::test]
+async fn test_search_catmat_items_ignores_accents_and_highlights() {
+    let app = common::spawn_app().await;
+    let word = random_word();
+    let item = create_catmat_item(&app, &format!("Parafuso {} de AÇO inoxidável", word)).await;
+
+    let body = get(
+        &app,
+        &format!("/api/admin/catalog/catmat/items/search?q={}%20aco", word),
+    )
+    .await;
+    let hit = find(&body["data"], &item["id"]).expect("item não encontrado");
+    assert_eq!(hit["match_kind"], "FULL_TEXT");
+    assert!(hit["rank"].as_f64().unwrap() > 0.0);
+    let highlight = hit["highlight"].as_str().unwrap();
+    assert!(
+        highlight.contains(&format!("<mark>{}</mark>", word)),
+        "{}",
+        highlight
+    );
+    assert!(highlight.contains("<mark>AÇO</mark>"), "{}", highlight);
+
+    // Filtro que exclui o item
+    let body = get(
+        &app,
+        &format!(
+            "/api/admin/catalog/catmat/items/search?q={}&is_sustainable=true",
+            word
+        ),
+    )
+    .await;
+    assert!(find(&body["data"], &item["id"]).is_none());
+}
+
+#[tokio::test]
+async fn test_search_catmat_items_falls_back_to_similarity() {
+    let app = common::spawn_app().await;
+    let word = random_word();
+    let item = create_catmat_item(&app, &format!("Caneta {}", word)).await;
+
+    // Último caractere trocado: nenhum resultado exato
+    let mut typo: String = word.chars().take(word.len() - 1).collect();
+    typo.push(if word.ends_with('b') { 'c' } else { 'b' });
+
+    let body = get(
+        &app,
+        &format!("/api/admin/catalog/catmat/items/search?q={}", typo),
+    )
+    .await;
+    let hit = find(&body["data"], &item["id"]).expect("item não encontrado");
+    assert_eq!(hit["match_kind"], "FUZZY");
+    assert_eq!(hit["highlight"], format!("Caneta <mark>{}</mark>", word));
+}
+
+#[tokio::test]
+async fn test_search_requires_text() {
+    let app = common::spawn_app().await;
+
+    let response = app
+        .api
+        .get("/api/admin/catalog/catser/items/search?q=%20")
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_autocomplete_suggests_by_prefix() {
+    let app = common::spawn_app().await;
+    let word = random_word();
+    let item = create_catmat_item(&app, &format!("Grampeador {}", word)).await;
+
+    let prefix: String = word.chars().take(8).collect();
+    let body = get(
+        &app,
+        &format!(
+            "/api/admin/catalog/autocomplete?q={}&catalog=CATMAT",
+            prefix
+        ),
+    )
+    .await;
+    let suggestion = find(&body, &item["id"]).expect("item não sugerido");
+    assert_eq!(suggestion["catalog"], "CATMAT");
+    assert!(suggestion["highlight"].as_str().unwrap().contains("<mark>"));
+
+    let body = get(
+        &app,
+        &format!(
+            "/api/admin/catalog/autocomplete?q={}&catalog=CATSER",
+            prefix
+        ),
+    )
+    .await;
+    assert!(find(&body, &item["id"]).is_none());
+
+    // Texto curto demais
+    let body = get(&app, "/api/admin/catalog/autocomplete?q=a").await;
+    assert!(body.as_array().unwrap().is_empty());
+}
@@ -0,0 +1,203 @@
+mod common;
+
+use common::TestApp;
+use http::StatusCode;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+const SYNC: &str = "/api/admin/catalog/sync";
+
+// ============================
+// HELPERS
+// ============================
+
+async fn start_sync(app: &TestApp, token: &str, body: Value) -> axum_test::TestResponse {
+    app.api
+        .post(SYNC)
+        .add_header("Authorization", format!("Bearer {}", token))
+        .json(&body)
+        .await
+}
+
+// ============================
+// TESTS
+// ============================
+
+#[tokio::test]
+async fn test_start_sync_rejects_invalid_scope() {
+    let app = common::spawn_app().await;
+
+    for body in [
+        json!({ "catalog": "CATMAT", "scope": "FULL", "code": "10" }),
+        json!({ "catalog": "CATMAT", "scope": "GROUP" }),
+        json!({ "catalog": "CATMAT", "scope": "PDM", "code": "abc" }),
+        json!({ "catalog": "CATSER", "scope": "PDM", "code": "123" }),
+    ] {
+        let response = start_sync(&app, &app.admin_token, body.clone()).await;
+        assert_eq!(
+            response.status_code(),
+            StatusCode::BAD_REQUEST,
+            "{} -> {}",
+            body,
+            response.text()
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_start_sync_conflicts_with_running_sync() {
+    let app = common::spawn_app().await;
+
+    // Execução efetiva deixada em andamento
+    let running_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO catalog_sync_runs (catalog, scope, dry_run) VALUES ('CATSER', 'FULL', FALSE) RETURNING id",
+    )
+    .fetch_one(&app.db_auth)
+    .await
+    .unwrap();
+
+    let response = start_sync(
+        &app,
+        &app.admin_token,
+        json!({ "catalog": "CATSER", "scope": "FULL" }),
+    )
+    .await;
+
+    sqlx::query("DELETE FROM catalog_sync_runs WHERE id = $1")
+        .bind(running_id)
+        .execute(&app.db_auth)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        response.status_code(),
+        StatusCode::CONFLICT,
+        "{}",
+        response.text()
+    );
+}
+
+#[tokio::test]
+async fn test_list_and_get_sync_runs() {
+    let app = common::spawn_app().await;
+
+    let run_id: Uuid = sqlx::query_scalar(
+        r#"INSERT INTO catalog_sync_runs
+               (catalog, scope, scope_code, dry_run, status, added_count, report, finished_at)
+           VALUES ('CATMAT', 'GROUP', '10', TRUE, 'COMPLETED', 1,
+                   '{"added":[{"level":"CLASS","code":"1005","name":"Armas"}],"changed":[],"deactivated":[],"skipped":[]}',
+                   NOW())
+           RETURNING id"#,
+    )
+    .fetch_one(&app.db_auth)
+    .await
+    .unwrap();
+
+    let response = app
+        .api
+        .get(&format!("{}/runs?catalog=CATMAT&limit=500", SYNC))
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .await;
+    assert_eq!(
+        response.status_code(),
+        StatusCode::OK,
+        "{}",
+        response.text()
+    );
+    let body: Value = response.json();
+    let listed = body["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|r| r["id"] == run_id.to_string())
+        .expect("execução não listada");
+    assert!(listed["report"].is_null());
+    assert!(body["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .all(|r| r["catalog"] == "CATMAT"));
+
+    let response = app
+        .api
+        .get(&format!("{}/runs/{}", SYNC, run_id))
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .await;
+    assert_eq!(
+        response.status_code(),
+        StatusCode::OK,
+        "{}",
+        response.text()
+    );
+    let run: Value = response.json();
+    assert_eq!(run["status"], "COMPLETED");
+    assert_eq!(run["report"]["added"][0]["code"], "1005");
+
+    let response = app
+        .api
+        .get(&format!("{}/runs/{}", SYNC, Uuid::new_v4()))
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::NOT_FOUND);
+
+    sqlx::query("DELETE FROM catalog_sync_runs WHERE id = $1")
+        .bind(run_id)
+        .execute(&app.db_auth)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_sync_requires_admin() {
+    let app = common::spawn_app().await;
+
+    let response = start_sync(
+        &app,
+        &app.user_token,
+        json!({ "catalog": "CATMAT", "scope": "FULL", "dry_run": true }),
+    )
+    .await;
+    assert_eq!(response.status_code(), StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn test_import_official_item_rejects_invalid_code() {
+    let app = common::spawn_app().await;
+
+    for path in [
+        "/api/admin/catalog/official/catmat/items/abc/import",
+        "/api/admin/catalog/official/catser/items/0/import",
+    ] {
+        let response = app
+            .api
+            .post(path)
+            .add_header("Authorization", format!("Bearer {}", app.admin_token))
+            .await;
+        assert_eq!(
+            response.status_code(),
+            StatusCode::BAD_REQUEST,
+            "{} -> {}",
+            path,
+            response.text()
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_official_catalog_requires_admin() {
+    let app = common::spawn_app().await;
+
+    let response = app
+        .api
+        .get("/api/admin/catalog/official/catmat/items?item_code=1")
+        .add_header("Authorization", format!("Bearer {}", app.user_token))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::FORBIDDEN);
+
+    let response = app
+        .api
+        .post("/api/admin/catalog/official/catser/items/1/import")
+        .add_header("Authorization", format!("Bearer {}", app.user_token))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::FORBIDDEN);
+}
@@ -1,16 +1,20 @@
+use axum::extract::connect_info::MockConnectInfo;
 use axum_test::TestServer;
-use core_services::field_encryption;
+use core_services::field_encryption::FieldKeyring;
 use core_services::jwt::JwtService;
 use domain::models::{Claims, TokenType};
 use domain::ports::EmailServicePort;
 use email_service::{EmailSender, MockEmailService};
 use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
+use serde_json::{json, Value};
 use sqlx::PgPool;
+use std::net::SocketAddr;
 use std::sync::Arc;
 use std::sync::Once;
 use std::time::{SystemTime, UNIX_EPOCH};
 use uuid::Uuid;
 use waterswamp::build_application_state;
+use waterswamp::extractors::client_ip::TrustedProxies;
 use waterswamp::infra::casbin_setup::setup_casbin;
 use waterswamp::infra::config::Config;
 use waterswamp::routes::build;
@@ -23,22 +27,34 @@ pub struct TestApp {
     pub admin_token: String,
     pub user_token: String,
     pub email_service: Arc<MockEmailService>,
-    pub field_encryption_key: [u8; 32],
+    pub field_keyring: FieldKeyring,
 }
 
 impl TestApp {
-    /// Encrypts `plaintext` with the test app's field encryption key.
+    /// Encrypts `plaintext` with the test app's current field encryption key.
     pub fn encrypt_field(&self, plaintext: &str) -> String {
-        field_encryption::encrypt_field(plaintext, &self.field_encryption_key)
+        self.field_keyring
+            .encrypt(plaintext)
             .expect("encrypt_field failed in test helper")
     }
 }
 
 pub async fn spawn_app() -> TestApp {
+    spawn_app_with(|_| {}).await
+}
+
+/// Como `spawn_app`, mas permite ajustar a configuração antes do wiring
+/// (ex.: registrar um provedor OIDC de teste).
+#[allow(dead_code)]
+pub async fn spawn_app_with(configure: impl FnOnce(&mut Config)) -> TestApp {
     dotenvy::dotenv().ok();
     std::env::set_var("DISABLE_RATE_LIMIT", "true");
 
-    let config = Config::from_env().expect("Falha ao carregar config de teste");
+    let mut config = Config::from_env().expect("Falha ao carregar config de teste");
+    // Os testes simulam clientes distintos por X-Forwarded-For, vindo do
+    // "proxy" de loopback abaixo
+    config.trusted_proxies = TrustedProxies::parse("127.0.0.0/8").unwrap();
+    configure(&mut config);
 
     let pool_auth = PgPool::connect(&config.main_db)
         .await
@@ -76,9 +92,13 @@ pub async fn spawn_app() -> TestApp {
     let email_service_legacy: Arc<dyn EmailSender + Send + Sync> = email_service.clone();
     let email_service_port: Arc<dyn EmailServicePort> = email_service.clone();
 
-    // Parse the encryption key BEFORE moving `config`
-    let field_encryption_key = field_encryption::parse_key(&config.field_encryption_key)
-        .expect("WS_FIELD_ENCRYPTION_KEY must be a valid 64-char hex string");
+    // Load the keyring BEFORE moving `config`
+    let field_keyring = FieldKeyring::from_config(
+        &config.field_encryption_key,
+        config.field_encryption_key_id,
+        &config.field_encryption_previous_keys,
+    )
+    .expect("WS_FIELD_ENCRYPTION_KEY must be a valid 64-char hex string");
 
     // --- WIRING COM FACTORY ---
     let app_state = build_application_state(
@@ -91,7 +111,11 @@ pub async fn spawn_app() -> TestApp {
         email_service_port,
     );
 
-    let app = build(app_state);
+    // Endereço de conexão próprio por app (e /24 própria), para que o
+    // throttling por origem de um teste não alcance os demais
+    let peer = Uuid::new_v4().into_bytes();
+    let peer = SocketAddr::from(([127, peer[0], peer[1], peer[2].max(1)], 40000));
+    let app = build(app_state).layer(MockConnectInfo(peer));
     let api = TestServer::new(app).unwrap();
 
     // Usa o usuário "vinicius" (admin) criado pelo seed do Casbin
@@ -113,7 +137,7 @@ pub async fn spawn_app() -> TestApp {
         admin_token: generate_test_token(vinicius_id),
         user_token: generate_test_token(bob_id),
         email_service,
-        field_encryption_key,
+        field_keyring,
     }
 }
 
@@ -202,7 +226,7 @@ pub async fn cleanup_test_users(pool: &PgPool) -> Result<(), sqlx::Error> {
 // Helpers para criar usuário no banco (bypass API)
 pub async fn create_test_user(
     pool: &PgPool,
-    enc_key: &[u8; 32],
+    keyring: &FieldKeyring,
 ) -> Result<(String, String, String), Box<dyn std::error::Error>> {
     use core_services::security::hash_password;
     let counter = std::time::SystemTime::now()
@@ -218,9 +242,10 @@ pub async fn create_test_user(
     })
     .await??;
 
-    let encrypted_email = field_encryption::encrypt_field(&email, enc_key)
-        .expect("encrypt_field failed in create_test_user");
-    let email_index = field_encryption::blind_index(&email, enc_key);
+    let encrypted_email = keyring
+        .encrypt(&email)
+        .expect("encrypt failed in create_test_user");
+    let email_index = keyring.blind_index(&email);
 
     sqlx::query(
         "INSERT INTO users (username, email, email_index, password_hash) VALUES ($1, $2, $3, $4)",
@@ -289,3 +314,142 @@ pub fn generate_custom_token(
 pub fn generate_expired_token(user_id: Uuid, token_type: TokenType) -> String {
     generate_custom_token(user_id, token_type, -60)
 }
+
+// ============================
+// Helpers de cadastro (via API do admin)
+// ============================
+
+#[allow(dead_code)]
+pub fn random_suffix() -> String {
+    Uuid::new_v4().simple().to_string()[..8].to_string()
+}
+
+/// POST autenticado como admin; falha o teste se a resposta não for 2xx
+#[allow(dead_code)]
+pub async fn admin_post(app: &TestApp, path: &str, body: Value) -> Value {
+    let response = app
+        .api
+        .post(path)
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .json(&body)
+        .await;
+    assert!(
+        response.status_code().is_success(),
+        "POST {} falhou: {} {}",
+        path,
+        response.status_code(),
+        response.text()
+    );
+    response.json()
+}
+
+/// CPF válido gerado a partir de um UUID
+#[allow(dead_code)]
+pub fn generate_cpf() -> String {
+    let uuid = Uuid::new_v4().simple().to_string();
+    let mut digits: Vec<u32> = uuid
+        .chars()
+        .filter(|c| c.is_ascii_digit())
+        .take(9)
+        .map(|c| c.to_digit(10).unwrap())
+        .collect();
+    while digits.len() < 9 {
+        digits.push(0);
+    }
+    if digits.iter().all(|&d| d == digits[0]) {
+        digits[8] = (digits[0] + 1) % 10;
+    }
+
+    for weight in [10, 11] {
+        let sum: u32 = digits
+            .iter()
+            .enumerate()
+            .map(|(i, d)| d * (weight - i as u32))
+            .sum();
+        let rem = (sum * 10) % 11;
+        digits.push(if rem >= 10 { 0 } else { rem });
+    }
+
+    digits.iter().map(|d| d.to_string()).collect()
+}
+
+#[allow(dead_code)]
+pub async fn create_driver(app: &TestApp) -> Uuid {
+    create_driver_with(app, json!({})).await
+}
+
+/// Condutor com os campos informados sobrepostos aos dados padrão
+#[allow(dead_code)]
+pub async fn create_driver_with(app: &TestApp, fields: Value) -> Uuid {
+    let mut body = json!({
+        "driver_type": "SERVER",
+        "full_name": format!("Motorista {}", random_suffix()),
+        "cpf": generate_cpf(),
+        "cnh_number": &Uuid::new_v4().simple().to_string()[..11],
+        "cnh_category": "B",
+        "cnh_expiration": "2028-06-15",
+    });
+    if let (Some(body), Some(fields)) = (body.as_object_mut(), fields.as_object()) {
+        body.extend(fields.clone());
+    }
+    let driver = admin_post(app, "/api/admin/drivers", body).await;
+    driver["id"].as_str().unwrap().parse().unwrap()
+}
+
+/// Veículo mínimo, sem departamento
+#[allow(dead_code)]
+pub async fn create_vehicle(app: &TestApp) -> Uuid {
+    insert_vehicle(app, None).await
+}
+
+/// Veículo mínimo alocado ao departamento (unidade) informado
+#[allow(dead_code)]
+pub async fn create_vehicle_for_department(app: &TestApp, department_id: Uuid) -> Uuid {
+    insert_vehicle(app, Some(department_id)).await
+}
+
+#[allow(dead_code)]
+async fn insert_vehicle(app: &TestApp, department_id: Option<Uuid>) -> Uuid {
+    let make = admin_post(
+        app,
+        "/api/admin/fleet/makes",
+        json!({ "name": format!("Marca {}", random_suffix()) }),
+    )
+    .await;
+    let model = admin_post(
+        app,
+        "/api/admin/fleet/models",
+        json!({ "make_id": make["id"], "name": format!("Modelo {}", random_suffix()) }),
+    )
+    .await;
+    let color = admin_post(
+        app,
+        "/api/admin/fleet/colors",
+        json!({ "name": format!("Cor {}", random_suffix()) }),
+    )
+    .await;
+    let fuel_type = admin_post(
+        app,
+        "/api/admin/fleet/fuel-types",
+        json!({ "name": format!("Combustível {}", random_suffix()) }),
+    )
+    .await;
+
+    let uid = Uuid::new_v4().simple().to_string().to_uppercase();
+    sqlx::query_scalar(
+        "INSERT INTO vehicles (license_plate, chassis_number, renavam, model_id, color_id,
+                               fuel_type_id, manufacture_year, model_year, department_id)
+         VALUES ($1, $2, $3, $4, $5, $6, 2024, 2024, $7)
+         RETURNING id",
+    )
+    .bind(&uid[..7])
+    .bind(&uid[7..24])
+    .bind(format!("{:011}", rand::random::<u64>() % 100000000000))
+    .bind(model["id"].as_str().unwrap().parse::<Uuid>().unwrap())
+    .bind(color["id"].as_str().unwrap().parse::<Uuid>().unwrap())
+    .bind(fuel_type["id"].as_str().unwrap().parse::<Uuid>().unwrap())
+    .bind(department_id)
+    .fetch_one(&app.db_auth)
+    .await
+    .expect("Falha ao criar veículo")
+}
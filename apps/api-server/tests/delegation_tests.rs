@@ -0,0 +1,441 @@
+mod common;
+
+use common::{admin_post, random_suffix, TestApp};
+use http::StatusCode;
+use serde_json::{json, Value};
+use std::time::Duration;
+use uuid::Uuid;
+
+const DELEGATIONS: &str = "/api/admin/delegations";
+
+// ============================
+// HELPERS
+// ============================
+
+async fn user_id(app: &TestApp, username: &str) -> Uuid {
+    sqlx::query_scalar("SELECT id FROM users WHERE username = $1")
+        .bind(username)
+        .fetch_one(&app.db_auth)
+        .await
+        .unwrap()
+}
+
+/// Usuário sem nenhum papel, e o token dele
+async fn create_delegate(app: &TestApp) -> (Uuid, String) {
+    let (username, _, _) = common::create_test_user(&app.db_auth, &app.field_keyring)
+        .await
+        .unwrap();
+    let id = user_id(app, &username).await;
+    (id, common::generate_test_token(id))
+}
+
+/// Requisição pendente em um almoxarifado qualquer, destinada à unidade
+/// informada
+async fn create_pending_requisition_for(
+    app: &TestApp,
+    requester_id: Uuid,
+    destination_unit_id: Uuid,
+) -> Uuid {
+    let uid = Uuid::new_v4().simple().to_string();
+    let city_id: Uuid = sqlx::query_scalar("SELECT id FROM cities LIMIT 1")
+        .fetch_one(&app.db_auth)
+        .await
+        .expect("Nenhuma cidade cadastrada");
+    let warehouse_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO warehouses (name, code, warehouse_type, city_id, is_active)
+         VALUES ($1, $2, 'SECTOR', $3, true)
+         RETURNING id",
+    )
+    .bind(format!("Almoxarifado {}", &uid[..8]))
+    .bind(format!("WD{}", &uid[..14]))
+    .bind(city_id)
+    .fetch_one(&app.db_auth)
+    .await
+    .unwrap();
+
+    sqlx::query_scalar(
+        "INSERT INTO requisitions (
+            requisition_number, warehouse_id, destination_unit_id, requester_id, status,
+            priority, request_date
+         )
+         VALUES ($1, $2, $3, $4, 'PENDING'::requisition_status_enum, 'NORMAL', CURRENT_DATE)
+         RETURNING id",
+    )
+    .bind(format!("REQ{}", &uid[..12]))
+    .bind(warehouse_id)
+    .bind(destination_unit_id)
+    .bind(requester_id)
+    .fetch_one(&app.db_auth)
+    .await
+    .unwrap()
+}
+
+async fn create_pending_requisition(app: &TestApp, requester_id: Uuid) -> Uuid {
+    let unit_id = create_unit(app).await;
+    create_pending_requisition_for(app, requester_id, unit_id).await
+}
+
+/// Unidade organizacional avulsa
+async fn create_unit(app: &TestApp) -> Uuid {
+    let organization = admin_post(
+        app,
+        "/api/admin/organizational/organizations",
+        json!({
+            "acronym": random_suffix(),
+            "name": format!("Org Delegação {}", random_suffix()),
+            "cnpj": format!("{:014}", rand::random::<u64>() % 100000000000000),
+            "ug_code": rand::random::<u32>() % 1000000,
+            "siorg_code": rand::random::<i32>() % 1000000,
+            "is_main_organization": false,
+            "is_active": true
+        }),
+    )
+    .await;
+    let category = admin_post(
+        app,
+        "/api/admin/organizational/unit-categories",
+        json!({
+            "name": format!("Categoria {}", random_suffix()),
+            "is_active": true,
+            "is_siorg_managed": false
+        }),
+    )
+    .await;
+    let unit_type = admin_post(
+        app,
+        "/api/admin/organizational/unit-types",
+        json!({
+            "code": random_suffix(),
+            "name": format!("Tipo {}", random_suffix()),
+            "is_active": true,
+            "is_siorg_managed": false
+        }),
+    )
+    .await;
+    let unit = admin_post(
+        app,
+        "/api/admin/organizational/units",
+        json!({
+            "organization_id": organization["id"],
+            "category_id": category["id"],
+            "unit_type_id": unit_type["id"],
+            "name": format!("Unidade {}", random_suffix()),
+            "activity_area": "Support",
+            "is_active": true
+        }),
+    )
+    .await;
+    unit["id"].as_str().unwrap().parse().unwrap()
+}
+
+/// Chefe de departamento: usuário sem papel global, com um papel na unidade
+/// que decide requisições e pode delegar essa competência
+async fn create_department_head(app: &TestApp, role: &str, unit_id: Uuid) -> (Uuid, String) {
+    let (id, token) = create_delegate(app).await;
+    admin_post(
+        app,
+        "/api/admin/policies/unit-roles",
+        json!({ "user_id": id, "role": role, "unit_id": unit_id, "include_subtree": false }),
+    )
+    .await;
+    (id, token)
+}
+
+async fn delegate_to(app: &TestApp, delegate_id: Uuid, scopes: Value) -> Value {
+    let response = app
+        .api
+        .post(DELEGATIONS)
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .json(&json!({
+            "delegate_id": delegate_id,
+            "scopes": scopes,
+            "valid_until": chrono::Utc::now() + chrono::Duration::days(7),
+            "reason": "Férias"
+        }))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::CREATED, "{}", response.text());
+    response.json()
+}
+
+async fn approve(app: &TestApp, token: &str, requisition_id: Uuid) -> axum_test::TestResponse {
+    app.api
+        .post(&format!("/api/admin/requisitions/{}/approve", requisition_id))
+        .add_header("Authorization", format!("Bearer {}", token))
+        .json(&json!({}))
+        .await
+}
+
+// ============================
+// TESTES
+// ============================
+
+#[tokio::test]
+async fn test_approval_without_delegation_is_denied() {
+    let app = common::spawn_app().await;
+    let admin_id = user_id(&app, "vinicius").await;
+    let (_, token) = create_delegate(&app).await;
+    let requisition_id = create_pending_requisition(&app, admin_id).await;
+
+    let response = approve(&app, &token, requisition_id).await;
+    assert_eq!(response.status_code(), StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn test_delegate_approves_on_behalf_of_delegator() {
+    let app = common::spawn_app().await;
+    let admin_id = user_id(&app, "vinicius").await;
+    let (delegate_id, token) = create_delegate(&app).await;
+    let requisition_id = create_pending_requisition(&app, admin_id).await;
+
+    let delegation = delegate_to(&app, delegate_id, json!(["REQUISITION"])).await;
+    assert_eq!(delegation["delegator_id"], json!(admin_id));
+    assert_eq!(delegation["is_active"], true);
+
+    let response = approve(&app, &token, requisition_id).await;
+    assert_eq!(response.status_code(), StatusCode::OK, "{}", response.text());
+    let body: Value = response.json();
+    assert_eq!(body["approved_by"], json!(delegate_id));
+    assert_eq!(body["approved_on_behalf_of"], json!(admin_id));
+
+    // "Aprovado por X em nome de Y" no log de auditoria (gravado em background)
+    let mut audited = None;
+    for _ in 0..20 {
+        audited = sqlx::query_as::<_, (Option<Uuid>, Value)>(
+            "SELECT user_id, details FROM audit_logs \
+             WHERE action = 'delegated_approval' AND resource = $1",
+        )
+        .bind(format!("/api/admin/requisitions/{}/approve", requisition_id))
+        .fetch_optional(&app.db_logs)
+        .await
+        .unwrap();
+        if audited.is_some() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    let (audited_user, details) = audited.expect("Aprovação delegada não auditada");
+    assert_eq!(audited_user, Some(delegate_id));
+    assert_eq!(details["on_behalf_of"], json!(admin_id));
+    assert_eq!(details["scope"], "REQUISITION");
+}
+
+#[tokio::test]
+async fn test_delegation_only_covers_its_scopes() {
+    let app = common::spawn_app().await;
+    let admin_id = user_id(&app, "vinicius").await;
+    let (delegate_id, token) = create_delegate(&app).await;
+    let requisition_id = create_pending_requisition(&app, admin_id).await;
+
+    delegate_to(&app, delegate_id, json!(["TRIP", "STOCK_TRANSFER"])).await;
+
+    let response = approve(&app, &token, requisition_id).await;
+    assert_eq!(response.status_code(), StatusCode::FORBIDDEN);
+
+    // A delegação não concede acesso a outras rotas
+    let response = app
+        .api
+        .get("/api/admin/requisitions")
+        .add_header("Authorization", format!("Bearer {}", token))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn test_revoked_delegation_stops_working() {
+    let app = common::spawn_app().await;
+    let admin_id = user_id(&app, "vinicius").await;
+    let (delegate_id, token) = create_delegate(&app).await;
+    let requisition_id = create_pending_requisition(&app, admin_id).await;
+
+    let delegation = delegate_to(&app, delegate_id, json!(["REQUISITION"])).await;
+
+    let response = app
+        .api
+        .delete(&format!("{}/{}", DELEGATIONS, delegation["id"].as_str().unwrap()))
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::NO_CONTENT);
+
+    let response = approve(&app, &token, requisition_id).await;
+    assert_eq!(response.status_code(), StatusCode::FORBIDDEN);
+
+    let response = app
+        .api
+        .delete(&format!("{}/{}", DELEGATIONS, delegation["id"].as_str().unwrap()))
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::CONFLICT);
+}
+
+#[tokio::test]
+async fn test_expired_delegation_stops_working() {
+    let app = common::spawn_app().await;
+    let admin_id = user_id(&app, "vinicius").await;
+    let (delegate_id, token) = create_delegate(&app).await;
+    let requisition_id = create_pending_requisition(&app, admin_id).await;
+
+    sqlx::query(
+        "INSERT INTO approval_delegations (delegator_id, delegate_id, scopes, valid_from, valid_until)
+         VALUES ($1, $2, '[\"REQUISITION\"]', NOW() - INTERVAL '10 days', NOW() - INTERVAL '1 day')",
+    )
+    .bind(admin_id)
+    .bind(delegate_id)
+    .execute(&app.db_auth)
+    .await
+    .unwrap();
+
+    let response = approve(&app, &token, requisition_id).await;
+    assert_eq!(response.status_code(), StatusCode::FORBIDDEN);
+
+    let response = app
+        .api
+        .get(&format!("{}?active_only=true", DELEGATIONS))
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    let delegations: Value = response.json();
+    assert!(delegations
+        .as_array()
+        .unwrap()
+        .iter()
+        .all(|d| d["delegate_id"] != json!(delegate_id)));
+}
+
+#[tokio::test]
+async fn test_create_delegation_validation() {
+    let app = common::spawn_app().await;
+    let admin_id = user_id(&app, "vinicius").await;
+    let now = chrono::Utc::now();
+
+    for body in [
+        // Para si mesmo
+        json!({
+            "delegate_id": admin_id,
+            "scopes": ["REQUISITION"],
+            "valid_until": now + chrono::Duration::days(1)
+        }),
+        // Já expirada
+        json!({
+            "delegate_id": Uuid::new_v4(),
+            "scopes": ["REQUISITION"],
+            "valid_from": now - chrono::Duration::days(2),
+            "valid_until": now - chrono::Duration::days(1)
+        }),
+        // Longa demais
+        json!({
+            "delegate_id": Uuid::new_v4(),
+            "scopes": ["TRIP"],
+            "valid_until": now + chrono::Duration::days(365)
+        }),
+    ] {
+        let response = app
+            .api
+            .post(DELEGATIONS)
+            .add_header("Authorization", format!("Bearer {}", app.admin_token))
+            .json(&body)
+            .await;
+        assert_eq!(response.status_code(), StatusCode::BAD_REQUEST, "{}", body);
+    }
+
+    // Sem escopos
+    let response = app
+        .api
+        .post(DELEGATIONS)
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .json(&json!({
+            "delegate_id": Uuid::new_v4(),
+            "scopes": [],
+            "valid_until": now + chrono::Duration::days(1)
+        }))
+        .await;
+    assert!(response.status_code().is_client_error());
+
+    let response = app
+        .api
+        .post(DELEGATIONS)
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .json(&json!({
+            "delegate_id": Uuid::new_v4(),
+            "scopes": ["REQUISITION"],
+            "valid_until": now + chrono::Duration::days(1)
+        }))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_only_delegator_or_delegate_can_revoke() {
+    let app = common::spawn_app().await;
+    let (delegate_id, _) = create_delegate(&app).await;
+    let delegation = delegate_to(&app, delegate_id, json!(["TRIP"])).await;
+
+    // bob não participa da delegação
+    let response = app
+        .api
+        .delete(&format!("{}/{}", DELEGATIONS, delegation["id"].as_str().unwrap()))
+        .add_header("Authorization", format!("Bearer {}", app.user_token))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_department_heads_covering_each_other() {
+    let app = common::spawn_app().await;
+    let admin_id = user_id(&app, "vinicius").await;
+    let (unit_a, unit_b) = (create_unit(&app).await, create_unit(&app).await);
+
+    let role = format!("role:chefe_{}", random_suffix());
+    for (obj, act) in [("/api/admin/requisitions/*", "POST"), (DELEGATIONS, "POST")] {
+        admin_post(
+            &app,
+            "/api/admin/policies",
+            json!({ "sub": role, "obj": obj, "act": act }),
+        )
+        .await;
+    }
+    let (head_a, token_a) = create_department_head(&app, &role, unit_a).await;
+    let (head_b, token_b) = create_department_head(&app, &role, unit_b).await;
+
+    // Cada chefe delega ao outro a decisão sobre requisições
+    for (token, delegate_id) in [(&token_a, head_b), (&token_b, head_a)] {
+        let response = app
+            .api
+            .post(DELEGATIONS)
+            .add_header("Authorization", format!("Bearer {}", token))
+            .json(&json!({
+                "delegate_id": delegate_id,
+                "scopes": ["REQUISITION"],
+                "valid_until": chrono::Utc::now() + chrono::Duration::days(7),
+                "reason": "Cobertura mútua"
+            }))
+            .await;
+        assert_eq!(response.status_code(), StatusCode::CREATED, "{}", response.text());
+    }
+
+    // Na própria unidade o chefe decide em nome próprio
+    let own = create_pending_requisition_for(&app, admin_id, unit_a).await;
+    let response = approve(&app, &token_a, own).await;
+    assert_eq!(response.status_code(), StatusCode::OK, "{}", response.text());
+    let body: Value = response.json();
+    assert_eq!(body["approved_by"], json!(head_a));
+    assert_eq!(body["approved_on_behalf_of"], Value::Null);
+
+    // Fora dela, recorre à delegação do outro chefe
+    let covered = create_pending_requisition_for(&app, admin_id, unit_b).await;
+    let response = approve(&app, &token_a, covered).await;
+    assert_eq!(response.status_code(), StatusCode::OK, "{}", response.text());
+    let body: Value = response.json();
+    assert_eq!(body["approved_by"], json!(head_a));
+    assert_eq!(body["approved_on_behalf_of"], json!(head_b));
+
+    let covered = create_pending_requisition_for(&app, admin_id, unit_a).await;
+    let response = approve(&app, &token_b, covered).await;
+    assert_eq!(response.status_code(), StatusCode::OK, "{}", response.text());
+    let body: Value = response.json();
+    assert_eq!(body["approved_on_behalf_of"], json!(head_a));
+
+    // Unidade que nenhum dos dois alcança
+    let outside = create_pending_requisition_for(&app, admin_id, create_unit(&app).await).await;
+    let response = approve(&app, &token_a, outside).await;
+    assert_eq!(response.status_code(), StatusCode::FORBIDDEN);
+}
@@ -0,0 +1,192 @@
+mod common;
+
+use common::{create_driver, TestApp};
+use http::StatusCode;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+const HISTORY: &str = "/api/admin/history";
+
+// ============================
+// HELPERS
+// ============================
+
+fn random_name(prefix: &str) -> String {
+    format!("{}-{}", prefix, &Uuid::new_v4().simple().to_string()[..8])
+}
+
+async fn admin_id(app: &TestApp) -> Uuid {
+    sqlx::query_scalar("SELECT id FROM users WHERE username = 'vinicius'")
+        .fetch_one(&app.db_auth)
+        .await
+        .unwrap()
+}
+
+async fn history(app: &TestApp, entity: &str, id: &str) -> Value {
+    let response = app
+        .api
+        .get(&format!("{}/{}/{}", HISTORY, entity, id))
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .await;
+    assert_eq!(
+        response.status_code(),
+        StatusCode::OK,
+        "{}",
+        response.text()
+    );
+    response.json()
+}
+
+/// Entrada de auditoria gravada pelo middleware (escrita assíncrona)
+async fn audit_log_id(app: &TestApp, request_id: Uuid) -> Uuid {
+    for _ in 0..20 {
+        let id: Option<Uuid> =
+            sqlx::query_scalar("SELECT id FROM audit_logs WHERE request_id = $1 LIMIT 1")
+                .bind(request_id)
+                .fetch_optional(&app.db_logs)
+                .await
+                .unwrap();
+        if let Some(id) = id {
+            return id;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+    panic!("Audit log for request {} not written", request_id);
+}
+
+// ============================
+// TESTS
+// ============================
+
+#[tokio::test]
+async fn test_update_records_field_diff_with_actor_and_request() {
+    let app = common::spawn_app().await;
+    let id = create_driver(&app).await.to_string();
+
+    let new_name = random_name("Atualizado");
+    let response = app
+        .api
+        .put(&format!("/api/admin/drivers/{}", id))
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .json(&json!({ "full_name": new_name, "cnh_category": "D" }))
+        .await;
+    assert_eq!(
+        response.status_code(),
+        StatusCode::OK,
+        "{}",
+        response.text()
+    );
+
+    let body = history(&app, "driver", &id).await;
+    assert_eq!(body["total"], 2);
+
+    let changes = body["data"].as_array().unwrap();
+    let update = &changes[0];
+    assert_eq!(update["operation"], "UPDATE");
+    let fields = update["changed_fields"].as_array().unwrap();
+    assert!(fields.contains(&json!("full_name")));
+    assert!(fields.contains(&json!("cnh_category")));
+    assert!(!fields.contains(&json!("updated_at")));
+    assert_eq!(update["diff"]["full_name"]["new"], new_name);
+    assert_eq!(update["diff"]["cnh_category"]["old"], "B");
+    assert_eq!(update["performed_by"], admin_id(&app).await.to_string());
+    assert_eq!(update["performed_by_username"], "vinicius");
+    assert!(update["request_id"].is_string());
+
+    assert_eq!(changes[1]["operation"], "INSERT");
+}
+
+#[tokio::test]
+async fn test_audit_entry_links_to_its_changes() {
+    let app = common::spawn_app().await;
+    let id = create_driver(&app).await.to_string();
+
+    app.api
+        .put(&format!("/api/admin/drivers/{}", id))
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .json(&json!({ "phone": "(65) 98888-0000" }))
+        .await;
+
+    let body = history(&app, "driver", &id).await;
+    let request_id: Uuid = serde_json::from_value(body["data"][0]["request_id"].clone()).unwrap();
+    let log_id = audit_log_id(&app, request_id).await;
+
+    let response = app
+        .api
+        .get(&format!("/api/admin/audit-logs/{}/changes", log_id))
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    let changes: Vec<Value> = response.json();
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0]["entity_type"], "driver");
+    assert_eq!(changes[0]["entity_id"], id);
+    assert!(changes[0]["changed_fields"]
+        .as_array()
+        .unwrap()
+        .contains(&json!("phone")));
+}
+
+#[tokio::test]
+async fn test_policy_changes_are_recorded() {
+    let app = common::spawn_app().await;
+    // Fora do formato de username: tratado como papel, sem consulta a usuários
+    let subject = random_name("role:history");
+    let rule = json!({ "subject": subject, "object": "/api/history_test", "action": "GET" });
+
+    let response = app
+        .api
+        .post("/api/admin/policies")
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .json(&rule)
+        .await;
+    assert_eq!(response.status_code(), StatusCode::CREATED);
+
+    let response = app
+        .api
+        .delete("/api/admin/policies")
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .json(&rule)
+        .await;
+    assert_eq!(response.status_code(), StatusCode::NO_CONTENT);
+
+    let body = history(&app, "policy", &subject).await;
+    assert_eq!(body["total"], 2);
+    let changes = body["data"].as_array().unwrap();
+    assert_eq!(changes[0]["operation"], "DELETE");
+    assert_eq!(changes[0]["data_before"]["v1"], "/api/history_test");
+    assert_eq!(changes[1]["operation"], "INSERT");
+    assert_eq!(changes[1]["data_after"]["ptype"], "p");
+    assert_eq!(changes[1]["performed_by"], admin_id(&app).await.to_string());
+}
+
+#[tokio::test]
+async fn test_history_rejects_unknown_entity_and_invalid_id() {
+    let app = common::spawn_app().await;
+
+    let response = app
+        .api
+        .get(&format!("{}/invoice_line/{}", HISTORY, Uuid::new_v4()))
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::BAD_REQUEST);
+
+    let response = app
+        .api
+        .get(&format!("{}/warehouse/not-a-uuid", HISTORY))
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_history_requires_admin() {
+    let app = common::spawn_app().await;
+
+    let response = app
+        .api
+        .get(&format!("{}/warehouse/{}", HISTORY, Uuid::new_v4()))
+        .add_header("Authorization", format!("Bearer {}", app.user_token))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::FORBIDDEN);
+}
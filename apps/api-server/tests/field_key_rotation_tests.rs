@@ -0,0 +1,279 @@
+mod common;
+
+use std::time::Duration;
+
+use common::TestApp;
+use core_services::field_encryption::FieldKeyring;
+use http::StatusCode;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+const ROTATIONS: &str = "/api/admin/security/field-key-rotations";
+
+/// Id da chave "antiga" aceita pelo keyring dos testes
+const PREVIOUS_KEY_ID: u32 = 7;
+
+// ============================
+// HELPERS
+// ============================
+
+/// App cuja chave atual é a do ambiente (id 1), com uma chave anterior
+/// aleatória ainda aceita. A recriptografia só move dados para a chave do
+/// ambiente, sem afetar os demais testes que compartilham o banco.
+async fn spawn_rotating_app() -> (TestApp, FieldKeyring) {
+    let previous_key: [u8; 32] = rand::random();
+    let app = common::spawn_app_with(|config| {
+        config.field_encryption_key_id = 1;
+        config.field_encryption_previous_keys =
+            format!("{}:{}", PREVIOUS_KEY_ID, hex::encode(previous_key));
+    })
+    .await;
+    (app, FieldKeyring::new(PREVIOUS_KEY_ID, previous_key))
+}
+
+/// Inicia uma rotação (aguardando a que estiver em andamento) e espera o fim
+async fn run_rotation(app: &TestApp) -> Value {
+    for _ in 0..120 {
+        let response = app
+            .api
+            .post(ROTATIONS)
+            .add_header("Authorization", format!("Bearer {}", app.admin_token))
+            .await;
+        match response.status_code() {
+            StatusCode::ACCEPTED => {
+                let rotation: Value = response.json();
+                return wait_for_rotation(app, rotation["id"].as_str().unwrap()).await;
+            }
+            StatusCode::CONFLICT => tokio::time::sleep(Duration::from_millis(500)).await,
+            other => panic!("Falha ao iniciar a rotação: {} {}", other, response.text()),
+        }
+    }
+    panic!("Rotação em andamento não terminou");
+}
+
+async fn wait_for_rotation(app: &TestApp, id: &str) -> Value {
+    for _ in 0..120 {
+        let rotation: Value = app
+            .api
+            .get(&format!("{}/{}", ROTATIONS, id))
+            .add_header("Authorization", format!("Bearer {}", app.admin_token))
+            .await
+            .json();
+        if rotation["status"] != "RUNNING" {
+            return rotation;
+        }
+        tokio::time::sleep(Duration::from_millis(250)).await;
+    }
+    panic!("Rotação {} não terminou", id);
+}
+
+async fn user_secrets(app: &TestApp, username: &str) -> (String, String, Option<String>) {
+    sqlx::query_as("SELECT email, email_index, mfa_secret FROM users WHERE username = $1")
+        .bind(username)
+        .fetch_one(&app.db_auth)
+        .await
+        .unwrap()
+}
+
+async fn signing_private_key(app: &TestApp, kid: &str) -> String {
+    sqlx::query_scalar("SELECT private_key FROM jwt_signing_keys WHERE kid = $1")
+        .bind(kid)
+        .fetch_one(&app.db_auth)
+        .await
+        .unwrap()
+}
+
+// ============================
+// TESTS
+// ============================
+
+#[tokio::test]
+async fn test_rotation_moves_previous_key_and_legacy_rows_to_current_key() {
+    let (app, previous) = spawn_rotating_app().await;
+
+    // Usuário cifrado com a chave anterior, com segredo MFA
+    let (old_user, old_email, password) = common::create_test_user(&app.db_auth, &previous)
+        .await
+        .unwrap();
+    sqlx::query("UPDATE users SET mfa_secret = $2 WHERE username = $1")
+        .bind(&old_user)
+        .bind(previous.encrypt("JBSWY3DPEHPK3PXP").unwrap())
+        .execute(&app.db_auth)
+        .await
+        .unwrap();
+
+    // Usuário gravado antes do envelope com id da chave (cifrado com a chave
+    // do ambiente, sem prefixo)
+    let (legacy_user, legacy_email, _) = common::create_test_user(&app.db_auth, &previous)
+        .await
+        .unwrap();
+    sqlx::query("UPDATE users SET email = $2 WHERE username = $1")
+        .bind(&legacy_user)
+        .bind(app.encrypt_field(&legacy_email).split_once(':').unwrap().1)
+        .execute(&app.db_auth)
+        .await
+        .unwrap();
+
+    // Antes da rotação o login por email já funciona com o índice antigo
+    let response = app
+        .api
+        .post("/login")
+        .json(&json!({ "username": old_email, "password": password }))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+
+    let rotation = run_rotation(&app).await;
+    assert_eq!(rotation["status"], "COMPLETED");
+    assert_eq!(rotation["target_key_id"], 1);
+    assert_eq!(rotation["stage"], "JWT_SIGNING_KEYS");
+    assert!(rotation["processed_rows"].as_i64().unwrap() >= 2);
+
+    let (email, email_index, mfa_secret) = user_secrets(&app, &old_user).await;
+    assert!(app.field_keyring.is_current(&email));
+    assert_eq!(app.field_keyring.decrypt(&email).unwrap(), old_email);
+    assert_eq!(email_index, app.field_keyring.blind_index(&old_email));
+    let mfa_secret = mfa_secret.unwrap();
+    assert!(app.field_keyring.is_current(&mfa_secret));
+    assert_eq!(
+        app.field_keyring.decrypt(&mfa_secret).unwrap(),
+        "JBSWY3DPEHPK3PXP"
+    );
+
+    let (email, email_index, _) = user_secrets(&app, &legacy_user).await;
+    assert!(app.field_keyring.is_current(&email));
+    assert_eq!(app.field_keyring.decrypt(&email).unwrap(), legacy_email);
+    assert_eq!(email_index, app.field_keyring.blind_index(&legacy_email));
+}
+
+#[tokio::test]
+async fn test_rotation_reseals_jwt_signing_keys_for_restart_without_previous_key() {
+    let (app, previous) = spawn_rotating_app().await;
+
+    // Chave de assinatura aposentada, selada com a chave anterior
+    let kid = format!("fkr-{}", Uuid::new_v4().simple());
+    let private_key = "MC4CAQAwBQYDK2VwBCIEIPrivateKeyDerDeTeste";
+    sqlx::query(
+        "INSERT INTO jwt_signing_keys
+            (kid, algorithm, status, public_jwk, private_key, retired_at, verify_until)
+         VALUES ($1, 'EdDSA', 'RETIRED', '{}', $2, NOW(), NOW())",
+    )
+    .bind(&kid)
+    .bind(previous.encrypt(private_key).unwrap())
+    .execute(&app.db_auth)
+    .await
+    .unwrap();
+
+    // Reinício com a chave anterior fora do keyring
+    let restarted = common::spawn_app_with(|config| {
+        config.field_encryption_key_id = 1;
+        config.field_encryption_previous_keys = String::new();
+    })
+    .await;
+    assert_eq!(restarted.field_keyring.key_ids(), vec![1]);
+
+    let sealed = signing_private_key(&app, &kid).await;
+    assert!(restarted.field_keyring.decrypt(&sealed).is_err());
+
+    let rotation = run_rotation(&app).await;
+    assert_eq!(rotation["status"], "COMPLETED");
+
+    let resealed = signing_private_key(&app, &kid).await;
+    assert!(restarted.field_keyring.is_current(&resealed));
+    assert_eq!(
+        restarted.field_keyring.decrypt(&resealed).unwrap(),
+        private_key
+    );
+}
+
+#[tokio::test]
+async fn test_failed_rotation_resumes_from_saved_stage() {
+    let (app, _) = spawn_rotating_app().await;
+
+    // Falhou depois de processar todas as etapas
+    let id: Uuid = sqlx::query_scalar(
+        "INSERT INTO field_key_rotations
+            (target_key_id, status, stage, last_processed_id, total_rows, processed_rows, error, finished_at)
+         VALUES (1, 'FAILED', 'JWT_SIGNING_KEYS', 'ffffffff-ffff-ffff-ffff-ffffffffffff', 10, 10, 'falha simulada', NOW())
+         RETURNING id",
+    )
+    .fetch_one(&app.db_auth)
+    .await
+    .unwrap();
+
+    let mut status = StatusCode::CONFLICT;
+    for _ in 0..120 {
+        status = app
+            .api
+            .post(&format!("{}/{}/resume", ROTATIONS, id))
+            .add_header("Authorization", format!("Bearer {}", app.admin_token))
+            .await
+            .status_code();
+        if status != StatusCode::CONFLICT {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    }
+    assert_eq!(status, StatusCode::ACCEPTED);
+
+    let rotation = wait_for_rotation(&app, &id.to_string()).await;
+    assert_eq!(rotation["status"], "COMPLETED");
+    assert_eq!(rotation["processed_rows"], 10);
+    assert_eq!(rotation["error"], Value::Null);
+
+    // Concluída: não pode ser retomada de novo
+    let response = app
+        .api
+        .post(&format!("{}/{}/resume", ROTATIONS, id))
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_resume_rejects_rotation_to_another_key() {
+    let (app, _) = spawn_rotating_app().await;
+    let id: Uuid = sqlx::query_scalar(
+        "INSERT INTO field_key_rotations (target_key_id, status, finished_at)
+         VALUES (99, 'FAILED', NOW()) RETURNING id",
+    )
+    .fetch_one(&app.db_auth)
+    .await
+    .unwrap();
+
+    let response = app
+        .api
+        .post(&format!("{}/{}/resume", ROTATIONS, id))
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .await;
+
+    assert_eq!(response.status_code(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_list_reports_keyring() {
+    let (app, _) = spawn_rotating_app().await;
+
+    let response = app
+        .api
+        .get(ROTATIONS)
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .await;
+
+    assert_eq!(response.status_code(), StatusCode::OK);
+    let body: Value = response.json();
+    assert_eq!(body["current_key_id"], 1);
+    assert_eq!(body["key_ids"], json!([1, PREVIOUS_KEY_ID]));
+}
+
+#[tokio::test]
+async fn test_field_key_rotation_requires_admin() {
+    let app = common::spawn_app().await;
+
+    let response = app
+        .api
+        .post(ROTATIONS)
+        .add_header("Authorization", format!("Bearer {}", app.user_token))
+        .await;
+
+    assert_eq!(response.status_code(), StatusCode::FORBIDDEN);
+}
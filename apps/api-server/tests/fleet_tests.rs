@@ -298,6 +298,52 @@ async fn test_crud_vehicle_make_and_model() {
     assert!(list["total"].as_i64().unwrap() >= 1);
 }
 
+#[tokio::test]
+async fn test_vehicle_model_preventive_interval() {
+    let app = common::spawn_app().await;
+    let make = create_make(&app, &random_name("Fiat")).await;
+
+    let response = app
+        .api
+        .post("/api/admin/fleet/models")
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .json(&json!({
+            "make_id": make["id"],
+            "name": random_name("Strada"),
+            "preventive_interval_km": 10000,
+        }))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::CREATED);
+    let model: Value = response.json();
+    assert_eq!(model["preventive_interval_km"], 10000);
+    let model_id = model["id"].as_str().unwrap();
+
+    let response = app
+        .api
+        .put(&format!("/api/admin/fleet/models/{}", model_id))
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .json(&json!({ "preventive_interval_km": 15000 }))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+
+    let response = app
+        .api
+        .get(&format!("/api/admin/fleet/models/{}", model_id))
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    let model: Value = response.json();
+    assert_eq!(model["preventive_interval_km"], 15000);
+
+    let response = app
+        .api
+        .put(&format!("/api/admin/fleet/models/{}", model_id))
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .json(&json!({ "preventive_interval_km": 0 }))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::BAD_REQUEST);
+}
+
 // ============================
 // VEHICLE CRUD TESTS
 // ============================
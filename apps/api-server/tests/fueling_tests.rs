@@ -537,3 +537,323 @@ async fn test_get_fueling_not_found() {
         .await;
     assert_eq!(resp.status_code(), StatusCode::NOT_FOUND);
 }
+
+// ============================
+// REVIEW QUEUE TESTS
+// ============================
+
+async fn create_fuel_type(app: &TestApp) -> String {
+    let resp = app
+        .api
+        .post("/api/admin/fleet/fuel-types")
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .json(&json!({ "name": random_name("Etanol") }))
+        .await;
+    assert_eq!(resp.status_code(), StatusCode::CREATED);
+    let fuel_type: Value = resp.json();
+    fuel_type["id"].as_str().unwrap().to_string()
+}
+
+async fn update_fueling(app: &TestApp, id: &str, body: Value) -> Value {
+    let resp = app
+        .api
+        .put(&format!("/api/admin/fuelings/{}", id))
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .json(&body)
+        .await;
+    assert_eq!(resp.status_code(), StatusCode::OK, "{}", resp.text());
+    resp.json()
+}
+
+async fn review_queue_ids(app: &TestApp, vehicle_id: &str) -> Vec<Value> {
+    let resp = app
+        .api
+        .get(&format!(
+            "/api/admin/fuelings/review-queue?vehicle_id={}",
+            vehicle_id
+        ))
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .await;
+    assert_eq!(resp.status_code(), StatusCode::OK);
+    let list: Value = resp.json();
+    list["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|f| f["id"].clone())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_update_fueling_rechecks_anomalies() {
+    let app = common::spawn_app().await;
+    let (vid, did, sid, ftid) = setup_prerequisites(&app).await;
+    let created = create_fueling(&app, &vid, &did, &sid, &ftid).await;
+    let id = created["id"].as_str().unwrap();
+    assert_eq!(created["review_status"], "VALIDADO");
+
+    // Combustível trocado para um diferente do veículo: volta para a fila
+    let other_fuel_type = create_fuel_type(&app).await;
+    let updated = update_fueling(&app, id, json!({ "fuel_type_id": other_fuel_type })).await;
+    assert_eq!(updated["review_status"], "QUARENTENA");
+    assert_eq!(updated["anomalies"][0]["kind"], "FUEL_TYPE_MISMATCH");
+    assert!(review_queue_ids(&app, &vid).await.contains(&created["id"]));
+
+    // Corrigido, sai da fila
+    let updated = update_fueling(&app, id, json!({ "fuel_type_id": ftid })).await;
+    assert_eq!(updated["review_status"], "VALIDADO");
+    assert!(updated["anomalies"].is_null());
+    assert!(review_queue_ids(&app, &vid).await.is_empty());
+}
+
+#[tokio::test]
+async fn test_rejected_fueling_is_left_out_of_reports_until_reviewed_again() {
+    let app = common::spawn_app().await;
+    let (vid, did, sid, ftid) = setup_prerequisites(&app).await;
+    let created = create_fueling(&app, &vid, &did, &sid, &ftid).await;
+    let id = created["id"].as_str().unwrap();
+    let other_fuel_type = create_fuel_type(&app).await;
+    update_fueling(&app, id, json!({ "fuel_type_id": other_fuel_type })).await;
+
+    let resp = app
+        .api
+        .put(&format!("/api/admin/fuelings/{}/review", id))
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .json(&json!({ "approve": false, "notes": "Cupom não confere" }))
+        .await;
+    assert_eq!(resp.status_code(), StatusCode::OK, "{}", resp.text());
+    let rejected: Value = resp.json();
+    assert_eq!(rejected["review_status"], "REJEITADO");
+
+    let resp = app
+        .api
+        .get(&format!(
+            "/api/admin/reports/fuel-consumption?vehicle_id={}",
+            vid
+        ))
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .await;
+    assert_eq!(resp.status_code(), StatusCode::OK, "{}", resp.text());
+    let report: Value = resp.json();
+    assert_eq!(report[0]["total_fuelings"], 0);
+    assert!(report[0]["total_cost"].is_null());
+
+    // Mesmo com dados limpos, um registro rejeitado editado volta para revisão
+    let updated = update_fueling(&app, id, json!({ "fuel_type_id": ftid })).await;
+    assert_eq!(updated["review_status"], "QUARENTENA");
+    assert!(review_queue_ids(&app, &vid).await.contains(&created["id"]));
+}
+
+#[tokio::test]
+async fn test_update_fueling_notes_keeps_review() {
+    let app = common::spawn_app().await;
+    let (vid, did, sid, ftid) = setup_prerequisites(&app).await;
+    let created = create_fueling(&app, &vid, &did, &sid, &ftid).await;
+    let id = created["id"].as_str().unwrap();
+    let other_fuel_type = create_fuel_type(&app).await;
+    update_fueling(&app, id, json!({ "fuel_type_id": other_fuel_type })).await;
+
+    let updated = update_fueling(&app, id, json!({ "notes": "Posto na rodovia" })).await;
+    assert_eq!(updated["review_status"], "QUARENTENA");
+    assert_eq!(updated["anomalies"][0]["kind"], "FUEL_TYPE_MISMATCH");
+}
+
+#[tokio::test]
+async fn test_backdated_fueling_is_checked_against_the_next_one() {
+    let app = common::spawn_app().await;
+    let (vid, did, sid, ftid) = setup_prerequisites(&app).await;
+    create_fueling(&app, &vid, &did, &sid, &ftid).await;
+
+    // Lançado depois, mas duas horas antes do abastecimento já registrado
+    let resp = app
+        .api
+        .post("/api/admin/fuelings")
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .json(&json!({
+            "vehicle_id": vid,
+            "driver_id": did,
+            "fuel_type_id": ftid,
+            "fueling_date": "2026-02-11T08:30:00Z",
+            "odometer_km": 45100,
+            "quantity_liters": 40.0,
+            "unit_price": 6.299,
+            "total_cost": 251.96,
+        }))
+        .await;
+    assert_eq!(resp.status_code(), StatusCode::CREATED, "{}", resp.text());
+    let backdated: Value = resp.json();
+    assert_eq!(backdated["review_status"], "QUARENTENA");
+    assert_eq!(backdated["anomalies"][0]["kind"], "TOO_CLOSE_TO_NEXT");
+}
+
+// ============================
+// FUEL CARD IMPORT TESTS
+// ============================
+
+async fn create_fueling_with(
+    app: &TestApp,
+    (vid, did, ftid): (&str, &str, &str),
+    fueling_date: &str,
+    quantity_liters: f64,
+    total_cost: f64,
+) -> Value {
+    let resp = app
+        .api
+        .post("/api/admin/fuelings")
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .json(&json!({
+            "vehicle_id": vid,
+            "driver_id": did,
+            "fuel_type_id": ftid,
+            "fueling_date": fueling_date,
+            "odometer_km": 45200,
+            "quantity_liters": quantity_liters,
+            "unit_price": 6.25,
+            "total_cost": total_cost,
+        }))
+        .await;
+    assert_eq!(resp.status_code(), StatusCode::CREATED, "{}", resp.text());
+    resp.json()
+}
+
+async fn import_card_file(app: &TestApp, csv: &str) -> Value {
+    let resp = app
+        .api
+        .post("/api/admin/fuelings/card-imports?operator=Ticket%20Log")
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .text(csv.to_string())
+        .await;
+    assert_eq!(resp.status_code(), StatusCode::CREATED, "{}", resp.text());
+    resp.json()
+}
+
+/// Placa e CPF como cadastrados, para montar o arquivo da operadora
+async fn plate_and_cpf(app: &TestApp, vid: &str, did: &str) -> (String, String) {
+    let plate: String = sqlx::query_scalar("SELECT license_plate FROM vehicles WHERE id = $1")
+        .bind(vid.parse::<Uuid>().unwrap())
+        .fetch_one(&app.db_auth)
+        .await
+        .unwrap();
+    let cpf: String = sqlx::query_scalar("SELECT cpf FROM drivers WHERE id = $1")
+        .bind(did.parse::<Uuid>().unwrap())
+        .fetch_one(&app.db_auth)
+        .await
+        .unwrap();
+    (plate, cpf)
+}
+
+#[tokio::test]
+async fn test_fuel_card_import_reconciles_transactions() {
+    let app = common::spawn_app().await;
+    let (vid, did, _sid, ftid) = setup_prerequisites(&app).await;
+    let (plate, cpf) = plate_and_cpf(&app, &vid, &did).await;
+    let ids = (vid.as_str(), did.as_str(), ftid.as_str());
+
+    let matched = create_fueling_with(&app, ids, "2026-02-11T10:30:00Z", 55.5, 349.59).await;
+    let divergent = create_fueling_with(&app, ids, "2026-02-13T10:30:00Z", 40.0, 250.0).await;
+
+    let tx = random_name("TX");
+    let csv = format!(
+        "data;placa;cpf;litros;valor;id_transacao;posto\n\
+         11/02/2026 07:30;{plate};{cpf};55,500;349,59;{tx}-1;Posto Central\n\
+         13/02/2026 07:30;{plate};{cpf};40,000;262,00;{tx}-2;Posto Central\n\
+         20/02/2026 09:00;{plate};{cpf};30,000;187,50;{tx}-3;Posto da Rodovia\n\
+         21/02/2026 09:00;ZZZ9Z99;{cpf};10,000;62,50;{tx}-4;Posto Central\n\
+         32/02/2026 09:00;{plate};{cpf};10,000;62,50;{tx}-5;Posto Central\n"
+    );
+
+    let report = import_card_file(&app, &csv).await;
+    assert_eq!(report["matched"], 1);
+    assert_eq!(report["divergent"], 1);
+    assert_eq!(report["pending"], 1);
+    assert_eq!(report["unidentified"], 1);
+    assert_eq!(report["job"]["status"], "PARTIAL");
+    assert_eq!(report["job"]["total_records"], 5);
+    assert_eq!(report["job"]["success_records"], 3);
+    assert_eq!(report["job"]["failed_records"], 2);
+
+    let job_id = report["job"]["id"].as_str().unwrap();
+    let transactions = |status: &str| {
+        let path = format!(
+            "/api/admin/fuelings/card-imports/{}/transactions?match_status={}",
+            job_id, status
+        );
+        let auth = format!("Bearer {}", app.admin_token);
+        let api = &app.api;
+        async move {
+            let resp = api.get(&path).add_header("Authorization", auth).await;
+            assert_eq!(resp.status_code(), StatusCode::OK, "{}", resp.text());
+            resp.json::<Vec<Value>>()
+        }
+    };
+
+    let conciliated = transactions("CONCILIADO").await;
+    assert_eq!(conciliated[0]["fueling_id"], matched["id"]);
+
+    let diverging = transactions("DIVERGENTE").await;
+    assert_eq!(diverging[0]["fueling_id"], divergent["id"]);
+    assert!(diverging[0]["mismatches"][0]
+        .as_str()
+        .unwrap()
+        .starts_with("Valor"));
+
+    // Sem abastecimento correspondente: gera um em quarentena
+    let pending = transactions("PENDENTE").await;
+    let generated = pending[0]["fueling_id"].clone();
+    assert!(generated.is_string());
+    assert!(review_queue_ids(&app, &vid).await.contains(&generated));
+
+    // Relatório continua disponível pelo job
+    let resp = app
+        .api
+        .get(&format!("/api/admin/fuelings/card-imports/{}", job_id))
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .await;
+    assert_eq!(resp.status_code(), StatusCode::OK);
+    let stored: Value = resp.json();
+    assert_eq!(stored["mismatches"].as_array().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_fuel_card_reimport_does_not_duplicate_transactions() {
+    let app = common::spawn_app().await;
+    let (vid, did, _sid, _ftid) = setup_prerequisites(&app).await;
+    let (plate, cpf) = plate_and_cpf(&app, &vid, &did).await;
+
+    let tx = random_name("TX");
+    let csv = format!(
+        "placa,cpf,data,litros,valor,nsu\n\
+         {plate},{cpf},2026-03-02 08:00,35.0,218.75,{tx}\n"
+    );
+
+    let first = import_card_file(&app, &csv).await;
+    assert_eq!(first["pending"], 1);
+    assert_eq!(first["job"]["status"], "COMPLETED");
+
+    let second = import_card_file(&app, &csv).await;
+    assert_eq!(second["pending"], 0);
+    assert_eq!(second["job"]["status"], "FAILED");
+    assert_eq!(second["job"]["failed_records"], 1);
+
+    let generated: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM fuel_card_transactions WHERE operator = 'TICKET LOG' AND external_id = $1",
+    )
+    .bind(&tx)
+    .fetch_one(&app.db_auth)
+    .await
+    .unwrap();
+    assert_eq!(generated, 1);
+}
+
+#[tokio::test]
+async fn test_fuel_card_import_requires_layout_columns() {
+    let app = common::spawn_app().await;
+
+    let resp = app
+        .api
+        .post("/api/admin/fuelings/card-imports?operator=Ticket%20Log")
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .text("placa;litros;valor\nABC1D23;10,0;62,50\n".to_string())
+        .await;
+    assert_eq!(resp.status_code(), StatusCode::BAD_REQUEST);
+}
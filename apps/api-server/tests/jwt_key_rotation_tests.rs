@@ -0,0 +1,170 @@
+mod common;
+
+use common::TestApp;
+use http::StatusCode;
+use jsonwebtoken::decode_header;
+use serde_json::{json, Value};
+
+const JWT_KEYS: &str = "/api/admin/security/jwt-keys";
+
+// ============================
+// HELPERS
+// ============================
+
+async fn login(app: &TestApp, username: &str, password: &str) -> String {
+    let response = app
+        .api
+        .post("/login")
+        .json(&json!({ "username": username, "password": password }))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK, "{}", response.text());
+    let body: Value = response.json();
+    body["access_token"].as_str().unwrap().to_string()
+}
+
+fn kid_of(token: &str) -> String {
+    decode_header(token).unwrap().kid.expect("token sem kid")
+}
+
+async fn profile_status(app: &TestApp, token: &str) -> StatusCode {
+    app.api
+        .get("/users/profile")
+        .add_header("Authorization", format!("Bearer {}", token))
+        .await
+        .status_code()
+}
+
+async fn rotate(app: &TestApp) -> Value {
+    let response = app
+        .api
+        .post(&format!("{}/rotate", JWT_KEYS))
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::CREATED, "{}", response.text());
+    response.json()
+}
+
+async fn jwks_kids(app: &TestApp) -> Vec<String> {
+    let jwks: Value = app.api.get("/.well-known/jwks.json").await.json();
+    jwks["keys"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|k| k["kid"].as_str().unwrap().to_string())
+        .collect()
+}
+
+// ============================
+// TESTS
+// ============================
+
+#[tokio::test]
+async fn test_jwks_publishes_key_of_issued_tokens() {
+    let app = common::spawn_app().await;
+    let (username, _, password) = common::create_test_user(&app.db_auth, &app.field_keyring)
+        .await
+        .unwrap();
+    let token = login(&app, &username, &password).await;
+
+    let response = app.api.get("/.well-known/jwks.json").await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    assert!(response.headers().get("cache-control").is_some());
+
+    let jwks: Value = response.json();
+    let key = jwks["keys"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|k| k["kid"] == kid_of(&token))
+        .expect("kid do token ausente do JWKS");
+    assert_eq!(key["kty"], "OKP");
+    assert_eq!(key["crv"], "Ed25519");
+    assert_eq!(key["alg"], "EdDSA");
+    assert_eq!(key["use"], "sig");
+}
+
+/// Único teste que rotaciona: o keyring do banco é compartilhado, mas só as
+/// instâncias que o releem (a deste teste) são afetadas.
+#[tokio::test]
+async fn test_rotation_keeps_previous_key_valid_during_grace_period() {
+    let app = common::spawn_app().await;
+    sqlx::query("DELETE FROM jwt_signing_keys")
+        .execute(&app.db_auth)
+        .await
+        .unwrap();
+
+    let (username, _, password) = common::create_test_user(&app.db_auth, &app.field_keyring)
+        .await
+        .unwrap();
+    let configured_token = login(&app, &username, &password).await;
+    let configured_kid = kid_of(&configured_token);
+
+    // Primeira rotação: a chave configurada fica aposentada em carência
+    let first = rotate(&app).await;
+    assert_eq!(first["status"], "ACTIVE");
+    assert_eq!(first["algorithm"], "EdDSA");
+    let first_kid = first["kid"].as_str().unwrap().to_string();
+    assert_ne!(first_kid, configured_kid);
+
+    let rotated_token = login(&app, &username, &password).await;
+    assert_eq!(kid_of(&rotated_token), first_kid);
+    assert_eq!(profile_status(&app, &configured_token).await, StatusCode::OK);
+    assert_eq!(profile_status(&app, &rotated_token).await, StatusCode::OK);
+
+    let kids = jwks_kids(&app).await;
+    assert_eq!(kids.len(), 2);
+    assert!(kids.contains(&first_kid) && kids.contains(&configured_kid));
+
+    let listing: Value = app
+        .api
+        .get(JWT_KEYS)
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .await
+        .json();
+    assert_eq!(listing["signing_kid"], first_kid);
+    let keys = listing["data"].as_array().unwrap();
+    assert_eq!(keys.len(), 2);
+    let configured = keys.iter().find(|k| k["kid"] == configured_kid).unwrap();
+    assert_eq!(configured["status"], "RETIRED");
+    assert!(configured["verify_until"].is_string());
+
+    // Fim da carência da chave configurada, aplicado na próxima rotação
+    sqlx::query(
+        "UPDATE jwt_signing_keys SET verify_until = NOW() - INTERVAL '1 second'
+         WHERE kid = $1",
+    )
+    .bind(&configured_kid)
+    .execute(&app.db_auth)
+    .await
+    .unwrap();
+    let second = rotate(&app).await;
+    let second_kid = second["kid"].as_str().unwrap().to_string();
+
+    assert_eq!(
+        profile_status(&app, &configured_token).await,
+        StatusCode::UNAUTHORIZED
+    );
+    assert_eq!(profile_status(&app, &rotated_token).await, StatusCode::OK);
+
+    let kids = jwks_kids(&app).await;
+    assert_eq!(kids.len(), 2);
+    assert!(kids.contains(&first_kid) && kids.contains(&second_kid));
+
+    sqlx::query("DELETE FROM jwt_signing_keys")
+        .execute(&app.db_auth)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_jwt_key_rotation_requires_admin() {
+    let app = common::spawn_app().await;
+
+    let response = app
+        .api
+        .post(&format!("{}/rotate", JWT_KEYS))
+        .add_header("Authorization", format!("Bearer {}", app.user_token))
+        .await;
+
+    assert_eq!(response.status_code(), StatusCode::FORBIDDEN);
+}
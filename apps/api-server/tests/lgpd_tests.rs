@@ -0,0 +1,319 @@
+mod common;
+
+use chrono::{Duration, NaiveDate};
+use common::{create_driver_with, generate_cpf, TestApp};
+use http::StatusCode;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+const LGPD: &str = "/api/admin/lgpd";
+
+// ============================
+// HELPERS
+// ============================
+
+fn random_name(prefix: &str) -> String {
+    format!("{}-{}", prefix, &Uuid::new_v4().simple().to_string()[..8])
+}
+
+/// Condutor terceirizado com contato: o titular dos dados pessoais
+async fn create_data_subject_driver(app: &TestApp, cpf: &str) -> String {
+    create_driver_with(
+        app,
+        json!({
+            "driver_type": "OUTSOURCED",
+            "full_name": random_name("Titular"),
+            "cpf": cpf,
+            "phone": "(65) 99999-0000",
+            "email": "titular@example.com",
+        }),
+    )
+    .await
+    .to_string()
+}
+
+async fn create_user(app: &TestApp) -> (Uuid, String) {
+    let username = random_name("titular");
+    let response = app
+        .api
+        .post("/api/admin/users")
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .json(&json!({
+            "username": username,
+            "email": format!("{}@example.com", username),
+            "password": "Tr0ng$ecuR3!Data#42",
+            "role": "user",
+        }))
+        .await;
+    assert_eq!(
+        response.status_code(),
+        StatusCode::OK,
+        "{}",
+        response.text()
+    );
+    let body: Value = response.json();
+    let id = serde_json::from_value(body["id"].clone()).unwrap();
+    (id, username)
+}
+
+async fn anonymize(app: &TestApp, body: Value) -> axum_test::TestResponse {
+    app.api
+        .post(&format!("{}/anonymize", LGPD))
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .json(&body)
+        .await
+}
+
+/// Entrada de auditoria gravada pelo serviço (escrita assíncrona)
+async fn audit_action_count(app: &TestApp, action: &str, subject: &str) -> i64 {
+    for _ in 0..20 {
+        let count: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM audit_logs WHERE action = $1 AND details::TEXT LIKE '%' || $2 || '%'",
+        )
+        .bind(action)
+        .bind(subject)
+        .fetch_one(&app.db_logs)
+        .await
+        .unwrap();
+        if count > 0 {
+            return count;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+    0
+}
+
+// ============================
+// TESTS
+// ============================
+
+#[tokio::test]
+async fn test_export_by_cpf_returns_driver_and_is_audited() {
+    let app = common::spawn_app().await;
+    let cpf = generate_cpf();
+    let driver_id = create_data_subject_driver(&app, &cpf).await;
+
+    let formatted = format!("{}.{}.{}-{}", &cpf[..3], &cpf[3..6], &cpf[6..9], &cpf[9..]);
+    let response = app
+        .api
+        .get(&format!("{}/export?cpf={}", LGPD, formatted))
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .await;
+    assert_eq!(
+        response.status_code(),
+        StatusCode::OK,
+        "{}",
+        response.text()
+    );
+
+    let body: Value = response.json();
+    assert!(body["user"].is_null());
+    assert_eq!(body["driver"]["id"], driver_id);
+    assert_eq!(body["driver"]["cpf"], cpf);
+    assert_eq!(body["driver"]["email"], "titular@example.com");
+    assert_eq!(body["driver"]["history"][0]["operation"], "INSERT");
+
+    assert_eq!(audit_action_count(&app, "lgpd_export", &driver_id).await, 1);
+}
+
+#[tokio::test]
+async fn test_anonymize_driver_replaces_personal_data_everywhere() {
+    let app = common::spawn_app().await;
+    let cpf = generate_cpf();
+    let driver_id = create_data_subject_driver(&app, &cpf).await;
+
+    let response = anonymize(
+        &app,
+        json!({ "cpf": cpf, "reason": "Solicitação do titular 2026/001" }),
+    )
+    .await;
+    assert_eq!(
+        response.status_code(),
+        StatusCode::OK,
+        "{}",
+        response.text()
+    );
+    let result: Value = response.json();
+    assert_eq!(result["driver_id"], driver_id);
+    assert!(result["user_id"].is_null());
+
+    let response = app
+        .api
+        .get(&format!("/api/admin/drivers/{}", driver_id))
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .await;
+    let driver: Value = response.json();
+    assert_ne!(driver["cpf"], cpf);
+    assert!(driver["phone"].is_null());
+    assert!(driver["email"].is_null());
+    assert_eq!(driver["is_active"], false);
+
+    // Nenhum snapshot do histórico guarda o CPF original
+    let leaked: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM entity_changelog
+         WHERE entity_type = 'driver' AND entity_id = $1
+           AND (COALESCE(data_before::TEXT, '') || COALESCE(data_after::TEXT, '')
+                || COALESCE(diff::TEXT, '')) LIKE '%' || $2 || '%'",
+    )
+    .bind(&driver_id)
+    .bind(&cpf)
+    .fetch_one(&app.db_auth)
+    .await
+    .unwrap();
+    assert_eq!(leaked, 0);
+
+    assert_eq!(
+        audit_action_count(&app, "lgpd_anonymization", &driver_id).await,
+        1
+    );
+
+    // O CPF original não localiza mais ninguém
+    let response = anonymize(
+        &app,
+        json!({ "cpf": cpf, "reason": "Solicitação repetida" }),
+    )
+    .await;
+    assert_eq!(response.status_code(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_anonymize_user_blocks_account_and_keeps_row() {
+    let app = common::spawn_app().await;
+    let (user_id, username) = create_user(&app).await;
+
+    let response = anonymize(
+        &app,
+        json!({ "user_id": user_id, "reason": "Solicitação do titular" }),
+    )
+    .await;
+    assert_eq!(
+        response.status_code(),
+        StatusCode::OK,
+        "{}",
+        response.text()
+    );
+    let result: Value = response.json();
+    assert_eq!(result["user_id"], user_id.to_string());
+    assert!(!result["retained"].as_array().unwrap().is_empty());
+
+    let (new_username, is_banned): (String, bool) =
+        sqlx::query_as("SELECT username, is_banned FROM users WHERE id = $1")
+            .bind(user_id)
+            .fetch_one(&app.db_auth)
+            .await
+            .unwrap();
+    assert_ne!(new_username, username);
+    assert!(new_username.starts_with("anon_"));
+    assert!(is_banned);
+
+    let response = app
+        .api
+        .get(&format!("{}/export?user_id={}", LGPD, user_id))
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .await;
+    let export: Value = response.json();
+    assert!(export["user"]["anonymized_at"].is_string());
+    assert!(export["user"]["email"]
+        .as_str()
+        .unwrap()
+        .ends_with("@anonimizado.invalid"));
+
+    let response = anonymize(
+        &app,
+        json!({ "user_id": user_id, "reason": "Solicitação repetida" }),
+    )
+    .await;
+    assert_eq!(response.status_code(), StatusCode::CONFLICT);
+}
+
+#[tokio::test]
+async fn test_anonymize_user_erases_personal_fields_from_audit_chain() {
+    let app = common::spawn_app().await;
+    let (user_id, username) = create_user(&app).await;
+
+    // Dia isolado no futuro: a cadeia verificada contém só estas entradas
+    let offset = (Uuid::new_v4().as_u128() % 365) as i64;
+    let day = NaiveDate::from_ymd_opt(2998, 1, 1).unwrap() + Duration::days(offset);
+    for action in ["login", "update_profile"] {
+        sqlx::query(
+            r#"
+            INSERT INTO audit_logs (user_id, username, action, resource, ip_address, user_agent, created_at)
+            VALUES ($1, $2, $3, '/lgpd', '203.0.113.7', 'Mozilla/5.0', ($4::DATE + TIME '12:00') AT TIME ZONE 'UTC')
+            "#,
+        )
+        .bind(user_id)
+        .bind(&username)
+        .bind(action)
+        .bind(day)
+        .execute(&app.db_logs)
+        .await
+        .unwrap();
+    }
+
+    let response = anonymize(
+        &app,
+        json!({ "user_id": user_id, "reason": "Solicitação do titular" }),
+    )
+    .await;
+    assert_eq!(response.status_code(), StatusCode::OK, "{}", response.text());
+    let result: Value = response.json();
+    assert!(result["affected"]["audit_logs"].as_i64().unwrap() >= 2);
+
+    let remaining: i64 = sqlx::query_scalar(
+        r#"
+        SELECT COUNT(*) FROM audit_logs
+        WHERE user_id = $1
+          AND (username IS NOT NULL OR ip_address IS NOT NULL OR user_agent IS NOT NULL)
+        "#,
+    )
+    .bind(user_id)
+    .fetch_one(&app.db_logs)
+    .await
+    .unwrap();
+    assert_eq!(remaining, 0);
+
+    let response = app
+        .api
+        .get("/api/admin/audit-logs/verify")
+        .add_query_param("from", day.to_string())
+        .add_query_param("to", day.to_string())
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .await;
+    response.assert_status_ok();
+    let report: Value = response.json();
+    assert_eq!(report["valid"], true, "{}", report);
+}
+
+#[tokio::test]
+async fn test_anonymize_rejects_own_account_and_missing_subject() {
+    let app = common::spawn_app().await;
+    let admin_id: Uuid = sqlx::query_scalar("SELECT id FROM users WHERE username = 'vinicius'")
+        .fetch_one(&app.db_auth)
+        .await
+        .unwrap();
+
+    let response = anonymize(
+        &app,
+        json!({ "user_id": admin_id, "reason": "Teste de bloqueio" }),
+    )
+    .await;
+    assert_eq!(response.status_code(), StatusCode::FORBIDDEN);
+
+    let response = anonymize(&app, json!({ "reason": "Sem titular informado" })).await;
+    assert_eq!(response.status_code(), StatusCode::BAD_REQUEST);
+
+    let response = anonymize(&app, json!({ "cpf": "123", "reason": "CPF inválido" })).await;
+    assert_eq!(response.status_code(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_lgpd_requires_admin() {
+    let app = common::spawn_app().await;
+
+    let response = app
+        .api
+        .get(&format!("{}/export?user_id={}", LGPD, Uuid::new_v4()))
+        .add_header("Authorization", format!("Bearer {}", app.user_token))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::FORBIDDEN);
+}
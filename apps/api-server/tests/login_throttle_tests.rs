@@ -0,0 +1,345 @@
+use serde_json::{json, Value};
+use uuid::Uuid;
+use waterswamp::extractors::client_ip::TrustedProxies;
+
+mod common;
+
+const WRONG_PASSWORD: &str = "Wrong_P@ssw0rd_999";
+
+async fn failed_login(app: &common::TestApp, username: &str, ip: &str) -> u16 {
+    app.api
+        .post("/login")
+        .add_header("X-Forwarded-For", ip)
+        .json(&json!({ "username": username, "password": WRONG_PASSWORD }))
+        .await
+        .status_code()
+        .as_u16()
+}
+
+/// IP de teste exclusivo (e sub-rede /24 exclusiva) para não interferir
+/// nos demais testes que compartilham o banco.
+fn unique_ip() -> String {
+    let bytes = Uuid::new_v4().into_bytes();
+    format!("10.{}.{}.{}", bytes[0], bytes[1], bytes[2].max(1))
+}
+
+async fn user_id_of(app: &common::TestApp, username: &str) -> Uuid {
+    sqlx::query_scalar("SELECT id FROM users WHERE username = $1")
+        .bind(username)
+        .fetch_one(&app.db_auth)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_account_locks_after_consecutive_failures() {
+    let app = common::spawn_app().await;
+    let (username, _, password) = common::create_test_user(&app.db_auth, &app.field_keyring)
+        .await
+        .unwrap();
+    let ip = unique_ip();
+
+    for _ in 0..5 {
+        assert_eq!(failed_login(&app, &username, &ip).await, 401);
+    }
+
+    // A senha correta não passa enquanto a conta estiver bloqueada
+    let response = app
+        .api
+        .post("/login")
+        .add_header("X-Forwarded-For", ip.as_str())
+        .json(&json!({ "username": username, "password": password }))
+        .await;
+
+    assert_eq!(response.status_code(), 429);
+    let retry_after: u64 = response
+        .header("retry-after")
+        .to_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert!(retry_after > 0 && retry_after <= 60);
+}
+
+#[tokio::test]
+async fn test_lockout_sends_unlock_email_and_token_unlocks() {
+    let app = common::spawn_app().await;
+    let (username, email, password) = common::create_test_user(&app.db_auth, &app.field_keyring)
+        .await
+        .unwrap();
+    let ip = unique_ip();
+
+    for _ in 0..5 {
+        failed_login(&app, &username, &ip).await;
+    }
+
+    let messages = app.email_service.get_messages().await;
+    let locked_email = messages
+        .iter()
+        .find(|m| m.to == email && m.template == "account_locked.html")
+        .expect("Email de conta bloqueada não enviado");
+    let link = locked_email
+        .context
+        .get("unlock_link")
+        .and_then(|v| v.as_str())
+        .unwrap()
+        .to_string();
+    let token = link.split("token=").nth(1).unwrap();
+
+    let response = app
+        .api
+        .post("/unlock-account")
+        .json(&json!({ "token": token }))
+        .await;
+    response.assert_status_ok();
+
+    let response = app
+        .api
+        .post("/login")
+        .add_header("X-Forwarded-For", ip.as_str())
+        .json(&json!({ "username": username, "password": password }))
+        .await;
+    response.assert_status_ok();
+
+    // Token de uso único
+    let response = app
+        .api
+        .post("/unlock-account")
+        .json(&json!({ "token": token }))
+        .await;
+    assert_eq!(response.status_code(), 400);
+}
+
+#[tokio::test]
+async fn test_successful_login_resets_failure_count() {
+    let app = common::spawn_app().await;
+    let (username, _, password) = common::create_test_user(&app.db_auth, &app.field_keyring)
+        .await
+        .unwrap();
+    let ip = unique_ip();
+
+    for _ in 0..4 {
+        failed_login(&app, &username, &ip).await;
+    }
+    app.api
+        .post("/login")
+        .json(&json!({ "username": username, "password": password }))
+        .await
+        .assert_status_ok();
+
+    // Contagem zerada: mais 4 falhas ainda não bloqueiam
+    for _ in 0..4 {
+        assert_eq!(failed_login(&app, &username, &ip).await, 401);
+    }
+}
+
+#[tokio::test]
+async fn test_concurrent_failures_are_all_counted() {
+    let app = common::spawn_app().await;
+    let (username, email, password) = common::create_test_user(&app.db_auth, &app.field_keyring)
+        .await
+        .unwrap();
+    let ip = unique_ip();
+
+    // Falhas simultâneas não podem sobrescrever a contagem umas das outras
+    let statuses =
+        futures::future::join_all((0..5).map(|_| failed_login(&app, &username, &ip))).await;
+    assert!(statuses.iter().all(|s| *s == 401), "{:?}", statuses);
+
+    let user_id = user_id_of(&app, &username).await;
+    let (failed_count, lockout_level): (i32, i32) = sqlx::query_as(
+        "SELECT failed_count, lockout_level FROM account_lockouts WHERE user_id = $1",
+    )
+    .bind(user_id)
+    .fetch_one(&app.db_auth)
+    .await
+    .unwrap();
+    assert_eq!((failed_count, lockout_level), (0, 1));
+
+    let response = app
+        .api
+        .post("/login")
+        .add_header("X-Forwarded-For", ip.as_str())
+        .json(&json!({ "username": username, "password": password }))
+        .await;
+    assert_eq!(response.status_code(), 429);
+
+    let locked_emails = app
+        .email_service
+        .get_messages()
+        .await
+        .iter()
+        .filter(|m| m.to == email && m.template == "account_locked.html")
+        .count();
+    assert_eq!(locked_emails, 1);
+}
+
+#[tokio::test]
+async fn test_session_login_is_throttled_too() {
+    let app = common::spawn_app().await;
+    let (username, _, password) = common::create_test_user(&app.db_auth, &app.field_keyring)
+        .await
+        .unwrap();
+    let ip = unique_ip();
+
+    for _ in 0..5 {
+        let status = app
+            .api
+            .post("/session/login")
+            .add_header("X-Forwarded-For", ip.as_str())
+            .json(&json!({ "username": username, "password": WRONG_PASSWORD }))
+            .await
+            .status_code();
+        assert_eq!(status, 401);
+    }
+
+    let response = app
+        .api
+        .post("/session/login")
+        .json(&json!({ "username": username, "password": password }))
+        .await;
+    assert_eq!(response.status_code(), 429);
+}
+
+#[tokio::test]
+async fn test_ip_is_throttled_across_usernames() {
+    let app = common::spawn_app().await;
+    let ip = unique_ip();
+
+    // Credential stuffing: cada tentativa usa um username diferente (inexistente)
+    for i in 0..20 {
+        let status = failed_login(
+            &app,
+            &format!("ghost_{}_{}", Uuid::new_v4().simple(), i),
+            &ip,
+        )
+        .await;
+        assert_eq!(status, 401);
+    }
+
+    let status = failed_login(&app, "ghost_final", &ip).await;
+    assert_eq!(status, 429);
+
+    // Outra sub-rede não é afetada
+    let status = failed_login(&app, "ghost_final", &unique_ip()).await;
+    assert_eq!(status, 401);
+}
+
+#[tokio::test]
+async fn test_unknown_username_gets_the_same_lockout() {
+    let app = common::spawn_app().await;
+    let (username, _, _) = common::create_test_user(&app.db_auth, &app.field_keyring)
+        .await
+        .unwrap();
+    let ghost = format!("ghost_{}", &Uuid::new_v4().simple().to_string()[..12]);
+    let ip = unique_ip();
+
+    let mut responses = Vec::new();
+    for name in [&username, &ghost] {
+        for _ in 0..5 {
+            assert_eq!(failed_login(&app, name, &ip).await, 401);
+        }
+        let response = app
+            .api
+            .post("/login")
+            .add_header("X-Forwarded-For", ip.as_str())
+            .json(&json!({ "username": name, "password": WRONG_PASSWORD }))
+            .await;
+        responses.push(response);
+    }
+
+    // Conta real e identificador inexistente: mesmo status, corpo e espera
+    let (real, unknown) = (&responses[0], &responses[1]);
+    assert_eq!(real.status_code(), 429);
+    assert_eq!(unknown.status_code(), 429);
+    assert_eq!(real.json::<Value>(), unknown.json::<Value>());
+    let retry_after: u64 = unknown
+        .header("retry-after")
+        .to_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert!(retry_after > 0 && retry_after <= 60);
+}
+
+#[tokio::test]
+async fn test_forwarded_for_is_ignored_without_trusted_proxy() {
+    let app = common::spawn_app_with(|c| c.trusted_proxies = TrustedProxies::default()).await;
+
+    // Cada tentativa forja um X-Forwarded-For diferente; vale o IP da conexão
+    for i in 0..20 {
+        let status = failed_login(
+            &app,
+            &format!("ghost_{}_{}", Uuid::new_v4().simple(), i),
+            &unique_ip(),
+        )
+        .await;
+        assert_eq!(status, 401);
+    }
+
+    let status = failed_login(&app, "ghost_final", &unique_ip()).await;
+    assert_eq!(status, 429);
+}
+
+#[tokio::test]
+async fn test_admin_can_list_and_clear_lockouts() {
+    let app = common::spawn_app().await;
+    let (username, _, password) = common::create_test_user(&app.db_auth, &app.field_keyring)
+        .await
+        .unwrap();
+    let user_id = user_id_of(&app, &username).await;
+    let ip = unique_ip();
+
+    for _ in 0..5 {
+        failed_login(&app, &username, &ip).await;
+    }
+
+    let response = app
+        .api
+        .get("/api/admin/security/lockouts")
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .await;
+    response.assert_status_ok();
+    let body: Value = response.json();
+    let entry = body
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|l| l["user_id"] == json!(user_id))
+        .expect("Bloqueio não listado");
+    assert_eq!(entry["username"], username);
+    assert_eq!(entry["lockout_level"], 1);
+    assert!(entry["locked_until"].is_string());
+
+    let response = app
+        .api
+        .delete(&format!("/api/admin/security/lockouts/{}", user_id))
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .await;
+    assert_eq!(response.status_code(), 204);
+
+    app.api
+        .post("/login")
+        .json(&json!({ "username": username, "password": password }))
+        .await
+        .assert_status_ok();
+
+    let response = app
+        .api
+        .delete(&format!("/api/admin/security/lockouts/{}", user_id))
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .await;
+    assert_eq!(response.status_code(), 404);
+}
+
+#[tokio::test]
+async fn test_lockouts_endpoint_requires_admin() {
+    let app = common::spawn_app().await;
+
+    let response = app
+        .api
+        .get("/api/admin/security/lockouts")
+        .add_header("Authorization", format!("Bearer {}", app.user_token))
+        .await;
+    assert_eq!(response.status_code(), 403);
+}
@@ -35,7 +35,7 @@ async fn test_mfa_setup_initiation() {
     let app = common::spawn_app().await;
 
     // Cria um usuário 100% isolado para este teste
-    let (username, _, _) = common::create_test_user(&app.db_auth, &app.field_encryption_key)
+    let (username, _, _) = common::create_test_user(&app.db_auth, &app.field_keyring)
         .await
         .unwrap();
     let user_id: Uuid = sqlx::query_scalar("SELECT id FROM users WHERE username = $1")
@@ -63,7 +63,7 @@ async fn test_mfa_setup_initiation() {
 async fn test_mfa_setup_already_enabled() {
     let app = common::spawn_app().await;
 
-    let (username, _, _) = common::create_test_user(&app.db_auth, &app.field_encryption_key)
+    let (username, _, _) = common::create_test_user(&app.db_auth, &app.field_keyring)
         .await
         .unwrap();
     let user_id: Uuid = sqlx::query_scalar("SELECT id FROM users WHERE username = $1")
@@ -94,7 +94,7 @@ async fn test_mfa_setup_already_enabled() {
 async fn test_mfa_verify_setup_success() {
     let app = common::spawn_app().await;
 
-    let (username, _, _) = common::create_test_user(&app.db_auth, &app.field_encryption_key)
+    let (username, _, _) = common::create_test_user(&app.db_auth, &app.field_keyring)
         .await
         .unwrap();
     let user_id: Uuid = sqlx::query_scalar("SELECT id FROM users WHERE username = $1")
@@ -140,7 +140,7 @@ async fn test_mfa_verify_setup_success() {
 async fn test_mfa_verify_setup_invalid_code() {
     let app = common::spawn_app().await;
 
-    let (username, _, _) = common::create_test_user(&app.db_auth, &app.field_encryption_key)
+    let (username, _, _) = common::create_test_user(&app.db_auth, &app.field_keyring)
         .await
         .unwrap();
     let user_id: Uuid = sqlx::query_scalar("SELECT id FROM users WHERE username = $1")
@@ -178,7 +178,7 @@ async fn test_mfa_verify_setup_invalid_code() {
 async fn test_mfa_verify_with_totp() {
     let app = common::spawn_app().await;
 
-    let (username, _, password) = common::create_test_user(&app.db_auth, &app.field_encryption_key)
+    let (username, _, password) = common::create_test_user(&app.db_auth, &app.field_keyring)
         .await
         .unwrap();
     let user_id: Uuid = sqlx::query_scalar("SELECT id FROM users WHERE username = $1")
@@ -221,7 +221,7 @@ async fn test_mfa_verify_with_totp() {
 async fn test_mfa_verify_with_backup_code() {
     let app = common::spawn_app().await;
 
-    let (username, _, password) = common::create_test_user(&app.db_auth, &app.field_encryption_key)
+    let (username, _, password) = common::create_test_user(&app.db_auth, &app.field_keyring)
         .await
         .unwrap();
     let user_id: Uuid = sqlx::query_scalar("SELECT id FROM users WHERE username = $1")
@@ -275,7 +275,7 @@ async fn test_mfa_verify_with_backup_code() {
 async fn test_mfa_status_with_backup_codes() {
     let app = common::spawn_app().await;
 
-    let (username, _, _) = common::create_test_user(&app.db_auth, &app.field_encryption_key)
+    let (username, _, _) = common::create_test_user(&app.db_auth, &app.field_keyring)
         .await
         .unwrap();
     let user_id: Uuid = sqlx::query_scalar("SELECT id FROM users WHERE username = $1")
@@ -317,7 +317,7 @@ async fn test_mfa_status_with_backup_codes() {
 async fn test_mfa_regenerate_backup_codes() {
     let app = common::spawn_app().await;
 
-    let (username, _, password) = common::create_test_user(&app.db_auth, &app.field_encryption_key)
+    let (username, _, password) = common::create_test_user(&app.db_auth, &app.field_keyring)
         .await
         .unwrap();
     let user_id: Uuid = sqlx::query_scalar("SELECT id FROM users WHERE username = $1")
@@ -0,0 +1,440 @@
+use application::external::OidcClient;
+use application::services::oidc_service::OidcProviderSettings;
+use axum::{
+    extract::{Form, State},
+    http::StatusCode as AxumStatus,
+    routing::{get, post},
+    Json, Router,
+};
+use base64::{engine::general_purpose::STANDARD, engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use core_services::session::config::{CSRF_COOKIE_NAME, OIDC_STATE_COOKIE_NAME, SESSION_COOKIE_NAME};
+use http::StatusCode;
+use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::net::TcpListener;
+use uuid::Uuid;
+
+mod common;
+
+const CLIENT_ID: &str = "waterswamp-test";
+const KID: &str = "mock-key-1";
+
+// =============================================================================
+// MOCK IDENTITY PROVIDER
+// =============================================================================
+
+/// Claims a serem emitidas para cada authorization code "consentido".
+#[derive(Clone)]
+struct Grant {
+    nonce: String,
+    claims: Value,
+}
+
+#[derive(Clone)]
+struct MockIdp {
+    issuer: String,
+    grants: Arc<Mutex<HashMap<String, Grant>>>,
+    jwks_requests: Arc<AtomicUsize>,
+}
+
+impl MockIdp {
+    /// Simula o usuário autenticando no IdP: registra o code que será
+    /// devolvido ao callback.
+    fn authorize(&self, nonce: &str, claims: Value) -> String {
+        let code = Uuid::new_v4().to_string();
+        self.grants.lock().unwrap().insert(
+            code.clone(),
+            Grant {
+                nonce: nonce.to_string(),
+                claims,
+            },
+        );
+        code
+    }
+}
+
+/// Chave pública Ed25519 de teste como JWK (OKP): os 32 bytes finais do SPKI.
+fn public_jwk() -> Value {
+    let pem = include_str!("keys/public_test.pem");
+    let der_b64: String = pem.lines().filter(|l| !l.starts_with("-----")).collect();
+    let der = STANDARD.decode(der_b64).unwrap();
+    let x = URL_SAFE_NO_PAD.encode(&der[der.len() - 32..]);
+    json!({ "kty": "OKP", "crv": "Ed25519", "x": x, "kid": KID, "alg": "EdDSA", "use": "sig" })
+}
+
+async fn discovery(State(idp): State<MockIdp>) -> Json<Value> {
+    Json(json!({
+        "issuer": idp.issuer,
+        "authorization_endpoint": format!("{}/authorize", idp.issuer),
+        "token_endpoint": format!("{}/token", idp.issuer),
+        "jwks_uri": format!("{}/jwks", idp.issuer),
+    }))
+}
+
+async fn jwks(State(idp): State<MockIdp>) -> Json<Value> {
+    idp.jwks_requests.fetch_add(1, Ordering::SeqCst);
+    Json(json!({ "keys": [public_jwk()] }))
+}
+
+async fn token(
+    State(idp): State<MockIdp>,
+    Form(form): Form<HashMap<String, String>>,
+) -> Result<Json<Value>, AxumStatus> {
+    if form.get("grant_type").map(String::as_str) != Some("authorization_code")
+        || form.get("code_verifier").is_none_or(|v| v.len() < 43)
+    {
+        return Err(AxumStatus::BAD_REQUEST);
+    }
+    let code = form.get("code").ok_or(AxumStatus::BAD_REQUEST)?;
+    let grant = idp
+        .grants
+        .lock()
+        .unwrap()
+        .remove(code)
+        .ok_or(AxumStatus::BAD_REQUEST)?;
+
+    let now = chrono::Utc::now().timestamp();
+    let mut claims = json!({
+        "iss": idp.issuer,
+        "aud": CLIENT_ID,
+        "iat": now,
+        "exp": now + 300,
+        "nonce": grant.nonce,
+    });
+    for (k, v) in grant.claims.as_object().unwrap() {
+        claims[k] = v.clone();
+    }
+
+    let mut header = Header::new(Algorithm::EdDSA);
+    header.kid = Some(KID.to_string());
+    let key = EncodingKey::from_ed_pem(include_bytes!("keys/private_test.pem")).unwrap();
+    let id_token = encode(&header, &claims, &key).unwrap();
+
+    Ok(Json(json!({ "id_token": id_token, "access_token": "opaque", "token_type": "Bearer" })))
+}
+
+async fn spawn_mock_idp() -> MockIdp {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let idp = MockIdp {
+        issuer: format!("http://{}", listener.local_addr().unwrap()),
+        grants: Arc::new(Mutex::new(HashMap::new())),
+        jwks_requests: Arc::new(AtomicUsize::new(0)),
+    };
+
+    let app = Router::new()
+        .route("/.well-known/openid-configuration", get(discovery))
+        .route("/jwks", get(jwks))
+        .route("/token", post(token))
+        .with_state(idp.clone());
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+
+    idp
+}
+
+fn provider_settings(idp: &MockIdp, jit: bool) -> OidcProviderSettings {
+    OidcProviderSettings {
+        name: "mock".to_string(),
+        display_name: "Mock IdP".to_string(),
+        issuer: idp.issuer.clone(),
+        client_id: CLIENT_ID.to_string(),
+        client_secret: Some("secret".to_string()),
+        redirect_uri: "http://localhost/api/v1/auth/oidc/mock/callback".to_string(),
+        scopes: vec!["openid".to_string(), "email".to_string()],
+        cpf_claim: Some("sub".to_string()),
+        jit_provisioning: jit,
+        default_role: "user".to_string(),
+    }
+}
+
+/// Parâmetro da URL de autorização (valores gerados são URL-safe).
+fn query_param(url: &str, name: &str) -> String {
+    url.split_once('?')
+        .map(|(_, q)| q)
+        .unwrap_or_default()
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(k, _)| *k == name)
+        .map(|(_, v)| v.to_string())
+        .unwrap_or_else(|| panic!("missing '{}' in {}", name, url))
+}
+
+// =============================================================================
+// CLIENT (sem banco de dados)
+// =============================================================================
+
+#[tokio::test]
+async fn test_oidc_client_verifies_id_token_from_mock_idp() {
+    let idp = spawn_mock_idp().await;
+    let client = OidcClient::new().unwrap();
+
+    let discovery = client.discover(&idp.issuer).await.unwrap();
+    assert_eq!(discovery.issuer, idp.issuer);
+
+    let code = idp.authorize(
+        "nonce-123",
+        json!({ "sub": "52998224725", "email": "Maria@UFMT.br", "email_verified": true }),
+    );
+    let verifier = "v".repeat(43);
+    let tokens = client
+        .exchange_code(&discovery, CLIENT_ID, Some("secret"), "http://localhost/cb", &code, &verifier)
+        .await
+        .unwrap();
+
+    let claims = client
+        .verify_id_token(&discovery, CLIENT_ID, &tokens.id_token, "nonce-123")
+        .await
+        .unwrap();
+    assert_eq!(claims.subject, "52998224725");
+    assert!(claims.email_verified);
+    assert_eq!(claims.email.as_deref(), Some("Maria@UFMT.br"));
+}
+
+#[tokio::test]
+async fn test_oidc_client_rejects_nonce_and_audience_mismatch() {
+    let idp = spawn_mock_idp().await;
+    let client = OidcClient::new().unwrap();
+    let discovery = client.discover(&idp.issuer).await.unwrap();
+    let verifier = "v".repeat(43);
+
+    let code = idp.authorize("expected", json!({ "sub": "abc" }));
+    let tokens = client
+        .exchange_code(&discovery, CLIENT_ID, None, "http://localhost/cb", &code, &verifier)
+        .await
+        .unwrap();
+
+    assert!(client
+        .verify_id_token(&discovery, CLIENT_ID, &tokens.id_token, "other")
+        .await
+        .is_err());
+    assert!(client
+        .verify_id_token(&discovery, "another-client", &tokens.id_token, "expected")
+        .await
+        .is_err());
+
+    // Code de uso único
+    assert!(client
+        .exchange_code(&discovery, CLIENT_ID, None, "http://localhost/cb", &code, &verifier)
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_oidc_client_caches_jwks_when_kid_is_unknown() {
+    let idp = spawn_mock_idp().await;
+    let client = OidcClient::new().unwrap();
+    let discovery = client.discover(&idp.issuer).await.unwrap();
+
+    let now = chrono::Utc::now().timestamp();
+    let claims = json!({
+        "iss": idp.issuer,
+        "aud": CLIENT_ID,
+        "sub": "abc",
+        "iat": now,
+        "exp": now + 300,
+        "nonce": "n",
+    });
+    let mut header = Header::new(Algorithm::EdDSA);
+    header.kid = Some("unknown-key".to_string());
+    let key = EncodingKey::from_ed_pem(include_bytes!("keys/private_test.pem")).unwrap();
+    let id_token = encode(&header, &claims, &key).unwrap();
+
+    // Tokens com `kid` desconhecido não disparam uma busca do JWKS cada
+    for _ in 0..3 {
+        assert!(client
+            .verify_id_token(&discovery, CLIENT_ID, &id_token, "n")
+            .await
+            .is_err());
+    }
+    assert_eq!(idp.jwks_requests.load(Ordering::SeqCst), 1);
+}
+
+// =============================================================================
+// FLUXO COMPLETO (login → callback → sessão)
+// =============================================================================
+
+#[tokio::test]
+async fn test_oidc_login_jit_provisioning_creates_session() {
+    let idp = spawn_mock_idp().await;
+    let settings = provider_settings(&idp, true);
+    let app = common::spawn_app_with(|c| c.oidc_providers = vec![settings]).await;
+
+    let providers = app.api.get("/oidc/providers").await;
+    assert_eq!(providers.status_code(), StatusCode::OK);
+    assert_eq!(providers.json::<Value>()[0]["name"], "mock");
+
+    let login = app.api.get("/oidc/mock/login?redirect_to=/frota").await;
+    assert_eq!(login.status_code(), StatusCode::SEE_OTHER);
+    let location = login.header("location").to_str().unwrap().to_string();
+    assert!(location.starts_with(&format!("{}/authorize", idp.issuer)));
+    assert_eq!(query_param(&location, "code_challenge_method"), "S256");
+    assert_eq!(query_param(&location, "client_id"), CLIENT_ID);
+
+    let unique = Uuid::new_v4().simple().to_string();
+    let email = format!("oidc_{}@example.com", &unique[..8]);
+    let code = idp.authorize(
+        &query_param(&location, "nonce"),
+        json!({
+            "sub": format!("sub-{}", unique),
+            "email": email,
+            "email_verified": true,
+            "preferred_username": format!("oidc_{}", &unique[..8]),
+        }),
+    );
+
+    let callback = app
+        .api
+        .get("/oidc/mock/callback")
+        .add_cookie(login.cookie(OIDC_STATE_COOKIE_NAME))
+        .add_query_param("code", &code)
+        .add_query_param("state", query_param(&location, "state"))
+        .await;
+    assert_eq!(callback.status_code(), StatusCode::SEE_OTHER);
+    assert_eq!(callback.header("location"), "/frota");
+    assert!(!callback.cookie(SESSION_COOKIE_NAME).value().is_empty());
+    assert!(!callback.cookie(CSRF_COOKIE_NAME).value().is_empty());
+
+    let role: String = sqlx::query_scalar(
+        "SELECT u.role FROM users u JOIN user_identities i ON i.user_id = u.id
+         WHERE i.provider = 'mock' AND u.username = $1",
+    )
+    .bind(format!("oidc_{}", &unique[..8]))
+    .fetch_one(&app.db_auth)
+    .await
+    .unwrap();
+    assert_eq!(role, "user");
+}
+
+#[tokio::test]
+async fn test_oidc_callback_rejects_reused_state() {
+    let idp = spawn_mock_idp().await;
+    let settings = provider_settings(&idp, true);
+    let app = common::spawn_app_with(|c| c.oidc_providers = vec![settings]).await;
+
+    let login = app.api.get("/oidc/mock/login").await;
+    let location = login.header("location").to_str().unwrap().to_string();
+    let state = query_param(&location, "state");
+    let nonce = query_param(&location, "nonce");
+    let unique = Uuid::new_v4().simple().to_string();
+    let claims = json!({
+        "sub": format!("sub-{}", unique),
+        "email": format!("oidc_{}@example.com", &unique[..8]),
+        "email_verified": true,
+    });
+
+    let first = app
+        .api
+        .get("/oidc/mock/callback")
+        .add_cookie(login.cookie(OIDC_STATE_COOKIE_NAME))
+        .add_query_param("code", idp.authorize(&nonce, claims.clone()))
+        .add_query_param("state", &state)
+        .await;
+    assert_eq!(first.status_code(), StatusCode::OK);
+    assert!(first.json::<Value>()["csrf_token"].is_string());
+
+    let replay = app
+        .api
+        .get("/oidc/mock/callback")
+        .add_cookie(login.cookie(OIDC_STATE_COOKIE_NAME))
+        .add_query_param("code", idp.authorize(&nonce, claims))
+        .add_query_param("state", &state)
+        .await;
+    assert_eq!(replay.status_code(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_oidc_login_without_link_and_jit_disabled_is_rejected() {
+    let idp = spawn_mock_idp().await;
+    let settings = provider_settings(&idp, false);
+    let app = common::spawn_app_with(|c| c.oidc_providers = vec![settings]).await;
+
+    let login = app.api.get("/oidc/mock/login").await;
+    let location = login.header("location").to_str().unwrap().to_string();
+    let code = idp.authorize(
+        &query_param(&location, "nonce"),
+        json!({ "sub": format!("sub-{}", Uuid::new_v4()), "email": "nobody@example.com", "email_verified": false }),
+    );
+
+    let callback = app
+        .api
+        .get("/oidc/mock/callback")
+        .add_cookie(login.cookie(OIDC_STATE_COOKIE_NAME))
+        .add_query_param("code", &code)
+        .add_query_param("state", query_param(&location, "state"))
+        .await;
+    assert_eq!(callback.status_code(), StatusCode::UNAUTHORIZED);
+    assert!(callback.maybe_cookie(SESSION_COOKIE_NAME).is_none());
+}
+
+#[tokio::test]
+async fn test_oidc_login_rejects_external_redirect() {
+    let idp = spawn_mock_idp().await;
+    let settings = provider_settings(&idp, false);
+    let app = common::spawn_app_with(|c| c.oidc_providers = vec![settings]).await;
+
+    let response = app
+        .api
+        .get("/oidc/mock/login?redirect_to=//evil.example")
+        .await;
+    assert_eq!(response.status_code(), StatusCode::BAD_REQUEST);
+
+    let unknown = app.api.get("/oidc/unknown/login").await;
+    assert_eq!(unknown.status_code(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_oidc_callback_requires_state_cookie_from_same_browser() {
+    let idp = spawn_mock_idp().await;
+    let settings = provider_settings(&idp, true);
+    let app = common::spawn_app_with(|c| c.oidc_providers = vec![settings]).await;
+
+    let login = app.api.get("/oidc/mock/login").await;
+    let state_cookie = login.cookie(OIDC_STATE_COOKIE_NAME);
+    assert!(state_cookie.http_only().unwrap_or(false));
+    assert_eq!(state_cookie.same_site(), Some(cookie::SameSite::Lax));
+    let location = login.header("location").to_str().unwrap().to_string();
+    let state = query_param(&location, "state");
+    let nonce = query_param(&location, "nonce");
+    let unique = Uuid::new_v4().simple().to_string();
+    let claims = json!({
+        "sub": format!("sub-{}", unique),
+        "email": format!("oidc_{}@example.com", &unique[..8]),
+        "email_verified": true,
+    });
+
+    // Callback forjado em outro navegador: sem o cookie
+    let without_cookie = app
+        .api
+        .get("/oidc/mock/callback")
+        .add_query_param("code", idp.authorize(&nonce, claims.clone()))
+        .add_query_param("state", &state)
+        .await;
+    assert_eq!(without_cookie.status_code(), StatusCode::BAD_REQUEST);
+    assert!(without_cookie.maybe_cookie(SESSION_COOKIE_NAME).is_none());
+
+    // Cookie de outro login
+    let other_login = app.api.get("/oidc/mock/login").await;
+    let mismatched = app
+        .api
+        .get("/oidc/mock/callback")
+        .add_cookie(other_login.cookie(OIDC_STATE_COOKIE_NAME))
+        .add_query_param("code", idp.authorize(&nonce, claims.clone()))
+        .add_query_param("state", &state)
+        .await;
+    assert_eq!(mismatched.status_code(), StatusCode::BAD_REQUEST);
+
+    // O state rejeitado continua válido para o navegador legítimo
+    let callback = app
+        .api
+        .get("/oidc/mock/callback")
+        .add_cookie(state_cookie)
+        .add_query_param("code", idp.authorize(&nonce, claims))
+        .add_query_param("state", &state)
+        .await;
+    assert_eq!(callback.status_code(), StatusCode::OK);
+    assert!(callback.cookie(OIDC_STATE_COOKIE_NAME).value().is_empty());
+}
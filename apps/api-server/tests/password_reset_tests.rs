@@ -43,7 +43,7 @@ async fn test_forgot_password_flow_and_email_mocking() {
 
     // Em vez de usar o "bob" do seed (que está sem email_index no banco devido às migrations cruas),
     // criamos um usuário fresco que passa pela lógica de criptografia e ganha o email_index correto.
-    let (test_user, test_email, _) = common::create_test_user(&app.db_auth, &app.field_encryption_key)
+    let (test_user, test_email, _) = common::create_test_user(&app.db_auth, &app.field_keyring)
         .await
         .unwrap();
 
@@ -0,0 +1,235 @@
+mod common;
+
+use common::{random_suffix, TestApp};
+use http::StatusCode;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+const PREVIEWS: &str = "/api/admin/organizational/sync/previews";
+
+// ============================
+// HELPERS
+// ============================
+
+/// Organização com código SIORG próprio; retorna (id, siorg_code)
+async fn create_organization(app: &TestApp) -> (Uuid, i32) {
+    let siorg_code = 1_000_000 + (rand::random::<u32>() % 1_000_000) as i32;
+    let response = app
+        .api
+        .post("/api/admin/organizational/organizations")
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .json(&json!({
+            "acronym": random_suffix(),
+            "name": format!("Org Prévia {}", random_suffix()),
+            "cnpj": format!("{:014}", rand::random::<u64>() % 100000000000000),
+            "ug_code": rand::random::<u32>() % 1000000,
+            "siorg_code": siorg_code,
+            "is_main_organization": false,
+            "is_active": true
+        }))
+        .await;
+    assert!(response.status_code().is_success(), "{}", response.text());
+    let body: Value = response.json();
+    (
+        Uuid::parse_str(body["id"].as_str().unwrap()).unwrap(),
+        siorg_code,
+    )
+}
+
+async fn set_stored_version(app: &TestApp, siorg_code: i32, version: &str) {
+    sqlx::query(
+        "INSERT INTO system_settings (key, value, value_type, category)
+         VALUES ($1, $2, 'string', 'siorg')
+         ON CONFLICT (key) DO UPDATE SET value = EXCLUDED.value",
+    )
+    .bind(format!("siorg_versao:{}", siorg_code))
+    .bind(json!(version))
+    .execute(&app.db_auth)
+    .await
+    .unwrap();
+}
+
+async fn stored_version(app: &TestApp, siorg_code: i32) -> Value {
+    sqlx::query_scalar("SELECT value FROM system_settings WHERE key = $1")
+        .bind(format!("siorg_versao:{}", siorg_code))
+        .fetch_one(&app.db_auth)
+        .await
+        .unwrap()
+}
+
+/// Prévia pendente de 100 → 101 com a criação de uma unidade
+async fn insert_preview(app: &TestApp, org_id: Uuid, siorg_code: i32) -> Uuid {
+    let unit_code = siorg_code + 1;
+    let entries = json!([{
+        "siorg_code": unit_code,
+        "local_unit_id": null,
+        "kinds": ["CREATED"],
+        "previous_name": null,
+        "name": "Coordenação Nova",
+        "previous_acronym": null,
+        "acronym": "CN",
+        "previous_parent_code": null,
+        "parent_code": siorg_code,
+        "is_active": true,
+        "references": { "warehouses": 0, "vehicles": 0, "open_requisitions": 0 },
+        "flagged": false,
+        "decision": "PENDING",
+        "upstream": {
+            "codigoUnidade": unit_code.to_string(),
+            "codigoUnidadePai": siorg_code.to_string(),
+            "nome": "Coordenação Nova",
+            "sigla": "CN",
+            "operacao": "INCLUSAO"
+        }
+    }]);
+    sqlx::query_scalar(
+        "INSERT INTO siorg_change_previews
+            (organization_id, org_siorg_code, from_version, to_version, total_changes, entries)
+         VALUES ($1, $2, '100', '101', 1, $3)
+         RETURNING id",
+    )
+    .bind(org_id)
+    .bind(siorg_code)
+    .bind(entries)
+    .fetch_one(&app.db_auth)
+    .await
+    .unwrap()
+}
+
+async fn apply(app: &TestApp, id: Uuid, body: Value) -> (StatusCode, Value) {
+    let response = app
+        .api
+        .post(&format!("{}/{}/apply", PREVIEWS, id))
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .json(&body)
+        .await;
+    let status = response.status_code();
+    let body = if status.is_success() {
+        response.json()
+    } else {
+        Value::Null
+    };
+    (status, body)
+}
+
+// ============================
+// TESTS
+// ============================
+
+#[tokio::test]
+async fn test_apply_rejecting_all_advances_version_without_changes() {
+    let app = common::spawn_app().await;
+    let (org_id, siorg_code) = create_organization(&app).await;
+    set_stored_version(&app, siorg_code, "100").await;
+    let id = insert_preview(&app, org_id, siorg_code).await;
+
+    let (status, body) = apply(&app, id, json!({ "siorg_codes": [] })).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "APPLIED");
+    assert_eq!(body["entries"][0]["decision"], "REJECTED");
+    assert_eq!(stored_version(&app, siorg_code).await, json!("101"));
+
+    let created: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM organizational_units WHERE siorg_code = $1")
+            .bind(siorg_code + 1)
+            .fetch_one(&app.db_auth)
+            .await
+            .unwrap();
+    assert_eq!(created, 0);
+
+    // Já decidida
+    let (status, _) = apply(&app, id, json!({ "approve_all": true })).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+}
+
+#[tokio::test]
+async fn test_apply_rejects_code_outside_preview() {
+    let app = common::spawn_app().await;
+    let (org_id, siorg_code) = create_organization(&app).await;
+    set_stored_version(&app, siorg_code, "100").await;
+    let id = insert_preview(&app, org_id, siorg_code).await;
+
+    let (status, _) = apply(&app, id, json!({ "siorg_codes": [siorg_code + 99] })).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_apply_stale_preview_returns_conflict() {
+    let app = common::spawn_app().await;
+    let (org_id, siorg_code) = create_organization(&app).await;
+    let id = insert_preview(&app, org_id, siorg_code).await;
+    // A estrutura foi sincronizada depois da prévia
+    set_stored_version(&app, siorg_code, "102").await;
+
+    let (status, _) = apply(&app, id, json!({ "approve_all": true })).await;
+
+    assert_eq!(status, StatusCode::CONFLICT);
+}
+
+#[tokio::test]
+async fn test_discard_preview() {
+    let app = common::spawn_app().await;
+    let (org_id, siorg_code) = create_organization(&app).await;
+    let id = insert_preview(&app, org_id, siorg_code).await;
+
+    let response = app
+        .api
+        .post(&format!("{}/{}/discard", PREVIEWS, id))
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    let body: Value = response.json();
+    assert_eq!(body["status"], "DISCARDED");
+
+    let response = app
+        .api
+        .get(&format!("{}?organization_id={}", PREVIEWS, org_id))
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    let list: Value = response.json();
+    assert_eq!(list["total"], 1);
+    assert!(list["data"][0]["entries"].is_null());
+}
+
+#[tokio::test]
+async fn test_get_unknown_preview_returns_404() {
+    let app = common::spawn_app().await;
+
+    let response = app
+        .api
+        .get(&format!("{}/{}", PREVIEWS, Uuid::new_v4()))
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .await;
+
+    assert_eq!(response.status_code(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_preview_unknown_organization_returns_404() {
+    let app = common::spawn_app().await;
+
+    let response = app
+        .api
+        .post(PREVIEWS)
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .json(&json!({ "organization_id": Uuid::new_v4() }))
+        .await;
+
+    assert_eq!(response.status_code(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_previews_require_admin() {
+    let app = common::spawn_app().await;
+
+    let response = app
+        .api
+        .get(PREVIEWS)
+        .add_header("Authorization", format!("Bearer {}", app.user_token))
+        .await;
+
+    assert_eq!(response.status_code(), StatusCode::FORBIDDEN);
+}
@@ -0,0 +1,168 @@
+mod common;
+
+use common::{admin_post, create_driver, create_vehicle, TestApp};
+use http::StatusCode;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+// ============================
+// HELPERS
+// ============================
+
+/// Solicita e aprova uma viagem; devolve (id, version).
+async fn approved_trip(
+    app: &TestApp,
+    vehicle_id: Uuid,
+    departure: &str,
+    planned_return: &str,
+) -> (String, i64) {
+    let trip = admin_post(
+        app,
+        "/api/admin/trips",
+        json!({
+            "vehicle_id": vehicle_id,
+            "destination": "Campus Sede",
+            "purpose": "Reunião",
+            "planned_departure": departure,
+            "planned_return": planned_return
+        }),
+    )
+    .await;
+    let id = trip["id"].as_str().unwrap().to_string();
+
+    let response = app
+        .api
+        .put(&format!("/api/admin/trips/{}/review", id))
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .json(&json!({ "approved": true, "version": trip["version"] }))
+        .await;
+    assert_eq!(
+        response.status_code(),
+        StatusCode::OK,
+        "{}",
+        response.text()
+    );
+    let approved: Value = response.json();
+    (id, approved["version"].as_i64().unwrap())
+}
+
+async fn allocate(app: &TestApp, trip: &(String, i64), driver_id: Uuid) -> StatusCode {
+    app.api
+        .put(&format!("/api/admin/trips/{}/allocate", trip.0))
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .json(&json!({ "driver_id": driver_id, "version": trip.1 }))
+        .await
+        .status_code()
+}
+
+// ============================
+// ALLOCATION TESTS
+// ============================
+
+#[tokio::test]
+async fn test_back_to_back_trips_do_not_conflict() {
+    let app = common::spawn_app().await;
+    let vehicle_id = create_vehicle(&app).await;
+    let driver_id = create_driver(&app).await;
+
+    let morning = approved_trip(
+        &app,
+        vehicle_id,
+        "2031-03-10T08:00:00Z",
+        "2031-03-10T10:00:00Z",
+    )
+    .await;
+    let late_morning = approved_trip(
+        &app,
+        vehicle_id,
+        "2031-03-10T10:00:00Z",
+        "2031-03-10T12:00:00Z",
+    )
+    .await;
+    let overlapping = approved_trip(
+        &app,
+        vehicle_id,
+        "2031-03-10T09:59:00Z",
+        "2031-03-10T11:00:00Z",
+    )
+    .await;
+
+    assert_eq!(allocate(&app, &morning, driver_id).await, StatusCode::OK);
+    // Retorno às 10h libera o veículo e o condutor para a saída das 10h
+    assert_eq!(
+        allocate(&app, &late_morning, driver_id).await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        allocate(&app, &overlapping, create_driver(&app).await).await,
+        StatusCode::CONFLICT
+    );
+}
+
+#[tokio::test]
+async fn test_concurrent_allocations_of_the_same_vehicle_book_it_once() {
+    let app = common::spawn_app().await;
+    let vehicle_id = create_vehicle(&app).await;
+
+    let first = approved_trip(
+        &app,
+        vehicle_id,
+        "2031-04-02T08:00:00Z",
+        "2031-04-02T18:00:00Z",
+    )
+    .await;
+    let second = approved_trip(
+        &app,
+        vehicle_id,
+        "2031-04-02T13:00:00Z",
+        "2031-04-02T20:00:00Z",
+    )
+    .await;
+    let (first_driver, second_driver) = (create_driver(&app).await, create_driver(&app).await);
+
+    let (a, b) = tokio::join!(
+        allocate(&app, &first, first_driver),
+        allocate(&app, &second, second_driver)
+    );
+    let mut statuses = vec![a, b];
+    statuses.sort();
+    assert_eq!(statuses, vec![StatusCode::OK, StatusCode::CONFLICT]);
+
+    let allocated: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM vehicle_trips WHERE vehicle_id = $1 AND status = 'ALOCADA'",
+    )
+    .bind(vehicle_id)
+    .fetch_one(&app.db_auth)
+    .await
+    .unwrap();
+    assert_eq!(allocated, 1);
+}
+
+#[tokio::test]
+async fn test_concurrent_allocations_of_the_same_driver_book_it_once() {
+    let app = common::spawn_app().await;
+    let driver_id = create_driver(&app).await;
+
+    let first = approved_trip(
+        &app,
+        create_vehicle(&app).await,
+        "2031-05-06T08:00:00Z",
+        "2031-05-06T18:00:00Z",
+    )
+    .await;
+    let second = approved_trip(
+        &app,
+        create_vehicle(&app).await,
+        "2031-05-06T09:00:00Z",
+        "2031-05-06T11:00:00Z",
+    )
+    .await;
+
+    let (a, b) = tokio::join!(
+        allocate(&app, &first, driver_id),
+        allocate(&app, &second, driver_id)
+    );
+    let mut statuses = vec![a, b];
+    statuses.sort();
+    assert_eq!(statuses, vec![StatusCode::OK, StatusCode::CONFLICT]);
+}
@@ -0,0 +1,321 @@
+mod common;
+
+use common::{admin_post, random_suffix, TestApp};
+use http::StatusCode;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+const RESTRUCTURINGS: &str = "/api/admin/organizational/restructurings";
+const UNITS: &str = "/api/admin/organizational/units";
+
+// ============================
+// HELPERS
+// ============================
+
+/// Organização com `count` unidades raiz; retorna os IDs das unidades
+async fn create_units(app: &TestApp, count: usize) -> Vec<Uuid> {
+    let organization = admin_post(
+        app,
+        "/api/admin/organizational/organizations",
+        json!({
+            "acronym": random_suffix(),
+            "name": format!("Org Reestruturação {}", random_suffix()),
+            "cnpj": format!("{:014}", rand::random::<u64>() % 100000000000000),
+            "ug_code": rand::random::<u32>() % 1000000,
+            "siorg_code": rand::random::<i32>() % 1000000,
+            "is_main_organization": false,
+            "is_active": true
+        }),
+    )
+    .await;
+    let category = admin_post(
+        app,
+        "/api/admin/organizational/unit-categories",
+        json!({
+            "name": format!("Categoria {}", random_suffix()),
+            "is_active": true,
+            "is_siorg_managed": false
+        }),
+    )
+    .await;
+    let unit_type = admin_post(
+        app,
+        "/api/admin/organizational/unit-types",
+        json!({
+            "code": random_suffix(),
+            "name": format!("Tipo {}", random_suffix()),
+            "is_active": true,
+            "is_siorg_managed": false
+        }),
+    )
+    .await;
+
+    let mut ids = Vec::new();
+    for _ in 0..count {
+        let unit = admin_post(
+            app,
+            UNITS,
+            json!({
+                "organization_id": organization["id"],
+                "category_id": category["id"],
+                "unit_type_id": unit_type["id"],
+                "name": format!("Unidade {}", random_suffix()),
+                "activity_area": "Support",
+                "is_active": true
+            }),
+        )
+        .await;
+        ids.push(unit["id"].as_str().unwrap().parse().unwrap());
+    }
+    ids
+}
+
+async fn create_child_unit(app: &TestApp, parent_id: Uuid) -> Uuid {
+    sqlx::query_scalar(
+        "INSERT INTO organizational_units
+            (organization_id, parent_id, category_id, unit_type_id, name, activity_area, is_active)
+         SELECT organization_id, id, category_id, unit_type_id, $2, activity_area, true
+         FROM organizational_units WHERE id = $1
+         RETURNING id",
+    )
+    .bind(parent_id)
+    .bind(format!("Filha {}", random_suffix()))
+    .fetch_one(&app.db_auth)
+    .await
+    .expect("Falha ao criar unidade filha")
+}
+
+async fn create_warehouse_for_unit(app: &TestApp, unit_id: Uuid) -> Uuid {
+    let uid = Uuid::new_v4().simple().to_string();
+    let city_id: Uuid = sqlx::query_scalar("SELECT id FROM cities LIMIT 1")
+        .fetch_one(&app.db_auth)
+        .await
+        .expect("Nenhuma cidade cadastrada");
+
+    sqlx::query_scalar(
+        "INSERT INTO warehouses (name, code, warehouse_type, city_id, responsible_unit_id, is_active)
+         VALUES ($1, $2, 'SECTOR', $3, $4, true)
+         RETURNING id",
+    )
+    .bind(format!("Almoxarifado {}", &uid[..8]))
+    .bind(format!("WR{}", &uid[..14]))
+    .bind(city_id)
+    .bind(unit_id)
+    .fetch_one(&app.db_auth)
+    .await
+    .expect("Falha ao criar almoxarifado")
+}
+
+async fn restructure(app: &TestApp, body: Value) -> (StatusCode, Value) {
+    let response = app
+        .api
+        .post(RESTRUCTURINGS)
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .json(&body)
+        .await;
+    let status = response.status_code();
+    let body = if status.is_success() {
+        response.json()
+    } else {
+        Value::Null
+    };
+    (status, body)
+}
+
+fn merge_body(predecessors: &[Uuid], successor: Uuid) -> Value {
+    json!({
+        "kind": "MERGE",
+        "predecessor_ids": predecessors,
+        "successor_ids": [successor],
+        "effective_date": "2026-01-01",
+        "reason": "Decreto de reestruturação",
+        "documento_sei": "00000.000000/2026-00"
+    })
+}
+
+// ============================
+// TESTS
+// ============================
+
+#[tokio::test]
+async fn test_merge_migrates_references_and_deactivates_predecessors() {
+    let app = common::spawn_app().await;
+    let units = create_units(&app, 3).await;
+    let (old_a, old_b, new_unit) = (units[0], units[1], units[2]);
+    let warehouse_id = create_warehouse_for_unit(&app, old_a).await;
+    let child_id = create_child_unit(&app, old_b).await;
+
+    let (status, body) = restructure(&app, merge_body(&[old_a, old_b], new_unit)).await;
+
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["kind"], "MERGE");
+    assert_eq!(body["migrated"]["warehouses"], 1);
+    assert_eq!(body["migrated"]["child_units"], 1);
+    assert_eq!(body["lineage"].as_array().unwrap().len(), 2);
+
+    let responsible: Uuid =
+        sqlx::query_scalar("SELECT responsible_unit_id FROM warehouses WHERE id = $1")
+            .bind(warehouse_id)
+            .fetch_one(&app.db_auth)
+            .await
+            .unwrap();
+    assert_eq!(responsible, new_unit);
+
+    let parent: Option<Uuid> =
+        sqlx::query_scalar("SELECT parent_id FROM organizational_units WHERE id = $1")
+            .bind(child_id)
+            .fetch_one(&app.db_auth)
+            .await
+            .unwrap();
+    assert_eq!(parent, Some(new_unit));
+
+    let active: bool =
+        sqlx::query_scalar("SELECT is_active FROM organizational_units WHERE id = $1")
+            .bind(old_a)
+            .fetch_one(&app.db_auth)
+            .await
+            .unwrap();
+    assert!(!active);
+
+    // Já sucedida
+    let (status, _) = restructure(&app, merge_body(&[old_a], new_unit)).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+}
+
+#[tokio::test]
+async fn test_merge_moves_unit_roles() {
+    let app = common::spawn_app().await;
+    let units = create_units(&app, 2).await;
+    let (old_unit, new_unit) = (units[0], units[1]);
+    let (username, _, _) = common::create_test_user(&app.db_auth, &app.field_keyring)
+        .await
+        .unwrap();
+    let user_id: Uuid = sqlx::query_scalar("SELECT id FROM users WHERE username = $1")
+        .bind(&username)
+        .fetch_one(&app.db_auth)
+        .await
+        .unwrap();
+    admin_post(
+        &app,
+        "/api/admin/policies/unit-roles",
+        json!({
+            "user_id": user_id,
+            "role": "user",
+            "unit_id": old_unit,
+            "include_subtree": true
+        }),
+    )
+    .await;
+
+    let (status, body) = restructure(&app, merge_body(&[old_unit], new_unit)).await;
+
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["migrated"]["unit_roles"], 1);
+    let domains: Vec<String> =
+        sqlx::query_scalar("SELECT v2 FROM casbin_rule WHERE ptype = 'g2' AND v0 = $1")
+            .bind(user_id.to_string())
+            .fetch_all(&app.db_auth)
+            .await
+            .unwrap();
+    assert_eq!(domains, vec![format!("{}/*", new_unit)]);
+}
+
+#[tokio::test]
+async fn test_lineage_follows_split_to_primary_successor() {
+    let app = common::spawn_app().await;
+    let units = create_units(&app, 3).await;
+    let (old_unit, first, second) = (units[0], units[1], units[2]);
+    let warehouse_id = create_warehouse_for_unit(&app, old_unit).await;
+
+    let (status, body) = restructure(
+        &app,
+        json!({
+            "kind": "SPLIT",
+            "predecessor_ids": [old_unit],
+            "successor_ids": [first, second],
+            "primary_successor_id": first,
+            "effective_date": "2026-02-01",
+            "reason": "Cisão da coordenação",
+            "overrides": [
+                { "reference": "WAREHOUSE", "id": warehouse_id, "successor_id": second }
+            ]
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["migrated"]["warehouses"], 1);
+
+    let responsible: Uuid =
+        sqlx::query_scalar("SELECT responsible_unit_id FROM warehouses WHERE id = $1")
+            .bind(warehouse_id)
+            .fetch_one(&app.db_auth)
+            .await
+            .unwrap();
+    assert_eq!(responsible, second);
+
+    let response = app
+        .api
+        .get(&format!("{}/{}/lineage", UNITS, old_unit))
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    let lineage: Value = response.json();
+    assert_eq!(lineage["current_unit_id"], json!(first));
+    assert_eq!(lineage["successors"].as_array().unwrap().len(), 2);
+    assert!(lineage["predecessors"].as_array().unwrap().is_empty());
+
+    let response = app
+        .api
+        .get(&format!("{}/{}/lineage", UNITS, second))
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .await;
+    let lineage: Value = response.json();
+    assert_eq!(lineage["current_unit_id"], json!(second));
+    assert_eq!(
+        lineage["predecessors"][0]["predecessor_id"],
+        json!(old_unit)
+    );
+}
+
+#[tokio::test]
+async fn test_split_without_primary_returns_400() {
+    let app = common::spawn_app().await;
+    let units = create_units(&app, 3).await;
+
+    let (status, _) = restructure(
+        &app,
+        json!({
+            "kind": "SPLIT",
+            "predecessor_ids": [units[0]],
+            "successor_ids": [units[1], units[2]],
+            "effective_date": "2026-01-01",
+            "reason": "Cisão"
+        }),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_restructuring_unknown_unit_returns_404() {
+    let app = common::spawn_app().await;
+    let units = create_units(&app, 1).await;
+
+    let (status, _) = restructure(&app, merge_body(&[Uuid::new_v4()], units[0])).await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_restructurings_require_admin() {
+    let app = common::spawn_app().await;
+
+    let response = app
+        .api
+        .get(RESTRUCTURINGS)
+        .add_header("Authorization", format!("Bearer {}", app.user_token))
+        .await;
+
+    assert_eq!(response.status_code(), StatusCode::FORBIDDEN);
+}
@@ -0,0 +1,686 @@
+mod common;
+
+use common::{admin_post, create_vehicle_for_department, random_suffix, TestApp};
+use http::StatusCode;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+const UNIT_ROLES: &str = "/api/admin/policies/unit-roles";
+const WAREHOUSES: &str = "/api/admin/warehouses";
+
+// ============================
+// HELPERS
+// ============================
+
+/// Cria organização com a árvore: raiz → filha, e uma unidade irmã da raiz.
+/// Retorna (raiz, filha, irmã).
+async fn create_unit_tree(app: &TestApp) -> (Uuid, Uuid, Uuid) {
+    let organization = admin_post(
+        app,
+        "/api/admin/organizational/organizations",
+        json!({
+            "acronym": random_suffix(),
+            "name": format!("Org Escopo {}", random_suffix()),
+            "cnpj": format!("{:014}", rand::random::<u64>() % 100000000000000),
+            "ug_code": rand::random::<u32>() % 1000000,
+            "siorg_code": rand::random::<i32>() % 1000000,
+            "is_main_organization": false,
+            "is_active": true
+        }),
+    )
+    .await;
+    let category = admin_post(
+        app,
+        "/api/admin/organizational/unit-categories",
+        json!({
+            "name": format!("Categoria {}", random_suffix()),
+            "is_active": true,
+            "is_siorg_managed": false
+        }),
+    )
+    .await;
+    let unit_type = admin_post(
+        app,
+        "/api/admin/organizational/unit-types",
+        json!({
+            "code": random_suffix(),
+            "name": format!("Tipo {}", random_suffix()),
+            "is_active": true,
+            "is_siorg_managed": false
+        }),
+    )
+    .await;
+
+    let unit_body = |parent: Option<Uuid>| {
+        let mut body = json!({
+            "organization_id": organization["id"],
+            "category_id": category["id"],
+            "unit_type_id": unit_type["id"],
+            "name": format!("Unidade {}", random_suffix()),
+            "activity_area": "Support",
+            "is_active": true
+        });
+        if let Some(parent) = parent {
+            body["parent_id"] = json!(parent);
+        }
+        body
+    };
+
+    let root_body = unit_body(None);
+    let root = admin_post(app, "/api/admin/organizational/units", root_body).await;
+    let root_id: Uuid = root["id"].as_str().unwrap().parse().unwrap();
+
+    let child_body = unit_body(Some(root_id));
+    let child = admin_post(app, "/api/admin/organizational/units", child_body).await;
+
+    let sibling_body = unit_body(None);
+    let sibling = admin_post(app, "/api/admin/organizational/units", sibling_body).await;
+
+    (
+        root_id,
+        child["id"].as_str().unwrap().parse().unwrap(),
+        sibling["id"].as_str().unwrap().parse().unwrap(),
+    )
+}
+
+async fn create_warehouse_for_unit(app: &TestApp, unit_id: Uuid) -> Uuid {
+    let uid = Uuid::new_v4().simple().to_string();
+    let city_id: Uuid = sqlx::query_scalar("SELECT id FROM cities LIMIT 1")
+        .fetch_one(&app.db_auth)
+        .await
+        .expect("Nenhuma cidade cadastrada");
+
+    sqlx::query_scalar(
+        "INSERT INTO warehouses (name, code, warehouse_type, city_id, responsible_unit_id, is_active)
+         VALUES ($1, $2, 'SECTOR', $3, $4, true)
+         RETURNING id",
+    )
+    .bind(format!("Almoxarifado {}", &uid[..8]))
+    .bind(format!("WS{}", &uid[..14]))
+    .bind(city_id)
+    .bind(unit_id)
+    .fetch_one(&app.db_auth)
+    .await
+    .expect("Falha ao criar almoxarifado")
+}
+
+/// Usuário sem papel global com acesso a almoxarifados, e o nome de um
+/// papel que só lê almoxarifados
+async fn create_clerk(app: &TestApp) -> (Uuid, String, String) {
+    let (username, _, _) = common::create_test_user(&app.db_auth, &app.field_keyring)
+        .await
+        .unwrap();
+    let user_id: Uuid = sqlx::query_scalar("SELECT id FROM users WHERE username = $1")
+        .bind(&username)
+        .fetch_one(&app.db_auth)
+        .await
+        .unwrap();
+
+    let role = format!("role:almoxarife_{}", random_suffix());
+    for obj in [WAREHOUSES, "/api/admin/warehouses/*"] {
+        admin_post(
+            app,
+            "/api/admin/policies",
+            json!({ "sub": role, "obj": obj, "act": "GET" }),
+        )
+        .await;
+    }
+
+    (user_id, common::generate_test_token(user_id), role)
+}
+
+/// Concede ao papel as ações informadas
+async fn allow(app: &TestApp, role: &str, rules: &[(&str, &str)]) {
+    for (obj, act) in rules {
+        admin_post(
+            app,
+            "/api/admin/policies",
+            json!({ "sub": role, "obj": obj, "act": act }),
+        )
+        .await;
+    }
+}
+
+async fn assign(app: &TestApp, user_id: Uuid, role: &str, unit_id: Uuid, include_subtree: bool) {
+    let response = app
+        .api
+        .post(UNIT_ROLES)
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .json(&json!({
+            "user_id": user_id,
+            "role": role,
+            "unit_id": unit_id,
+            "include_subtree": include_subtree
+        }))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::CREATED, "{}", response.text());
+}
+
+async fn listed_warehouse_ids(app: &TestApp, token: &str) -> Vec<String> {
+    let response = app
+        .api
+        .get(&format!("{}?limit=100&offset=0", WAREHOUSES))
+        .add_header("Authorization", format!("Bearer {}", token))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK, "{}", response.text());
+    let body: Value = response.json();
+    body["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|w| w["id"].as_str().unwrap().to_string())
+        .collect()
+}
+
+// ============================
+// TESTES
+// ============================
+
+#[tokio::test]
+async fn test_user_without_unit_role_is_denied() {
+    let app = common::spawn_app().await;
+    let (_, token, _) = create_clerk(&app).await;
+
+    let response = app
+        .api
+        .get(WAREHOUSES)
+        .add_header("Authorization", format!("Bearer {}", token))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn test_unit_role_limits_list_to_its_unit() {
+    let app = common::spawn_app().await;
+    let (root, child, sibling) = create_unit_tree(&app).await;
+    let root_wh = create_warehouse_for_unit(&app, root).await;
+    let child_wh = create_warehouse_for_unit(&app, child).await;
+    let sibling_wh = create_warehouse_for_unit(&app, sibling).await;
+    let (user_id, token, role) = create_clerk(&app).await;
+
+    assign(&app, user_id, &role, root, false).await;
+
+    let ids = listed_warehouse_ids(&app, &token).await;
+    assert_eq!(ids, vec![root_wh.to_string()]);
+    assert!(!ids.contains(&child_wh.to_string()));
+    assert!(!ids.contains(&sibling_wh.to_string()));
+}
+
+#[tokio::test]
+async fn test_unit_role_with_subtree_includes_descendants() {
+    let app = common::spawn_app().await;
+    let (root, child, sibling) = create_unit_tree(&app).await;
+    let root_wh = create_warehouse_for_unit(&app, root).await;
+    let child_wh = create_warehouse_for_unit(&app, child).await;
+    let sibling_wh = create_warehouse_for_unit(&app, sibling).await;
+    let (user_id, token, role) = create_clerk(&app).await;
+
+    assign(&app, user_id, &role, root, true).await;
+
+    let ids = listed_warehouse_ids(&app, &token).await;
+    assert_eq!(ids.len(), 2);
+    assert!(ids.contains(&root_wh.to_string()));
+    assert!(ids.contains(&child_wh.to_string()));
+
+    let response = app
+        .api
+        .get(&format!("{}/{}", WAREHOUSES, child_wh))
+        .add_header("Authorization", format!("Bearer {}", token))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+
+    // Fora do escopo o almoxarifado não existe para o usuário
+    let response = app
+        .api
+        .get(&format!("{}/{}", WAREHOUSES, sibling_wh))
+        .add_header("Authorization", format!("Bearer {}", token))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_unit_role_only_grants_its_role_permissions() {
+    let app = common::spawn_app().await;
+    let (root, _, _) = create_unit_tree(&app).await;
+    let (user_id, token, role) = create_clerk(&app).await;
+
+    assign(&app, user_id, &role, root, true).await;
+
+    // O papel só lê almoxarifados
+    let response = app
+        .api
+        .get("/api/admin/requisitions")
+        .add_header("Authorization", format!("Bearer {}", token))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn test_global_role_still_sees_every_unit() {
+    let app = common::spawn_app().await;
+    let (root, _, sibling) = create_unit_tree(&app).await;
+    let root_wh = create_warehouse_for_unit(&app, root).await;
+    let sibling_wh = create_warehouse_for_unit(&app, sibling).await;
+
+    for id in [root_wh, sibling_wh] {
+        let response = app
+            .api
+            .get(&format!("{}/{}", WAREHOUSES, id))
+            .add_header("Authorization", format!("Bearer {}", app.admin_token))
+            .await;
+        assert_eq!(response.status_code(), StatusCode::OK);
+    }
+}
+
+#[tokio::test]
+async fn test_list_and_revoke_unit_role() {
+    let app = common::spawn_app().await;
+    let (root, _, _) = create_unit_tree(&app).await;
+    let root_wh = create_warehouse_for_unit(&app, root).await;
+    let (user_id, token, role) = create_clerk(&app).await;
+
+    assign(&app, user_id, &role, root, true).await;
+
+    let response = app
+        .api
+        .get(&format!("{}?user_id={}", UNIT_ROLES, user_id))
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    let assignments: Value = response.json();
+    assert_eq!(
+        assignments,
+        json!([{
+            "user_id": user_id.to_string(),
+            "role": role,
+            "unit_id": root,
+            "include_subtree": true
+        }])
+    );
+    assert_eq!(listed_warehouse_ids(&app, &token).await, vec![root_wh.to_string()]);
+
+    let response = app
+        .api
+        .delete(UNIT_ROLES)
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .json(&json!({
+            "user_id": user_id,
+            "role": role,
+            "unit_id": root,
+            "include_subtree": true
+        }))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::NO_CONTENT);
+
+    let response = app
+        .api
+        .get(WAREHOUSES)
+        .add_header("Authorization", format!("Bearer {}", token))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn test_assign_unit_role_validates_user_and_unit() {
+    let app = common::spawn_app().await;
+    let (root, _, _) = create_unit_tree(&app).await;
+    let (user_id, _, role) = create_clerk(&app).await;
+
+    for (user, unit) in [(Uuid::new_v4(), root), (user_id, Uuid::new_v4())] {
+        let response = app
+            .api
+            .post(UNIT_ROLES)
+            .add_header("Authorization", format!("Bearer {}", app.admin_token))
+            .json(&json!({ "user_id": user, "role": role, "unit_id": unit }))
+            .await;
+        assert_eq!(response.status_code(), StatusCode::NOT_FOUND);
+    }
+}
+
+#[tokio::test]
+async fn test_unit_roles_require_admin() {
+    let app = common::spawn_app().await;
+
+    let response = app
+        .api
+        .get(UNIT_ROLES)
+        .add_header("Authorization", format!("Bearer {}", app.user_token))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn test_warehouse_changes_outside_scope_are_forbidden() {
+    let app = common::spawn_app().await;
+    let (root, _, sibling) = create_unit_tree(&app).await;
+    let root_wh = create_warehouse_for_unit(&app, root).await;
+    let sibling_wh = create_warehouse_for_unit(&app, sibling).await;
+    let (user_id, token, role) = create_clerk(&app).await;
+    allow(
+        &app,
+        &role,
+        &[
+            ("/api/admin/warehouses/*", "PUT"),
+            ("/api/admin/warehouses/*", "DELETE"),
+        ],
+    )
+    .await;
+    assign(&app, user_id, &role, root, false).await;
+    let auth = format!("Bearer {}", token);
+
+    let response = app
+        .api
+        .put(&format!("{}/{}", WAREHOUSES, sibling_wh))
+        .add_header("Authorization", &auth)
+        .json(&json!({ "name": format!("Renomeado {}", random_suffix()) }))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::FORBIDDEN);
+
+    let response = app
+        .api
+        .delete(&format!("{}/{}", WAREHOUSES, sibling_wh))
+        .add_header("Authorization", &auth)
+        .await;
+    assert_eq!(response.status_code(), StatusCode::FORBIDDEN);
+
+    for nested in ["stocks", "movements", "disposal-requests"] {
+        let response = app
+            .api
+            .get(&format!("{}/{}/{}", WAREHOUSES, sibling_wh, nested))
+            .add_header("Authorization", &auth)
+            .await;
+        assert_eq!(response.status_code(), StatusCode::FORBIDDEN, "{}", nested);
+    }
+
+    // Não pode entregar o próprio almoxarifado a uma unidade fora do escopo
+    let response = app
+        .api
+        .put(&format!("{}/{}", WAREHOUSES, root_wh))
+        .add_header("Authorization", &auth)
+        .json(&json!({ "responsible_unit_id": sibling }))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::FORBIDDEN);
+
+    let response = app
+        .api
+        .put(&format!("{}/{}", WAREHOUSES, root_wh))
+        .add_header("Authorization", &auth)
+        .json(&json!({ "name": format!("Renomeado {}", random_suffix()) }))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK, "{}", response.text());
+
+    let response = app
+        .api
+        .get(&format!("{}/{}/stocks", WAREHOUSES, root_wh))
+        .add_header("Authorization", &auth)
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK, "{}", response.text());
+}
+
+#[tokio::test]
+async fn test_vehicle_changes_outside_scope_are_forbidden() {
+    let app = common::spawn_app().await;
+    let (root, child, sibling) = create_unit_tree(&app).await;
+    let root_vehicle = create_vehicle_for_department(&app, root).await;
+    let sibling_vehicle = create_vehicle_for_department(&app, sibling).await;
+    let (user_id, token, role) = create_clerk(&app).await;
+    allow(
+        &app,
+        &role,
+        &[
+            ("/api/admin/fleet/vehicles/*", "PUT"),
+            ("/api/admin/fleet/vehicles/*", "POST"),
+            ("/api/admin/fleet/vehicles/*", "GET"),
+        ],
+    )
+    .await;
+    assign(&app, user_id, &role, root, true).await;
+    let auth = format!("Bearer {}", token);
+    let vehicles = "/api/admin/fleet/vehicles";
+    let transfer = |target: Uuid| {
+        json!({
+            "target_dept_id": target,
+            "effective_date": "2026-01-15",
+            "reason": "Redistribuição da frota"
+        })
+    };
+
+    let response = app
+        .api
+        .put(&format!("{}/{}", vehicles, sibling_vehicle))
+        .add_header("Authorization", &auth)
+        .json(&json!({ "notes": "fora do escopo", "version": 1 }))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::FORBIDDEN);
+
+    let response = app
+        .api
+        .put(&format!("{}/{}", vehicles, root_vehicle))
+        .add_header("Authorization", &auth)
+        .json(&json!({ "department_id": sibling, "version": 1 }))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::FORBIDDEN);
+
+    let response = app
+        .api
+        .post(&format!("{}/{}/transfers", vehicles, sibling_vehicle))
+        .add_header("Authorization", &auth)
+        .json(&transfer(root))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::FORBIDDEN);
+
+    let response = app
+        .api
+        .post(&format!("{}/{}/transfers", vehicles, root_vehicle))
+        .add_header("Authorization", &auth)
+        .json(&transfer(sibling))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::FORBIDDEN);
+
+    let response = app
+        .api
+        .get(&format!("{}/{}/transfers", vehicles, sibling_vehicle))
+        .add_header("Authorization", &auth)
+        .await;
+    assert_eq!(response.status_code(), StatusCode::FORBIDDEN);
+
+    // Dentro da subárvore a transferência é permitida
+    let response = app
+        .api
+        .post(&format!("{}/{}/transfers", vehicles, root_vehicle))
+        .add_header("Authorization", &auth)
+        .json(&transfer(child))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::CREATED, "{}", response.text());
+}
+
+#[tokio::test]
+async fn test_vehicle_reads_outside_scope_are_not_found() {
+    let app = common::spawn_app().await;
+    let (root, _, sibling) = create_unit_tree(&app).await;
+    let root_vehicle = create_vehicle_for_department(&app, root).await;
+    let sibling_vehicle = create_vehicle_for_department(&app, sibling).await;
+    let (user_id, token, role) = create_clerk(&app).await;
+    allow(
+        &app,
+        &role,
+        &[
+            ("/api/admin/fleet/vehicles/*", "GET"),
+            ("/api/admin/fleet/vehicles/*", "PUT"),
+        ],
+    )
+    .await;
+    assign(&app, user_id, &role, root, true).await;
+    let auth = format!("Bearer {}", token);
+    let vehicles = "/api/admin/fleet/vehicles";
+
+    let response = app
+        .api
+        .get(&format!("{}/{}/history", vehicles, sibling_vehicle))
+        .add_header("Authorization", &auth)
+        .await;
+    assert_eq!(response.status_code(), StatusCode::NOT_FOUND);
+
+    let response = app
+        .api
+        .get(&format!("{}/{}/history", vehicles, root_vehicle))
+        .add_header("Authorization", &auth)
+        .await;
+    assert_eq!(
+        response.status_code(),
+        StatusCode::OK,
+        "{}",
+        response.text()
+    );
+
+    let plate: String = sqlx::query_scalar("SELECT license_plate FROM vehicles WHERE id = $1")
+        .bind(sibling_vehicle)
+        .fetch_one(&app.db_auth)
+        .await
+        .unwrap();
+    let response = app
+        .api
+        .get(&format!("{}/search?q={}", vehicles, plate))
+        .add_header("Authorization", &auth)
+        .await;
+    assert_eq!(
+        response.status_code(),
+        StatusCode::OK,
+        "{}",
+        response.text()
+    );
+    assert_eq!(
+        response.json::<Value>()["vehicles"]
+            .as_array()
+            .map(Vec::len),
+        Some(0)
+    );
+
+    let response = app
+        .api
+        .put(&format!(
+            "{}/{}/operational-status",
+            vehicles, sibling_vehicle
+        ))
+        .add_header("Authorization", &auth)
+        .json(&json!({ "operational_status": "MANUTENCAO", "version": 1 }))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn test_requisition_changes_outside_scope_are_not_found() {
+    let app = common::spawn_app().await;
+    let (root, _, sibling) = create_unit_tree(&app).await;
+    let warehouse_id = create_warehouse_for_unit(&app, sibling).await;
+    let (user_id, token, role) = create_clerk(&app).await;
+    allow(
+        &app,
+        &role,
+        &[
+            ("/api/admin/requisitions/*", "POST"),
+            ("/api/admin/requisitions/*", "GET"),
+        ],
+    )
+    .await;
+    assign(&app, user_id, &role, root, true).await;
+    let auth = format!("Bearer {}", token);
+
+    let uid = Uuid::new_v4().simple().to_string();
+    let requisition_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO requisitions (
+            requisition_number, warehouse_id, destination_unit_id, requester_id,
+            status, priority, total_value, request_date
+         )
+         VALUES ($1, $2, $3, $4, 'PENDING'::requisition_status_enum,
+                 'NORMAL'::requisition_priority_enum, 0, CURRENT_DATE)
+         RETURNING id",
+    )
+    .bind(format!("REQ{}", &uid[..12]))
+    .bind(warehouse_id)
+    .bind(sibling)
+    .bind(user_id)
+    .fetch_one(&app.db_auth)
+    .await
+    .unwrap();
+
+    let response = app
+        .api
+        .post(&format!(
+            "/api/admin/requisitions/{}/cancel",
+            requisition_id
+        ))
+        .add_header("Authorization", &auth)
+        .json(&json!({ "reason": "fora do escopo" }))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::NOT_FOUND);
+
+    let response = app
+        .api
+        .get(&format!("/api/admin/requisitions/{}/items", requisition_id))
+        .add_header("Authorization", &auth)
+        .await;
+    assert_eq!(response.status_code(), StatusCode::NOT_FOUND);
+
+    let status: String = sqlx::query_scalar("SELECT status::TEXT FROM requisitions WHERE id = $1")
+        .bind(requisition_id)
+        .fetch_one(&app.db_auth)
+        .await
+        .unwrap();
+    assert_eq!(status, "PENDING");
+}
+
+#[tokio::test]
+async fn test_trip_changes_outside_scope_are_not_found() {
+    let app = common::spawn_app().await;
+    let (root, _, sibling) = create_unit_tree(&app).await;
+    let sibling_vehicle = create_vehicle_for_department(&app, sibling).await;
+    let (user_id, token, role) = create_clerk(&app).await;
+    allow(
+        &app,
+        &role,
+        &[
+            ("/api/admin/trips/*", "PUT"),
+            ("/api/admin/trips/*", "POST"),
+        ],
+    )
+    .await;
+    assign(&app, user_id, &role, root, true).await;
+    let auth = format!("Bearer {}", token);
+
+    let trip = admin_post(
+        &app,
+        "/api/admin/trips",
+        json!({
+            "vehicle_id": sibling_vehicle,
+            "destination": "Campus Sede",
+            "purpose": "Reunião",
+            "planned_departure": "2030-03-10T08:00:00Z",
+            "planned_return": "2030-03-10T18:00:00Z"
+        }),
+    )
+    .await;
+    let trip_id = trip["id"].as_str().unwrap();
+
+    let response = app
+        .api
+        .put(&format!("/api/admin/trips/{}/cancel", trip_id))
+        .add_header("Authorization", &auth)
+        .json(&json!({ "cancellation_reason": "fora do escopo", "version": trip["version"] }))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::NOT_FOUND);
+
+    let response = app
+        .api
+        .post(&format!("/api/admin/trips/{}/suggestions", trip_id))
+        .add_header("Authorization", &auth)
+        .await;
+    assert_eq!(response.status_code(), StatusCode::NOT_FOUND);
+
+    let response = app
+        .api
+        .get(&format!("/api/admin/trips/{}", trip_id))
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .await;
+    assert_eq!(response.json::<Value>()["status"], trip["status"]);
+}
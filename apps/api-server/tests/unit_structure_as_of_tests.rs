@@ -0,0 +1,270 @@
+mod common;
+
+use common::{admin_post, random_suffix, TestApp};
+use http::StatusCode;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+const UNITS: &str = "/api/admin/organizational/units";
+const REPORTS: &str = "/api/admin/reports";
+
+// ============================
+// HELPERS
+// ============================
+
+async fn admin_get(app: &TestApp, path: &str) -> (StatusCode, Value) {
+    let response = app
+        .api
+        .get(path)
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .await;
+    let status = response.status_code();
+    let body = if status.is_success() {
+        response.json()
+    } else {
+        Value::Null
+    };
+    (status, body)
+}
+
+/// Organização com uma unidade raiz e uma filha; retorna (org, raiz, filha)
+async fn create_structure(app: &TestApp) -> (Uuid, Uuid, Uuid) {
+    let organization = admin_post(
+        app,
+        "/api/admin/organizational/organizations",
+        json!({
+            "acronym": random_suffix(),
+            "name": format!("Org Histórica {}", random_suffix()),
+            "cnpj": format!("{:014}", rand::random::<u64>() % 100000000000000),
+            "ug_code": rand::random::<u32>() % 1000000,
+            "siorg_code": rand::random::<i32>() % 1000000,
+            "is_main_organization": false,
+            "is_active": true
+        }),
+    )
+    .await;
+    let category = admin_post(
+        app,
+        "/api/admin/organizational/unit-categories",
+        json!({
+            "name": format!("Categoria {}", random_suffix()),
+            "is_active": true,
+            "is_siorg_managed": false
+        }),
+    )
+    .await;
+    let unit_type = admin_post(
+        app,
+        "/api/admin/organizational/unit-types",
+        json!({
+            "code": random_suffix(),
+            "name": format!("Tipo {}", random_suffix()),
+            "is_active": true,
+            "is_siorg_managed": false
+        }),
+    )
+    .await;
+
+    let root = admin_post(
+        app,
+        UNITS,
+        json!({
+            "organization_id": organization["id"],
+            "category_id": category["id"],
+            "unit_type_id": unit_type["id"],
+            "name": format!("Reitoria {}", random_suffix()),
+            "activity_area": "Support",
+            "is_active": true
+        }),
+    )
+    .await;
+    let child = admin_post(
+        app,
+        UNITS,
+        json!({
+            "organization_id": organization["id"],
+            "parent_id": root["id"],
+            "category_id": category["id"],
+            "unit_type_id": unit_type["id"],
+            "name": format!("Coordenação {}", random_suffix()),
+            "activity_area": "Support",
+            "is_active": true
+        }),
+    )
+    .await;
+
+    let id = |v: &Value| v["id"].as_str().unwrap().parse().unwrap();
+    (id(&organization), id(&root), id(&child))
+}
+
+/// Recua o início das versões atuais para 2020 e registra a filha, antes
+/// disso, como unidade raiz com outro nome
+async fn backdate_with_old_child_version(app: &TestApp, root: Uuid, child: Uuid) {
+    sqlx::query(
+        "UPDATE organizational_unit_versions SET valid_from = '2020-01-01'
+         WHERE unit_id = ANY($1) AND valid_to IS NULL",
+    )
+    .bind(vec![root, child])
+    .execute(&app.db_auth)
+    .await
+    .unwrap();
+
+    sqlx::query(
+        "INSERT INTO organizational_unit_versions
+            (unit_id, valid_from, valid_to, name, parent_id, is_active)
+         VALUES ($1, NULL, '2020-01-01', 'Divisão Antiga', NULL, true)",
+    )
+    .bind(child)
+    .execute(&app.db_auth)
+    .await
+    .unwrap();
+}
+
+// ============================
+// TESTS
+// ============================
+
+#[tokio::test]
+async fn test_path_and_children_follow_structure_as_of_date() {
+    let app = common::spawn_app().await;
+    let (_, root, child) = create_structure(&app).await;
+    backdate_with_old_child_version(&app, root, child).await;
+
+    let (status, path) =
+        admin_get(&app, &format!("{}/{}/path?as_of=2021-06-30", UNITS, child)).await;
+    assert_eq!(status, StatusCode::OK);
+    let path = path.as_array().unwrap();
+    assert_eq!(path.len(), 2);
+    assert_eq!(path[0]["id"], json!(root));
+
+    let (status, path) =
+        admin_get(&app, &format!("{}/{}/path?as_of=2019-06-30", UNITS, child)).await;
+    assert_eq!(status, StatusCode::OK);
+    let path = path.as_array().unwrap();
+    assert_eq!(path.len(), 1);
+    assert_eq!(path[0]["name"], "Divisão Antiga");
+    assert_eq!(path[0]["level"], 1);
+
+    let (_, children) = admin_get(
+        &app,
+        &format!("{}/{}/children?as_of=2021-06-30", UNITS, root),
+    )
+    .await;
+    assert_eq!(children.as_array().unwrap().len(), 1);
+    let (_, children) = admin_get(
+        &app,
+        &format!("{}/{}/children?as_of=2019-06-30", UNITS, root),
+    )
+    .await;
+    assert!(children.as_array().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_tree_as_of_date_is_filtered_by_organization() {
+    let app = common::spawn_app().await;
+    let (org, root, child) = create_structure(&app).await;
+    backdate_with_old_child_version(&app, root, child).await;
+
+    let (status, tree) = admin_get(
+        &app,
+        &format!("{}/tree?organization_id={}&as_of=2019-06-30", UNITS, org),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let tree = tree.as_array().unwrap();
+    assert_eq!(tree.len(), 1);
+    assert_eq!(tree[0]["id"], json!(child));
+    assert_eq!(tree[0]["name"], "Divisão Antiga");
+
+    let (_, tree) = admin_get(&app, &format!("{}/tree?organization_id={}", UNITS, org)).await;
+    let tree = tree.as_array().unwrap();
+    assert_eq!(tree.len(), 1);
+    assert_eq!(tree[0]["id"], json!(root));
+    assert_eq!(tree[0]["children"][0]["id"], json!(child));
+}
+
+#[tokio::test]
+async fn test_path_for_unit_missing_on_date_returns_404() {
+    let app = common::spawn_app().await;
+    let (_, _, child) = create_structure(&app).await;
+
+    let (status, _) = admin_get(&app, &format!("{}/{}/path?as_of=2000-01-01", UNITS, child)).await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_rename_closes_current_version() {
+    let app = common::spawn_app().await;
+    let (_, root, child) = create_structure(&app).await;
+    backdate_with_old_child_version(&app, root, child).await;
+
+    sqlx::query("UPDATE organizational_units SET name = 'Reitoria Renomeada' WHERE id = $1")
+        .bind(root)
+        .execute(&app.db_auth)
+        .await
+        .unwrap();
+
+    let versions: Vec<(String, bool)> = sqlx::query_as(
+        "SELECT name, valid_to IS NULL FROM organizational_unit_versions
+         WHERE unit_id = $1 ORDER BY valid_from",
+    )
+    .bind(root)
+    .fetch_all(&app.db_auth)
+    .await
+    .unwrap();
+
+    assert_eq!(versions.len(), 2);
+    assert!(!versions[0].1);
+    assert_eq!(versions[1], ("Reitoria Renomeada".to_string(), true));
+}
+
+#[tokio::test]
+async fn test_cost_reports_by_unit_for_organization_without_costs() {
+    let app = common::spawn_app().await;
+    let (org, _, _) = create_structure(&app).await;
+
+    for report in ["requisition-cost-by-unit", "fleet-cost-by-unit"] {
+        let (status, body) = admin_get(
+            &app,
+            &format!(
+                "{}/{}?organization_id={}&start_date=2024-01-01&end_date=2024-12-31",
+                REPORTS, report, org
+            ),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["as_of"], "2024-12-31");
+        assert_eq!(body["total_count"], 0);
+        assert!(body["units"].as_array().unwrap().is_empty());
+    }
+}
+
+#[tokio::test]
+async fn test_cost_report_with_inverted_period_returns_400() {
+    let app = common::spawn_app().await;
+
+    let (status, _) = admin_get(
+        &app,
+        &format!(
+            "{}/fleet-cost-by-unit?start_date=2024-12-31&end_date=2024-01-01",
+            REPORTS
+        ),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_cost_reports_by_unit_require_admin() {
+    let app = common::spawn_app().await;
+
+    let response = app
+        .api
+        .get(&format!("{}/requisition-cost-by-unit", REPORTS))
+        .add_header("Authorization", format!("Bearer {}", app.user_token))
+        .await;
+
+    assert_eq!(response.status_code(), StatusCode::FORBIDDEN);
+}
@@ -0,0 +1,318 @@
+mod common;
+
+use chrono::{DateTime, Duration, Utc};
+use common::{admin_post, create_driver, create_vehicle, random_suffix, TestApp};
+use http::StatusCode;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+const BASE: &str = "/api/admin/vehicle-fines";
+
+// ============================
+// HELPERS
+// ============================
+
+async fn admin_get(app: &TestApp, path: &str) -> Value {
+    let response = app
+        .api
+        .get(path)
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .await;
+    assert_eq!(
+        response.status_code(),
+        StatusCode::OK,
+        "GET {} -> {}",
+        path,
+        response.text()
+    );
+    response.json()
+}
+
+/// Órgão autuador
+async fn create_authority(app: &TestApp) -> Uuid {
+    sqlx::query_scalar(
+        "INSERT INTO suppliers (legal_name, document_number) VALUES ($1, $2) RETURNING id",
+    )
+    .bind(format!("Detran {}", random_suffix()))
+    .bind(format!("{:014}", rand::random::<u64>() % 100000000000000))
+    .fetch_one(&app.db_auth)
+    .await
+    .expect("Falha ao criar órgão autuador")
+}
+
+async fn create_fine_type(app: &TestApp, points: i32) -> Value {
+    admin_post(
+        app,
+        &format!("{}/fine-types", BASE),
+        json!({
+            "code": format!("T{}", random_suffix()),
+            "description": "Transitar em velocidade superior à máxima permitida",
+            "severity": "SERIOUS",
+            "points": points,
+            "fine_amount": "195.23"
+        }),
+    )
+    .await
+}
+
+async fn create_fine(
+    app: &TestApp,
+    vehicle_id: Uuid,
+    authority_id: Uuid,
+    fine_type: &Value,
+    fine_date: DateTime<Utc>,
+) -> Value {
+    admin_post(
+        app,
+        &format!("{}/fines", BASE),
+        json!({
+            "vehicle_id": vehicle_id,
+            "fine_type_id": fine_type["id"],
+            "supplier_id": authority_id,
+            "auto_number": format!("AI{}", random_suffix()),
+            "fine_date": fine_date,
+            "notification_date": fine_date + Duration::days(5),
+            "due_date": Utc::now() + Duration::days(30),
+            "fine_amount": fine_type["fine_amount"]
+        }),
+    )
+    .await
+}
+
+/// Cadastra uma multa do mês passado e indica o condutor
+async fn identify_fine(
+    app: &TestApp,
+    vehicle_id: Uuid,
+    authority_id: Uuid,
+    fine_type: &Value,
+    driver_id: Uuid,
+) -> String {
+    let fine_date = Utc::now() - Duration::days(30);
+    let fine = create_fine(app, vehicle_id, authority_id, fine_type, fine_date).await;
+    let id = fine["id"].as_str().unwrap().to_string();
+
+    let identified = admin_post(
+        app,
+        &format!("{}/fines/{}/identify-driver", BASE, id),
+        json!({ "driver_id": driver_id }),
+    )
+    .await;
+    assert_eq!(identified["status"], "DRIVER_IDENTIFIED");
+    id
+}
+
+async fn driver_alerts(app: &TestApp, driver_id: Uuid, include_acknowledged: bool) -> Vec<Value> {
+    let alerts = admin_get(
+        app,
+        &format!(
+            "{}/driver-points/alerts?include_acknowledged={}",
+            BASE, include_acknowledged
+        ),
+    )
+    .await;
+    alerts
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|a| a["driver_id"] == json!(driver_id))
+        .cloned()
+        .collect()
+}
+
+/// Viagem com check-out registrado e ainda sem check-in
+async fn open_trip(
+    app: &TestApp,
+    vehicle_id: Uuid,
+    driver_id: Uuid,
+    checkout_em: DateTime<Utc>,
+    planned_return: Option<DateTime<Utc>>,
+) -> Uuid {
+    sqlx::query_scalar(
+        "INSERT INTO vehicle_trips (vehicle_id, driver_id, destino, finalidade, status,
+                                    data_saida_prevista, data_retorno_prevista, checkout_em)
+         VALUES ($1, $2, 'Campus Sede', 'Reunião', 'EM_CURSO', $3, $4, $3)
+         RETURNING id",
+    )
+    .bind(vehicle_id)
+    .bind(driver_id)
+    .bind(checkout_em)
+    .bind(planned_return)
+    .fetch_one(&app.db_auth)
+    .await
+    .expect("Falha ao criar viagem")
+}
+
+// ============================
+// TRIP DRIVER TESTS
+// ============================
+
+#[tokio::test]
+async fn test_open_trip_only_covers_fines_until_planned_return() {
+    let app = common::spawn_app().await;
+    let (vehicle_id, authority_id) = (create_vehicle(&app).await, create_authority(&app).await);
+    let driver_id = create_driver(&app).await;
+    let fine_type = create_fine_type(&app, 4).await;
+
+    let checkout = Utc::now() - Duration::days(20);
+    let trip_id = open_trip(
+        &app,
+        vehicle_id,
+        driver_id,
+        checkout,
+        Some(checkout + Duration::days(2)),
+    )
+    .await;
+
+    // Dentro do período previsto: condutor da viagem
+    let fine = create_fine(
+        &app,
+        vehicle_id,
+        authority_id,
+        &fine_type,
+        checkout + Duration::days(1),
+    )
+    .await;
+    assert_eq!(fine["driver_id"], json!(driver_id));
+    assert_eq!(fine["trip_id"], json!(trip_id));
+
+    // Depois do retorno previsto, o check-in ausente não estende a viagem
+    let fine = create_fine(
+        &app,
+        vehicle_id,
+        authority_id,
+        &fine_type,
+        checkout + Duration::days(5),
+    )
+    .await;
+    assert!(fine["driver_id"].is_null(), "{}", fine);
+
+    // Viagem sem check-in e sem retorno previsto não indica ninguém
+    let other_vehicle = create_vehicle(&app).await;
+    let checkout = Utc::now() - Duration::days(10);
+    open_trip(&app, other_vehicle, driver_id, checkout, None).await;
+    let fine = create_fine(
+        &app,
+        other_vehicle,
+        authority_id,
+        &fine_type,
+        checkout + Duration::hours(1),
+    )
+    .await;
+    assert!(fine["driver_id"].is_null(), "{}", fine);
+}
+
+// ============================
+// DRIVER POINTS ALERT TESTS
+// ============================
+
+#[tokio::test]
+async fn test_identifying_driver_records_points_alerts() {
+    let app = common::spawn_app().await;
+    let (vehicle_id, authority_id) = (create_vehicle(&app).await, create_authority(&app).await);
+    let driver_id = create_driver(&app).await;
+    let fine_type = create_fine_type(&app, 15).await;
+
+    // 15 de 40 pontos: abaixo do alerta
+    identify_fine(&app, vehicle_id, authority_id, &fine_type, driver_id).await;
+    assert!(driver_alerts(&app, driver_id, true).await.is_empty());
+
+    // 30 de 40 pontos: alerta
+    let second = identify_fine(&app, vehicle_id, authority_id, &fine_type, driver_id).await;
+    let alerts = driver_alerts(&app, driver_id, false).await;
+    assert_eq!(alerts.len(), 1);
+    assert_eq!(alerts[0]["alert_level"], "WARNING");
+    assert_eq!(alerts[0]["total_points"], 30);
+    assert_eq!(alerts[0]["suspension_threshold"], 40);
+    assert_eq!(alerts[0]["vehicle_fine_id"], json!(second));
+
+    // 45 pontos: suspensão, que encerra o alerta de WARNING
+    identify_fine(&app, vehicle_id, authority_id, &fine_type, driver_id).await;
+    let alerts = driver_alerts(&app, driver_id, false).await;
+    assert_eq!(alerts.len(), 1);
+    assert_eq!(alerts[0]["alert_level"], "SUSPENSION");
+    let warning: Vec<Value> = driver_alerts(&app, driver_id, true)
+        .await
+        .into_iter()
+        .filter(|a| a["alert_level"] == "WARNING")
+        .collect();
+    assert_eq!(warning.len(), 1);
+    assert_eq!(warning[0]["superseded_by"], alerts[0]["id"]);
+    assert!(warning[0]["acknowledged_at"].is_null());
+
+    let response = app
+        .api
+        .post(&format!(
+            "{}/driver-points/alerts/{}/acknowledge",
+            BASE,
+            warning[0]["id"].as_str().unwrap()
+        ))
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::BAD_REQUEST);
+
+    // Nova multa atualiza o alerta aberto em vez de duplicá-lo
+    let fourth = identify_fine(&app, vehicle_id, authority_id, &fine_type, driver_id).await;
+    let suspension: Vec<Value> = driver_alerts(&app, driver_id, false)
+        .await
+        .into_iter()
+        .filter(|a| a["alert_level"] == "SUSPENSION")
+        .collect();
+    assert_eq!(suspension.len(), 1);
+    assert_eq!(suspension[0]["total_points"], 60);
+    assert_eq!(suspension[0]["vehicle_fine_id"], json!(fourth));
+}
+
+#[tokio::test]
+async fn test_acknowledge_driver_points_alert() {
+    let app = common::spawn_app().await;
+    let (vehicle_id, authority_id) = (create_vehicle(&app).await, create_authority(&app).await);
+    let driver_id = create_driver(&app).await;
+    let fine_type = create_fine_type(&app, 40).await;
+
+    identify_fine(&app, vehicle_id, authority_id, &fine_type, driver_id).await;
+    let alerts = driver_alerts(&app, driver_id, false).await;
+    assert_eq!(alerts.len(), 1);
+    assert_eq!(alerts[0]["alert_level"], "SUSPENSION");
+    let alert_id = alerts[0]["id"].as_str().unwrap();
+
+    let acknowledged = admin_post(
+        &app,
+        &format!("{}/driver-points/alerts/{}/acknowledge", BASE, alert_id),
+        json!({}),
+    )
+    .await;
+    assert!(acknowledged["acknowledged_at"].is_string());
+    assert!(acknowledged["acknowledged_by"].is_string());
+
+    // Sai da lista de abertos, mas continua no histórico
+    assert!(driver_alerts(&app, driver_id, false).await.is_empty());
+    assert_eq!(driver_alerts(&app, driver_id, true).await.len(), 1);
+
+    let response = app
+        .api
+        .post(&format!(
+            "{}/driver-points/alerts/{}/acknowledge",
+            BASE, alert_id
+        ))
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::BAD_REQUEST);
+
+    let response = app
+        .api
+        .post(&format!(
+            "{}/driver-points/alerts/{}/acknowledge",
+            BASE,
+            Uuid::new_v4()
+        ))
+        .add_header("Authorization", format!("Bearer {}", app.admin_token))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::NOT_FOUND);
+
+    // Depois da ciência, nova multa abre outro alerta
+    identify_fine(&app, vehicle_id, authority_id, &fine_type, driver_id).await;
+    let open = driver_alerts(&app, driver_id, false).await;
+    assert_eq!(open.len(), 1);
+    assert_eq!(open[0]["total_points"], 80);
+    assert_ne!(open[0]["id"], json!(alert_id));
+}
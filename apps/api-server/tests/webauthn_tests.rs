@@ -0,0 +1,158 @@
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+mod common;
+
+async fn create_user_token(app: &common::TestApp) -> (Uuid, String) {
+    let (username, _, _) = common::create_test_user(&app.db_auth, &app.field_keyring)
+        .await
+        .unwrap();
+    let user_id: Uuid = sqlx::query_scalar("SELECT id FROM users WHERE username = $1")
+        .bind(&username)
+        .fetch_one(&app.db_auth)
+        .await
+        .unwrap();
+    (user_id, common::generate_test_token(user_id))
+}
+
+#[tokio::test]
+async fn test_passkey_registration_requires_auth() {
+    let app = common::spawn_app().await;
+
+    let response = app
+        .api
+        .post("/auth/webauthn/register/start")
+        .json(&json!({ "name": "YubiKey" }))
+        .await;
+
+    assert_eq!(response.status_code(), 401);
+}
+
+#[tokio::test]
+async fn test_passkey_registration_start_returns_options() {
+    let app = common::spawn_app().await;
+    let (_, token) = create_user_token(&app).await;
+
+    let response = app
+        .api
+        .post("/auth/webauthn/register/start")
+        .add_header("Authorization", format!("Bearer {}", token))
+        .json(&json!({ "name": "YubiKey" }))
+        .await;
+
+    response.assert_status_ok();
+    let body: Value = response.json();
+    assert!(body["challenge_id"].is_string());
+    assert!(body["options"]["publicKey"]["challenge"].is_string());
+    assert_eq!(
+        body["options"]["publicKey"]["authenticatorSelection"]["residentKey"],
+        "preferred"
+    );
+}
+
+#[tokio::test]
+async fn test_passkey_registration_finish_with_unknown_challenge() {
+    let app = common::spawn_app().await;
+    let (_, token) = create_user_token(&app).await;
+
+    let response = app
+        .api
+        .post("/auth/webauthn/register/finish")
+        .add_header("Authorization", format!("Bearer {}", token))
+        .json(&json!({ "challenge_id": Uuid::new_v4(), "credential": {} }))
+        .await;
+
+    assert_eq!(response.status_code(), 400);
+}
+
+#[tokio::test]
+async fn test_passkey_list_starts_empty() {
+    let app = common::spawn_app().await;
+    let (_, token) = create_user_token(&app).await;
+
+    let response = app
+        .api
+        .get("/auth/webauthn/credentials")
+        .add_header("Authorization", format!("Bearer {}", token))
+        .await;
+
+    response.assert_status_ok();
+    let body: Value = response.json();
+    assert_eq!(body.as_array().unwrap().len(), 0);
+}
+
+#[tokio::test]
+async fn test_delete_unknown_passkey_returns_404() {
+    let app = common::spawn_app().await;
+    let (_, token) = create_user_token(&app).await;
+
+    let response = app
+        .api
+        .delete(&format!("/auth/webauthn/credentials/{}", Uuid::new_v4()))
+        .add_header("Authorization", format!("Bearer {}", token))
+        .await;
+
+    assert_eq!(response.status_code(), 404);
+}
+
+#[tokio::test]
+async fn test_passwordless_start_returns_discoverable_challenge() {
+    let app = common::spawn_app().await;
+
+    let response = app.api.post("/session/passkey/start").await;
+
+    response.assert_status_ok();
+    let body: Value = response.json();
+    assert!(body["challenge_id"].is_string());
+    assert!(body["options"]["publicKey"]["challenge"].is_string());
+}
+
+#[tokio::test]
+async fn test_passwordless_challenge_is_single_use() {
+    let app = common::spawn_app().await;
+
+    let response = app.api.post("/session/passkey/start").await;
+    response.assert_status_ok();
+    let challenge_id = response.json::<Value>()["challenge_id"].clone();
+
+    let credential = json!({
+        "id": "AAAA",
+        "rawId": "AAAA",
+        "type": "public-key",
+        "response": {
+            "authenticatorData": "AAAA",
+            "clientDataJSON": "AAAA",
+            "signature": "AAAA",
+            "userHandle": null
+        },
+        "extensions": {}
+    });
+
+    let first = app
+        .api
+        .post("/session/passkey/finish")
+        .json(&json!({ "challenge_id": challenge_id, "credential": credential }))
+        .await;
+    assert_eq!(first.status_code(), 401);
+
+    // O desafio foi consumido na primeira tentativa
+    let second = app
+        .api
+        .post("/session/passkey/finish")
+        .json(&json!({ "challenge_id": challenge_id, "credential": credential }))
+        .await;
+    assert_eq!(second.status_code(), 400);
+}
+
+#[tokio::test]
+async fn test_mfa_passkey_start_with_invalid_token() {
+    let app = common::spawn_app().await;
+
+    let response = app
+        .api
+        .post("/auth/mfa/passkey/start")
+        .json(&json!({ "mfa_token": "not-a-token" }))
+        .await;
+
+    assert_eq!(response.status_code(), 401);
+}
@@ -1,11 +1,13 @@
 use anyhow::{Context, Result};
 use application::external::{SiorgClient, SiorgSyncService};
 use application::workers::siorg_sync_worker::{SiorgSyncWorkerCore, WorkerConfig};
+use core_services::secrets::{Secret, SecretStore};
 use domain::ports::{
     OrganizationRepositoryPort, OrganizationalUnitCategoryRepositoryPort,
     OrganizationalUnitRepositoryPort, OrganizationalUnitTypeRepositoryPort,
-    SiorgEsferaRepositoryPort, SiorgHistoryRepositoryPort, SiorgNaturezaJuridicaRepositoryPort,
-    SiorgPoderRepositoryPort, SiorgSyncQueueRepositoryPort, SystemSettingsRepositoryPort,
+    SiorgChangePreviewRepositoryPort, SiorgEsferaRepositoryPort, SiorgHistoryRepositoryPort,
+    SiorgNaturezaJuridicaRepositoryPort, SiorgPoderRepositoryPort, SiorgSyncQueueRepositoryPort,
+    SystemSettingsRepositoryPort,
 };
 use persistence::repositories::{
     organizational_repository::{
@@ -13,14 +15,22 @@ use persistence::repositories::{
         OrganizationalUnitTypeRepository, SiorgEsferaRepository, SiorgNaturezaJuridicaRepository,
         SiorgPoderRepository, SystemSettingsRepository,
     },
+    siorg_preview_repository::SiorgChangePreviewRepository,
     siorg_sync_repository::{SiorgHistoryRepository, SiorgSyncQueueRepository},
 };
+use sqlx::postgres::PgConnectOptions;
 use sqlx::PgPool;
 use std::env;
+use std::str::FromStr;
 use std::sync::Arc;
 use tracing::{error, info};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
+/// Mesmos nomes usados pela API no provedor de segredos
+const DATABASE_URL: Secret = Secret::new("main_database_url", "DATABASE_URL");
+const SIORG_API_TOKEN: Secret = Secret::new("siorg_api_token", "SIORG_API_TOKEN");
+static SECRETS: &[Secret] = &[DATABASE_URL, SIORG_API_TOKEN];
+
 #[tokio::main]
 async fn main() -> Result<()> {
     // Load environment variables
@@ -37,15 +47,33 @@ async fn main() -> Result<()> {
     let config = load_config_from_env()?;
     info!("📝 Configuração carregada: {:?}", config);
 
+    // Carrega os segredos (ambiente, arquivos ou Vault — ver SECRETS_PROVIDER)
+    let secrets = SecretStore::load_from_env(SECRETS).await?;
+    info!(provider = secrets.provider_name(), "🔒 Segredos carregados");
+
     // Connect to database
     info!("🔌 Conectando ao banco de dados...");
-    let database_url =
-        env::var("DATABASE_URL").context("DATABASE_URL deve estar definida no ambiente")?;
+    let database_url = secrets
+        .get(&DATABASE_URL)
+        .context("DATABASE_URL deve estar definida no provedor de segredos ou no ambiente")?;
     let pool = PgPool::connect(&database_url)
         .await
         .context("Falha ao conectar ao banco de dados")?;
     info!("✅ Conectado ao banco de dados");
 
+    // Credenciais de banco rotacionadas valem para novas conexões sem reinício
+    let refresh_pool = pool.clone();
+    secrets.spawn_refresh(move |store, changed| {
+        if !changed.contains(&DATABASE_URL.name) {
+            return;
+        }
+        match store.get(&DATABASE_URL).map(|url| PgConnectOptions::from_str(&url)) {
+            Some(Ok(options)) => refresh_pool.set_connect_options(options),
+            Some(Err(e)) => error!("DATABASE_URL inválida: {}", e),
+            None => error!("DATABASE_URL removida do provedor de segredos"),
+        }
+    });
+
     // Setup repositories
     info!("📦 Inicializando repositórios...");
     let arc_pool = Arc::new(pool.clone());
@@ -70,7 +98,7 @@ async fn main() -> Result<()> {
     info!("🌐 Inicializando cliente SIORG...");
     let siorg_base_url = env::var("SIORG_API_URL")
         .unwrap_or_else(|_| "https://estruturaorganizacional.dados.gov.br/doc".to_string());
-    let siorg_token = env::var("SIORG_API_TOKEN").ok();
+    let siorg_token = secrets.handle(&SIORG_API_TOKEN);
 
     let siorg_client = Arc::new(
         SiorgClient::new(siorg_base_url, siorg_token).expect("Failed to create SIORG client"),
@@ -83,19 +111,26 @@ async fn main() -> Result<()> {
     let esfera_repo: Arc<dyn SiorgEsferaRepositoryPort> =
         Arc::new(SiorgEsferaRepository::new(arc_pool.clone()));
 
-    let sync_service = Arc::new(SiorgSyncService::new(
-        siorg_client,
-        organization_repo,
-        unit_repo,
-        category_repo,
-        type_repo,
-        settings_repo,
-        natureza_juridica_repo,
-        poder_repo,
-        esfera_repo,
-        history_repo.clone(),
-        pool.clone(),
-    ));
+    // Mesma prévia da API: com aprovação obrigatória, nada é aplicado sem revisão
+    let change_preview_repo: Arc<dyn SiorgChangePreviewRepositoryPort> =
+        Arc::new(SiorgChangePreviewRepository::new(pool.clone()));
+
+    let sync_service = Arc::new(
+        SiorgSyncService::new(
+            siorg_client,
+            organization_repo,
+            unit_repo,
+            category_repo,
+            type_repo,
+            settings_repo,
+            natureza_juridica_repo,
+            poder_repo,
+            esfera_repo,
+            history_repo.clone(),
+            pool.clone(),
+        )
+        .with_change_previews(change_preview_repo),
+    );
 
     // Create worker
     info!("⚙️  Criando worker...");
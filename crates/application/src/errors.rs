@@ -15,6 +15,9 @@ pub enum ServiceError {
     #[error("{0}")]
     NotFound(String),
 
+    #[error("{0}")]
+    Forbidden(String),
+
     #[error("{0}")]
     Conflict(String),
 
@@ -30,6 +33,10 @@ pub enum ServiceError {
     /// Optimistic concurrency conflict — HTTP 409 (DRS 4.2 / RNF-01)
     #[error("Optimistic lock conflict: {0}")]
     OptimisticLockConflict(String),
+
+    /// Too many attempts — HTTP 429, with the wait sent as `Retry-After`
+    #[error("{message}")]
+    TooManyRequests { message: String, retry_after_secs: u64 },
 }
 
 impl ServiceError {
@@ -39,11 +46,13 @@ impl ServiceError {
             ServiceError::InvalidCredentials => http::StatusCode::UNAUTHORIZED,
             ServiceError::BadRequest(_) => http::StatusCode::BAD_REQUEST,
             ServiceError::NotFound(_) => http::StatusCode::NOT_FOUND,
+            ServiceError::Forbidden(_) => http::StatusCode::FORBIDDEN,
             ServiceError::Conflict(_) => http::StatusCode::CONFLICT,
             ServiceError::Repository(_) => http::StatusCode::INTERNAL_SERVER_ERROR,
             ServiceError::RepositoryError(_) => http::StatusCode::INTERNAL_SERVER_ERROR,
             ServiceError::Internal(_) => http::StatusCode::INTERNAL_SERVER_ERROR,
             ServiceError::OptimisticLockConflict(_) => http::StatusCode::CONFLICT,
+            ServiceError::TooManyRequests { .. } => http::StatusCode::TOO_MANY_REQUESTS,
         }
     }
 }
@@ -0,0 +1,1324 @@
+use super::{CircuitBreaker, ComprasGovClient};
+use crate::errors::ServiceError;
+use domain::errors::RepositoryError;
+use domain::models::catalog::{
+    ComprasGovClasseMaterial, ComprasGovClasseServico, ComprasGovDivisionService,
+    ComprasGovGrupoMaterial, ComprasGovGrupoServico, ComprasGovItemMaterial, ComprasGovItemServico,
+    ComprasGovPdmMaterial, ComprasGovResponse, ComprasGovSectionService,
+};
+use domain::models::{
+    CatalogKind, CatalogLevel, CatalogNode, CatalogNodeFilter, CatalogSyncEntry, CatalogSyncPlan,
+    CatalogSyncReport, CatalogSyncRunDto, CatalogSyncScope, CatalogSyncStatus,
+    MaterialClassification, OfficialCatmatItem, OfficialCatserItem,
+};
+use domain::ports::{
+    CatalogSyncRepositoryPort, SystemSettingsRepositoryPort, UnitOfMeasureRepositoryPort,
+};
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::sync::Arc;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+// ============================================================================
+// ComprasGov Catalog Sync Service
+// ============================================================================
+
+const CATMAT_URL_KEY: &str = "compras_gov.catmat_api_base_url";
+const CATSER_URL_KEY: &str = "compras_gov.catser_api_base_url";
+const DEFAULT_UNIT_KEY: &str = "compras_gov.sync_default_unit_symbol";
+const DEFAULT_CLASSIFICATION_KEY: &str = "compras_gov.sync_default_material_classification";
+
+const DEFAULT_CATMAT_URL: &str = "https://dadosabertos.compras.gov.br/modulo-material";
+const DEFAULT_CATSER_URL: &str = "https://dadosabertos.compras.gov.br/modulo-servico";
+const DEFAULT_UNIT_SYMBOL: &str = "UNID";
+
+/// Proteção contra paginação inconsistente da API
+const MAX_PAGES: i64 = 20_000;
+
+const CATMAT_LEVELS: [CatalogLevel; 4] = [
+    CatalogLevel::Group,
+    CatalogLevel::Class,
+    CatalogLevel::Pdm,
+    CatalogLevel::Item,
+];
+const CATSER_LEVELS: [CatalogLevel; 5] = [
+    CatalogLevel::Section,
+    CatalogLevel::Division,
+    CatalogLevel::Group,
+    CatalogLevel::Class,
+    CatalogLevel::Item,
+];
+
+fn levels(catalog: CatalogKind) -> &'static [CatalogLevel] {
+    match catalog {
+        CatalogKind::Catmat => &CATMAT_LEVELS,
+        CatalogKind::Catser => &CATSER_LEVELS,
+    }
+}
+
+fn parent_level(catalog: CatalogKind, level: CatalogLevel) -> Option<CatalogLevel> {
+    let levels = levels(catalog);
+    let pos = levels.iter().position(|l| *l == level)?;
+    pos.checked_sub(1).map(|p| levels[p])
+}
+
+/// CATSER groups may exist without a division
+fn parent_optional(catalog: CatalogKind, level: CatalogLevel) -> bool {
+    catalog == CatalogKind::Catser && level == CatalogLevel::Group
+}
+
+/// Keeps pulled catalogs in line with ComprasGov: the hierarchy of the scope
+/// is fetched through the `comprasnet` circuit breaker, compared with the
+/// local records by official code and, unless it is a dry run, written in a
+/// single transaction. Entries that disappear from the official catalog are
+/// deactivated, never removed.
+pub struct CatalogSyncService {
+    sync_repo: Arc<dyn CatalogSyncRepositoryPort>,
+    unit_repo: Arc<dyn UnitOfMeasureRepositoryPort>,
+    settings_repo: Arc<dyn SystemSettingsRepositoryPort>,
+    circuit_breaker: Arc<CircuitBreaker>,
+}
+
+impl CatalogSyncService {
+    pub fn new(
+        sync_repo: Arc<dyn CatalogSyncRepositoryPort>,
+        unit_repo: Arc<dyn UnitOfMeasureRepositoryPort>,
+        settings_repo: Arc<dyn SystemSettingsRepositoryPort>,
+        circuit_breaker: Arc<CircuitBreaker>,
+    ) -> Self {
+        Self {
+            sync_repo,
+            unit_repo,
+            settings_repo,
+            circuit_breaker,
+        }
+    }
+
+    // ========================================================================
+    // Runs
+    // ========================================================================
+
+    /// Valida o pedido e registra a execução; o trabalho é feito por `execute`
+    pub async fn start(
+        &self,
+        catalog: CatalogKind,
+        scope: CatalogSyncScope,
+        code: Option<&str>,
+        dry_run: bool,
+        triggered_by: Option<Uuid>,
+    ) -> Result<CatalogSyncRunDto, ServiceError> {
+        let code = code.map(str::trim).filter(|c| !c.is_empty());
+        match (scope, code) {
+            (CatalogSyncScope::Full, Some(_)) => {
+                return Err(ServiceError::BadRequest(
+                    "A sincronização completa não aceita código".to_string(),
+                ))
+            }
+            (CatalogSyncScope::Group | CatalogSyncScope::Pdm, None) => {
+                return Err(ServiceError::BadRequest(
+                    "Informe o código do grupo ou PDM a sincronizar".to_string(),
+                ))
+            }
+            (_, Some(c)) if c.parse::<i64>().map_or(true, |n| n <= 0) => {
+                return Err(ServiceError::BadRequest(format!(
+                    "Código inválido: '{}'",
+                    c
+                )))
+            }
+            _ => {}
+        }
+        if scope == CatalogSyncScope::Pdm && catalog == CatalogKind::Catser {
+            return Err(ServiceError::BadRequest(
+                "O CATSER não possui PDM; sincronize por grupo".to_string(),
+            ));
+        }
+
+        self.sync_repo
+            .create_run(catalog, scope, code, dry_run, triggered_by)
+            .await
+            .map_err(|e| match e {
+                RepositoryError::Duplicate(_) => ServiceError::Conflict(format!(
+                    "Já existe uma sincronização do {:?} em andamento",
+                    catalog
+                )),
+                e => ServiceError::from(e),
+            })
+    }
+
+    /// Executa a sincronização registrada por `start`. Falhas encerram a
+    /// execução como `FAILED`, com o erro gravado.
+    pub async fn execute(&self, run: CatalogSyncRunDto) -> Result<CatalogSyncRunDto, ServiceError> {
+        info!(
+            run_id = %run.id,
+            catalog = ?run.catalog,
+            scope = ?run.scope,
+            code = ?run.scope_code,
+            dry_run = run.dry_run,
+            "Iniciando sincronização do catálogo com o ComprasGov"
+        );
+
+        let finished = match self.sync(&run).await {
+            Ok(report) => {
+                info!(
+                    run_id = %run.id,
+                    added = report.added.len(),
+                    changed = report.changed.len(),
+                    deactivated = report.deactivated.len(),
+                    skipped = report.skipped.len(),
+                    "Sincronização do catálogo concluída"
+                );
+                self.sync_repo
+                    .finish_run(run.id, CatalogSyncStatus::Completed, Some(&report), None)
+                    .await?
+            }
+            Err(e) => {
+                error!(run_id = %run.id, "Sincronização do catálogo falhou: {}", e);
+                self.sync_repo
+                    .finish_run(
+                        run.id,
+                        CatalogSyncStatus::Failed,
+                        None,
+                        Some(&e.to_string()),
+                    )
+                    .await?
+            }
+        };
+
+        Ok(finished)
+    }
+
+    /// `start` seguido de `execute`
+    pub async fn run(
+        &self,
+        catalog: CatalogKind,
+        scope: CatalogSyncScope,
+        code: Option<&str>,
+        dry_run: bool,
+        triggered_by: Option<Uuid>,
+    ) -> Result<CatalogSyncRunDto, ServiceError> {
+        let run = self
+            .start(catalog, scope, code, dry_run, triggered_by)
+            .await?;
+        self.execute(run).await
+    }
+
+    pub async fn get_run(&self, id: Uuid) -> Result<CatalogSyncRunDto, ServiceError> {
+        self.sync_repo
+            .find_run(id)
+            .await?
+            .ok_or(ServiceError::NotFound(
+                "Sincronização não encontrada".to_string(),
+            ))
+    }
+
+    pub async fn list_runs(
+        &self,
+        catalog: Option<CatalogKind>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<CatalogSyncRunDto>, i64), ServiceError> {
+        self.sync_repo
+            .list_runs(catalog, limit, offset)
+            .await
+            .map_err(ServiceError::from)
+    }
+
+    /// Encerra execuções que ficaram em andamento quando o processo parou
+    pub async fn fail_interrupted_runs(&self) -> Result<u64, ServiceError> {
+        let failed = self
+            .sync_repo
+            .fail_interrupted_runs("Interrompida pela reinicialização do servidor")
+            .await?;
+        if failed > 0 {
+            warn!(
+                failed,
+                "Sincronizações do catálogo interrompidas marcadas como falhas"
+            );
+        }
+        Ok(failed)
+    }
+
+    // ========================================================================
+    // On-demand import
+    // ========================================================================
+
+    /// Importa um item oficial e os ancestrais que faltam localmente numa
+    /// única transação, como `verified`. Retorna o código normalizado do
+    /// item e os níveis gravados, do topo para o item.
+    pub async fn import_item(
+        &self,
+        catalog: CatalogKind,
+        code: &str,
+    ) -> Result<(String, Vec<CatalogLevel>), ServiceError> {
+        let number = parse_code(code)?;
+        let code = number.to_string();
+        if let Some(local) = self
+            .sync_repo
+            .list_nodes(catalog, CatalogLevel::Item, &CatalogNodeFilter::Codes(vec![code.clone()]))
+            .await?
+            .first()
+        {
+            return Err(ServiceError::Conflict(if local.merged {
+                format!("Item {} foi fundido em outro item do catálogo local", code)
+            } else {
+                format!("Item {} já cadastrado localmente", code)
+            }));
+        }
+
+        let client = self.client().await?;
+        let lineage = match catalog {
+            CatalogKind::Catmat => self.fetch_catmat_lineage(&client, number).await?,
+            CatalogKind::Catser => self.fetch_catser_lineage(&client, number).await?,
+        };
+        if lineage.last().is_some_and(|item| !item.is_active) {
+            return Err(ServiceError::BadRequest(format!(
+                "Item {} está inativo no catálogo oficial",
+                code
+            )));
+        }
+
+        let mut missing = Vec::new();
+        for node in lineage {
+            if self
+                .local_codes(catalog, node.level, vec![node.code.clone()])
+                .await?
+                .is_empty()
+            {
+                missing.push(node);
+            }
+        }
+
+        let (default_unit_id, default_material_classification) = self.defaults().await?;
+        let created = missing.iter().map(|n| n.level).collect();
+        let plan = CatalogSyncPlan {
+            catalog,
+            upserts: missing,
+            deactivations: Vec::new(),
+            default_unit_id,
+            default_material_classification,
+        };
+        self.sync_repo.apply(&plan).await?;
+
+        info!(catalog = ?catalog, code = %code, "Item importado do catálogo oficial");
+        Ok((code, created))
+    }
+
+    /// Item and ancestors, top level first
+    async fn fetch_catmat_lineage(
+        &self,
+        client: &ComprasGovClient,
+        code: i64,
+    ) -> Result<Vec<CatalogNode>, ServiceError> {
+        let item = self
+            .first(client.search_itens_material(Some(code), None, None, None, None))
+            .await?
+            .ok_or_else(|| not_found("Item CATMAT", code))?;
+        let group = self
+            .first(client.search_grupos_material(Some(item.codigo_grupo), None))
+            .await?;
+        let class = self
+            .first(client.search_classes_material(Some(item.codigo_classe), None, None))
+            .await?;
+        let pdm = self
+            .first(client.search_pdms_material(Some(item.codigo_pdm), None, None))
+            .await?;
+
+        match (group, class, pdm) {
+            (Some(group), Some(class), Some(pdm)) => Ok(vec![
+                catmat_group_node(group),
+                catmat_class_node(class),
+                catmat_pdm_node(pdm),
+                catmat_item_node(item),
+            ]),
+            _ => Err(incomplete_lineage(code)),
+        }
+    }
+
+    /// Item and ancestors, top level first. Section and division are left
+    /// out when the official catalog does not place the group under them.
+    async fn fetch_catser_lineage(
+        &self,
+        client: &ComprasGovClient,
+        code: i64,
+    ) -> Result<Vec<CatalogNode>, ServiceError> {
+        let item = self
+            .first(client.search_itens_servico(Some(code), None, None, None))
+            .await?
+            .ok_or_else(|| not_found("Item CATSER", code))?;
+        let (Some(group), Some(class)) = (
+            self.first(client.search_grupos_servico(Some(item.codigo_grupo), None, None))
+                .await?,
+            self.first(client.search_classes_servico(Some(item.codigo_classe), None, None))
+                .await?,
+        ) else {
+            return Err(incomplete_lineage(code));
+        };
+
+        let mut division = None;
+        if group.codigo_divisao > 0 {
+            division = self
+                .first(client.search_divisions_service(Some(group.codigo_divisao), None, None))
+                .await?;
+        }
+        let mut section = None;
+        if let Some(section_code) = division.as_ref().map(|d| d.codigo_secao) {
+            section = self
+                .first(client.search_sections_service(Some(section_code), None))
+                .await?;
+        }
+
+        let mut nodes = Vec::new();
+        let mut group_node = catser_group_node(group);
+        match (section, division) {
+            (Some(section), Some(division)) => {
+                nodes.push(catser_section_node(section));
+                nodes.push(catser_division_node(division));
+            }
+            _ => group_node.parent_code = None,
+        }
+        nodes.push(group_node);
+        nodes.push(catser_class_node(class));
+        nodes.push(catser_item_node(item));
+        Ok(nodes)
+    }
+
+    // ========================================================================
+    // Official catalog search
+    // ========================================================================
+
+    /// One page of the official CATMAT item search, flagging the items
+    /// already registered locally
+    pub async fn search_official_catmat_items(
+        &self,
+        item_code: Option<i64>,
+        pdm_code: Option<i64>,
+        class_code: Option<i64>,
+        group_code: Option<i64>,
+        page: i64,
+    ) -> Result<ComprasGovResponse<OfficialCatmatItem>, ServiceError> {
+        let client = self.client().await?;
+        let response = self
+            .call(client.search_itens_material(
+                item_code,
+                pdm_code,
+                class_code,
+                group_code,
+                Some(page.max(1)),
+            ))
+            .await?;
+
+        let codes = response
+            .resultado
+            .iter()
+            .map(|i| i.codigo_item.to_string())
+            .collect();
+        let local = self
+            .local_codes(CatalogKind::Catmat, CatalogLevel::Item, codes)
+            .await?;
+
+        Ok(ComprasGovResponse {
+            resultado: response
+                .resultado
+                .into_iter()
+                .map(|item| OfficialCatmatItem {
+                    exists_locally: local.contains(&item.codigo_item.to_string()),
+                    item,
+                })
+                .collect(),
+            total_registros: response.total_registros,
+            total_paginas: response.total_paginas,
+            paginas_restantes: response.paginas_restantes,
+        })
+    }
+
+    /// One page of the official CATSER item search, flagging the items
+    /// already registered locally
+    pub async fn search_official_catser_items(
+        &self,
+        item_code: Option<i64>,
+        class_code: Option<i64>,
+        group_code: Option<i64>,
+        page: i64,
+    ) -> Result<ComprasGovResponse<OfficialCatserItem>, ServiceError> {
+        let client = self.client().await?;
+        let response = self
+            .call(client.search_itens_servico(item_code, class_code, group_code, Some(page.max(1))))
+            .await?;
+
+        let codes = response
+            .resultado
+            .iter()
+            .map(|i| i.codigo_item.to_string())
+            .collect();
+        let local = self
+            .local_codes(CatalogKind::Catser, CatalogLevel::Item, codes)
+            .await?;
+
+        Ok(ComprasGovResponse {
+            resultado: response
+                .resultado
+                .into_iter()
+                .map(|item| OfficialCatserItem {
+                    exists_locally: local.contains(&item.codigo_item.to_string()),
+                    item,
+                })
+                .collect(),
+            total_registros: response.total_registros,
+            total_paginas: response.total_paginas,
+            paginas_restantes: response.paginas_restantes,
+        })
+    }
+
+    /// Which of `codes` exist locally at `level`
+    async fn local_codes(
+        &self,
+        catalog: CatalogKind,
+        level: CatalogLevel,
+        codes: Vec<String>,
+    ) -> Result<HashSet<String>, ServiceError> {
+        if codes.is_empty() {
+            return Ok(HashSet::new());
+        }
+        Ok(self
+            .sync_repo
+            .list_nodes(catalog, level, &CatalogNodeFilter::Codes(codes))
+            .await?
+            .into_iter()
+            .map(|n| n.code)
+            .collect())
+    }
+
+    // ========================================================================
+    // Sync
+    // ========================================================================
+
+    async fn sync(&self, run: &CatalogSyncRunDto) -> Result<CatalogSyncReport, ServiceError> {
+        let code = run.scope_code.as_deref();
+        let client = self.client().await?;
+
+        let remote = match run.catalog {
+            CatalogKind::Catmat => self.fetch_catmat(&client, run.scope, code).await?,
+            CatalogKind::Catser => self.fetch_catser(&client, run.scope, code).await?,
+        };
+        let scope_nodes = self.load_scope(run.catalog, run.scope, code).await?;
+
+        // Registros oficiais que já existem localmente fora do escopo
+        // (ancestrais ou itens que mudaram de ramo) são atualizados, não duplicados
+        let in_scope: HashSet<(CatalogLevel, &str)> = scope_nodes
+            .iter()
+            .map(|n| (n.level, n.code.as_str()))
+            .collect();
+        let mut outside = Vec::new();
+        for &level in levels(run.catalog) {
+            let codes: Vec<String> = remote
+                .iter()
+                .filter(|n| n.level == level && !in_scope.contains(&(level, n.code.as_str())))
+                .map(|n| n.code.clone())
+                .collect();
+            if !codes.is_empty() {
+                outside.extend(
+                    self.sync_repo
+                        .list_nodes(run.catalog, level, &CatalogNodeFilter::Codes(codes))
+                        .await?,
+                );
+            }
+        }
+
+        let diff = diff_catalog(run.catalog, &remote, &scope_nodes, &outside);
+
+        let needs_defaults = diff
+            .report
+            .added
+            .iter()
+            .any(|a| matches!(a.level, CatalogLevel::Pdm | CatalogLevel::Item));
+        let (default_unit_id, default_material_classification) = if needs_defaults {
+            self.defaults().await?
+        } else {
+            (Uuid::nil(), MaterialClassification::DirectUse)
+        };
+
+        if !run.dry_run && (!diff.upserts.is_empty() || !diff.deactivations.is_empty()) {
+            let plan = CatalogSyncPlan {
+                catalog: run.catalog,
+                upserts: diff.upserts,
+                deactivations: diff.deactivations,
+                default_unit_id,
+                default_material_classification,
+            };
+            self.sync_repo.apply(&plan).await?;
+        }
+
+        Ok(diff.report)
+    }
+
+    /// Local records of the scope subtree: the candidates for deactivation
+    async fn load_scope(
+        &self,
+        catalog: CatalogKind,
+        scope: CatalogSyncScope,
+        code: Option<&str>,
+    ) -> Result<Vec<CatalogNode>, ServiceError> {
+        let root = match scope {
+            CatalogSyncScope::Full => levels(catalog)[0],
+            CatalogSyncScope::Group => CatalogLevel::Group,
+            CatalogSyncScope::Pdm => CatalogLevel::Pdm,
+        };
+
+        let mut nodes = Vec::new();
+        let mut filter = match code {
+            Some(code) => CatalogNodeFilter::Codes(vec![code.to_string()]),
+            None => CatalogNodeFilter::All,
+        };
+        for &level in levels(catalog).iter().skip_while(|l| **l != root) {
+            let found = self.sync_repo.list_nodes(catalog, level, &filter).await?;
+            if scope != CatalogSyncScope::Full {
+                if found.is_empty() {
+                    break;
+                }
+                filter =
+                    CatalogNodeFilter::ParentCodes(found.iter().map(|n| n.code.clone()).collect());
+            }
+            nodes.extend(found);
+        }
+
+        Ok(nodes)
+    }
+
+    async fn fetch_catmat(
+        &self,
+        client: &ComprasGovClient,
+        scope: CatalogSyncScope,
+        code: Option<&str>,
+    ) -> Result<Vec<CatalogNode>, ServiceError> {
+        let code = code.and_then(|c| c.parse::<i64>().ok());
+        let mut nodes = Vec::new();
+
+        match (scope, code) {
+            (CatalogSyncScope::Group, Some(group)) => {
+                let groups = self
+                    .fetch_pages(|p| client.search_grupos_material(Some(group), Some(p)))
+                    .await?;
+                if groups.is_empty() {
+                    return Err(not_found("Grupo CATMAT", group));
+                }
+                let classes = self
+                    .fetch_pages(|p| client.search_classes_material(None, Some(group), Some(p)))
+                    .await?;
+                let mut pdms = Vec::new();
+                for class in &classes {
+                    pdms.extend(
+                        self.fetch_pages(|p| {
+                            client.search_pdms_material(None, Some(class.codigo_classe), Some(p))
+                        })
+                        .await?,
+                    );
+                }
+                let items = self
+                    .fetch_pages(|p| {
+                        client.search_itens_material(None, None, None, Some(group), Some(p))
+                    })
+                    .await?;
+
+                nodes.extend(groups.into_iter().map(catmat_group_node));
+                nodes.extend(classes.into_iter().map(catmat_class_node));
+                nodes.extend(pdms.into_iter().map(catmat_pdm_node));
+                nodes.extend(items.into_iter().map(catmat_item_node));
+            }
+            (CatalogSyncScope::Pdm, Some(pdm)) => {
+                let pdms = self
+                    .fetch_pages(|p| client.search_pdms_material(Some(pdm), None, Some(p)))
+                    .await?;
+                let Some(class_code) = pdms.first().map(|p| p.codigo_classe) else {
+                    return Err(not_found("PDM", pdm));
+                };
+                let classes = self
+                    .fetch_pages(|p| {
+                        client.search_classes_material(Some(class_code), None, Some(p))
+                    })
+                    .await?;
+                let mut groups = Vec::new();
+                if let Some(group_code) = classes.first().map(|c| c.codigo_grupo) {
+                    groups = self
+                        .fetch_pages(|p| client.search_grupos_material(Some(group_code), Some(p)))
+                        .await?;
+                }
+                let items = self
+                    .fetch_pages(|p| {
+                        client.search_itens_material(None, Some(pdm), None, None, Some(p))
+                    })
+                    .await?;
+
+                nodes.extend(groups.into_iter().map(catmat_group_node));
+                nodes.extend(classes.into_iter().map(catmat_class_node));
+                nodes.extend(pdms.into_iter().map(catmat_pdm_node));
+                nodes.extend(items.into_iter().map(catmat_item_node));
+            }
+            _ => {
+                let groups = self
+                    .fetch_pages(|p| client.search_grupos_material(None, Some(p)))
+                    .await?;
+                if groups.is_empty() {
+                    return Err(ServiceError::Internal(
+                        "A API do ComprasGov não retornou grupos CATMAT".to_string(),
+                    ));
+                }
+                let classes = self
+                    .fetch_pages(|p| client.search_classes_material(None, None, Some(p)))
+                    .await?;
+                let pdms = self
+                    .fetch_pages(|p| client.search_pdms_material(None, None, Some(p)))
+                    .await?;
+                let items = self
+                    .fetch_pages(|p| client.search_itens_material(None, None, None, None, Some(p)))
+                    .await?;
+
+                nodes.extend(groups.into_iter().map(catmat_group_node));
+                nodes.extend(classes.into_iter().map(catmat_class_node));
+                nodes.extend(pdms.into_iter().map(catmat_pdm_node));
+                nodes.extend(items.into_iter().map(catmat_item_node));
+            }
+        }
+
+        Ok(nodes)
+    }
+
+    async fn fetch_catser(
+        &self,
+        client: &ComprasGovClient,
+        scope: CatalogSyncScope,
+        code: Option<&str>,
+    ) -> Result<Vec<CatalogNode>, ServiceError> {
+        let code = code.and_then(|c| c.parse::<i64>().ok());
+        let mut nodes = Vec::new();
+
+        match (scope, code) {
+            (CatalogSyncScope::Group, Some(group)) => {
+                let groups = self
+                    .fetch_pages(|p| client.search_grupos_servico(Some(group), None, Some(p)))
+                    .await?;
+                let Some(division_code) = groups.first().map(|g| g.codigo_divisao) else {
+                    return Err(not_found("Grupo CATSER", group));
+                };
+                let divisions = self
+                    .fetch_pages(|p| {
+                        client.search_divisions_service(Some(division_code), None, Some(p))
+                    })
+                    .await?;
+                let mut sections = Vec::new();
+                if let Some(section_code) = divisions.first().map(|d| d.codigo_secao) {
+                    sections = self
+                        .fetch_pages(|p| {
+                            client.search_sections_service(Some(section_code), Some(p))
+                        })
+                        .await?;
+                }
+                let classes = self
+                    .fetch_pages(|p| client.search_classes_servico(None, Some(group), Some(p)))
+                    .await?;
+                let items = self
+                    .fetch_pages(|p| client.search_itens_servico(None, None, Some(group), Some(p)))
+                    .await?;
+
+                nodes.extend(sections.into_iter().map(catser_section_node));
+                nodes.extend(divisions.into_iter().map(catser_division_node));
+                nodes.extend(groups.into_iter().map(catser_group_node));
+                nodes.extend(classes.into_iter().map(catser_class_node));
+                nodes.extend(items.into_iter().map(catser_item_node));
+            }
+            _ => {
+                let sections = self
+                    .fetch_pages(|p| client.search_sections_service(None, Some(p)))
+                    .await?;
+                if sections.is_empty() {
+                    return Err(ServiceError::Internal(
+                        "A API do ComprasGov não retornou seções CATSER".to_string(),
+                    ));
+                }
+                let divisions = self
+                    .fetch_pages(|p| client.search_divisions_service(None, None, Some(p)))
+                    .await?;
+                let groups = self
+                    .fetch_pages(|p| client.search_grupos_servico(None, None, Some(p)))
+                    .await?;
+                let classes = self
+                    .fetch_pages(|p| client.search_classes_servico(None, None, Some(p)))
+                    .await?;
+                let items = self
+                    .fetch_pages(|p| client.search_itens_servico(None, None, None, Some(p)))
+                    .await?;
+
+                nodes.extend(sections.into_iter().map(catser_section_node));
+                nodes.extend(divisions.into_iter().map(catser_division_node));
+                nodes.extend(groups.into_iter().map(catser_group_node));
+                nodes.extend(classes.into_iter().map(catser_class_node));
+                nodes.extend(items.into_iter().map(catser_item_node));
+            }
+        }
+
+        Ok(nodes)
+    }
+
+    // ========================================================================
+    // ComprasGov access
+    // ========================================================================
+
+    /// Cliente com as URLs de `system_settings` (editáveis sem reiniciar)
+    async fn client(&self) -> Result<ComprasGovClient, ServiceError> {
+        let catmat_url = self
+            .setting_str(CATMAT_URL_KEY)
+            .await?
+            .unwrap_or_else(|| DEFAULT_CATMAT_URL.to_string());
+        let catser_url = self
+            .setting_str(CATSER_URL_KEY)
+            .await?
+            .unwrap_or_else(|| DEFAULT_CATSER_URL.to_string());
+
+        ComprasGovClient::new(catmat_url, catser_url)
+            .map_err(|e| ServiceError::Internal(format!("Cliente ComprasGov inválido: {:#}", e)))
+    }
+
+    /// Unidade de medida e classificação atribuídas aos registros novos
+    async fn defaults(&self) -> Result<(Uuid, MaterialClassification), ServiceError> {
+        let symbol = self
+            .setting_str(DEFAULT_UNIT_KEY)
+            .await?
+            .unwrap_or_else(|| DEFAULT_UNIT_SYMBOL.to_string());
+        let unit = self
+            .unit_repo
+            .find_by_symbol(&symbol)
+            .await?
+            .ok_or_else(|| {
+                ServiceError::BadRequest(format!(
+                    "Unidade de medida padrão '{}' ({}) não cadastrada",
+                    symbol, DEFAULT_UNIT_KEY
+                ))
+            })?;
+
+        let classification = match self.settings_repo.get(DEFAULT_CLASSIFICATION_KEY).await? {
+            Some(setting) => serde_json::from_value(setting.value).map_err(|_| {
+                ServiceError::BadRequest(format!(
+                    "Classificação padrão inválida em {}",
+                    DEFAULT_CLASSIFICATION_KEY
+                ))
+            })?,
+            None => MaterialClassification::DirectUse,
+        };
+
+        Ok((unit.id, classification))
+    }
+
+    async fn setting_str(&self, key: &str) -> Result<Option<String>, ServiceError> {
+        Ok(self
+            .settings_repo
+            .get(key)
+            .await?
+            .and_then(|s| s.value.as_str().map(str::to_string)))
+    }
+
+    /// Chamada à API protegida pelo circuit breaker
+    async fn call<T, Fut>(&self, fut: Fut) -> Result<T, ServiceError>
+    where
+        Fut: Future<Output = anyhow::Result<T>>,
+    {
+        if !self.circuit_breaker.allow_call().await {
+            return Err(ServiceError::Internal(format!(
+                "Circuito '{}' aberto: API do ComprasGov indisponível",
+                self.circuit_breaker.name()
+            )));
+        }
+
+        match fut.await {
+            Ok(value) => {
+                self.circuit_breaker.record_success().await;
+                Ok(value)
+            }
+            Err(e) => {
+                self.circuit_breaker.record_failure().await;
+                Err(ServiceError::Internal(format!(
+                    "Falha na API do ComprasGov: {:#}",
+                    e
+                )))
+            }
+        }
+    }
+
+    /// Primeiro registro de uma consulta por código
+    async fn first<T, Fut>(&self, fut: Fut) -> Result<Option<T>, ServiceError>
+    where
+        Fut: Future<Output = anyhow::Result<ComprasGovResponse<T>>>,
+    {
+        Ok(self.call(fut).await?.resultado.into_iter().next())
+    }
+
+    /// Todas as páginas de uma consulta
+    async fn fetch_pages<T, F, Fut>(&self, fetch: F) -> Result<Vec<T>, ServiceError>
+    where
+        F: Fn(i64) -> Fut,
+        Fut: Future<Output = anyhow::Result<ComprasGovResponse<T>>>,
+    {
+        let mut all = Vec::new();
+        for page in 1..=MAX_PAGES {
+            let response = self.call(fetch(page)).await?;
+            let last = response.paginas_restantes <= 0 || response.resultado.is_empty();
+            all.extend(response.resultado);
+            if last {
+                return Ok(all);
+            }
+        }
+
+        Err(ServiceError::Internal(format!(
+            "Consulta ao ComprasGov excedeu {} páginas",
+            MAX_PAGES
+        )))
+    }
+}
+
+fn parse_code(code: &str) -> Result<i64, ServiceError> {
+    code.trim()
+        .parse::<i64>()
+        .ok()
+        .filter(|n| *n > 0)
+        .ok_or_else(|| ServiceError::BadRequest(format!("Código inválido: '{}'", code)))
+}
+
+fn incomplete_lineage(code: i64) -> ServiceError {
+    ServiceError::Internal(format!(
+        "Hierarquia do item {} incompleta no catálogo oficial",
+        code
+    ))
+}
+
+fn not_found(what: &str, code: i64) -> ServiceError {
+    ServiceError::NotFound(format!(
+        "{} {} não encontrado no catálogo oficial",
+        what, code
+    ))
+}
+
+// ============================================================================
+// ComprasGov → CatalogNode
+// ============================================================================
+
+/// Tamanho das colunas de nome (300) e descrição (500)
+fn fit_name(level: CatalogLevel, name: &str) -> String {
+    let max = match level {
+        CatalogLevel::Pdm | CatalogLevel::Item => 500,
+        _ => 300,
+    };
+    name.trim().chars().take(max).collect()
+}
+
+fn node(
+    level: CatalogLevel,
+    code: i64,
+    parent: Option<i64>,
+    name: &str,
+    is_active: bool,
+) -> CatalogNode {
+    CatalogNode {
+        level,
+        code: code.to_string(),
+        parent_code: parent.filter(|p| *p > 0).map(|p| p.to_string()),
+        name: fit_name(level, name),
+        is_active,
+        is_sustainable: None,
+        code_cpc: None,
+        merged: false,
+    }
+}
+
+fn catmat_group_node(g: ComprasGovGrupoMaterial) -> CatalogNode {
+    node(
+        CatalogLevel::Group,
+        g.codigo_grupo,
+        None,
+        &g.nome_grupo,
+        g.status,
+    )
+}
+
+fn catmat_class_node(c: ComprasGovClasseMaterial) -> CatalogNode {
+    node(
+        CatalogLevel::Class,
+        c.codigo_classe,
+        Some(c.codigo_grupo),
+        &c.nome_classe,
+        c.status,
+    )
+}
+
+fn catmat_pdm_node(p: ComprasGovPdmMaterial) -> CatalogNode {
+    node(
+        CatalogLevel::Pdm,
+        p.codigo_pdm,
+        Some(p.codigo_classe),
+        &p.nome_pdm,
+        p.status,
+    )
+}
+
+fn catmat_item_node(i: ComprasGovItemMaterial) -> CatalogNode {
+    CatalogNode {
+        is_sustainable: Some(i.sustentavel),
+        ..node(
+            CatalogLevel::Item,
+            i.codigo_item,
+            Some(i.codigo_pdm),
+            &i.nome_item,
+            i.status,
+        )
+    }
+}
+
+fn catser_section_node(s: ComprasGovSectionService) -> CatalogNode {
+    node(
+        CatalogLevel::Section,
+        s.codigo_secao,
+        None,
+        &s.nome_secao,
+        s.status,
+    )
+}
+
+fn catser_division_node(d: ComprasGovDivisionService) -> CatalogNode {
+    node(
+        CatalogLevel::Division,
+        d.codigo_divisao,
+        Some(d.codigo_secao),
+        &d.nome_divisao,
+        d.status,
+    )
+}
+
+fn catser_group_node(g: ComprasGovGrupoServico) -> CatalogNode {
+    node(
+        CatalogLevel::Group,
+        g.codigo_grupo,
+        Some(g.codigo_divisao),
+        &g.nome_grupo,
+        g.status,
+    )
+}
+
+fn catser_class_node(c: ComprasGovClasseServico) -> CatalogNode {
+    node(
+        CatalogLevel::Class,
+        c.codigo_classe,
+        Some(c.codigo_grupo),
+        &c.nome_classe,
+        c.status,
+    )
+}
+
+fn catser_item_node(i: ComprasGovItemServico) -> CatalogNode {
+    let code_cpc = i
+        .codigo_cpc
+        .as_deref()
+        .map(str::trim)
+        .filter(|c| !c.is_empty())
+        .map(|c| c.chars().take(20).collect());
+    CatalogNode {
+        code_cpc,
+        ..node(
+            CatalogLevel::Item,
+            i.codigo_item,
+            Some(i.codigo_classe),
+            &i.nome_item,
+            i.status,
+        )
+    }
+}
+
+// ============================================================================
+// Diff
+// ============================================================================
+
+struct CatalogDiff {
+    report: CatalogSyncReport,
+    upserts: Vec<CatalogNode>,
+    deactivations: Vec<(CatalogLevel, String)>,
+}
+
+fn entry(node: &CatalogNode, fields: Vec<String>) -> CatalogSyncEntry {
+    CatalogSyncEntry {
+        level: node.level,
+        code: node.code.clone(),
+        name: node.name.clone(),
+        fields,
+        reason: None,
+    }
+}
+
+fn changed_fields(local: &CatalogNode, remote: &CatalogNode) -> Vec<String> {
+    let mut fields = Vec::new();
+    if local.name != remote.name {
+        fields.push("name".to_string());
+    }
+    if local.parent_code != remote.parent_code {
+        fields.push("parent".to_string());
+    }
+    if local.is_active != remote.is_active {
+        fields.push("is_active".to_string());
+    }
+    if local.is_sustainable != remote.is_sustainable {
+        fields.push("is_sustainable".to_string());
+    }
+    if local.code_cpc != remote.code_cpc {
+        fields.push("code_cpc".to_string());
+    }
+    fields
+}
+
+/// Compares the official nodes of the scope with the local ones.
+///
+/// `scope` holds the local subtree of the scope (deactivated when missing
+/// from `remote`); `outside` the local records with codes of `remote` found
+/// elsewhere in the hierarchy. Inactive official items that do not exist
+/// locally are not imported.
+fn diff_catalog(
+    catalog: CatalogKind,
+    remote: &[CatalogNode],
+    scope: &[CatalogNode],
+    outside: &[CatalogNode],
+) -> CatalogDiff {
+    let mut report = CatalogSyncReport::default();
+    let mut upserts = Vec::new();
+    let mut deactivations = Vec::new();
+
+    let local: HashMap<(CatalogLevel, &str), &CatalogNode> = scope
+        .iter()
+        .chain(outside)
+        .map(|n| ((n.level, n.code.as_str()), n))
+        .collect();
+
+    // Último registro de cada código, na ordem da hierarquia
+    let mut official: HashMap<(CatalogLevel, &str), &CatalogNode> = HashMap::new();
+    for n in remote {
+        official.insert((n.level, n.code.as_str()), n);
+    }
+    let mut ordered: Vec<&CatalogNode> = official.values().copied().collect();
+    ordered.sort_by(|a, b| {
+        let rank = |l: CatalogLevel| levels(catalog).iter().position(|x| *x == l);
+        rank(a.level)
+            .cmp(&rank(b.level))
+            .then_with(|| a.code.cmp(&b.code))
+    });
+
+    // Registros que existirão localmente após a aplicação
+    let mut present: HashSet<(CatalogLevel, String)> =
+        local.keys().map(|(l, c)| (*l, c.to_string())).collect();
+
+    for remote_node in ordered {
+        let mut node = remote_node.clone();
+
+        if let (Some(parent), Some(parent_code)) =
+            (parent_level(catalog, node.level), node.parent_code.clone())
+        {
+            if !present.contains(&(parent, parent_code.clone())) {
+                if parent_optional(catalog, node.level) {
+                    node.parent_code = None;
+                } else {
+                    report.skipped.push(CatalogSyncEntry {
+                        reason: Some(format!("Registro pai {} não encontrado", parent_code)),
+                        ..entry(&node, Vec::new())
+                    });
+                    continue;
+                }
+            }
+        }
+
+        match local.get(&(node.level, node.code.as_str())) {
+            None => {
+                if !node.is_active && node.level == CatalogLevel::Item {
+                    continue;
+                }
+                report.added.push(entry(&node, Vec::new()));
+            }
+            // Fundido em outro item: o código segue ocupado, mas o registro não
+            // volta a ser atualizado nem reativado
+            Some(existing) if existing.merged => {
+                report.skipped.push(CatalogSyncEntry {
+                    reason: Some("Item fundido em outro item do catálogo".to_string()),
+                    ..entry(&node, Vec::new())
+                });
+                continue;
+            }
+            Some(existing) => {
+                let fields = changed_fields(existing, &node);
+                if fields.is_empty() {
+                    continue;
+                }
+                if existing.is_active && !node.is_active {
+                    report.deactivated.push(entry(&node, fields));
+                } else {
+                    report.changed.push(entry(&node, fields));
+                }
+            }
+        }
+        present.insert((node.level, node.code.clone()));
+        upserts.push(node);
+    }
+
+    for node in scope {
+        if node.is_active && !official.contains_key(&(node.level, node.code.as_str())) {
+            report
+                .deactivated
+                .push(entry(node, vec!["is_active".to_string()]));
+            deactivations.push((node.level, node.code.clone()));
+        }
+    }
+
+    CatalogDiff {
+        report,
+        upserts,
+        deactivations,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn local(level: CatalogLevel, code: &str, parent: Option<&str>, name: &str) -> CatalogNode {
+        CatalogNode {
+            level,
+            code: code.to_string(),
+            parent_code: parent.map(str::to_string),
+            name: name.to_string(),
+            is_active: true,
+            is_sustainable: (level == CatalogLevel::Item).then_some(false),
+            code_cpc: None,
+            merged: false,
+        }
+    }
+
+    fn codes(entries: &[CatalogSyncEntry]) -> Vec<&str> {
+        entries.iter().map(|e| e.code.as_str()).collect()
+    }
+
+    #[test]
+    fn test_diff_reports_added_changed_and_deactivated() {
+        let scope = vec![
+            local(CatalogLevel::Pdm, "100", Some("10"), "CANETA"),
+            local(CatalogLevel::Item, "1", Some("100"), "CANETA AZUL"),
+            local(CatalogLevel::Item, "2", Some("100"), "CANETA PRETA"),
+            local(CatalogLevel::Item, "3", Some("100"), "CANETA VERDE"),
+        ];
+        let outside = vec![local(CatalogLevel::Class, "10", Some("7"), "ESCRITÓRIO")];
+        let mut renamed = local(
+            CatalogLevel::Item,
+            "1",
+            Some("100"),
+            "CANETA ESFEROGRÁFICA AZUL",
+        );
+        renamed.is_sustainable = Some(true);
+        let mut retired = local(CatalogLevel::Item, "2", Some("100"), "CANETA PRETA");
+        retired.is_active = false;
+        let remote = vec![
+            local(CatalogLevel::Class, "10", Some("7"), "ESCRITÓRIO"),
+            local(CatalogLevel::Pdm, "100", Some("10"), "CANETA"),
+            renamed,
+            retired,
+            local(CatalogLevel::Item, "4", Some("100"), "CANETA VERMELHA"),
+        ];
+
+        let diff = diff_catalog(CatalogKind::Catmat, &remote, &scope, &outside);
+
+        assert_eq!(codes(&diff.report.added), vec!["4"]);
+        assert_eq!(codes(&diff.report.changed), vec!["1"]);
+        assert_eq!(
+            diff.report.changed[0].fields,
+            vec!["name", "is_sustainable"]
+        );
+        assert_eq!(codes(&diff.report.deactivated), vec!["2", "3"]);
+        assert_eq!(
+            diff.deactivations,
+            vec![(CatalogLevel::Item, "3".to_string())]
+        );
+        // Inalterados (classe e PDM) não são regravados
+        let upserted: Vec<&str> = diff.upserts.iter().map(|n| n.code.as_str()).collect();
+        assert_eq!(upserted, vec!["1", "2", "4"]);
+    }
+
+    #[test]
+    fn test_diff_orders_parents_first_and_skips_orphans() {
+        let remote = vec![
+            local(CatalogLevel::Item, "1", Some("100"), "PAPEL A4"),
+            local(CatalogLevel::Pdm, "100", Some("10"), "PAPEL"),
+            local(CatalogLevel::Class, "10", Some("7"), "PAPELARIA"),
+            local(CatalogLevel::Group, "7", None, "EXPEDIENTE"),
+            local(CatalogLevel::Item, "2", Some("999"), "SEM PDM"),
+        ];
+
+        let diff = diff_catalog(CatalogKind::Catmat, &remote, &[], &[]);
+
+        let upserted: Vec<CatalogLevel> = diff.upserts.iter().map(|n| n.level).collect();
+        assert_eq!(
+            upserted,
+            vec![
+                CatalogLevel::Group,
+                CatalogLevel::Class,
+                CatalogLevel::Pdm,
+                CatalogLevel::Item
+            ]
+        );
+        assert_eq!(codes(&diff.report.skipped), vec!["2"]);
+        assert!(diff.report.skipped[0]
+            .reason
+            .as_deref()
+            .unwrap()
+            .contains("999"));
+    }
+
+    #[test]
+    fn test_diff_ignores_inactive_new_items_and_detaches_unknown_divisions() {
+        let mut inactive = local(CatalogLevel::Item, "5", Some("20"), "SERVIÇO EXTINTO");
+        inactive.is_active = false;
+        inactive.is_sustainable = None;
+        let remote = vec![
+            local(CatalogLevel::Group, "2", Some("55"), "LIMPEZA"),
+            local(CatalogLevel::Class, "20", Some("2"), "LIMPEZA PREDIAL"),
+            inactive,
+        ];
+
+        let diff = diff_catalog(CatalogKind::Catser, &remote, &[], &[]);
+
+        assert_eq!(codes(&diff.report.added), vec!["2", "20"]);
+        assert_eq!(diff.upserts[0].parent_code, None);
+        assert!(diff.report.skipped.is_empty());
+    }
+
+    #[test]
+    fn test_diff_leaves_merged_items_alone() {
+        let mut merged = local(CatalogLevel::Item, "1", Some("100"), "CANETA AZUL");
+        merged.is_active = false;
+        merged.merged = true;
+        let scope = vec![local(CatalogLevel::Pdm, "100", Some("10"), "CANETA"), merged];
+        let remote = vec![
+            local(CatalogLevel::Pdm, "100", Some("10"), "CANETA"),
+            local(CatalogLevel::Item, "1", Some("100"), "CANETA ESFEROGRÁFICA AZUL"),
+        ];
+
+        let outside = vec![local(CatalogLevel::Class, "10", Some("7"), "ESCRITÓRIO")];
+
+        let diff = diff_catalog(CatalogKind::Catmat, &remote, &scope, &outside);
+
+        assert_eq!(codes(&diff.report.skipped), vec!["1"]);
+        assert!(diff.report.changed.is_empty());
+        assert!(diff.upserts.is_empty());
+        assert!(diff.deactivations.is_empty());
+    }
+
+    #[test]
+    fn test_fit_name_trims_and_truncates_to_column_size() {
+        assert_eq!(fit_name(CatalogLevel::Group, "  MATERIAL  "), "MATERIAL");
+        assert_eq!(
+            fit_name(CatalogLevel::Class, &"É".repeat(400))
+                .chars()
+                .count(),
+            300
+        );
+        assert_eq!(fit_name(CatalogLevel::Item, &"x".repeat(600)).len(), 500);
+    }
+}
@@ -1,4 +1,5 @@
 use anyhow::{Context, Result};
+use core_services::secrets::SecretHandle;
 use reqwest::Client;
 use rust_decimal::Decimal;
 use serde::Deserialize;
@@ -88,11 +89,12 @@ struct ComprasnetEmpenhoResponse {
 pub struct ComprasnetEmpenhoClient {
     client: Client,
     base_url: String,
-    api_token: Option<String>,
+    /// Lido a cada requisição: um token rotacionado vale sem reinício
+    api_token: SecretHandle,
 }
 
 impl ComprasnetEmpenhoClient {
-    pub fn new(base_url: String, api_token: Option<String>) -> Result<Self> {
+    pub fn new(base_url: String, api_token: SecretHandle) -> Result<Self> {
         ssrf_validate(&base_url, COMPRASNET_ALLOWED_HOSTS)
             .context("SSRF validation failed for Comprasnet empenho base URL")?;
 
@@ -105,7 +107,7 @@ impl ComprasnetEmpenhoClient {
         Ok(Self { client, base_url, api_token })
     }
 
-    pub fn update_config(&mut self, base_url: String, api_token: Option<String>) -> Result<()> {
+    pub fn update_config(&mut self, base_url: String, api_token: SecretHandle) -> Result<()> {
         ssrf_validate(&base_url, COMPRASNET_ALLOWED_HOSTS)
             .context("SSRF validation failed for updated Comprasnet base URL")?;
         self.base_url = base_url;
@@ -127,7 +129,7 @@ impl ComprasnetEmpenhoClient {
         );
 
         let mut req = self.client.get(&url);
-        if let Some(ref token) = self.api_token {
+        if let Some(token) = self.api_token.get() {
             req = req.bearer_auth(token);
         }
 
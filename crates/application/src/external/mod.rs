@@ -1,11 +1,15 @@
+pub mod catalog_sync_service;
 pub mod circuit_breaker;
 pub mod compras_gov_client;
 pub mod comprasnet_empenho_client;
+pub mod oidc_client;
 pub mod siorg_client;
 pub mod siorg_sync_service;
 
+pub use catalog_sync_service::CatalogSyncService;
 pub use circuit_breaker::{CircuitBreaker, CircuitBreakerRegistry, CircuitBreakerSnapshot, CircuitState};
 pub use compras_gov_client::ComprasGovClient;
 pub use comprasnet_empenho_client::ComprasnetEmpenhoClient;
+pub use oidc_client::{IdTokenClaims, OidcClient, OidcDiscovery};
 pub use siorg_client::SiorgClient;
 pub use siorg_sync_service::{SiorgSyncService, SyncError, SyncSummary};
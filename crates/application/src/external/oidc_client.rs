@@ -0,0 +1,299 @@
+use anyhow::{Context, Result};
+use jsonwebtoken::{decode, decode_header, jwk::JwkSet, Algorithm, DecodingKey, Validation};
+use reqwest::Client;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+
+/// Tempo de vida do documento de descoberta e do JWKS em cache.
+const METADATA_TTL: Duration = Duration::from_secs(3600);
+
+/// Intervalo mínimo entre recargas do JWKS motivadas por `kid` desconhecido
+/// (rotação de chaves no IdP), para não amplificar tokens forjados.
+const JWKS_REFRESH_COOLDOWN: Duration = Duration::from_secs(60);
+
+/// Tolerância de relógio na validação de `exp`/`iat`.
+const CLOCK_SKEW_SECONDS: u64 = 60;
+
+/// Algoritmos aceitos no ID token. `none` e HMAC nunca são aceitos.
+const ALLOWED_ALGORITHMS: &[Algorithm] = &[
+    Algorithm::RS256,
+    Algorithm::RS384,
+    Algorithm::RS512,
+    Algorithm::PS256,
+    Algorithm::ES256,
+    Algorithm::ES384,
+    Algorithm::EdDSA,
+];
+
+/// Subconjunto do documento `/.well-known/openid-configuration`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct OidcDiscovery {
+    pub issuer: String,
+    pub authorization_endpoint: String,
+    pub token_endpoint: String,
+    pub jwks_uri: String,
+    #[serde(default)]
+    pub userinfo_endpoint: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OidcTokenResponse {
+    pub id_token: String,
+    #[serde(default)]
+    pub access_token: Option<String>,
+}
+
+/// Claims do ID token já validado (assinatura, `iss`, `aud`, `exp`, `nonce`).
+#[derive(Debug, Clone)]
+pub struct IdTokenClaims {
+    pub subject: String,
+    pub email: Option<String>,
+    pub email_verified: bool,
+    pub name: Option<String>,
+    pub preferred_username: Option<String>,
+    pub raw: HashMap<String, serde_json::Value>,
+}
+
+impl IdTokenClaims {
+    /// Valor textual de um claim arbitrário (ex.: o claim de CPF do IdP).
+    pub fn string_claim(&self, name: &str) -> Option<String> {
+        match self.raw.get(name)? {
+            serde_json::Value::String(s) => Some(s.clone()),
+            serde_json::Value::Number(n) => Some(n.to_string()),
+            _ => None,
+        }
+    }
+}
+
+struct CachedDiscovery {
+    document: OidcDiscovery,
+    fetched_at: Instant,
+}
+
+struct CachedJwks {
+    keys: JwkSet,
+    fetched_at: Instant,
+}
+
+/// Cliente de Relying Party OpenID Connect.
+///
+/// Mantém em cache, por emissor, o documento de descoberta e o JWKS. Um `kid`
+/// ausente do cache força uma nova busca do JWKS (respeitando o cooldown).
+pub struct OidcClient {
+    client: Client,
+    discovery: RwLock<HashMap<String, CachedDiscovery>>,
+    jwks: RwLock<HashMap<String, CachedJwks>>,
+}
+
+impl OidcClient {
+    pub fn new() -> Result<Self> {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(15))
+            .connect_timeout(Duration::from_secs(5))
+            .build()
+            .context("Failed to build HTTP client")?;
+
+        Ok(Self {
+            client,
+            discovery: RwLock::new(HashMap::new()),
+            jwks: RwLock::new(HashMap::new()),
+        })
+    }
+
+    /// Documento de descoberta do emissor (em cache por `METADATA_TTL`).
+    pub async fn discover(&self, issuer: &str) -> Result<OidcDiscovery> {
+        if let Some(cached) = self.discovery.read().await.get(issuer) {
+            if cached.fetched_at.elapsed() < METADATA_TTL {
+                return Ok(cached.document.clone());
+            }
+        }
+
+        let url = format!(
+            "{}/.well-known/openid-configuration",
+            issuer.trim_end_matches('/')
+        );
+        let document: OidcDiscovery = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .with_context(|| format!("OIDC discovery request failed: {}", url))?
+            .error_for_status()
+            .context("OIDC discovery returned an error status")?
+            .json()
+            .await
+            .context("Invalid OIDC discovery document")?;
+
+        // OIDC Discovery §4.3: o emissor anunciado deve ser idêntico ao configurado
+        if document.issuer.trim_end_matches('/') != issuer.trim_end_matches('/') {
+            anyhow::bail!(
+                "OIDC issuer mismatch: configured '{}', discovered '{}'",
+                issuer,
+                document.issuer
+            );
+        }
+
+        self.discovery.write().await.insert(
+            issuer.to_string(),
+            CachedDiscovery {
+                document: document.clone(),
+                fetched_at: Instant::now(),
+            },
+        );
+        Ok(document)
+    }
+
+    /// Troca o authorization code pelo ID token (PKCE `code_verifier`).
+    ///
+    /// Com `client_secret`, autentica via `client_secret_basic`; sem ele, o
+    /// cliente é público e envia apenas `client_id`.
+    pub async fn exchange_code(
+        &self,
+        discovery: &OidcDiscovery,
+        client_id: &str,
+        client_secret: Option<&str>,
+        redirect_uri: &str,
+        code: &str,
+        code_verifier: &str,
+    ) -> Result<OidcTokenResponse> {
+        let form = [
+            ("grant_type", "authorization_code"),
+            ("code", code),
+            ("redirect_uri", redirect_uri),
+            ("code_verifier", code_verifier),
+            ("client_id", client_id),
+        ];
+
+        let mut request = self.client.post(&discovery.token_endpoint).form(&form);
+        if let Some(secret) = client_secret {
+            request = request.basic_auth(client_id, Some(secret));
+        }
+
+        let response = request
+            .send()
+            .await
+            .context("OIDC token request failed")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!("OIDC token endpoint returned {}: {}", status, body);
+        }
+
+        response
+            .json()
+            .await
+            .context("Invalid OIDC token response")
+    }
+
+    /// Valida assinatura e claims do ID token e confere o `nonce`.
+    pub async fn verify_id_token(
+        &self,
+        discovery: &OidcDiscovery,
+        client_id: &str,
+        id_token: &str,
+        expected_nonce: &str,
+    ) -> Result<IdTokenClaims> {
+        let header = decode_header(id_token).context("Malformed ID token header")?;
+        if !ALLOWED_ALGORITHMS.contains(&header.alg) {
+            anyhow::bail!("ID token algorithm {:?} is not allowed", header.alg);
+        }
+
+        let key = self
+            .decoding_key(&discovery.jwks_uri, header.kid.as_deref())
+            .await?;
+
+        let mut validation = Validation::new(header.alg);
+        validation.set_issuer(&[discovery.issuer.as_str()]);
+        validation.set_audience(&[client_id]);
+        validation.set_required_spec_claims(&["exp", "iss", "aud", "sub"]);
+        validation.leeway = CLOCK_SKEW_SECONDS;
+
+        let data = decode::<HashMap<String, serde_json::Value>>(id_token, &key, &validation)
+            .context("ID token validation failed")?;
+        let raw = data.claims;
+
+        let nonce = raw.get("nonce").and_then(|v| v.as_str());
+        if nonce != Some(expected_nonce) {
+            anyhow::bail!("ID token nonce mismatch");
+        }
+
+        let subject = raw
+            .get("sub")
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+            .ok_or_else(|| anyhow::anyhow!("ID token without subject"))?
+            .to_string();
+
+        let text = |name: &str| raw.get(name).and_then(|v| v.as_str()).map(str::to_string);
+        // Alguns IdPs enviam `email_verified` como string "true"
+        let email_verified = match raw.get("email_verified") {
+            Some(serde_json::Value::Bool(b)) => *b,
+            Some(serde_json::Value::String(s)) => s.eq_ignore_ascii_case("true"),
+            _ => false,
+        };
+
+        Ok(IdTokenClaims {
+            subject,
+            email: text("email"),
+            email_verified,
+            name: text("name"),
+            preferred_username: text("preferred_username"),
+            raw,
+        })
+    }
+
+    async fn decoding_key(&self, jwks_uri: &str, kid: Option<&str>) -> Result<DecodingKey> {
+        {
+            let cache = self.jwks.read().await;
+            if let Some(entry) = cache.get(jwks_uri) {
+                let age = entry.fetched_at.elapsed();
+                match select_jwk(&entry.keys, kid) {
+                    Some(jwk) if age < METADATA_TTL => {
+                        return DecodingKey::from_jwk(jwk).context("Unsupported JWK");
+                    }
+                    None if age < JWKS_REFRESH_COOLDOWN => {
+                        anyhow::bail!("Signing key '{}' not found in JWKS", kid.unwrap_or("-"));
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let keys: JwkSet = self
+            .client
+            .get(jwks_uri)
+            .send()
+            .await
+            .context("JWKS request failed")?
+            .error_for_status()
+            .context("JWKS endpoint returned an error status")?
+            .json()
+            .await
+            .context("Invalid JWKS document")?;
+
+        // Guarda antes de escolher a chave: um `kid` desconhecido também
+        // respeita o intervalo mínimo entre buscas
+        let mut cache = self.jwks.write().await;
+        let entry = cache.entry(jwks_uri.to_string()).insert_entry(CachedJwks {
+            keys,
+            fetched_at: Instant::now(),
+        });
+
+        select_jwk(&entry.get().keys, kid)
+            .map(DecodingKey::from_jwk)
+            .ok_or_else(|| anyhow::anyhow!("Signing key '{}' not found in JWKS", kid.unwrap_or("-")))?
+            .context("Unsupported JWK")
+    }
+}
+
+/// Chave pelo `kid`; sem `kid` no cabeçalho, só aceita JWKS com chave única.
+fn select_jwk<'a>(keys: &'a JwkSet, kid: Option<&str>) -> Option<&'a jsonwebtoken::jwk::Jwk> {
+    match kid {
+        Some(kid) => keys.find(kid),
+        None if keys.keys.len() == 1 => keys.keys.first(),
+        None => None,
+    }
+}
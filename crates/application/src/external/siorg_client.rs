@@ -1,4 +1,5 @@
 use anyhow::{Context, Result};
+use core_services::secrets::SecretHandle;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
@@ -557,12 +558,14 @@ pub struct SiorgAlteradasResponse {
 pub struct SiorgClient {
     client: Client,
     base_url: String,
+    /// Lido a cada requisição: um token rotacionado vale sem reinício
+    api_token: SecretHandle,
 }
 
 impl SiorgClient {
     /// Cria um novo cliente da API SIORG.
-    /// `_api_token` é aceito por compatibilidade, mas a API é pública e não exige autenticação.
-    pub fn new(base_url: String, _api_token: Option<String>) -> Result<Self> {
+    /// A API é pública; o token só é enviado quando configurado.
+    pub fn new(base_url: String, api_token: SecretHandle) -> Result<Self> {
         ssrf_validate(&base_url, SIORG_ALLOWED_HOSTS)
             .context("SSRF validation failed for SIORG_API_URL")?;
 
@@ -572,7 +575,19 @@ impl SiorgClient {
             .build()
             .context("Failed to build HTTP client")?;
 
-        Ok(Self { client, base_url })
+        Ok(Self {
+            client,
+            base_url,
+            api_token,
+        })
+    }
+
+    fn get(&self, url: &str) -> reqwest::RequestBuilder {
+        let request = self.client.get(url);
+        match self.api_token.get() {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        }
     }
 
     // ========================================================================
@@ -593,7 +608,6 @@ impl SiorgClient {
         );
 
         let response = self
-            .client
             .get(&url)
             .send()
             .await
@@ -650,7 +664,6 @@ impl SiorgClient {
         );
 
         let response = self
-            .client
             .get(&url)
             .send()
             .await
@@ -700,7 +713,6 @@ impl SiorgClient {
         );
 
         let response = self
-            .client
             .get(&url)
             .send()
             .await
@@ -742,7 +754,6 @@ impl SiorgClient {
         );
 
         let response = self
-            .client
             .get(&url)
             .send()
             .await
@@ -815,7 +826,6 @@ impl SiorgClient {
         );
 
         let response = self
-            .client
             .get(&url)
             .send()
             .await
@@ -849,7 +859,6 @@ impl SiorgClient {
         let url = format!("{}/tipo-unidade", self.base_url);
 
         let response = self
-            .client
             .get(&url)
             .timeout(Duration::from_secs(5))
             .send()
@@ -863,7 +872,7 @@ impl SiorgClient {
     pub async fn get_category_metadata(&self, id_or_uri: &str) -> Result<String> {
         let id = id_or_uri.rsplit('/').next().unwrap_or(id_or_uri);
         let url = format!("{}/categoria-unidade/{}", self.base_url, id);
-        let resp: serde_json::Value = self.client.get(&url).send().await?.json().await?;
+        let resp: serde_json::Value = self.get(&url).send().await?.json().await?;
         // A API SIORG usa "descricaoCategoriaUnidade" dentro de "categoriaUnidade"
         let name = resp
             .get("categoriaUnidade")
@@ -878,7 +887,7 @@ impl SiorgClient {
     pub async fn get_unit_type_metadata(&self, id_or_uri: &str) -> Result<String> {
         let id = id_or_uri.rsplit('/').next().unwrap_or(id_or_uri);
         let url = format!("{}/tipo-unidade/{}", self.base_url, id);
-        let resp: serde_json::Value = self.client.get(&url).send().await?.json().await?;
+        let resp: serde_json::Value = self.get(&url).send().await?.json().await?;
         // A API SIORG usa "descricaoTipoUnidade" dentro de "tipoUnidade"
         let name = resp
             .get("tipoUnidade")
@@ -898,7 +907,6 @@ impl SiorgClient {
     pub async fn get_all_tipo_unidade(&self) -> Result<Vec<SiorgTipoUnidade>> {
         let url = format!("{}/tipo-unidade", self.base_url);
         let body = self
-            .client
             .get(&url)
             .send()
             .await
@@ -930,7 +938,6 @@ impl SiorgClient {
     pub async fn get_all_categoria_unidade(&self) -> Result<Vec<SiorgCategoriaUnidade>> {
         let url = format!("{}/categoria-unidade", self.base_url);
         let body = self
-            .client
             .get(&url)
             .send()
             .await
@@ -962,7 +969,6 @@ impl SiorgClient {
     pub async fn get_all_natureza_juridica(&self) -> Result<Vec<SiorgNaturezaJuridica>> {
         let url = format!("{}/natureza-juridica", self.base_url);
         let body = self
-            .client
             .get(&url)
             .send()
             .await
@@ -981,7 +987,6 @@ impl SiorgClient {
     pub async fn get_all_poder(&self) -> Result<Vec<SiorgPoder>> {
         let url = format!("{}/poder", self.base_url);
         let body = self
-            .client
             .get(&url)
             .send()
             .await
@@ -1000,7 +1005,6 @@ impl SiorgClient {
     pub async fn get_all_esfera(&self) -> Result<Vec<SiorgEsfera>> {
         let url = format!("{}/esfera", self.base_url);
         let body = self
-            .client
             .get(&url)
             .send()
             .await
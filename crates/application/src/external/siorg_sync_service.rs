@@ -1,4 +1,5 @@
 use super::siorg_client::{SiorgClient, SiorgUnidadeCompleta};
+use domain::errors::RepositoryError;
 pub use domain::models::{
     ActivityArea, ContactInfo, CreateHistoryItemPayload, CreateOrganizationPayload,
     CreateOrganizationalUnitCategoryPayload, CreateOrganizationalUnitTypePayload,
@@ -7,6 +8,10 @@ pub use domain::models::{
     OrganizationalUnitDto, OrganizationalUnitTypeDto, SiorgChangeType, SiorgEntityType,
     SiorgUpsertPayload, SyncSummary, UpdateOrganizationPayload, UpdateSystemSettingPayload,
 };
+use domain::models::{
+    ApplySiorgPreviewPayload, NewSiorgChangePreview, SiorgChangePreviewDto, SiorgDiffDecision,
+    SiorgDiffKind, SiorgLocalUnit, SiorgPreviewStatus, SiorgUnitDiff,
+};
 use domain::ports::*;
 use std::collections::{hash_map::Entry, HashMap, HashSet};
 use std::sync::Arc;
@@ -25,6 +30,10 @@ fn versao_setting_key(org_siorg_code: i32) -> String {
     format!("{}:{}", SIORG_VERSAO_KEY_PREFIX, org_siorg_code)
 }
 
+/// Quando `true`, o sync incremental agendado gera uma prévia para aprovação
+/// em vez de aplicar as alterações.
+const REQUIRE_CHANGE_APPROVAL_KEY: &str = "siorg.require_change_approval";
+
 pub struct SiorgSyncService {
     siorg_client: Arc<SiorgClient>,
     organization_repo: Arc<dyn OrganizationRepositoryPort>,
@@ -37,6 +46,7 @@ pub struct SiorgSyncService {
     esfera_repo: Arc<dyn SiorgEsferaRepositoryPort>,
     history_repo: Arc<dyn SiorgHistoryRepositoryPort>,
     pool: sqlx::PgPool,
+    change_previews: Option<Arc<dyn SiorgChangePreviewRepositoryPort>>,
 }
 
 impl SiorgSyncService {
@@ -66,9 +76,16 @@ impl SiorgSyncService {
             esfera_repo,
             history_repo,
             pool,
+            change_previews: None,
         }
     }
 
+    /// Habilita a prévia das alterações de estrutura com aprovação seletiva
+    pub fn with_change_previews(mut self, repo: Arc<dyn SiorgChangePreviewRepositoryPort>) -> Self {
+        self.change_previews = Some(repo);
+        self
+    }
+
     // ========================================================================
     // Basic Table Sync (tipo-unidade, categoria-unidade, natureza-juridica, poder, esfera)
     // ========================================================================
@@ -585,6 +602,17 @@ impl SiorgSyncService {
                     org_siorg_code, from
                 );
             }
+            // Cenário B com aprovação obrigatória -> Gera a prévia e não aplica nada
+            Some(from) if !base_vazia && self.requires_change_approval().await => {
+                self.queue_preview_for_review(
+                    org_id,
+                    org_siorg_code,
+                    &from,
+                    &versao_api.versao_consulta,
+                    summary,
+                )
+                .await?;
+            }
             // Cenário B: Existe versão anterior e dados locais -> Executa Sync Incremental
             Some(from) if !base_vazia => {
                 info!(
@@ -819,6 +847,275 @@ impl SiorgSyncService {
         Ok(summary)
     }
 
+    // ========================================================================
+    // Change Preview (dry-run com aprovação seletiva)
+    // ========================================================================
+
+    fn previews(&self) -> Result<&Arc<dyn SiorgChangePreviewRepositoryPort>, SyncError> {
+        self.change_previews.as_ref().ok_or_else(|| {
+            SyncError::InvalidRequest("Prévia de alterações do SIORG não habilitada".to_string())
+        })
+    }
+
+    /// Lê `siorg.require_change_approval`; ausente equivale a `false`.
+    pub async fn requires_change_approval(&self) -> bool {
+        self.change_previews.is_some()
+            && self
+                .settings_repo
+                .get(REQUIRE_CHANGE_APPROVAL_KEY)
+                .await
+                .ok()
+                .flatten()
+                .and_then(|s| s.value.as_bool())
+                .unwrap_or(false)
+    }
+
+    /// Sync agendado com aprovação obrigatória: guarda a prévia para o
+    /// administrador, a menos que já exista uma pendente para a mesma versão.
+    async fn queue_preview_for_review(
+        &self,
+        org_id: Uuid,
+        org_siorg_code: i32,
+        from_versao: &str,
+        to_versao: &str,
+        summary: &mut SyncSummary,
+    ) -> Result<(), SyncError> {
+        let repo = self.previews()?;
+        let (pending, _) = repo
+            .list(Some(org_id), Some(SiorgPreviewStatus::Pending), 1, 0)
+            .await
+            .map_err(|e| SyncError::DatabaseError(e.to_string()))?;
+        if pending.first().is_some_and(|p| p.to_version == to_versao) {
+            info!(
+                "Org {}: prévia da versão {} já aguarda aprovação.",
+                org_siorg_code, to_versao
+            );
+            summary.pending_review += 1;
+            return Ok(());
+        }
+
+        let preview = self
+            .build_preview(org_id, org_siorg_code, from_versao, to_versao, None)
+            .await?;
+
+        // Nada a revisar (ex.: só extinções de unidades já inativas)
+        if preview.entries.is_empty() {
+            self.save_versao(org_siorg_code, to_versao).await;
+            return Ok(());
+        }
+
+        let created = repo
+            .create(&preview)
+            .await
+            .map_err(|e| SyncError::DatabaseError(e.to_string()))?;
+        info!(
+            "Org {}: prévia {} com {} alteração(ões) aguardando aprovação ({} → {}).",
+            org_siorg_code, created.id, created.total_changes, from_versao, to_versao
+        );
+        summary.pending_review += 1;
+        Ok(())
+    }
+
+    /// Calcula as alterações publicadas pelo SIORG desde a versão sincronizada
+    /// e guarda a prévia, sem gravar nada nas unidades.
+    pub async fn preview_changes(
+        &self,
+        org_id: Uuid,
+        created_by: Option<Uuid>,
+    ) -> Result<SiorgChangePreviewDto, SyncError> {
+        let repo = self.previews()?;
+        let org = self
+            .organization_repo
+            .find_by_id(org_id)
+            .await
+            .map_err(|e| SyncError::DatabaseError(e.to_string()))?
+            .ok_or_else(|| SyncError::NotFound("Organização não encontrada".to_string()))?;
+        if org.siorg_code <= 0 {
+            return Err(SyncError::InvalidRequest(
+                "Organização sem código SIORG".to_string(),
+            ));
+        }
+
+        let from = self
+            .get_stored_versao(org.siorg_code)
+            .await
+            .ok_or_else(|| {
+                SyncError::InvalidRequest(
+                    "Organização nunca sincronizada; execute a carga completa antes da prévia"
+                        .to_string(),
+                )
+            })?;
+        let versao_api = self
+            .siorg_client
+            .get_versao(org.siorg_code)
+            .await
+            .map_err(|e| SyncError::ApiError(format!("{:#}", e)))?;
+        if versao_api.versao_consulta == from {
+            return Err(SyncError::InvalidRequest(format!(
+                "Estrutura já sincronizada na versão {}",
+                from
+            )));
+        }
+
+        let preview = self
+            .build_preview(
+                org.id,
+                org.siorg_code,
+                &from,
+                &versao_api.versao_consulta,
+                created_by,
+            )
+            .await?;
+
+        repo.create(&preview)
+            .await
+            .map_err(|e| SyncError::DatabaseError(e.to_string()))
+    }
+
+    async fn build_preview(
+        &self,
+        org_id: Uuid,
+        org_siorg_code: i32,
+        from_versao: &str,
+        to_versao: &str,
+        created_by: Option<Uuid>,
+    ) -> Result<NewSiorgChangePreview, SyncError> {
+        let repo = self.previews()?;
+        let units: Vec<SiorgUnidadeCompleta> = self
+            .siorg_client
+            .get_alteradas(org_siorg_code, from_versao)
+            .await
+            .map_err(|e| SyncError::ApiError(format!("{:#}", e)))?
+            .into_iter()
+            .map(SiorgUnidadeCompleta::from)
+            .filter(|u| u.siorg_code().is_some() && u.siorg_code() != Some(org_siorg_code))
+            .collect();
+
+        let local: HashMap<i32, SiorgLocalUnit> = repo
+            .list_local_units(org_id)
+            .await
+            .map_err(|e| SyncError::DatabaseError(e.to_string()))?
+            .into_iter()
+            .map(|u| (u.siorg_code, u))
+            .collect();
+
+        let mut entries = diff_units(&units, &local);
+
+        let unit_ids: Vec<Uuid> = entries.iter().filter_map(|e| e.local_unit_id).collect();
+        let references = repo
+            .count_references(&unit_ids)
+            .await
+            .map_err(|e| SyncError::DatabaseError(e.to_string()))?;
+        for entry in &mut entries {
+            if let Some(refs) = entry.local_unit_id.and_then(|id| references.get(&id)) {
+                entry.references = *refs;
+                entry.flagged = true;
+            }
+        }
+
+        Ok(NewSiorgChangePreview {
+            organization_id: org_id,
+            org_siorg_code,
+            from_version: from_versao.to_string(),
+            to_version: to_versao.to_string(),
+            entries,
+            created_by,
+        })
+    }
+
+    /// Aplica as entradas aprovadas de uma prévia pendente; as demais ficam
+    /// rejeitadas. A versão avança para a da prévia, então as rejeitadas não
+    /// voltam no próximo sync incremental.
+    pub async fn apply_preview(
+        &self,
+        id: Uuid,
+        payload: ApplySiorgPreviewPayload,
+        decided_by: Option<Uuid>,
+    ) -> Result<SiorgChangePreviewDto, SyncError> {
+        let repo = self.previews()?;
+        let preview = repo
+            .find_by_id(id)
+            .await
+            .map_err(|e| SyncError::DatabaseError(e.to_string()))?
+            .ok_or_else(|| SyncError::NotFound("Prévia não encontrada".to_string()))?;
+        if preview.status != SiorgPreviewStatus::Pending {
+            return Err(SyncError::Conflict(
+                "A prévia já foi aplicada ou descartada".to_string(),
+            ));
+        }
+
+        let stored = self.get_stored_versao(preview.org_siorg_code).await;
+        if stored.as_deref() != Some(preview.from_version.as_str()) {
+            return Err(SyncError::Conflict(
+                "A estrutura foi sincronizada depois da prévia; gere uma nova prévia".to_string(),
+            ));
+        }
+
+        let mut entries = preview.entries.unwrap_or_default();
+        decide_entries(&mut entries, &payload)?;
+
+        let units = entries
+            .iter()
+            .filter(|e| e.decision == SiorgDiffDecision::Approved)
+            .map(|e| serde_json::from_value::<SiorgUnidadeCompleta>(e.upstream.clone()))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| SyncError::DatabaseError(format!("Prévia corrompida: {}", e)))?;
+
+        let applied = units.len();
+        if !units.is_empty() {
+            self.execute_bulk_sync(preview.organization_id, units, preview.org_siorg_code)
+                .await?;
+        }
+        self.save_versao(preview.org_siorg_code, &preview.to_version)
+            .await;
+
+        info!(
+            "Prévia {} aplicada na org {}: {} de {} alteração(ões) aprovada(s).",
+            id,
+            preview.org_siorg_code,
+            applied,
+            entries.len()
+        );
+
+        repo.finish(id, SiorgPreviewStatus::Applied, Some(&entries), decided_by)
+            .await
+            .map_err(preview_repo_error)
+    }
+
+    /// Descarta uma prévia pendente sem aplicar nada; a versão sincronizada
+    /// continua a mesma.
+    pub async fn discard_preview(
+        &self,
+        id: Uuid,
+        decided_by: Option<Uuid>,
+    ) -> Result<SiorgChangePreviewDto, SyncError> {
+        self.previews()?
+            .finish(id, SiorgPreviewStatus::Discarded, None, decided_by)
+            .await
+            .map_err(preview_repo_error)
+    }
+
+    pub async fn get_preview(&self, id: Uuid) -> Result<SiorgChangePreviewDto, SyncError> {
+        self.previews()?
+            .find_by_id(id)
+            .await
+            .map_err(|e| SyncError::DatabaseError(e.to_string()))?
+            .ok_or_else(|| SyncError::NotFound("Prévia não encontrada".to_string()))
+    }
+
+    pub async fn list_previews(
+        &self,
+        organization_id: Option<Uuid>,
+        status: Option<SiorgPreviewStatus>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<SiorgChangePreviewDto>, i64), SyncError> {
+        self.previews()?
+            .list(organization_id, status, limit, offset)
+            .await
+            .map_err(|e| SyncError::DatabaseError(e.to_string()))
+    }
+
     // ========================================================================
     // Version Storage (via SystemSettings)
     // ========================================================================
@@ -1030,4 +1327,310 @@ pub enum SyncError {
 
     #[error("Conflict: {0}")]
     Conflict(String),
+
+    #[error("Not found: {0}")]
+    NotFound(String),
+
+    #[error("Invalid request: {0}")]
+    InvalidRequest(String),
+}
+
+impl From<&SyncError> for http::StatusCode {
+    fn from(err: &SyncError) -> Self {
+        match err {
+            SyncError::NotFound(_) | SyncError::NotFoundInSiorg(_) => http::StatusCode::NOT_FOUND,
+            SyncError::InvalidRequest(_) | SyncError::MissingRequiredField(_) => {
+                http::StatusCode::BAD_REQUEST
+            }
+            SyncError::Conflict(_) => http::StatusCode::CONFLICT,
+            SyncError::ApiError(_) => http::StatusCode::BAD_GATEWAY,
+            SyncError::DatabaseError(_) => http::StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+fn preview_repo_error(e: RepositoryError) -> SyncError {
+    match e {
+        RepositoryError::NotFound => SyncError::NotFound("Prévia não encontrada".to_string()),
+        RepositoryError::OptimisticLockConflict(msg) => SyncError::Conflict(msg),
+        e => SyncError::DatabaseError(e.to_string()),
+    }
+}
+
+/// Compara as unidades publicadas pelo SIORG com as locais (por código SIORG).
+/// Extinções de unidades inexistentes ou já inativas ficam de fora.
+fn diff_units(
+    units: &[SiorgUnidadeCompleta],
+    local: &HashMap<i32, SiorgLocalUnit>,
+) -> Vec<SiorgUnitDiff> {
+    let mut entries: Vec<SiorgUnitDiff> = units
+        .iter()
+        .filter_map(|unit| {
+            let siorg_code = unit.siorg_code()?;
+            let is_deletion = unit.base.is_exclusao();
+            let current = local.get(&siorg_code);
+
+            let mut kinds = Vec::new();
+            match current {
+                None if is_deletion => return None,
+                None => kinds.push(SiorgDiffKind::Created),
+                Some(c) if is_deletion => {
+                    if !c.is_active {
+                        return None;
+                    }
+                    kinds.push(SiorgDiffKind::Deactivated);
+                }
+                Some(c) => {
+                    if !c.is_active {
+                        kinds.push(SiorgDiffKind::Reactivated);
+                    }
+                    if c.name != unit.base.nome || c.acronym != unit.base.sigla {
+                        kinds.push(SiorgDiffKind::Renamed);
+                    }
+                    if c.siorg_parent_code != unit.parent_siorg_code() {
+                        kinds.push(SiorgDiffKind::Moved);
+                    }
+                    if kinds.is_empty() {
+                        kinds.push(SiorgDiffKind::Details);
+                    }
+                }
+            }
+
+            Some(SiorgUnitDiff {
+                siorg_code,
+                local_unit_id: current.map(|c| c.id),
+                kinds,
+                previous_name: current.map(|c| c.name.clone()),
+                name: unit.base.nome.clone(),
+                previous_acronym: current.and_then(|c| c.acronym.clone()),
+                acronym: unit.base.sigla.clone(),
+                previous_parent_code: current.and_then(|c| c.siorg_parent_code),
+                parent_code: unit.parent_siorg_code(),
+                is_active: !is_deletion,
+                references: Default::default(),
+                flagged: false,
+                decision: SiorgDiffDecision::Pending,
+                upstream: serde_json::to_value(unit).unwrap_or_default(),
+            })
+        })
+        .collect();
+
+    // A mesma unidade pode aparecer mais de uma vez entre as alteradas; vale a última
+    let mut seen = HashSet::new();
+    entries.reverse();
+    entries.retain(|e| seen.insert(e.siorg_code));
+    entries.reverse();
+    entries
+}
+
+/// Marca cada entrada como aprovada ou rejeitada. Uma unidade aprovada não
+/// pode ficar subordinada a uma unidade nova que foi rejeitada.
+fn decide_entries(
+    entries: &mut [SiorgUnitDiff],
+    payload: &ApplySiorgPreviewPayload,
+) -> Result<(), SyncError> {
+    let approved: HashSet<i32> = if payload.approve_all {
+        entries.iter().map(|e| e.siorg_code).collect()
+    } else {
+        payload.siorg_codes.iter().copied().collect()
+    };
+
+    let known: HashSet<i32> = entries.iter().map(|e| e.siorg_code).collect();
+    if let Some(code) = approved.iter().find(|c| !known.contains(c)) {
+        return Err(SyncError::InvalidRequest(format!(
+            "A unidade SIORG {} não faz parte da prévia",
+            code
+        )));
+    }
+
+    let rejected_new: HashSet<i32> = entries
+        .iter()
+        .filter(|e| e.kinds.contains(&SiorgDiffKind::Created) && !approved.contains(&e.siorg_code))
+        .map(|e| e.siorg_code)
+        .collect();
+    if let Some(orphan) = entries.iter().find(|e| {
+        approved.contains(&e.siorg_code) && e.parent_code.is_some_and(|p| rejected_new.contains(&p))
+    }) {
+        return Err(SyncError::InvalidRequest(format!(
+            "A unidade SIORG {} depende da criação da unidade {}, que não foi aprovada",
+            orphan.siorg_code,
+            orphan.parent_code.unwrap_or_default()
+        )));
+    }
+
+    for entry in entries.iter_mut() {
+        entry.decision = if approved.contains(&entry.siorg_code) {
+            SiorgDiffDecision::Approved
+        } else {
+            SiorgDiffDecision::Rejected
+        };
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::external::siorg_client::SiorgUnidade;
+
+    fn upstream(
+        code: i32,
+        parent: Option<i32>,
+        nome: &str,
+        operacao: &str,
+    ) -> SiorgUnidadeCompleta {
+        SiorgUnidadeCompleta {
+            base: SiorgUnidade {
+                codigo_unidade: code.to_string(),
+                codigo_unidade_pai: parent.map(|p| p.to_string()),
+                codigo_orgao_entidade: None,
+                codigo_tipo_unidade: None,
+                nome: nome.to_string(),
+                sigla: None,
+                codigo_esfera: None,
+                codigo_poder: None,
+                nivel_normatizacao: None,
+                versao_consulta: None,
+                operacao: Some(operacao.to_string()),
+            },
+            codigo_categoria_unidade: None,
+            area_atuacao: None,
+            competencia: None,
+            missao: None,
+            contato: None,
+            endereco: None,
+        }
+    }
+
+    fn local(code: i32, parent: Option<i32>, name: &str, is_active: bool) -> (i32, SiorgLocalUnit) {
+        (
+            code,
+            SiorgLocalUnit {
+                id: Uuid::new_v4(),
+                siorg_code: code,
+                name: name.to_string(),
+                acronym: None,
+                siorg_parent_code: parent,
+                is_active,
+            },
+        )
+    }
+
+    fn entry(code: i32, parent: Option<i32>, kinds: Vec<SiorgDiffKind>) -> SiorgUnitDiff {
+        let mut entry = diff_units(
+            &[upstream(code, parent, "Unidade", "INCLUSAO")],
+            &HashMap::new(),
+        )
+        .remove(0);
+        entry.kinds = kinds;
+        entry
+    }
+
+    #[test]
+    fn test_diff_units_classifies_changes() {
+        let local_units = HashMap::from([
+            local(10, Some(1), "Pró-Reitoria de Gestão", true),
+            local(20, Some(1), "Departamento de Compras", true),
+            local(30, Some(1), "Coordenação Extinta", false),
+            local(40, Some(1), "Setor Inativo", false),
+        ]);
+        let units = vec![
+            upstream(10, Some(2), "Pró-Reitoria de Administração", "ALTERACAO"),
+            upstream(20, Some(1), "Departamento de Compras", "EXCLUSAO"),
+            upstream(30, Some(1), "Coordenação Extinta", "EXCLUSAO"),
+            upstream(40, Some(1), "Setor Inativo", "ALTERACAO"),
+            upstream(50, Some(10), "Nova Coordenação", "INCLUSAO"),
+            upstream(60, Some(1), "Nunca Existiu", "EXTINCAO"),
+        ];
+
+        let entries = diff_units(&units, &local_units);
+        let kinds = |code: i32| {
+            entries
+                .iter()
+                .find(|e| e.siorg_code == code)
+                .map(|e| e.kinds.clone())
+        };
+
+        assert_eq!(
+            kinds(10),
+            Some(vec![SiorgDiffKind::Renamed, SiorgDiffKind::Moved])
+        );
+        assert_eq!(kinds(20), Some(vec![SiorgDiffKind::Deactivated]));
+        assert_eq!(kinds(30), None);
+        assert_eq!(kinds(40), Some(vec![SiorgDiffKind::Reactivated]));
+        assert_eq!(kinds(50), Some(vec![SiorgDiffKind::Created]));
+        assert_eq!(kinds(60), None);
+
+        let created = entries.iter().find(|e| e.siorg_code == 50).unwrap();
+        assert!(created.local_unit_id.is_none());
+        let roundtrip: SiorgUnidadeCompleta =
+            serde_json::from_value(created.upstream.clone()).unwrap();
+        assert_eq!(roundtrip.parent_siorg_code(), Some(10));
+    }
+
+    #[test]
+    fn test_diff_units_keeps_last_change_of_a_unit() {
+        let units = vec![
+            upstream(70, Some(1), "Primeiro Nome", "INCLUSAO"),
+            upstream(70, Some(1), "Segundo Nome", "ALTERACAO"),
+        ];
+
+        let entries = diff_units(&units, &HashMap::new());
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].name, "Segundo Nome");
+    }
+
+    #[test]
+    fn test_decide_entries_selective_approval() {
+        let mut entries = vec![
+            entry(10, Some(1), vec![SiorgDiffKind::Renamed]),
+            entry(20, Some(1), vec![SiorgDiffKind::Deactivated]),
+        ];
+        let payload = ApplySiorgPreviewPayload {
+            approve_all: false,
+            siorg_codes: vec![20],
+        };
+
+        decide_entries(&mut entries, &payload).unwrap();
+
+        assert_eq!(entries[0].decision, SiorgDiffDecision::Rejected);
+        assert_eq!(entries[1].decision, SiorgDiffDecision::Approved);
+    }
+
+    #[test]
+    fn test_decide_entries_rejects_unknown_code_and_orphans() {
+        let mut entries = vec![
+            entry(50, Some(1), vec![SiorgDiffKind::Created]),
+            entry(51, Some(50), vec![SiorgDiffKind::Created]),
+        ];
+
+        let unknown = ApplySiorgPreviewPayload {
+            approve_all: false,
+            siorg_codes: vec![99],
+        };
+        assert!(matches!(
+            decide_entries(&mut entries, &unknown),
+            Err(SyncError::InvalidRequest(_))
+        ));
+
+        // Filha aprovada sob uma unidade nova rejeitada
+        let orphan = ApplySiorgPreviewPayload {
+            approve_all: false,
+            siorg_codes: vec![51],
+        };
+        assert!(matches!(
+            decide_entries(&mut entries, &orphan),
+            Err(SyncError::InvalidRequest(_))
+        ));
+
+        let all = ApplySiorgPreviewPayload {
+            approve_all: true,
+            siorg_codes: vec![],
+        };
+        decide_entries(&mut entries, &all).unwrap();
+        assert!(entries
+            .iter()
+            .all(|e| e.decision == SiorgDiffDecision::Approved));
+    }
 }
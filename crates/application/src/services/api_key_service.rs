@@ -0,0 +1,467 @@
+use crate::errors::ServiceError;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use chrono::{DateTime, Duration, Utc};
+use domain::models::{
+    ApiKeyCreatedDto, ApiKeyDto, ApiKeyScope, AuthenticatedApiKey, CreateApiKeyPayload,
+    CreateServicePrincipalPayload, NewApiKey, RotateApiKeyPayload, ServicePrincipalDto,
+    UpdateServicePrincipalPayload,
+};
+use domain::ports::ApiKeyRepositoryPort;
+use sha2::{Digest, Sha256};
+use std::net::IpAddr;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::login_throttle_service::network_of;
+
+/// Prefixo fixo que identifica uma chave do Waterswamp em logs e scanners de segredos.
+const KEY_PREFIX: &str = "wsk_";
+
+/// Validade máxima de uma chave.
+const MAX_KEY_LIFETIME_DAYS: i64 = 365;
+
+/// Período padrão em que a chave substituída continua válida após a rotação.
+const DEFAULT_ROTATION_GRACE_SECS: i64 = 86_400;
+
+const ALLOWED_ACTIONS: [&str; 5] = ["GET", "POST", "PUT", "PATCH", "DELETE"];
+
+/// Chaves de API de service principals (integrações máquina a máquina).
+///
+/// A chave tem o formato `wsk_<prefixo>.<segredo>`: o prefixo é público e
+/// localiza o registro; do segredo só o SHA-256 é guardado. O acesso efetivo
+/// é a interseção entre os escopos da chave e as políticas do Casbin da role
+/// do principal. A rotação cria uma chave nova e mantém a anterior válida por
+/// um período de transição, para que a integração troque sem indisponibilidade.
+pub struct ApiKeyService {
+    repo: Arc<dyn ApiKeyRepositoryPort>,
+}
+
+impl ApiKeyService {
+    pub fn new(repo: Arc<dyn ApiKeyRepositoryPort>) -> Self {
+        Self { repo }
+    }
+
+    // ========================================================================
+    // Service principals
+    // ========================================================================
+
+    pub async fn create_principal(
+        &self,
+        payload: CreateServicePrincipalPayload,
+        created_by: Uuid,
+    ) -> Result<ServicePrincipalDto, ServiceError> {
+        if self.repo.exists_principal_by_name(&payload.name).await? {
+            return Err(ServiceError::Conflict(format!(
+                "Já existe um service principal chamado '{}'",
+                payload.name
+            )));
+        }
+
+        let principal = self
+            .repo
+            .create_principal(
+                &payload.name,
+                payload.description.as_deref(),
+                &payload.role,
+                Some(created_by),
+            )
+            .await?;
+
+        tracing::info!(principal_id = %principal.id, name = %principal.name, role = %principal.role, "Service principal criado");
+        Ok(principal)
+    }
+
+    pub async fn list_principals(&self) -> Result<Vec<ServicePrincipalDto>, ServiceError> {
+        Ok(self.repo.list_principals().await?)
+    }
+
+    pub async fn update_principal(
+        &self,
+        id: Uuid,
+        payload: UpdateServicePrincipalPayload,
+    ) -> Result<ServicePrincipalDto, ServiceError> {
+        self.repo
+            .update_principal(
+                id,
+                payload.description.as_deref(),
+                payload.role.as_deref(),
+                payload.is_active,
+            )
+            .await?
+            .ok_or_else(|| ServiceError::NotFound("Service principal não encontrado".to_string()))
+    }
+
+    // ========================================================================
+    // API keys
+    // ========================================================================
+
+    pub async fn list_keys(&self, principal_id: Uuid) -> Result<Vec<ApiKeyDto>, ServiceError> {
+        self.get_principal(principal_id).await?;
+        Ok(self.repo.list_keys_by_principal(principal_id).await?)
+    }
+
+    pub async fn create_key(
+        &self,
+        principal_id: Uuid,
+        payload: CreateApiKeyPayload,
+        created_by: Uuid,
+    ) -> Result<ApiKeyCreatedDto, ServiceError> {
+        self.get_principal(principal_id).await?;
+
+        let scopes = normalize_scopes(payload.scopes)?;
+        let allowed_ips = normalize_allowlist(&payload.allowed_ips)?;
+        validate_expiry(payload.expires_at, Utc::now())?;
+
+        let (key, prefix, secret_hash) = generate_key();
+        let api_key = self
+            .repo
+            .create_key(&NewApiKey {
+                principal_id,
+                name: payload.name,
+                prefix,
+                secret_hash,
+                scopes,
+                allowed_ips,
+                expires_at: payload.expires_at,
+                rotated_from_id: None,
+                created_by: Some(created_by),
+            })
+            .await?;
+
+        tracing::info!(api_key_id = %api_key.id, prefix = %api_key.prefix, principal_id = %principal_id, "Chave de API criada");
+        Ok(ApiKeyCreatedDto { key, api_key })
+    }
+
+    /// Emite uma chave nova com os mesmos escopos e lista de IPs; a antiga
+    /// expira ao fim do período de transição.
+    pub async fn rotate_key(
+        &self,
+        id: Uuid,
+        payload: RotateApiKeyPayload,
+        created_by: Uuid,
+    ) -> Result<ApiKeyCreatedDto, ServiceError> {
+        let old = self
+            .repo
+            .find_key(id)
+            .await?
+            .ok_or_else(|| ServiceError::NotFound("Chave de API não encontrada".to_string()))?;
+
+        let now = Utc::now();
+        if old.revoked_at.is_some() || old.expires_at <= now {
+            return Err(ServiceError::BadRequest(
+                "Somente chaves ativas podem ser rotacionadas".to_string(),
+            ));
+        }
+
+        let expires_at = payload
+            .expires_at
+            .unwrap_or_else(|| now + (old.expires_at - old.created_at));
+        validate_expiry(expires_at, now)?;
+
+        let grace = payload.grace_period_secs.unwrap_or(DEFAULT_ROTATION_GRACE_SECS);
+        let old_expires_at = now + Duration::seconds(grace);
+
+        let (key, prefix, secret_hash) = generate_key();
+        let api_key = self
+            .repo
+            .rotate_key(
+                old.id,
+                old_expires_at,
+                &NewApiKey {
+                    principal_id: old.principal_id,
+                    name: old.name,
+                    prefix,
+                    secret_hash,
+                    scopes: old.scopes,
+                    allowed_ips: old.allowed_ips,
+                    expires_at,
+                    rotated_from_id: Some(old.id),
+                    created_by: Some(created_by),
+                },
+            )
+            .await?;
+
+        tracing::info!(
+            old_api_key_id = %old.id,
+            api_key_id = %api_key.id,
+            old_key_valid_until = %old_expires_at.min(old.expires_at),
+            "Chave de API rotacionada"
+        );
+        Ok(ApiKeyCreatedDto { key, api_key })
+    }
+
+    pub async fn revoke_key(&self, id: Uuid) -> Result<(), ServiceError> {
+        if !self.repo.revoke_key(id).await? {
+            return Err(ServiceError::NotFound(
+                "Chave de API não encontrada ou já revogada".to_string(),
+            ));
+        }
+        tracing::info!(api_key_id = %id, "Chave de API revogada");
+        Ok(())
+    }
+
+    /// Autentica o valor do header `Authorization: ApiKey <key>`.
+    ///
+    /// Qualquer falha (formato, segredo, expiração, revogação, principal
+    /// inativo, IP fora da lista) resulta em `InvalidCredentials`; o motivo
+    /// só aparece no log.
+    pub async fn authenticate(
+        &self,
+        key: &str,
+        ip: Option<IpAddr>,
+    ) -> Result<AuthenticatedApiKey, ServiceError> {
+        let (prefix, secret) = parse_key(key).ok_or(ServiceError::InvalidCredentials)?;
+
+        let stored = match self.repo.find_stored_by_prefix(prefix).await? {
+            Some(stored) => stored,
+            None => {
+                tracing::warn!(prefix = %prefix, "Chave de API desconhecida");
+                return Err(ServiceError::InvalidCredentials);
+            }
+        };
+
+        let rejection = if !constant_time_eq(&hash_secret(secret), &stored.secret_hash) {
+            Some("segredo inválido")
+        } else if stored.revoked_at.is_some() {
+            Some("chave revogada")
+        } else if stored.expires_at <= Utc::now() {
+            Some("chave expirada")
+        } else if !stored.principal_active {
+            Some("service principal inativo")
+        } else if !ip_allowed(ip, &stored.allowed_ips) {
+            Some("IP fora da lista permitida")
+        } else {
+            None
+        };
+
+        if let Some(reason) = rejection {
+            tracing::warn!(
+                api_key_id = %stored.id,
+                prefix = %stored.prefix,
+                ip = ?ip,
+                reason = reason,
+                "Chave de API recusada"
+            );
+            return Err(ServiceError::InvalidCredentials);
+        }
+
+        let ip_string = ip.map(|ip| ip.to_string());
+        self.repo.touch_key(stored.id, ip_string.as_deref()).await?;
+
+        Ok(AuthenticatedApiKey {
+            key_id: stored.id,
+            prefix: stored.prefix,
+            principal_id: stored.principal_id,
+            principal_name: stored.principal_name,
+            role: stored.principal_role,
+            scopes: stored.scopes,
+        })
+    }
+
+    async fn get_principal(&self, id: Uuid) -> Result<ServicePrincipalDto, ServiceError> {
+        self.repo
+            .find_principal(id)
+            .await?
+            .ok_or_else(|| ServiceError::NotFound("Service principal não encontrado".to_string()))
+    }
+}
+
+/// Gera (chave completa, prefixo, hash do segredo).
+fn generate_key() -> (String, String, String) {
+    let prefix_bytes: [u8; 6] = rand::random();
+    let secret_bytes: [u8; 32] = rand::random();
+
+    let prefix = format!("{}{}", KEY_PREFIX, hex_encode(&prefix_bytes));
+    let secret = URL_SAFE_NO_PAD.encode(secret_bytes);
+    let secret_hash = hash_secret(&secret);
+
+    (format!("{}.{}", prefix, secret), prefix, secret_hash)
+}
+
+/// Separa `wsk_<prefixo>.<segredo>` em (prefixo, segredo).
+pub fn parse_key(key: &str) -> Option<(&str, &str)> {
+    let (prefix, secret) = key.trim().split_once('.')?;
+    if !prefix.starts_with(KEY_PREFIX) || prefix.len() <= KEY_PREFIX.len() || secret.is_empty() {
+        return None;
+    }
+    Some((prefix, secret))
+}
+
+fn hash_secret(secret: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(secret.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len()
+        && a
+            .bytes()
+            .zip(b.bytes())
+            .fold(0u8, |acc, (x, y)| acc | (x ^ y))
+            == 0
+}
+
+fn validate_expiry(expires_at: DateTime<Utc>, now: DateTime<Utc>) -> Result<(), ServiceError> {
+    if expires_at <= now {
+        return Err(ServiceError::BadRequest(
+            "A data de expiração deve estar no futuro".to_string(),
+        ));
+    }
+    if expires_at > now + Duration::days(MAX_KEY_LIFETIME_DAYS) {
+        return Err(ServiceError::BadRequest(format!(
+            "A validade máxima de uma chave é de {} dias",
+            MAX_KEY_LIFETIME_DAYS
+        )));
+    }
+    Ok(())
+}
+
+/// Valida os escopos (caminho absoluto + método HTTP) e normaliza o método.
+fn normalize_scopes(scopes: Vec<ApiKeyScope>) -> Result<Vec<ApiKeyScope>, ServiceError> {
+    scopes
+        .into_iter()
+        .map(|scope| {
+            let act = scope.act.trim().to_uppercase();
+            if !ALLOWED_ACTIONS.contains(&act.as_str()) {
+                return Err(ServiceError::BadRequest(format!(
+                    "Ação de escopo inválida: '{}'",
+                    scope.act
+                )));
+            }
+            let obj = scope.obj.trim().to_string();
+            if !obj.starts_with('/') {
+                return Err(ServiceError::BadRequest(format!(
+                    "Objeto de escopo deve ser um caminho absoluto: '{}'",
+                    scope.obj
+                )));
+            }
+            Ok(ApiKeyScope { obj, act })
+        })
+        .collect()
+}
+
+/// Converte IPs e redes da lista permitida para a forma CIDR canônica.
+pub fn normalize_allowlist(entries: &[String]) -> Result<Vec<String>, ServiceError> {
+    entries
+        .iter()
+        .map(|entry| {
+            parse_network(entry)
+                .map(|(addr, prefix)| network_of(addr, prefix, prefix))
+                .ok_or_else(|| {
+                    ServiceError::BadRequest(format!("IP ou rede inválida: '{}'", entry))
+                })
+        })
+        .collect()
+}
+
+fn parse_network(entry: &str) -> Option<(IpAddr, u8)> {
+    let entry = entry.trim();
+    let (addr, prefix) = match entry.split_once('/') {
+        Some((addr, prefix)) => (addr.parse::<IpAddr>().ok()?, Some(prefix.parse::<u8>().ok()?)),
+        None => (entry.parse::<IpAddr>().ok()?, None),
+    };
+    let max = if addr.is_ipv4() { 32 } else { 128 };
+    let prefix = prefix.unwrap_or(max);
+    (prefix <= max).then_some((addr, prefix))
+}
+
+/// Lista vazia libera qualquer origem; do contrário o IP precisa ser conhecido
+/// e pertencer a uma das redes.
+pub fn ip_allowed(ip: Option<IpAddr>, allowlist: &[String]) -> bool {
+    if allowlist.is_empty() {
+        return true;
+    }
+    let Some(ip) = ip else {
+        return false;
+    };
+    allowlist.iter().any(|entry| {
+        parse_network(entry).is_some_and(|(network, prefix)| {
+            network.is_ipv4() == ip.is_ipv4()
+                && network_of(ip, prefix, prefix) == network_of(network, prefix, prefix)
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generated_key_round_trips_through_parse() {
+        let (key, prefix, secret_hash) = generate_key();
+        let (parsed_prefix, secret) = parse_key(&key).unwrap();
+
+        assert_eq!(parsed_prefix, prefix);
+        assert!(prefix.starts_with("wsk_"));
+        assert_eq!(hash_secret(secret), secret_hash);
+        assert!(constant_time_eq(&hash_secret(secret), &secret_hash));
+    }
+
+    #[test]
+    fn test_parse_key_rejects_malformed_values() {
+        assert!(parse_key("").is_none());
+        assert!(parse_key("wsk_abc").is_none());
+        assert!(parse_key("wsk_.secret").is_none());
+        assert!(parse_key("abc123.secret").is_none());
+        assert!(parse_key("wsk_abc.").is_none());
+    }
+
+    #[test]
+    fn test_ip_allowlist_matching() {
+        let allowlist =
+            normalize_allowlist(&["200.130.0.0/16".to_string(), "10.1.2.3".to_string()]).unwrap();
+        assert_eq!(allowlist, vec!["200.130.0.0/16", "10.1.2.3/32"]);
+
+        assert!(ip_allowed(Some("200.130.45.7".parse().unwrap()), &allowlist));
+        assert!(ip_allowed(Some("10.1.2.3".parse().unwrap()), &allowlist));
+        assert!(!ip_allowed(Some("10.1.2.4".parse().unwrap()), &allowlist));
+        assert!(!ip_allowed(Some("2001:db8::1".parse().unwrap()), &allowlist));
+        assert!(!ip_allowed(None, &allowlist));
+        assert!(ip_allowed(None, &[]));
+    }
+
+    #[test]
+    fn test_normalize_allowlist_rejects_invalid_entries() {
+        assert!(normalize_allowlist(&["10.0.0.0/33".to_string()]).is_err());
+        assert!(normalize_allowlist(&["not-an-ip".to_string()]).is_err());
+        assert_eq!(
+            normalize_allowlist(&["10.9.8.7/8".to_string()]).unwrap(),
+            vec!["10.0.0.0/8"]
+        );
+    }
+
+    #[test]
+    fn test_normalize_scopes() {
+        let scopes = normalize_scopes(vec![ApiKeyScope {
+            obj: " /api/admin/fuelings/* ".to_string(),
+            act: "get".to_string(),
+        }])
+        .unwrap();
+        assert_eq!(scopes[0].obj, "/api/admin/fuelings/*");
+        assert_eq!(scopes[0].act, "GET");
+
+        assert!(normalize_scopes(vec![ApiKeyScope {
+            obj: "/api/admin/fuelings".to_string(),
+            act: "TRACE".to_string(),
+        }])
+        .is_err());
+        assert!(normalize_scopes(vec![ApiKeyScope {
+            obj: "api/admin".to_string(),
+            act: "GET".to_string(),
+        }])
+        .is_err());
+    }
+
+    #[test]
+    fn test_validate_expiry() {
+        let now = Utc::now();
+        assert!(validate_expiry(now + Duration::days(30), now).is_ok());
+        assert!(validate_expiry(now - Duration::seconds(1), now).is_err());
+        assert!(validate_expiry(now + Duration::days(MAX_KEY_LIFETIME_DAYS + 1), now).is_err());
+    }
+}
@@ -0,0 +1,507 @@
+use crate::errors::ServiceError;
+use chrono::Utc;
+use domain::models::{
+    ApprovalChainDto, ApprovalChainStepPayload, ApprovalDocumentContext, ApprovalDocumentType,
+    ApprovalProgress, ApprovalStepStatus, CreateApprovalChainPayload, DocumentApprovalStepDto,
+    UpdateApprovalChainPayload,
+};
+use domain::ports::ApprovalChainRepositoryPort;
+use rust_decimal::Decimal;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Configurable multi-level approval of requisitions, stock transfers and
+/// disposal requests. When no chain applies to a document its single
+/// approve step keeps deciding on its own.
+pub struct ApprovalChainService {
+    repo: Arc<dyn ApprovalChainRepositoryPort>,
+}
+
+impl ApprovalChainService {
+    pub fn new(repo: Arc<dyn ApprovalChainRepositoryPort>) -> Self {
+        Self { repo }
+    }
+
+    // ========================================================================
+    // CHAIN CONFIGURATION
+    // ========================================================================
+
+    pub async fn create_chain(
+        &self,
+        payload: CreateApprovalChainPayload,
+        created_by: Uuid,
+    ) -> Result<ApprovalChainDto, ServiceError> {
+        validate_conditions(payload.min_value, &payload.steps)?;
+
+        let chain = self
+            .repo
+            .create_chain(&payload, created_by)
+            .await
+            .map_err(ServiceError::from)?;
+
+        tracing::info!(
+            chain_id = %chain.id,
+            document_type = ?chain.document_type,
+            steps = chain.steps.len(),
+            "Cadeia de aprovação criada"
+        );
+        Ok(chain)
+    }
+
+    pub async fn update_chain(
+        &self,
+        id: Uuid,
+        payload: UpdateApprovalChainPayload,
+    ) -> Result<ApprovalChainDto, ServiceError> {
+        validate_conditions(payload.min_value, &payload.steps)?;
+        self.get_chain(id).await?;
+
+        self.repo
+            .update_chain(id, &payload)
+            .await
+            .map_err(ServiceError::from)
+    }
+
+    pub async fn get_chain(&self, id: Uuid) -> Result<ApprovalChainDto, ServiceError> {
+        self.repo
+            .find_chain(id)
+            .await
+            .map_err(ServiceError::from)?
+            .ok_or_else(|| ServiceError::NotFound("Cadeia de aprovação não encontrada".to_string()))
+    }
+
+    pub async fn list_chains(
+        &self,
+        document_type: Option<ApprovalDocumentType>,
+    ) -> Result<Vec<ApprovalChainDto>, ServiceError> {
+        self.repo
+            .list_chains(document_type)
+            .await
+            .map_err(ServiceError::from)
+    }
+
+    /// Stops applying the chain to new documents. Documents already in
+    /// approval finish with the steps they were started with.
+    pub async fn deactivate_chain(&self, id: Uuid) -> Result<(), ServiceError> {
+        self.get_chain(id).await?;
+        if !self
+            .repo
+            .deactivate_chain(id)
+            .await
+            .map_err(ServiceError::from)?
+        {
+            return Err(ServiceError::Conflict(
+                "Cadeia de aprovação já desativada".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    // ========================================================================
+    // DOCUMENT APPROVAL
+    // ========================================================================
+
+    /// Steps of the document. Until the first decision starts its chain,
+    /// lists the steps of the chain that would apply without storing them.
+    /// Empty when no chain applies.
+    pub async fn steps_for(
+        &self,
+        document: &ApprovalDocumentContext,
+    ) -> Result<Vec<DocumentApprovalStepDto>, ServiceError> {
+        let steps = self
+            .repo
+            .list_document_steps(document.document_type, document.document_id)
+            .await
+            .map_err(ServiceError::from)?;
+        if !steps.is_empty() {
+            return Ok(steps);
+        }
+
+        Ok(self
+            .applicable_chain(document)
+            .await?
+            .map(|chain| preview_steps(document, &chain))
+            .unwrap_or_default())
+    }
+
+    /// Stored steps of the document, starting its chain on first use
+    async fn start_steps(
+        &self,
+        document: &ApprovalDocumentContext,
+    ) -> Result<Vec<DocumentApprovalStepDto>, ServiceError> {
+        let steps = self
+            .repo
+            .list_document_steps(document.document_type, document.document_id)
+            .await
+            .map_err(ServiceError::from)?;
+        if !steps.is_empty() {
+            return Ok(steps);
+        }
+
+        let Some(chain) = self.applicable_chain(document).await? else {
+            return Ok(Vec::new());
+        };
+        self.repo
+            .start_document_chain(document.document_type, document.document_id, &chain)
+            .await
+            .map_err(ServiceError::from)
+    }
+
+    async fn applicable_chain(
+        &self,
+        document: &ApprovalDocumentContext,
+    ) -> Result<Option<ApprovalChainDto>, ServiceError> {
+        let chains = self
+            .repo
+            .list_active_chains(document.document_type)
+            .await
+            .map_err(ServiceError::from)?;
+        Ok(chains.into_iter().find(|c| c.applies_to(document)))
+    }
+
+    /// Records the caller's decision on the step of the current group they
+    /// are eligible for. `on_behalf_of` is the delegator when the caller acts
+    /// as a delegate; eligibility is then checked against the delegator.
+    pub async fn decide(
+        &self,
+        document: &ApprovalDocumentContext,
+        decided_by: Uuid,
+        on_behalf_of: Option<Uuid>,
+        approve: bool,
+        comments: Option<&str>,
+    ) -> Result<ApprovalProgress, ServiceError> {
+        let steps = self.start_steps(document).await?;
+        match evaluate_progress(&steps) {
+            ApprovalProgress::InProgress => {}
+            ApprovalProgress::Rejected => {
+                return Err(ServiceError::BadRequest(
+                    "Cadeia de aprovação já foi rejeitada".to_string(),
+                ))
+            }
+            progress => return Ok(progress),
+        }
+
+        let identity = on_behalf_of.unwrap_or(decided_by);
+        if steps.iter().any(|s| decided_as(s) == Some(identity)) {
+            return Err(ServiceError::Conflict(
+                "Uma mesma pessoa não pode decidir duas etapas do mesmo documento".to_string(),
+            ));
+        }
+
+        let roles = self
+            .repo
+            .roles_of(identity, document.destination_unit_id)
+            .await
+            .map_err(ServiceError::from)?;
+        let step = current_group(&steps)
+            .into_iter()
+            .find(|s| is_eligible(s, identity, &roles))
+            .ok_or_else(|| {
+                ServiceError::Forbidden(
+                    "Você não é aprovador da etapa pendente deste documento".to_string(),
+                )
+            })?;
+
+        let status = if approve {
+            ApprovalStepStatus::Approved
+        } else {
+            ApprovalStepStatus::Rejected
+        };
+        if !self
+            .repo
+            .decide_step(step.id, status, decided_by, on_behalf_of, comments)
+            .await
+            .map_err(ServiceError::from)?
+        {
+            return Err(ServiceError::Conflict(
+                "Etapa já decidida por outro aprovador".to_string(),
+            ));
+        }
+
+        tracing::info!(
+            document_type = ?document.document_type,
+            document_id = %document.document_id,
+            step = %step.name,
+            decided_by = %decided_by,
+            approved = approve,
+            "Etapa de aprovação decidida"
+        );
+
+        let steps = self
+            .repo
+            .list_document_steps(document.document_type, document.document_id)
+            .await
+            .map_err(ServiceError::from)?;
+        Ok(evaluate_progress(&steps))
+    }
+
+    /// Fails while the document's chain still has pending steps
+    pub async fn ensure_completed(
+        &self,
+        document: &ApprovalDocumentContext,
+    ) -> Result<(), ServiceError> {
+        let steps = self.start_steps(document).await?;
+        match evaluate_progress(&steps) {
+            ApprovalProgress::NoChain | ApprovalProgress::Completed => Ok(()),
+            ApprovalProgress::InProgress => {
+                let pending: Vec<&str> = current_group(&steps)
+                    .iter()
+                    .map(|s| s.name.as_str())
+                    .collect();
+                Err(ServiceError::BadRequest(format!(
+                    "Cadeia de aprovação pendente: {}",
+                    pending.join(", ")
+                )))
+            }
+            ApprovalProgress::Rejected => Err(ServiceError::BadRequest(
+                "Cadeia de aprovação foi rejeitada".to_string(),
+            )),
+        }
+    }
+}
+
+fn validate_conditions(
+    min_value: Option<Decimal>,
+    steps: &[ApprovalChainStepPayload],
+) -> Result<(), ServiceError> {
+    if steps.is_empty() {
+        return Err(ServiceError::BadRequest(
+            "A cadeia deve ter ao menos uma etapa".to_string(),
+        ));
+    }
+    if min_value.is_some_and(|v| v < Decimal::ZERO) {
+        return Err(ServiceError::BadRequest(
+            "Valor mínimo não pode ser negativo".to_string(),
+        ));
+    }
+    if steps
+        .iter()
+        .any(|s| s.approver_role.is_none() && s.approver_user_id.is_none())
+    {
+        return Err(ServiceError::BadRequest(
+            "Cada etapa deve indicar um papel ou um usuário aprovador".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Chain steps as they will be stored for the document, all pending
+fn preview_steps(
+    document: &ApprovalDocumentContext,
+    chain: &ApprovalChainDto,
+) -> Vec<DocumentApprovalStepDto> {
+    let now = Utc::now();
+    chain
+        .steps
+        .iter()
+        .map(|step| DocumentApprovalStepDto {
+            id: step.id,
+            document_type: document.document_type,
+            document_id: document.document_id,
+            chain_id: Some(chain.id),
+            step_order: step.step_order,
+            name: step.name.clone(),
+            approver_role: step.approver_role.clone(),
+            approver_user_id: step.approver_user_id,
+            status: ApprovalStepStatus::Pending,
+            decided_by: None,
+            decided_by_username: None,
+            decided_on_behalf_of: None,
+            decided_at: None,
+            comments: None,
+            created_at: now,
+        })
+        .collect()
+}
+
+/// Identity on whose authority the step was decided
+fn decided_as(step: &DocumentApprovalStepDto) -> Option<Uuid> {
+    step.decided_on_behalf_of.or(step.decided_by)
+}
+
+fn evaluate_progress(steps: &[DocumentApprovalStepDto]) -> ApprovalProgress {
+    if steps.is_empty() {
+        ApprovalProgress::NoChain
+    } else if steps
+        .iter()
+        .any(|s| s.status == ApprovalStepStatus::Rejected)
+    {
+        ApprovalProgress::Rejected
+    } else if steps
+        .iter()
+        .all(|s| s.status == ApprovalStepStatus::Approved)
+    {
+        ApprovalProgress::Completed
+    } else {
+        ApprovalProgress::InProgress
+    }
+}
+
+/// Pending steps of the lowest order still open; they may be decided in
+/// any order, later orders wait for them
+fn current_group(steps: &[DocumentApprovalStepDto]) -> Vec<&DocumentApprovalStepDto> {
+    let pending = steps
+        .iter()
+        .filter(|s| s.status == ApprovalStepStatus::Pending);
+    let Some(order) = pending.clone().map(|s| s.step_order).min() else {
+        return Vec::new();
+    };
+    pending.filter(|s| s.step_order == order).collect()
+}
+
+fn is_eligible(step: &DocumentApprovalStepDto, user_id: Uuid, roles: &[String]) -> bool {
+    step.approver_user_id == Some(user_id)
+        || step
+            .approver_role
+            .as_ref()
+            .is_some_and(|role| roles.contains(role))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use domain::models::RequisitionPriority;
+
+    fn step(order: i32, role: &str, status: ApprovalStepStatus) -> DocumentApprovalStepDto {
+        DocumentApprovalStepDto {
+            id: Uuid::new_v4(),
+            document_type: ApprovalDocumentType::Requisition,
+            document_id: Uuid::nil(),
+            chain_id: None,
+            step_order: order,
+            name: role.to_string(),
+            approver_role: Some(role.to_string()),
+            approver_user_id: None,
+            status,
+            decided_by: None,
+            decided_by_username: None,
+            decided_on_behalf_of: None,
+            decided_at: None,
+            comments: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    fn chain() -> ApprovalChainDto {
+        ApprovalChainDto {
+            id: Uuid::new_v4(),
+            document_type: ApprovalDocumentType::Requisition,
+            name: "Acima do limite".to_string(),
+            description: None,
+            min_value: None,
+            priorities: None,
+            warehouse_id: None,
+            destination_unit_id: None,
+            precedence: 0,
+            is_active: true,
+            created_by: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            steps: Vec::new(),
+        }
+    }
+
+    fn document(value: i64, priority: RequisitionPriority) -> ApprovalDocumentContext {
+        ApprovalDocumentContext {
+            document_type: ApprovalDocumentType::Requisition,
+            document_id: Uuid::new_v4(),
+            value: Decimal::from(value),
+            priority: Some(priority),
+            warehouse_id: Some(Uuid::nil()),
+            destination_unit_id: None,
+        }
+    }
+
+    #[test]
+    fn test_evaluate_progress() {
+        use ApprovalStepStatus::*;
+
+        assert_eq!(evaluate_progress(&[]), ApprovalProgress::NoChain);
+        assert_eq!(
+            evaluate_progress(&[step(1, "A", Approved), step(2, "B", Pending)]),
+            ApprovalProgress::InProgress
+        );
+        assert_eq!(
+            evaluate_progress(&[step(1, "A", Approved), step(1, "B", Approved)]),
+            ApprovalProgress::Completed
+        );
+        assert_eq!(
+            evaluate_progress(&[step(1, "A", Rejected), step(2, "B", Pending)]),
+            ApprovalProgress::Rejected
+        );
+    }
+
+    #[test]
+    fn test_current_group_is_lowest_pending_order() {
+        use ApprovalStepStatus::*;
+
+        let steps = [
+            step(1, "GESTOR", Approved),
+            step(2, "DIRETOR", Pending),
+            step(2, "FINANCEIRO", Pending),
+            step(3, "REITOR", Pending),
+        ];
+        let group: Vec<&str> = current_group(&steps)
+            .iter()
+            .map(|s| s.name.as_str())
+            .collect();
+        assert_eq!(group, ["DIRETOR", "FINANCEIRO"]);
+
+        assert!(current_group(&[step(1, "A", Approved)]).is_empty());
+    }
+
+    #[test]
+    fn test_is_eligible_by_role_or_user() {
+        let user = Uuid::new_v4();
+        let by_role = step(1, "ROLE_DIRECTOR", ApprovalStepStatus::Pending);
+        assert!(is_eligible(&by_role, user, &["ROLE_DIRECTOR".to_string()]));
+        assert!(!is_eligible(&by_role, user, &["ROLE_USER".to_string()]));
+
+        let mut by_user = by_role.clone();
+        by_user.approver_role = None;
+        by_user.approver_user_id = Some(user);
+        assert!(is_eligible(&by_user, user, &[]));
+        assert!(!is_eligible(&by_user, Uuid::new_v4(), &[]));
+    }
+
+    #[test]
+    fn test_chain_applies_to_conditions() {
+        let mut c = chain();
+        assert!(c.applies_to(&document(10, RequisitionPriority::Normal)));
+
+        c.min_value = Some(Decimal::from(1000));
+        assert!(!c.applies_to(&document(999, RequisitionPriority::Normal)));
+        assert!(c.applies_to(&document(1000, RequisitionPriority::Normal)));
+
+        c.min_value = None;
+        c.priorities = Some(vec![RequisitionPriority::Urgent]);
+        assert!(!c.applies_to(&document(10, RequisitionPriority::High)));
+        assert!(c.applies_to(&document(10, RequisitionPriority::Urgent)));
+
+        c.warehouse_id = Some(Uuid::new_v4());
+        assert!(!c.applies_to(&document(10, RequisitionPriority::Urgent)));
+
+        c.warehouse_id = None;
+        c.is_active = false;
+        assert!(!c.applies_to(&document(10, RequisitionPriority::Urgent)));
+    }
+
+    #[test]
+    fn test_validate_conditions() {
+        let step = ApprovalChainStepPayload {
+            step_order: 1,
+            name: "Diretor".to_string(),
+            approver_role: None,
+            approver_user_id: None,
+        };
+        assert!(validate_conditions(None, std::slice::from_ref(&step)).is_err());
+
+        let step = ApprovalChainStepPayload {
+            approver_role: Some("ROLE_DIRECTOR".to_string()),
+            ..step
+        };
+        assert!(validate_conditions(Some(Decimal::from(-1)), std::slice::from_ref(&step)).is_err());
+        assert!(validate_conditions(Some(Decimal::ZERO), &[step]).is_ok());
+        assert!(validate_conditions(None, &[]).is_err());
+    }
+}
@@ -6,6 +6,7 @@ use domain::{
     ports::asset_management::*,
     ports::vehicle::{VehicleModelRepositoryPort, VehicleRepositoryPort, VehicleStatusHistoryRepositoryPort},
     models::vehicle::VehicleStatus,
+    models::UnitScope,
 };
 use rust_decimal::{prelude::ToPrimitive, Decimal};
 use std::sync::Arc;
@@ -62,6 +63,7 @@ impl AssetManagementService {
         vehicle_id: Uuid,
         payload: CreateVehicleDepartmentTransferPayload,
         created_by: Option<Uuid>,
+        scope: &UnitScope,
     ) -> Result<VehicleDepartmentTransferDto, ServiceError> {
         let vehicle = self.vehicle_repo
             .find_by_id(vehicle_id)
@@ -69,6 +71,13 @@ impl AssetManagementService {
             .map_err(ServiceError::from)?
             .ok_or_else(|| ServiceError::NotFound("Veículo não encontrado".to_string()))?;
 
+        // Origem e destino precisam estar no escopo de quem transfere
+        if !scope.contains(vehicle.department_id) || !scope.contains(Some(payload.target_dept_id)) {
+            return Err(ServiceError::Forbidden(
+                "Transferência envolve unidade fora do seu escopo de atuação".to_string(),
+            ));
+        }
+
         if vehicle.department_id == Some(payload.target_dept_id) {
             return Err(ServiceError::BadRequest(
                 "Destino igual ao departamento atual".to_string(),
@@ -112,13 +121,20 @@ impl AssetManagementService {
     pub async fn list_transfers(
         &self,
         vehicle_id: Uuid,
+        scope: &UnitScope,
     ) -> Result<Vec<VehicleDepartmentTransferDto>, ServiceError> {
-        self.vehicle_repo
+        let vehicle = self.vehicle_repo
             .find_by_id(vehicle_id)
             .await
             .map_err(ServiceError::from)?
             .ok_or_else(|| ServiceError::NotFound("Veículo não encontrado".to_string()))?;
 
+        if !scope.contains(vehicle.department_id) {
+            return Err(ServiceError::Forbidden(
+                "Veículo fora do seu escopo de atuação".to_string(),
+            ));
+        }
+
         self.transfer_repo
             .list_by_vehicle(vehicle_id)
             .await
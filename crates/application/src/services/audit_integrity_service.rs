@@ -0,0 +1,696 @@
+use crate::errors::ServiceError;
+use chrono::{Duration, NaiveDate, Utc};
+use core_services::jwt::JwtService;
+use domain::models::{
+    AuditArchiveDto, AuditArchiveResult, AuditChainEntry, AuditChainIssue, AuditChainIssueKind,
+    AuditChainVerification, AuditCheckpointDto, AuditDayVerification, AuditSealResult,
+    NewAuditArchive, NewAuditRedaction,
+};
+use domain::ports::AuditIntegrityRepositoryPort;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// Longest range accepted by a single verification request
+const MAX_VERIFY_DAYS: i64 = 366;
+
+/// Range verified when the caller does not provide one
+const DEFAULT_VERIFY_DAYS: i64 = 30;
+
+/// `prev_hash` of the first entry of each day
+const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Format tag written to sealed archive files
+const ARCHIVE_FORMAT: &str = "waterswamp-audit-archive/v1";
+
+/// Contents of a sealed archive file
+#[derive(Serialize)]
+struct SealedAuditFile<'a> {
+    format: &'static str,
+    chain_date: NaiveDate,
+    first_seq: i64,
+    last_seq: i64,
+    entry_count: i64,
+    /// Link to the entry preceding `first_seq` (genesis for the first range)
+    prev_hash: &'a str,
+    last_hash: &'a str,
+    checkpoint: Option<&'a AuditCheckpointDto>,
+    entries: &'a [AuditChainEntry],
+}
+
+/// Tamper evidence for the audit logs: each entry carries the hash of the
+/// previous one (computed by the logs database on insert), closed days are
+/// sealed with a checkpoint signed with the JWT signing key, and retention
+/// exports entries to signed files instead of deleting them.
+pub struct AuditIntegrityService {
+    repo: Arc<dyn AuditIntegrityRepositoryPort>,
+    jwt_service: Arc<JwtService>,
+    archive_dir: PathBuf,
+}
+
+impl AuditIntegrityService {
+    pub fn new(
+        repo: Arc<dyn AuditIntegrityRepositoryPort>,
+        jwt_service: Arc<JwtService>,
+        archive_dir: impl Into<PathBuf>,
+    ) -> Self {
+        Self {
+            repo,
+            jwt_service,
+            archive_dir: archive_dir.into(),
+        }
+    }
+
+    /// Signs a checkpoint for every day before today that has none yet.
+    /// Days whose chain is already broken are left unsealed so the signature
+    /// never vouches for tampered entries.
+    pub async fn seal_closed_days(&self) -> Result<AuditSealResult, ServiceError> {
+        let today = Utc::now().date_naive();
+        let mut checkpoints = Vec::new();
+        let mut skipped_days = Vec::new();
+
+        for day in self.repo.unsealed_days_before(today).await? {
+            let entries = self.repo.chain_entries(day).await?;
+            let archives = self.repo.list_archives(day).await?;
+            let report = self.verify_day(day, &entries, None, &archives);
+
+            let Some(last) = entries.last() else {
+                continue;
+            };
+            if !report.valid {
+                warn!(%day, "Cadeia de auditoria inválida; dia não foi selado");
+                skipped_days.push(day);
+                continue;
+            }
+
+            let entry_count = report.archived_count + report.entry_count;
+            let digest = checkpoint_digest(day, entry_count, last.seq, &last.entry_hash);
+            let signature = self.sign(&digest)?;
+            let checkpoint = AuditCheckpointDto {
+                chain_date: day,
+                entry_count,
+                last_seq: last.seq,
+                last_hash: last.entry_hash.clone(),
+                digest,
+                signature,
+                signed_at: Utc::now(),
+            };
+
+            if self.repo.save_checkpoint(&checkpoint).await? {
+                checkpoints.push(checkpoint);
+            }
+        }
+
+        if !checkpoints.is_empty() {
+            info!(sealed = checkpoints.len(), "Dias de auditoria selados");
+        }
+
+        Ok(AuditSealResult {
+            checkpoints,
+            skipped_days,
+        })
+    }
+
+    pub async fn list_checkpoints(
+        &self,
+        from: Option<NaiveDate>,
+        to: Option<NaiveDate>,
+    ) -> Result<Vec<AuditCheckpointDto>, ServiceError> {
+        let (from, to) = resolve_range(from, to, Utc::now().date_naive())?;
+        Ok(self.repo.list_checkpoints(from, to).await?)
+    }
+
+    /// Walks the chain of each day in the range looking for gaps, broken
+    /// links, edited entries and disagreement with the signed checkpoints.
+    pub async fn verify(
+        &self,
+        from: Option<NaiveDate>,
+        to: Option<NaiveDate>,
+    ) -> Result<AuditChainVerification, ServiceError> {
+        let (from, to) = resolve_range(from, to, Utc::now().date_naive())?;
+
+        let mut days = Vec::new();
+        for day in self.repo.chain_days(from, to).await? {
+            let entries = self.repo.chain_entries(day).await?;
+            let checkpoint = self.repo.find_checkpoint(day).await?;
+            let archives = self.repo.list_archives(day).await?;
+            days.push(self.verify_day(day, &entries, checkpoint.as_ref(), &archives));
+        }
+
+        Ok(AuditChainVerification {
+            from,
+            to,
+            valid: days.iter().all(|d| d.valid),
+            days,
+        })
+    }
+
+    /// Retention: exports every day older than `retention_days` to a sealed
+    /// file and only then removes its entries from the database.
+    pub async fn archive(&self, retention_days: i64) -> Result<AuditArchiveResult, ServiceError> {
+        if retention_days < 1 {
+            return Err(ServiceError::BadRequest(
+                "O período de retenção deve ser de ao menos 1 dia".to_string(),
+            ));
+        }
+
+        // Todo dia arquivado precisa estar selado antes de sair do banco
+        let sealed = self.seal_closed_days().await?;
+        let mut skipped_days = sealed.skipped_days;
+
+        let cutoff = Utc::now().date_naive() - Duration::days(retention_days);
+        let mut archives = Vec::new();
+        let mut archived_count = 0;
+
+        for day in self.repo.days_with_entries_before(cutoff).await? {
+            if skipped_days.contains(&day) {
+                continue;
+            }
+
+            let entries = self.repo.chain_entries(day).await?;
+            let checkpoint = self.repo.find_checkpoint(day).await?;
+            let existing = self.repo.list_archives(day).await?;
+            let report = self.verify_day(day, &entries, checkpoint.as_ref(), &existing);
+
+            let (Some(first), Some(last)) = (entries.first(), entries.last()) else {
+                continue;
+            };
+            if !report.valid {
+                warn!(%day, "Cadeia de auditoria inválida; dia mantido no banco");
+                skipped_days.push(day);
+                continue;
+            }
+
+            let entry_count = entries.len() as i64;
+            let file = SealedAuditFile {
+                format: ARCHIVE_FORMAT,
+                chain_date: day,
+                first_seq: first.seq,
+                last_seq: last.seq,
+                entry_count,
+                prev_hash: &first.prev_hash,
+                last_hash: &last.entry_hash,
+                checkpoint: checkpoint.as_ref(),
+                entries: &entries,
+            };
+            let bytes = serde_json::to_vec_pretty(&file)
+                .map_err(|e| ServiceError::Internal(e.to_string()))?;
+            let file_sha256 = sha256_hex(&bytes);
+            let signature = self.sign(&archive_message(
+                day,
+                first.seq,
+                last.seq,
+                &last.entry_hash,
+                &file_sha256,
+            ))?;
+
+            let path = self.archive_dir.join(format!(
+                "audit-{}-{:06}-{:06}.json",
+                day, first.seq, last.seq
+            ));
+            self.write_sealed_file(&path, &bytes, &signature).await?;
+
+            let archive = self
+                .repo
+                .archive_range(&NewAuditArchive {
+                    chain_date: day,
+                    first_seq: first.seq,
+                    last_seq: last.seq,
+                    entry_count,
+                    last_hash: last.entry_hash.clone(),
+                    file_path: path.to_string_lossy().into_owned(),
+                    file_sha256,
+                    signature,
+                })
+                .await?;
+
+            archived_count += archive.entry_count;
+            archives.push(archive);
+        }
+
+        if archived_count > 0 {
+            info!(archived_count, "Registros de auditoria arquivados");
+        }
+
+        Ok(AuditArchiveResult {
+            archived_count,
+            archives,
+            skipped_days,
+        })
+    }
+
+    /// LGPD: erases username, IP address and user agent from the live entries
+    /// of the user. Intact entries get a signed redaction so the chain still
+    /// verifies; entries already tampered with are erased without one and keep
+    /// failing verification. Returns the number of entries erased.
+    pub async fn redact_user(&self, user_id: Uuid) -> Result<u64, ServiceError> {
+        let candidates = self.repo.redaction_candidates(user_id).await?;
+        if candidates.is_empty() {
+            return Ok(0);
+        }
+
+        let mut redactions = Vec::new();
+        for candidate in candidates.iter().filter(|c| c.hash_valid) {
+            let message =
+                redaction_message(candidate.id, &candidate.entry_hash, &candidate.redacted_hash);
+            redactions.push(NewAuditRedaction {
+                entry_id: candidate.id,
+                entry_hash: candidate.entry_hash.clone(),
+                redacted_hash: candidate.redacted_hash.clone(),
+                signature: self.sign(&message)?,
+            });
+        }
+        let entry_ids: Vec<Uuid> = candidates.iter().map(|c| c.id).collect();
+
+        let erased = self.repo.redact(&entry_ids, &redactions).await?;
+        info!(%user_id, erased, "Dados pessoais removidos dos registros de auditoria");
+        Ok(erased)
+    }
+
+    fn verify_day(
+        &self,
+        day: NaiveDate,
+        entries: &[AuditChainEntry],
+        checkpoint: Option<&AuditCheckpointDto>,
+        archives: &[AuditArchiveDto],
+    ) -> AuditDayVerification {
+        verify_chain(day, entries, checkpoint, archives, |signature, message| {
+            self.jwt_service.verify_detached(signature, message)
+        })
+    }
+
+    fn sign(&self, message: &str) -> Result<String, ServiceError> {
+        self.jwt_service
+            .sign_detached(message.as_bytes())
+            .map_err(|e| ServiceError::Internal(e.to_string()))
+    }
+
+    async fn write_sealed_file(
+        &self,
+        path: &std::path::Path,
+        bytes: &[u8],
+        signature: &str,
+    ) -> Result<(), ServiceError> {
+        let io_err = |e: std::io::Error| {
+            ServiceError::Internal(format!("Falha ao gravar arquivo de auditoria: {}", e))
+        };
+
+        tokio::fs::create_dir_all(&self.archive_dir)
+            .await
+            .map_err(io_err)?;
+        tokio::fs::write(path, bytes).await.map_err(io_err)?;
+
+        let mut sig_path = path.as_os_str().to_owned();
+        sig_path.push(".sig");
+        tokio::fs::write(sig_path, format!("{}\n", signature))
+            .await
+            .map_err(io_err)
+    }
+}
+
+/// Digest signed by a daily checkpoint
+pub fn checkpoint_digest(
+    day: NaiveDate,
+    entry_count: i64,
+    last_seq: i64,
+    last_hash: &str,
+) -> String {
+    sha256_hex(
+        format!(
+            "waterswamp-audit-checkpoint|{}|{}|{}|{}",
+            day, entry_count, last_seq, last_hash
+        )
+        .as_bytes(),
+    )
+}
+
+/// Message signed for an archived range
+fn archive_message(
+    day: NaiveDate,
+    first_seq: i64,
+    last_seq: i64,
+    last_hash: &str,
+    file_sha256: &str,
+) -> String {
+    format!(
+        "waterswamp-audit-archive|{}|{}|{}|{}|{}",
+        day, first_seq, last_seq, last_hash, file_sha256
+    )
+}
+
+/// Message signed for a redacted entry
+fn redaction_message(entry_id: Uuid, entry_hash: &str, redacted_hash: &str) -> String {
+    format!(
+        "waterswamp-audit-redaction|{}|{}|{}",
+        entry_id, entry_hash, redacted_hash
+    )
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+fn resolve_range(
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+    today: NaiveDate,
+) -> Result<(NaiveDate, NaiveDate), ServiceError> {
+    let to = to.unwrap_or(today);
+    let from = from.unwrap_or(to - Duration::days(DEFAULT_VERIFY_DAYS));
+
+    if from > to {
+        return Err(ServiceError::BadRequest(
+            "A data inicial deve ser anterior à final".to_string(),
+        ));
+    }
+    if (to - from).num_days() > MAX_VERIFY_DAYS {
+        return Err(ServiceError::BadRequest(format!(
+            "O intervalo máximo de verificação é de {} dias",
+            MAX_VERIFY_DAYS
+        )));
+    }
+    Ok((from, to))
+}
+
+fn issue(kind: AuditChainIssueKind, seq: Option<i64>, detail: String) -> AuditChainIssue {
+    AuditChainIssue { kind, seq, detail }
+}
+
+/// Checks one day: archived ranges first, then the live entries, then the
+/// signed checkpoint against the recomputed chain.
+fn verify_chain(
+    day: NaiveDate,
+    entries: &[AuditChainEntry],
+    checkpoint: Option<&AuditCheckpointDto>,
+    archives: &[AuditArchiveDto],
+    verify_signature: impl Fn(&str, &[u8]) -> bool,
+) -> AuditDayVerification {
+    let mut issues = Vec::new();
+    let mut expected_seq = 1;
+    let mut expected_prev = GENESIS_HASH.to_string();
+
+    for archive in archives {
+        if archive.first_seq != expected_seq {
+            issues.push(issue(
+                AuditChainIssueKind::Gap,
+                Some(expected_seq),
+                format!(
+                    "Arquivo {} começa em {} (esperado {})",
+                    archive.file_path, archive.first_seq, expected_seq
+                ),
+            ));
+        }
+        let message = archive_message(
+            day,
+            archive.first_seq,
+            archive.last_seq,
+            &archive.last_hash,
+            &archive.file_sha256,
+        );
+        if !verify_signature(&archive.signature, message.as_bytes()) {
+            issues.push(issue(
+                AuditChainIssueKind::InvalidSignature,
+                Some(archive.last_seq),
+                format!("Assinatura inválida no arquivo {}", archive.file_path),
+            ));
+        }
+        expected_seq = archive.last_seq + 1;
+        expected_prev = archive.last_hash.clone();
+    }
+
+    for entry in entries {
+        if entry.seq > expected_seq {
+            issues.push(issue(
+                AuditChainIssueKind::Gap,
+                Some(expected_seq),
+                format!("Entradas {} a {} ausentes", expected_seq, entry.seq - 1),
+            ));
+        } else if entry.prev_hash != expected_prev {
+            issues.push(issue(
+                AuditChainIssueKind::BrokenLink,
+                Some(entry.seq),
+                "prev_hash não corresponde à entrada anterior".to_string(),
+            ));
+        }
+        if !entry.hash_valid {
+            issues.push(issue(
+                AuditChainIssueKind::HashMismatch,
+                Some(entry.seq),
+                format!("Entrada {} foi alterada após o registro", entry.id),
+            ));
+        }
+        if let Some(redacted_hash) = &entry.redacted_hash {
+            let message = redaction_message(entry.id, &entry.entry_hash, redacted_hash);
+            let signature = entry.redaction_signature.as_deref().unwrap_or_default();
+            if !verify_signature(signature, message.as_bytes()) {
+                issues.push(issue(
+                    AuditChainIssueKind::InvalidSignature,
+                    Some(entry.seq),
+                    format!("Assinatura inválida na anonimização da entrada {}", entry.id),
+                ));
+            }
+        }
+        expected_seq = entry.seq + 1;
+        expected_prev = entry.entry_hash.clone();
+    }
+
+    let last_seq = (expected_seq > 1).then_some(expected_seq - 1);
+
+    if let Some(cp) = checkpoint {
+        let digest = checkpoint_digest(cp.chain_date, cp.entry_count, cp.last_seq, &cp.last_hash);
+        if digest != cp.digest || !verify_signature(&cp.signature, cp.digest.as_bytes()) {
+            issues.push(issue(
+                AuditChainIssueKind::InvalidSignature,
+                Some(cp.last_seq),
+                "Assinatura do checkpoint inválida".to_string(),
+            ));
+        }
+
+        let sealed_hash = entries
+            .iter()
+            .find(|e| e.seq == cp.last_seq)
+            .map(|e| &e.entry_hash)
+            .or_else(|| {
+                archives
+                    .iter()
+                    .find(|a| a.last_seq == cp.last_seq)
+                    .map(|a| &a.last_hash)
+            });
+        match sealed_hash {
+            None => issues.push(issue(
+                AuditChainIssueKind::CheckpointMismatch,
+                Some(cp.last_seq),
+                format!("Entrada {} selada pelo checkpoint não existe", cp.last_seq),
+            )),
+            Some(hash) if *hash != cp.last_hash => issues.push(issue(
+                AuditChainIssueKind::CheckpointMismatch,
+                Some(cp.last_seq),
+                "Hash da entrada difere do checkpoint assinado".to_string(),
+            )),
+            Some(_) => {}
+        }
+
+        let appended = last_seq.unwrap_or(0) - cp.last_seq;
+        if appended > 0 {
+            issues.push(issue(
+                AuditChainIssueKind::AppendedAfterCheckpoint,
+                Some(cp.last_seq + 1),
+                format!("{} entrada(s) registrada(s) após o selo do dia", appended),
+            ));
+        }
+    }
+
+    AuditDayVerification {
+        chain_date: day,
+        entry_count: entries.len() as i64,
+        archived_count: archives.iter().map(|a| a.entry_count).sum(),
+        last_seq,
+        checkpointed: checkpoint.is_some(),
+        valid: issues
+            .iter()
+            .all(|i| i.kind == AuditChainIssueKind::AppendedAfterCheckpoint),
+        issues,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn day() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, 10).unwrap()
+    }
+
+    /// Chain of `n` valid entries with synthetic hashes
+    fn chain(n: i64) -> Vec<AuditChainEntry> {
+        let mut prev = GENESIS_HASH.to_string();
+        (1..=n)
+            .map(|seq| {
+                let hash = sha256_hex(format!("{}{}", prev, seq).as_bytes());
+                let entry = AuditChainEntry {
+                    id: Uuid::new_v4(),
+                    user_id: None,
+                    username: None,
+                    action: "login".to_string(),
+                    resource: "/login".to_string(),
+                    method: None,
+                    status_code: None,
+                    details: None,
+                    ip_address: None,
+                    user_agent: None,
+                    request_id: None,
+                    duration_ms: None,
+                    created_at: Utc::now(),
+                    chain_date: day(),
+                    seq,
+                    prev_hash: prev.clone(),
+                    entry_hash: hash.clone(),
+                    hash_valid: true,
+                    redacted_hash: None,
+                    redaction_signature: None,
+                };
+                prev = hash;
+                entry
+            })
+            .collect()
+    }
+
+    fn checkpoint_for(entries: &[AuditChainEntry], upto: i64) -> AuditCheckpointDto {
+        let last = entries.iter().find(|e| e.seq == upto).unwrap();
+        AuditCheckpointDto {
+            chain_date: day(),
+            entry_count: upto,
+            last_seq: upto,
+            last_hash: last.entry_hash.clone(),
+            digest: checkpoint_digest(day(), upto, upto, &last.entry_hash),
+            signature: "sig".to_string(),
+            signed_at: Utc::now(),
+        }
+    }
+
+    fn kinds(report: &AuditDayVerification) -> Vec<AuditChainIssueKind> {
+        report.issues.iter().map(|i| i.kind).collect()
+    }
+
+    #[test]
+    fn test_intact_chain_with_checkpoint() {
+        let entries = chain(5);
+        let cp = checkpoint_for(&entries, 5);
+        let report = verify_chain(day(), &entries, Some(&cp), &[], |_, _| true);
+
+        assert!(report.valid);
+        assert!(report.issues.is_empty());
+        assert_eq!(report.last_seq, Some(5));
+    }
+
+    #[test]
+    fn test_detects_edit_and_gap() {
+        let mut entries = chain(5);
+        entries[1].hash_valid = false;
+        let report = verify_chain(day(), &entries, None, &[], |_, _| true);
+        assert!(!report.valid);
+        assert_eq!(kinds(&report), vec![AuditChainIssueKind::HashMismatch]);
+        assert_eq!(report.issues[0].seq, Some(2));
+
+        let mut entries = chain(5);
+        entries.remove(2);
+        let report = verify_chain(day(), &entries, None, &[], |_, _| true);
+        assert!(!report.valid);
+        assert_eq!(kinds(&report), vec![AuditChainIssueKind::Gap]);
+        assert_eq!(report.issues[0].seq, Some(3));
+    }
+
+    #[test]
+    fn test_detects_relinked_chain_and_truncated_tail() {
+        // Hashes recalculados após a edição: a cadeia fecha, mas o checkpoint não
+        let original = chain(4);
+        let cp = checkpoint_for(&original, 4);
+        let mut forged = chain(4);
+        forged[3].entry_hash = "f".repeat(64);
+        let report = verify_chain(day(), &forged, Some(&cp), &[], |_, _| true);
+        assert_eq!(
+            kinds(&report),
+            vec![AuditChainIssueKind::CheckpointMismatch]
+        );
+
+        // Remoção das últimas entradas seladas
+        let report = verify_chain(day(), &original[..2], Some(&cp), &[], |_, _| true);
+        assert_eq!(
+            kinds(&report),
+            vec![AuditChainIssueKind::CheckpointMismatch]
+        );
+    }
+
+    #[test]
+    fn test_checkpoint_signature_and_late_entries() {
+        let entries = chain(6);
+        let cp = checkpoint_for(&entries, 4);
+
+        let report = verify_chain(day(), &entries, Some(&cp), &[], |_, _| true);
+        assert!(report.valid);
+        assert_eq!(
+            kinds(&report),
+            vec![AuditChainIssueKind::AppendedAfterCheckpoint]
+        );
+
+        let report = verify_chain(day(), &entries[..4], Some(&cp), &[], |_, _| false);
+        assert!(!report.valid);
+        assert_eq!(kinds(&report), vec![AuditChainIssueKind::InvalidSignature]);
+    }
+
+    #[test]
+    fn test_redacted_entry_requires_valid_signature() {
+        let mut entries = chain(3);
+        entries[1].redacted_hash = Some("r".repeat(64));
+        entries[1].redaction_signature = Some("sig".to_string());
+
+        let report = verify_chain(day(), &entries, None, &[], |_, _| true);
+        assert!(report.valid);
+
+        let report = verify_chain(day(), &entries, None, &[], |_, _| false);
+        assert!(!report.valid);
+        assert_eq!(kinds(&report), vec![AuditChainIssueKind::InvalidSignature]);
+        assert_eq!(report.issues[0].seq, Some(2));
+    }
+
+    #[test]
+    fn test_continues_after_archive() {
+        let entries = chain(5);
+        let archive = AuditArchiveDto {
+            id: Uuid::new_v4(),
+            chain_date: day(),
+            first_seq: 1,
+            last_seq: 3,
+            entry_count: 3,
+            last_hash: entries[2].entry_hash.clone(),
+            file_path: "audit.json".to_string(),
+            file_sha256: "0".repeat(64),
+            signature: "sig".to_string(),
+            archived_at: Utc::now(),
+        };
+        let cp = checkpoint_for(&entries, 3);
+
+        let report = verify_chain(day(), &entries[3..], Some(&cp), &[archive], |_, _| true);
+        assert_eq!(report.archived_count, 3);
+        assert_eq!(report.entry_count, 2);
+        assert_eq!(
+            kinds(&report),
+            vec![AuditChainIssueKind::AppendedAfterCheckpoint]
+        );
+    }
+
+    #[test]
+    fn test_resolve_range() {
+        let today = day();
+        let (from, to) = resolve_range(None, None, today).unwrap();
+        assert_eq!(to, today);
+        assert_eq!((to - from).num_days(), DEFAULT_VERIFY_DAYS);
+
+        assert!(resolve_range(Some(today), Some(today - Duration::days(1)), today).is_err());
+        assert!(resolve_range(Some(today - Duration::days(400)), None, today).is_err());
+    }
+}
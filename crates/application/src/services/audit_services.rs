@@ -1,3 +1,5 @@
+use core_services::request_context;
+use domain::models::DelegationScope;
 use sqlx::PgPool;
 use std::sync::Arc;
 use uuid::Uuid;
@@ -38,15 +40,18 @@ impl AuditService {
         let pool = self.pool.clone();
         let action = action.to_string();
         let resource = resource.to_string();
+        // Links the entry to the entity changes of the same request
+        let request_id = request_context::current().map(|ctx| ctx.request_id);
 
         // Spawn task to avoid blocking the request
         tokio::spawn(async move {
             if let Err(e) = sqlx::query(
                 r#"
                 INSERT INTO audit_logs (
-                    user_id, username, action, resource, details, ip_address, user_agent
+                    user_id, username, action, resource, details, ip_address, user_agent,
+                    request_id
                 )
-                VALUES ($1, $2, $3, $4, $5, $6::INET, $7)
+                VALUES ($1, $2, $3, $4, $5, $6::INET, $7, $8)
                 "#,
             )
             .bind(user_id)
@@ -56,6 +61,7 @@ impl AuditService {
             .bind(details)
             .bind(ip_address)
             .bind(user_agent)
+            .bind(request_id)
             .execute(pool.as_ref())
             .await
             {
@@ -614,6 +620,194 @@ impl AuditService {
             "Access denied to resource"
         );
     }
+
+    /// Logs a request authenticated with an API key.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn log_api_key_use(
+        &self,
+        principal_id: Uuid,
+        principal_name: &str,
+        api_key_id: Uuid,
+        api_key_prefix: &str,
+        resource: &str,
+        method: &str,
+        status_code: u16,
+        ip_address: Option<String>,
+        user_agent: Option<String>,
+    ) {
+        let details = serde_json::json!({
+            "api_key_id": api_key_id,
+            "api_key_prefix": api_key_prefix,
+            "method": method,
+            "status_code": status_code
+        });
+
+        self.log_event(
+            Some(principal_id),
+            Some(principal_name.to_string()),
+            "api_key_used",
+            resource,
+            Some(details),
+            ip_address,
+            user_agent,
+        )
+        .await;
+
+        tracing::debug!(
+            principal_id = %principal_id,
+            api_key_prefix = %api_key_prefix,
+            resource = %resource,
+            event_type = "audit_api_key_used",
+            "Request authenticated with API key"
+        );
+    }
+
+    /// Logs a request rejected because its API key did not authenticate.
+    pub async fn log_api_key_failed(
+        &self,
+        api_key_prefix: Option<&str>,
+        resource: &str,
+        method: &str,
+        ip_address: Option<String>,
+        user_agent: Option<String>,
+    ) {
+        let details = serde_json::json!({
+            "api_key_prefix": api_key_prefix,
+            "method": method
+        });
+
+        self.log_event(
+            None,
+            None,
+            "api_key_failed",
+            resource,
+            Some(details),
+            ip_address,
+            user_agent,
+        )
+        .await;
+
+        tracing::warn!(
+            api_key_prefix = ?api_key_prefix,
+            resource = %resource,
+            event_type = "audit_api_key_failed",
+            "Request rejected with invalid API key"
+        );
+    }
+
+    /// Logs a decision taken by a delegate on behalf of the approval holder.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn log_delegated_approval(
+        &self,
+        delegate_id: Uuid,
+        delegate_name: &str,
+        on_behalf_of: Uuid,
+        scope: DelegationScope,
+        decision: &str,
+        resource: &str,
+        ip_address: Option<String>,
+        user_agent: Option<String>,
+    ) {
+        let details = serde_json::json!({
+            "on_behalf_of": on_behalf_of,
+            "scope": scope.as_str(),
+            "decision": decision
+        });
+
+        self.log_event(
+            Some(delegate_id),
+            Some(delegate_name.to_string()),
+            "delegated_approval",
+            resource,
+            Some(details),
+            ip_address,
+            user_agent,
+        )
+        .await;
+
+        tracing::info!(
+            delegate_id = %delegate_id,
+            on_behalf_of = %on_behalf_of,
+            resource = %resource,
+            decision = %decision,
+            event_type = "audit_delegated_approval",
+            "Decision taken by delegation"
+        );
+    }
+
+    /// Logs an LGPD personal-data export. The CPF used in the lookup is
+    /// not recorded.
+    pub async fn log_lgpd_export(
+        &self,
+        exported_by: Uuid,
+        exported_by_name: &str,
+        subject_user_id: Option<Uuid>,
+        subject_driver_id: Option<Uuid>,
+        ip_address: Option<String>,
+        user_agent: Option<String>,
+    ) {
+        let details = serde_json::json!({
+            "subject_user_id": subject_user_id,
+            "subject_driver_id": subject_driver_id
+        });
+
+        self.log_event(
+            Some(exported_by),
+            Some(exported_by_name.to_string()),
+            "lgpd_export",
+            "/api/admin/lgpd/export",
+            Some(details),
+            ip_address,
+            user_agent,
+        )
+        .await;
+
+        tracing::info!(
+            exported_by = %exported_by,
+            subject_user_id = ?subject_user_id,
+            subject_driver_id = ?subject_driver_id,
+            event_type = "audit_lgpd_export",
+            "Personal data exported"
+        );
+    }
+
+    /// Logs an LGPD anonymization.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn log_lgpd_anonymization(
+        &self,
+        anonymized_by: Uuid,
+        anonymized_by_name: &str,
+        subject_user_id: Option<Uuid>,
+        subject_driver_id: Option<Uuid>,
+        reason: &str,
+        ip_address: Option<String>,
+        user_agent: Option<String>,
+    ) {
+        let details = serde_json::json!({
+            "subject_user_id": subject_user_id,
+            "subject_driver_id": subject_driver_id,
+            "reason": reason
+        });
+
+        self.log_event(
+            Some(anonymized_by),
+            Some(anonymized_by_name.to_string()),
+            "lgpd_anonymization",
+            "/api/admin/lgpd/anonymize",
+            Some(details),
+            ip_address,
+            user_agent,
+        )
+        .await;
+
+        tracing::warn!(
+            anonymized_by = %anonymized_by,
+            subject_user_id = ?subject_user_id,
+            subject_driver_id = ?subject_driver_id,
+            event_type = "audit_lgpd_anonymization",
+            "Personal data anonymized"
+        );
+    }
 }
 
 /// Helper trait for extracting client info from request
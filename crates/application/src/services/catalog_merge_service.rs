@@ -0,0 +1,304 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use domain::errors::RepositoryError;
+use domain::models::{
+    CatalogDuplicateCandidate, CatalogDuplicateCluster, CatalogDuplicateFilter,
+    CatalogDuplicatePair, CatalogDuplicateReport, CatalogItemMergeResult, CatalogItemRedirectDto,
+    MergeCatalogItemsPayload,
+};
+use domain::ports::CatalogMergeRepositoryPort;
+use tracing::info;
+use uuid::Uuid;
+
+use crate::errors::ServiceError;
+
+/// Abaixo disso o trigram junta itens que só compartilham palavras comuns
+const MIN_SIMILARITY: f32 = 0.3;
+
+/// Pares examinados por relatório
+const MAX_PAIRS: usize = 5000;
+
+/// Duplicados fundidos de uma vez
+const MAX_MERGE: usize = 50;
+
+/// Finds CATMAT items that look like the same product (left behind mostly by
+/// the legacy import) and merges them into a single surviving item.
+pub struct CatalogMergeService {
+    repo: Arc<dyn CatalogMergeRepositoryPort>,
+}
+
+impl CatalogMergeService {
+    pub fn new(repo: Arc<dyn CatalogMergeRepositoryPort>) -> Self {
+        Self { repo }
+    }
+
+    /// Agrupa itens de descrição parecida (e, conforme o filtro, mesmo PDM e
+    /// mesma unidade), sugerindo qual deve sobreviver em cada grupo
+    pub async fn duplicate_report(
+        &self,
+        filter: &CatalogDuplicateFilter,
+    ) -> Result<CatalogDuplicateReport, ServiceError> {
+        if !(MIN_SIMILARITY..=1.0).contains(&filter.min_similarity) {
+            return Err(ServiceError::BadRequest(format!(
+                "A similaridade mínima deve estar entre {} e 1",
+                MIN_SIMILARITY
+            )));
+        }
+
+        let mut pairs = self
+            .repo
+            .find_duplicate_pairs(filter, MAX_PAIRS as i64 + 1)
+            .await?;
+        let truncated = pairs.len() > MAX_PAIRS;
+        pairs.truncate(MAX_PAIRS);
+
+        let groups = cluster_pairs(&pairs);
+        let ids: Vec<Uuid> = groups.iter().flat_map(|(ids, _)| ids.clone()).collect();
+        let mut candidates: HashMap<Uuid, CatalogDuplicateCandidate> = self
+            .repo
+            .find_candidates(&ids)
+            .await?
+            .into_iter()
+            .map(|c| (c.id, c))
+            .collect();
+
+        let mut clusters: Vec<CatalogDuplicateCluster> = groups
+            .into_iter()
+            .filter_map(|(ids, max_similarity)| {
+                let mut items: Vec<_> = ids.iter().filter_map(|id| candidates.remove(id)).collect();
+                if items.len() < 2 {
+                    return None;
+                }
+                items.sort_by(|a, b| a.code.cmp(&b.code));
+                let suggested_survivor_id = suggest_survivor(&items)?.id;
+                Some(CatalogDuplicateCluster {
+                    items,
+                    max_similarity,
+                    suggested_survivor_id,
+                })
+            })
+            .collect();
+        clusters.sort_by(|a, b| b.max_similarity.total_cmp(&a.max_similarity));
+
+        Ok(CatalogDuplicateReport {
+            clusters,
+            truncated,
+        })
+    }
+
+    /// Funde os duplicados no item sobrevivente: referências, saldos e lotes
+    /// passam para ele e os antigos ficam inativos, redirecionados a ele
+    pub async fn merge_items(
+        &self,
+        payload: MergeCatalogItemsPayload,
+        merged_by: Option<Uuid>,
+    ) -> Result<CatalogItemMergeResult, ServiceError> {
+        validate_merge(&payload)?;
+        let reason = payload
+            .reason
+            .as_deref()
+            .map(str::trim)
+            .filter(|r| !r.is_empty());
+
+        let result = self
+            .repo
+            .merge_items(
+                payload.survivor_id,
+                &payload.duplicate_ids,
+                reason,
+                merged_by,
+            )
+            .await
+            .map_err(|e| match e {
+                RepositoryError::NotFound => {
+                    ServiceError::NotFound("Item CATMAT a fundir não encontrado".to_string())
+                }
+                e => ServiceError::from(e),
+            })?;
+
+        info!(
+            survivor_id = %result.survivor_id,
+            merged = result.merged_item_ids.len(),
+            combined_stocks = result.combined_stocks,
+            "Itens CATMAT duplicados fundidos"
+        );
+        Ok(result)
+    }
+
+    pub async fn list_redirects(
+        &self,
+        new_item_id: Option<Uuid>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<CatalogItemRedirectDto>, i64), ServiceError> {
+        self.repo
+            .list_redirects(new_item_id, limit, offset)
+            .await
+            .map_err(ServiceError::from)
+    }
+}
+
+fn validate_merge(payload: &MergeCatalogItemsPayload) -> Result<(), ServiceError> {
+    if payload.duplicate_ids.is_empty() {
+        return Err(ServiceError::BadRequest(
+            "Informe ao menos um item duplicado".to_string(),
+        ));
+    }
+    if payload.duplicate_ids.len() > MAX_MERGE {
+        return Err(ServiceError::BadRequest(format!(
+            "No máximo {} itens podem ser fundidos de uma vez",
+            MAX_MERGE
+        )));
+    }
+    let mut seen = HashSet::from([payload.survivor_id]);
+    if !payload.duplicate_ids.iter().all(|id| seen.insert(*id)) {
+        return Err(ServiceError::BadRequest(
+            "Os itens duplicados devem ser distintos entre si e do sobrevivente".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Groups items connected by similar pairs, even when only through a third
+/// item. Returns the members of each group and its highest similarity.
+fn cluster_pairs(pairs: &[CatalogDuplicatePair]) -> Vec<(Vec<Uuid>, f32)> {
+    fn root(parent: &mut HashMap<Uuid, Uuid>, id: Uuid) -> Uuid {
+        let mut current = id;
+        while let Some(&next) = parent.get(&current) {
+            if next == current {
+                break;
+            }
+            current = next;
+        }
+        parent.insert(id, current);
+        current
+    }
+
+    let mut parent: HashMap<Uuid, Uuid> = HashMap::new();
+    for pair in pairs {
+        parent.entry(pair.item_id).or_insert(pair.item_id);
+        parent.entry(pair.other_id).or_insert(pair.other_id);
+        let (a, b) = (
+            root(&mut parent, pair.item_id),
+            root(&mut parent, pair.other_id),
+        );
+        if a != b {
+            parent.insert(b, a);
+        }
+    }
+
+    let mut groups: HashMap<Uuid, (Vec<Uuid>, f32)> = HashMap::new();
+    let ids: Vec<Uuid> = parent.keys().copied().collect();
+    for id in ids {
+        let r = root(&mut parent, id);
+        groups
+            .entry(r)
+            .or_insert_with(|| (Vec::new(), 0.0))
+            .0
+            .push(id);
+    }
+    for pair in pairs {
+        let r = root(&mut parent, pair.item_id);
+        if let Some(group) = groups.get_mut(&r) {
+            group.1 = group.1.max(pair.similarity);
+        }
+    }
+    groups.into_values().collect()
+}
+
+/// Verified items first, then the most moved, then the oldest
+fn suggest_survivor(items: &[CatalogDuplicateCandidate]) -> Option<&CatalogDuplicateCandidate> {
+    items.iter().min_by(|a, b| {
+        let unverified = |c: &CatalogDuplicateCandidate| c.verification_status != "verified";
+        unverified(a)
+            .cmp(&unverified(b))
+            .then(b.movement_count.cmp(&a.movement_count))
+            .then(a.created_at.cmp(&b.created_at))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+    use rust_decimal::Decimal;
+
+    fn candidate(status: &str, movements: i64, age_days: i64) -> CatalogDuplicateCandidate {
+        CatalogDuplicateCandidate {
+            id: Uuid::new_v4(),
+            code: "1".to_string(),
+            description: "CANETA".to_string(),
+            pdm_id: Uuid::nil(),
+            pdm_code: "1".to_string(),
+            pdm_description: "CANETA".to_string(),
+            unit_of_measure_id: Uuid::nil(),
+            unit_symbol: "UN".to_string(),
+            verification_status: status.to_string(),
+            is_active: true,
+            stock_quantity: Decimal::ZERO,
+            movement_count: movements,
+            created_at: Utc::now() - Duration::days(age_days),
+        }
+    }
+
+    fn pair(item_id: Uuid, other_id: Uuid, similarity: f32) -> CatalogDuplicatePair {
+        CatalogDuplicatePair {
+            item_id,
+            other_id,
+            similarity,
+        }
+    }
+
+    #[test]
+    fn test_cluster_pairs_joins_transitive_pairs() {
+        let (a, b, c, d, e) = (
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+        );
+        let mut clusters = cluster_pairs(&[pair(a, b, 0.9), pair(d, e, 0.5), pair(b, c, 0.7)]);
+        clusters.sort_by_key(|(ids, _)| ids.len());
+
+        assert_eq!(clusters.len(), 2);
+        assert_eq!(clusters[0].0.len(), 2);
+        assert_eq!(clusters[0].1, 0.5);
+        let mut big = clusters[1].0.clone();
+        big.sort();
+        let mut expected = vec![a, b, c];
+        expected.sort();
+        assert_eq!(big, expected);
+        assert_eq!(clusters[1].1, 0.9);
+    }
+
+    #[test]
+    fn test_suggest_survivor_prefers_verified_then_used_then_oldest() {
+        let items = vec![
+            candidate("pending", 100, 900),
+            candidate("verified", 2, 10),
+            candidate("verified", 5, 1),
+        ];
+        assert_eq!(suggest_survivor(&items).unwrap().id, items[2].id);
+
+        let items = vec![candidate("pending", 3, 10), candidate("pending", 3, 400)];
+        assert_eq!(suggest_survivor(&items).unwrap().id, items[1].id);
+    }
+
+    #[test]
+    fn test_validate_merge_rejects_survivor_among_duplicates() {
+        let survivor_id = Uuid::new_v4();
+        let payload = |duplicate_ids: Vec<Uuid>| MergeCatalogItemsPayload {
+            survivor_id,
+            duplicate_ids,
+            reason: None,
+        };
+
+        assert!(validate_merge(&payload(vec![])).is_err());
+        assert!(validate_merge(&payload(vec![survivor_id])).is_err());
+        let dup = Uuid::new_v4();
+        assert!(validate_merge(&payload(vec![dup, dup])).is_err());
+        assert!(validate_merge(&payload(vec![dup, Uuid::new_v4()])).is_ok());
+    }
+}
@@ -0,0 +1,280 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use domain::models::{
+    CatalogKind, CatalogMatchKind, CatalogSuggestion, CatmatItemSearchFilter, CatmatItemSearchHit,
+    CatserItemSearchFilter, CatserItemSearchHit,
+};
+use domain::ports::CatalogSearchRepositoryPort;
+use tracing::warn;
+
+use crate::errors::ServiceError;
+
+/// Termos mais longos que isso são cortados antes de ir ao banco
+const MAX_QUERY_CHARS: usize = 200;
+
+/// Autocompletar: mínimo de caracteres, teto de sugestões e tempo de resposta esperado
+const SUGGEST_MIN_CHARS: usize = 2;
+const SUGGEST_MAX_LIMIT: i64 = 20;
+const SUGGEST_BUDGET: Duration = Duration::from_millis(50);
+
+/// Ranked search over CATMAT and CATSER items: Portuguese full-text search
+/// with accents ignored, falling back to trigram similarity when nothing
+/// matches (typos). The same indexes answer the autocomplete.
+pub struct CatalogSearchService {
+    repo: Arc<dyn CatalogSearchRepositoryPort>,
+}
+
+impl CatalogSearchService {
+    pub fn new(repo: Arc<dyn CatalogSearchRepositoryPort>) -> Self {
+        Self { repo }
+    }
+
+    pub async fn search_catmat_items(
+        &self,
+        query: &str,
+        filter: &CatmatItemSearchFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<CatmatItemSearchHit>, i64), ServiceError> {
+        let query = search_text(query)?;
+        let (hits, total) = self
+            .repo
+            .search_catmat_items(&query, CatalogMatchKind::FullText, filter, limit, offset)
+            .await?;
+        if total > 0 {
+            return Ok((hits, total));
+        }
+
+        let (mut hits, total) = self
+            .repo
+            .search_catmat_items(&query, CatalogMatchKind::Fuzzy, filter, limit, offset)
+            .await?;
+        for hit in &mut hits {
+            hit.highlight = highlight_similar(&hit.item.description, &query);
+        }
+        Ok((hits, total))
+    }
+
+    pub async fn search_catser_items(
+        &self,
+        query: &str,
+        filter: &CatserItemSearchFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<CatserItemSearchHit>, i64), ServiceError> {
+        let query = search_text(query)?;
+        let (hits, total) = self
+            .repo
+            .search_catser_items(&query, CatalogMatchKind::FullText, filter, limit, offset)
+            .await?;
+        if total > 0 {
+            return Ok((hits, total));
+        }
+
+        let (mut hits, total) = self
+            .repo
+            .search_catser_items(&query, CatalogMatchKind::Fuzzy, filter, limit, offset)
+            .await?;
+        for hit in &mut hits {
+            hit.highlight = highlight_similar(&hit.item.description, &query);
+        }
+        Ok((hits, total))
+    }
+
+    /// Sugestões de itens ativos para o texto digitado até agora. Textos
+    /// curtos demais não geram sugestões.
+    pub async fn suggest(
+        &self,
+        query: &str,
+        catalog: Option<CatalogKind>,
+        limit: i64,
+    ) -> Result<Vec<CatalogSuggestion>, ServiceError> {
+        let terms = terms(query);
+        if terms.iter().map(|t| t.chars().count()).sum::<usize>() < SUGGEST_MIN_CHARS {
+            return Ok(Vec::new());
+        }
+        let prefix_query = terms
+            .iter()
+            .map(|t| format!("{}:*", t))
+            .collect::<Vec<_>>()
+            .join(" & ");
+        let code_prefix = match terms.as_slice() {
+            [code] if code.chars().all(|c| c.is_ascii_digit()) => Some(code.as_str()),
+            _ => None,
+        };
+
+        let started = Instant::now();
+        let suggestions = self
+            .repo
+            .suggest(
+                &prefix_query,
+                code_prefix,
+                catalog,
+                limit.clamp(1, SUGGEST_MAX_LIMIT),
+            )
+            .await?;
+        let elapsed = started.elapsed();
+        if elapsed > SUGGEST_BUDGET {
+            warn!(
+                elapsed_ms = elapsed.as_millis() as u64,
+                query = %prefix_query,
+                "Autocompletar do catálogo acima do tempo esperado"
+            );
+        }
+
+        Ok(suggestions)
+    }
+}
+
+fn search_text(query: &str) -> Result<String, ServiceError> {
+    let query: String = query.trim().chars().take(MAX_QUERY_CHARS).collect();
+    if query.is_empty() {
+        return Err(ServiceError::BadRequest(
+            "Informe o texto da busca".to_string(),
+        ));
+    }
+    Ok(query)
+}
+
+/// Palavras do texto (letras e dígitos), em minúsculas
+fn terms(query: &str) -> Vec<String> {
+    query
+        .chars()
+        .take(MAX_QUERY_CHARS)
+        .collect::<String>()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Minúsculas e sem acentos, para comparar palavras
+fn fold(word: &str) -> String {
+    word.chars()
+        .flat_map(char::to_lowercase)
+        .map(|c| match c {
+            'á' | 'à' | 'â' | 'ã' | 'ä' => 'a',
+            'é' | 'è' | 'ê' | 'ë' => 'e',
+            'í' | 'ì' | 'î' | 'ï' => 'i',
+            'ó' | 'ò' | 'ô' | 'õ' | 'ö' => 'o',
+            'ú' | 'ù' | 'û' | 'ü' => 'u',
+            'ç' => 'c',
+            'ñ' => 'n',
+            c => c,
+        })
+        .collect()
+}
+
+fn levenshtein(a: &[char], b: &[char]) -> usize {
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// Whether a description word is close to a query term: same word, the term
+/// as a prefix, or within a small edit distance of the word or its prefix
+fn similar(word: &[char], term: &[char]) -> bool {
+    if term.len() < 3 {
+        return word == term;
+    }
+    if word.starts_with(term) {
+        return true;
+    }
+    let allowed = if term.len() <= 5 { 1 } else { 2 };
+    let prefix = &word[..word.len().min(term.len())];
+    levenshtein(word, term) <= allowed || levenshtein(prefix, term) <= allowed
+}
+
+/// Marks the words of `description` similar to the terms of `query`. The
+/// trigram search has no headline of its own.
+fn highlight_similar(description: &str, query: &str) -> String {
+    let terms: Vec<Vec<char>> = terms(query)
+        .iter()
+        .map(|t| fold(t).chars().collect())
+        .collect();
+
+    let mut out = String::with_capacity(description.len() + 16);
+    let mut word = String::new();
+    let flush = |word: &mut String, out: &mut String| {
+        if word.is_empty() {
+            return;
+        }
+        let folded: Vec<char> = fold(word).chars().collect();
+        if terms.iter().any(|t| similar(&folded, t)) {
+            out.push_str("<mark>");
+            out.push_str(word);
+            out.push_str("</mark>");
+        } else {
+            out.push_str(word);
+        }
+        word.clear();
+    };
+
+    for c in description.chars() {
+        if c.is_alphanumeric() {
+            word.push(c);
+        } else {
+            flush(&mut word, &mut out);
+            out.push(c);
+        }
+    }
+    flush(&mut word, &mut out);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_terms_drop_tsquery_operators() {
+        assert_eq!(
+            terms("Caneta & (azul) | !esferográfica:*"),
+            vec!["caneta", "azul", "esferográfica"]
+        );
+        assert!(terms(" ,;: ").is_empty());
+    }
+
+    #[test]
+    fn test_fold_removes_accents() {
+        assert_eq!(fold("AÇÚCAR Refinado"), "acucar refinado");
+        assert_eq!(
+            fold("Papel Sulfite Não-Reciclado"),
+            "papel sulfite nao-reciclado"
+        );
+    }
+
+    #[test]
+    fn test_highlight_similar_marks_typos_and_prefixes() {
+        assert_eq!(
+            highlight_similar("PARAFUSO SEXTAVADO, AÇO INOX", "parafuzo aco"),
+            "<mark>PARAFUSO</mark> SEXTAVADO, <mark>AÇO</mark> INOX"
+        );
+        assert_eq!(
+            highlight_similar("Papel sulfite A4", "sulf"),
+            "Papel <mark>sulfite</mark> A4"
+        );
+    }
+
+    #[test]
+    fn test_highlight_similar_ignores_distant_words() {
+        assert_eq!(
+            highlight_similar("Cadeira giratória", "caneta"),
+            "Cadeira giratória"
+        );
+    }
+
+    #[test]
+    fn test_search_text_rejects_blank() {
+        assert!(search_text("   ").is_err());
+        assert_eq!(search_text("  caneta ").unwrap(), "caneta");
+    }
+}
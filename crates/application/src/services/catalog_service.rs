@@ -1,8 +1,11 @@
 use domain::{
     models::catalog::*,
+    models::{CatalogKind, CatmatImportResult, CatserImportResult},
     ports::catalog::*,
+    ports::CatalogMergeRepositoryPort,
 };
 use crate::errors::ServiceError;
+use crate::external::CatalogSyncService;
 use std::sync::Arc;
 use uuid::Uuid;
 
@@ -18,6 +21,8 @@ pub struct CatalogService {
     catser_group_repo: Arc<dyn CatserGroupRepositoryPort>,
     catser_class_repo: Arc<dyn CatserClassRepositoryPort>,
     catser_item_repo: Arc<dyn CatserItemRepositoryPort>,
+    catalog_sync: Option<Arc<CatalogSyncService>>,
+    item_redirects: Option<Arc<dyn CatalogMergeRepositoryPort>>,
 }
 
 impl CatalogService {
@@ -39,9 +44,22 @@ impl CatalogService {
             unit_repo, conversion_repo,
             catmat_group_repo, catmat_class_repo, catmat_pdm_repo, catmat_item_repo,
             catser_section_repo, catser_division_repo, catser_group_repo, catser_class_repo, catser_item_repo,
+            catalog_sync: None,
+            item_redirects: None,
         }
     }
 
+    pub fn with_catalog_sync(mut self, catalog_sync: Arc<CatalogSyncService>) -> Self {
+        self.catalog_sync = Some(catalog_sync);
+        self
+    }
+
+    /// IDs de itens fundidos passam a resolver para o item que os substituiu
+    pub fn with_item_redirects(mut self, item_redirects: Arc<dyn CatalogMergeRepositoryPort>) -> Self {
+        self.item_redirects = Some(item_redirects);
+        self
+    }
+
     // ============================
     // Unit of Measure
     // ============================
@@ -251,11 +269,23 @@ impl CatalogService {
     }
 
     pub async fn get_catmat_item(&self, id: Uuid) -> Result<CatmatItemWithDetailsDto, ServiceError> {
+        // Item fundido segue cadastrado (inativo), mas resolve para o sobrevivente
+        let id = match &self.item_redirects {
+            Some(redirects) => redirects.find_redirect(id).await?.map_or(id, |r| r.new_item_id),
+            None => id,
+        };
         self.catmat_item_repo.find_with_details_by_id(id).await?.ok_or(ServiceError::NotFound("Item CATMAT não encontrado".to_string()))
     }
 
     pub async fn update_catmat_item(&self, id: Uuid, payload: UpdateCatmatItemPayload) -> Result<CatmatItemDto, ServiceError> {
         let _ = self.catmat_item_repo.find_by_id(id).await?.ok_or(ServiceError::NotFound("Item CATMAT não encontrado".to_string()))?;
+        if payload.is_active == Some(true) {
+            if let Some(redirects) = &self.item_redirects {
+                if let Some(redirect) = redirects.find_redirect(id).await? {
+                    return Err(ServiceError::BadRequest(format!("Item CATMAT fundido no item {} não pode ser reativado", redirect.new_item_id)));
+                }
+            }
+        }
         if let Some(ref code) = payload.code {
             if self.catmat_item_repo.exists_by_code_excluding(code, id).await? {
                 return Err(ServiceError::Conflict(format!("Item CATMAT com código '{}' já existe", code)));
@@ -471,4 +501,43 @@ impl CatalogService {
     pub async fn list_catser_items(&self, limit: i64, offset: i64, search: Option<String>, class_id: Option<Uuid>, is_active: Option<bool>) -> Result<(Vec<CatserItemWithDetailsDto>, i64), ServiceError> {
         self.catser_item_repo.list(limit, offset, search, class_id, is_active).await.map_err(ServiceError::from)
     }
+
+    // ============================
+    // Official catalog import
+    // ============================
+
+    fn catalog_sync(&self) -> Result<&CatalogSyncService, ServiceError> {
+        self.catalog_sync.as_deref().ok_or(ServiceError::Internal("Integração com o ComprasGov não configurada".to_string()))
+    }
+
+    /// Importa um item CATMAT do ComprasGov com o grupo, a classe e o PDM que faltarem
+    pub async fn import_catmat_item(&self, code: &str) -> Result<CatmatImportResult, ServiceError> {
+        let (code, created) = self.catalog_sync()?.import_item(CatalogKind::Catmat, code).await?;
+        let missing = || ServiceError::Internal(format!("Hierarquia do item CATMAT {} não encontrada após a importação", code));
+
+        let item = self.catmat_item_repo.find_by_code(&code).await?.ok_or_else(missing)?;
+        let pdm = self.catmat_pdm_repo.find_by_id(item.pdm_id).await?.ok_or_else(missing)?;
+        let class = self.catmat_class_repo.find_by_id(pdm.class_id).await?.ok_or_else(missing)?;
+        let group = self.catmat_group_repo.find_by_id(class.group_id).await?.ok_or_else(missing)?;
+        Ok(CatmatImportResult { group, class, pdm, item, created })
+    }
+
+    /// Importa um item CATSER do ComprasGov com a seção, a divisão, o grupo e a classe que faltarem
+    pub async fn import_catser_item(&self, code: &str) -> Result<CatserImportResult, ServiceError> {
+        let (code, created) = self.catalog_sync()?.import_item(CatalogKind::Catser, code).await?;
+        let missing = || ServiceError::Internal(format!("Hierarquia do serviço CATSER {} não encontrada após a importação", code));
+
+        let item = self.catser_item_repo.find_by_code(&code).await?.ok_or_else(missing)?;
+        let class = self.catser_class_repo.find_by_id(item.class_id).await?.ok_or_else(missing)?;
+        let group = self.catser_group_repo.find_by_id(class.group_id).await?.ok_or_else(missing)?;
+        let division = match group.division_id {
+            Some(id) => Some(self.catser_division_repo.find_by_id(id).await?.ok_or_else(missing)?),
+            None => None,
+        };
+        let section = match division.as_ref() {
+            Some(d) => Some(self.catser_section_repo.find_by_id(d.section_id).await?.ok_or_else(missing)?),
+            None => None,
+        };
+        Ok(CatserImportResult { section, division, group, class, item, created })
+    }
 }
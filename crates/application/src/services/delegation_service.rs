@@ -0,0 +1,203 @@
+use crate::errors::ServiceError;
+use chrono::{DateTime, Duration, Utc};
+use domain::models::{ApprovalDelegationDto, CreateDelegationPayload, DelegationScope};
+use domain::ports::{ApprovalDelegationRepositoryPort, UserRepositoryPort};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Longest validity window accepted for a single delegation (covers a
+/// full leave period; longer absences should reassign the role instead).
+const MAX_DELEGATION_DAYS: i64 = 180;
+
+pub struct DelegationService {
+    repo: Arc<dyn ApprovalDelegationRepositoryPort>,
+    user_repo: Arc<dyn UserRepositoryPort>,
+}
+
+impl DelegationService {
+    pub fn new(
+        repo: Arc<dyn ApprovalDelegationRepositoryPort>,
+        user_repo: Arc<dyn UserRepositoryPort>,
+    ) -> Self {
+        Self { repo, user_repo }
+    }
+
+    /// Grants the caller's approval authority for `payload.scopes` to another user
+    pub async fn create_delegation(
+        &self,
+        delegator_id: Uuid,
+        payload: CreateDelegationPayload,
+    ) -> Result<ApprovalDelegationDto, ServiceError> {
+        if payload.delegate_id == delegator_id {
+            return Err(ServiceError::BadRequest(
+                "Não é possível delegar para si mesmo".to_string(),
+            ));
+        }
+
+        let now = Utc::now();
+        let valid_from = payload.valid_from.unwrap_or(now);
+        validate_period(valid_from, payload.valid_until, now)?;
+
+        let mut scopes = payload.scopes;
+        scopes.sort_by_key(|s| s.as_str());
+        scopes.dedup();
+        if scopes.is_empty() {
+            return Err(ServiceError::BadRequest(
+                "Informe ao menos um escopo".to_string(),
+            ));
+        }
+
+        let reason = payload
+            .reason
+            .as_deref()
+            .map(str::trim)
+            .filter(|r| !r.is_empty());
+
+        if self
+            .user_repo
+            .find_by_id(payload.delegate_id)
+            .await
+            .map_err(ServiceError::from)?
+            .is_none()
+        {
+            return Err(ServiceError::NotFound(
+                "Usuário delegado não encontrado".to_string(),
+            ));
+        }
+
+        let delegation = self
+            .repo
+            .create(
+                delegator_id,
+                payload.delegate_id,
+                &scopes,
+                valid_from,
+                payload.valid_until,
+                reason,
+                delegator_id,
+            )
+            .await
+            .map_err(ServiceError::from)?;
+
+        tracing::info!(
+            delegation_id = %delegation.id,
+            delegator_id = %delegator_id,
+            delegate_id = %delegation.delegate_id,
+            valid_until = %delegation.valid_until,
+            "Delegação de aprovação criada"
+        );
+        Ok(delegation)
+    }
+
+    /// Delegations granted or received by the user
+    pub async fn list_delegations(
+        &self,
+        user_id: Uuid,
+        active_only: bool,
+    ) -> Result<Vec<ApprovalDelegationDto>, ServiceError> {
+        self.repo
+            .list_for_user(user_id, active_only)
+            .await
+            .map_err(ServiceError::from)
+    }
+
+    /// Ends a delegation immediately. Either side may revoke it.
+    pub async fn revoke_delegation(&self, id: Uuid, user_id: Uuid) -> Result<(), ServiceError> {
+        let delegation = self
+            .repo
+            .find_by_id(id)
+            .await
+            .map_err(ServiceError::from)?
+            .ok_or_else(|| ServiceError::NotFound("Delegação não encontrada".to_string()))?;
+
+        if delegation.delegator_id != user_id && delegation.delegate_id != user_id {
+            return Err(ServiceError::NotFound("Delegação não encontrada".to_string()));
+        }
+
+        if !self.repo.revoke(id, user_id).await.map_err(ServiceError::from)? {
+            return Err(ServiceError::Conflict("Delegação já revogada".to_string()));
+        }
+        Ok(())
+    }
+
+    /// Users whose `scope` authority the delegate currently holds
+    pub async fn active_delegators(
+        &self,
+        delegate_id: Uuid,
+        scope: DelegationScope,
+    ) -> Result<Vec<Uuid>, ServiceError> {
+        let mut delegators: Vec<Uuid> = self
+            .repo
+            .list_active_for_delegate(delegate_id, scope)
+            .await
+            .map_err(ServiceError::from)?
+            .into_iter()
+            .map(|d| d.delegator_id)
+            .collect();
+        delegators.sort();
+        delegators.dedup();
+        Ok(delegators)
+    }
+
+    /// Checks, at the moment of the decision, that the delegation is still in force
+    pub async fn ensure_active(
+        &self,
+        delegator_id: Uuid,
+        delegate_id: Uuid,
+        scope: DelegationScope,
+    ) -> Result<ApprovalDelegationDto, ServiceError> {
+        self.repo
+            .find_active(delegator_id, delegate_id, scope)
+            .await
+            .map_err(ServiceError::from)?
+            .ok_or_else(|| {
+                ServiceError::Forbidden(
+                    "Delegação de competência inexistente, revogada ou expirada".to_string(),
+                )
+            })
+    }
+}
+
+fn validate_period(
+    valid_from: DateTime<Utc>,
+    valid_until: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> Result<(), ServiceError> {
+    if valid_until <= valid_from {
+        return Err(ServiceError::BadRequest(
+            "Fim da vigência deve ser posterior ao início".to_string(),
+        ));
+    }
+    if valid_until <= now {
+        return Err(ServiceError::BadRequest(
+            "Fim da vigência deve estar no futuro".to_string(),
+        ));
+    }
+    if valid_until - valid_from > Duration::days(MAX_DELEGATION_DAYS) {
+        return Err(ServiceError::BadRequest(format!(
+            "Vigência máxima de uma delegação é de {} dias",
+            MAX_DELEGATION_DAYS
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_period() {
+        let now = Utc::now();
+
+        assert!(validate_period(now, now + Duration::days(30), now).is_ok());
+        assert!(validate_period(now + Duration::days(7), now + Duration::days(14), now).is_ok());
+
+        // Fim antes do início
+        assert!(validate_period(now, now - Duration::hours(1), now).is_err());
+        // Já expirada
+        assert!(validate_period(now - Duration::days(10), now - Duration::days(1), now).is_err());
+        // Longa demais
+        assert!(validate_period(now, now + Duration::days(MAX_DELEGATION_DAYS + 1), now).is_err());
+    }
+}
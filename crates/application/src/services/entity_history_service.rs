@@ -0,0 +1,128 @@
+use crate::errors::ServiceError;
+use domain::models::{AuditOperation, EntityChangeDto, HistoryEntity, NewEntityChange};
+use domain::ports::EntityHistoryRepositoryPort;
+use serde_json::{Map, Value};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Field-level change history of the core aggregates.
+///
+/// Table writes are captured by database triggers; Casbin rules are stored
+/// by the enforcer adapter outside the request transaction, so their changes
+/// are recorded here explicitly.
+pub struct EntityHistoryService {
+    repo: Arc<dyn EntityHistoryRepositoryPort>,
+}
+
+impl EntityHistoryService {
+    pub fn new(repo: Arc<dyn EntityHistoryRepositoryPort>) -> Self {
+        Self { repo }
+    }
+
+    /// Changes of one entity, newest first
+    pub async fn list(
+        &self,
+        entity: HistoryEntity,
+        entity_id: &str,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<EntityChangeDto>, i64), ServiceError> {
+        let entity_id = entity_id.trim();
+        if entity_id.is_empty() {
+            return Err(ServiceError::BadRequest(
+                "Identificador da entidade é obrigatório".to_string(),
+            ));
+        }
+        // Normaliza a forma do UUID gravada pelo trigger (minúsculas, com hífens)
+        let entity_id = if entity.has_uuid_key() {
+            Uuid::parse_str(entity_id)
+                .map_err(|_| {
+                    ServiceError::BadRequest(format!(
+                        "Identificador inválido para {}: {}",
+                        entity.as_str(),
+                        entity_id
+                    ))
+                })?
+                .to_string()
+        } else {
+            entity_id.to_string()
+        };
+
+        let limit = limit.clamp(1, 100);
+        let offset = offset.max(0);
+
+        Ok(self.repo.list(entity, &entity_id, limit, offset).await?)
+    }
+
+    /// Entity changes made by the request of an audit log entry
+    pub async fn list_by_request(
+        &self,
+        request_id: Uuid,
+    ) -> Result<Vec<EntityChangeDto>, ServiceError> {
+        Ok(self.repo.list_by_request(request_id).await?)
+    }
+
+    /// Records a Casbin rule added or removed. Failures are logged and do not
+    /// undo the policy change, which is already in force.
+    pub async fn record_policy_change(&self, ptype: &str, rule: &[String], added: bool) {
+        let Some(subject) = rule.first() else {
+            return;
+        };
+
+        let snapshot = policy_snapshot(ptype, rule);
+        let change = NewEntityChange {
+            entity: HistoryEntity::Policy,
+            entity_id: subject.clone(),
+            operation: if added {
+                AuditOperation::Insert
+            } else {
+                AuditOperation::Delete
+            },
+            data_before: (!added).then(|| snapshot.clone()),
+            data_after: added.then_some(snapshot),
+        };
+
+        if let Err(e) = self.repo.record(&change).await {
+            tracing::error!(
+                error = ?e,
+                ptype = %ptype,
+                subject = %subject,
+                "Failed to record policy change history"
+            );
+        }
+    }
+}
+
+/// Rule as stored in `casbin_rule`: `{ptype, v0, v1, ...}`
+fn policy_snapshot(ptype: &str, rule: &[String]) -> Value {
+    let mut fields = Map::new();
+    fields.insert("ptype".to_string(), Value::String(ptype.to_string()));
+    for (i, value) in rule.iter().enumerate() {
+        fields.insert(format!("v{}", i), Value::String(value.clone()));
+    }
+    Value::Object(fields)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_policy_snapshot_matches_casbin_rule_columns() {
+        let rule = vec![
+            "admin".to_string(),
+            "/api/admin/history/{entity}/{id}".to_string(),
+            "GET".to_string(),
+        ];
+
+        assert_eq!(
+            policy_snapshot("p", &rule),
+            serde_json::json!({
+                "ptype": "p",
+                "v0": "admin",
+                "v1": "/api/admin/history/{entity}/{id}",
+                "v2": "GET",
+            })
+        );
+    }
+}
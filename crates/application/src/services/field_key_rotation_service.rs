@@ -0,0 +1,428 @@
+use std::sync::Arc;
+
+use domain::errors::RepositoryError;
+use domain::models::{FieldKeyRotationDto, FieldKeyRotationStatus};
+use domain::ports::FieldKeyRotationRepositoryPort;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::errors::ServiceError;
+
+/// Rows re-encrypted per transaction (and per progress update)
+const BATCH_SIZE: i64 = 500;
+
+/// Moves field-level encrypted data to the current key of the keyring.
+///
+/// Progress is saved after every batch, so a run stopped by a failure or a
+/// restart resumes from the last processed row instead of starting over.
+pub struct FieldKeyRotationService {
+    rotation_repo: Arc<dyn FieldKeyRotationRepositoryPort>,
+    current_key_id: i32,
+}
+
+impl FieldKeyRotationService {
+    pub fn new(
+        rotation_repo: Arc<dyn FieldKeyRotationRepositoryPort>,
+        current_key_id: u32,
+    ) -> Self {
+        Self {
+            rotation_repo,
+            current_key_id: current_key_id as i32,
+        }
+    }
+
+    /// Registra uma rotação para a chave atual; a execução é feita por `execute`
+    pub async fn start(
+        &self,
+        triggered_by: Option<Uuid>,
+    ) -> Result<FieldKeyRotationDto, ServiceError> {
+        let total_rows = self.rotation_repo.count_rows().await?;
+        self.rotation_repo
+            .create_run(self.current_key_id, total_rows, triggered_by)
+            .await
+            .map_err(|e| match e {
+                RepositoryError::Duplicate(_) => ServiceError::Conflict(
+                    "Já existe uma rotação de chave em andamento".to_string(),
+                ),
+                other => other.into(),
+            })
+    }
+
+    /// Retoma uma rotação que falhou, a partir do último registro processado
+    pub async fn resume(&self, id: Uuid) -> Result<FieldKeyRotationDto, ServiceError> {
+        let rotation = self.get_run(id).await?;
+        if rotation.status != FieldKeyRotationStatus::Failed {
+            return Err(ServiceError::BadRequest(
+                "Somente rotações com falha podem ser retomadas".to_string(),
+            ));
+        }
+        self.ensure_current_target(&rotation)?;
+
+        self.rotation_repo
+            .reopen_run(id)
+            .await
+            .map_err(|e| match e {
+                RepositoryError::Duplicate(_) => ServiceError::Conflict(
+                    "Já existe uma rotação de chave em andamento".to_string(),
+                ),
+                other => other.into(),
+            })
+    }
+
+    /// Rotação deixada em andamento por um processo anterior, para ser
+    /// retomada. Se a chave atual mudou desde então, ela é encerrada como
+    /// falha: uma nova rotação deve ser iniciada.
+    pub async fn take_interrupted_run(&self) -> Result<Option<FieldKeyRotationDto>, ServiceError> {
+        let Some(rotation) = self.rotation_repo.find_running().await? else {
+            return Ok(None);
+        };
+        if let Err(e) = self.ensure_current_target(&rotation) {
+            warn!(rotation_id = %rotation.id, "Rotação de chave interrompida descartada: {}", e);
+            self.rotation_repo
+                .finish_run(
+                    rotation.id,
+                    FieldKeyRotationStatus::Failed,
+                    Some(&e.to_string()),
+                )
+                .await?;
+            return Ok(None);
+        }
+        Ok(Some(rotation))
+    }
+
+    /// Processa os lotes a partir do ponto de retomada até o fim da última
+    /// etapa. Falhas são registradas na própria rotação.
+    pub async fn execute(
+        &self,
+        rotation: FieldKeyRotationDto,
+    ) -> Result<FieldKeyRotationDto, ServiceError> {
+        info!(
+            rotation_id = %rotation.id,
+            target_key_id = rotation.target_key_id,
+            stage = ?rotation.stage,
+            resume_after = ?rotation.last_processed_id,
+            "Iniciando recriptografia dos campos cifrados"
+        );
+
+        match self.process(rotation.clone()).await {
+            Ok(done) => {
+                if done.unreadable_rows > 0 {
+                    warn!(
+                        rotation_id = %done.id,
+                        unreadable = done.unreadable_rows,
+                        "Registros que nenhuma chave do keyring abre foram mantidos"
+                    );
+                }
+                info!(
+                    rotation_id = %done.id,
+                    processed = done.processed_rows,
+                    reencrypted = done.reencrypted_rows,
+                    "Recriptografia concluída"
+                );
+                Ok(self
+                    .rotation_repo
+                    .finish_run(done.id, FieldKeyRotationStatus::Completed, None)
+                    .await?)
+            }
+            Err(e) => {
+                error!(rotation_id = %rotation.id, "Recriptografia falhou: {}", e);
+                Ok(self
+                    .rotation_repo
+                    .finish_run(
+                        rotation.id,
+                        FieldKeyRotationStatus::Failed,
+                        Some(&e.to_string()),
+                    )
+                    .await?)
+            }
+        }
+    }
+
+    async fn process(
+        &self,
+        mut rotation: FieldKeyRotationDto,
+    ) -> Result<FieldKeyRotationDto, ServiceError> {
+        self.ensure_current_target(&rotation)?;
+
+        let mut stage = rotation.stage;
+        let mut cursor = rotation.last_processed_id;
+        loop {
+            let batch = self
+                .rotation_repo
+                .reencrypt_batch(stage, cursor, BATCH_SIZE)
+                .await?;
+
+            if batch.processed > 0 {
+                cursor = batch.last_id;
+                rotation = self
+                    .rotation_repo
+                    .save_progress(rotation.id, stage, cursor, &batch)
+                    .await?;
+            }
+            if batch.processed < BATCH_SIZE {
+                let Some(next) = stage.next() else {
+                    return Ok(rotation);
+                };
+                stage = next;
+                cursor = None;
+                rotation = self
+                    .rotation_repo
+                    .save_progress(rotation.id, stage, None, &Default::default())
+                    .await?;
+            }
+        }
+    }
+
+    fn ensure_current_target(&self, rotation: &FieldKeyRotationDto) -> Result<(), ServiceError> {
+        if rotation.target_key_id != self.current_key_id {
+            return Err(ServiceError::BadRequest(format!(
+                "A rotação recriptografa para a chave {}, mas a chave atual é {}",
+                rotation.target_key_id, self.current_key_id
+            )));
+        }
+        Ok(())
+    }
+
+    pub async fn get_run(&self, id: Uuid) -> Result<FieldKeyRotationDto, ServiceError> {
+        self.rotation_repo
+            .find_run(id)
+            .await?
+            .ok_or(ServiceError::NotFound(
+                "Rotação de chave não encontrada".to_string(),
+            ))
+    }
+
+    pub async fn list_runs(
+        &self,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<FieldKeyRotationDto>, i64), ServiceError> {
+        self.rotation_repo
+            .list_runs(limit, offset)
+            .await
+            .map_err(ServiceError::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use chrono::Utc;
+    use domain::models::{FieldKeyRotationBatch, FieldKeyRotationStage};
+    use std::sync::Mutex;
+
+    /// Ids of the rows of each stage, in order, and the run being updated
+    struct FakeRepo {
+        users: Vec<Uuid>,
+        tokens: Vec<Uuid>,
+        signing_keys: Vec<Uuid>,
+        run: Mutex<FieldKeyRotationDto>,
+        reads: Mutex<Vec<(FieldKeyRotationStage, Option<Uuid>)>>,
+    }
+
+    fn run(stage: FieldKeyRotationStage, last_processed_id: Option<Uuid>) -> FieldKeyRotationDto {
+        FieldKeyRotationDto {
+            id: Uuid::new_v4(),
+            target_key_id: 2,
+            status: FieldKeyRotationStatus::Running,
+            stage,
+            last_processed_id,
+            total_rows: 0,
+            processed_rows: 0,
+            reencrypted_rows: 0,
+            unreadable_rows: 0,
+            error: None,
+            triggered_by: None,
+            started_at: Utc::now(),
+            updated_at: Utc::now(),
+            finished_at: None,
+        }
+    }
+
+    fn sorted_ids(n: usize) -> Vec<Uuid> {
+        let mut ids: Vec<Uuid> = (0..n).map(|_| Uuid::new_v4()).collect();
+        ids.sort();
+        ids
+    }
+
+    #[async_trait]
+    impl FieldKeyRotationRepositoryPort for FakeRepo {
+        async fn count_rows(&self) -> Result<i64, RepositoryError> {
+            Ok((self.users.len() + self.tokens.len() + self.signing_keys.len()) as i64)
+        }
+
+        async fn reencrypt_batch(
+            &self,
+            stage: FieldKeyRotationStage,
+            after: Option<Uuid>,
+            limit: i64,
+        ) -> Result<FieldKeyRotationBatch, RepositoryError> {
+            self.reads.lock().unwrap().push((stage, after));
+            let rows = match stage {
+                FieldKeyRotationStage::Users => &self.users,
+                FieldKeyRotationStage::MfaSetupTokens => &self.tokens,
+                FieldKeyRotationStage::JwtSigningKeys => &self.signing_keys,
+            };
+            let batch: Vec<&Uuid> = rows
+                .iter()
+                .filter(|id| after.is_none_or(|a| **id > a))
+                .take(limit as usize)
+                .collect();
+            Ok(FieldKeyRotationBatch {
+                last_id: batch.last().copied().copied(),
+                processed: batch.len() as i64,
+                reencrypted: batch.len() as i64,
+                unreadable: 0,
+            })
+        }
+
+        async fn create_run(
+            &self,
+            _target_key_id: i32,
+            _total_rows: i64,
+            _triggered_by: Option<Uuid>,
+        ) -> Result<FieldKeyRotationDto, RepositoryError> {
+            Err(RepositoryError::Duplicate("running".to_string()))
+        }
+
+        async fn save_progress(
+            &self,
+            _id: Uuid,
+            stage: FieldKeyRotationStage,
+            last_processed_id: Option<Uuid>,
+            batch: &FieldKeyRotationBatch,
+        ) -> Result<FieldKeyRotationDto, RepositoryError> {
+            let mut run = self.run.lock().unwrap();
+            run.stage = stage;
+            run.last_processed_id = last_processed_id;
+            run.processed_rows += batch.processed;
+            run.reencrypted_rows += batch.reencrypted;
+            Ok(run.clone())
+        }
+
+        async fn finish_run(
+            &self,
+            _id: Uuid,
+            status: FieldKeyRotationStatus,
+            error: Option<&str>,
+        ) -> Result<FieldKeyRotationDto, RepositoryError> {
+            let mut run = self.run.lock().unwrap();
+            run.status = status;
+            run.error = error.map(str::to_string);
+            Ok(run.clone())
+        }
+
+        async fn reopen_run(&self, _id: Uuid) -> Result<FieldKeyRotationDto, RepositoryError> {
+            Ok(self.run.lock().unwrap().clone())
+        }
+
+        async fn find_running(&self) -> Result<Option<FieldKeyRotationDto>, RepositoryError> {
+            Ok(Some(self.run.lock().unwrap().clone()))
+        }
+
+        async fn find_run(
+            &self,
+            _id: Uuid,
+        ) -> Result<Option<FieldKeyRotationDto>, RepositoryError> {
+            Ok(Some(self.run.lock().unwrap().clone()))
+        }
+
+        async fn list_runs(
+            &self,
+            _limit: i64,
+            _offset: i64,
+        ) -> Result<(Vec<FieldKeyRotationDto>, i64), RepositoryError> {
+            Ok((vec![], 0))
+        }
+    }
+
+    fn service(repo: &Arc<FakeRepo>, current_key_id: u32) -> FieldKeyRotationService {
+        FieldKeyRotationService::new(repo.clone(), current_key_id)
+    }
+
+    #[tokio::test]
+    async fn test_execute_walks_every_stage_in_batches() {
+        let users = sorted_ids(BATCH_SIZE as usize + 3);
+        let tokens = sorted_ids(2);
+        let signing_keys = sorted_ids(1);
+        let start = run(FieldKeyRotationStage::Users, None);
+        let repo = Arc::new(FakeRepo {
+            users: users.clone(),
+            tokens: tokens.clone(),
+            signing_keys: signing_keys.clone(),
+            run: Mutex::new(start.clone()),
+            reads: Mutex::new(vec![]),
+        });
+
+        let done = service(&repo, 2).execute(start).await.unwrap();
+
+        assert_eq!(done.status, FieldKeyRotationStatus::Completed);
+        assert_eq!(
+            done.processed_rows,
+            (users.len() + tokens.len() + signing_keys.len()) as i64
+        );
+        assert_eq!(done.stage, FieldKeyRotationStage::JwtSigningKeys);
+        assert_eq!(done.last_processed_id, signing_keys.last().copied());
+        assert_eq!(
+            *repo.reads.lock().unwrap(),
+            vec![
+                (FieldKeyRotationStage::Users, None),
+                (
+                    FieldKeyRotationStage::Users,
+                    Some(users[BATCH_SIZE as usize - 1])
+                ),
+                (FieldKeyRotationStage::MfaSetupTokens, None),
+                (FieldKeyRotationStage::JwtSigningKeys, None),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_execute_resumes_from_saved_cursor() {
+        let users = sorted_ids(5);
+        let start = run(FieldKeyRotationStage::Users, Some(users[2]));
+        let repo = Arc::new(FakeRepo {
+            users: users.clone(),
+            tokens: vec![],
+            signing_keys: vec![],
+            run: Mutex::new(start.clone()),
+            reads: Mutex::new(vec![]),
+        });
+
+        let done = service(&repo, 2).execute(start).await.unwrap();
+
+        assert_eq!(done.status, FieldKeyRotationStatus::Completed);
+        assert_eq!(done.processed_rows, 2);
+        assert_eq!(
+            repo.reads.lock().unwrap()[0],
+            (FieldKeyRotationStage::Users, Some(users[2]))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_interrupted_run_for_previous_key_is_failed() {
+        let repo = Arc::new(FakeRepo {
+            users: vec![],
+            tokens: vec![],
+            signing_keys: vec![],
+            run: Mutex::new(run(FieldKeyRotationStage::Users, None)),
+            reads: Mutex::new(vec![]),
+        });
+
+        assert!(service(&repo, 2)
+            .take_interrupted_run()
+            .await
+            .unwrap()
+            .is_some());
+        assert!(service(&repo, 3)
+            .take_interrupted_run()
+            .await
+            .unwrap()
+            .is_none());
+        assert_eq!(
+            repo.run.lock().unwrap().status,
+            FieldKeyRotationStatus::Failed
+        );
+    }
+}
@@ -0,0 +1,701 @@
+use std::str::FromStr;
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use domain::{
+    models::fuel_card::*,
+    models::fueling::{FuelingAnomaly, FuelingAnomalyKind, FuelingDto},
+    models::legacy_import::{ImportEntityType, ImportJobStatus},
+    ports::fuel_card::FuelCardRepositoryPort,
+    ports::legacy_import::LegacyImportRepositoryPort,
+};
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+use crate::errors::ServiceError;
+use crate::services::supplier_service::normalize_document;
+use crate::services::vehicle_service::normalize_license_plate;
+
+/// Janela (± horas) para procurar o abastecimento correspondente à transação.
+const MATCH_WINDOW_HOURS: i64 = 12;
+
+/// Diferença de horário tolerada antes de reportar divergência.
+const TIME_TOLERANCE_HOURS: i64 = 2;
+
+/// Tolerâncias de conciliação: litros (absoluta, em L) e valor (em R$).
+const LITERS_TOLERANCE: Decimal = Decimal::from_parts(1, 0, 0, false, 1); // 0.1
+const AMOUNT_TOLERANCE: Decimal = Decimal::from_parts(10, 0, 0, false, 2); // 0.10
+
+/// Horário de Brasília — arquivos das operadoras trazem horário local sem fuso.
+const BRT_OFFSET_SECONDS: i32 = 3 * 3600;
+
+pub struct FuelCardService {
+    fuel_card_repo: Arc<dyn FuelCardRepositoryPort>,
+    import_repo: Arc<dyn LegacyImportRepositoryPort>,
+}
+
+impl FuelCardService {
+    pub fn new(
+        fuel_card_repo: Arc<dyn FuelCardRepositoryPort>,
+        import_repo: Arc<dyn LegacyImportRepositoryPort>,
+    ) -> Self {
+        Self { fuel_card_repo, import_repo }
+    }
+
+    /// Importa o arquivo CSV da operadora e concilia cada transação.
+    ///
+    /// O job é registrado em `legacy_import_jobs`: linhas ilegíveis, duplicadas
+    /// ou sem veículo/condutor identificados contam como falha e vão para o
+    /// `error_log`; divergências ficam nas transações DIVERGENTE do relatório.
+    pub async fn import_csv(
+        &self,
+        operator: &str,
+        content: &str,
+        submitted_by: Option<Uuid>,
+    ) -> Result<FuelCardImportReportDto, ServiceError> {
+        let operator = operator.trim().to_uppercase();
+        if operator.is_empty() {
+            return Err(ServiceError::BadRequest("Operadora obrigatória".into()));
+        }
+
+        let (records, parse_errors) = parse_fuel_card_csv(content)?;
+        if records.is_empty() && parse_errors.is_empty() {
+            return Err(ServiceError::BadRequest("No records to import".into()));
+        }
+
+        let total = (records.len() + parse_errors.len()) as i32;
+        let job = self
+            .import_repo
+            .create_job(ImportEntityType::FuelCardTransaction, submitted_by, total)
+            .await
+            .map_err(ServiceError::from)?;
+
+        self.import_repo.start_job(job.id).await.map_err(ServiceError::from)?;
+
+        let mut success = 0i32;
+        let mut failed = parse_errors.len() as i32;
+        let mut errors: Vec<serde_json::Value> = parse_errors;
+
+        for record in records {
+            let line = record.line_number;
+            match self.reconcile_record(job.id, &operator, record, submitted_by).await {
+                Ok(tx) if tx.match_status == FuelCardMatchStatus::NaoIdentificado => {
+                    failed += 1;
+                    errors.push(serde_json::json!({
+                        "line": line,
+                        "error": tx.mismatches
+                    }));
+                }
+                Ok(_) => success += 1,
+                Err(e) => {
+                    failed += 1;
+                    errors.push(serde_json::json!({
+                        "line": line,
+                        "error": e.to_string()
+                    }));
+                }
+            }
+        }
+
+        let error_log = if errors.is_empty() {
+            None
+        } else {
+            Some(serde_json::Value::Array(errors))
+        };
+
+        self.import_repo
+            .update_progress(job.id, total, success, failed, error_log)
+            .await
+            .map_err(ServiceError::from)?;
+
+        let final_status = if failed == 0 {
+            ImportJobStatus::Completed
+        } else if success == 0 {
+            ImportJobStatus::Failed
+        } else {
+            ImportJobStatus::Partial
+        };
+
+        self.import_repo
+            .complete_job(job.id, final_status)
+            .await
+            .map_err(ServiceError::from)?;
+
+        self.get_report(job.id).await
+    }
+
+    async fn reconcile_record(
+        &self,
+        job_id: Uuid,
+        operator: &str,
+        record: FuelCardRecord,
+        submitted_by: Option<Uuid>,
+    ) -> Result<FuelCardTransactionDto, ServiceError> {
+        if let Some(external_id) = record.external_id.as_deref() {
+            if self
+                .fuel_card_repo
+                .exists_by_external_id(operator, external_id)
+                .await
+                .map_err(ServiceError::from)?
+            {
+                return Err(ServiceError::Conflict(format!(
+                    "Transação {} já importada",
+                    external_id
+                )));
+            }
+        }
+
+        let vehicle = self
+            .fuel_card_repo
+            .find_vehicle_by_plate(&normalize_license_plate(&record.license_plate))
+            .await
+            .map_err(ServiceError::from)?;
+
+        let driver_id = match record.driver_cpf.as_deref() {
+            Some(cpf) => self
+                .fuel_card_repo
+                .find_driver_by_cpf(&normalize_document(cpf))
+                .await
+                .map_err(ServiceError::from)?,
+            None => None,
+        };
+
+        let mut new_tx = NewFuelCardTransaction {
+            record,
+            vehicle_id: vehicle.as_ref().map(|v| v.id),
+            driver_id,
+            fueling_id: None,
+            match_status: FuelCardMatchStatus::NaoIdentificado,
+            mismatches: Vec::new(),
+            pending_fueling: None,
+        };
+
+        if new_tx.record.driver_cpf.is_some() && driver_id.is_none() {
+            new_tx.mismatches.push(format!(
+                "CPF {} não cadastrado",
+                new_tx.record.driver_cpf.as_deref().unwrap_or_default()
+            ));
+        }
+
+        let Some(vehicle) = vehicle else {
+            new_tx
+                .mismatches
+                .push(format!("Placa {} não cadastrada", new_tx.record.license_plate));
+            return self
+                .fuel_card_repo
+                .create_transaction(job_id, operator, &new_tx)
+                .await
+                .map_err(ServiceError::from);
+        };
+
+        let candidates = self
+            .fuel_card_repo
+            .find_unlinked_fuelings(
+                vehicle.id,
+                new_tx.record.transaction_at - Duration::hours(MATCH_WINDOW_HOURS),
+                new_tx.record.transaction_at + Duration::hours(MATCH_WINDOW_HOURS),
+            )
+            .await
+            .map_err(ServiceError::from)?;
+
+        let (fueling_id, status, mismatches) = reconcile(&new_tx.record, driver_id, &candidates);
+        new_tx.fueling_id = fueling_id;
+        new_tx.match_status = status;
+        new_tx.mismatches.extend(mismatches);
+
+        if new_tx.match_status == FuelCardMatchStatus::Pendente {
+            match driver_id {
+                Some(driver_id) => {
+                    new_tx.pending_fueling = Some(
+                        self.pending_fueling(operator, &new_tx.record, &vehicle, driver_id, submitted_by)
+                            .await?,
+                    );
+                }
+                None => {
+                    new_tx.match_status = FuelCardMatchStatus::NaoIdentificado;
+                    new_tx
+                        .mismatches
+                        .push("Condutor não identificado — abastecimento não gerado".to_string());
+                }
+            }
+        }
+
+        self.fuel_card_repo
+            .create_transaction(job_id, operator, &new_tx)
+            .await
+            .map_err(ServiceError::from)
+    }
+
+    /// Gera o abastecimento de uma transação sem correspondente, em QUARENTENA
+    /// para conferência do Gestor de Frota.
+    async fn pending_fueling(
+        &self,
+        operator: &str,
+        record: &FuelCardRecord,
+        vehicle: &FuelCardVehicleRef,
+        driver_id: Uuid,
+        submitted_by: Option<Uuid>,
+    ) -> Result<PendingCardFueling, ServiceError> {
+        let fuel_type_id = match record.fuel_name.as_deref() {
+            Some(name) => self
+                .fuel_card_repo
+                .find_fuel_type_by_name(name)
+                .await
+                .map_err(ServiceError::from)?
+                .unwrap_or(vehicle.fuel_type_id),
+            None => vehicle.fuel_type_id,
+        };
+        let odometer_km = record
+            .odometer_km
+            .or(vehicle.last_odometer_km)
+            .unwrap_or(0);
+
+        let anomalies = serde_json::to_value(vec![FuelingAnomaly {
+            kind: FuelingAnomalyKind::CardTransactionPending,
+            detail: format!(
+                "Transação do cartão {} sem abastecimento registrado",
+                operator
+            ),
+        }])
+        .map_err(|e| ServiceError::Internal(e.to_string()))?;
+
+        let notes = format!(
+            "Cartão combustível {} — transação {}{}",
+            operator,
+            record.external_id.as_deref().unwrap_or("sem identificador"),
+            record
+                .merchant
+                .as_deref()
+                .map(|m| format!(" — {}", m))
+                .unwrap_or_default()
+        );
+
+        Ok(PendingCardFueling {
+            vehicle_id: vehicle.id,
+            driver_id,
+            fuel_type_id,
+            odometer_km,
+            notes,
+            anomalies,
+            created_by: submitted_by,
+        })
+    }
+
+    pub async fn get_report(&self, job_id: Uuid) -> Result<FuelCardImportReportDto, ServiceError> {
+        let job = self
+            .import_repo
+            .find_by_id(job_id)
+            .await
+            .map_err(ServiceError::from)?
+            .filter(|j| j.entity_type == ImportEntityType::FuelCardTransaction)
+            .ok_or_else(|| ServiceError::NotFound(format!("Import job {} not found", job_id)))?;
+
+        let counts = self
+            .fuel_card_repo
+            .count_by_status(job_id)
+            .await
+            .map_err(ServiceError::from)?;
+        let count_of = |status: FuelCardMatchStatus| {
+            counts
+                .iter()
+                .find(|(s, _)| *s == status)
+                .map_or(0, |(_, n)| *n)
+        };
+
+        let mismatches = self
+            .fuel_card_repo
+            .list_by_job(job_id, None)
+            .await
+            .map_err(ServiceError::from)?
+            .into_iter()
+            .filter(|t| {
+                matches!(
+                    t.match_status,
+                    FuelCardMatchStatus::Divergente | FuelCardMatchStatus::NaoIdentificado
+                )
+            })
+            .collect();
+
+        Ok(FuelCardImportReportDto {
+            job,
+            matched: count_of(FuelCardMatchStatus::Conciliado),
+            divergent: count_of(FuelCardMatchStatus::Divergente),
+            pending: count_of(FuelCardMatchStatus::Pendente),
+            unidentified: count_of(FuelCardMatchStatus::NaoIdentificado),
+            mismatches,
+        })
+    }
+
+    pub async fn list_transactions(
+        &self,
+        job_id: Uuid,
+        filters: FuelCardTransactionFilters,
+    ) -> Result<Vec<FuelCardTransactionDto>, ServiceError> {
+        self.get_report(job_id).await?;
+        self.fuel_card_repo
+            .list_by_job(job_id, filters.match_status)
+            .await
+            .map_err(ServiceError::from)
+    }
+}
+
+// ============================
+// Conciliação
+// ============================
+
+/// Escolhe o abastecimento mais próximo da transação (litros, valor e
+/// horário) e apura as divergências. Sem candidatos, a transação fica
+/// PENDENTE.
+fn reconcile(
+    record: &FuelCardRecord,
+    driver_id: Option<Uuid>,
+    candidates: &[FuelingDto],
+) -> (Option<Uuid>, FuelCardMatchStatus, Vec<String>) {
+    let distance = |f: &FuelingDto| {
+        let liters = (f.quantity_liters - record.liters).abs();
+        let amount = (f.total_cost - record.total_amount).abs();
+        let hours = Decimal::from((f.fueling_date - record.transaction_at).num_minutes().abs()) / Decimal::from(60);
+        liters + amount + hours
+    };
+
+    let Some(best) = candidates.iter().min_by_key(|f| distance(f)) else {
+        return (None, FuelCardMatchStatus::Pendente, Vec::new());
+    };
+
+    let mut mismatches = Vec::new();
+    if (best.quantity_liters - record.liters).abs() > LITERS_TOLERANCE {
+        mismatches.push(format!(
+            "Litros: registrado {} L, operadora {} L",
+            best.quantity_liters.normalize(),
+            record.liters.normalize()
+        ));
+    }
+    if (best.total_cost - record.total_amount).abs() > AMOUNT_TOLERANCE {
+        mismatches.push(format!(
+            "Valor: registrado R$ {}, operadora R$ {}",
+            best.total_cost.round_dp(2),
+            record.total_amount.round_dp(2)
+        ));
+    }
+    let hours = (best.fueling_date - record.transaction_at).num_hours().abs();
+    if hours > TIME_TOLERANCE_HOURS {
+        mismatches.push(format!("Horário difere em {} h", hours));
+    }
+    if driver_id.is_some_and(|d| d != best.driver_id) {
+        mismatches.push("Condutor do cartão difere do registrado".to_string());
+    }
+
+    let status = if mismatches.is_empty() {
+        FuelCardMatchStatus::Conciliado
+    } else {
+        FuelCardMatchStatus::Divergente
+    };
+    (Some(best.id), status, mismatches)
+}
+
+// ============================
+// Leitura do arquivo
+// ============================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Column {
+    Date,
+    Plate,
+    Cpf,
+    Liters,
+    UnitPrice,
+    Amount,
+    Odometer,
+    Fuel,
+    Merchant,
+    ExternalId,
+}
+
+/// Cabeçalhos aceitos (após normalização) para cada coluna do layout.
+fn column_for_header(header: &str) -> Option<Column> {
+    let normalized: String = header
+        .trim()
+        .to_lowercase()
+        .chars()
+        .map(|c| match c {
+            'á' | 'à' | 'ã' | 'â' => 'a',
+            'é' | 'ê' => 'e',
+            'í' => 'i',
+            'ó' | 'ô' | 'õ' => 'o',
+            'ú' => 'u',
+            'ç' => 'c',
+            ' ' | '-' | '.' => '_',
+            other => other,
+        })
+        .collect();
+
+    match normalized.as_str() {
+        "data" | "data_hora" | "data_transacao" | "date" | "transaction_at" => Some(Column::Date),
+        "placa" | "license_plate" | "plate" => Some(Column::Plate),
+        "cpf" | "cpf_condutor" | "cpf_motorista" | "driver_cpf" => Some(Column::Cpf),
+        "litros" | "quantidade" | "qtd_litros" | "liters" => Some(Column::Liters),
+        "preco_unitario" | "valor_unitario" | "valor_litro" | "unit_price" => Some(Column::UnitPrice),
+        "valor" | "valor_total" | "total" | "amount" | "total_amount" => Some(Column::Amount),
+        "hodometro" | "km" | "odometro" | "odometer" | "odometer_km" => Some(Column::Odometer),
+        "combustivel" | "produto" | "servico" | "fuel" => Some(Column::Fuel),
+        "estabelecimento" | "posto" | "merchant" => Some(Column::Merchant),
+        "id_transacao" | "transacao" | "nsu" | "autorizacao" | "transaction_id" => Some(Column::ExternalId),
+        _ => None,
+    }
+}
+
+/// Interpreta o CSV da operadora. Aceita `;` ou `,` como separador e números
+/// no formato brasileiro (`1.234,56`). Retorna os registros válidos e, para
+/// cada linha rejeitada, uma entrada do `error_log`.
+fn parse_fuel_card_csv(
+    content: &str,
+) -> Result<(Vec<FuelCardRecord>, Vec<serde_json::Value>), ServiceError> {
+    let content = content.trim_start_matches('\u{feff}');
+    let header_line = content.lines().next().unwrap_or_default();
+    let delimiter = if header_line.matches(';').count() >= header_line.matches(',').count() {
+        b';'
+    } else {
+        b','
+    };
+
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(content.as_bytes());
+
+    let headers = reader
+        .headers()
+        .map_err(|e| ServiceError::BadRequest(format!("Cabeçalho inválido: {}", e)))?
+        .clone();
+    let columns: Vec<Option<Column>> = headers.iter().map(column_for_header).collect();
+
+    for required in [Column::Date, Column::Plate, Column::Liters, Column::Amount] {
+        if !columns.contains(&Some(required)) {
+            return Err(ServiceError::BadRequest(format!(
+                "Coluna obrigatória ausente: {:?}",
+                required
+            )));
+        }
+    }
+
+    let mut records = Vec::new();
+    let mut errors = Vec::new();
+
+    for (i, row) in reader.records().enumerate() {
+        // Linha 1 é o cabeçalho.
+        let line_number = i as i32 + 2;
+        let parsed = row
+            .map_err(|e| e.to_string())
+            .and_then(|row| {
+                let field = |col: Column| {
+                    columns
+                        .iter()
+                        .position(|c| *c == Some(col))
+                        .and_then(|idx| row.get(idx))
+                        .filter(|v| !v.is_empty())
+                };
+                parse_record(line_number, field)
+            });
+
+        match parsed {
+            Ok(record) => records.push(record),
+            Err(error) => errors.push(serde_json::json!({
+                "line": line_number,
+                "error": error
+            })),
+        }
+    }
+
+    Ok((records, errors))
+}
+
+fn parse_record<'a>(
+    line_number: i32,
+    field: impl Fn(Column) -> Option<&'a str>,
+) -> Result<FuelCardRecord, String> {
+    let transaction_at = field(Column::Date)
+        .ok_or("Data ausente")
+        .and_then(|v| parse_datetime(v).ok_or("Data inválida"))?;
+    let license_plate = field(Column::Plate).ok_or("Placa ausente")?.to_uppercase();
+    let liters = field(Column::Liters)
+        .ok_or("Litros ausentes")
+        .and_then(|v| parse_decimal(v).ok_or("Litros inválidos"))?;
+    let total_amount = field(Column::Amount)
+        .ok_or("Valor ausente")
+        .and_then(|v| parse_decimal(v).ok_or("Valor inválido"))?;
+
+    if liters <= Decimal::ZERO || total_amount <= Decimal::ZERO {
+        return Err("Litros e valor devem ser positivos".to_string());
+    }
+
+    let unit_price = match field(Column::UnitPrice) {
+        Some(v) => parse_decimal(v).ok_or("Preço unitário inválido")?,
+        None => (total_amount / liters).round_dp(4),
+    };
+    let odometer_km = match field(Column::Odometer) {
+        Some(v) => Some(parse_km(v).ok_or("Hodômetro inválido")?),
+        None => None,
+    };
+
+    Ok(FuelCardRecord {
+        line_number,
+        external_id: field(Column::ExternalId).map(str::to_string),
+        transaction_at,
+        license_plate,
+        driver_cpf: field(Column::Cpf).map(normalize_document).filter(|c| !c.is_empty()),
+        liters,
+        unit_price,
+        total_amount,
+        odometer_km,
+        fuel_name: field(Column::Fuel).map(str::to_string),
+        merchant: field(Column::Merchant).map(str::to_string),
+    })
+}
+
+/// `1.234,56` / `1234,56` / `1234.56` / `R$ 10,00` → Decimal.
+fn parse_decimal(value: &str) -> Option<Decimal> {
+    let cleaned: String = value
+        .trim()
+        .trim_start_matches("R$")
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect();
+    let normalized = if cleaned.contains(',') {
+        cleaned.replace('.', "").replace(',', ".")
+    } else {
+        cleaned
+    };
+    Decimal::from_str(&normalized).ok()
+}
+
+/// Quilometragem inteira: `10.250`, `10250` ou `10250,7` → 10250.
+fn parse_km(value: &str) -> Option<i32> {
+    let integer_part = value.split(',').next()?;
+    let digits: String = integer_part.chars().filter(|c| c.is_ascii_digit()).collect();
+    digits.parse().ok()
+}
+
+/// Datas com fuso (RFC 3339) ou no horário de Brasília (`dd/mm/aaaa hh:mm[:ss]`,
+/// `aaaa-mm-dd hh:mm[:ss]` ou só a data).
+fn parse_datetime(value: &str) -> Option<DateTime<Utc>> {
+    if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
+        return Some(dt.with_timezone(&Utc));
+    }
+
+    let naive = ["%d/%m/%Y %H:%M:%S", "%d/%m/%Y %H:%M", "%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M"]
+        .iter()
+        .find_map(|fmt| NaiveDateTime::parse_from_str(value, fmt).ok())
+        .or_else(|| {
+            ["%d/%m/%Y", "%Y-%m-%d"]
+                .iter()
+                .find_map(|fmt| NaiveDate::parse_from_str(value, fmt).ok())
+                .and_then(|d| d.and_hms_opt(0, 0, 0))
+        })?;
+
+    FixedOffset::west_opt(BRT_OFFSET_SECONDS)?
+        .from_local_datetime(&naive)
+        .single()
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use domain::models::fueling::FuelingReviewStatus;
+
+    fn fueling(at: DateTime<Utc>, liters: i64, total: i64, driver_id: Uuid) -> FuelingDto {
+        FuelingDto {
+            id: Uuid::new_v4(),
+            vehicle_id: Uuid::new_v4(),
+            driver_id,
+            supplier_id: None,
+            fuel_type_id: Uuid::new_v4(),
+            fueling_date: at,
+            odometer_km: 10_000,
+            quantity_liters: Decimal::from(liters),
+            unit_price: Decimal::from(total) / Decimal::from(liters),
+            total_cost: Decimal::from(total),
+            notes: None,
+            review_status: FuelingReviewStatus::Validado,
+            anomalies: None,
+            reviewed_by: None,
+            reviewed_at: None,
+            review_notes: None,
+            created_at: at,
+            updated_at: at,
+            created_by: None,
+            updated_by: None,
+        }
+    }
+
+    fn record(at: DateTime<Utc>, liters: i64, total: i64) -> FuelCardRecord {
+        FuelCardRecord {
+            line_number: 2,
+            external_id: Some("123".to_string()),
+            transaction_at: at,
+            license_plate: "ABC1D23".to_string(),
+            driver_cpf: None,
+            liters: Decimal::from(liters),
+            unit_price: Decimal::from(total) / Decimal::from(liters),
+            total_amount: Decimal::from(total),
+            odometer_km: None,
+            fuel_name: None,
+            merchant: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_semicolon_csv_with_brazilian_numbers() {
+        let csv = "Data;Placa;CPF;Litros;Valor Total;Hodômetro;Combustível;NSU\n\
+                   05/03/2026 14:30;abc-1d23;123.456.789-09;40,500;R$ 1.234,56;10.250;GASOLINA;987\n\
+                   31/02/2026;ABC1D23;;10;50;;;\n";
+        let (records, errors) = parse_fuel_card_csv(csv).unwrap();
+
+        assert_eq!(records.len(), 1);
+        assert_eq!(errors.len(), 1);
+        let r = &records[0];
+        assert_eq!(r.license_plate, "ABC-1D23");
+        assert_eq!(r.driver_cpf.as_deref(), Some("12345678909"));
+        assert_eq!(r.liters, Decimal::from_str("40.5").unwrap());
+        assert_eq!(r.total_amount, Decimal::from_str("1234.56").unwrap());
+        assert_eq!(r.odometer_km, Some(10_250));
+        assert_eq!(r.external_id.as_deref(), Some("987"));
+        // 14:30 BRT = 17:30 UTC
+        assert_eq!(r.transaction_at.to_rfc3339(), "2026-03-05T17:30:00+00:00");
+        assert_eq!(errors[0]["line"], 3);
+    }
+
+    #[test]
+    fn test_parse_rejects_missing_required_column() {
+        let csv = "date,plate,liters\n2026-03-05,ABC1D23,40\n";
+        assert!(parse_fuel_card_csv(csv).is_err());
+    }
+
+    #[test]
+    fn test_reconcile_matches_closest_fueling() {
+        let now = Utc::now();
+        let driver = Uuid::new_v4();
+        let exact = fueling(now + Duration::minutes(30), 40, 240, driver);
+        let other = fueling(now - Duration::hours(8), 20, 120, driver);
+
+        let (id, status, mismatches) = reconcile(&record(now, 40, 240), Some(driver), &[other, exact.clone()]);
+        assert_eq!(id, Some(exact.id));
+        assert_eq!(status, FuelCardMatchStatus::Conciliado);
+        assert!(mismatches.is_empty());
+    }
+
+    #[test]
+    fn test_reconcile_reports_divergences_and_pending() {
+        let now = Utc::now();
+        let registered = fueling(now, 40, 240, Uuid::new_v4());
+
+        let (_, status, mismatches) = reconcile(&record(now, 45, 270), Some(Uuid::new_v4()), &[registered]);
+        assert_eq!(status, FuelCardMatchStatus::Divergente);
+        assert_eq!(mismatches.len(), 3);
+
+        let (id, status, _) = reconcile(&record(now, 40, 240), None, &[]);
+        assert_eq!(id, None);
+        assert_eq!(status, FuelCardMatchStatus::Pendente);
+    }
+}
@@ -1,19 +1,92 @@
 use crate::errors::ServiceError;
+use chrono::Duration;
 use domain::{
     models::fueling::*,
     ports::fueling::*,
+    ports::vehicle::VehicleRepositoryPort,
 };
+use rust_decimal::prelude::ToPrimitive;
 use rust_decimal::Decimal;
 use std::sync::Arc;
 use uuid::Uuid;
 
+/// Tolerância sobre a capacidade do tanque (bocal, gargalo, mangueira).
+const TANK_CAPACITY_TOLERANCE: f64 = 1.05;
+
+/// Quantidade de abastecimentos anteriores usada na média móvel de consumo.
+const CONSUMPTION_WINDOW: i64 = 10;
+
+/// Mínimo de intervalos válidos para a média móvel ser considerada.
+const MIN_CONSUMPTION_SAMPLES: usize = 3;
+
+/// Desvio relativo máximo do consumo (km/L) em relação à média móvel.
+const MAX_CONSUMPTION_DEVIATION: f64 = 0.35;
+
+/// Intervalo mínimo plausível entre dois abastecimentos do mesmo veículo.
+const MIN_HOURS_BETWEEN_FUELINGS: i64 = 4;
+const MIN_KM_BETWEEN_FUELINGS: i32 = 50;
+
+/// Janela e limite para o preço unitário em relação à mediana do combustível.
+const PRICE_MEDIAN_WINDOW_DAYS: i64 = 30;
+const MAX_PRICE_ABOVE_MEDIAN: f64 = 0.20;
+
 pub struct FuelingService {
     fueling_repo: Arc<dyn FuelingRepositoryPort>,
+    vehicle_repo: Arc<dyn VehicleRepositoryPort>,
 }
 
 impl FuelingService {
-    pub fn new(fueling_repo: Arc<dyn FuelingRepositoryPort>) -> Self {
-        Self { fueling_repo }
+    pub fn new(
+        fueling_repo: Arc<dyn FuelingRepositoryPort>,
+        vehicle_repo: Arc<dyn VehicleRepositoryPort>,
+    ) -> Self {
+        Self { fueling_repo, vehicle_repo }
+    }
+
+    /// Regras de plausibilidade: anomalias não bloqueiam o registro, enviam-no
+    /// para a fila de revisão do Gestor de Frota.
+    async fn classify(
+        &self,
+        payload: &CreateFuelingPayload,
+    ) -> Result<(FuelingReviewStatus, Option<serde_json::Value>), ServiceError> {
+        let vehicle = self.vehicle_repo
+            .find_by_id(payload.vehicle_id)
+            .await
+            .map_err(ServiceError::from)?
+            .ok_or(ServiceError::NotFound("Veículo não encontrado".to_string()))?;
+
+        let history = self.fueling_repo
+            .find_previous_by_vehicle(payload.vehicle_id, payload.fueling_date, CONSUMPTION_WINDOW + 1)
+            .await
+            .map_err(ServiceError::from)?;
+        // Registro retroativo: o abastecimento seguinte também é vizinho
+        let next = self.fueling_repo
+            .find_next_by_vehicle(payload.vehicle_id, payload.fueling_date)
+            .await
+            .map_err(ServiceError::from)?;
+        let median_price = self.fueling_repo
+            .median_unit_price(
+                payload.fuel_type_id,
+                payload.fueling_date - Duration::days(PRICE_MEDIAN_WINDOW_DAYS),
+                payload.fueling_date,
+            )
+            .await
+            .map_err(ServiceError::from)?;
+
+        let anomalies = detect_anomalies(
+            payload,
+            vehicle.fuel_tank_capacity,
+            vehicle.fuel_type_id,
+            &history,
+            next.as_ref(),
+            median_price,
+        );
+        if anomalies.is_empty() {
+            return Ok((FuelingReviewStatus::Validado, None));
+        }
+        let json = serde_json::to_value(&anomalies)
+            .map_err(|e| ServiceError::Internal(e.to_string()))?;
+        Ok((FuelingReviewStatus::Quarentena, Some(json)))
     }
 
     pub async fn create_fueling(
@@ -35,6 +108,8 @@ impl FuelingService {
             return Err(ServiceError::BadRequest("Quilometragem deve ser zero ou positiva".to_string()));
         }
 
+        let (review_status, anomalies) = self.classify(&payload).await?;
+
         let fueling = self.fueling_repo
             .create(
                 payload.vehicle_id,
@@ -47,6 +122,8 @@ impl FuelingService {
                 payload.unit_price,
                 payload.total_cost,
                 payload.notes.as_deref(),
+                review_status,
+                anomalies,
                 created_by,
             )
             .await
@@ -73,7 +150,7 @@ impl FuelingService {
         payload: UpdateFuelingPayload,
         updated_by: Option<Uuid>,
     ) -> Result<FuelingWithDetailsDto, ServiceError> {
-        let _current = self.fueling_repo.find_by_id(id).await.map_err(ServiceError::from)?
+        let current = self.fueling_repo.find_by_id(id).await.map_err(ServiceError::from)?
             .ok_or(ServiceError::NotFound("Abastecimento não encontrado".to_string()))?;
 
         // Validate positive values if provided
@@ -98,6 +175,38 @@ impl FuelingService {
             }
         }
 
+        // Edições que mexem nos dados verificados reavaliam o registro; um
+        // abastecimento rejeitado só sai da rejeição passando por nova revisão.
+        let rechecked = payload.vehicle_id.is_some()
+            || payload.fuel_type_id.is_some()
+            || payload.fueling_date.is_some()
+            || payload.odometer_km.is_some()
+            || payload.quantity_liters.is_some()
+            || payload.unit_price.is_some();
+        let (review_status, anomalies) = if rechecked {
+            let effective = CreateFuelingPayload {
+                vehicle_id: payload.vehicle_id.unwrap_or(current.vehicle_id),
+                driver_id: payload.driver_id.unwrap_or(current.driver_id),
+                supplier_id: payload.supplier_id.or(current.supplier_id),
+                fuel_type_id: payload.fuel_type_id.unwrap_or(current.fuel_type_id),
+                fueling_date: payload.fueling_date.unwrap_or(current.fueling_date),
+                odometer_km: payload.odometer_km.unwrap_or(current.odometer_km),
+                quantity_liters: payload.quantity_liters.unwrap_or(current.quantity_liters),
+                unit_price: payload.unit_price.unwrap_or(current.unit_price),
+                total_cost: payload.total_cost.unwrap_or(current.total_cost),
+                notes: None,
+            };
+            let (status, anomalies) = self.classify(&effective).await?;
+            let status = if current.review_status == FuelingReviewStatus::Rejeitado {
+                FuelingReviewStatus::Quarentena
+            } else {
+                status
+            };
+            (Some(status), anomalies)
+        } else {
+            (None, None)
+        };
+
         let _ = self.fueling_repo
             .update(
                 id,
@@ -111,6 +220,8 @@ impl FuelingService {
                 payload.unit_price,
                 payload.total_cost,
                 payload.notes.as_deref(),
+                review_status,
+                anomalies,
                 updated_by,
             )
             .await
@@ -138,8 +249,282 @@ impl FuelingService {
         supplier_id: Option<Uuid>,
     ) -> Result<(Vec<FuelingWithDetailsDto>, i64), ServiceError> {
         self.fueling_repo
-            .list(limit, offset, vehicle_id, driver_id, supplier_id)
+            .list(limit, offset, vehicle_id, driver_id, supplier_id, None)
+            .await
+            .map_err(ServiceError::from)
+    }
+
+    /// Fila de revisão: abastecimentos em QUARENTENA, mais recentes primeiro.
+    pub async fn list_review_queue(
+        &self,
+        limit: i64,
+        offset: i64,
+        vehicle_id: Option<Uuid>,
+    ) -> Result<(Vec<FuelingWithDetailsDto>, i64), ServiceError> {
+        self.fueling_repo
+            .list(limit, offset, vehicle_id, None, None, Some(FuelingReviewStatus::Quarentena))
             .await
             .map_err(ServiceError::from)
     }
+
+    /// Resolve um abastecimento em quarentena: valida ou rejeita.
+    pub async fn review_fueling(
+        &self,
+        id: Uuid,
+        payload: ReviewFuelingPayload,
+        reviewed_by: Uuid,
+    ) -> Result<FuelingWithDetailsDto, ServiceError> {
+        let fueling = self.fueling_repo.find_by_id(id).await.map_err(ServiceError::from)?
+            .ok_or(ServiceError::NotFound("Abastecimento não encontrado".to_string()))?;
+
+        if fueling.review_status != FuelingReviewStatus::Quarentena {
+            return Err(ServiceError::BadRequest(
+                "Apenas abastecimentos em QUARENTENA podem ser revisados".to_string(),
+            ));
+        }
+
+        let status = if payload.approve {
+            FuelingReviewStatus::Validado
+        } else {
+            FuelingReviewStatus::Rejeitado
+        };
+
+        self.fueling_repo
+            .review(id, status, reviewed_by, payload.notes.as_deref())
+            .await
+            .map_err(ServiceError::from)?
+            .ok_or_else(|| ServiceError::Conflict("Abastecimento já foi revisado".to_string()))?;
+
+        self.fueling_repo
+            .find_with_details_by_id(id)
+            .await
+            .map_err(ServiceError::from)?
+            .ok_or(ServiceError::Internal("Falha ao buscar abastecimento revisado".to_string()))
+    }
+}
+
+/// Aplica as regras de plausibilidade a um novo abastecimento.
+///
+/// `history` são os abastecimentos anteriores do veículo, do mais recente
+/// para o mais antigo; `next` é o primeiro posterior, quando o registro é
+/// retroativo.
+fn detect_anomalies(
+    payload: &CreateFuelingPayload,
+    tank_capacity: Option<Decimal>,
+    vehicle_fuel_type_id: Uuid,
+    history: &[FuelingDto],
+    next: Option<&FuelingDto>,
+    median_price: Option<Decimal>,
+) -> Vec<FuelingAnomaly> {
+    let mut anomalies = Vec::new();
+    let liters = payload.quantity_liters.to_f64().unwrap_or(0.0);
+
+    if let Some(capacity) = tank_capacity.and_then(|c| c.to_f64()) {
+        if liters > capacity * TANK_CAPACITY_TOLERANCE {
+            anomalies.push(FuelingAnomaly {
+                kind: FuelingAnomalyKind::TankCapacityExceeded,
+                detail: format!("{:.2} L abastecidos para tanque de {:.2} L", liters, capacity),
+            });
+        }
+    }
+
+    if payload.fuel_type_id != vehicle_fuel_type_id {
+        anomalies.push(FuelingAnomaly {
+            kind: FuelingAnomalyKind::FuelTypeMismatch,
+            detail: "Combustível diferente do cadastrado para o veículo".to_string(),
+        });
+    }
+
+    if let Some(previous) = history.first() {
+        let km_since = payload.odometer_km - previous.odometer_km;
+        let hours_since = (payload.fueling_date - previous.fueling_date).num_hours();
+        if km_since < MIN_KM_BETWEEN_FUELINGS || hours_since < MIN_HOURS_BETWEEN_FUELINGS {
+            anomalies.push(FuelingAnomaly {
+                kind: FuelingAnomalyKind::TooCloseToPrevious,
+                detail: format!(
+                    "{} km e {} h desde o abastecimento anterior",
+                    km_since, hours_since
+                ),
+            });
+        }
+
+        if km_since > 0 && liters > 0.0 {
+            if let Some(average) = rolling_km_per_liter(history) {
+                let current = km_since as f64 / liters;
+                if (current - average).abs() / average > MAX_CONSUMPTION_DEVIATION {
+                    anomalies.push(FuelingAnomaly {
+                        kind: FuelingAnomalyKind::ConsumptionDeviation,
+                        detail: format!(
+                            "Consumo de {:.2} km/L frente à média de {:.2} km/L",
+                            current, average
+                        ),
+                    });
+                }
+            }
+        }
+    }
+
+    if let Some(next) = next {
+        let km_until = next.odometer_km - payload.odometer_km;
+        let hours_until = (next.fueling_date - payload.fueling_date).num_hours();
+        if km_until < MIN_KM_BETWEEN_FUELINGS || hours_until < MIN_HOURS_BETWEEN_FUELINGS {
+            anomalies.push(FuelingAnomaly {
+                kind: FuelingAnomalyKind::TooCloseToNext,
+                detail: format!(
+                    "{} km e {} h até o abastecimento seguinte",
+                    km_until, hours_until
+                ),
+            });
+        }
+    }
+
+    if let (Some(median), Some(price)) = (
+        median_price.and_then(|m| m.to_f64()),
+        payload.unit_price.to_f64(),
+    ) {
+        if median > 0.0 && price > median * (1.0 + MAX_PRICE_ABOVE_MEDIAN) {
+            anomalies.push(FuelingAnomaly {
+                kind: FuelingAnomalyKind::UnitPriceAboveMedian,
+                detail: format!(
+                    "Preço de {:.4}/L frente à mediana de {:.4}/L no período",
+                    price, median
+                ),
+            });
+        }
+    }
+
+    anomalies
+}
+
+/// Média móvel de km/L entre abastecimentos consecutivos (método tanque
+/// cheio): km rodados até cada abastecimento ÷ litros nele abastecidos.
+fn rolling_km_per_liter(history: &[FuelingDto]) -> Option<f64> {
+    let (km, liters, samples) = history
+        .windows(2)
+        .filter_map(|pair| {
+            let km = pair[0].odometer_km - pair[1].odometer_km;
+            let liters = pair[0].quantity_liters.to_f64()?;
+            (km > 0 && liters > 0.0).then_some((km as f64, liters))
+        })
+        .fold((0.0, 0.0, 0usize), |(k, l, n), (km, liters)| (k + km, l + liters, n + 1));
+
+    (samples >= MIN_CONSUMPTION_SAMPLES && liters > 0.0).then(|| km / liters)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{DateTime, Utc};
+
+    fn payload(odometer_km: i32, liters: i64, unit_price: Decimal, at: DateTime<Utc>, fuel_type_id: Uuid) -> CreateFuelingPayload {
+        CreateFuelingPayload {
+            vehicle_id: Uuid::new_v4(),
+            driver_id: Uuid::new_v4(),
+            supplier_id: None,
+            fuel_type_id,
+            fueling_date: at,
+            odometer_km,
+            quantity_liters: Decimal::from(liters),
+            unit_price,
+            total_cost: unit_price * Decimal::from(liters),
+            notes: None,
+        }
+    }
+
+    fn past(odometer_km: i32, liters: i64, at: DateTime<Utc>) -> FuelingDto {
+        FuelingDto {
+            id: Uuid::new_v4(),
+            vehicle_id: Uuid::new_v4(),
+            driver_id: Uuid::new_v4(),
+            supplier_id: None,
+            fuel_type_id: Uuid::new_v4(),
+            fueling_date: at,
+            odometer_km,
+            quantity_liters: Decimal::from(liters),
+            unit_price: Decimal::from(6),
+            total_cost: Decimal::from(6 * liters),
+            notes: None,
+            review_status: FuelingReviewStatus::Validado,
+            anomalies: None,
+            reviewed_by: None,
+            reviewed_at: None,
+            review_notes: None,
+            created_at: at,
+            updated_at: at,
+            created_by: None,
+            updated_by: None,
+        }
+    }
+
+    /// Four fuelings of 40 L every 400 km, one per week — 10 km/L.
+    fn regular_history(now: DateTime<Utc>) -> Vec<FuelingDto> {
+        (1..=4)
+            .map(|i| past(10_000 - 400 * (i - 1), 40, now - Duration::days(7 * i as i64)))
+            .collect()
+    }
+
+    fn kinds(anomalies: &[FuelingAnomaly]) -> Vec<FuelingAnomalyKind> {
+        anomalies.iter().map(|a| a.kind.clone()).collect()
+    }
+
+    #[test]
+    fn test_regular_fueling_has_no_anomalies() {
+        let now = Utc::now();
+        let fuel = Uuid::new_v4();
+        let p = payload(10_400, 40, Decimal::from(6), now, fuel);
+        let anomalies = detect_anomalies(&p, Some(Decimal::from(50)), fuel, &regular_history(now), None, Some(Decimal::from(6)));
+        assert!(anomalies.is_empty(), "{:?}", anomalies);
+    }
+
+    #[test]
+    fn test_detects_each_anomaly_kind() {
+        let now = Utc::now();
+        let fuel = Uuid::new_v4();
+
+        let over_tank = payload(10_600, 60, Decimal::from(6), now, fuel);
+        let found = kinds(&detect_anomalies(&over_tank, Some(Decimal::from(50)), fuel, &regular_history(now), None, None));
+        assert!(found.contains(&FuelingAnomalyKind::TankCapacityExceeded));
+
+        let wrong_fuel = payload(10_400, 40, Decimal::from(6), now, Uuid::new_v4());
+        let found = kinds(&detect_anomalies(&wrong_fuel, None, fuel, &regular_history(now), None, None));
+        assert_eq!(found, vec![FuelingAnomalyKind::FuelTypeMismatch]);
+
+        let history = regular_history(now);
+        let too_soon = payload(10_010, 40, Decimal::from(6), history[0].fueling_date + Duration::hours(1), fuel);
+        let found = kinds(&detect_anomalies(&too_soon, None, fuel, &history, None, None));
+        assert!(found.contains(&FuelingAnomalyKind::TooCloseToPrevious));
+
+        // 200 km on 40 L = 5 km/L against a 10 km/L average.
+        let thirsty = payload(10_200, 40, Decimal::from(6), now, fuel);
+        let found = kinds(&detect_anomalies(&thirsty, None, fuel, &regular_history(now), None, None));
+        assert_eq!(found, vec![FuelingAnomalyKind::ConsumptionDeviation]);
+
+        let expensive = payload(10_400, 40, Decimal::from(9), now, fuel);
+        let found = kinds(&detect_anomalies(&expensive, None, fuel, &regular_history(now), None, Some(Decimal::from(6))));
+        assert_eq!(found, vec![FuelingAnomalyKind::UnitPriceAboveMedian]);
+    }
+
+    #[test]
+    fn test_backdated_fueling_checks_the_next_one() {
+        let now = Utc::now();
+        let fuel = Uuid::new_v4();
+        let history = regular_history(now);
+        let (next, older) = (&history[0], &history[1..]);
+
+        let too_close = payload(9_990, 40, Decimal::from(6), next.fueling_date - Duration::hours(1), fuel);
+        let found = kinds(&detect_anomalies(&too_close, None, fuel, older, Some(next), None));
+        assert_eq!(found, vec![FuelingAnomalyKind::TooCloseToNext]);
+
+        let between = payload(9_800, 20, Decimal::from(6), next.fueling_date - Duration::days(3), fuel);
+        let found = kinds(&detect_anomalies(&between, None, fuel, older, Some(next), None));
+        assert!(found.is_empty(), "{:?}", found);
+    }
+
+    #[test]
+    fn test_rolling_average_needs_enough_samples() {
+        let now = Utc::now();
+        assert_eq!(rolling_km_per_liter(&regular_history(now)[..2]), None);
+        let avg = rolling_km_per_liter(&regular_history(now)).unwrap();
+        assert!((avg - 10.0).abs() < 1e-9);
+    }
 }
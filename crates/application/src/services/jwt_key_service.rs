@@ -0,0 +1,72 @@
+use std::sync::Arc;
+
+use chrono::{Duration, Utc};
+use core_services::jwt::JwtService;
+use domain::errors::RepositoryError;
+use domain::models::JwtSigningKeyDto;
+use domain::ports::JwtSigningKeyRepositoryPort;
+use tracing::info;
+use uuid::Uuid;
+
+use crate::errors::ServiceError;
+
+/// Rotation of the keys that sign JWTs.
+///
+/// The keyring lives in the database so every instance signs with the same
+/// active key; `refresh` reloads it into the `JwtService` of this instance.
+pub struct JwtKeyService {
+    key_repo: Arc<dyn JwtSigningKeyRepositoryPort>,
+    jwt_service: Arc<JwtService>,
+    grace_period: Duration,
+}
+
+impl JwtKeyService {
+    pub fn new(
+        key_repo: Arc<dyn JwtSigningKeyRepositoryPort>,
+        jwt_service: Arc<JwtService>,
+        grace_period_secs: i64,
+    ) -> Self {
+        Self {
+            key_repo,
+            jwt_service,
+            grace_period: Duration::seconds(grace_period_secs),
+        }
+    }
+
+    /// Recarrega o keyring do banco no `JwtService`
+    pub async fn refresh(&self) -> Result<(), ServiceError> {
+        let keys = self.key_repo.list_keys().await?;
+        let active = self.key_repo.find_active().await?;
+        self.jwt_service
+            .install_keys(&keys, active.as_ref())
+            .map_err(|e| ServiceError::Internal(format!("Keyring JWT inválido: {}", e)))
+    }
+
+    /// Gera uma nova chave ativa. A anterior continua verificando tokens
+    /// durante o período de carência.
+    pub async fn rotate(&self, triggered_by: Option<Uuid>) -> Result<JwtSigningKeyDto, ServiceError> {
+        let internal = |e: anyhow::Error| ServiceError::Internal(e.to_string());
+        let new_key = JwtService::generate_signing_key().map_err(internal)?;
+        let configured = self.jwt_service.configured_key().map_err(internal)?;
+        let verify_until = Utc::now() + self.grace_period;
+
+        let key = self
+            .key_repo
+            .rotate(&new_key, &configured, verify_until, triggered_by)
+            .await
+            .map_err(|e| match e {
+                RepositoryError::Duplicate(_) => ServiceError::Conflict(
+                    "Outra rotação da chave JWT foi concluída ao mesmo tempo".to_string(),
+                ),
+                other => other.into(),
+            })?;
+        self.refresh().await?;
+
+        info!(kid = %key.kid, %verify_until, "Chave de assinatura JWT rotacionada");
+        Ok(key)
+    }
+
+    pub async fn list_keys(&self) -> Result<Vec<JwtSigningKeyDto>, ServiceError> {
+        Ok(self.key_repo.list_keys().await?)
+    }
+}
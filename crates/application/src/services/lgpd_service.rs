@@ -0,0 +1,213 @@
+use crate::errors::ServiceError;
+use crate::services::audit_integrity_service::AuditIntegrityService;
+use crate::services::supplier_service::{normalize_document, validate_cpf};
+use chrono::Utc;
+use core_services::security::hash_password;
+use domain::models::{
+    AnonymizationPlan, AnonymizationResult, DataSubjectExport, DriverAnonymization,
+    UserAnonymization,
+};
+use domain::ports::LgpdRepositoryPort;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Kept after anonymization: sealed files are signed as a whole and cannot
+/// be rewritten; they are discarded with the retention of the archive
+const RETAINED_AUDIT_ARCHIVES: &str =
+    "audit_log_archives: arquivos selados da retenção, mantidos por obrigação legal (LGPD art. 16, I) até o descarte";
+
+/// LGPD data-subject requests: export of everything held about a person and
+/// irreversible anonymization.
+///
+/// Anonymized rows stay in place, so trips, fines, fuelings and requisitions
+/// keep their references and accounting; only the personal fields are
+/// replaced by random tokens.
+pub struct LgpdService {
+    repo: Arc<dyn LgpdRepositoryPort>,
+    audit_integrity: Arc<AuditIntegrityService>,
+}
+
+/// Subjects matched by a request
+struct ResolvedSubject {
+    user_id: Option<Uuid>,
+    driver_id: Option<Uuid>,
+}
+
+impl LgpdService {
+    pub fn new(
+        repo: Arc<dyn LgpdRepositoryPort>,
+        audit_integrity: Arc<AuditIntegrityService>,
+    ) -> Self {
+        Self {
+            repo,
+            audit_integrity,
+        }
+    }
+
+    /// JSON bundle with the user account and/or driver of the subject
+    pub async fn export(
+        &self,
+        user_id: Option<Uuid>,
+        cpf: Option<&str>,
+    ) -> Result<DataSubjectExport, ServiceError> {
+        let subject = self.resolve(user_id, cpf).await?;
+
+        let user = match subject.user_id {
+            Some(id) => self.repo.export_user(id).await?,
+            None => None,
+        };
+        let driver = match subject.driver_id {
+            Some(id) => self.repo.export_driver(id).await?,
+            None => None,
+        };
+
+        Ok(DataSubjectExport {
+            generated_at: Utc::now(),
+            user,
+            driver,
+        })
+    }
+
+    /// Replaces the personal data of the subject by irreversible tokens
+    pub async fn anonymize(
+        &self,
+        user_id: Option<Uuid>,
+        cpf: Option<&str>,
+        requested_by: Uuid,
+    ) -> Result<AnonymizationResult, ServiceError> {
+        let subject = self.resolve(user_id, cpf).await?;
+        if subject.user_id == Some(requested_by) {
+            return Err(ServiceError::Forbidden(
+                "Não é possível anonimizar a própria conta".to_string(),
+            ));
+        }
+
+        let user = match subject.user_id {
+            Some(user_id) => Some(user_tokens(user_id).await?),
+            None => None,
+        };
+        let plan = AnonymizationPlan {
+            user,
+            driver: subject.driver_id.map(driver_tokens),
+        };
+
+        // Logs ficam em outro banco: a remoção vem antes e pode ser repetida
+        let audit_redacted = match subject.user_id {
+            Some(user_id) => Some(self.audit_integrity.redact_user(user_id).await?),
+            None => None,
+        };
+
+        let mut result = self.repo.anonymize(&plan).await?;
+        if result.user_id.is_none() && result.driver_id.is_none() {
+            return Err(ServiceError::Conflict("Titular já anonimizado".to_string()));
+        }
+        if let Some(redacted) = audit_redacted {
+            result.affected["audit_logs"] = redacted.into();
+            result.retained.push(RETAINED_AUDIT_ARCHIVES.to_string());
+        }
+
+        Ok(result)
+    }
+
+    /// User by ID (or by CPF when no ID is given) and driver by CPF
+    async fn resolve(
+        &self,
+        user_id: Option<Uuid>,
+        cpf: Option<&str>,
+    ) -> Result<ResolvedSubject, ServiceError> {
+        let cpf = match cpf.map(str::trim).filter(|c| !c.is_empty()) {
+            Some(raw) => {
+                let cpf = normalize_document(raw);
+                validate_cpf(&cpf).map_err(ServiceError::BadRequest)?;
+                Some(cpf)
+            }
+            None => None,
+        };
+
+        let (user_id, driver_id) = match (user_id, cpf) {
+            (None, None) => {
+                return Err(ServiceError::BadRequest(
+                    "Informe user_id ou cpf do titular".to_string(),
+                ))
+            }
+            (Some(user_id), None) => (Some(user_id), None),
+            (user_id, Some(cpf)) => {
+                let user_by_cpf = self.repo.find_user_id_by_cpf(&cpf).await?;
+                if let (Some(id), Some(other)) = (user_id, user_by_cpf) {
+                    if id != other {
+                        return Err(ServiceError::BadRequest(
+                            "O CPF informado pertence a outro usuário".to_string(),
+                        ));
+                    }
+                }
+                let driver_id = self.repo.find_driver_id_by_cpf(&cpf).await?;
+                (user_id.or(user_by_cpf), driver_id)
+            }
+        };
+
+        if user_id.is_none() && driver_id.is_none() {
+            return Err(ServiceError::NotFound(
+                "Nenhum dado pessoal encontrado para o titular".to_string(),
+            ));
+        }
+
+        Ok(ResolvedSubject { user_id, driver_id })
+    }
+}
+
+/// Random hex, unrelated to the original data
+fn random_token() -> String {
+    Uuid::new_v4().simple().to_string()
+}
+
+async fn user_tokens(user_id: Uuid) -> Result<UserAnonymization, ServiceError> {
+    let token = random_token();
+    let password = random_token();
+    let password_hash = tokio::task::spawn_blocking(move || hash_password(&password))
+        .await
+        .map_err(|e| ServiceError::Internal(format!("Password hashing task failed: {}", e)))?
+        .map_err(|e| ServiceError::Internal(e.to_string()))?;
+
+    Ok(UserAnonymization {
+        user_id,
+        username: format!("anon_{}", &token[..12]),
+        email: format!("anon_{}@anonimizado.invalid", &token[..12]),
+        password_hash,
+    })
+}
+
+fn driver_tokens(driver_id: Uuid) -> DriverAnonymization {
+    let token = random_token().to_uppercase();
+    DriverAnonymization {
+        driver_id,
+        full_name: format!("Titular anonimizado {}", &token[..8]),
+        // Letra inicial: nunca colide com um CPF real
+        cpf: format!("X{}", &token[..10]),
+        cnh_number: format!("ANON-{}", &token[..12]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use domain::value_objects::{Email, Username};
+
+    #[tokio::test]
+    async fn test_user_tokens_are_valid_account_values() {
+        let tokens = user_tokens(Uuid::new_v4()).await.unwrap();
+
+        assert!(Username::try_from(tokens.username.as_str()).is_ok());
+        assert!(Email::try_from(tokens.email.as_str()).is_ok());
+        assert!(tokens.password_hash.starts_with("$argon2id$"));
+    }
+
+    #[test]
+    fn test_driver_tokens_fit_columns_and_are_not_cpfs() {
+        let tokens = driver_tokens(Uuid::new_v4());
+
+        assert_eq!(tokens.cpf.len(), 11);
+        assert!(validate_cpf(&tokens.cpf).is_err());
+        assert!(tokens.cnh_number.len() <= 20);
+        assert_ne!(driver_tokens(Uuid::new_v4()).cpf, tokens.cpf);
+    }
+}
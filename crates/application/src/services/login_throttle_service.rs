@@ -0,0 +1,508 @@
+use crate::errors::ServiceError;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use chrono::{DateTime, Duration, Utc};
+use core_services::security::verify_password_or_dummy;
+use domain::models::{AccountLockout, AccountLockoutDto};
+use domain::ports::{EmailServicePort, LoginThrottleRepositoryPort, UserRepositoryPort};
+use sha2::{Digest, Sha256};
+use std::net::IpAddr;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Validade do link de desbloqueio enviado por email.
+const UNLOCK_TOKEN_EXPIRY_HOURS: i64 = 24;
+
+/// Sem novos bloqueios por esse período, a escalada volta ao primeiro nível.
+const LOCKOUT_LEVEL_DECAY_HOURS: i64 = 24;
+
+/// Mesma mensagem para bloqueio de conta e de origem: a resposta não indica
+/// qual dos limites foi atingido.
+const THROTTLED_MESSAGE: &str = "Muitas tentativas de login. Tente novamente mais tarde.";
+
+/// Limites do bloqueio progressivo por conta e do throttling por origem.
+#[derive(Debug, Clone)]
+pub struct LoginThrottleSettings {
+    /// Falhas consecutivas que bloqueiam a conta
+    pub max_failures: i32,
+    /// Falhas mais antigas que isso não entram na contagem consecutiva
+    pub failure_window_secs: i64,
+    /// Duração do primeiro bloqueio; dobra a cada bloqueio seguinte
+    pub base_lockout_secs: i64,
+    pub max_lockout_secs: i64,
+    /// Falhas aceitas de um mesmo IP dentro de `origin_window_secs`
+    pub ip_max_failures: i64,
+    /// Falhas aceitas de uma mesma sub-rede dentro de `origin_window_secs`
+    pub subnet_max_failures: i64,
+    pub origin_window_secs: i64,
+    pub ipv4_subnet_prefix: u8,
+    pub ipv6_subnet_prefix: u8,
+}
+
+impl Default for LoginThrottleSettings {
+    fn default() -> Self {
+        Self {
+            max_failures: 5,
+            failure_window_secs: 900,
+            base_lockout_secs: 60,
+            max_lockout_secs: 86_400,
+            ip_max_failures: 20,
+            subnet_max_failures: 100,
+            origin_window_secs: 900,
+            ipv4_subnet_prefix: 24,
+            ipv6_subnet_prefix: 64,
+        }
+    }
+}
+
+/// Defesa contra força bruta e credential stuffing no login por senha.
+///
+/// Cada tentativa fica registrada em `login_attempts`; o mesmo registro
+/// alimenta o limite por IP, o limite por sub-rede (/24 e /64 por padrão) e
+/// o bloqueio progressivo da conta, cuja duração dobra a cada reincidência.
+/// Ao bloquear, o titular recebe um email com link de desbloqueio.
+///
+/// A verificação da senha custa o mesmo tempo quando o usuário não existe
+/// (ver `verify_password_or_dummy`), e bloqueio de conta e de origem
+/// respondem com o mesmo 429.
+pub struct LoginThrottleService {
+    repo: Arc<dyn LoginThrottleRepositoryPort>,
+    user_repo: Arc<dyn UserRepositoryPort>,
+    email_service: Arc<dyn EmailServicePort>,
+    settings: LoginThrottleSettings,
+}
+
+impl LoginThrottleService {
+    pub fn new(
+        repo: Arc<dyn LoginThrottleRepositoryPort>,
+        user_repo: Arc<dyn UserRepositoryPort>,
+        email_service: Arc<dyn EmailServicePort>,
+        settings: LoginThrottleSettings,
+    ) -> Self {
+        Self {
+            repo,
+            user_repo,
+            email_service,
+            settings,
+        }
+    }
+
+    /// Verifica a senha de uma tentativa de login aplicando os limites.
+    ///
+    /// `user` é o par (id, hash) encontrado para `identifier`, ou `None` se o
+    /// username/email não existe. Retorna `InvalidCredentials` para senha
+    /// errada e `TooManyRequests` quando a origem ou a conta estão bloqueadas.
+    pub async fn verify_password(
+        &self,
+        identifier: &str,
+        password: &str,
+        user: Option<(Uuid, String)>,
+        ip: Option<IpAddr>,
+    ) -> Result<(), ServiceError> {
+        self.check_origin(ip).await?;
+        match &user {
+            Some((user_id, _)) => self.check_account(*user_id).await?,
+            // Sem conta, o bloqueio é recalculado das falhas do identificador:
+            // o 429 não pode revelar quais contas existem
+            None => self.check_unknown_identifier(identifier).await?,
+        }
+
+        let user_id = user.as_ref().map(|(id, _)| *id);
+        let password = password.to_string();
+        let hash = user.map(|(_, hash)| hash);
+        let valid = tokio::task::spawn_blocking(move || {
+            verify_password_or_dummy(&password, hash.as_deref())
+        })
+        .await
+        .map_err(|e| ServiceError::Internal(format!("Password verification task failed: {}", e)))?;
+
+        match (valid, user_id) {
+            (true, Some(user_id)) => {
+                self.record_success(user_id, identifier, ip).await?;
+                Ok(())
+            }
+            _ => {
+                self.record_failure(user_id, identifier, ip).await?;
+                Err(ServiceError::InvalidCredentials)
+            }
+        }
+    }
+
+    /// Desbloqueia a conta pelo link enviado por email.
+    pub async fn unlock_with_token(&self, token: &str) -> Result<(), ServiceError> {
+        let user_id = self
+            .repo
+            .find_user_by_unlock_token(&hash_token(token))
+            .await?
+            .ok_or_else(|| {
+                ServiceError::BadRequest("Token de desbloqueio inválido ou expirado".to_string())
+            })?;
+
+        self.repo.clear_lockout(user_id).await?;
+        tracing::info!(user_id = %user_id, "Conta desbloqueada pelo link de email");
+        Ok(())
+    }
+
+    pub async fn list_lockouts(&self) -> Result<Vec<AccountLockoutDto>, ServiceError> {
+        Ok(self.repo.list_lockouts().await?)
+    }
+
+    /// Remove bloqueio e falhas pendentes da conta (ação administrativa).
+    pub async fn clear_lockout(&self, user_id: Uuid) -> Result<(), ServiceError> {
+        if !self.repo.clear_lockout(user_id).await? {
+            return Err(ServiceError::NotFound(
+                "Nenhum bloqueio registrado para o usuário".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Apaga tentativas mais antigas que `retention_days`.
+    pub async fn purge_attempts(&self, retention_days: i64) -> Result<u64, ServiceError> {
+        Ok(self
+            .repo
+            .purge_attempts_before(Utc::now() - Duration::days(retention_days))
+            .await?)
+    }
+
+    // ── Auxiliares ───────────────────────────────────────────────────────
+
+    async fn check_origin(&self, ip: Option<IpAddr>) -> Result<(), ServiceError> {
+        let Some(ip) = ip else {
+            return Ok(());
+        };
+        let since = Utc::now() - Duration::seconds(self.settings.origin_window_secs);
+
+        let host = network_of(ip, 32, 128);
+        let subnet = network_of(
+            ip,
+            self.settings.ipv4_subnet_prefix,
+            self.settings.ipv6_subnet_prefix,
+        );
+
+        for (network, limit) in [
+            (host, self.settings.ip_max_failures),
+            (subnet, self.settings.subnet_max_failures),
+        ] {
+            let failures = self.repo.count_failures_in_network(&network, since).await?;
+            if failures >= limit {
+                tracing::warn!(network = %network, failures, "Login bloqueado por excesso de falhas na origem");
+                return Err(ServiceError::TooManyRequests {
+                    message: THROTTLED_MESSAGE.to_string(),
+                    retry_after_secs: self.settings.origin_window_secs as u64,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    async fn check_account(&self, user_id: Uuid) -> Result<(), ServiceError> {
+        let locked_until = self
+            .repo
+            .get_lockout(user_id)
+            .await?
+            .and_then(|l| l.locked_until);
+        reject_while_locked(locked_until)
+    }
+
+    async fn check_unknown_identifier(&self, identifier: &str) -> Result<(), ServiceError> {
+        let since = Utc::now()
+            - Duration::hours(LOCKOUT_LEVEL_DECAY_HOURS)
+            - Duration::seconds(self.settings.max_lockout_secs);
+        let failures = self
+            .repo
+            .unknown_identifier_failures(identifier, since)
+            .await?;
+        reject_while_locked(replay_failures(&failures, &self.settings).locked_until)
+    }
+
+    async fn record_success(
+        &self,
+        user_id: Uuid,
+        identifier: &str,
+        ip: Option<IpAddr>,
+    ) -> Result<(), ServiceError> {
+        let ip = ip.map(|ip| ip.to_string());
+        self.repo
+            .record_attempt(Some(user_id), identifier, ip.as_deref(), true)
+            .await?;
+        self.repo.clear_lockout(user_id).await?;
+        Ok(())
+    }
+
+    async fn record_failure(
+        &self,
+        user_id: Option<Uuid>,
+        identifier: &str,
+        ip: Option<IpAddr>,
+    ) -> Result<(), ServiceError> {
+        let ip = ip.map(|ip| ip.to_string());
+        self.repo
+            .record_attempt(user_id, identifier, ip.as_deref(), false)
+            .await?;
+
+        let Some(user_id) = user_id else {
+            return Ok(());
+        };
+
+        // A contagem é incrementada no banco: falhas concorrentes não se perdem
+        let now = Utc::now();
+        let window_start = now - Duration::seconds(self.settings.failure_window_secs);
+        let counted = self
+            .repo
+            .increment_failures(user_id, ip.as_deref(), now, window_start)
+            .await?;
+
+        let (next, newly_locked) = apply_lockout(counted, now, &self.settings);
+        if !newly_locked {
+            return Ok(());
+        }
+
+        let token = URL_SAFE_NO_PAD.encode(rand::random::<[u8; 32]>());
+        let expires_at = now + Duration::hours(UNLOCK_TOKEN_EXPIRY_HOURS);
+        let locked = self
+            .repo
+            .lock_account(
+                &next,
+                self.settings.max_failures,
+                (&hash_token(&token), expires_at),
+            )
+            .await?;
+        if !locked {
+            return Ok(());
+        }
+
+        let locked_until = next.locked_until.unwrap_or(now);
+        tracing::warn!(
+            user_id = %user_id,
+            level = next.lockout_level,
+            locked_until = %locked_until,
+            "Conta bloqueada após falhas consecutivas de login"
+        );
+        self.notify_locked(user_id, locked_until, &token).await;
+        Ok(())
+    }
+
+    async fn notify_locked(&self, user_id: Uuid, locked_until: DateTime<Utc>, token: &str) {
+        let user = match self.user_repo.find_by_id(user_id).await {
+            Ok(Some(user)) => user,
+            Ok(None) => return,
+            Err(e) => {
+                tracing::warn!(error = %e, user_id = %user_id, "Falha ao buscar usuário para aviso de bloqueio");
+                return;
+            }
+        };
+
+        if let Err(e) = self
+            .email_service
+            .send_account_locked_email(&user.email, &user.username, locked_until, token)
+            .await
+        {
+            tracing::warn!(error = %e, user_id = %user_id, "Falha ao enviar email de conta bloqueada");
+        }
+    }
+}
+
+/// Aplica uma falha ao estado da conta. Retorna o novo estado e se a falha
+/// acabou de bloquear a conta.
+pub fn register_failure(
+    current: AccountLockout,
+    now: DateTime<Utc>,
+    ip: Option<String>,
+    settings: &LoginThrottleSettings,
+) -> (AccountLockout, bool) {
+    let mut next = current;
+
+    if next
+        .last_failed_at
+        .is_none_or(|t| now - t > Duration::seconds(settings.failure_window_secs))
+    {
+        next.failed_count = 0;
+    }
+
+    next.failed_count += 1;
+    next.last_failed_at = Some(now);
+    next.last_failed_ip = ip;
+    apply_lockout(next, now, settings)
+}
+
+/// Bloqueia a conta se a contagem de falhas atingiu o limite. Retorna o novo
+/// estado e se a conta acabou de ser bloqueada.
+pub fn apply_lockout(
+    current: AccountLockout,
+    now: DateTime<Utc>,
+    settings: &LoginThrottleSettings,
+) -> (AccountLockout, bool) {
+    let mut next = current;
+
+    if next.failed_count < settings.max_failures {
+        return (next, false);
+    }
+    if next
+        .locked_until
+        .is_some_and(|until| now - until > Duration::hours(LOCKOUT_LEVEL_DECAY_HOURS))
+    {
+        next.lockout_level = 0;
+    }
+
+    next.locked_until = Some(now + lockout_duration(next.lockout_level, settings));
+    next.lockout_level += 1;
+    next.failed_count = 0;
+    (next, true)
+}
+
+/// Estado que uma conta teria após as falhas em `failures` (em ordem).
+/// Tentativas durante um bloqueio não são registradas, como nas contas reais.
+pub fn replay_failures(
+    failures: &[DateTime<Utc>],
+    settings: &LoginThrottleSettings,
+) -> AccountLockout {
+    failures
+        .iter()
+        .fold(AccountLockout::default(), |state, at| {
+            register_failure(state, *at, None, settings).0
+        })
+}
+
+fn reject_while_locked(locked_until: Option<DateTime<Utc>>) -> Result<(), ServiceError> {
+    if let Some(until) = locked_until {
+        let remaining = (until - Utc::now()).num_seconds();
+        if remaining > 0 {
+            return Err(ServiceError::TooManyRequests {
+                message: THROTTLED_MESSAGE.to_string(),
+                retry_after_secs: remaining as u64,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Duração do bloqueio no nível `level`: base × 2^level, limitada ao máximo.
+pub fn lockout_duration(level: i32, settings: &LoginThrottleSettings) -> Duration {
+    let factor = 1i64 << level.clamp(0, 30);
+    Duration::seconds(
+        settings
+            .base_lockout_secs
+            .saturating_mul(factor)
+            .min(settings.max_lockout_secs),
+    )
+}
+
+/// Rede CIDR que contém `ip` com o prefixo da família correspondente.
+pub fn network_of(ip: IpAddr, ipv4_prefix: u8, ipv6_prefix: u8) -> String {
+    match ip {
+        IpAddr::V4(v4) => {
+            let prefix = ipv4_prefix.min(32);
+            let mask = u32::MAX.checked_shl(32 - prefix as u32).unwrap_or(0);
+            let network = std::net::Ipv4Addr::from(u32::from(v4) & mask);
+            format!("{}/{}", network, prefix)
+        }
+        IpAddr::V6(v6) => {
+            let prefix = ipv6_prefix.min(128);
+            let mask = u128::MAX.checked_shl(128 - prefix as u32).unwrap_or(0);
+            let network = std::net::Ipv6Addr::from(u128::from(v6) & mask);
+            format!("{}/{}", network, prefix)
+        }
+    }
+}
+
+fn hash_token(token: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(token.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings() -> LoginThrottleSettings {
+        LoginThrottleSettings::default()
+    }
+
+    fn fail_times(mut state: AccountLockout, times: usize, now: DateTime<Utc>) -> (AccountLockout, bool) {
+        let mut locked = false;
+        for _ in 0..times {
+            let (next, newly_locked) = register_failure(state, now, None, &settings());
+            state = next;
+            locked = newly_locked;
+        }
+        (state, locked)
+    }
+
+    #[test]
+    fn locks_after_max_consecutive_failures() {
+        let now = Utc::now();
+        let (state, locked) = fail_times(AccountLockout::default(), 4, now);
+        assert!(!locked);
+        assert_eq!(state.failed_count, 4);
+        assert!(state.locked_until.is_none());
+
+        let (state, locked) = fail_times(state, 1, now);
+        assert!(locked);
+        assert_eq!(state.lockout_level, 1);
+        assert_eq!(state.failed_count, 0);
+        assert_eq!(state.locked_until, Some(now + Duration::seconds(60)));
+    }
+
+    #[test]
+    fn lockout_duration_doubles_and_is_capped() {
+        let s = settings();
+        assert_eq!(lockout_duration(0, &s), Duration::seconds(60));
+        assert_eq!(lockout_duration(1, &s), Duration::seconds(120));
+        assert_eq!(lockout_duration(3, &s), Duration::seconds(480));
+        assert_eq!(lockout_duration(40, &s), Duration::seconds(s.max_lockout_secs));
+    }
+
+    #[test]
+    fn old_failures_leave_the_window() {
+        let now = Utc::now();
+        let (state, _) = fail_times(AccountLockout::default(), 4, now - Duration::hours(1));
+        let (state, locked) = register_failure(state, now, None, &settings());
+        assert!(!locked);
+        assert_eq!(state.failed_count, 1);
+    }
+
+    #[test]
+    fn escalation_decays_after_a_quiet_day() {
+        let now = Utc::now();
+        let state = AccountLockout {
+            lockout_level: 3,
+            locked_until: Some(now - Duration::hours(30)),
+            ..Default::default()
+        };
+        let (state, locked) = fail_times(state, 5, now);
+        assert!(locked);
+        assert_eq!(state.lockout_level, 1);
+        assert_eq!(state.locked_until, Some(now + Duration::seconds(60)));
+    }
+
+    #[test]
+    fn unknown_identifier_replays_like_an_account() {
+        let now = Utc::now();
+        let failures: Vec<_> = (0..5).map(|i| now - Duration::seconds(5 - i)).collect();
+        let state = replay_failures(&failures, &settings());
+        assert_eq!(state.lockout_level, 1);
+        assert_eq!(state.locked_until, Some(now + Duration::seconds(59)));
+
+        // Depois do primeiro bloqueio, mais cinco falhas dobram a duração
+        let later = now + Duration::minutes(2);
+        let mut failures = failures;
+        failures.extend((0..5).map(|i| later + Duration::seconds(i)));
+        let state = replay_failures(&failures, &settings());
+        assert_eq!(state.lockout_level, 2);
+        assert_eq!(state.locked_until, Some(later + Duration::seconds(4 + 120)));
+
+        assert!(replay_failures(&failures[..4], &settings()).locked_until.is_none());
+    }
+
+    #[test]
+    fn networks_are_masked_by_family() {
+        let v4: IpAddr = "203.0.113.77".parse().unwrap();
+        let v6: IpAddr = "2001:db8:abcd:12:1::7".parse().unwrap();
+
+        assert_eq!(network_of(v4, 24, 64), "203.0.113.0/24");
+        assert_eq!(network_of(v4, 32, 128), "203.0.113.77/32");
+        assert_eq!(network_of(v6, 24, 64), "2001:db8:abcd:12::/64");
+        assert_eq!(network_of(v4, 0, 0), "0.0.0.0/0");
+    }
+}
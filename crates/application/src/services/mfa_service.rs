@@ -1,9 +1,10 @@
 use crate::errors::ServiceError;
+use crate::services::webauthn_service::WebauthnService;
 use chrono::{Duration, Utc};
 use core_services::jwt::JwtService;
 use domain::models::{
-    MfaBackupCodesResponse, MfaSetupCompleteResponse, MfaSetupResponse, MfaVerifyResponse,
-    TokenType,
+    FinishPasskeyCeremonyPayload, MfaBackupCodesResponse, MfaSetupCompleteResponse,
+    MfaSetupResponse, MfaVerifyResponse, TokenType, WebauthnChallengeDto,
 };
 use domain::ports::{AuthRepositoryPort, EmailServicePort, MfaRepositoryPort, UserRepositoryPort};
 use sha2::{Digest, Sha256};
@@ -24,6 +25,7 @@ pub struct MfaService {
     auth_repo: Arc<dyn AuthRepositoryPort>,
     email_service: Arc<dyn EmailServicePort>,
     jwt_service: Arc<JwtService>,
+    webauthn_service: Arc<WebauthnService>,
 }
 
 impl MfaService {
@@ -33,6 +35,7 @@ impl MfaService {
         auth_repo: Arc<dyn AuthRepositoryPort>,
         email_service: Arc<dyn EmailServicePort>,
         jwt_service: Arc<JwtService>,
+        webauthn_service: Arc<WebauthnService>,
     ) -> Self {
         Self {
             mfa_repo,
@@ -40,6 +43,7 @@ impl MfaService {
             auth_repo,
             email_service,
             jwt_service,
+            webauthn_service,
         }
     }
 
@@ -207,6 +211,46 @@ impl MfaService {
             return Err(ServiceError::InvalidCredentials);
         }
 
+        self.issue_tokens(user_id, backup_used).await
+    }
+
+    /// Inicia a verificação do desafio MFA com chave de segurança / passkey.
+    pub async fn start_passkey_verification(
+        &self,
+        mfa_token: &str,
+    ) -> Result<WebauthnChallengeDto, ServiceError> {
+        let claims = self
+            .jwt_service
+            .verify_mfa_token(mfa_token)
+            .map_err(|_| ServiceError::InvalidCredentials)?;
+
+        self.webauthn_service.start_authentication(claims.sub).await
+    }
+
+    /// Conclui o desafio MFA com a asserção WebAuthn, como alternativa ao TOTP.
+    pub async fn verify_login_with_passkey(
+        &self,
+        mfa_token: &str,
+        payload: FinishPasskeyCeremonyPayload,
+    ) -> Result<MfaVerifyResponse, ServiceError> {
+        let claims = self
+            .jwt_service
+            .verify_mfa_token(mfa_token)
+            .map_err(|_| ServiceError::InvalidCredentials)?;
+
+        let assertion = self
+            .webauthn_service
+            .finish_authentication(claims.sub, payload)
+            .await?;
+
+        self.issue_tokens(assertion.user_id, false).await
+    }
+
+    async fn issue_tokens(
+        &self,
+        user_id: Uuid,
+        backup_used: bool,
+    ) -> Result<MfaVerifyResponse, ServiceError> {
         // 4. Buscar username do usuário
         let user = self
             .user_repo
@@ -2,6 +2,8 @@ pub mod audit_services;
 pub mod auth_service;
 pub mod budget_classifications_service;
 pub mod catalog_service;
+pub mod catalog_merge_service;
+pub mod catalog_search_service;
 pub mod conflict_resolution_service;
 pub mod geo_regions_service;
 pub mod mfa_service;
@@ -30,3 +32,17 @@ pub mod alert_service;
 pub mod dashboard_service;
 pub mod abc_analysis_service;
 pub mod legacy_import_service;
+pub mod fuel_card_service;
+pub mod oidc_service;
+pub mod webauthn_service;
+pub mod login_throttle_service;
+pub mod api_key_service;
+pub mod delegation_service;
+pub mod approval_chain_service;
+pub mod audit_integrity_service;
+pub mod entity_history_service;
+pub mod lgpd_service;
+pub mod unit_restructuring_service;
+pub mod unit_cost_report_service;
+pub mod field_key_rotation_service;
+pub mod jwt_key_service;
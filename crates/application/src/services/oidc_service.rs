@@ -0,0 +1,491 @@
+use crate::errors::ServiceError;
+use crate::external::oidc_client::{IdTokenClaims, OidcClient};
+use crate::services::supplier_service::{normalize_document, validate_cpf};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use chrono::{Duration, Utc};
+use core_services::security::hash_password;
+use core_services::session::generate_token;
+use domain::models::{
+    IdentityLinkMethod, NewOidcAccount, OidcAuthorizationDto, OidcLoginState, OidcProviderDto,
+    UserIdentityDto,
+};
+use domain::ports::{OidcRepositoryPort, UserRepositoryPort};
+use domain::value_objects::{Email, Username};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Validade do `state` entre o redirecionamento ao IdP e o callback.
+const LOGIN_STATE_EXPIRY_MINUTES: i64 = 10;
+
+/// Configuração de um provedor OpenID Connect (ex.: gov.br).
+#[derive(Clone)]
+pub struct OidcProviderSettings {
+    /// Identificador usado nas rotas (`/auth/oidc/{name}/...`)
+    pub name: String,
+    pub display_name: String,
+    pub issuer: String,
+    pub client_id: String,
+    /// Ausente para clientes públicos (somente PKCE)
+    pub client_secret: Option<String>,
+    pub redirect_uri: String,
+    pub scopes: Vec<String>,
+    /// Claim que carrega o CPF do usuário (no gov.br, o próprio `sub`)
+    pub cpf_claim: Option<String>,
+    /// Cria a conta local no primeiro login quando não há vínculo possível
+    pub jit_provisioning: bool,
+    /// Papel Casbin atribuído às contas criadas via JIT
+    pub default_role: String,
+}
+
+impl fmt::Debug for OidcProviderSettings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OidcProviderSettings")
+            .field("name", &self.name)
+            .field("issuer", &self.issuer)
+            .field("client_id", &self.client_id)
+            .field("client_secret", &self.client_secret.as_ref().map(|_| "[REDACTED]"))
+            .field("redirect_uri", &self.redirect_uri)
+            .field("scopes", &self.scopes)
+            .field("cpf_claim", &self.cpf_claim)
+            .field("jit_provisioning", &self.jit_provisioning)
+            .field("default_role", &self.default_role)
+            .finish()
+    }
+}
+
+/// Resultado de um login OIDC bem-sucedido.
+#[derive(Debug, Clone)]
+pub struct OidcLoginOutcome {
+    pub user_id: Uuid,
+    pub username: String,
+    pub redirect_to: Option<String>,
+    /// Papel a registrar no Casbin quando a conta acabou de ser criada (JIT)
+    pub provisioned_role: Option<String>,
+}
+
+pub struct OidcService {
+    oidc_repo: Arc<dyn OidcRepositoryPort>,
+    user_repo: Arc<dyn UserRepositoryPort>,
+    client: Arc<OidcClient>,
+    providers: Vec<OidcProviderSettings>,
+}
+
+impl OidcService {
+    pub fn new(
+        oidc_repo: Arc<dyn OidcRepositoryPort>,
+        user_repo: Arc<dyn UserRepositoryPort>,
+        client: Arc<OidcClient>,
+        providers: Vec<OidcProviderSettings>,
+    ) -> Self {
+        Self {
+            oidc_repo,
+            user_repo,
+            client,
+            providers,
+        }
+    }
+
+    pub fn list_providers(&self) -> Vec<OidcProviderDto> {
+        self.providers
+            .iter()
+            .map(|p| OidcProviderDto {
+                name: p.name.clone(),
+                display_name: p.display_name.clone(),
+            })
+            .collect()
+    }
+
+    fn provider(&self, name: &str) -> Result<&OidcProviderSettings, ServiceError> {
+        self.providers
+            .iter()
+            .find(|p| p.name == name)
+            .ok_or_else(|| ServiceError::NotFound(format!("Provedor OIDC '{}' não configurado", name)))
+    }
+
+    /// Inicia o fluxo Authorization Code + PKCE, persistindo `state`, `nonce`
+    /// e `code_verifier` para conferência no callback. O `state` devolvido
+    /// deve ser vinculado ao navegador (cookie) pelo chamador.
+    pub async fn begin_login(
+        &self,
+        provider_name: &str,
+        redirect_to: Option<String>,
+    ) -> Result<OidcAuthorizationDto, ServiceError> {
+        let provider = self.provider(provider_name)?;
+        let redirect_to = match redirect_to {
+            Some(path) => Some(validate_redirect_path(&path)?),
+            None => None,
+        };
+
+        let discovery = self.client.discover(&provider.issuer).await?;
+
+        let login_state = OidcLoginState {
+            state: random_token(),
+            provider: provider.name.clone(),
+            nonce: random_token(),
+            code_verifier: random_token(),
+            redirect_to,
+            expires_at: Utc::now() + Duration::minutes(LOGIN_STATE_EXPIRY_MINUTES),
+        };
+        self.oidc_repo.save_login_state(&login_state).await?;
+
+        let scope = provider.scopes.join(" ");
+        let challenge = pkce_challenge(&login_state.code_verifier);
+        let url = reqwest::Url::parse_with_params(
+            &discovery.authorization_endpoint,
+            &[
+                ("response_type", "code"),
+                ("client_id", provider.client_id.as_str()),
+                ("redirect_uri", provider.redirect_uri.as_str()),
+                ("scope", scope.as_str()),
+                ("state", login_state.state.as_str()),
+                ("nonce", login_state.nonce.as_str()),
+                ("code_challenge", challenge.as_str()),
+                ("code_challenge_method", "S256"),
+            ],
+        )
+        .map_err(|e| ServiceError::Internal(format!("authorization_endpoint inválido: {}", e)))?;
+
+        Ok(OidcAuthorizationDto {
+            provider: provider.name.clone(),
+            authorization_url: url.to_string(),
+            state: login_state.state,
+            expires_at: login_state.expires_at,
+        })
+    }
+
+    /// Conclui o login: consome o `state`, troca o code, valida o ID token e
+    /// resolve o usuário local (vínculo existente → e-mail verificado → CPF →
+    /// JIT, se habilitado).
+    pub async fn complete_login(
+        &self,
+        provider_name: &str,
+        code: &str,
+        state: &str,
+    ) -> Result<OidcLoginOutcome, ServiceError> {
+        let provider = self.provider(provider_name)?;
+
+        let login_state = self
+            .oidc_repo
+            .take_login_state(state)
+            .await?
+            .filter(|s| s.provider == provider.name && s.expires_at > Utc::now())
+            .ok_or_else(|| ServiceError::BadRequest("Estado de autenticação inválido ou expirado".to_string()))?;
+
+        let discovery = self.client.discover(&provider.issuer).await?;
+        let tokens = self
+            .client
+            .exchange_code(
+                &discovery,
+                &provider.client_id,
+                provider.client_secret.as_deref(),
+                &provider.redirect_uri,
+                code,
+                &login_state.code_verifier,
+            )
+            .await
+            .map_err(|e| {
+                tracing::warn!(provider = %provider.name, "OIDC code exchange failed: {:#}", e);
+                ServiceError::InvalidCredentials
+            })?;
+
+        let claims = self
+            .client
+            .verify_id_token(&discovery, &provider.client_id, &tokens.id_token, &login_state.nonce)
+            .await
+            .map_err(|e| {
+                tracing::warn!(provider = %provider.name, "OIDC ID token rejected: {:#}", e);
+                ServiceError::InvalidCredentials
+            })?;
+
+        let (user_id, identity, provisioned_role) = self.resolve_user(provider, &claims).await?;
+
+        let user = self
+            .user_repo
+            .find_extended_by_id(user_id)
+            .await?
+            .ok_or(ServiceError::InvalidCredentials)?;
+        if user.is_banned {
+            tracing::warn!(user_id = %user_id, provider = %provider.name, "OIDC login by banned user");
+            return Err(ServiceError::InvalidCredentials);
+        }
+
+        self.oidc_repo.touch_identity(identity.id).await?;
+
+        tracing::info!(
+            user_id = %user_id,
+            provider = %provider.name,
+            link_method = ?identity.link_method,
+            "OIDC login"
+        );
+
+        Ok(OidcLoginOutcome {
+            user_id,
+            username: user.username.as_str().to_string(),
+            redirect_to: login_state.redirect_to,
+            provisioned_role,
+        })
+    }
+
+    pub async fn list_user_identities(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<UserIdentityDto>, ServiceError> {
+        Ok(self.oidc_repo.list_identities_by_user(user_id).await?)
+    }
+
+    pub async fn purge_expired_states(&self) -> Result<u64, ServiceError> {
+        Ok(self.oidc_repo.purge_expired_login_states().await?)
+    }
+
+    async fn resolve_user(
+        &self,
+        provider: &OidcProviderSettings,
+        claims: &IdTokenClaims,
+    ) -> Result<(Uuid, UserIdentityDto, Option<String>), ServiceError> {
+        if let Some(identity) = self.oidc_repo.find_identity(&provider.name, &claims.subject).await? {
+            return Ok((identity.user_id, identity, None));
+        }
+
+        let cpf = extract_cpf(claims, provider.cpf_claim.as_deref());
+
+        // E-mail só é usado para vínculo quando o IdP afirma tê-lo verificado
+        if claims.email_verified {
+            if let Some(email) = claims.email.clone().and_then(|e| Email::try_from(e).ok()) {
+                if let Some(user) = self.user_repo.find_by_email(&email).await? {
+                    let identity = self
+                        .link(user.id, provider, claims, &IdentityLinkMethod::Email)
+                        .await?;
+                    return Ok((user.id, identity, None));
+                }
+            }
+        }
+
+        if let Some(cpf) = &cpf {
+            if let Some(user_id) = self.oidc_repo.find_user_id_by_cpf(cpf).await? {
+                let identity = self
+                    .link(user_id, provider, claims, &IdentityLinkMethod::Cpf)
+                    .await?;
+                return Ok((user_id, identity, None));
+            }
+        }
+
+        if !provider.jit_provisioning {
+            tracing::warn!(provider = %provider.name, "OIDC login without matching local account");
+            return Err(ServiceError::InvalidCredentials);
+        }
+
+        let identity = self.provision_user(provider, claims, cpf).await?;
+        Ok((identity.user_id, identity, Some(provider.default_role.clone())))
+    }
+
+    async fn link(
+        &self,
+        user_id: Uuid,
+        provider: &OidcProviderSettings,
+        claims: &IdTokenClaims,
+        method: &IdentityLinkMethod,
+    ) -> Result<UserIdentityDto, ServiceError> {
+        self.oidc_repo
+            .create_identity(user_id, &provider.name, &claims.subject, method)
+            .await
+            .map_err(|e| match ServiceError::from(e) {
+                ServiceError::Conflict(_) => ServiceError::Conflict(format!(
+                    "Usuário já possui outra identidade vinculada ao provedor '{}'",
+                    provider.name
+                )),
+                other => other,
+            })
+    }
+
+    /// Cria a conta local (JIT) já vinculada à identidade, numa única
+    /// transação. A senha é aleatória e descartada: o acesso
+    /// por senha só passa a existir após redefinição pelo próprio usuário.
+    async fn provision_user(
+        &self,
+        provider: &OidcProviderSettings,
+        claims: &IdTokenClaims,
+        cpf: Option<String>,
+    ) -> Result<UserIdentityDto, ServiceError> {
+        let email = claims
+            .email
+            .clone()
+            .and_then(|e| Email::try_from(e).ok())
+            .ok_or_else(|| {
+                ServiceError::BadRequest(
+                    "O provedor não informou um e-mail válido para criar a conta".to_string(),
+                )
+            })?;
+        if self.user_repo.exists_by_email(&email).await? {
+            // E-mail não verificado pelo IdP pertencente a outra conta: não vincula
+            return Err(ServiceError::Conflict(
+                "Já existe uma conta com este e-mail; faça login com senha para vinculá-la".to_string(),
+            ));
+        }
+
+        let base = username_candidate(claims, &email);
+        let mut username = None;
+        for attempt in 0..5 {
+            let candidate = if attempt == 0 {
+                base.clone()
+            } else {
+                let suffix = hex_suffix();
+                format!("{}_{}", truncate(&base, 50 - suffix.len() - 1), suffix)
+            };
+            let parsed = Username::try_from(candidate)
+                .map_err(|e| ServiceError::Internal(format!("Nome de usuário gerado inválido: {}", e)))?;
+            if !self.user_repo.exists_by_username(&parsed).await? {
+                username = Some(parsed);
+                break;
+            }
+        }
+        let username = username
+            .ok_or_else(|| ServiceError::Internal("Não foi possível gerar nome de usuário".to_string()))?;
+
+        let unusable_password = URL_SAFE_NO_PAD.encode(generate_token(32));
+        let password_hash =
+            hash_password(&unusable_password).map_err(|e| ServiceError::Internal(e.to_string()))?;
+
+        let identity = self
+            .oidc_repo
+            .provision_account(&NewOidcAccount {
+                username,
+                email,
+                email_verified: claims.email_verified,
+                password_hash,
+                role: provider.default_role.clone(),
+                cpf,
+                provider: provider.name.clone(),
+                subject: claims.subject.clone(),
+            })
+            .await?;
+
+        tracing::info!(
+            user_id = %identity.user_id,
+            provider = %provider.name,
+            role = %provider.default_role,
+            "OIDC just-in-time provisioning"
+        );
+        Ok(identity)
+    }
+}
+
+/// 32 bytes aleatórios em base64url (43 caracteres, válido como verifier PKCE).
+fn random_token() -> String {
+    URL_SAFE_NO_PAD.encode(generate_token(32))
+}
+
+fn hex_suffix() -> String {
+    generate_token(3).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// `code_challenge` S256 (RFC 7636 §4.2).
+pub fn pkce_challenge(verifier: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()))
+}
+
+/// Aceita apenas caminhos relativos à própria aplicação, evitando open redirect.
+pub fn validate_redirect_path(path: &str) -> Result<String, ServiceError> {
+    let path = path.trim();
+    let valid = path.starts_with('/')
+        && !path.starts_with("//")
+        && !path.starts_with("/\\")
+        && !path.chars().any(|c| c.is_control());
+    if valid {
+        Ok(path.to_string())
+    } else {
+        Err(ServiceError::BadRequest(
+            "redirect_to deve ser um caminho relativo (ex.: /frota)".to_string(),
+        ))
+    }
+}
+
+/// CPF do claim configurado, somente se tiver dígitos verificadores válidos.
+pub fn extract_cpf(claims: &IdTokenClaims, cpf_claim: Option<&str>) -> Option<String> {
+    let raw = claims.string_claim(cpf_claim?)?;
+    let cpf = normalize_document(&raw);
+    validate_cpf(&cpf).ok().map(|_| cpf)
+}
+
+fn username_candidate(claims: &IdTokenClaims, email: &Email) -> String {
+    let source = claims
+        .preferred_username
+        .clone()
+        .unwrap_or_else(|| email.as_str().split('@').next().unwrap_or_default().to_string());
+    let sanitized: String = source
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' || c == '-' { c } else { '_' })
+        .collect();
+    let sanitized = truncate(&sanitized, 50).to_string();
+    if sanitized.len() < 3 {
+        format!("user_{}", hex_suffix())
+    } else {
+        sanitized
+    }
+}
+
+fn truncate(s: &str, max: usize) -> &str {
+    // `s` contém apenas ASCII após a sanitização
+    &s[..s.len().min(max)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn claims(raw: serde_json::Value) -> IdTokenClaims {
+        let raw: HashMap<String, serde_json::Value> = serde_json::from_value(raw).unwrap();
+        IdTokenClaims {
+            subject: raw["sub"].as_str().unwrap().to_string(),
+            email: raw.get("email").and_then(|v| v.as_str()).map(str::to_string),
+            email_verified: false,
+            name: None,
+            preferred_username: raw
+                .get("preferred_username")
+                .and_then(|v| v.as_str())
+                .map(str::to_string),
+            raw,
+        }
+    }
+
+    #[test]
+    fn pkce_challenge_matches_rfc7636_example() {
+        // RFC 7636, Apêndice B
+        assert_eq!(
+            pkce_challenge("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk"),
+            "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"
+        );
+    }
+
+    #[test]
+    fn redirect_path_rejects_external_targets() {
+        assert_eq!(validate_redirect_path("/frota?aba=1").unwrap(), "/frota?aba=1");
+        assert!(validate_redirect_path("https://evil.example").is_err());
+        assert!(validate_redirect_path("//evil.example").is_err());
+        assert!(validate_redirect_path("/\\evil.example").is_err());
+        assert!(validate_redirect_path("frota").is_err());
+    }
+
+    #[test]
+    fn cpf_claim_is_normalized_and_validated() {
+        let c = claims(serde_json::json!({ "sub": "529.982.247-25" }));
+        assert_eq!(extract_cpf(&c, Some("sub")), Some("52998224725".to_string()));
+        assert_eq!(extract_cpf(&c, None), None);
+
+        let invalid = claims(serde_json::json!({ "sub": "12345678900" }));
+        assert_eq!(extract_cpf(&invalid, Some("sub")), None);
+    }
+
+    #[test]
+    fn username_candidate_is_sanitized() {
+        let email = Email::try_from("maria.silva@ufmt.br".to_string()).unwrap();
+        let c = claims(serde_json::json!({ "sub": "x" }));
+        assert_eq!(username_candidate(&c, &email), "maria_silva");
+
+        let c = claims(serde_json::json!({ "sub": "x", "preferred_username": "jo" }));
+        let name = username_candidate(&c, &email);
+        assert!(name.starts_with("user_") && name.len() == 11);
+    }
+}
@@ -1,4 +1,6 @@
 use crate::errors::ServiceError;
+use chrono::NaiveDate;
+use domain::errors::RepositoryError;
 use domain::models::organizational::*;
 use domain::ports::{
     OrganizationRepositoryPort, OrganizationalUnitCategoryRepositoryPort,
@@ -461,25 +463,68 @@ impl OrganizationalUnitService {
             .await?)
     }
 
+    /// Current hierarchy, or the one in effect on `as_of`
     pub async fn get_tree(
         &self,
         organization_id: Option<Uuid>,
+        as_of: Option<NaiveDate>,
     ) -> Result<Vec<OrganizationalUnitTreeNode>, ServiceError> {
-        Ok(self.unit_repository.get_tree(organization_id).await?)
+        Ok(self.unit_repository.get_tree(organization_id, as_of).await?)
+    }
+
+    /// IDs of the unit and all of its descendants, taken from `get_tree`
+    pub async fn get_subtree_ids(&self, root_id: Uuid) -> Result<Vec<Uuid>, ServiceError> {
+        let root = self.get(root_id).await?;
+        let tree = self.get_tree(Some(root.organization_id), None).await?;
+
+        fn find(nodes: &[OrganizationalUnitTreeNode], id: Uuid) -> Option<&OrganizationalUnitTreeNode> {
+            nodes.iter().find_map(|node| {
+                if node.unit.id == id {
+                    Some(node)
+                } else {
+                    find(&node.children, id)
+                }
+            })
+        }
+
+        fn collect(node: &OrganizationalUnitTreeNode, ids: &mut Vec<Uuid>) {
+            ids.push(node.unit.id);
+            for child in &node.children {
+                collect(child, ids);
+            }
+        }
+
+        let mut ids = Vec::new();
+        match find(&tree, root_id) {
+            Some(node) => collect(node, &mut ids),
+            None => ids.push(root_id),
+        }
+        Ok(ids)
     }
 
     pub async fn get_children(
         &self,
         parent_id: Uuid,
+        as_of: Option<NaiveDate>,
     ) -> Result<Vec<OrganizationalUnitDto>, ServiceError> {
-        Ok(self.unit_repository.get_children(parent_id).await?)
+        Ok(self.unit_repository.get_children(parent_id, as_of).await?)
     }
 
     pub async fn get_path_to_root(
         &self,
         id: Uuid,
+        as_of: Option<NaiveDate>,
     ) -> Result<Vec<OrganizationalUnitDto>, ServiceError> {
-        Ok(self.unit_repository.get_path_to_root(id).await?)
+        self.unit_repository
+            .get_path_to_root(id, as_of)
+            .await
+            .map_err(|e| match e {
+                RepositoryError::NotFound => ServiceError::NotFound(match as_of {
+                    Some(date) => format!("Organizational unit {} did not exist on {}", id, date),
+                    None => format!("Organizational unit {} not found", id),
+                }),
+                e => ServiceError::from(e),
+            })
     }
 
     pub async fn create(
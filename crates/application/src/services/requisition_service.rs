@@ -1,7 +1,13 @@
 use crate::errors::ServiceError;
+use crate::services::approval_chain_service::ApprovalChainService;
+use crate::services::delegation_service::DelegationService;
 use crate::services::stock_movement_service::{ProcessMovementInput, StockMovementService, StockMovementType};
 use domain::{
     models::requisition::*,
+    models::{
+        ApprovalDocumentContext, ApprovalDocumentType, ApprovalProgress, DelegatedAuthority,
+        DelegationScope, DocumentApprovalStepDto, UnitScope,
+    },
     ports::requisition::*,
 };
 use rust_decimal::Decimal;
@@ -18,6 +24,8 @@ pub struct RequisitionService {
     requisition_repo: Arc<dyn RequisitionRepositoryPort>,
     item_repo: Arc<dyn RequisitionItemRepositoryPort>,
     stock_movement_service: Arc<StockMovementService>,
+    delegation_service: Arc<DelegationService>,
+    approval_chain_service: Arc<ApprovalChainService>,
 }
 
 impl RequisitionService {
@@ -26,12 +34,16 @@ impl RequisitionService {
         requisition_repo: Arc<dyn RequisitionRepositoryPort>,
         item_repo: Arc<dyn RequisitionItemRepositoryPort>,
         stock_movement_service: Arc<StockMovementService>,
+        delegation_service: Arc<DelegationService>,
+        approval_chain_service: Arc<ApprovalChainService>,
     ) -> Self {
         Self {
             pool,
             requisition_repo,
             item_repo,
             stock_movement_service,
+            delegation_service,
+            approval_chain_service,
         }
     }
 
@@ -64,6 +76,91 @@ impl RequisitionService {
             .ok_or(ServiceError::NotFound("Requisição não encontrada".to_string()))
     }
 
+    /// Get a requisition by ID, hiding requisitions destined to units
+    /// outside the caller's scope
+    pub async fn get_requisition_in_scope(
+        &self,
+        id: Uuid,
+        scope: &UnitScope,
+    ) -> Result<RequisitionDto, ServiceError> {
+        let requisition = self.get_requisition(id).await?;
+        if !scope.contains(requisition.destination_unit_id) {
+            return Err(ServiceError::NotFound("Requisição não encontrada".to_string()));
+        }
+        Ok(requisition)
+    }
+
+    /// Like `get_requisition_in_scope`, without loading the requisition for
+    /// global roles (its history outlives it)
+    async fn ensure_visible(&self, id: Uuid, scope: &UnitScope) -> Result<(), ServiceError> {
+        if !matches!(scope, UnitScope::All) {
+            self.get_requisition_in_scope(id, scope).await?;
+        }
+        Ok(())
+    }
+
+    fn ensure_in_scope(requisition: &RequisitionDto, scope: &UnitScope) -> Result<(), ServiceError> {
+        if !scope.contains(requisition.destination_unit_id) {
+            return Err(ServiceError::Forbidden(
+                "Requisição de unidade fora do seu escopo de atuação".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Checks the caller may decide on the requisition, either with their own
+    /// authority or with a delegation still in force. Own authority wins;
+    /// returns the delegator when the decision is taken on someone else's behalf.
+    async fn resolve_decision_authority(
+        &self,
+        requisition: &RequisitionDto,
+        ctx: &AuditContext,
+        scope: &UnitScope,
+        delegated: Option<&DelegatedAuthority>,
+    ) -> Result<Option<Uuid>, ServiceError> {
+        let Some(delegated) = delegated.filter(|_| !scope.contains(requisition.destination_unit_id))
+        else {
+            Self::ensure_in_scope(requisition, scope)?;
+            return Ok(None);
+        };
+
+        let delegator_id = delegated
+            .delegator_for(requisition.destination_unit_id)
+            .ok_or_else(|| {
+                ServiceError::Forbidden(
+                    "Requisição de unidade fora do escopo da delegação".to_string(),
+                )
+            })?;
+        self.delegation_service
+            .ensure_active(delegator_id, ctx.user_id, DelegationScope::Requisition)
+            .await?;
+        Ok(Some(delegator_id))
+    }
+
+    fn approval_document(requisition: &RequisitionDto) -> ApprovalDocumentContext {
+        ApprovalDocumentContext {
+            document_type: ApprovalDocumentType::Requisition,
+            document_id: requisition.id,
+            value: requisition.total_value.unwrap_or(Decimal::ZERO),
+            priority: Some(requisition.priority),
+            warehouse_id: Some(requisition.warehouse_id),
+            destination_unit_id: requisition.destination_unit_id,
+        }
+    }
+
+    /// Steps of the approval chain applied to the requisition (empty when
+    /// the single approve step decides)
+    pub async fn get_approval_steps(
+        &self,
+        id: Uuid,
+        scope: &UnitScope,
+    ) -> Result<Vec<DocumentApprovalStepDto>, ServiceError> {
+        let requisition = self.get_requisition_in_scope(id, scope).await?;
+        self.approval_chain_service
+            .steps_for(&Self::approval_document(&requisition))
+            .await
+    }
+
     /// Get a requisition by number
     pub async fn get_requisition_by_number(
         &self,
@@ -75,7 +172,9 @@ impl RequisitionService {
             .ok_or(ServiceError::NotFound("Requisição não encontrada".to_string()))
     }
 
-    /// Approve a requisition. Atomically:
+    /// Approve a requisition. When an approval chain applies, records the
+    /// caller's step and keeps the requisition PENDING until the chain
+    /// completes. Then, atomically:
     /// 1. Updates status to APPROVED
     /// 2. Inserts stock_reservations per item (replaces fn_manage_stock_reservation)
     /// 3. Updates warehouse_stocks.reserved_quantity
@@ -84,8 +183,13 @@ impl RequisitionService {
         id: Uuid,
         ctx: &AuditContext,
         payload: ApproveRequisitionPayload,
+        scope: &UnitScope,
+        delegated: Option<&DelegatedAuthority>,
     ) -> Result<RequisitionDto, ServiceError> {
         let requisition = self.get_requisition(id).await?;
+        let on_behalf_of = self
+            .resolve_decision_authority(&requisition, ctx, scope, delegated)
+            .await?;
 
         if requisition.status != RequisitionStatus::Pending {
             return Err(ServiceError::BadRequest(format!(
@@ -94,6 +198,20 @@ impl RequisitionService {
             )));
         }
 
+        let progress = self
+            .approval_chain_service
+            .decide(
+                &Self::approval_document(&requisition),
+                ctx.user_id,
+                on_behalf_of,
+                true,
+                payload.notes.as_deref(),
+            )
+            .await?;
+        if progress == ApprovalProgress::InProgress {
+            return Ok(requisition);
+        }
+
         let mut tx = self
             .pool
             .begin()
@@ -115,6 +233,7 @@ impl RequisitionService {
                 approved_by = $2,
                 approved_at = NOW(),
                 internal_notes = COALESCE($3, internal_notes),
+                approved_on_behalf_of = $4,
                 updated_at = NOW()
                WHERE id = $1
                RETURNING *"#,
@@ -122,6 +241,7 @@ impl RequisitionService {
         .bind(id)
         .bind(ctx.user_id)
         .bind(payload.notes.as_deref())
+        .bind(on_behalf_of)
         .fetch_one(&mut *tx)
         .await
         .map_err(|e| ServiceError::Internal(e.to_string()))?;
@@ -188,9 +308,14 @@ impl RequisitionService {
         id: Uuid,
         ctx: &AuditContext,
         payload: RejectRequisitionPayload,
+        scope: &UnitScope,
+        delegated: Option<&DelegatedAuthority>,
     ) -> Result<RequisitionDto, ServiceError> {
         // Verify requisition exists and is in pending status
         let requisition = self.get_requisition(id).await?;
+        let on_behalf_of = self
+            .resolve_decision_authority(&requisition, ctx, scope, delegated)
+            .await?;
 
         if requisition.status != RequisitionStatus::Pending {
             return Err(ServiceError::BadRequest(format!(
@@ -206,12 +331,23 @@ impl RequisitionService {
             ));
         }
 
+        // A rejection in any step of the chain rejects the requisition
+        self.approval_chain_service
+            .decide(
+                &Self::approval_document(&requisition),
+                ctx.user_id,
+                on_behalf_of,
+                false,
+                Some(&payload.reason),
+            )
+            .await?;
+
         // Set audit context
         self.set_audit_context(ctx).await?;
 
         // Reject
         self.requisition_repo
-            .reject(id, ctx.user_id, &payload.reason)
+            .reject(id, ctx.user_id, &payload.reason, on_behalf_of)
             .await
             .map_err(ServiceError::from)
     }
@@ -226,6 +362,7 @@ impl RequisitionService {
         id: Uuid,
         ctx: &AuditContext,
         payload: CancelRequisitionPayload,
+        scope: &UnitScope,
     ) -> Result<serde_json::Value, ServiceError> {
         if payload.reason.trim().is_empty() {
             return Err(ServiceError::BadRequest(
@@ -233,7 +370,7 @@ impl RequisitionService {
             ));
         }
 
-        let requisition = self.get_requisition(id).await?;
+        let requisition = self.get_requisition_in_scope(id, scope).await?;
 
         let cancellable = matches!(
             requisition.status,
@@ -354,6 +491,7 @@ impl RequisitionService {
         id: Uuid,
         ctx: &AuditContext,
         payload: RollbackPayload,
+        scope: &UnitScope,
     ) -> Result<serde_json::Value, ServiceError> {
         // Validate reason
         if payload.reason.trim().is_empty() {
@@ -361,6 +499,7 @@ impl RequisitionService {
                 "Justificativa é obrigatória para rollback".to_string(),
             ));
         }
+        self.ensure_visible(id, scope).await?;
 
         // The database function will handle all validations:
         // - Check if requisition exists
@@ -378,7 +517,7 @@ impl RequisitionService {
             })
     }
 
-    /// List requisitions with pagination and filters
+    /// List requisitions with pagination and filters, restricted to the caller's units
     pub async fn list_requisitions(
         &self,
         limit: i64,
@@ -386,9 +525,10 @@ impl RequisitionService {
         status: Option<RequisitionStatus>,
         requester_id: Option<Uuid>,
         warehouse_id: Option<Uuid>,
+        scope: &UnitScope,
     ) -> Result<(Vec<RequisitionDto>, i64), ServiceError> {
         self.requisition_repo
-            .list(limit, offset, status, requester_id, warehouse_id)
+            .list(limit, offset, status, requester_id, warehouse_id, scope.unit_ids())
             .await
             .map_err(ServiceError::from)
     }
@@ -402,9 +542,11 @@ impl RequisitionService {
         &self,
         id: Uuid,
         limit: Option<i64>,
+        scope: &UnitScope,
     ) -> Result<Vec<RequisitionHistoryEntry>, ServiceError> {
         // Verify requisition exists (or existed)
         // Note: We allow getting history even for deleted requisitions
+        self.ensure_visible(id, scope).await?;
 
         self.requisition_repo
             .get_history(id, limit.unwrap_or(50))
@@ -417,9 +559,10 @@ impl RequisitionService {
         &self,
         id: Uuid,
         limit: Option<i32>,
+        scope: &UnitScope,
     ) -> Result<Vec<RollbackPoint>, ServiceError> {
         // Verify requisition exists
-        let _ = self.get_requisition(id).await?;
+        let _ = self.get_requisition_in_scope(id, scope).await?;
 
         self.requisition_repo
             .get_rollback_points(id, limit.unwrap_or(20))
@@ -438,8 +581,9 @@ impl RequisitionService {
         id: Uuid,
         ctx: &AuditContext,
         payload: StartProcessingPayload,
+        scope: &UnitScope,
     ) -> Result<RequisitionDto, ServiceError> {
-        let requisition = self.get_requisition(id).await?;
+        let requisition = self.get_requisition_in_scope(id, scope).await?;
 
         if requisition.status != RequisitionStatus::Approved {
             return Err(ServiceError::BadRequest(format!(
@@ -495,8 +639,9 @@ impl RequisitionService {
         id: Uuid,
         ctx: &AuditContext,
         payload: FulfillRequisitionPayload,
+        scope: &UnitScope,
     ) -> Result<RequisitionDto, ServiceError> {
-        let requisition = self.get_requisition(id).await?;
+        let requisition = self.get_requisition_in_scope(id, scope).await?;
 
         if requisition.status != RequisitionStatus::Processing {
             return Err(ServiceError::BadRequest(format!(
@@ -792,6 +937,7 @@ impl RequisitionService {
         requisition_id: Uuid,
         payload: CreateRequisitionItemPayload,
         _user_id: Uuid,
+        scope: &UnitScope,
     ) -> Result<RequisitionItemDto, ServiceError> {
         if payload.requested_quantity <= Decimal::ZERO {
             return Err(ServiceError::BadRequest(
@@ -799,7 +945,7 @@ impl RequisitionService {
             ));
         }
 
-        let requisition = self.get_requisition(requisition_id).await?;
+        let requisition = self.get_requisition_in_scope(requisition_id, scope).await?;
 
         if !matches!(
             requisition.status,
@@ -876,9 +1022,10 @@ impl RequisitionService {
     pub async fn get_requisition_items(
         &self,
         requisition_id: Uuid,
+        scope: &UnitScope,
     ) -> Result<Vec<RequisitionItemDto>, ServiceError> {
         // Verify requisition exists
-        let _ = self.get_requisition(requisition_id).await?;
+        let _ = self.get_requisition_in_scope(requisition_id, scope).await?;
 
         self.item_repo
             .find_by_requisition_id(requisition_id)
@@ -886,12 +1033,29 @@ impl RequisitionService {
             .map_err(ServiceError::from)
     }
 
+    /// Item of the requisition, which must be in the caller's scope
+    async fn find_item_in_scope(
+        &self,
+        requisition_id: Uuid,
+        item_id: Uuid,
+        scope: &UnitScope,
+    ) -> Result<RequisitionItemDto, ServiceError> {
+        self.get_requisition_in_scope(requisition_id, scope).await?;
+        self.item_repo
+            .find_by_id(item_id)
+            .await?
+            .filter(|item| item.requisition_id == requisition_id)
+            .ok_or(ServiceError::NotFound("Item não encontrado".to_string()))
+    }
+
     /// Soft delete a requisition item
     pub async fn soft_delete_item(
         &self,
+        requisition_id: Uuid,
         item_id: Uuid,
         ctx: &AuditContext,
         reason: &str,
+        scope: &UnitScope,
     ) -> Result<(), ServiceError> {
         // Validate reason
         if reason.trim().is_empty() {
@@ -900,12 +1064,9 @@ impl RequisitionService {
             ));
         }
 
-        // Verify item exists
         let item = self
-            .item_repo
-            .find_by_id(item_id)
-            .await?
-            .ok_or(ServiceError::NotFound("Item não encontrado".to_string()))?;
+            .find_item_in_scope(requisition_id, item_id, scope)
+            .await?;
 
         // Verify item is not already deleted
         if item.deleted_at.is_some() {
@@ -927,15 +1088,14 @@ impl RequisitionService {
     /// Restore a soft-deleted requisition item
     pub async fn restore_item(
         &self,
+        requisition_id: Uuid,
         item_id: Uuid,
         ctx: &AuditContext,
+        scope: &UnitScope,
     ) -> Result<RequisitionItemDto, ServiceError> {
-        // Verify item exists
         let item = self
-            .item_repo
-            .find_by_id(item_id)
-            .await?
-            .ok_or(ServiceError::NotFound("Item não encontrado".to_string()))?;
+            .find_item_in_scope(requisition_id, item_id, scope)
+            .await?;
 
         // Verify item is deleted
         if item.deleted_at.is_none() {
@@ -985,6 +1145,7 @@ mod tests {
             needed_by: None,
             approved_by: None,
             approved_at: None,
+            approved_on_behalf_of: None,
             fulfilled_by: None,
             fulfilled_at: None,
             rejection_reason: None,
@@ -1,8 +1,14 @@
 use crate::errors::ServiceError;
+use crate::services::approval_chain_service::ApprovalChainService;
+use crate::services::delegation_service::DelegationService;
 use crate::services::stock_movement_service::{
     ProcessMovementInput, StockMovementService, StockMovementType,
 };
 use chrono::Utc;
+use domain::models::{
+    ApprovalDocumentContext, ApprovalDocumentType, ApprovalProgress, ApprovalStepDecisionPayload,
+    DelegatedAuthority, DelegationScope, DocumentApprovalStepDto, UnitScope,
+};
 use domain::models::warehouse::{
     CancelTransferPayload, ConfirmGovbrSignatureTransferPayload, ConfirmTransferPayload,
     InitiateTransferPayload, RejectTransferPayload, StockTransferDto, StockTransferItemDto,
@@ -16,13 +22,22 @@ use uuid::Uuid;
 pub struct StockTransferService {
     pool: PgPool,
     stock_movement_service: Arc<StockMovementService>,
+    delegation_service: Arc<DelegationService>,
+    approval_chain_service: Arc<ApprovalChainService>,
 }
 
 impl StockTransferService {
-    pub fn new(pool: PgPool, stock_movement_service: Arc<StockMovementService>) -> Self {
+    pub fn new(
+        pool: PgPool,
+        stock_movement_service: Arc<StockMovementService>,
+        delegation_service: Arc<DelegationService>,
+        approval_chain_service: Arc<ApprovalChainService>,
+    ) -> Self {
         Self {
             pool,
             stock_movement_service,
+            delegation_service,
+            approval_chain_service,
         }
     }
 
@@ -41,7 +56,8 @@ impl StockTransferService {
                 u.username AS initiated_by_name,
                 t.status,
                 t.notes, t.rejection_reason, t.cancellation_reason,
-                t.initiated_by, t.confirmed_by, t.rejected_by, t.cancelled_by,
+                t.initiated_by, t.confirmed_by, t.confirmed_on_behalf_of,
+                t.rejected_by, t.cancelled_by,
                 t.initiated_at, t.confirmed_at, t.rejected_at, t.cancelled_at,
                 t.expires_at, t.created_at, t.updated_at,
                 t.requires_govbr_signature,
@@ -84,7 +100,8 @@ impl StockTransferService {
                 u.username AS initiated_by_name,
                 t.status,
                 t.notes, t.rejection_reason, t.cancellation_reason,
-                t.initiated_by, t.confirmed_by, t.rejected_by, t.cancelled_by,
+                t.initiated_by, t.confirmed_by, t.confirmed_on_behalf_of,
+                t.rejected_by, t.cancelled_by,
                 t.initiated_at, t.confirmed_at, t.rejected_at, t.cancelled_at,
                 t.expires_at, t.created_at, t.updated_at,
                 t.requires_govbr_signature,
@@ -342,6 +359,124 @@ impl StockTransferService {
         self.get_transfer(transfer_id).await
     }
 
+    // ========================================================================
+    // APPROVAL CHAIN
+    // ========================================================================
+
+    async fn destination_unit(&self, transfer: &StockTransferDto) -> Result<Option<Uuid>, ServiceError> {
+        sqlx::query_scalar::<_, Option<Uuid>>(
+            "SELECT responsible_unit_id FROM warehouses WHERE id = $1",
+        )
+        .bind(transfer.destination_warehouse_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map(Option::flatten)
+        .map_err(|e| ServiceError::Internal(e.to_string()))
+    }
+
+    /// Delegator whose authority the caller exercises, checked to still be in
+    /// force. `None` when the destination is within the caller's own scope.
+    async fn resolve_on_behalf_of(
+        &self,
+        transfer: &StockTransferDto,
+        user_id: Uuid,
+        scope: &UnitScope,
+        delegated: Option<&DelegatedAuthority>,
+    ) -> Result<Option<Uuid>, ServiceError> {
+        if matches!(scope, UnitScope::All) {
+            return Ok(None);
+        }
+        let destination_unit_id = self.destination_unit(transfer).await?;
+        if scope.contains(destination_unit_id) {
+            return Ok(None);
+        }
+        let Some(delegated) = delegated else {
+            return Err(ServiceError::Forbidden(
+                "Almoxarifado de destino fora do seu escopo de atuação".to_string(),
+            ));
+        };
+        let delegator_id = delegated.delegator_for(destination_unit_id).ok_or_else(|| {
+            ServiceError::Forbidden(
+                "Almoxarifado de destino fora do escopo da delegação".to_string(),
+            )
+        })?;
+        self.delegation_service
+            .ensure_active(delegator_id, user_id, DelegationScope::StockTransfer)
+            .await?;
+        Ok(Some(delegator_id))
+    }
+
+    /// Value of the transfer at the source cost of its items
+    async fn approval_document(
+        &self,
+        transfer: &StockTransferDto,
+    ) -> Result<ApprovalDocumentContext, ServiceError> {
+        let value: Decimal = sqlx::query_scalar(
+            r#"SELECT COALESCE(SUM(ti.quantity_requested * COALESCE(m.unit_price_base, 0)), 0)
+               FROM stock_transfer_items ti
+               LEFT JOIN stock_movements m ON m.id = ti.source_movement_id
+               WHERE ti.transfer_id = $1"#,
+        )
+        .bind(transfer.id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| ServiceError::Internal(e.to_string()))?;
+
+        Ok(ApprovalDocumentContext {
+            document_type: ApprovalDocumentType::StockTransfer,
+            document_id: transfer.id,
+            value,
+            priority: None,
+            warehouse_id: Some(transfer.source_warehouse_id),
+            destination_unit_id: self.destination_unit(transfer).await?,
+        })
+    }
+
+    pub async fn get_approval_steps(
+        &self,
+        transfer_id: Uuid,
+    ) -> Result<Vec<DocumentApprovalStepDto>, ServiceError> {
+        let transfer = self.get_transfer(transfer_id).await?;
+        self.approval_chain_service
+            .steps_for(&self.approval_document(&transfer.transfer).await?)
+            .await
+    }
+
+    /// Approves the caller's step of the transfer's approval chain. The
+    /// destination can only confirm receipt once every step is approved.
+    pub async fn approve_transfer_step(
+        &self,
+        transfer_id: Uuid,
+        payload: ApprovalStepDecisionPayload,
+        approved_by: Uuid,
+        scope: &UnitScope,
+        delegated: Option<&DelegatedAuthority>,
+    ) -> Result<Vec<DocumentApprovalStepDto>, ServiceError> {
+        let transfer = self.get_transfer(transfer_id).await?;
+        let on_behalf_of = self
+            .resolve_on_behalf_of(&transfer.transfer, approved_by, scope, delegated)
+            .await?;
+
+        if transfer.transfer.status != StockTransferStatus::Pending {
+            return Err(ServiceError::BadRequest(format!(
+                "Transferência não pode ser aprovada. Status atual: {:?}",
+                transfer.transfer.status
+            )));
+        }
+
+        let document = self.approval_document(&transfer.transfer).await?;
+        let progress = self
+            .approval_chain_service
+            .decide(&document, approved_by, on_behalf_of, true, payload.comments.as_deref())
+            .await?;
+        if progress == ApprovalProgress::NoChain {
+            return Err(ServiceError::BadRequest(
+                "Nenhuma cadeia de aprovação se aplica a esta transferência".to_string(),
+            ));
+        }
+        self.approval_chain_service.steps_for(&document).await
+    }
+
     // ========================================================================
     // CONFIRM (Step 2a)
     // ========================================================================
@@ -353,9 +488,15 @@ impl StockTransferService {
         transfer_id: Uuid,
         payload: ConfirmTransferPayload,
         confirmed_by: Uuid,
+        scope: &UnitScope,
+        delegated: Option<&DelegatedAuthority>,
     ) -> Result<StockTransferWithItemsDto, ServiceError> {
         let transfer = self.get_transfer(transfer_id).await?;
 
+        let on_behalf_of = self
+            .resolve_on_behalf_of(&transfer.transfer, confirmed_by, scope, delegated)
+            .await?;
+
         if transfer.transfer.status != StockTransferStatus::Pending {
             return Err(ServiceError::BadRequest(format!(
                 "Transferência não pode ser confirmada. Status atual: {:?}",
@@ -371,6 +512,10 @@ impl StockTransferService {
             }
         }
 
+        self.approval_chain_service
+            .ensure_completed(&self.approval_document(&transfer.transfer).await?)
+            .await?;
+
         let requires_govbr = transfer.transfer.requires_govbr_signature;
 
         let mut tx = self
@@ -511,12 +656,14 @@ impl StockTransferService {
                 status = $3,
                 confirmed_by = $2,
                 confirmed_at = NOW(),
+                confirmed_on_behalf_of = $4,
                 updated_at = NOW()
                WHERE id = $1"#,
         )
         .bind(transfer_id)
         .bind(confirmed_by)
         .bind(next_status)
+        .bind(on_behalf_of)
         .execute(&mut *tx)
         .await
         .map_err(|e| ServiceError::Internal(e.to_string()))?;
@@ -1,18 +1,39 @@
 use crate::errors::ServiceError;
-use chrono::Utc;
+use crate::services::delegation_service::DelegationService;
+use crate::services::fleet_report_service::FleetReportService;
+use chrono::{DateTime, Duration, Utc};
 use domain::{
     models::trip::*,
     models::vehicle::{AllocationStatus, OperationalStatus},
     models::odometer::{FonteLeitura, StatusLeitura},
+    models::report::FuelConsumptionDto,
+    models::{DelegatedAuthority, DelegationScope, UnitScope},
     ports::driver::DriverRepositoryPort,
     ports::trip::VehicleTripRepositoryPort,
     ports::vehicle::{VehicleRepositoryPort, VehicleStatusHistoryRepositoryPort},
     ports::odometer::OdometerReadingRepositoryPort,
 };
+use rust_decimal::prelude::ToPrimitive;
 use rust_decimal::Decimal;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
 use uuid::Uuid;
 
+/// Largest calendar window accepted by the availability queries.
+const MAX_CALENDAR_WINDOW_DAYS: i64 = 92;
+
+/// Upper bound on the number of drivers listed by the driver calendar.
+const MAX_CALENDAR_DRIVERS: i64 = 500;
+
+/// Number of ranked vehicles kept per trip by the suggestion engine.
+const MAX_SUGGESTIONS: usize = 5;
+
+/// Look-back window for fuel efficiency and fleet usage balance.
+const SUGGESTION_USAGE_WINDOW_DAYS: i64 = 90;
+
+/// Distance to the next preventive maintenance that earns the full score.
+const PREVENTIVE_COMFORT_KM: f64 = 5_000.0;
+
 pub struct TripService {
     trip_repo: Arc<dyn VehicleTripRepositoryPort>,
     vehicle_repo: Arc<dyn VehicleRepositoryPort>,
@@ -20,6 +41,8 @@ pub struct TripService {
     odometer_repo: Arc<dyn OdometerReadingRepositoryPort>,
     #[allow(dead_code)]
     status_history_repo: Arc<dyn VehicleStatusHistoryRepositoryPort>,
+    fleet_report_service: Arc<FleetReportService>,
+    delegation_service: Arc<DelegationService>,
 }
 
 impl TripService {
@@ -29,8 +52,18 @@ impl TripService {
         driver_repo: Arc<dyn DriverRepositoryPort>,
         odometer_repo: Arc<dyn OdometerReadingRepositoryPort>,
         status_history_repo: Arc<dyn VehicleStatusHistoryRepositoryPort>,
+        fleet_report_service: Arc<FleetReportService>,
+        delegation_service: Arc<DelegationService>,
     ) -> Self {
-        Self { trip_repo, vehicle_repo, driver_repo, odometer_repo, status_history_repo }
+        Self {
+            trip_repo,
+            vehicle_repo,
+            driver_repo,
+            odometer_repo,
+            status_history_repo,
+            fleet_report_service,
+            delegation_service,
+        }
     }
 
     // ── RF-USO-01: Request trip ─────────────────────────────────────────────
@@ -76,6 +109,8 @@ impl TripService {
         trip_id: Uuid,
         payload: ReviewTripPayload,
         reviewer_id: Uuid,
+        scope: &UnitScope,
+        delegated: Option<&DelegatedAuthority>,
     ) -> Result<VehicleTripDto, ServiceError> {
         let trip = self.trip_repo
             .find_by_id(trip_id)
@@ -83,6 +118,29 @@ impl TripService {
             .map_err(ServiceError::from)?
             .ok_or_else(|| ServiceError::NotFound("Viagem não encontrada".to_string()))?;
 
+        // Competência própria prevalece; a delegação cobre o que está fora dela
+        let in_own_scope = self.trip_in_scope(&trip, scope).await?;
+        let on_behalf_of = match delegated {
+            _ if in_own_scope => None,
+            None => {
+                return Err(ServiceError::Forbidden(
+                    "Viagem de veículo fora do seu escopo de atuação".to_string(),
+                ));
+            }
+            Some(delegated) => {
+                let department_id = self.vehicle_department(trip.vehicle_id).await?;
+                let delegator_id = delegated.delegator_for(department_id).ok_or_else(|| {
+                    ServiceError::Forbidden(
+                        "Viagem de veículo fora do escopo da delegação".to_string(),
+                    )
+                })?;
+                self.delegation_service
+                    .ensure_active(delegator_id, reviewer_id, DelegationScope::Trip)
+                    .await?;
+                Some(delegator_id)
+            }
+        };
+
         if trip.status != TripStatus::Requested {
             return Err(ServiceError::BadRequest(
                 "Apenas viagens SOLICITADA podem ser revisadas".to_string(),
@@ -90,16 +148,24 @@ impl TripService {
         }
 
         if payload.approved {
-            self.trip_repo
-                .approve(trip_id, reviewer_id, payload.version)
+            let approved = self.trip_repo
+                .approve(trip_id, reviewer_id, on_behalf_of, payload.version)
                 .await
-                .map_err(ServiceError::from)
+                .map_err(ServiceError::from)?;
+
+            // RF-VIG-05: rank candidate vehicles right away. Approval stands
+            // even if the ranking fails — it can be regenerated on demand.
+            if let Err(e) = self.suggest_vehicles(trip_id, Some(reviewer_id), &UnitScope::All).await {
+                tracing::warn!(trip_id = %trip_id, error = %e, "Falha ao gerar sugestões de alocação");
+            }
+
+            Ok(approved)
         } else {
             let reason = payload.rejection_reason.ok_or_else(|| {
                 ServiceError::BadRequest("Motivo de rejeição obrigatório".to_string())
             })?;
             self.trip_repo
-                .reject(trip_id, &reason, reviewer_id, payload.version)
+                .reject(trip_id, &reason, reviewer_id, on_behalf_of, payload.version)
                 .await
                 .map_err(ServiceError::from)
         }
@@ -112,12 +178,9 @@ impl TripService {
         trip_id: Uuid,
         payload: AllocateTripPayload,
         allocator_id: Uuid,
+        scope: &UnitScope,
     ) -> Result<VehicleTripDto, ServiceError> {
-        let trip = self.trip_repo
-            .find_by_id(trip_id)
-            .await
-            .map_err(ServiceError::from)?
-            .ok_or_else(|| ServiceError::NotFound("Viagem não encontrada".to_string()))?;
+        let trip = self.get_trip_in_scope(trip_id, scope).await?;
 
         if trip.status != TripStatus::Approved {
             return Err(ServiceError::BadRequest(
@@ -125,40 +188,83 @@ impl TripService {
             ));
         }
 
+        // RF-VIG-05: a suggestion redirects the allocation to the ranked vehicle.
+        let suggestion = match payload.suggestion_id {
+            Some(suggestion_id) => {
+                let suggestion = self.trip_repo
+                    .find_suggestion(suggestion_id)
+                    .await
+                    .map_err(ServiceError::from)?
+                    .ok_or_else(|| {
+                        ServiceError::NotFound("Sugestão de alocação não encontrada".to_string())
+                    })?;
+                if suggestion.trip_id != trip_id {
+                    return Err(ServiceError::BadRequest(
+                        "Sugestão de alocação não pertence a esta viagem".to_string(),
+                    ));
+                }
+                Some(suggestion)
+            }
+            None => None,
+        };
+        let vehicle_id = suggestion.as_ref().map_or(trip.vehicle_id, |s| s.vehicle_id);
+
         let vehicle = self.vehicle_repo
-            .find_by_id(trip.vehicle_id)
+            .find_by_id(vehicle_id)
             .await
             .map_err(ServiceError::from)?
             .ok_or_else(|| ServiceError::NotFound("Veículo não encontrado".to_string()))?;
 
-        if vehicle.allocation_status != AllocationStatus::Livre {
-            return Err(ServiceError::Conflict(
-                "Veículo não está disponível (allocation_status ≠ LIVRE)".to_string(),
-            ));
-        }
-
         if vehicle.operational_status != OperationalStatus::Ativo {
             return Err(ServiceError::Conflict(
                 "Veículo inoperante — não pode ser alocado".to_string(),
             ));
         }
 
-        // Allocate trip with pessimistic vehicle lock (FOR UPDATE NOWAIT).
-        let allocated = self.trip_repo
-            .allocate(trip_id, trip.vehicle_id, payload.driver_id, allocator_id, payload.version)
-            .await
-            .map_err(ServiceError::from)?;
-
-        // Mark vehicle as reserved.
-        let _ = self.vehicle_repo
-            .change_allocation_status(
-                trip.vehicle_id,
-                AllocationStatus::Reservado,
-                vehicle.version,
-                Some(allocator_id),
+        // RF-VIG-03: availability is decided by the calendar, not by the
+        // current allocation_status — a vehicle reserved for next week can
+        // still take a trip today. The repository rejects overlaps with other
+        // allocated or in-progress trips (same vehicle or same driver) and
+        // with open maintenance orders while holding the vehicle/driver locks.
+        let window_end = trip.planned_return.unwrap_or(trip.planned_departure);
+        let allocated = match self.trip_repo
+            .allocate(
+                trip_id,
+                vehicle_id,
+                payload.driver_id,
+                trip.planned_departure,
+                window_end,
+                allocator_id,
+                payload.version,
             )
             .await
-            .map_err(ServiceError::from)?;
+            .map_err(ServiceError::from)?
+        {
+            TripAllocationOutcome::Allocated(trip) => *trip,
+            TripAllocationOutcome::Conflicts(conflicts) => {
+                return Err(ServiceError::Conflict(describe_conflicts(&conflicts)));
+            }
+        };
+
+        // Mark vehicle as reserved (unless it already is, or is in use).
+        if vehicle.allocation_status == AllocationStatus::Livre {
+            let _ = self.vehicle_repo
+                .change_allocation_status(
+                    vehicle_id,
+                    AllocationStatus::Reservado,
+                    vehicle.version,
+                    Some(allocator_id),
+                )
+                .await
+                .map_err(ServiceError::from)?;
+        }
+
+        if let Some(suggestion) = suggestion {
+            self.trip_repo
+                .mark_suggestion_accepted(suggestion.id)
+                .await
+                .map_err(ServiceError::from)?;
+        }
 
         Ok(allocated)
     }
@@ -170,12 +276,9 @@ impl TripService {
         trip_id: Uuid,
         payload: CheckoutPayload,
         user_id: Uuid,
+        scope: &UnitScope,
     ) -> Result<VehicleTripDto, ServiceError> {
-        let trip = self.trip_repo
-            .find_by_id(trip_id)
-            .await
-            .map_err(ServiceError::from)?
-            .ok_or_else(|| ServiceError::NotFound("Viagem não encontrada".to_string()))?;
+        let trip = self.get_trip_in_scope(trip_id, scope).await?;
 
         if trip.status != TripStatus::Allocated {
             return Err(ServiceError::BadRequest(
@@ -257,12 +360,9 @@ impl TripService {
         trip_id: Uuid,
         payload: CheckinPayload,
         user_id: Uuid,
+        scope: &UnitScope,
     ) -> Result<VehicleTripDto, ServiceError> {
-        let trip = self.trip_repo
-            .find_by_id(trip_id)
-            .await
-            .map_err(ServiceError::from)?
-            .ok_or_else(|| ServiceError::NotFound("Viagem não encontrada".to_string()))?;
+        let trip = self.get_trip_in_scope(trip_id, scope).await?;
 
         if trip.status != TripStatus::InProgress {
             return Err(ServiceError::BadRequest(
@@ -295,11 +395,17 @@ impl TripService {
             .await
             .map_err(ServiceError::from)?;
 
-        // allocation_status → LIVRE (OCC on vehicle)
+        // allocation_status → LIVRE, or RESERVADO when later trips are
+        // already allocated to this vehicle (OCC on vehicle)
+        let next_status = if self.allocated_trip_count(trip.vehicle_id).await? > 0 {
+            AllocationStatus::Reservado
+        } else {
+            AllocationStatus::Livre
+        };
         let _ = self.vehicle_repo
             .change_allocation_status(
                 trip.vehicle_id,
-                AllocationStatus::Livre,
+                next_status,
                 payload.vehicle_version,
                 Some(user_id),
             )
@@ -326,12 +432,9 @@ impl TripService {
         trip_id: Uuid,
         payload: FinalizeTripPayload,
         user_id: Uuid,
+        scope: &UnitScope,
     ) -> Result<VehicleTripDto, ServiceError> {
-        let trip = self.trip_repo
-            .find_by_id(trip_id)
-            .await
-            .map_err(ServiceError::from)?
-            .ok_or_else(|| ServiceError::NotFound("Viagem não encontrada".to_string()))?;
+        let trip = self.get_trip_in_scope(trip_id, scope).await?;
 
         if trip.status != TripStatus::AwaitingAccounting {
             return Err(ServiceError::BadRequest(
@@ -352,12 +455,9 @@ impl TripService {
         trip_id: Uuid,
         payload: SetConflictPayload,
         user_id: Uuid,
+        scope: &UnitScope,
     ) -> Result<VehicleTripDto, ServiceError> {
-        self.trip_repo
-            .find_by_id(trip_id)
-            .await
-            .map_err(ServiceError::from)?
-            .ok_or_else(|| ServiceError::NotFound("Viagem não encontrada".to_string()))?;
+        self.get_trip_in_scope(trip_id, scope).await?;
 
         self.trip_repo
             .set_conflict(trip_id, &payload.conflict_reason, user_id, payload.version)
@@ -372,12 +472,9 @@ impl TripService {
         trip_id: Uuid,
         payload: CancelTripPayload,
         user_id: Uuid,
+        scope: &UnitScope,
     ) -> Result<VehicleTripDto, ServiceError> {
-        let trip = self.trip_repo
-            .find_by_id(trip_id)
-            .await
-            .map_err(ServiceError::from)?
-            .ok_or_else(|| ServiceError::NotFound("Viagem não encontrada".to_string()))?;
+        let trip = self.get_trip_in_scope(trip_id, scope).await?;
 
         if !matches!(trip.status, TripStatus::Requested | TripStatus::Approved | TripStatus::Allocated) {
             return Err(ServiceError::BadRequest(
@@ -385,8 +482,10 @@ impl TripService {
             ));
         }
 
-        // If the vehicle was reserved, release it back to LIVRE.
-        if trip.status == TripStatus::Allocated {
+        // If the vehicle was reserved only for this trip, release it back to LIVRE.
+        if trip.status == TripStatus::Allocated
+            && self.allocated_trip_count(trip.vehicle_id).await? <= 1
+        {
             let vehicle = self.vehicle_repo
                 .find_by_id(trip.vehicle_id)
                 .await
@@ -423,9 +522,45 @@ impl TripService {
             .ok_or_else(|| ServiceError::NotFound("Viagem não encontrada".to_string()))
     }
 
+    /// Trips of vehicles allocated outside the caller's scope are reported
+    /// as not found
+    pub async fn get_trip_in_scope(
+        &self,
+        id: Uuid,
+        scope: &UnitScope,
+    ) -> Result<VehicleTripDto, ServiceError> {
+        let trip = self.get_trip(id).await?;
+        if !self.trip_in_scope(&trip, scope).await? {
+            return Err(ServiceError::NotFound("Viagem não encontrada".to_string()));
+        }
+        Ok(trip)
+    }
+
+    /// A trip belongs to the department its vehicle is allocated to
+    async fn trip_in_scope(
+        &self,
+        trip: &VehicleTripDto,
+        scope: &UnitScope,
+    ) -> Result<bool, ServiceError> {
+        if matches!(scope, UnitScope::All) {
+            return Ok(true);
+        }
+        let department_id = self.vehicle_department(trip.vehicle_id).await?;
+        Ok(scope.contains(department_id))
+    }
+
+    async fn vehicle_department(&self, vehicle_id: Uuid) -> Result<Option<Uuid>, ServiceError> {
+        Ok(self.vehicle_repo
+            .find_by_id(vehicle_id)
+            .await
+            .map_err(ServiceError::from)?
+            .and_then(|v| v.department_id))
+    }
+
     pub async fn list_trips(
         &self,
         filters: TripListFilters,
+        scope: &UnitScope,
     ) -> Result<(Vec<VehicleTripDto>, i64), ServiceError> {
         self.trip_repo
             .list(
@@ -434,8 +569,475 @@ impl TripService {
                 filters.status,
                 filters.limit.unwrap_or(50),
                 filters.offset.unwrap_or(0),
+                scope.unit_ids(),
             )
             .await
             .map_err(ServiceError::from)
     }
+
+    // ── RF-VIG-05: Vehicle suggestion engine ────────────────────────────────
+
+    /// Ranks the vehicles free over the trip window and stores the top
+    /// suggestions, replacing any previous ranking for the trip.
+    pub async fn suggest_vehicles(
+        &self,
+        trip_id: Uuid,
+        requested_by: Option<Uuid>,
+        scope: &UnitScope,
+    ) -> Result<Vec<TripAllocationSuggestionDto>, ServiceError> {
+        let trip = self.get_trip_in_scope(trip_id, scope).await?;
+
+        if trip.status != TripStatus::Approved {
+            return Err(ServiceError::BadRequest(
+                "Sugestões de alocação só podem ser geradas para viagens APROVADA".to_string(),
+            ));
+        }
+
+        let window_end = trip.planned_return.unwrap_or(trip.planned_departure);
+        let min_seats = (trip.passengers > 0).then_some(trip.passengers);
+        let candidates = self.trip_repo
+            .find_allocation_candidates(trip.planned_departure, window_end, min_seats, trip.vehicle_id)
+            .await
+            .map_err(ServiceError::from)?;
+
+        let now = Utc::now();
+        let usage: HashMap<Uuid, FuelConsumptionDto> = self.fleet_report_service
+            .fuel_consumption(None, Some(now - Duration::days(SUGGESTION_USAGE_WINDOW_DAYS)), Some(now))
+            .await?
+            .into_iter()
+            .map(|c| (c.vehicle_id, c))
+            .collect();
+
+        let ranked = rank_candidates(trip.passengers, &candidates, &usage);
+
+        self.trip_repo
+            .replace_suggestions(trip_id, &ranked, requested_by)
+            .await
+            .map_err(ServiceError::from)
+    }
+
+    pub async fn list_suggestions(
+        &self,
+        trip_id: Uuid,
+    ) -> Result<Vec<TripAllocationSuggestionDto>, ServiceError> {
+        self.get_trip(trip_id).await?;
+        self.trip_repo
+            .list_suggestions(trip_id)
+            .await
+            .map_err(ServiceError::from)
+    }
+
+    // ── RF-VIG-03: Calendar and availability ────────────────────────────────
+
+    /// Number of ALOCADA trips currently holding `vehicle_id`.
+    async fn allocated_trip_count(&self, vehicle_id: Uuid) -> Result<i64, ServiceError> {
+        let (_, total) = self.trip_repo
+            .list(Some(vehicle_id), None, Some(TripStatus::Allocated), 1, 0, None)
+            .await
+            .map_err(ServiceError::from)?;
+        Ok(total)
+    }
+
+    pub async fn vehicle_calendar(
+        &self,
+        query: TripCalendarQuery,
+    ) -> Result<Vec<VehicleScheduleDto>, ServiceError> {
+        validate_window(query.start, query.end)?;
+
+        let bookings = self.trip_repo
+            .list_bookings(query.start, query.end, query.vehicle_id, None)
+            .await
+            .map_err(ServiceError::from)?;
+
+        let mut schedules = group_by_vehicle(bookings);
+
+        // Vehicles with nothing booked are still part of the calendar.
+        let free = self.trip_repo
+            .find_available_vehicles(query.start, query.end, None, None)
+            .await
+            .map_err(ServiceError::from)?;
+        for vehicle in free {
+            if query.vehicle_id.is_some_and(|id| id != vehicle.vehicle_id) {
+                continue;
+            }
+            if schedules.iter().all(|s| s.vehicle_id != vehicle.vehicle_id) {
+                schedules.push(VehicleScheduleDto {
+                    vehicle_id: vehicle.vehicle_id,
+                    license_plate: Some(vehicle.license_plate),
+                    is_available: true,
+                    bookings: Vec::new(),
+                });
+            }
+        }
+
+        schedules.sort_by(|a, b| a.license_plate.cmp(&b.license_plate));
+        Ok(schedules)
+    }
+
+    pub async fn driver_calendar(
+        &self,
+        query: TripCalendarQuery,
+    ) -> Result<Vec<DriverScheduleDto>, ServiceError> {
+        validate_window(query.start, query.end)?;
+
+        let bookings = self.trip_repo
+            .list_bookings(query.start, query.end, None, query.driver_id)
+            .await
+            .map_err(ServiceError::from)?;
+
+        let mut schedules = group_by_driver(bookings);
+
+        let drivers = match query.driver_id {
+            Some(id) => self.driver_repo
+                .find_by_id(id)
+                .await
+                .map_err(ServiceError::from)?
+                .ok_or_else(|| ServiceError::NotFound("Condutor não encontrado".to_string()))
+                .map(|d| vec![d])?,
+            None => self.driver_repo
+                .list(MAX_CALENDAR_DRIVERS, 0, None, None, Some(true))
+                .await
+                .map_err(ServiceError::from)?
+                .0,
+        };
+
+        for driver in drivers {
+            if schedules.iter().all(|s| s.driver_id != driver.id) {
+                schedules.push(DriverScheduleDto {
+                    driver_id: driver.id,
+                    driver_name: Some(driver.full_name),
+                    is_available: true,
+                    bookings: Vec::new(),
+                });
+            }
+        }
+
+        schedules.sort_by(|a, b| a.driver_name.cmp(&b.driver_name));
+        Ok(schedules)
+    }
+
+    pub async fn find_available_vehicles(
+        &self,
+        query: AvailableVehiclesQuery,
+    ) -> Result<Vec<AvailableVehicleDto>, ServiceError> {
+        validate_window(query.start, query.end)?;
+
+        self.trip_repo
+            .find_available_vehicles(query.start, query.end, query.category_id, query.min_seats)
+            .await
+            .map_err(ServiceError::from)
+    }
+}
+
+/// Scores each candidate from 0 to 100 and returns the best ones, ranked.
+///
+/// Weights: seat fit 30, same category as requested 20, distance to the next
+/// preventive maintenance 20, fuel efficiency 15 and fleet usage balance 15.
+/// Efficiency and usage are normalised across the candidates themselves;
+/// missing data scores half of the criterion.
+fn rank_candidates(
+    passengers: i32,
+    candidates: &[AllocationCandidateDto],
+    usage: &HashMap<Uuid, FuelConsumptionDto>,
+) -> Vec<NewTripSuggestion> {
+    let consumption_of = |c: &AllocationCandidateDto| {
+        usage
+            .get(&c.vehicle_id)
+            .and_then(|u| u.avg_consumption_l100km)
+            .and_then(|d| d.to_f64())
+    };
+    let km_driven_of = |c: &AllocationCandidateDto| {
+        usage.get(&c.vehicle_id).and_then(|u| u.total_km_driven).unwrap_or(0)
+    };
+
+    let consumption_bounds = bounds(candidates.iter().filter_map(consumption_of));
+    let km_bounds = bounds(candidates.iter().map(|c| km_driven_of(c) as f64));
+
+    let mut scored: Vec<(f64, Vec<String>, &AllocationCandidateDto)> = candidates
+        .iter()
+        .map(|c| {
+            let mut score = 0.0;
+            let mut reasons = Vec::new();
+
+            match c.passenger_capacity {
+                Some(capacity) if capacity > 0 => {
+                    let needed = passengers.max(1) as f64;
+                    score += 30.0 * (needed / capacity as f64).min(1.0);
+                    reasons.push(format!(
+                        "Capacidade de {} lugares para {} passageiro(s)",
+                        capacity, passengers
+                    ));
+                }
+                _ => {
+                    score += 15.0;
+                    reasons.push("Capacidade do modelo não informada".to_string());
+                }
+            }
+
+            if c.same_category {
+                score += 20.0;
+                reasons.push(format!(
+                    "Mesma categoria do veículo solicitado ({})",
+                    c.category_name.as_deref().unwrap_or("sem nome")
+                ));
+            }
+
+            match c.km_to_preventive {
+                Some(km) if km <= 0 => {
+                    reasons.push(format!("Revisão preventiva vencida há {} km", -km));
+                }
+                Some(km) => {
+                    score += 20.0 * (km as f64 / PREVENTIVE_COMFORT_KM).min(1.0);
+                    reasons.push(format!("{} km até a próxima revisão preventiva", km));
+                }
+                None => score += 10.0,
+            }
+
+            match consumption_of(c) {
+                Some(l100km) => {
+                    score += 15.0 * inverse_normalized(l100km, consumption_bounds);
+                    reasons.push(format!("Consumo médio de {:.1} L/100km", l100km));
+                }
+                None => score += 7.5,
+            }
+
+            let km_driven = km_driven_of(c);
+            score += 15.0 * inverse_normalized(km_driven as f64, km_bounds);
+            reasons.push(format!(
+                "{} km rodados nos últimos {} dias",
+                km_driven, SUGGESTION_USAGE_WINDOW_DAYS
+            ));
+
+            ((score * 100.0).round() / 100.0, reasons, c)
+        })
+        .collect();
+
+    scored.sort_by(|a, b| {
+        b.0.partial_cmp(&a.0)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.2.license_plate.cmp(&b.2.license_plate))
+    });
+
+    scored
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .enumerate()
+        .map(|(i, (score, reasons, c))| NewTripSuggestion {
+            vehicle_id: c.vehicle_id,
+            rank: i as i32 + 1,
+            score,
+            reasons,
+        })
+        .collect()
+}
+
+fn bounds(values: impl Iterator<Item = f64>) -> Option<(f64, f64)> {
+    values.fold(None, |acc, v| match acc {
+        None => Some((v, v)),
+        Some((min, max)) => Some((min.min(v), max.max(v))),
+    })
+}
+
+/// 1.0 for the lowest value in `bounds`, 0.0 for the highest.
+fn inverse_normalized(value: f64, bounds: Option<(f64, f64)>) -> f64 {
+    match bounds {
+        Some((min, max)) if max - min > f64::EPSILON => (max - value) / (max - min),
+        _ => 1.0,
+    }
+}
+
+fn validate_window(start: DateTime<Utc>, end: DateTime<Utc>) -> Result<(), ServiceError> {
+    if end < start {
+        return Err(ServiceError::BadRequest(
+            "end deve ser posterior a start".to_string(),
+        ));
+    }
+    if end - start > Duration::days(MAX_CALENDAR_WINDOW_DAYS) {
+        return Err(ServiceError::BadRequest(format!(
+            "Janela máxima do calendário é de {} dias",
+            MAX_CALENDAR_WINDOW_DAYS
+        )));
+    }
+    Ok(())
+}
+
+fn describe_conflicts(conflicts: &[TripBookingDto]) -> String {
+    let details: Vec<String> = conflicts
+        .iter()
+        .map(|c| {
+            let origin = match c.kind {
+                BookingKind::Trip => "viagem",
+                BookingKind::Maintenance => "OS de manutenção",
+            };
+            format!("{} {} ({}) a partir de {}", origin, c.reference_id, c.status, c.starts_at)
+        })
+        .collect();
+    format!("Conflito de agenda: {}", details.join("; "))
+}
+
+fn group_by_vehicle(bookings: Vec<TripBookingDto>) -> Vec<VehicleScheduleDto> {
+    let mut grouped: BTreeMap<Uuid, VehicleScheduleDto> = BTreeMap::new();
+    for booking in bookings {
+        grouped
+            .entry(booking.vehicle_id)
+            .or_insert_with(|| VehicleScheduleDto {
+                vehicle_id: booking.vehicle_id,
+                license_plate: booking.license_plate.clone(),
+                is_available: false,
+                bookings: Vec::new(),
+            })
+            .bookings
+            .push(booking);
+    }
+    grouped.into_values().collect()
+}
+
+fn group_by_driver(bookings: Vec<TripBookingDto>) -> Vec<DriverScheduleDto> {
+    let mut grouped: BTreeMap<Uuid, DriverScheduleDto> = BTreeMap::new();
+    for booking in bookings {
+        let Some(driver_id) = booking.driver_id else { continue };
+        grouped
+            .entry(driver_id)
+            .or_insert_with(|| DriverScheduleDto {
+                driver_id,
+                driver_name: booking.driver_name.clone(),
+                is_available: false,
+                bookings: Vec::new(),
+            })
+            .bookings
+            .push(booking);
+    }
+    grouped.into_values().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn booking(kind: BookingKind, vehicle_id: Uuid, driver_id: Option<Uuid>) -> TripBookingDto {
+        TripBookingDto {
+            kind,
+            reference_id: Uuid::new_v4(),
+            vehicle_id,
+            license_plate: Some("ABC1D23".to_string()),
+            driver_id,
+            driver_name: driver_id.map(|_| "Fulano".to_string()),
+            starts_at: Utc::now(),
+            ends_at: None,
+            status: "ALOCADA".to_string(),
+            description: None,
+        }
+    }
+
+    #[test]
+    fn test_validate_window() {
+        let now = Utc::now();
+        assert!(validate_window(now, now).is_ok());
+        assert!(validate_window(now, now + Duration::days(7)).is_ok());
+        assert!(validate_window(now, now - Duration::hours(1)).is_err());
+        assert!(validate_window(now, now + Duration::days(MAX_CALENDAR_WINDOW_DAYS + 1)).is_err());
+    }
+
+    #[test]
+    fn test_group_by_vehicle_merges_trips_and_maintenance() {
+        let vehicle = Uuid::new_v4();
+        let other = Uuid::new_v4();
+        let schedules = group_by_vehicle(vec![
+            booking(BookingKind::Trip, vehicle, Some(Uuid::new_v4())),
+            booking(BookingKind::Maintenance, vehicle, None),
+            booking(BookingKind::Trip, other, None),
+        ]);
+
+        assert_eq!(schedules.len(), 2);
+        let s = schedules.iter().find(|s| s.vehicle_id == vehicle).unwrap();
+        assert_eq!(s.bookings.len(), 2);
+        assert!(!s.is_available);
+    }
+
+    #[test]
+    fn test_group_by_driver_skips_maintenance() {
+        let driver = Uuid::new_v4();
+        let schedules = group_by_driver(vec![
+            booking(BookingKind::Trip, Uuid::new_v4(), Some(driver)),
+            booking(BookingKind::Trip, Uuid::new_v4(), Some(driver)),
+            booking(BookingKind::Maintenance, Uuid::new_v4(), None),
+        ]);
+
+        assert_eq!(schedules.len(), 1);
+        assert_eq!(schedules[0].driver_id, driver);
+        assert_eq!(schedules[0].bookings.len(), 2);
+    }
+
+    #[test]
+    fn test_describe_conflicts_lists_every_booking() {
+        let vehicle = Uuid::new_v4();
+        let msg = describe_conflicts(&[
+            booking(BookingKind::Trip, vehicle, None),
+            booking(BookingKind::Maintenance, vehicle, None),
+        ]);
+        assert!(msg.contains("viagem"));
+        assert!(msg.contains("OS de manutenção"));
+    }
+
+    fn candidate(plate: &str, capacity: Option<i32>, km_to_preventive: Option<i64>, same_category: bool) -> AllocationCandidateDto {
+        AllocationCandidateDto {
+            vehicle_id: Uuid::new_v4(),
+            license_plate: plate.to_string(),
+            model_name: "Modelo".to_string(),
+            category_id: None,
+            category_name: Some("Sedan".to_string()),
+            passenger_capacity: capacity,
+            last_odometer_km: Some(10_000),
+            km_to_preventive,
+            same_category,
+        }
+    }
+
+    fn consumption(vehicle_id: Uuid, l100km: i64, km_driven: i64) -> FuelConsumptionDto {
+        FuelConsumptionDto {
+            vehicle_id,
+            license_plate: None,
+            total_fuelings: 1,
+            total_liters: None,
+            total_cost: None,
+            avg_consumption_l100km: Some(Decimal::from(l100km)),
+            cost_per_km: None,
+            total_km_driven: Some(km_driven),
+        }
+    }
+
+    #[test]
+    fn test_rank_candidates_prefers_best_fit() {
+        let fit = candidate("AAA1A11", Some(5), Some(8_000), true);
+        let bus = candidate("BBB2B22", Some(40), Some(8_000), false);
+        let overdue = candidate("CCC3C33", Some(5), Some(-200), true);
+
+        let mut usage = HashMap::new();
+        usage.insert(fit.vehicle_id, consumption(fit.vehicle_id, 8, 1_000));
+        usage.insert(bus.vehicle_id, consumption(bus.vehicle_id, 30, 500));
+        usage.insert(overdue.vehicle_id, consumption(overdue.vehicle_id, 8, 3_000));
+
+        let ranked = rank_candidates(4, &[bus.clone(), overdue.clone(), fit.clone()], &usage);
+
+        assert_eq!(ranked.len(), 3);
+        assert_eq!(ranked[0].vehicle_id, fit.vehicle_id);
+        assert_eq!(ranked[0].rank, 1);
+        assert_eq!(ranked[2].rank, 3);
+        assert!(ranked.windows(2).all(|w| w[0].score >= w[1].score));
+        let overdue_reasons = &ranked.iter().find(|r| r.vehicle_id == overdue.vehicle_id).unwrap().reasons;
+        assert!(overdue_reasons.iter().any(|r| r.contains("vencida")));
+    }
+
+    #[test]
+    fn test_rank_candidates_keeps_top_suggestions() {
+        let candidates: Vec<_> = (0..(MAX_SUGGESTIONS + 3))
+            .map(|i| candidate(&format!("PLT{i:04}"), Some(5), None, false))
+            .collect();
+        let ranked = rank_candidates(2, &candidates, &HashMap::new());
+
+        assert_eq!(ranked.len(), MAX_SUGGESTIONS);
+        // Equal scores fall back to license plate order.
+        assert_eq!(ranked[0].vehicle_id, candidates[0].vehicle_id);
+        assert!(ranked.iter().all(|r| r.score > 0.0 && r.score <= 100.0));
+    }
 }
@@ -0,0 +1,292 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use chrono::{NaiveDate, Utc};
+use domain::models::organizational::OrganizationalUnitTreeNode;
+use domain::models::report::{UnitCostDto, UnitCostNode, UnitCostReport};
+use domain::ports::report::UnitCostReportRepositoryPort;
+use domain::ports::OrganizationalUnitRepositoryPort;
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+use crate::errors::ServiceError;
+
+/// Rolls requisition and fleet costs up the organizational structure in
+/// effect on a chosen date, so past fiscal years can be reported by the
+/// units that existed back then.
+pub struct UnitCostReportService {
+    report_repo: Arc<dyn UnitCostReportRepositoryPort>,
+    unit_repo: Arc<dyn OrganizationalUnitRepositoryPort>,
+}
+
+impl UnitCostReportService {
+    pub fn new(
+        report_repo: Arc<dyn UnitCostReportRepositoryPort>,
+        unit_repo: Arc<dyn OrganizationalUnitRepositoryPort>,
+    ) -> Self {
+        Self {
+            report_repo,
+            unit_repo,
+        }
+    }
+
+    /// Valor das requisições atendidas por unidade de destino
+    pub async fn requisition_cost_by_unit(
+        &self,
+        organization_id: Option<Uuid>,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+        as_of: Option<NaiveDate>,
+    ) -> Result<UnitCostReport, ServiceError> {
+        let as_of = resolve_as_of(start_date, end_date, as_of)?;
+        let costs = self
+            .report_repo
+            .requisition_costs_by_unit(organization_id, start_date, end_date, as_of)
+            .await?;
+        self.build(organization_id, start_date, end_date, as_of, costs)
+            .await
+    }
+
+    /// Custo de abastecimentos e manutenções concluídas por departamento do
+    /// veículo na data de cada despesa
+    pub async fn fleet_cost_by_unit(
+        &self,
+        organization_id: Option<Uuid>,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+        as_of: Option<NaiveDate>,
+    ) -> Result<UnitCostReport, ServiceError> {
+        let as_of = resolve_as_of(start_date, end_date, as_of)?;
+        let costs = self
+            .report_repo
+            .fleet_costs_by_unit(organization_id, start_date, end_date, as_of)
+            .await?;
+        self.build(organization_id, start_date, end_date, as_of, costs)
+            .await
+    }
+
+    async fn build(
+        &self,
+        organization_id: Option<Uuid>,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+        as_of: NaiveDate,
+        costs: Vec<UnitCostDto>,
+    ) -> Result<UnitCostReport, ServiceError> {
+        let tree = self
+            .unit_repo
+            .get_tree(organization_id, Some(as_of))
+            .await?;
+
+        let (units, unassigned_count, unassigned_cost) = roll_up(&tree, costs);
+        Ok(UnitCostReport {
+            as_of,
+            start_date,
+            end_date,
+            total_count: units.iter().map(|u| u.total_count).sum::<i64>() + unassigned_count,
+            total_cost: units.iter().map(|u| u.total_cost).sum::<Decimal>() + unassigned_cost,
+            unassigned_count,
+            unassigned_cost,
+            units,
+        })
+    }
+}
+
+/// Structure date defaults to the end of the period (or today)
+fn resolve_as_of(
+    start_date: Option<NaiveDate>,
+    end_date: Option<NaiveDate>,
+    as_of: Option<NaiveDate>,
+) -> Result<NaiveDate, ServiceError> {
+    if let (Some(start), Some(end)) = (start_date, end_date) {
+        if start > end {
+            return Err(ServiceError::BadRequest(
+                "A data inicial deve ser anterior ou igual à data final".to_string(),
+            ));
+        }
+    }
+    Ok(as_of
+        .or(end_date)
+        .unwrap_or_else(|| Utc::now().date_naive()))
+}
+
+/// Attaches the costs to the tree, sums them up each subtree and drops the
+/// subtrees without any cost. Costs of units outside the tree are returned as
+/// the unassigned count and value.
+fn roll_up(
+    tree: &[OrganizationalUnitTreeNode],
+    costs: Vec<UnitCostDto>,
+) -> (Vec<UnitCostNode>, i64, Decimal) {
+    let mut direct: HashMap<Option<Uuid>, (i64, Decimal)> = HashMap::new();
+    for cost in costs {
+        let entry = direct.entry(cost.unit_id).or_insert((0, Decimal::ZERO));
+        entry.0 += cost.document_count;
+        entry.1 += cost.total_cost;
+    }
+
+    fn visit(
+        node: &OrganizationalUnitTreeNode,
+        direct: &mut HashMap<Option<Uuid>, (i64, Decimal)>,
+    ) -> Option<UnitCostNode> {
+        let (direct_count, direct_cost) = direct
+            .remove(&Some(node.unit.id))
+            .unwrap_or((0, Decimal::ZERO));
+        let children: Vec<UnitCostNode> = node
+            .children
+            .iter()
+            .filter_map(|child| visit(child, direct))
+            .collect();
+
+        let total_count = direct_count + children.iter().map(|c| c.total_count).sum::<i64>();
+        if total_count == 0 {
+            return None;
+        }
+        let total_cost = direct_cost + children.iter().map(|c| c.total_cost).sum::<Decimal>();
+
+        Some(UnitCostNode {
+            unit_id: node.unit.id,
+            name: node.unit.name.clone(),
+            acronym: node.unit.acronym.clone(),
+            is_active: node.unit.is_active,
+            direct_count,
+            direct_cost,
+            total_count,
+            total_cost,
+            children,
+        })
+    }
+
+    let units: Vec<UnitCostNode> = tree
+        .iter()
+        .filter_map(|root| visit(root, &mut direct))
+        .collect();
+
+    let (unassigned_count, unassigned_cost) = direct
+        .into_values()
+        .fold((0, Decimal::ZERO), |(count, cost), (c, v)| {
+            (count + c, cost + v)
+        });
+
+    (units, unassigned_count, unassigned_cost)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use domain::models::organizational::{
+        ActivityArea, ContactInfo, OrganizationalUnitDto, SyncStatus,
+    };
+
+    fn node(
+        id: Uuid,
+        name: &str,
+        children: Vec<OrganizationalUnitTreeNode>,
+    ) -> OrganizationalUnitTreeNode {
+        OrganizationalUnitTreeNode {
+            unit: OrganizationalUnitDto {
+                id,
+                organization_id: Uuid::nil(),
+                parent_id: None,
+                category_id: Uuid::nil(),
+                unit_type_id: Uuid::nil(),
+                name: name.to_string(),
+                formal_name: None,
+                acronym: None,
+                siorg_code: None,
+                siorg_parent_code: None,
+                siorg_url: None,
+                siorg_last_version: None,
+                is_siorg_managed: false,
+                activity_area: ActivityArea::Support,
+                contact_info: ContactInfo::default(),
+                level: 1,
+                path_ids: vec![id],
+                path_names: None,
+                is_active: true,
+                deactivated_at: None,
+                deactivation_reason: None,
+                siorg_synced_at: None,
+                siorg_sync_status: SyncStatus::Completed,
+                siorg_raw_data: None,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+            },
+            child_count: children.len() as i64,
+            children,
+        }
+    }
+
+    fn cost(unit_id: Option<Uuid>, count: i64, total: i64) -> UnitCostDto {
+        UnitCostDto {
+            unit_id,
+            document_count: count,
+            total_cost: Decimal::from(total),
+        }
+    }
+
+    #[test]
+    fn test_roll_up_sums_subtrees_and_prunes_empty_branches() {
+        let (root, dept, sector, idle) = (
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+        );
+        let tree = vec![node(
+            root,
+            "Reitoria",
+            vec![
+                node(dept, "Pró-Reitoria", vec![node(sector, "Setor", vec![])]),
+                node(idle, "Sem custos", vec![]),
+            ],
+        )];
+
+        let (units, unassigned_count, _) = roll_up(
+            &tree,
+            vec![cost(Some(root), 1, 10), cost(Some(sector), 2, 30)],
+        );
+
+        assert_eq!(unassigned_count, 0);
+        assert_eq!(units.len(), 1);
+        let root_node = &units[0];
+        assert_eq!(root_node.total_count, 3);
+        assert_eq!(root_node.direct_cost, Decimal::from(10));
+        assert_eq!(root_node.total_cost, Decimal::from(40));
+        assert_eq!(root_node.children.len(), 1);
+        let dept_node = &root_node.children[0];
+        assert_eq!(dept_node.unit_id, dept);
+        assert_eq!(dept_node.direct_count, 0);
+        assert_eq!(dept_node.total_count, 2);
+        assert_eq!(dept_node.children[0].unit_id, sector);
+    }
+
+    #[test]
+    fn test_roll_up_reports_units_outside_structure_as_unassigned() {
+        let unit = Uuid::new_v4();
+        let tree = vec![node(unit, "Unidade", vec![])];
+
+        let (units, unassigned_count, unassigned_cost) = roll_up(
+            &tree,
+            vec![
+                cost(Some(Uuid::new_v4()), 1, 5),
+                cost(None, 2, 7),
+                cost(Some(unit), 1, 3),
+            ],
+        );
+
+        assert_eq!(unassigned_count, 3);
+        assert_eq!(unassigned_cost, Decimal::from(12));
+        assert_eq!(units.len(), 1);
+        assert_eq!(units[0].total_cost, Decimal::from(3));
+    }
+
+    #[test]
+    fn test_resolve_as_of_defaults_to_end_date() {
+        let start = NaiveDate::from_ymd_opt(2024, 1, 1);
+        let end = NaiveDate::from_ymd_opt(2024, 12, 31);
+
+        assert_eq!(resolve_as_of(start, end, None).unwrap(), end.unwrap());
+        assert_eq!(resolve_as_of(start, end, start).unwrap(), start.unwrap());
+        assert!(resolve_as_of(end, start, None).is_err());
+    }
+}
@@ -0,0 +1,252 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use domain::errors::RepositoryError;
+use domain::models::{
+    CreateUnitRestructuringPayload, UnitLineageResponse, UnitRestructuringDto,
+    UnitRestructuringKind,
+};
+use domain::ports::UnitRestructuringRepositoryPort;
+use tracing::info;
+use uuid::Uuid;
+
+use crate::errors::ServiceError;
+
+/// Merges and splits organizational units, moving the references of the old
+/// units to their successors and keeping the lineage between them.
+pub struct UnitRestructuringService {
+    repo: Arc<dyn UnitRestructuringRepositoryPort>,
+}
+
+impl UnitRestructuringService {
+    pub fn new(repo: Arc<dyn UnitRestructuringRepositoryPort>) -> Self {
+        Self { repo }
+    }
+
+    /// Registra a fusão ou cisão e migra almoxarifados, requisições em
+    /// aberto, veículos, cadeias de aprovação, papéis por unidade e unidades
+    /// filhas para a sucessora principal (ou a direcionada, na cisão)
+    pub async fn restructure(
+        &self,
+        mut payload: CreateUnitRestructuringPayload,
+        created_by: Option<Uuid>,
+    ) -> Result<UnitRestructuringDto, ServiceError> {
+        let primary_successor_id = validate_restructuring(&payload)?;
+        payload.reason = payload.reason.trim().to_string();
+
+        let restructuring = self
+            .repo
+            .restructure(&payload, primary_successor_id, created_by)
+            .await
+            .map_err(|e| match e {
+                RepositoryError::NotFound => ServiceError::NotFound(
+                    "Unidade organizacional da reestruturação não encontrada".to_string(),
+                ),
+                e => ServiceError::from(e),
+            })?;
+
+        info!(
+            restructuring_id = %restructuring.id,
+            kind = ?restructuring.kind,
+            warehouses = restructuring.migrated.warehouses,
+            open_requisitions = restructuring.migrated.open_requisitions,
+            vehicles = restructuring.migrated.vehicles,
+            unit_roles = restructuring.migrated.unit_roles,
+            "Unidades organizacionais reestruturadas"
+        );
+        Ok(restructuring)
+    }
+
+    pub async fn get(&self, id: Uuid) -> Result<UnitRestructuringDto, ServiceError> {
+        self.repo
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| ServiceError::NotFound("Reestruturação não encontrada".to_string()))
+    }
+
+    pub async fn list(
+        &self,
+        organization_id: Option<Uuid>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<UnitRestructuringDto>, i64), ServiceError> {
+        self.repo
+            .list(organization_id, limit, offset)
+            .await
+            .map_err(ServiceError::from)
+    }
+
+    /// Antecessoras e sucessoras da unidade, com a unidade atual que a
+    /// substitui (ela mesma quando não foi reestruturada)
+    pub async fn lineage(&self, unit_id: Uuid) -> Result<UnitLineageResponse, ServiceError> {
+        let current_unit_id = self.repo.current_unit(unit_id).await.map_err(|e| match e {
+            RepositoryError::NotFound => {
+                ServiceError::NotFound("Unidade organizacional não encontrada".to_string())
+            }
+            e => ServiceError::from(e),
+        })?;
+
+        let (predecessors, successors): (Vec<_>, Vec<_>) = self
+            .repo
+            .lineage_of(unit_id)
+            .await?
+            .into_iter()
+            .partition(|link| link.successor_id == unit_id);
+
+        Ok(UnitLineageResponse {
+            unit_id,
+            current_unit_id,
+            predecessors,
+            successors,
+        })
+    }
+}
+
+/// Checks the shape of a merge or split and returns the primary successor
+fn validate_restructuring(payload: &CreateUnitRestructuringPayload) -> Result<Uuid, ServiceError> {
+    let bad = |msg: &str| Err(ServiceError::BadRequest(msg.to_string()));
+
+    if payload.reason.trim().is_empty() {
+        return bad("Informe o motivo da reestruturação");
+    }
+
+    let mut seen = HashSet::new();
+    let mut all = payload.predecessor_ids.iter().chain(&payload.successor_ids);
+    if !all.all(|id| seen.insert(*id)) {
+        return bad("As unidades sucedidas e sucessoras devem ser distintas");
+    }
+
+    let primary = match payload.kind {
+        UnitRestructuringKind::Merge => {
+            if payload.predecessor_ids.is_empty() || payload.successor_ids.len() != 1 {
+                return bad(
+                    "A fusão exige ao menos uma unidade sucedida e exatamente uma sucessora",
+                );
+            }
+            if !payload.overrides.is_empty() {
+                return bad("Direcionamentos só se aplicam à cisão");
+            }
+            payload.successor_ids[0]
+        }
+        UnitRestructuringKind::Split => {
+            if payload.predecessor_ids.len() != 1 || payload.successor_ids.len() < 2 {
+                return bad(
+                    "A cisão exige exatamente uma unidade sucedida e ao menos duas sucessoras",
+                );
+            }
+            match payload.primary_successor_id {
+                Some(id) if payload.successor_ids.contains(&id) => id,
+                _ => return bad("Informe a sucessora principal entre as sucessoras da cisão"),
+            }
+        }
+    };
+
+    if let Some(id) = payload.primary_successor_id {
+        if id != primary {
+            return bad("A sucessora principal deve ser uma das sucessoras");
+        }
+    }
+
+    let mut targeted = HashSet::new();
+    for target in &payload.overrides {
+        if !payload.successor_ids.contains(&target.successor_id) {
+            return bad("Direcionamento para unidade que não é sucessora");
+        }
+        if !targeted.insert((target.reference, target.id)) {
+            return bad("Registro direcionado mais de uma vez");
+        }
+    }
+
+    Ok(primary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use domain::models::{RestructuringOverride, RestructuringReference};
+
+    fn payload(
+        kind: UnitRestructuringKind,
+        predecessor_ids: Vec<Uuid>,
+        successor_ids: Vec<Uuid>,
+        primary_successor_id: Option<Uuid>,
+    ) -> CreateUnitRestructuringPayload {
+        CreateUnitRestructuringPayload {
+            kind,
+            predecessor_ids,
+            successor_ids,
+            primary_successor_id,
+            effective_date: NaiveDate::from_ymd_opt(2026, 1, 1).unwrap(),
+            reason: "Decreto de reestruturação".to_string(),
+            documento_sei: None,
+            overrides: Vec::new(),
+            deactivate_predecessors: true,
+        }
+    }
+
+    #[test]
+    fn test_validate_merge_returns_single_successor() {
+        let (a, b, c) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let merge = payload(UnitRestructuringKind::Merge, vec![a, b], vec![c], None);
+        assert_eq!(validate_restructuring(&merge).unwrap(), c);
+
+        let two = payload(UnitRestructuringKind::Merge, vec![a], vec![b, c], None);
+        assert!(validate_restructuring(&two).is_err());
+
+        let overlap = payload(UnitRestructuringKind::Merge, vec![a, c], vec![c], None);
+        assert!(validate_restructuring(&overlap).is_err());
+    }
+
+    #[test]
+    fn test_validate_split_requires_primary_among_successors() {
+        let (a, b, c) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let split = payload(UnitRestructuringKind::Split, vec![a], vec![b, c], Some(c));
+        assert_eq!(validate_restructuring(&split).unwrap(), c);
+
+        let missing = payload(UnitRestructuringKind::Split, vec![a], vec![b, c], None);
+        assert!(validate_restructuring(&missing).is_err());
+
+        let outside = payload(UnitRestructuringKind::Split, vec![a], vec![b, c], Some(a));
+        assert!(validate_restructuring(&outside).is_err());
+
+        let single = payload(UnitRestructuringKind::Split, vec![a], vec![b], Some(b));
+        assert!(validate_restructuring(&single).is_err());
+    }
+
+    #[test]
+    fn test_validate_overrides_target_successors() {
+        let (a, b, c) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let target = |successor_id| RestructuringOverride {
+            reference: RestructuringReference::Vehicle,
+            id: Uuid::nil(),
+            successor_id,
+        };
+
+        let mut split = payload(UnitRestructuringKind::Split, vec![a], vec![b, c], Some(b));
+        split.overrides = vec![target(c)];
+        assert!(validate_restructuring(&split).is_ok());
+
+        split.overrides = vec![target(a)];
+        assert!(validate_restructuring(&split).is_err());
+
+        split.overrides = vec![target(c), target(b)];
+        assert!(validate_restructuring(&split).is_err());
+
+        let mut merge = payload(UnitRestructuringKind::Merge, vec![a], vec![b], None);
+        merge.overrides = vec![target(b)];
+        assert!(validate_restructuring(&merge).is_err());
+    }
+
+    #[test]
+    fn test_validate_requires_reason() {
+        let mut merge = payload(
+            UnitRestructuringKind::Merge,
+            vec![Uuid::new_v4()],
+            vec![Uuid::new_v4()],
+            None,
+        );
+        merge.reason = "  ".to_string();
+        assert!(validate_restructuring(&merge).is_err());
+    }
+}
@@ -218,6 +218,7 @@ mod tests {
             async fn send_welcome_email(&self, to: &Email, username: &Username) -> Result<(), domain::errors::EmailError>;
             async fn send_password_reset_email(&self, to: &Email, username: &Username, token: &str) -> Result<(), domain::errors::EmailError>;
             async fn send_mfa_enabled_email(&self, to: &Email, username: &Username) -> Result<(), domain::errors::EmailError>;
+            async fn send_account_locked_email(&self, to: &Email, username: &Username, locked_until: chrono::DateTime<chrono::Utc>, unlock_token: &str) -> Result<(), domain::errors::EmailError>;
         }
     }
 
@@ -1,16 +1,34 @@
 use crate::errors::ServiceError;
+use chrono::{DateTime, Duration, FixedOffset, Months, NaiveDate, Utc};
 use domain::{
     models::vehicle_fine::*,
+    ports::driver::DriverRepositoryPort,
     ports::vehicle_fine::*,
 };
 use rust_decimal::Decimal;
 use std::sync::Arc;
 use uuid::Uuid;
 
+/// Prazo para indicação do condutor infrator (CTB Art. 257 §7º).
+const IDENTIFICATION_DEADLINE_DAYS: i64 = 30;
+
+/// Prazo mínimo para defesa prévia a partir da notificação (CTB Art. 281-A).
+const DEFENSE_DEADLINE_DAYS: i64 = 30;
+
+/// Horizonte padrão da fila de prazos.
+const DEFAULT_DEADLINE_WINDOW_DAYS: i64 = 15;
+
+/// Percentual do limite de suspensão a partir do qual o condutor entra em alerta.
+const POINTS_WARNING_PERCENT: i64 = 75;
+
+/// Horário de Brasília — prazos são contados em dias corridos locais.
+const BRT_OFFSET_SECONDS: i32 = 3 * 3600;
+
 pub struct VehicleFineService {
     fine_type_repo: Arc<dyn VehicleFineTypeRepositoryPort>,
     fine_repo: Arc<dyn VehicleFineRepositoryPort>,
     status_history_repo: Arc<dyn VehicleFineStatusHistoryRepositoryPort>,
+    driver_repo: Arc<dyn DriverRepositoryPort>,
 }
 
 impl VehicleFineService {
@@ -18,11 +36,13 @@ impl VehicleFineService {
         fine_type_repo: Arc<dyn VehicleFineTypeRepositoryPort>,
         fine_repo: Arc<dyn VehicleFineRepositoryPort>,
         status_history_repo: Arc<dyn VehicleFineStatusHistoryRepositoryPort>,
+        driver_repo: Arc<dyn DriverRepositoryPort>,
     ) -> Self {
         Self {
             fine_type_repo,
             fine_repo,
             status_history_repo,
+            driver_repo,
         }
     }
 
@@ -187,6 +207,37 @@ impl VehicleFineService {
             .create(fine.id, None, fine.status.clone(), Some("Cadastro inicial"), created_by)
             .await;
 
+        self.refresh_deadlines(&fine).await?;
+
+        match fine.driver_id {
+            Some(driver_id) => {
+                self.fine_repo
+                    .assign_driver(fine.id, driver_id, None, &FineDriverSource::Manual, created_by)
+                    .await
+                    .map_err(ServiceError::from)?;
+            }
+            None => {
+                // Condutor da viagem em curso no veículo no momento da infração
+                if let Some(trip) = self
+                    .fine_repo
+                    .find_trip_driver_at(fine.vehicle_id, fine.fine_date)
+                    .await
+                    .map_err(ServiceError::from)?
+                {
+                    self.fine_repo
+                        .assign_driver(
+                            fine.id,
+                            trip.driver_id,
+                            Some(trip.trip_id),
+                            &FineDriverSource::Trip,
+                            created_by,
+                        )
+                        .await
+                        .map_err(ServiceError::from)?;
+                }
+            }
+        }
+
         self.fine_repo
             .find_with_details_by_id(fine.id)
             .await
@@ -231,7 +282,7 @@ impl VehicleFineService {
             }
         }
 
-        let _ = self.fine_repo
+        let updated = self.fine_repo
             .update(
                 id,
                 payload.vehicle_id,
@@ -254,6 +305,10 @@ impl VehicleFineService {
             .await
             .map_err(ServiceError::from)?;
 
+        if payload.notification_date.is_some() || payload.due_date.is_some() {
+            self.refresh_deadlines(&updated).await?;
+        }
+
         self.fine_repo
             .find_with_details_by_id(id)
             .await
@@ -352,4 +407,450 @@ impl VehicleFineService {
             .await
             .map_err(ServiceError::from)
     }
+
+    // ============================
+    // Driver identification
+    // ============================
+
+    /// Indica o condutor infrator. Sem `driver_id` no payload, usa o condutor
+    /// da viagem em curso no veículo na data da infração.
+    pub async fn identify_driver(
+        &self,
+        id: Uuid,
+        payload: IdentifyFineDriverPayload,
+        changed_by: Option<Uuid>,
+    ) -> Result<VehicleFineWithDetailsDto, ServiceError> {
+        let current = self.fine_repo.find_by_id(id).await.map_err(ServiceError::from)?
+            .ok_or(ServiceError::NotFound("Multa não encontrada".to_string()))?;
+
+        if current.is_deleted {
+            return Err(ServiceError::BadRequest("Não é possível indicar condutor de multa excluída".to_string()));
+        }
+        if !matches!(
+            current.status,
+            FineStatus::PendingNotification | FineStatus::Notified | FineStatus::AwaitingDriverIdentification
+        ) {
+            return Err(ServiceError::BadRequest(
+                "Indicação de condutor não é permitida no status atual da multa".to_string(),
+            ));
+        }
+
+        let trip = self
+            .fine_repo
+            .find_trip_driver_at(current.vehicle_id, current.fine_date)
+            .await
+            .map_err(ServiceError::from)?;
+
+        let (driver_id, trip_id, source) = match payload.driver_id {
+            Some(driver_id) => {
+                let driver = self.driver_repo.find_by_id(driver_id).await.map_err(ServiceError::from)?
+                    .ok_or(ServiceError::NotFound("Condutor não encontrado".to_string()))?;
+                // Mantém o vínculo com a viagem quando o condutor informado é o da viagem
+                let trip_id = trip.filter(|t| t.driver_id == driver.id).map(|t| t.trip_id);
+                (driver.id, trip_id, FineDriverSource::Manual)
+            }
+            None => {
+                let trip = trip.ok_or(ServiceError::BadRequest(
+                    "Nenhuma viagem em curso no veículo na data da infração; informe o condutor".to_string(),
+                ))?;
+                (trip.driver_id, Some(trip.trip_id), FineDriverSource::Trip)
+            }
+        };
+
+        let late = current
+            .identification_deadline
+            .is_some_and(|deadline| deadline < local_date(Utc::now()));
+        let reason = match (payload.reason.as_deref(), late) {
+            (Some(r), true) => format!("{} (indicação fora do prazo)", r),
+            (Some(r), false) => r.to_string(),
+            (None, true) => "Condutor indicado fora do prazo".to_string(),
+            (None, false) => "Condutor indicado".to_string(),
+        };
+
+        self.fine_repo
+            .assign_driver(id, driver_id, trip_id, &source, changed_by)
+            .await
+            .map_err(ServiceError::from)?;
+
+        let _ = self.status_history_repo
+            .create(id, Some(current.status), FineStatus::DriverIdentified, Some(&reason), changed_by)
+            .await;
+
+        self.fine_repo
+            .update_status(id, &FineStatus::DriverIdentified, changed_by)
+            .await
+            .map_err(ServiceError::from)?;
+
+        // O alerta não desfaz a indicação: falhas aqui só são registradas em log
+        match self.get_driver_points(driver_id).await {
+            Ok(summary) if summary.alert_level != DriverPointsAlertLevel::Ok => {
+                tracing::warn!(
+                    driver_id = %driver_id,
+                    total_points = summary.total_points,
+                    threshold = summary.suspension_threshold,
+                    "Condutor próximo ou acima do limite de suspensão da CNH"
+                );
+                if let Err(e) = self
+                    .fine_repo
+                    .upsert_points_alert(
+                        driver_id,
+                        id,
+                        summary.alert_level,
+                        summary.total_points,
+                        summary.suspension_threshold,
+                    )
+                    .await
+                {
+                    tracing::warn!("Falha ao gravar alerta de pontuação do condutor {}: {}", driver_id, e);
+                }
+            }
+            Ok(_) => {}
+            Err(e) => tracing::warn!("Falha ao calcular pontuação do condutor {}: {}", driver_id, e),
+        }
+
+        self.fine_repo
+            .find_with_details_by_id(id)
+            .await
+            .map_err(ServiceError::from)?
+            .ok_or(ServiceError::Internal("Falha ao buscar multa atualizada".to_string()))
+    }
+
+    /// Dados do Formulário de Identificação do Condutor Infrator.
+    pub async fn get_identification_form(&self, id: Uuid) -> Result<FineIdentificationFormDto, ServiceError> {
+        let fine = self.fine_repo.find_by_id(id).await.map_err(ServiceError::from)?
+            .ok_or(ServiceError::NotFound("Multa não encontrada".to_string()))?;
+
+        if fine.driver_id.is_none() {
+            return Err(ServiceError::BadRequest("Multa sem condutor indicado".to_string()));
+        }
+
+        self.fine_repo
+            .find_identification_form(id)
+            .await
+            .map_err(ServiceError::from)?
+            .ok_or(ServiceError::Internal("Falha ao montar formulário de indicação".to_string()))
+    }
+
+    // ============================
+    // Deadlines
+    // ============================
+
+    pub async fn list_upcoming_deadlines(
+        &self,
+        query: FineDeadlineQuery,
+    ) -> Result<Vec<FineDeadlineDto>, ServiceError> {
+        let within_days = query.within_days.unwrap_or(DEFAULT_DEADLINE_WINDOW_DAYS);
+        if !(0..=365).contains(&within_days) {
+            return Err(ServiceError::BadRequest("within_days deve estar entre 0 e 365".to_string()));
+        }
+
+        let today = local_date(Utc::now());
+        let until = today + Duration::days(within_days);
+
+        let fines = self.fine_repo.list_open_deadlines(until).await.map_err(ServiceError::from)?;
+
+        let mut deadlines: Vec<FineDeadlineDto> = fines
+            .iter()
+            .flat_map(|fine| pending_deadlines(fine, today, until))
+            .collect();
+        deadlines.sort_by_key(|d| d.deadline);
+        Ok(deadlines)
+    }
+
+    async fn refresh_deadlines(&self, fine: &VehicleFineDto) -> Result<(), ServiceError> {
+        let (identification, defense, appeal) = compute_deadlines(fine.notification_date, fine.due_date);
+        self.fine_repo
+            .set_deadlines(fine.id, identification, defense, appeal)
+            .await
+            .map_err(ServiceError::from)?;
+        Ok(())
+    }
+
+    // ============================
+    // Driver points
+    // ============================
+
+    pub async fn list_driver_points(
+        &self,
+        query: DriverPointsQuery,
+    ) -> Result<Vec<DriverPointsSummaryDto>, ServiceError> {
+        let window_start = points_window_start(Utc::now());
+        let totals = self
+            .fine_repo
+            .driver_points_totals(window_start, None)
+            .await
+            .map_err(ServiceError::from)?;
+
+        let only_alerts = query.only_alerts.unwrap_or(false);
+        Ok(totals
+            .into_iter()
+            .map(|t| summarize_points(t, window_start))
+            .filter(|s| !only_alerts || s.alert_level != DriverPointsAlertLevel::Ok)
+            .collect())
+    }
+
+    pub async fn get_driver_points(&self, driver_id: Uuid) -> Result<DriverPointsSummaryDto, ServiceError> {
+        let driver = self.driver_repo.find_by_id(driver_id).await.map_err(ServiceError::from)?
+            .ok_or(ServiceError::NotFound("Condutor não encontrado".to_string()))?;
+
+        let window_start = points_window_start(Utc::now());
+        let totals = self
+            .fine_repo
+            .driver_points_totals(window_start, Some(driver_id))
+            .await
+            .map_err(ServiceError::from)?
+            .into_iter()
+            .next()
+            .unwrap_or(DriverPointsTotalsDto {
+                driver_id: driver.id,
+                driver_name: driver.full_name,
+                cnh_number: driver.cnh_number,
+                total_points: 0,
+                fine_count: 0,
+                very_serious_count: 0,
+            });
+
+        Ok(summarize_points(totals, window_start))
+    }
+
+    pub async fn list_points_alerts(
+        &self,
+        query: DriverPointsAlertQuery,
+    ) -> Result<Vec<DriverPointsAlertDto>, ServiceError> {
+        self.fine_repo
+            .list_points_alerts(query.include_acknowledged.unwrap_or(false))
+            .await
+            .map_err(ServiceError::from)
+    }
+
+    pub async fn acknowledge_points_alert(
+        &self,
+        id: Uuid,
+        acknowledged_by: Uuid,
+    ) -> Result<DriverPointsAlertDto, ServiceError> {
+        let alert = self.fine_repo.find_points_alert_by_id(id).await.map_err(ServiceError::from)?
+            .ok_or(ServiceError::NotFound("Alerta de pontuação não encontrado".to_string()))?;
+        if alert.acknowledged_at.is_some() {
+            return Err(ServiceError::BadRequest("Ciência do alerta já registrada".to_string()));
+        }
+        if alert.superseded_by.is_some() {
+            return Err(ServiceError::BadRequest("Alerta substituído pelo alerta de suspensão".to_string()));
+        }
+
+        self.fine_repo
+            .acknowledge_points_alert(id, acknowledged_by)
+            .await
+            .map_err(ServiceError::from)
+    }
+}
+
+fn local_date(at: DateTime<Utc>) -> NaiveDate {
+    let brt = FixedOffset::west_opt(BRT_OFFSET_SECONDS).expect("valid offset");
+    at.with_timezone(&brt).date_naive()
+}
+
+/// Prazos de indicação, defesa prévia e recurso à JARI.
+///
+/// Indicação e defesa contam da notificação da autuação; o recurso vai até o
+/// vencimento da notificação de penalidade (CTB Art. 282 §4º, 286).
+fn compute_deadlines(
+    notification_date: Option<DateTime<Utc>>,
+    due_date: DateTime<Utc>,
+) -> (Option<NaiveDate>, Option<NaiveDate>, Option<NaiveDate>) {
+    let notified = notification_date.map(local_date);
+    (
+        notified.map(|d| d + Duration::days(IDENTIFICATION_DEADLINE_DAYS)),
+        notified.map(|d| d + Duration::days(DEFENSE_DEADLINE_DAYS)),
+        Some(local_date(due_date)),
+    )
+}
+
+/// Prazos ainda relevantes para o status atual da multa, até `until`.
+fn pending_deadlines(fine: &VehicleFineWithDetailsDto, today: NaiveDate, until: NaiveDate) -> Vec<FineDeadlineDto> {
+    let awaiting_identification = fine.driver_identified_at.is_none()
+        && matches!(
+            fine.status,
+            FineStatus::PendingNotification | FineStatus::Notified | FineStatus::AwaitingDriverIdentification
+        );
+    let awaiting_defense = matches!(
+        fine.status,
+        FineStatus::PendingNotification
+            | FineStatus::Notified
+            | FineStatus::AwaitingDriverIdentification
+            | FineStatus::DriverIdentified
+    );
+    let awaiting_appeal = matches!(fine.status, FineStatus::DefenseRejected | FineStatus::PendingPayment);
+
+    [
+        (FineDeadlineKind::DriverIdentification, fine.identification_deadline, awaiting_identification),
+        (FineDeadlineKind::PriorDefense, fine.defense_deadline, awaiting_defense),
+        (FineDeadlineKind::Appeal, fine.appeal_deadline, awaiting_appeal),
+    ]
+    .into_iter()
+    .filter_map(|(kind, deadline, open)| match deadline {
+        Some(deadline) if open && deadline <= until => Some(FineDeadlineDto {
+            fine_id: fine.id,
+            vehicle_license_plate: fine.vehicle_license_plate.clone(),
+            auto_number: fine.auto_number.clone(),
+            status: fine.status.clone(),
+            kind,
+            deadline,
+            days_remaining: (deadline - today).num_days(),
+        }),
+        _ => None,
+    })
+    .collect()
+}
+
+fn points_window_start(now: DateTime<Utc>) -> DateTime<Utc> {
+    now.checked_sub_months(Months::new(12)).unwrap_or(now)
+}
+
+/// Limite de suspensão da CNH em 12 meses (CTB Art. 261, I): 20 pontos com
+/// duas ou mais gravíssimas, 30 com uma, 40 sem nenhuma.
+fn suspension_threshold(very_serious_count: i64) -> i64 {
+    match very_serious_count {
+        0 => 40,
+        1 => 30,
+        _ => 20,
+    }
+}
+
+fn summarize_points(totals: DriverPointsTotalsDto, window_start: DateTime<Utc>) -> DriverPointsSummaryDto {
+    let threshold = suspension_threshold(totals.very_serious_count);
+    let alert_level = if totals.total_points >= threshold {
+        DriverPointsAlertLevel::Suspension
+    } else if totals.total_points * 100 >= threshold * POINTS_WARNING_PERCENT {
+        DriverPointsAlertLevel::Warning
+    } else {
+        DriverPointsAlertLevel::Ok
+    };
+
+    DriverPointsSummaryDto {
+        driver_id: totals.driver_id,
+        driver_name: totals.driver_name,
+        cnh_number: totals.cnh_number,
+        total_points: totals.total_points,
+        fine_count: totals.fine_count,
+        very_serious_count: totals.very_serious_count,
+        suspension_threshold: threshold,
+        remaining_points: (threshold - totals.total_points).max(0),
+        alert_level,
+        window_start,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn totals(points: i64, very_serious: i64) -> DriverPointsTotalsDto {
+        DriverPointsTotalsDto {
+            driver_id: Uuid::new_v4(),
+            driver_name: "Fulano".to_string(),
+            cnh_number: "12345678900".to_string(),
+            total_points: points,
+            fine_count: 1,
+            very_serious_count: very_serious,
+        }
+    }
+
+    fn fine(status: FineStatus) -> VehicleFineWithDetailsDto {
+        let now = Utc::now();
+        VehicleFineWithDetailsDto {
+            id: Uuid::new_v4(),
+            vehicle_id: Uuid::new_v4(),
+            vehicle_license_plate: Some("ABC1D23".to_string()),
+            fine_type_id: Uuid::new_v4(),
+            fine_type_code: None,
+            fine_type_description: None,
+            fine_type_severity: None,
+            fine_type_points: None,
+            supplier_id: Uuid::new_v4(),
+            supplier_name: None,
+            driver_id: None,
+            driver_name: None,
+            auto_number: Some("A123".to_string()),
+            fine_date: now,
+            notification_date: None,
+            due_date: now,
+            location: None,
+            sei_process_number: None,
+            fine_amount: Decimal::ZERO,
+            discount_amount: None,
+            paid_amount: None,
+            payment_date: None,
+            status,
+            notes: None,
+            trip_id: None,
+            driver_source: None,
+            driver_identified_at: None,
+            identification_deadline: NaiveDate::from_ymd_opt(2026, 5, 10),
+            defense_deadline: NaiveDate::from_ymd_opt(2026, 5, 10),
+            appeal_deadline: NaiveDate::from_ymd_opt(2026, 6, 20),
+            is_deleted: false,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn test_compute_deadlines_uses_local_notification_date() {
+        // 01:00 UTC ainda é o dia anterior em Brasília
+        let notified = Utc.with_ymd_and_hms(2026, 4, 11, 1, 0, 0).unwrap();
+        let due = Utc.with_ymd_and_hms(2026, 6, 20, 15, 0, 0).unwrap();
+
+        let (identification, defense, appeal) = compute_deadlines(Some(notified), due);
+        assert_eq!(identification, NaiveDate::from_ymd_opt(2026, 5, 10));
+        assert_eq!(defense, NaiveDate::from_ymd_opt(2026, 5, 10));
+        assert_eq!(appeal, NaiveDate::from_ymd_opt(2026, 6, 20));
+
+        let (identification, defense, _) = compute_deadlines(None, due);
+        assert!(identification.is_none() && defense.is_none());
+    }
+
+    #[test]
+    fn test_pending_deadlines_follow_status() {
+        let today = NaiveDate::from_ymd_opt(2026, 5, 5).unwrap();
+        let until = NaiveDate::from_ymd_opt(2026, 5, 20).unwrap();
+
+        let notified = pending_deadlines(&fine(FineStatus::Notified), today, until);
+        let kinds: Vec<_> = notified.iter().map(|d| d.kind).collect();
+        assert_eq!(kinds, vec![FineDeadlineKind::DriverIdentification, FineDeadlineKind::PriorDefense]);
+        assert_eq!(notified[0].days_remaining, 5);
+
+        let identified = pending_deadlines(&fine(FineStatus::DriverIdentified), today, until);
+        assert_eq!(identified.len(), 1);
+        assert_eq!(identified[0].kind, FineDeadlineKind::PriorDefense);
+
+        // Recurso vence depois do horizonte
+        assert!(pending_deadlines(&fine(FineStatus::PendingPayment), today, until).is_empty());
+        let later = NaiveDate::from_ymd_opt(2026, 6, 30).unwrap();
+        let appeal = pending_deadlines(&fine(FineStatus::PendingPayment), today, later);
+        assert_eq!(appeal.len(), 1);
+        assert_eq!(appeal[0].kind, FineDeadlineKind::Appeal);
+    }
+
+    #[test]
+    fn test_suspension_threshold_by_very_serious_count() {
+        assert_eq!(suspension_threshold(0), 40);
+        assert_eq!(suspension_threshold(1), 30);
+        assert_eq!(suspension_threshold(3), 20);
+    }
+
+    #[test]
+    fn test_points_alert_levels() {
+        let start = Utc::now();
+        assert_eq!(summarize_points(totals(20, 0), start).alert_level, DriverPointsAlertLevel::Ok);
+        assert_eq!(summarize_points(totals(30, 0), start).alert_level, DriverPointsAlertLevel::Warning);
+
+        let suspended = summarize_points(totals(21, 2), start);
+        assert_eq!(suspended.alert_level, DriverPointsAlertLevel::Suspension);
+        assert_eq!(suspended.remaining_points, 0);
+
+        let one_very_serious = summarize_points(totals(23, 1), start);
+        assert_eq!(one_very_serious.alert_level, DriverPointsAlertLevel::Warning);
+        assert_eq!(one_very_serious.remaining_points, 7);
+    }
 }
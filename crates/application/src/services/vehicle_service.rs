@@ -1,6 +1,7 @@
 use crate::errors::ServiceError;
 use domain::{
     models::vehicle::*,
+    models::UnitScope,
     ports::vehicle::*,
 };
 use regex::Regex;
@@ -213,6 +214,11 @@ impl VehicleService {
         // Validate make exists
         let _ = self.get_vehicle_make(payload.make_id).await?;
 
+        // Validate preventive interval (if provided)
+        if payload.preventive_interval_km.is_some_and(|km| km <= 0) {
+            return Err(ServiceError::BadRequest("Intervalo de manutenção preventiva deve ser positivo".to_string()));
+        }
+
         // Validate category exists (if provided)
         if let Some(category_id) = payload.category_id {
             let _ = self.category_repo.find_by_id(category_id).await.map_err(ServiceError::from)?
@@ -236,6 +242,7 @@ impl VehicleService {
                 payload.avg_consumption_min,
                 payload.avg_consumption_max,
                 payload.avg_consumption_target,
+                payload.preventive_interval_km,
             )
             .await
             .map_err(ServiceError::from)
@@ -252,6 +259,11 @@ impl VehicleService {
     pub async fn update_vehicle_model(&self, id: Uuid, payload: UpdateVehicleModelPayload) -> Result<VehicleModelDto, ServiceError> {
         let current = self.get_vehicle_model(id).await?;
 
+        // Validate preventive interval (if provided)
+        if payload.preventive_interval_km.is_some_and(|km| km <= 0) {
+            return Err(ServiceError::BadRequest("Intervalo de manutenção preventiva deve ser positivo".to_string()));
+        }
+
         // Validate category exists (if provided)
         if let Some(category_id) = payload.category_id {
             let _ = self.category_repo.find_by_id(category_id).await.map_err(ServiceError::from)?
@@ -277,6 +289,7 @@ impl VehicleService {
                 payload.avg_consumption_min,
                 payload.avg_consumption_max,
                 payload.avg_consumption_target,
+                payload.preventive_interval_km,
                 payload.is_active,
             )
             .await
@@ -500,9 +513,31 @@ impl VehicleService {
             .ok_or(ServiceError::NotFound("Veículo não encontrado".to_string()))
     }
 
-    pub async fn update_vehicle(&self, id: Uuid, payload: UpdateVehiclePayload, updated_by: Option<Uuid>) -> Result<VehicleWithDetailsDto, ServiceError> {
+    /// Vehicles allocated to departments outside the caller's scope are
+    /// reported as not found
+    pub async fn get_vehicle_in_scope(&self, id: Uuid, scope: &UnitScope) -> Result<VehicleWithDetailsDto, ServiceError> {
+        let vehicle = self.get_vehicle(id).await?;
+        if !scope.contains(vehicle.department_id) {
+            return Err(ServiceError::NotFound("Veículo não encontrado".to_string()));
+        }
+        Ok(vehicle)
+    }
+
+    /// Changes to a vehicle require its department to be in the caller's scope
+    fn check_department_in_scope(department_id: Option<Uuid>, scope: &UnitScope) -> Result<(), ServiceError> {
+        if !scope.contains(department_id) {
+            return Err(ServiceError::Forbidden("Veículo fora do seu escopo de atuação".to_string()));
+        }
+        Ok(())
+    }
+
+    pub async fn update_vehicle(&self, id: Uuid, payload: UpdateVehiclePayload, updated_by: Option<Uuid>, scope: &UnitScope) -> Result<VehicleWithDetailsDto, ServiceError> {
         let current = self.vehicle_repo.find_by_id(id).await.map_err(ServiceError::from)?
             .ok_or(ServiceError::NotFound("Veículo não encontrado".to_string()))?;
+        Self::check_department_in_scope(current.department_id, scope)?;
+        if payload.department_id.is_some() {
+            Self::check_department_in_scope(payload.department_id, scope)?;
+        }
 
         // Validate and check uniqueness for plate
         let plate = payload.license_plate.as_ref().map(|p| normalize_license_plate(p));
@@ -584,9 +619,10 @@ impl VehicleService {
             .ok_or(ServiceError::Internal("Falha ao buscar veículo atualizado".to_string()))
     }
 
-    pub async fn delete_vehicle(&self, id: Uuid, deleted_by: Option<Uuid>) -> Result<bool, ServiceError> {
+    pub async fn delete_vehicle(&self, id: Uuid, deleted_by: Option<Uuid>, scope: &UnitScope) -> Result<bool, ServiceError> {
         let current = self.vehicle_repo.find_by_id(id).await.map_err(ServiceError::from)?
             .ok_or(ServiceError::NotFound("Veículo não encontrado".to_string()))?;
+        Self::check_department_in_scope(current.department_id, scope)?;
 
         // Record status change
         let _ = self.status_history_repo
@@ -596,9 +632,10 @@ impl VehicleService {
         self.vehicle_repo.soft_delete(id, deleted_by).await.map_err(ServiceError::from)
     }
 
-    pub async fn change_vehicle_status(&self, id: Uuid, payload: ChangeVehicleStatusPayload, changed_by: Option<Uuid>) -> Result<VehicleWithDetailsDto, ServiceError> {
+    pub async fn change_vehicle_status(&self, id: Uuid, payload: ChangeVehicleStatusPayload, changed_by: Option<Uuid>, scope: &UnitScope) -> Result<VehicleWithDetailsDto, ServiceError> {
         let current = self.vehicle_repo.find_by_id(id).await.map_err(ServiceError::from)?
             .ok_or(ServiceError::NotFound("Veículo não encontrado".to_string()))?;
+        Self::check_department_in_scope(current.department_id, scope)?;
 
         if current.status == payload.status {
             return Err(ServiceError::BadRequest("Veículo já está neste status".to_string()));
@@ -636,12 +673,14 @@ impl VehicleService {
         id: Uuid,
         payload: ChangeOperationalStatusPayload,
         changed_by: Option<Uuid>,
+        scope: &UnitScope,
     ) -> Result<VehicleWithDetailsDto, ServiceError> {
         let current = self.vehicle_repo
             .find_by_id(id)
             .await
             .map_err(ServiceError::from)?
             .ok_or_else(|| ServiceError::NotFound("Veículo não encontrado".to_string()))?;
+        Self::check_department_in_scope(current.department_id, scope)?;
 
         if current.operational_status == payload.operational_status {
             return Err(ServiceError::BadRequest("Veículo já está neste status operacional".to_string()));
@@ -689,21 +728,25 @@ impl VehicleService {
         fuel_type_id: Option<Uuid>,
         department_id: Option<Uuid>,
         include_deleted: bool,
+        scope: &UnitScope,
     ) -> Result<(Vec<VehicleWithDetailsDto>, i64), ServiceError> {
         self.vehicle_repo
-            .list(limit, offset, search, status, model_id, fuel_type_id, department_id, include_deleted)
+            .list(limit, offset, search, status, model_id, fuel_type_id, department_id, include_deleted, scope.unit_ids())
             .await
             .map_err(ServiceError::from)
     }
 
-    pub async fn search_vehicles(&self, query: &str, limit: i64) -> Result<Vec<VehicleDto>, ServiceError> {
-        self.vehicle_repo.search_autocomplete(query, limit).await.map_err(ServiceError::from)
+    pub async fn search_vehicles(&self, query: &str, limit: i64, scope: &UnitScope) -> Result<Vec<VehicleDto>, ServiceError> {
+        self.vehicle_repo.search_autocomplete(query, limit, scope.unit_ids()).await.map_err(ServiceError::from)
     }
 
-    pub async fn get_vehicle_status_history(&self, vehicle_id: Uuid) -> Result<Vec<VehicleStatusHistoryDto>, ServiceError> {
+    pub async fn get_vehicle_status_history(&self, vehicle_id: Uuid, scope: &UnitScope) -> Result<Vec<VehicleStatusHistoryDto>, ServiceError> {
         // Verify vehicle exists
-        let _ = self.vehicle_repo.find_by_id(vehicle_id).await.map_err(ServiceError::from)?
+        let current = self.vehicle_repo.find_by_id(vehicle_id).await.map_err(ServiceError::from)?
             .ok_or(ServiceError::NotFound("Veículo não encontrado".to_string()))?;
+        if !scope.contains(current.department_id) {
+            return Err(ServiceError::NotFound("Veículo não encontrado".to_string()));
+        }
         self.status_history_repo.list_by_vehicle(vehicle_id).await.map_err(ServiceError::from)
     }
 
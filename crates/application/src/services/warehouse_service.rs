@@ -1,8 +1,16 @@
 use crate::errors::ServiceError;
+use crate::services::approval_chain_service::ApprovalChainService;
 use crate::services::stock_movement_service::{
     ProcessMovementInput, StockMovementService, StockMovementType,
 };
-use domain::{models::warehouse::*, ports::warehouse::*};
+use domain::{
+    models::warehouse::*,
+    models::{
+        ApprovalDocumentContext, ApprovalDocumentType, ApprovalProgress,
+        ApprovalStepDecisionPayload, DocumentApprovalStepDto, UnitScope,
+    },
+    ports::warehouse::*,
+};
 use rust_decimal::Decimal;
 use sqlx::PgPool;
 use std::sync::Arc;
@@ -17,6 +25,7 @@ pub struct WarehouseService {
     stock_repo: Arc<dyn WarehouseStockRepositoryPort>,
     stock_movement_service: Arc<StockMovementService>,
     disposal_request_repo: Arc<dyn DisposalRequestRepositoryPort>,
+    approval_chain_service: Arc<ApprovalChainService>,
 }
 
 impl WarehouseService {
@@ -26,6 +35,7 @@ impl WarehouseService {
         stock_repo: Arc<dyn WarehouseStockRepositoryPort>,
         stock_movement_service: Arc<StockMovementService>,
         disposal_request_repo: Arc<dyn DisposalRequestRepositoryPort>,
+        approval_chain_service: Arc<ApprovalChainService>,
     ) -> Self {
         Self {
             pool,
@@ -33,7 +43,81 @@ impl WarehouseService {
             stock_repo,
             stock_movement_service,
             disposal_request_repo,
+            approval_chain_service,
+        }
+    }
+
+    // ============================
+    // Unit scope
+    // ============================
+
+    /// Changes to a warehouse (and reads nested under it) require its
+    /// responsible unit to be in the caller's scope
+    fn check_unit_in_scope(
+        responsible_unit_id: Option<Uuid>,
+        scope: &UnitScope,
+    ) -> Result<(), ServiceError> {
+        if !scope.contains(responsible_unit_id) {
+            return Err(ServiceError::Forbidden(
+                "Almoxarifado fora do seu escopo de atuação".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    async fn ensure_warehouse_in_scope(
+        &self,
+        warehouse_id: Uuid,
+        scope: &UnitScope,
+    ) -> Result<(), ServiceError> {
+        if matches!(scope, UnitScope::All) {
+            return Ok(());
+        }
+        let warehouse = self
+            .warehouse_repo
+            .find_by_id(warehouse_id)
+            .await
+            .map_err(ServiceError::from)?
+            .ok_or(ServiceError::NotFound(
+                "Almoxarifado não encontrado".to_string(),
+            ))?;
+        Self::check_unit_in_scope(warehouse.responsible_unit_id, scope)
+    }
+
+    async fn ensure_stock_in_scope(
+        &self,
+        stock_id: Uuid,
+        scope: &UnitScope,
+    ) -> Result<(), ServiceError> {
+        if matches!(scope, UnitScope::All) {
+            return Ok(());
+        }
+        let stock = self
+            .stock_repo
+            .find_by_id(stock_id)
+            .await
+            .map_err(ServiceError::from)?
+            .ok_or(ServiceError::NotFound("Estoque não encontrado".to_string()))?;
+        self.ensure_warehouse_in_scope(stock.warehouse_id, scope).await
+    }
+
+    async fn ensure_disposal_in_scope(
+        &self,
+        request_id: Uuid,
+        scope: &UnitScope,
+    ) -> Result<(), ServiceError> {
+        if matches!(scope, UnitScope::All) {
+            return Ok(());
         }
+        let request = self
+            .disposal_request_repo
+            .find_by_id(request_id)
+            .await
+            .map_err(ServiceError::from)?
+            .ok_or(ServiceError::NotFound(
+                "Pedido de desfazimento não encontrado".to_string(),
+            ))?;
+        self.ensure_warehouse_in_scope(request.warehouse_id, scope).await
     }
 
     // ============================
@@ -43,7 +127,10 @@ impl WarehouseService {
     pub async fn create_warehouse(
         &self,
         payload: CreateWarehousePayload,
+        scope: &UnitScope,
     ) -> Result<WarehouseWithDetailsDto, ServiceError> {
+        Self::check_unit_in_scope(payload.responsible_unit_id, scope)?;
+
         if payload.name.trim().is_empty() {
             return Err(ServiceError::BadRequest(
                 "Nome do almoxarifado é obrigatório".to_string(),
@@ -107,11 +194,33 @@ impl WarehouseService {
             ))
     }
 
+    /// Warehouses whose responsible unit is outside the caller's scope are
+    /// reported as not found
+    pub async fn get_warehouse_in_scope(
+        &self,
+        id: Uuid,
+        scope: &UnitScope,
+    ) -> Result<WarehouseWithDetailsDto, ServiceError> {
+        let warehouse = self.get_warehouse(id).await?;
+        if !scope.contains(warehouse.responsible_unit_id) {
+            return Err(ServiceError::NotFound(
+                "Almoxarifado não encontrado".to_string(),
+            ));
+        }
+        Ok(warehouse)
+    }
+
     pub async fn update_warehouse(
         &self,
         id: Uuid,
         payload: UpdateWarehousePayload,
+        scope: &UnitScope,
     ) -> Result<WarehouseWithDetailsDto, ServiceError> {
+        self.ensure_warehouse_in_scope(id, scope).await?;
+        if payload.responsible_unit_id.is_some() {
+            Self::check_unit_in_scope(payload.responsible_unit_id, scope)?;
+        }
+
         let _ = self
             .warehouse_repo
             .find_by_id(id)
@@ -164,7 +273,13 @@ impl WarehouseService {
             ))
     }
 
-    pub async fn delete_warehouse(&self, id: Uuid) -> Result<bool, ServiceError> {
+    pub async fn delete_warehouse(
+        &self,
+        id: Uuid,
+        scope: &UnitScope,
+    ) -> Result<bool, ServiceError> {
+        self.ensure_warehouse_in_scope(id, scope).await?;
+
         let _ = self
             .warehouse_repo
             .find_by_id(id)
@@ -180,6 +295,7 @@ impl WarehouseService {
             .map_err(ServiceError::from)
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub async fn list_warehouses(
         &self,
         limit: i64,
@@ -188,9 +304,18 @@ impl WarehouseService {
         warehouse_type: Option<WarehouseType>,
         city_id: Option<Uuid>,
         is_active: Option<bool>,
+        scope: &UnitScope,
     ) -> Result<(Vec<WarehouseWithDetailsDto>, i64), ServiceError> {
         self.warehouse_repo
-            .list(limit, offset, search, warehouse_type, city_id, is_active)
+            .list(
+                limit,
+                offset,
+                search,
+                warehouse_type,
+                city_id,
+                is_active,
+                scope.unit_ids(),
+            )
             .await
             .map_err(ServiceError::from)
     }
@@ -199,7 +324,13 @@ impl WarehouseService {
     // Warehouse Stock
     // ============================
 
-    pub async fn get_stock(&self, id: Uuid) -> Result<WarehouseStockWithDetailsDto, ServiceError> {
+    pub async fn get_stock(
+        &self,
+        id: Uuid,
+        scope: &UnitScope,
+    ) -> Result<WarehouseStockWithDetailsDto, ServiceError> {
+        self.ensure_stock_in_scope(id, scope).await?;
+
         self.stock_repo
             .find_with_details_by_id(id)
             .await
@@ -214,7 +345,10 @@ impl WarehouseService {
         offset: i64,
         search: Option<String>,
         is_blocked: Option<bool>,
+        scope: &UnitScope,
     ) -> Result<(Vec<WarehouseStockWithDetailsDto>, i64), ServiceError> {
+        self.ensure_warehouse_in_scope(warehouse_id, scope).await?;
+
         let _ = self
             .warehouse_repo
             .find_by_id(warehouse_id)
@@ -234,7 +368,10 @@ impl WarehouseService {
         &self,
         id: Uuid,
         payload: UpdateStockParamsPayload,
+        scope: &UnitScope,
     ) -> Result<WarehouseStockDto, ServiceError> {
+        self.ensure_stock_in_scope(id, scope).await?;
+
         let _ = self
             .stock_repo
             .find_by_id(id)
@@ -261,7 +398,10 @@ impl WarehouseService {
         id: Uuid,
         payload: BlockStockPayload,
         blocked_by: Uuid,
+        scope: &UnitScope,
     ) -> Result<WarehouseStockDto, ServiceError> {
+        self.ensure_stock_in_scope(id, scope).await?;
+
         let current = self
             .stock_repo
             .find_by_id(id)
@@ -287,7 +427,13 @@ impl WarehouseService {
             .map_err(ServiceError::from)
     }
 
-    pub async fn unblock_stock(&self, id: Uuid) -> Result<WarehouseStockDto, ServiceError> {
+    pub async fn unblock_stock(
+        &self,
+        id: Uuid,
+        scope: &UnitScope,
+    ) -> Result<WarehouseStockDto, ServiceError> {
+        self.ensure_stock_in_scope(id, scope).await?;
+
         let current = self
             .stock_repo
             .find_by_id(id)
@@ -318,7 +464,10 @@ impl WarehouseService {
         warehouse_id: Uuid,
         payload: StandaloneEntryPayload,
         user_id: Uuid,
+        scope: &UnitScope,
     ) -> Result<StandaloneEntryResult, ServiceError> {
+        self.ensure_warehouse_in_scope(warehouse_id, scope).await?;
+
         // Validate warehouse exists and check hierarchy (RN-001)
         let warehouse = self
             .warehouse_repo
@@ -427,7 +576,10 @@ impl WarehouseService {
         warehouse_id: Uuid,
         payload: ReturnEntryPayload,
         user_id: Uuid,
+        scope: &UnitScope,
     ) -> Result<ReturnEntryResult, ServiceError> {
+        self.ensure_warehouse_in_scope(warehouse_id, scope).await?;
+
         let _ = self
             .warehouse_repo
             .find_by_id(warehouse_id)
@@ -535,7 +687,10 @@ impl WarehouseService {
         warehouse_id: Uuid,
         payload: CreateDisposalRequestPayload,
         user_id: Uuid,
+        scope: &UnitScope,
     ) -> Result<DisposalRequestWithItemsDto, ServiceError> {
+        self.ensure_warehouse_in_scope(warehouse_id, scope).await?;
+
         let _ = self
             .warehouse_repo
             .find_by_id(warehouse_id)
@@ -617,12 +772,94 @@ impl WarehouseService {
             ))
     }
 
+    /// Value of the disposal at the warehouse's average cost of its items
+    async fn disposal_approval_document(
+        &self,
+        request: &DisposalRequestDto,
+    ) -> Result<ApprovalDocumentContext, ServiceError> {
+        let (value, responsible_unit_id): (Decimal, Option<Uuid>) = sqlx::query_as(
+            r#"SELECT
+                COALESCE((
+                    SELECT SUM(i.quantity_raw * i.conversion_factor * COALESCE(ws.average_unit_value, 0))
+                    FROM disposal_request_items i
+                    LEFT JOIN warehouse_stocks ws
+                      ON ws.warehouse_id = w.id AND ws.catalog_item_id = i.catalog_item_id
+                    WHERE i.disposal_request_id = $1
+                ), 0),
+                w.responsible_unit_id
+               FROM warehouses w
+               WHERE w.id = $2"#,
+        )
+        .bind(request.id)
+        .bind(request.warehouse_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| ServiceError::Internal(e.to_string()))?;
+
+        Ok(ApprovalDocumentContext {
+            document_type: ApprovalDocumentType::DisposalRequest,
+            document_id: request.id,
+            value,
+            priority: None,
+            warehouse_id: Some(request.warehouse_id),
+            destination_unit_id: responsible_unit_id,
+        })
+    }
+
+    pub async fn get_disposal_approval_steps(
+        &self,
+        request_id: Uuid,
+        scope: &UnitScope,
+    ) -> Result<Vec<DocumentApprovalStepDto>, ServiceError> {
+        self.ensure_disposal_in_scope(request_id, scope).await?;
+
+        let request = self.get_disposal_request(request_id).await?.request;
+        self.approval_chain_service
+            .steps_for(&self.disposal_approval_document(&request).await?)
+            .await
+    }
+
+    /// Approves the caller's step of the disposal's approval chain. The
+    /// Gov.br signature can only be confirmed once every step is approved.
+    pub async fn approve_disposal_step(
+        &self,
+        request_id: Uuid,
+        payload: ApprovalStepDecisionPayload,
+        approved_by: Uuid,
+        scope: &UnitScope,
+    ) -> Result<Vec<DocumentApprovalStepDto>, ServiceError> {
+        self.ensure_disposal_in_scope(request_id, scope).await?;
+
+        let request = self.get_disposal_request(request_id).await?.request;
+        if request.status != DisposalRequestStatus::AwaitingSignature {
+            return Err(ServiceError::BadRequest(format!(
+                "Pedido não pode ser aprovado. Status atual: {:?}",
+                request.status
+            )));
+        }
+
+        let document = self.disposal_approval_document(&request).await?;
+        let progress = self
+            .approval_chain_service
+            .decide(&document, approved_by, None, true, payload.comments.as_deref())
+            .await?;
+        if progress == ApprovalProgress::NoChain {
+            return Err(ServiceError::BadRequest(
+                "Nenhuma cadeia de aprovação se aplica a este pedido".to_string(),
+            ));
+        }
+        self.approval_chain_service.steps_for(&document).await
+    }
+
     /// RF-016: Confirma assinatura Gov.br e deduz estoque (LOSS) para cada item.
     pub async fn confirm_disposal_signature(
         &self,
         request_id: Uuid,
         signed_by: Uuid,
+        scope: &UnitScope,
     ) -> Result<DisposalRequestWithItemsDto, ServiceError> {
+        self.ensure_disposal_in_scope(request_id, scope).await?;
+
         let with_items = self
             .disposal_request_repo
             .find_with_items(request_id)
@@ -639,6 +876,10 @@ impl WarehouseService {
             )));
         }
 
+        self.approval_chain_service
+            .ensure_completed(&self.disposal_approval_document(&with_items.request).await?)
+            .await?;
+
         let mut tx = self
             .pool
             .begin()
@@ -727,7 +968,10 @@ impl WarehouseService {
         request_id: Uuid,
         payload: CancelDisposalRequestPayload,
         cancelled_by: Uuid,
+        scope: &UnitScope,
     ) -> Result<DisposalRequestWithItemsDto, ServiceError> {
+        self.ensure_disposal_in_scope(request_id, scope).await?;
+
         if payload.cancellation_reason.trim().is_empty() {
             return Err(ServiceError::BadRequest(
                 "Motivo de cancelamento é obrigatório".to_string(),
@@ -768,6 +1012,15 @@ impl WarehouseService {
             ))
     }
 
+    pub async fn get_disposal_request_in_scope(
+        &self,
+        request_id: Uuid,
+        scope: &UnitScope,
+    ) -> Result<DisposalRequestWithItemsDto, ServiceError> {
+        self.ensure_disposal_in_scope(request_id, scope).await?;
+        self.get_disposal_request(request_id).await
+    }
+
     pub async fn get_disposal_request(
         &self,
         request_id: Uuid,
@@ -787,7 +1040,10 @@ impl WarehouseService {
         limit: i64,
         offset: i64,
         status: Option<DisposalRequestStatus>,
+        scope: &UnitScope,
     ) -> Result<(Vec<DisposalRequestDto>, i64), ServiceError> {
+        self.ensure_warehouse_in_scope(warehouse_id, scope).await?;
+
         let _ = self
             .warehouse_repo
             .find_by_id(warehouse_id)
@@ -811,7 +1067,10 @@ impl WarehouseService {
         offset: i64,
         catalog_item_id: Option<Uuid>,
         movement_type: Option<String>,
+        scope: &UnitScope,
     ) -> Result<(Vec<StockMovementDto>, i64), ServiceError> {
+        self.ensure_warehouse_in_scope(warehouse_id, scope).await?;
+
         let _ = self
             .warehouse_repo
             .find_by_id(warehouse_id)
@@ -893,7 +1152,10 @@ impl WarehouseService {
         warehouse_id: Uuid,
         payload: ManualExitPayload,
         user_id: Uuid,
+        scope: &UnitScope,
     ) -> Result<ManualExitResult, ServiceError> {
+        self.ensure_warehouse_in_scope(warehouse_id, scope).await?;
+
         let _ = self
             .warehouse_repo
             .find_by_id(warehouse_id)
@@ -0,0 +1,487 @@
+use crate::errors::ServiceError;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use chrono::{Duration, Utc};
+use domain::models::{
+    FinishPasskeyCeremonyPayload, StoredWebauthnCredential, WebauthnChallenge,
+    WebauthnChallengeDto, WebauthnChallengeKind, WebauthnCredentialDto,
+};
+use domain::ports::{UserRepositoryPort, WebauthnRepositoryPort};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+use webauthn_rs::prelude::{
+    AuthenticationResult, DiscoverableAuthentication, DiscoverableKey, Passkey,
+    PasskeyAuthentication, PasskeyRegistration, PublicKeyCredential, RegisterPublicKeyCredential,
+    Url, Webauthn, WebauthnBuilder, WebauthnError,
+};
+
+/// Validade de um desafio entre `start` e `finish` (igual ao timeout do navegador).
+const CHALLENGE_EXPIRY_MINUTES: i64 = 5;
+
+/// Identificação do Relying Party WebAuthn.
+#[derive(Debug, Clone)]
+pub struct WebauthnSettings {
+    /// Domínio efetivo (ex.: "sistema.ufmt.br"); credenciais ficam presas a ele
+    pub rp_id: String,
+    /// Origem completa do frontend (ex.: "https://sistema.ufmt.br")
+    pub rp_origin: String,
+    pub rp_name: String,
+}
+
+/// Estado persistido do cadastro: o nome escolhido acompanha o desafio.
+#[derive(Serialize, Deserialize)]
+struct RegistrationState {
+    name: String,
+    registration: PasskeyRegistration,
+}
+
+/// Autenticação WebAuthn verificada.
+#[derive(Debug, Clone)]
+pub struct PasskeyAssertion {
+    pub user_id: Uuid,
+    pub credential_id: Uuid,
+}
+
+/// Cadastro e uso de chaves de segurança / passkeys (WebAuthn).
+///
+/// As credenciais servem como segundo fator no desafio MFA do login e, por
+/// serem verificadas com `userVerification = required`, também como login
+/// sem senha. A regressão do contador de assinaturas marca a credencial como
+/// possivelmente clonada e a bloqueia.
+pub struct WebauthnService {
+    repo: Arc<dyn WebauthnRepositoryPort>,
+    user_repo: Arc<dyn UserRepositoryPort>,
+    webauthn: Webauthn,
+}
+
+impl WebauthnService {
+    pub fn new(
+        repo: Arc<dyn WebauthnRepositoryPort>,
+        user_repo: Arc<dyn UserRepositoryPort>,
+        settings: &WebauthnSettings,
+    ) -> Result<Self, ServiceError> {
+        let origin = Url::parse(&settings.rp_origin).map_err(|e| {
+            ServiceError::Internal(format!("WebAuthn RP origin inválida: {}", e))
+        })?;
+        let webauthn = WebauthnBuilder::new(&settings.rp_id, &origin)
+            .and_then(|b| b.rp_name(&settings.rp_name).build())
+            .map_err(|e| ServiceError::Internal(format!("Configuração WebAuthn inválida: {}", e)))?;
+
+        Ok(Self {
+            repo,
+            user_repo,
+            webauthn,
+        })
+    }
+
+    // ── Cadastro ─────────────────────────────────────────────────────────
+
+    pub async fn start_registration(
+        &self,
+        user_id: Uuid,
+        name: &str,
+    ) -> Result<WebauthnChallengeDto, ServiceError> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(ServiceError::BadRequest("Nome da credencial é obrigatório".to_string()));
+        }
+
+        let user = self
+            .user_repo
+            .find_by_id(user_id)
+            .await?
+            .ok_or_else(|| ServiceError::NotFound("Usuário não encontrado".to_string()))?;
+
+        let existing = self.repo.list_by_user(user_id).await?;
+        if existing.iter().any(|c| c.name.eq_ignore_ascii_case(name)) {
+            return Err(ServiceError::Conflict(format!(
+                "Já existe uma credencial chamada '{}'",
+                name
+            )));
+        }
+        let exclude = existing
+            .iter()
+            .filter_map(|c| URL_SAFE_NO_PAD.decode(&c.credential_id).ok())
+            .map(Into::into)
+            .collect::<Vec<_>>();
+
+        let (options, registration) = self
+            .webauthn
+            .start_passkey_registration(
+                user_id,
+                user.username.as_str(),
+                user.username.as_str(),
+                Some(exclude),
+            )
+            .map_err(|e| ServiceError::Internal(format!("WebAuthn: {}", e)))?;
+
+        let mut options = to_json(&options)?;
+        prefer_resident_key(&mut options);
+
+        let state = RegistrationState {
+            name: name.to_string(),
+            registration,
+        };
+        self.save_challenge(Some(user_id), WebauthnChallengeKind::Registration, &state, options)
+            .await
+    }
+
+    pub async fn finish_registration(
+        &self,
+        user_id: Uuid,
+        payload: FinishPasskeyCeremonyPayload,
+    ) -> Result<WebauthnCredentialDto, ServiceError> {
+        let state: RegistrationState = self
+            .take_challenge(payload.challenge_id, WebauthnChallengeKind::Registration, Some(user_id))
+            .await?;
+        let response: RegisterPublicKeyCredential = from_json(payload.credential)?;
+
+        let passkey = self
+            .webauthn
+            .finish_passkey_registration(&response, &state.registration)
+            .map_err(|e| {
+                tracing::warn!(user_id = %user_id, "WebAuthn registration rejected: {}", e);
+                ServiceError::BadRequest("Credencial WebAuthn inválida".to_string())
+            })?;
+
+        let credential_id = URL_SAFE_NO_PAD.encode(passkey.cred_id());
+        // A especificação exige recusar IDs já vinculados a qualquer conta
+        if self.repo.exists_by_credential_id(&credential_id).await? {
+            return Err(ServiceError::Conflict("Credencial já cadastrada".to_string()));
+        }
+
+        let stored = to_json(&passkey)?;
+        let backup_eligible = stored
+            .pointer("/cred/backup_eligible")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        let backup_state = stored
+            .pointer("/cred/backup_state")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
+        let credential = self
+            .repo
+            .create_credential(user_id, &state.name, &credential_id, &stored, backup_eligible, backup_state)
+            .await?;
+
+        tracing::info!(user_id = %user_id, credential = %credential.id, "WebAuthn credential registered");
+        Ok(credential)
+    }
+
+    // ── Gestão ───────────────────────────────────────────────────────────
+
+    pub async fn list_credentials(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<WebauthnCredentialDto>, ServiceError> {
+        Ok(self.repo.list_by_user(user_id).await?)
+    }
+
+    pub async fn rename_credential(
+        &self,
+        user_id: Uuid,
+        id: Uuid,
+        name: &str,
+    ) -> Result<WebauthnCredentialDto, ServiceError> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(ServiceError::BadRequest("Nome da credencial é obrigatório".to_string()));
+        }
+        self.repo
+            .rename(user_id, id, name)
+            .await?
+            .ok_or_else(|| ServiceError::NotFound("Credencial não encontrada".to_string()))
+    }
+
+    pub async fn delete_credential(&self, user_id: Uuid, id: Uuid) -> Result<(), ServiceError> {
+        if !self.repo.delete(user_id, id).await? {
+            return Err(ServiceError::NotFound("Credencial não encontrada".to_string()));
+        }
+        tracing::info!(user_id = %user_id, credential = %id, "WebAuthn credential removed");
+        Ok(())
+    }
+
+    pub async fn has_active_credentials(&self, user_id: Uuid) -> Result<bool, ServiceError> {
+        Ok(!self.repo.list_active_stored_by_user(user_id).await?.is_empty())
+    }
+
+    pub async fn purge_expired_challenges(&self) -> Result<u64, ServiceError> {
+        Ok(self.repo.purge_expired_challenges().await?)
+    }
+
+    // ── Autenticação (segundo fator) ─────────────────────────────────────
+
+    /// Desafio restrito às credenciais ativas do usuário já identificado por senha.
+    pub async fn start_authentication(
+        &self,
+        user_id: Uuid,
+    ) -> Result<WebauthnChallengeDto, ServiceError> {
+        let passkeys = self
+            .repo
+            .list_active_stored_by_user(user_id)
+            .await?
+            .iter()
+            .map(|c| from_json::<Passkey>(c.passkey.clone()))
+            .collect::<Result<Vec<_>, _>>()?;
+        if passkeys.is_empty() {
+            return Err(ServiceError::BadRequest(
+                "Nenhuma chave de segurança cadastrada".to_string(),
+            ));
+        }
+
+        let (options, authentication) = self
+            .webauthn
+            .start_passkey_authentication(&passkeys)
+            .map_err(|e| ServiceError::Internal(format!("WebAuthn: {}", e)))?;
+
+        self.save_challenge(
+            Some(user_id),
+            WebauthnChallengeKind::Authentication,
+            &authentication,
+            to_json(&options)?,
+        )
+        .await
+    }
+
+    pub async fn finish_authentication(
+        &self,
+        user_id: Uuid,
+        payload: FinishPasskeyCeremonyPayload,
+    ) -> Result<PasskeyAssertion, ServiceError> {
+        let state: PasskeyAuthentication = self
+            .take_challenge(payload.challenge_id, WebauthnChallengeKind::Authentication, Some(user_id))
+            .await?;
+        let response: PublicKeyCredential = from_json(payload.credential)?;
+
+        let stored = self.find_usable(&response, user_id).await?;
+        let result = self.check_assertion(
+            &stored,
+            self.webauthn.finish_passkey_authentication(&response, &state),
+        );
+        self.record_assertion(stored, result).await
+    }
+
+    // ── Login sem senha ──────────────────────────────────────────────────
+
+    /// Desafio sem lista de credenciais: o navegador oferece as passkeys
+    /// residentes deste RP e o usuário é identificado pelo `userHandle`.
+    pub async fn start_passwordless(&self) -> Result<WebauthnChallengeDto, ServiceError> {
+        let (options, authentication) = self
+            .webauthn
+            .start_discoverable_authentication()
+            .map_err(|e| ServiceError::Internal(format!("WebAuthn: {}", e)))?;
+
+        self.save_challenge(
+            None,
+            WebauthnChallengeKind::Discoverable,
+            &authentication,
+            to_json(&options)?,
+        )
+        .await
+    }
+
+    pub async fn finish_passwordless(
+        &self,
+        payload: FinishPasskeyCeremonyPayload,
+    ) -> Result<PasskeyAssertion, ServiceError> {
+        let state: DiscoverableAuthentication = self
+            .take_challenge(payload.challenge_id, WebauthnChallengeKind::Discoverable, None)
+            .await?;
+        let response: PublicKeyCredential = from_json(payload.credential)?;
+
+        let (user_id, _) = self
+            .webauthn
+            .identify_discoverable_authentication(&response)
+            .map_err(|_| ServiceError::InvalidCredentials)?;
+
+        let stored = self.find_usable(&response, user_id).await?;
+        let passkey: Passkey = from_json(stored.passkey.clone())?;
+        let result = self.check_assertion(
+            &stored,
+            self.webauthn.finish_discoverable_authentication(
+                &response,
+                state,
+                &[DiscoverableKey::from(&passkey)],
+            ),
+        );
+        self.record_assertion(stored, result).await
+    }
+
+    // ── Auxiliares ───────────────────────────────────────────────────────
+
+    async fn save_challenge<S: Serialize>(
+        &self,
+        user_id: Option<Uuid>,
+        kind: WebauthnChallengeKind,
+        state: &S,
+        options: serde_json::Value,
+    ) -> Result<WebauthnChallengeDto, ServiceError> {
+        let challenge = WebauthnChallenge {
+            id: Uuid::new_v4(),
+            user_id,
+            kind,
+            state: to_json(state)?,
+            expires_at: Utc::now() + Duration::minutes(CHALLENGE_EXPIRY_MINUTES),
+        };
+        self.repo.save_challenge(&challenge).await?;
+
+        Ok(WebauthnChallengeDto {
+            challenge_id: challenge.id,
+            options,
+            expires_at: challenge.expires_at,
+        })
+    }
+
+    /// Consome o desafio, conferindo tipo, dono e validade.
+    async fn take_challenge<S: DeserializeOwned>(
+        &self,
+        id: Uuid,
+        kind: WebauthnChallengeKind,
+        user_id: Option<Uuid>,
+    ) -> Result<S, ServiceError> {
+        let challenge = self
+            .repo
+            .take_challenge(id)
+            .await?
+            .filter(|c| c.kind == kind && c.user_id == user_id && c.expires_at > Utc::now())
+            .ok_or_else(|| ServiceError::BadRequest("Desafio WebAuthn inválido ou expirado".to_string()))?;
+        from_json(challenge.state)
+    }
+
+    /// Credencial apresentada, desde que pertença ao usuário e não esteja bloqueada.
+    async fn find_usable(
+        &self,
+        response: &PublicKeyCredential,
+        user_id: Uuid,
+    ) -> Result<StoredWebauthnCredential, ServiceError> {
+        let credential_id = URL_SAFE_NO_PAD.encode(response.get_credential_id());
+        let stored = self
+            .repo
+            .find_stored_by_credential_id(&credential_id)
+            .await?
+            .filter(|c| c.user_id == user_id)
+            .ok_or(ServiceError::InvalidCredentials)?;
+
+        if stored.clone_detected_at.is_some() {
+            tracing::warn!(user_id = %user_id, credential = %stored.id, "Blocked WebAuthn credential used");
+            return Err(ServiceError::InvalidCredentials);
+        }
+        Ok(stored)
+    }
+
+    fn check_assertion(
+        &self,
+        stored: &StoredWebauthnCredential,
+        result: Result<AuthenticationResult, WebauthnError>,
+    ) -> Result<AuthenticationResult, WebauthnError> {
+        if let Ok(r) = &result {
+            if counter_regressed(stored.sign_count, r.counter()) {
+                return Err(WebauthnError::CredentialPossibleCompromise);
+            }
+        }
+        result
+    }
+
+    async fn record_assertion(
+        &self,
+        stored: StoredWebauthnCredential,
+        result: Result<AuthenticationResult, WebauthnError>,
+    ) -> Result<PasskeyAssertion, ServiceError> {
+        let result = match result {
+            Ok(r) => r,
+            Err(WebauthnError::CredentialPossibleCompromise) => {
+                self.repo.flag_clone_detected(&stored.credential_id).await?;
+                tracing::warn!(
+                    user_id = %stored.user_id,
+                    credential = %stored.id,
+                    stored_count = stored.sign_count,
+                    "WebAuthn signature counter regressed: possible cloned authenticator, credential blocked"
+                );
+                return Err(ServiceError::InvalidCredentials);
+            }
+            Err(e) => {
+                tracing::warn!(user_id = %stored.user_id, "WebAuthn assertion rejected: {}", e);
+                return Err(ServiceError::InvalidCredentials);
+            }
+        };
+
+        let mut passkey: Passkey = from_json(stored.passkey)?;
+        passkey.update_credential(&result);
+        self.repo
+            .record_use(
+                stored.id,
+                &to_json(&passkey)?,
+                i64::from(result.counter()),
+                result.backup_state(),
+            )
+            .await?;
+
+        Ok(PasskeyAssertion {
+            user_id: stored.user_id,
+            credential_id: stored.id,
+        })
+    }
+}
+
+/// Contador não nulo que não avançou em relação ao armazenado (WebAuthn §7.2, passo 21).
+pub fn counter_regressed(stored: i64, presented: u32) -> bool {
+    (presented > 0 || stored > 0) && i64::from(presented) <= stored
+}
+
+/// Pede credencial residente para que a mesma passkey sirva ao login sem senha.
+fn prefer_resident_key(options: &mut serde_json::Value) {
+    if let Some(selection) = options
+        .pointer_mut("/publicKey/authenticatorSelection")
+        .and_then(|v| v.as_object_mut())
+    {
+        selection.insert("residentKey".to_string(), "preferred".into());
+    }
+}
+
+fn to_json<T: Serialize>(value: &T) -> Result<serde_json::Value, ServiceError> {
+    serde_json::to_value(value).map_err(|e| ServiceError::Internal(e.to_string()))
+}
+
+fn from_json<T: DeserializeOwned>(value: serde_json::Value) -> Result<T, ServiceError> {
+    serde_json::from_value(value)
+        .map_err(|e| ServiceError::BadRequest(format!("Dados WebAuthn inválidos: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counter_regression_detects_clones() {
+        // Passkeys sincronizadas não têm contador: sempre zero
+        assert!(!counter_regressed(0, 0));
+        assert!(!counter_regressed(5, 6));
+        assert!(counter_regressed(5, 5));
+        assert!(counter_regressed(5, 3));
+        // Contador zerado depois de já ter avançado também é suspeito
+        assert!(counter_regressed(5, 0));
+    }
+
+    #[test]
+    fn registration_options_prefer_resident_keys() {
+        let webauthn = WebauthnBuilder::new("example.com", &Url::parse("https://example.com").unwrap())
+            .unwrap()
+            .build()
+            .unwrap();
+        let (options, _) = webauthn
+            .start_passkey_registration(Uuid::new_v4(), "maria", "maria", None)
+            .unwrap();
+
+        let mut options = to_json(&options).unwrap();
+        prefer_resident_key(&mut options);
+        assert_eq!(
+            options["publicKey"]["authenticatorSelection"]["residentKey"],
+            "preferred"
+        );
+        assert_eq!(
+            options["publicKey"]["authenticatorSelection"]["userVerification"],
+            "required"
+        );
+    }
+}
@@ -231,6 +231,11 @@ impl SiorgSyncWorkerCore {
         item: &SiorgSyncQueueItem,
     ) -> Result<ProcessingResult, String> {
         match item.operation {
+            SiorgChangeType::Creation | SiorgChangeType::Update
+                if self.sync_service.requires_change_approval().await =>
+            {
+                Ok(ProcessingResult::Conflict(pending_approval(item)))
+            }
             SiorgChangeType::Creation | SiorgChangeType::Update => {
                 // Sync organization from SIORG
                 let org = self
@@ -289,6 +294,11 @@ impl SiorgSyncWorkerCore {
     /// Process unit sync
     async fn process_unit(&self, item: &SiorgSyncQueueItem) -> Result<ProcessingResult, String> {
         match item.operation {
+            SiorgChangeType::Creation | SiorgChangeType::Update
+                if self.sync_service.requires_change_approval().await =>
+            {
+                Ok(ProcessingResult::Conflict(pending_approval(item)))
+            }
             SiorgChangeType::Creation | SiorgChangeType::Update => {
                 // Sync unit from SIORG
                 let unit = self
@@ -419,7 +429,15 @@ impl SiorgSyncWorkerCore {
 
 enum ProcessingResult {
     Success,
-    #[allow(dead_code)]
     Conflict(serde_json::Value),
     Skip(String),
 }
+
+/// Com `siorg.require_change_approval` ligado, a alteração vai para a fila de
+/// conflitos e só é aplicada quando um administrador a resolve.
+fn pending_approval(item: &SiorgSyncQueueItem) -> serde_json::Value {
+    serde_json::json!({
+        "reason": "Alteração do SIORG aguarda aprovação do administrador",
+        "detected_changes": item.detected_changes,
+    })
+}
@@ -3,6 +3,8 @@
 //! - AES-256-GCM with random nonce for confidentiality
 //! - HMAC-SHA256 blind index for deterministic lookups (emails, etc.)
 //! - Key parsing from hex-encoded environment variable
+//! - Versioned keyring: new values carry the id of the key that sealed them,
+//!   so older keys keep decrypting while rows are re-encrypted
 
 use aes_gcm::{
     aead::{Aead, KeyInit},
@@ -13,9 +15,16 @@ use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
 use hmac::{Hmac, Mac};
 use rand::RngCore;
 use sha2::Sha256;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
 
 const NONCE_SIZE: usize = 12;
 
+/// Envelope marker: keyring ciphertexts look like `k<key id>:<base64url>`.
+/// Base64url never contains `:`, so legacy values are unambiguous.
+const ENVELOPE_PREFIX: char = 'k';
+
 /// Encrypts `plaintext` using AES-256-GCM with a random 96-bit nonce.
 /// Returns a base64url-encoded string of the form `nonce || ciphertext`.
 pub fn encrypt_field(plaintext: &str, key: &[u8; 32]) -> Result<String> {
@@ -89,6 +98,152 @@ pub fn parse_key(hex_key: &str) -> Result<[u8; 32]> {
     Ok(key)
 }
 
+/// Versioned set of field encryption keys.
+///
+/// The current key encrypts new values and computes blind indexes; every key
+/// in the ring decrypts. Values written before the keyring existed carry no
+/// key id and are tried against all keys.
+#[derive(Clone)]
+pub struct FieldKeyring {
+    current_id: u32,
+    keys: Arc<BTreeMap<u32, [u8; 32]>>,
+}
+
+impl FieldKeyring {
+    /// Keyring holding only the current key.
+    pub fn new(current_id: u32, current_key: [u8; 32]) -> Self {
+        Self {
+            current_id,
+            keys: Arc::new(BTreeMap::from([(current_id, current_key)])),
+        }
+    }
+
+    /// Loads the keyring from configuration: the current key with its id and
+    /// the previous keys still accepted for decryption, as
+    /// `<id>:<hex>,<id>:<hex>` (WS_FIELD_ENCRYPTION_PREVIOUS_KEYS).
+    pub fn from_config(
+        current_key_hex: &str,
+        current_id: u32,
+        previous_keys: &str,
+    ) -> Result<Self> {
+        let mut keys = BTreeMap::from([(current_id, parse_key(current_key_hex)?)]);
+
+        for entry in previous_keys
+            .split(',')
+            .map(str::trim)
+            .filter(|e| !e.is_empty())
+        {
+            let (id, hex_key) = entry.split_once(':').ok_or_else(|| {
+                anyhow::anyhow!(
+                    "WS_FIELD_ENCRYPTION_PREVIOUS_KEYS entries must be `<id>:<hex key>`"
+                )
+            })?;
+            let id: u32 = id
+                .trim()
+                .parse()
+                .map_err(|_| anyhow::anyhow!("Invalid field encryption key id `{}`", id.trim()))?;
+            if keys.insert(id, parse_key(hex_key)?).is_some() {
+                bail!(
+                    "Field encryption key id {} is configured more than once",
+                    id
+                );
+            }
+        }
+
+        Ok(Self {
+            current_id,
+            keys: Arc::new(keys),
+        })
+    }
+
+    /// Id of the key used for new values.
+    pub fn current_id(&self) -> u32 {
+        self.current_id
+    }
+
+    /// Ids of every key in the ring, ascending.
+    pub fn key_ids(&self) -> Vec<u32> {
+        self.keys.keys().copied().collect()
+    }
+
+    fn current_key(&self) -> &[u8; 32] {
+        &self.keys[&self.current_id]
+    }
+
+    /// Current key first, then the others from newest to oldest.
+    fn keys_by_preference(&self) -> impl Iterator<Item = &[u8; 32]> {
+        std::iter::once(self.current_key()).chain(
+            self.keys
+                .iter()
+                .rev()
+                .filter(|(id, _)| **id != self.current_id)
+                .map(|(_, key)| key),
+        )
+    }
+
+    /// Encrypts with the current key into a `k<id>:<base64url>` envelope.
+    pub fn encrypt(&self, plaintext: &str) -> Result<String> {
+        let sealed = encrypt_field(plaintext, self.current_key())?;
+        Ok(format!("{}{}:{}", ENVELOPE_PREFIX, self.current_id, sealed))
+    }
+
+    /// Decrypts an envelope with the key it names, or a legacy value with
+    /// whichever key opens it.
+    pub fn decrypt(&self, value: &str) -> Result<String> {
+        match split_envelope(value) {
+            Some((id, sealed)) => {
+                let key = self.keys.get(&id).ok_or_else(|| {
+                    anyhow::anyhow!("Field encryption key {} is not in the keyring", id)
+                })?;
+                decrypt_field(sealed, key)
+            }
+            None => self
+                .keys_by_preference()
+                .find_map(|key| decrypt_field(value, key).ok())
+                .ok_or_else(|| {
+                    anyhow::anyhow!("Decryption failed — no key in the keyring opens this value")
+                }),
+        }
+    }
+
+    /// Key id named by an envelope (`None` for legacy values).
+    pub fn key_id_of(value: &str) -> Option<u32> {
+        split_envelope(value).map(|(id, _)| id)
+    }
+
+    /// Whether `value` is already sealed with the current key.
+    pub fn is_current(&self, value: &str) -> bool {
+        Self::key_id_of(value) == Some(self.current_id)
+    }
+
+    /// Blind index under the current key, for writes.
+    pub fn blind_index(&self, value: &str) -> String {
+        blind_index(value, self.current_key())
+    }
+
+    /// Blind indexes under every key, current first, for lookups that must
+    /// still match rows indexed before a rotation.
+    pub fn blind_indexes(&self, value: &str) -> Vec<String> {
+        self.keys_by_preference()
+            .map(|key| blind_index(value, key))
+            .collect()
+    }
+}
+
+impl fmt::Debug for FieldKeyring {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FieldKeyring")
+            .field("current_id", &self.current_id)
+            .field("key_ids", &self.key_ids())
+            .finish()
+    }
+}
+
+fn split_envelope(value: &str) -> Option<(u32, &str)> {
+    let (id, sealed) = value.strip_prefix(ENVELOPE_PREFIX)?.split_once(':')?;
+    Some((id.parse().ok()?, sealed))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -132,4 +287,59 @@ mod tests {
             blind_index("user@example.com", &key)
         );
     }
+
+    #[test]
+    fn keyring_envelope_names_current_key() {
+        let keyring = FieldKeyring::new(3, random_key());
+        let enc = keyring.encrypt("secret").unwrap();
+        assert!(enc.starts_with("k3:"));
+        assert_eq!(FieldKeyring::key_id_of(&enc), Some(3));
+        assert!(keyring.is_current(&enc));
+        assert_eq!(keyring.decrypt(&enc).unwrap(), "secret");
+    }
+
+    #[test]
+    fn keyring_decrypts_with_previous_and_legacy_keys() {
+        let (old, new) = (random_key(), random_key());
+        let old_ring = FieldKeyring::new(1, old);
+        let rotated =
+            FieldKeyring::from_config(&hex::encode(new), 2, &format!(" 1:{} ", hex::encode(old)))
+                .unwrap();
+
+        let sealed_with_old = old_ring.encrypt("old@example.com").unwrap();
+        let legacy = encrypt_field("legacy@example.com", &old).unwrap();
+
+        assert_eq!(rotated.key_ids(), vec![1, 2]);
+        assert!(!rotated.is_current(&sealed_with_old));
+        assert_eq!(
+            rotated.decrypt(&sealed_with_old).unwrap(),
+            "old@example.com"
+        );
+        assert_eq!(rotated.decrypt(&legacy).unwrap(), "legacy@example.com");
+        assert!(FieldKeyring::new(2, new).decrypt(&sealed_with_old).is_err());
+    }
+
+    #[test]
+    fn keyring_blind_indexes_cover_every_key() {
+        let (old, new) = (random_key(), random_key());
+        let rotated =
+            FieldKeyring::from_config(&hex::encode(new), 2, &format!("1:{}", hex::encode(old)))
+                .unwrap();
+
+        assert_eq!(
+            rotated.blind_indexes("User@Example.com"),
+            vec![
+                blind_index("user@example.com", &new),
+                blind_index("user@example.com", &old)
+            ]
+        );
+        assert_eq!(rotated.blind_index("a"), blind_index("a", &new));
+    }
+
+    #[test]
+    fn keyring_rejects_duplicate_ids() {
+        let key = hex::encode(random_key());
+        assert!(FieldKeyring::from_config(&key, 1, &format!("1:{}", key)).is_err());
+        assert!(FieldKeyring::from_config(&key, 2, "1").is_err());
+    }
 }
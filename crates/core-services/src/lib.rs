@@ -1,4 +1,6 @@
 pub mod field_encryption;
 pub mod jwt;
+pub mod request_context;
+pub mod secrets;
 pub mod security;
 pub mod session;
@@ -0,0 +1,79 @@
+//! Contexto da requisição HTTP em andamento (ator, origem e request ID),
+//! disponível para as camadas internas sem precisar ser repassado por
+//! parâmetro. Usado para atribuir alterações capturadas no banco e para
+//! correlacionar registros de auditoria.
+
+use std::future::Future;
+use std::sync::{Arc, OnceLock};
+use uuid::Uuid;
+
+tokio::task_local! {
+    static REQUEST_CONTEXT: RequestContext;
+}
+
+#[derive(Debug, Clone)]
+pub struct RequestContext {
+    pub request_id: Uuid,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    /// Preenchido pela autenticação, que roda depois da criação do contexto
+    actor: Arc<OnceLock<Uuid>>,
+}
+
+impl RequestContext {
+    pub fn new(request_id: Uuid, ip_address: Option<String>, user_agent: Option<String>) -> Self {
+        Self {
+            request_id,
+            ip_address,
+            user_agent,
+            actor: Arc::new(OnceLock::new()),
+        }
+    }
+
+    /// Usuário autenticado da requisição, se já identificado
+    pub fn actor(&self) -> Option<Uuid> {
+        self.actor.get().copied()
+    }
+}
+
+/// Executa `f` com `ctx` como contexto da requisição
+pub async fn scope<F: Future>(ctx: RequestContext, f: F) -> F::Output {
+    REQUEST_CONTEXT.scope(ctx, f).await
+}
+
+/// Contexto da requisição atual (`None` fora de uma requisição, ex.: jobs)
+pub fn current() -> Option<RequestContext> {
+    REQUEST_CONTEXT.try_with(|ctx| ctx.clone()).ok()
+}
+
+/// Registra o usuário autenticado; apenas a primeira chamada tem efeito
+pub fn set_actor(user_id: Uuid) {
+    let _ = REQUEST_CONTEXT.try_with(|ctx| ctx.actor.set(user_id));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_context_is_scoped_to_the_request() {
+        assert!(current().is_none());
+
+        let request_id = Uuid::new_v4();
+        let user_id = Uuid::new_v4();
+        scope(RequestContext::new(request_id, None, None), async {
+            assert_eq!(current().unwrap().actor(), None);
+            set_actor(user_id);
+            set_actor(Uuid::new_v4());
+
+            let ctx = current().unwrap();
+            assert_eq!(ctx.request_id, request_id);
+            assert_eq!(ctx.actor(), Some(user_id));
+        })
+        .await;
+
+        assert!(current().is_none());
+        // Fora de um escopo é um no-op
+        set_actor(user_id);
+    }
+}
@@ -0,0 +1,468 @@
+//! Configuração sensível (URLs de banco, chaves, credenciais, tokens de API).
+//!
+//! - Backends de `SecretsProvider`: ambiente, um arquivo por segredo (secrets
+//!   montados pelo Docker e Kubernetes) e HashiCorp Vault KV v2
+//! - `SecretStore` guarda os últimos valores lidos e os relê
+//!   periodicamente; quem consome lê no uso (`SecretHandle`) ou reage aos
+//!   nomes informados como alterados
+//! - O backend é escolhido por `SECRETS_PROVIDER`, da mesma forma em todos
+//!   os binários (ver `SecretStore::load_from_env`)
+
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+type Values = HashMap<&'static str, String>;
+
+/// Segredo conhecido por um binário: `name` é a chave nos arquivos e no
+/// Vault, `env` a variável de ambiente que o fornece.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Secret {
+    pub name: &'static str,
+    pub env: &'static str,
+}
+
+impl Secret {
+    pub const fn new(name: &'static str, env: &'static str) -> Self {
+        Self { name, env }
+    }
+
+    fn env_value(&self) -> Option<String> {
+        std::env::var(self.env).ok().filter(|v| !v.is_empty())
+    }
+}
+
+#[async_trait]
+pub trait SecretsProvider: Send + Sync {
+    /// Nome do backend, para logs
+    fn name(&self) -> &'static str;
+
+    /// Valores encontrados para os segredos pedidos; os ausentes são omitidos
+    async fn load(&self, secrets: &[Secret]) -> Result<Values>;
+}
+
+/// Lê cada segredo da sua variável de ambiente.
+pub struct EnvSecretsProvider;
+
+#[async_trait]
+impl SecretsProvider for EnvSecretsProvider {
+    fn name(&self) -> &'static str {
+        "env"
+    }
+
+    async fn load(&self, secrets: &[Secret]) -> Result<Values> {
+        Ok(secrets
+            .iter()
+            .filter_map(|s| s.env_value().map(|v| (s.name, v)))
+            .collect())
+    }
+}
+
+/// Lê cada segredo de `<dir>/<name>`, sem a quebra de linha final.
+pub struct FileSecretsProvider {
+    dir: PathBuf,
+}
+
+impl FileSecretsProvider {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+}
+
+#[async_trait]
+impl SecretsProvider for FileSecretsProvider {
+    fn name(&self) -> &'static str {
+        "file"
+    }
+
+    async fn load(&self, secrets: &[Secret]) -> Result<Values> {
+        let mut values = Values::new();
+        for secret in secrets {
+            let path = self.dir.join(secret.name);
+            match tokio::fs::read_to_string(&path).await {
+                Ok(value) => {
+                    let value = value.trim_end_matches(['\r', '\n']);
+                    if !value.is_empty() {
+                        values.insert(secret.name, value.to_string());
+                    }
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => {
+                    return Err(e).with_context(|| format!("Falha ao ler {}", path.display()))
+                }
+            }
+        }
+        Ok(values)
+    }
+}
+
+/// Lê todos os segredos de uma entrada do HashiCorp Vault KV v2, cujos campos
+/// são os nomes dos segredos.
+pub struct VaultKvProvider {
+    client: reqwest::Client,
+    url: String,
+    token: String,
+}
+
+impl VaultKvProvider {
+    pub fn new(addr: &str, token: String, mount: &str, path: &str) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .context("Falha ao criar cliente HTTP do Vault")?;
+        let url = format!(
+            "{}/v1/{}/data/{}",
+            addr.trim_end_matches('/'),
+            mount.trim_matches('/'),
+            path.trim_matches('/')
+        );
+        Ok(Self { client, url, token })
+    }
+}
+
+#[async_trait]
+impl SecretsProvider for VaultKvProvider {
+    fn name(&self) -> &'static str {
+        "vault"
+    }
+
+    async fn load(&self, secrets: &[Secret]) -> Result<Values> {
+        let response = self
+            .client
+            .get(&self.url)
+            .header("X-Vault-Token", &self.token)
+            .send()
+            .await
+            .context("Falha ao consultar o Vault")?;
+        let status = response.status();
+        if !status.is_success() {
+            bail!("Vault respondeu {} para {}", status, self.url);
+        }
+        let body: serde_json::Value = response
+            .json()
+            .await
+            .context("Resposta inválida do Vault")?;
+        let Some(fields) = body["data"]["data"].as_object() else {
+            bail!("Resposta do Vault sem data.data (o mount é KV v2?)");
+        };
+
+        Ok(secrets
+            .iter()
+            .filter_map(|s| {
+                let value = match fields.get(s.name)? {
+                    serde_json::Value::String(v) => v.clone(),
+                    serde_json::Value::Null => return None,
+                    other => other.to_string(),
+                };
+                (!value.is_empty()).then_some((s.name, value))
+            })
+            .collect())
+    }
+}
+
+/// Valor atual de um segredo; barato de clonar e sempre em dia com a última
+/// releitura do seu store.
+#[derive(Clone)]
+pub struct SecretHandle {
+    secret: Secret,
+    values: Arc<RwLock<Values>>,
+}
+
+impl SecretHandle {
+    /// Handle com valor fixo (ou o ambiente, quando `None`), para quem não
+    /// tem um store
+    pub fn fixed(secret: Secret, value: Option<String>) -> Self {
+        let values = value.map(|v| (secret.name, v)).into_iter().collect();
+        Self {
+            secret,
+            values: Arc::new(RwLock::new(values)),
+        }
+    }
+
+    pub fn get(&self) -> Option<String> {
+        self.values
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(self.secret.name)
+            .cloned()
+            .or_else(|| self.secret.env_value())
+    }
+}
+
+impl fmt::Debug for SecretHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SecretHandle")
+            .field("name", &self.secret.name)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Segredos de um binário conforme a última leitura do seu provider.
+///
+/// Valores que o provider não tem caem na variável de ambiente, então uma
+/// entrada parcial no Vault ou nos arquivos ainda funciona junto do `.env`.
+#[derive(Clone)]
+pub struct SecretStore {
+    provider: Arc<dyn SecretsProvider>,
+    secrets: &'static [Secret],
+    values: Arc<RwLock<Values>>,
+    refresh_interval: Duration,
+}
+
+impl SecretStore {
+    /// Store ainda não carregado: toda leitura vai ao ambiente
+    pub fn new(
+        provider: Arc<dyn SecretsProvider>,
+        secrets: &'static [Secret],
+        refresh_interval: Duration,
+    ) -> Self {
+        Self {
+            provider,
+            secrets,
+            values: Arc::new(RwLock::new(Values::new())),
+            refresh_interval,
+        }
+    }
+
+    /// Escolhe o backend pelo ambiente e faz a primeira leitura.
+    ///
+    /// | Variável               | Padrão         |
+    /// |------------------------|----------------|
+    /// | SECRETS_PROVIDER       | `env` (`env`, `file` ou `vault`) |
+    /// | SECRETS_DIR            | `/run/secrets` |
+    /// | SECRETS_REFRESH_SECS   | `300`          |
+    /// | VAULT_ADDR             | obrigatória para `vault` |
+    /// | VAULT_TOKEN            | obrigatória para `vault` |
+    /// | VAULT_KV_MOUNT         | `secret`       |
+    /// | VAULT_SECRET_PATH      | `waterswamp`   |
+    pub async fn load_from_env(secrets: &'static [Secret]) -> Result<Self> {
+        let var = |key: &str| std::env::var(key).ok().filter(|v| !v.is_empty());
+
+        let provider: Arc<dyn SecretsProvider> = match var("SECRETS_PROVIDER")
+            .unwrap_or_else(|| "env".to_string())
+            .to_lowercase()
+            .as_str()
+        {
+            "env" => Arc::new(EnvSecretsProvider),
+            "file" => Arc::new(FileSecretsProvider::new(
+                var("SECRETS_DIR").unwrap_or_else(|| "/run/secrets".to_string()),
+            )),
+            "vault" => Arc::new(VaultKvProvider::new(
+                &var("VAULT_ADDR").context("VAULT_ADDR deve estar definida")?,
+                var("VAULT_TOKEN").context("VAULT_TOKEN deve estar definida")?,
+                &var("VAULT_KV_MOUNT").unwrap_or_else(|| "secret".to_string()),
+                &var("VAULT_SECRET_PATH").unwrap_or_else(|| "waterswamp".to_string()),
+            )?),
+            other => bail!("SECRETS_PROVIDER desconhecido: {}", other),
+        };
+        let refresh_secs = var("SECRETS_REFRESH_SECS")
+            .map(|v| v.parse::<u64>().context("SECRETS_REFRESH_SECS inválida"))
+            .transpose()?
+            .unwrap_or(300);
+
+        let store = Self::new(provider, secrets, Duration::from_secs(refresh_secs.max(1)));
+        store
+            .refresh()
+            .await
+            .with_context(|| format!("Falha ao carregar segredos ({})", store.provider.name()))?;
+        Ok(store)
+    }
+
+    pub fn provider_name(&self) -> &'static str {
+        self.provider.name()
+    }
+
+    pub fn get(&self, secret: &Secret) -> Option<String> {
+        self.handle(secret).get()
+    }
+
+    pub fn handle(&self, secret: &Secret) -> SecretHandle {
+        SecretHandle {
+            secret: *secret,
+            values: self.values.clone(),
+        }
+    }
+
+    /// Relê os segredos e devolve os nomes cujo valor mudou
+    pub async fn refresh(&self) -> Result<Vec<&'static str>> {
+        let mut loaded = self.provider.load(self.secrets).await?;
+        for secret in self.secrets {
+            if !loaded.contains_key(secret.name) {
+                if let Some(value) = secret.env_value() {
+                    loaded.insert(secret.name, value);
+                }
+            }
+        }
+
+        let mut values = self.values.write().unwrap_or_else(|e| e.into_inner());
+        let changed = self
+            .secrets
+            .iter()
+            .map(|s| s.name)
+            .filter(|name| values.get(name) != loaded.get(name))
+            .collect();
+        *values = loaded;
+        Ok(changed)
+    }
+
+    /// Relê os segredos a cada `SECRETS_REFRESH_SECS` e chama `on_change`
+    /// com os nomes alterados. Falhas mantêm os valores anteriores.
+    pub fn spawn_refresh<F>(&self, on_change: F) -> tokio::task::JoinHandle<()>
+    where
+        F: Fn(&SecretStore, &[&'static str]) + Send + 'static,
+    {
+        let store = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(store.refresh_interval);
+            interval.tick().await;
+            loop {
+                interval.tick().await;
+                match store.refresh().await {
+                    Ok(changed) if changed.is_empty() => {}
+                    Ok(changed) => {
+                        tracing::info!(
+                            ?changed,
+                            provider = store.provider.name(),
+                            "Segredos atualizados"
+                        );
+                        on_change(&store, &changed);
+                    }
+                    Err(e) => tracing::error!(
+                        provider = store.provider.name(),
+                        "Falha ao recarregar segredos: {:#}",
+                        e
+                    ),
+                }
+            }
+        })
+    }
+}
+
+impl Default for SecretStore {
+    /// Só o ambiente, nada carregado
+    fn default() -> Self {
+        Self::new(Arc::new(EnvSecretsProvider), &[], Duration::from_secs(300))
+    }
+}
+
+impl fmt::Debug for SecretStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SecretStore")
+            .field("provider", &self.provider.name())
+            .field(
+                "secrets",
+                &self.secrets.iter().map(|s| s.name).collect::<Vec<_>>(),
+            )
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    static SECRETS: &[Secret] = &[
+        Secret::new("db_url", "CORE_SECRETS_TEST_DB_URL"),
+        Secret::new("api_token", "CORE_SECRETS_TEST_API_TOKEN"),
+    ];
+
+    struct FakeProvider(Mutex<Values>);
+
+    #[async_trait]
+    impl SecretsProvider for FakeProvider {
+        fn name(&self) -> &'static str {
+            "fake"
+        }
+
+        async fn load(&self, _secrets: &[Secret]) -> Result<Values> {
+            Ok(self.0.lock().unwrap().clone())
+        }
+    }
+
+    #[tokio::test]
+    async fn file_provider_reads_one_file_per_secret() {
+        let dir = std::env::temp_dir().join(format!("secrets-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("db_url"), "postgres://u:p@db/app\n").unwrap();
+
+        let values = FileSecretsProvider::new(&dir).load(SECRETS).await.unwrap();
+        assert_eq!(values.get("db_url").unwrap(), "postgres://u:p@db/app");
+        assert!(!values.contains_key("api_token"));
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn refresh_reports_changes_and_updates_handles() {
+        let provider = Arc::new(FakeProvider(Mutex::new(Values::from([(
+            "db_url",
+            "one".to_string(),
+        )]))));
+        let store = SecretStore::new(provider.clone(), SECRETS, Duration::from_secs(60));
+        let handle = store.handle(&SECRETS[0]);
+
+        assert_eq!(store.refresh().await.unwrap(), vec!["db_url"]);
+        assert_eq!(handle.get().as_deref(), Some("one"));
+        assert!(store.refresh().await.unwrap().is_empty());
+
+        provider
+            .0
+            .lock()
+            .unwrap()
+            .insert("db_url", "two".to_string());
+        assert_eq!(store.refresh().await.unwrap(), vec!["db_url"]);
+        assert_eq!(handle.get().as_deref(), Some("two"));
+    }
+
+    #[tokio::test]
+    async fn missing_values_fall_back_to_environment() {
+        std::env::set_var("CORE_SECRETS_TEST_API_TOKEN", "from-env");
+        let provider = Arc::new(FakeProvider(Mutex::new(Values::new())));
+        let store = SecretStore::new(provider, SECRETS, Duration::from_secs(60));
+        store.refresh().await.unwrap();
+
+        assert_eq!(store.get(&SECRETS[1]).as_deref(), Some("from-env"));
+        assert_eq!(store.get(&SECRETS[0]), None);
+        std::env::remove_var("CORE_SECRETS_TEST_API_TOKEN");
+    }
+
+    #[tokio::test]
+    async fn vault_provider_reads_kv_v2_entry() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = format!("http://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = vec![0u8; 4096];
+            let n = socket.read(&mut request).await.unwrap();
+            let request = String::from_utf8_lossy(&request[..n]).to_lowercase();
+            let body = r#"{"data":{"data":{"db_url":"postgres://vault","other":"x"},"metadata":{"version":3}}}"#;
+            socket
+                .write_all(
+                    format!(
+                        "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                        body.len(),
+                        body
+                    )
+                    .as_bytes(),
+                )
+                .await
+                .unwrap();
+            request
+        });
+
+        let provider = VaultKvProvider::new(&addr, "root".to_string(), "secret", "app").unwrap();
+        let values = provider.load(SECRETS).await.unwrap();
+        assert_eq!(values.get("db_url").unwrap(), "postgres://vault");
+        assert_eq!(values.len(), 1);
+
+        let request = server.await.unwrap();
+        assert!(request.starts_with("get /v1/secret/data/app "));
+        assert!(request.contains("x-vault-token: root"));
+    }
+}
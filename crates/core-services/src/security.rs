@@ -3,6 +3,7 @@ use argon2::{
     Algorithm, Argon2, Params, Version,
 };
 use axum::http::{header, HeaderName, HeaderValue, Method};
+use std::sync::OnceLock;
 use std::time::Duration;
 use tower_http::{cors::CorsLayer, set_header::SetResponseHeaderLayer};
 use zxcvbn::{zxcvbn, Score};
@@ -311,6 +312,32 @@ pub fn verify_password(password: &str, hash: &str) -> anyhow::Result<bool> {
         .is_ok())
 }
 
+/// Hash de referência para usuários inexistentes (gerado uma vez por processo).
+static DUMMY_PASSWORD_HASH: OnceLock<String> = OnceLock::new();
+
+/// Verifica a senha gastando o mesmo tempo quer o usuário exista ou não.
+///
+/// Sem hash (usuário não encontrado, conta sem senha local), a senha é
+/// verificada contra um hash descartável com os mesmos parâmetros Argon2id e
+/// o resultado é sempre `false`. Hash malformado também conta como senha
+/// inválida. Assim o tempo de resposta do login não revela quais
+/// usernames/emails existem.
+///
+/// ⚠️ Também é **blocking**: use `spawn_blocking` em contextos assíncronos.
+pub fn verify_password_or_dummy(password: &str, hash: Option<&str>) -> bool {
+    match hash {
+        Some(hash) => verify_password(password, hash).unwrap_or(false),
+        None => {
+            let dummy = DUMMY_PASSWORD_HASH.get_or_init(|| {
+                hash_password(&uuid::Uuid::new_v4().to_string())
+                    .expect("Falha ao gerar hash de referência")
+            });
+            let _ = verify_password(password, dummy);
+            false
+        }
+    }
+}
+
 // =============================================================================
 // TESTES
 // =============================================================================
@@ -369,6 +396,18 @@ mod tests {
         assert!(verify_password(password, &hash2).unwrap());
     }
 
+    #[test]
+    fn test_verify_password_or_dummy() {
+        let password = "Correct_P@ssw0rd_123";
+        let hash = hash_password(password).expect("Falha ao gerar hash");
+
+        assert!(verify_password_or_dummy(password, Some(&hash)));
+        assert!(!verify_password_or_dummy("Wrong_P@ssw0rd_456", Some(&hash)));
+        assert!(!verify_password_or_dummy(password, Some("invalid_hash_format")));
+        // Usuário inexistente: nunca valida, mesmo com qualquer senha
+        assert!(!verify_password_or_dummy(password, None));
+    }
+
     #[test]
     fn test_invalid_hash_format() {
         let result = verify_password("password", "invalid_hash_format");
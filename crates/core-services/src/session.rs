@@ -39,6 +39,9 @@ pub mod config {
     /// Session token length in bytes (256 bits)
     pub const SESSION_TOKEN_BYTES: usize = 32;
 
+    /// OIDC login state cookie (hash of the `state` sent to the provider)
+    pub const OIDC_STATE_COOKIE_NAME: &str = "oidc_state";
+
     /// CSRF token length in bytes (256 bits)
     pub const CSRF_TOKEN_BYTES: usize = 32;
 
@@ -212,6 +215,18 @@ pub fn build_csrf_cookie(csrf_token: &str, duration: Duration) -> Cookie<'static
         .build()
 }
 
+/// Builds the cookie that binds an OIDC login to the browser that started it.
+/// SameSite=Lax: the provider returns through a cross-site top-level redirect.
+pub fn build_oidc_state_cookie(state_hash: &str, duration: Duration) -> Cookie<'static> {
+    SecureCookieBuilder::new(config::OIDC_STATE_COOKIE_NAME, state_hash.to_string())
+        .max_age(duration)
+        .path("/")
+        .secure(true)
+        .http_only(true)
+        .same_site(SameSite::Lax)
+        .build()
+}
+
 /// Builds a cookie to delete/expire an existing cookie
 pub fn build_removal_cookie(name: &str) -> Cookie<'static> {
     Cookie::build((name.to_string(), ""))
@@ -0,0 +1,142 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+/// Non-human identity (SIAFI bridge, BI tools, fuel card imports) that owns API keys
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, FromRow)]
+pub struct ServicePrincipalDto {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    /// Casbin role whose permissions bound every key of the principal
+    pub role: String,
+    pub is_active: bool,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct CreateServicePrincipalPayload {
+    #[validate(length(min = 3, max = 100, message = "Nome deve ter entre 3 e 100 caracteres"))]
+    pub name: String,
+    pub description: Option<String>,
+    #[validate(length(min = 1, max = 50, message = "Role deve ter entre 1 e 50 caracteres"))]
+    pub role: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct UpdateServicePrincipalPayload {
+    pub description: Option<String>,
+    #[validate(length(min = 1, max = 50, message = "Role deve ter entre 1 e 50 caracteres"))]
+    pub role: Option<String>,
+    pub is_active: Option<bool>,
+}
+
+/// Casbin object/action pair a key is allowed to use (same syntax as the policies)
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, ToSchema, PartialEq)]
+pub struct ApiKeyScope {
+    /// Path pattern, e.g. `/api/admin/fuelings/{id}` or `/api/admin/fuelings/*`
+    #[validate(length(min = 1, max = 255, message = "Objeto deve ter entre 1 e 255 caracteres"))]
+    pub obj: String,
+    /// HTTP method
+    #[validate(length(min = 1, max = 10, message = "Ação inválida"))]
+    pub act: String,
+}
+
+/// API key metadata (never includes the secret)
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, FromRow)]
+pub struct ApiKeyDto {
+    pub id: Uuid,
+    pub principal_id: Uuid,
+    pub name: String,
+    pub prefix: String,
+    #[sqlx(json)]
+    pub scopes: Vec<ApiKeyScope>,
+    /// CIDR networks allowed to use the key (empty = any origin)
+    pub allowed_ips: Vec<String>,
+    pub expires_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub last_used_ip: Option<String>,
+    pub rotated_from_id: Option<Uuid>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Stored key joined with its principal, used to authenticate requests
+#[derive(Debug, Clone, FromRow)]
+pub struct StoredApiKey {
+    pub id: Uuid,
+    pub principal_id: Uuid,
+    pub principal_name: String,
+    pub principal_role: String,
+    pub principal_active: bool,
+    pub prefix: String,
+    pub secret_hash: String,
+    #[sqlx(json)]
+    pub scopes: Vec<ApiKeyScope>,
+    pub allowed_ips: Vec<String>,
+    pub expires_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+/// Data of a new key, as persisted
+#[derive(Debug, Clone)]
+pub struct NewApiKey {
+    pub principal_id: Uuid,
+    pub name: String,
+    pub prefix: String,
+    pub secret_hash: String,
+    pub scopes: Vec<ApiKeyScope>,
+    pub allowed_ips: Vec<String>,
+    pub expires_at: DateTime<Utc>,
+    pub rotated_from_id: Option<Uuid>,
+    pub created_by: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct CreateApiKeyPayload {
+    #[validate(length(min = 1, max = 100, message = "Nome deve ter entre 1 e 100 caracteres"))]
+    pub name: String,
+    #[validate(
+        length(min = 1, message = "Informe ao menos um escopo"),
+        nested
+    )]
+    pub scopes: Vec<ApiKeyScope>,
+    pub expires_at: DateTime<Utc>,
+    /// IPs or CIDR networks (e.g. `200.130.0.0/16`); empty = any origin
+    #[serde(default)]
+    pub allowed_ips: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Validate, ToSchema)]
+pub struct RotateApiKeyPayload {
+    /// How long the replaced key keeps working (default: 24h)
+    #[validate(range(min = 0, max = 604800, message = "Período de transição deve ser de no máximo 7 dias"))]
+    pub grace_period_secs: Option<i64>,
+    /// Expiry of the new key (default: same lifetime as the replaced key)
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// Response of key creation/rotation: the only time the full key is shown
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ApiKeyCreatedDto {
+    /// Value for the `Authorization: ApiKey <key>` header
+    pub key: String,
+    pub api_key: ApiKeyDto,
+}
+
+/// Identity of a request authenticated with an API key
+#[derive(Debug, Clone)]
+pub struct AuthenticatedApiKey {
+    pub key_id: Uuid,
+    pub prefix: String,
+    pub principal_id: Uuid,
+    pub principal_name: String,
+    pub role: String,
+    pub scopes: Vec<ApiKeyScope>,
+}
@@ -0,0 +1,205 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+use super::RequisitionPriority;
+
+/// Documents that go through an approval chain
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema, sqlx::Type)]
+#[sqlx(
+    type_name = "approval_document_type_enum",
+    rename_all = "SCREAMING_SNAKE_CASE"
+)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ApprovalDocumentType {
+    Requisition,
+    StockTransfer,
+    DisposalRequest,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema, sqlx::Type)]
+#[sqlx(
+    type_name = "approval_step_status_enum",
+    rename_all = "SCREAMING_SNAKE_CASE"
+)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ApprovalStepStatus {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+// ============================================================================
+// Chain configuration
+// ============================================================================
+
+/// Step of a chain. Steps sharing `step_order` run in parallel; a higher
+/// order only starts once every step before it is approved.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, FromRow)]
+pub struct ApprovalChainStepDto {
+    pub id: Uuid,
+    pub chain_id: Uuid,
+    pub step_order: i32,
+    pub name: String,
+    /// Role (global or assigned in the document's unit) allowed to decide
+    pub approver_role: Option<String>,
+    /// User allowed to decide
+    pub approver_user_id: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, FromRow)]
+pub struct ApprovalChainDto {
+    pub id: Uuid,
+    pub document_type: ApprovalDocumentType,
+    pub name: String,
+    pub description: Option<String>,
+    /// Applies from this document value on
+    pub min_value: Option<Decimal>,
+    /// Applies only to these priorities (requisitions)
+    #[sqlx(json(nullable))]
+    #[schema(value_type = Option<Vec<String>>)]
+    pub priorities: Option<Vec<RequisitionPriority>>,
+    pub warehouse_id: Option<Uuid>,
+    pub destination_unit_id: Option<Uuid>,
+    /// Among applicable chains the highest precedence wins
+    pub precedence: i32,
+    pub is_active: bool,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    #[sqlx(skip)]
+    pub steps: Vec<ApprovalChainStepDto>,
+}
+
+impl ApprovalChainDto {
+    /// Whether every condition of the chain holds for the document
+    pub fn applies_to(&self, document: &ApprovalDocumentContext) -> bool {
+        self.is_active
+            && self.document_type == document.document_type
+            && self.min_value.is_none_or(|min| document.value >= min)
+            && self
+                .priorities
+                .as_ref()
+                .is_none_or(|priorities| document.priority.is_some_and(|p| priorities.contains(&p)))
+            && self
+                .warehouse_id
+                .is_none_or(|id| document.warehouse_id == Some(id))
+            && self
+                .destination_unit_id
+                .is_none_or(|id| document.destination_unit_id == Some(id))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, ToSchema)]
+pub struct ApprovalChainStepPayload {
+    #[validate(range(min = 1, message = "Ordem da etapa deve ser maior que zero"))]
+    pub step_order: i32,
+    #[validate(length(
+        min = 1,
+        max = 200,
+        message = "Nome da etapa deve ter entre 1 e 200 caracteres"
+    ))]
+    pub name: String,
+    #[validate(length(
+        min = 1,
+        max = 100,
+        message = "Papel deve ter entre 1 e 100 caracteres"
+    ))]
+    pub approver_role: Option<String>,
+    pub approver_user_id: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct CreateApprovalChainPayload {
+    pub document_type: ApprovalDocumentType,
+    #[validate(length(min = 1, max = 200, message = "Nome deve ter entre 1 e 200 caracteres"))]
+    pub name: String,
+    pub description: Option<String>,
+    pub min_value: Option<Decimal>,
+    #[schema(value_type = Option<Vec<String>>)]
+    pub priorities: Option<Vec<RequisitionPriority>>,
+    pub warehouse_id: Option<Uuid>,
+    pub destination_unit_id: Option<Uuid>,
+    #[serde(default)]
+    pub precedence: i32,
+    #[validate(length(min = 1, message = "Informe ao menos uma etapa"), nested)]
+    pub steps: Vec<ApprovalChainStepPayload>,
+}
+
+/// Replaces conditions and steps; documents already in approval keep the
+/// steps they were started with
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct UpdateApprovalChainPayload {
+    #[validate(length(min = 1, max = 200, message = "Nome deve ter entre 1 e 200 caracteres"))]
+    pub name: String,
+    pub description: Option<String>,
+    pub min_value: Option<Decimal>,
+    #[schema(value_type = Option<Vec<String>>)]
+    pub priorities: Option<Vec<RequisitionPriority>>,
+    pub warehouse_id: Option<Uuid>,
+    pub destination_unit_id: Option<Uuid>,
+    #[serde(default)]
+    pub precedence: i32,
+    pub is_active: bool,
+    #[validate(length(min = 1, message = "Informe ao menos uma etapa"), nested)]
+    pub steps: Vec<ApprovalChainStepPayload>,
+}
+
+// ============================================================================
+// Document approval state
+// ============================================================================
+
+/// Attributes of a document the chain conditions are evaluated against
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApprovalDocumentContext {
+    pub document_type: ApprovalDocumentType,
+    pub document_id: Uuid,
+    pub value: Decimal,
+    pub priority: Option<RequisitionPriority>,
+    pub warehouse_id: Option<Uuid>,
+    pub destination_unit_id: Option<Uuid>,
+}
+
+/// A chain step instantiated for a document
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, FromRow)]
+pub struct DocumentApprovalStepDto {
+    pub id: Uuid,
+    pub document_type: ApprovalDocumentType,
+    pub document_id: Uuid,
+    pub chain_id: Option<Uuid>,
+    pub step_order: i32,
+    pub name: String,
+    pub approver_role: Option<String>,
+    pub approver_user_id: Option<Uuid>,
+    pub status: ApprovalStepStatus,
+    pub decided_by: Option<Uuid>,
+    pub decided_by_username: Option<String>,
+    pub decided_on_behalf_of: Option<Uuid>,
+    pub decided_at: Option<DateTime<Utc>>,
+    pub comments: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Where a document stands in its approval chain after a decision
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ApprovalProgress {
+    /// No chain applies: the single approve step decides
+    NoChain,
+    /// Steps remain; the document stays pending
+    InProgress,
+    /// Every step approved
+    Completed,
+    /// A step was rejected
+    Rejected,
+}
+
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct ApprovalStepDecisionPayload {
+    #[validate(length(max = 1000, message = "Comentário deve ter no máximo 1000 caracteres"))]
+    pub comments: Option<String>,
+}
@@ -0,0 +1,159 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Audit log entry with its position in the daily hash chain
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AuditChainEntry {
+    pub id: Uuid,
+    pub user_id: Option<Uuid>,
+    pub username: Option<String>,
+    pub action: String,
+    pub resource: String,
+    pub method: Option<String>,
+    pub status_code: Option<i32>,
+    pub details: Option<serde_json::Value>,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub request_id: Option<Uuid>,
+    pub duration_ms: Option<i32>,
+    pub created_at: DateTime<Utc>,
+    pub chain_date: NaiveDate,
+    pub seq: i64,
+    pub prev_hash: String,
+    pub entry_hash: String,
+    /// `entry_hash` matches the hash recomputed from the stored fields (or,
+    /// for a redacted entry, the redaction matches the remaining fields)
+    #[serde(skip)]
+    pub hash_valid: bool,
+    /// Hash of the non-personal fields, recorded when the personal ones were
+    /// erased (LGPD)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub redacted_hash: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub redaction_signature: Option<String>,
+}
+
+/// Entry holding personal fields of a data subject
+#[derive(Debug, Clone, FromRow)]
+pub struct AuditRedactionCandidate {
+    pub id: Uuid,
+    pub entry_hash: String,
+    pub redacted_hash: String,
+    /// Only intact entries get a signed redaction
+    pub hash_valid: bool,
+}
+
+/// Signed redaction of one audit entry
+#[derive(Debug, Clone)]
+pub struct NewAuditRedaction {
+    pub entry_id: Uuid,
+    pub entry_hash: String,
+    pub redacted_hash: String,
+    pub signature: String,
+}
+
+/// Signed digest of the last link of a closed day
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, FromRow)]
+pub struct AuditCheckpointDto {
+    pub chain_date: NaiveDate,
+    pub entry_count: i64,
+    pub last_seq: i64,
+    pub last_hash: String,
+    pub digest: String,
+    pub signature: String,
+    pub signed_at: DateTime<Utc>,
+}
+
+/// Range of a daily chain exported to a sealed file by retention
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, FromRow)]
+pub struct AuditArchiveDto {
+    pub id: Uuid,
+    pub chain_date: NaiveDate,
+    pub first_seq: i64,
+    pub last_seq: i64,
+    pub entry_count: i64,
+    pub last_hash: String,
+    pub file_path: String,
+    pub file_sha256: String,
+    pub signature: String,
+    pub archived_at: DateTime<Utc>,
+}
+
+/// Data recorded when a chain range is archived
+#[derive(Debug, Clone)]
+pub struct NewAuditArchive {
+    pub chain_date: NaiveDate,
+    pub first_seq: i64,
+    pub last_seq: i64,
+    pub entry_count: i64,
+    pub last_hash: String,
+    pub file_path: String,
+    pub file_sha256: String,
+    pub signature: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum AuditChainIssueKind {
+    /// Sequence numbers are missing (entries deleted)
+    Gap,
+    /// `prev_hash` does not match the previous entry
+    BrokenLink,
+    /// Stored fields no longer produce `entry_hash` (entry edited)
+    HashMismatch,
+    /// Chain no longer matches the signed checkpoint of the day
+    CheckpointMismatch,
+    /// Checkpoint or archive signature does not verify
+    InvalidSignature,
+    /// Entries were written to the day after it was sealed. The chain is
+    /// intact, so this alone does not invalidate the day.
+    AppendedAfterCheckpoint,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AuditChainIssue {
+    pub kind: AuditChainIssueKind,
+    pub seq: Option<i64>,
+    pub detail: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AuditDayVerification {
+    pub chain_date: NaiveDate,
+    /// Entries still in the database
+    pub entry_count: i64,
+    /// Entries exported to sealed archive files
+    pub archived_count: i64,
+    pub last_seq: Option<i64>,
+    pub checkpointed: bool,
+    pub valid: bool,
+    pub issues: Vec<AuditChainIssue>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AuditChainVerification {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub valid: bool,
+    pub days: Vec<AuditDayVerification>,
+}
+
+/// Outcome of sealing closed days
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AuditSealResult {
+    pub checkpoints: Vec<AuditCheckpointDto>,
+    /// Days left unsealed because their chain failed verification
+    pub skipped_days: Vec<NaiveDate>,
+}
+
+/// Outcome of a retention run
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AuditArchiveResult {
+    pub archived_count: i64,
+    pub archives: Vec<AuditArchiveDto>,
+    /// Days kept in the database because their chain failed verification
+    pub skipped_days: Vec<NaiveDate>,
+}
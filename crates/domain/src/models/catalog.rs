@@ -336,6 +336,8 @@ pub struct CatmatClassTreeNode {
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema, sqlx::FromRow)]
 pub struct CatserSectionDto {
     pub id: Uuid,
+    /// Official CATSER code (filled in by the ComprasGov sync)
+    pub code: Option<String>,
     pub name: String,
     pub is_active: bool,
     pub verification_status: String,
@@ -372,6 +374,8 @@ pub struct UpdateCatserSectionPayload {
 pub struct CatserDivisionDto {
     pub id: Uuid,
     pub section_id: Uuid,
+    /// Official CATSER code (filled in by the ComprasGov sync)
+    pub code: Option<String>,
     pub name: String,
     pub is_active: bool,
     pub verification_status: String,
@@ -0,0 +1,97 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Criteria of the duplicate report over CATMAT items
+#[derive(Debug, Clone)]
+pub struct CatalogDuplicateFilter {
+    /// Minimum trigram similarity of the descriptions (0.3 to 1.0)
+    pub min_similarity: f32,
+    /// Only pair items of the same PDM
+    pub same_pdm: bool,
+    /// Only pair items with the same unit of measure
+    pub same_unit: bool,
+    pub pdm_id: Option<Uuid>,
+    pub include_inactive: bool,
+}
+
+/// Two items whose descriptions are similar enough to be the same product
+#[derive(Debug, Clone)]
+pub struct CatalogDuplicatePair {
+    pub item_id: Uuid,
+    pub other_id: Uuid,
+    pub similarity: f32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CatalogDuplicateCandidate {
+    pub id: Uuid,
+    pub code: String,
+    pub description: String,
+    pub pdm_id: Uuid,
+    pub pdm_code: String,
+    pub pdm_description: String,
+    pub unit_of_measure_id: Uuid,
+    pub unit_symbol: String,
+    pub verification_status: String,
+    pub is_active: bool,
+    /// Balance across all warehouses
+    pub stock_quantity: Decimal,
+    pub movement_count: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Items linked by similar descriptions, directly or through each other
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CatalogDuplicateCluster {
+    pub items: Vec<CatalogDuplicateCandidate>,
+    pub max_similarity: f32,
+    /// Verified item first, then the most used, then the oldest
+    pub suggested_survivor_id: Uuid,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CatalogDuplicateReport {
+    pub clusters: Vec<CatalogDuplicateCluster>,
+    /// The pair limit was reached; narrow the report by PDM to see the rest
+    pub truncated: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct MergeCatalogItemsPayload {
+    pub survivor_id: Uuid,
+    pub duplicate_ids: Vec<Uuid>,
+    pub reason: Option<String>,
+}
+
+/// Rows moved to the surviving item in one referencing table
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CatalogMergeReference {
+    pub table: String,
+    pub rows: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CatalogItemMergeResult {
+    pub survivor_id: Uuid,
+    pub merged_item_ids: Vec<Uuid>,
+    /// Warehouse balances added to an existing balance of the survivor
+    pub combined_stocks: i64,
+    /// Batch balances added to an existing batch of the survivor
+    pub combined_batches: i64,
+    pub references: Vec<CatalogMergeReference>,
+}
+
+/// ID of an item retired by a merge and the item that replaced it
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, sqlx::FromRow)]
+pub struct CatalogItemRedirectDto {
+    pub old_item_id: Uuid,
+    pub new_item_id: Uuid,
+    pub old_code: String,
+    pub old_description: String,
+    pub reason: Option<String>,
+    pub merged_by: Option<Uuid>,
+    pub merged_at: DateTime<Utc>,
+}
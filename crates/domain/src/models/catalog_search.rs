@@ -0,0 +1,65 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::{
+    CatalogKind, CatmatItemWithDetailsDto, CatserItemWithDetailsDto, MaterialClassification,
+};
+
+/// How a search hit matched the query
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CatalogMatchKind {
+    /// Portuguese full-text match, accents ignored
+    FullText,
+    /// Trigram similarity, used when the full-text search finds nothing
+    Fuzzy,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CatmatItemSearchFilter {
+    /// Classification of the item's PDM
+    pub material_classification: Option<MaterialClassification>,
+    pub code_ncm: Option<String>,
+    pub is_sustainable: Option<bool>,
+    pub budget_classification_id: Option<Uuid>,
+    pub is_active: Option<bool>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CatserItemSearchFilter {
+    pub code_cpc: Option<String>,
+    pub budget_classification_id: Option<Uuid>,
+    pub is_active: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CatmatItemSearchHit {
+    #[serde(flatten)]
+    pub item: CatmatItemWithDetailsDto,
+    pub rank: f32,
+    /// Description with the matched terms wrapped in `<mark>`
+    pub highlight: String,
+    pub match_kind: CatalogMatchKind,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CatserItemSearchHit {
+    #[serde(flatten)]
+    pub item: CatserItemWithDetailsDto,
+    pub rank: f32,
+    /// Description with the matched terms wrapped in `<mark>`
+    pub highlight: String,
+    pub match_kind: CatalogMatchKind,
+}
+
+/// Active item suggested while typing
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CatalogSuggestion {
+    pub catalog: CatalogKind,
+    pub id: Uuid,
+    pub code: String,
+    pub description: String,
+    /// Description with the matched prefixes wrapped in `<mark>`
+    pub highlight: String,
+}
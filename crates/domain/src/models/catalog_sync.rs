@@ -0,0 +1,191 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::{
+    CatmatClassDto, CatmatGroupDto, CatmatItemDto, CatmatPdmDto, CatserClassDto, CatserDivisionDto,
+    CatserGroupDto, CatserItemDto, CatserSectionDto, ComprasGovItemMaterial, ComprasGovItemServico,
+    MaterialClassification,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema, sqlx::Type)]
+#[sqlx(type_name = "catalog_kind_enum", rename_all = "SCREAMING_SNAKE_CASE")]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CatalogKind {
+    Catmat,
+    Catser,
+}
+
+/// Part of the official catalog pulled by a sync run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema, sqlx::Type)]
+#[sqlx(
+    type_name = "catalog_sync_scope_enum",
+    rename_all = "SCREAMING_SNAKE_CASE"
+)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CatalogSyncScope {
+    /// Whole catalog
+    Full,
+    /// One group and everything below it
+    Group,
+    /// One PDM and its items (CATMAT only)
+    Pdm,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema, sqlx::Type)]
+#[sqlx(
+    type_name = "catalog_sync_status_enum",
+    rename_all = "SCREAMING_SNAKE_CASE"
+)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CatalogSyncStatus {
+    Running,
+    Completed,
+    Failed,
+}
+
+/// Level of the catalog hierarchy. CATMAT: group > class > PDM > item;
+/// CATSER: section > division > group > class > item.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, ToSchema,
+)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CatalogLevel {
+    Section,
+    Division,
+    Group,
+    Class,
+    Pdm,
+    Item,
+}
+
+/// Catalog entry keyed by its official code, as held locally or as
+/// published by ComprasGov
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CatalogNode {
+    pub level: CatalogLevel,
+    pub code: String,
+    /// Official code of the parent (`None` at the top level, or for CATSER
+    /// groups without a division)
+    pub parent_code: Option<String>,
+    /// Name, or description for PDMs and items
+    pub name: String,
+    pub is_active: bool,
+    /// CATMAT items only
+    pub is_sustainable: Option<bool>,
+    /// CATSER items only
+    pub code_cpc: Option<String>,
+    /// Local CATMAT item merged into another one: the sync leaves it alone
+    pub merged: bool,
+}
+
+/// Which nodes of a level to load
+#[derive(Debug, Clone)]
+pub enum CatalogNodeFilter {
+    All,
+    Codes(Vec<String>),
+    ParentCodes(Vec<String>),
+}
+
+/// Entry of the sync report
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CatalogSyncEntry {
+    pub level: CatalogLevel,
+    pub code: String,
+    pub name: String,
+    /// Fields that differ from the local record (`name`, `parent`,
+    /// `is_active`, `is_sustainable`, `code_cpc`); empty when added
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<String>,
+    /// Why the entry was skipped
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct CatalogSyncReport {
+    pub added: Vec<CatalogSyncEntry>,
+    pub changed: Vec<CatalogSyncEntry>,
+    /// Disappeared from the official catalog or no longer active there
+    pub deactivated: Vec<CatalogSyncEntry>,
+    /// Official entries that could not be placed in the local hierarchy
+    pub skipped: Vec<CatalogSyncEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, FromRow)]
+pub struct CatalogSyncRunDto {
+    pub id: Uuid,
+    pub catalog: CatalogKind,
+    pub scope: CatalogSyncScope,
+    pub scope_code: Option<String>,
+    /// The report was computed but nothing was written
+    pub dry_run: bool,
+    pub status: CatalogSyncStatus,
+    pub added_count: i32,
+    pub changed_count: i32,
+    pub deactivated_count: i32,
+    /// Omitted in listings
+    #[sqlx(json(nullable))]
+    pub report: Option<CatalogSyncReport>,
+    pub error: Option<String>,
+    pub triggered_by: Option<Uuid>,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+/// Writes of a sync run, applied in a single transaction
+#[derive(Debug, Clone)]
+pub struct CatalogSyncPlan {
+    pub catalog: CatalogKind,
+    /// New or changed nodes, parents before children
+    pub upserts: Vec<CatalogNode>,
+    pub deactivations: Vec<(CatalogLevel, String)>,
+    /// Unit of measure of new items (not informed by the official catalog)
+    pub default_unit_id: Uuid,
+    /// Classification of new PDMs
+    pub default_material_classification: MaterialClassification,
+}
+
+/// CATMAT item imported on demand, with its ancestors
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CatmatImportResult {
+    pub group: CatmatGroupDto,
+    pub class: CatmatClassDto,
+    pub pdm: CatmatPdmDto,
+    pub item: CatmatItemDto,
+    /// Levels written by the import; the others already existed locally
+    pub created: Vec<CatalogLevel>,
+}
+
+/// CATSER item imported on demand, with its ancestors
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CatserImportResult {
+    /// Absent when the group has no division
+    pub section: Option<CatserSectionDto>,
+    pub division: Option<CatserDivisionDto>,
+    pub group: CatserGroupDto,
+    pub class: CatserClassDto,
+    pub item: CatserItemDto,
+    /// Levels written by the import; the others already existed locally
+    pub created: Vec<CatalogLevel>,
+}
+
+/// CATMAT item of the official catalog search
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct OfficialCatmatItem {
+    #[serde(flatten)]
+    pub item: ComprasGovItemMaterial,
+    /// An item with this code is already registered locally
+    pub exists_locally: bool,
+}
+
+/// CATSER item of the official catalog search
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct OfficialCatserItem {
+    #[serde(flatten)]
+    pub item: ComprasGovItemServico,
+    /// An item with this code is already registered locally
+    pub exists_locally: bool,
+}
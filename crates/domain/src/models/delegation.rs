@@ -0,0 +1,82 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+use super::UnitScope;
+
+/// Kind of approval authority that can be delegated
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum DelegationScope {
+    /// Approve or reject requisitions
+    Requisition,
+    /// Review (approve or reject) vehicle trips
+    Trip,
+    /// Confirm stock transfers at the destination warehouse
+    StockTransfer,
+}
+
+impl DelegationScope {
+    /// Value stored in `approval_delegations.scopes`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DelegationScope::Requisition => "REQUISITION",
+            DelegationScope::Trip => "TRIP",
+            DelegationScope::StockTransfer => "STOCK_TRANSFER",
+        }
+    }
+}
+
+/// Approval authority granted by `delegator_id` to `delegate_id` for a period
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, FromRow)]
+pub struct ApprovalDelegationDto {
+    pub id: Uuid,
+    pub delegator_id: Uuid,
+    pub delegator_username: Option<String>,
+    pub delegate_id: Uuid,
+    pub delegate_username: Option<String>,
+    #[sqlx(json)]
+    pub scopes: Vec<DelegationScope>,
+    pub valid_from: DateTime<Utc>,
+    pub valid_until: DateTime<Utc>,
+    pub reason: Option<String>,
+    /// Not revoked and within the validity window right now
+    pub is_active: bool,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub revoked_by: Option<Uuid>,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct CreateDelegationPayload {
+    pub delegate_id: Uuid,
+    #[validate(length(min = 1, message = "Informe ao menos um escopo"))]
+    pub scopes: Vec<DelegationScope>,
+    /// Defaults to now
+    pub valid_from: Option<DateTime<Utc>>,
+    pub valid_until: DateTime<Utc>,
+    #[validate(length(max = 500, message = "Motivo deve ter no máximo 500 caracteres"))]
+    pub reason: Option<String>,
+}
+
+/// Authority the caller exercises on behalf of other users in this request:
+/// each delegator and the units where that delegator may act
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DelegatedAuthority {
+    pub scope: DelegationScope,
+    pub grants: Vec<(Uuid, UnitScope)>,
+}
+
+impl DelegatedAuthority {
+    /// Delegator whose authority covers a row of `unit_id`
+    pub fn delegator_for(&self, unit_id: Option<Uuid>) -> Option<Uuid> {
+        self.grants
+            .iter()
+            .find(|(_, scope)| scope.contains(unit_id))
+            .map(|(delegator_id, _)| *delegator_id)
+    }
+}
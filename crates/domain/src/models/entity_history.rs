@@ -0,0 +1,77 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::AuditOperation;
+
+/// Aggregate with field-level change history in `entity_changelog`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum HistoryEntity {
+    Warehouse,
+    /// Stock parameters (min/max, reorder point, location, block)
+    WarehouseStock,
+    Supplier,
+    Driver,
+    CatmatItem,
+    CatserItem,
+    OrganizationalUnit,
+    /// Casbin rules, keyed by subject (`v0`) instead of a UUID
+    Policy,
+}
+
+impl HistoryEntity {
+    /// Value stored in `entity_changelog.entity_type`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HistoryEntity::Warehouse => "warehouse",
+            HistoryEntity::WarehouseStock => "warehouse_stock",
+            HistoryEntity::Supplier => "supplier",
+            HistoryEntity::Driver => "driver",
+            HistoryEntity::CatmatItem => "catmat_item",
+            HistoryEntity::CatserItem => "catser_item",
+            HistoryEntity::OrganizationalUnit => "organizational_unit",
+            HistoryEntity::Policy => "policy",
+        }
+    }
+
+    /// Whether the entity is identified by a UUID primary key
+    pub fn has_uuid_key(&self) -> bool {
+        !matches!(self, HistoryEntity::Policy)
+    }
+}
+
+/// One captured change of an entity, with the actor and the request that
+/// made it
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, FromRow)]
+pub struct EntityChangeDto {
+    pub id: Uuid,
+    pub entity_type: String,
+    pub entity_id: String,
+    pub operation: AuditOperation,
+    /// Sorted names of the fields in `diff`
+    pub changed_fields: Vec<String>,
+    /// Changed fields as `{field: {old, new}}`
+    pub diff: Option<serde_json::Value>,
+    pub data_before: Option<serde_json::Value>,
+    pub data_after: Option<serde_json::Value>,
+    pub performed_by: Option<Uuid>,
+    pub performed_by_username: Option<String>,
+    pub performed_at: DateTime<Utc>,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    /// Same value as `audit_logs.request_id` of the request
+    pub request_id: Option<Uuid>,
+}
+
+/// Change recorded by the application where no trigger can capture it
+#[derive(Debug, Clone)]
+pub struct NewEntityChange {
+    pub entity: HistoryEntity,
+    pub entity_id: String,
+    pub operation: AuditOperation,
+    pub data_before: Option<serde_json::Value>,
+    pub data_after: Option<serde_json::Value>,
+}
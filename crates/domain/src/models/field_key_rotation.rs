@@ -0,0 +1,77 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema, sqlx::Type)]
+#[sqlx(
+    type_name = "field_key_rotation_status_enum",
+    rename_all = "SCREAMING_SNAKE_CASE"
+)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum FieldKeyRotationStatus {
+    Running,
+    Completed,
+    Failed,
+}
+
+/// Table being re-encrypted, in processing order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema, sqlx::Type)]
+#[sqlx(
+    type_name = "field_key_rotation_stage_enum",
+    rename_all = "SCREAMING_SNAKE_CASE"
+)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum FieldKeyRotationStage {
+    /// Email (and its blind index) and MFA secret of each user
+    Users,
+    /// Secrets of pending MFA enrollments
+    MfaSetupTokens,
+    /// Private keys of the JWT signing keys generated by rotation
+    JwtSigningKeys,
+}
+
+impl FieldKeyRotationStage {
+    pub fn next(self) -> Option<Self> {
+        match self {
+            Self::Users => Some(Self::MfaSetupTokens),
+            Self::MfaSetupTokens => Some(Self::JwtSigningKeys),
+            Self::JwtSigningKeys => None,
+        }
+    }
+}
+
+/// Run of the job that moves encrypted fields to the current key
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, FromRow)]
+pub struct FieldKeyRotationDto {
+    pub id: Uuid,
+    /// Key the rows are re-encrypted with (the current key when started)
+    pub target_key_id: i32,
+    pub status: FieldKeyRotationStatus,
+    pub stage: FieldKeyRotationStage,
+    /// Resume point: last row of `stage` already processed
+    pub last_processed_id: Option<Uuid>,
+    /// Rows holding encrypted fields when the run started
+    pub total_rows: i64,
+    pub processed_rows: i64,
+    /// Rows rewritten (new ciphertext or rebuilt blind index)
+    pub reencrypted_rows: i64,
+    /// Rows no key in the ring could decrypt; left untouched
+    pub unreadable_rows: i64,
+    pub error: Option<String>,
+    pub triggered_by: Option<Uuid>,
+    pub started_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+/// Outcome of re-encrypting one batch of rows
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FieldKeyRotationBatch {
+    /// Last row of the batch (`None` when the stage has no rows left)
+    pub last_id: Option<Uuid>,
+    pub processed: i64,
+    pub reencrypted: i64,
+    pub unreadable: i64,
+}
@@ -0,0 +1,124 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::models::legacy_import::ImportJobDto;
+
+// ============================================================
+// RF-ABT — Cartão combustível: importação e conciliação
+// ============================================================
+
+/// Resultado da conciliação de uma transação da operadora.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema, sqlx::Type)]
+#[sqlx(type_name = "fuel_card_match_status_enum", rename_all = "SCREAMING_SNAKE_CASE")]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum FuelCardMatchStatus {
+    /// Abastecimento existente confere em data, litros e valor.
+    Conciliado,
+    /// Abastecimento existente encontrado, mas com divergências.
+    Divergente,
+    /// Sem abastecimento correspondente — gerado abastecimento em QUARENTENA.
+    Pendente,
+    /// Placa ou CPF não cadastrados.
+    NaoIdentificado,
+}
+
+/// Linha do arquivo da operadora já interpretada.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct FuelCardRecord {
+    pub line_number: i32,
+    pub external_id: Option<String>,
+    pub transaction_at: DateTime<Utc>,
+    pub license_plate: String,
+    pub driver_cpf: Option<String>,
+    pub liters: Decimal,
+    pub unit_price: Decimal,
+    pub total_amount: Decimal,
+    pub odometer_km: Option<i32>,
+    pub fuel_name: Option<String>,
+    pub merchant: Option<String>,
+}
+
+/// Transação em staging, com o resultado da conciliação.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, sqlx::FromRow)]
+pub struct FuelCardTransactionDto {
+    pub id: Uuid,
+    pub job_id: Uuid,
+    pub operator: String,
+    pub line_number: i32,
+    pub external_id: Option<String>,
+    pub transaction_at: DateTime<Utc>,
+    pub license_plate: String,
+    pub driver_cpf: Option<String>,
+    pub liters: Decimal,
+    pub unit_price: Decimal,
+    pub total_amount: Decimal,
+    pub odometer_km: Option<i32>,
+    pub fuel_name: Option<String>,
+    pub merchant: Option<String>,
+    pub vehicle_id: Option<Uuid>,
+    pub driver_id: Option<Uuid>,
+    pub fueling_id: Option<Uuid>,
+    pub match_status: FuelCardMatchStatus,
+    /// Lista de divergências (texto) apuradas na conciliação.
+    pub mismatches: Option<serde_json::Value>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Resultado da conciliação de um registro, pronto para gravar em staging.
+#[derive(Debug, Clone)]
+pub struct NewFuelCardTransaction {
+    pub record: FuelCardRecord,
+    pub vehicle_id: Option<Uuid>,
+    pub driver_id: Option<Uuid>,
+    pub fueling_id: Option<Uuid>,
+    pub match_status: FuelCardMatchStatus,
+    pub mismatches: Vec<String>,
+    /// Abastecimento a gerar junto com a transação, na mesma transação do banco
+    pub pending_fueling: Option<PendingCardFueling>,
+}
+
+/// Abastecimento em QUARENTENA gerado para transação sem correspondente.
+#[derive(Debug, Clone)]
+pub struct PendingCardFueling {
+    pub vehicle_id: Uuid,
+    pub driver_id: Uuid,
+    pub fuel_type_id: Uuid,
+    pub odometer_km: i32,
+    pub notes: String,
+    pub anomalies: serde_json::Value,
+    pub created_by: Option<Uuid>,
+}
+
+/// Veículo resolvido pela placa, com os dados usados para gerar abastecimentos.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct FuelCardVehicleRef {
+    pub id: Uuid,
+    pub fuel_type_id: Uuid,
+    pub last_odometer_km: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct FuelCardImportQuery {
+    /// Nome da operadora do cartão (ex.: "TICKET LOG", "PRIME").
+    pub operator: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct FuelCardTransactionFilters {
+    pub match_status: Option<FuelCardMatchStatus>,
+}
+
+/// Relatório de conciliação de uma importação.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct FuelCardImportReportDto {
+    pub job: ImportJobDto,
+    pub matched: i64,
+    pub divergent: i64,
+    pub pending: i64,
+    pub unidentified: i64,
+    /// Transações DIVERGENTE e NAO_IDENTIFICADO, para conferência.
+    pub mismatches: Vec<FuelCardTransactionDto>,
+}
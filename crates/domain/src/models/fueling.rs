@@ -8,6 +8,45 @@ use uuid::Uuid;
 // Fueling DTOs
 // ============================
 
+/// Status de revisão do abastecimento — abastecimentos com anomalias entram
+/// em QUARENTENA e aguardam o Gestor de Frota (mesmo fluxo do odômetro).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema, sqlx::Type)]
+#[sqlx(type_name = "fueling_review_status_enum", rename_all = "SCREAMING_SNAKE_CASE")]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum FuelingReviewStatus {
+    Validado,
+    Quarentena,
+    Rejeitado,
+}
+
+/// Tipo de anomalia detectada no registro do abastecimento.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum FuelingAnomalyKind {
+    /// Litros acima da capacidade do tanque do veículo.
+    TankCapacityExceeded,
+    /// Consumo (km/L) desde o abastecimento anterior fora da média móvel.
+    ConsumptionDeviation,
+    /// Intervalo de tempo ou de km muito curto desde o abastecimento anterior.
+    TooCloseToPrevious,
+    /// Intervalo de tempo ou de km muito curto até o abastecimento seguinte
+    /// (registro retroativo).
+    TooCloseToNext,
+    /// Combustível diferente do `fuel_type_id` do veículo.
+    FuelTypeMismatch,
+    /// Preço unitário muito acima da mediana do período.
+    UnitPriceAboveMedian,
+    /// Gerado a partir de transação do cartão combustível sem abastecimento
+    /// correspondente — pendente de conferência.
+    CardTransactionPending,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct FuelingAnomaly {
+    pub kind: FuelingAnomalyKind,
+    pub detail: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema, sqlx::FromRow)]
 pub struct FuelingDto {
     pub id: Uuid,
@@ -21,6 +60,12 @@ pub struct FuelingDto {
     pub unit_price: Decimal,
     pub total_cost: Decimal,
     pub notes: Option<String>,
+    pub review_status: FuelingReviewStatus,
+    /// Lista de `FuelingAnomaly` detectadas no registro.
+    pub anomalies: Option<serde_json::Value>,
+    pub reviewed_by: Option<Uuid>,
+    pub reviewed_at: Option<DateTime<Utc>>,
+    pub review_notes: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub created_by: Option<Uuid>,
@@ -45,6 +90,8 @@ pub struct FuelingWithDetailsDto {
     pub unit_price: Decimal,
     pub total_cost: Decimal,
     pub notes: Option<String>,
+    pub review_status: FuelingReviewStatus,
+    pub anomalies: Option<serde_json::Value>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -76,3 +123,11 @@ pub struct UpdateFuelingPayload {
     pub total_cost: Option<Decimal>,
     pub notes: Option<String>,
 }
+
+/// Resolve um abastecimento em QUARENTENA.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ReviewFuelingPayload {
+    /// `true` = promover para VALIDADO; `false` = marcar como REJEITADO.
+    pub approve: bool,
+    pub notes: Option<String>,
+}
@@ -0,0 +1,53 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema, sqlx::Type)]
+#[sqlx(
+    type_name = "jwt_signing_key_status_enum",
+    rename_all = "SCREAMING_SNAKE_CASE"
+)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum JwtSigningKeyStatus {
+    /// Signs every new token
+    Active,
+    /// Replaced by a rotation; verifies tokens until `verify_until`
+    Retired,
+}
+
+/// Key pair used to sign JWTs, identified by the `kid` header
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, FromRow)]
+pub struct JwtSigningKeyDto {
+    pub kid: String,
+    /// JWS algorithm (`EdDSA` or `RS256`)
+    pub algorithm: String,
+    pub status: JwtSigningKeyStatus,
+    /// Public key as published in `/.well-known/jwks.json`
+    #[schema(value_type = Object)]
+    pub public_jwk: serde_json::Value,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub retired_at: Option<DateTime<Utc>>,
+    /// End of the grace period; tokens signed with the key are rejected
+    /// afterwards
+    pub verify_until: Option<DateTime<Utc>>,
+}
+
+impl JwtSigningKeyDto {
+    pub fn verifies_tokens_at(&self, now: DateTime<Utc>) -> bool {
+        self.verify_until.is_none_or(|until| until > now)
+    }
+}
+
+/// Key pair registered by a rotation
+#[derive(Debug, Clone)]
+pub struct NewJwtSigningKey {
+    pub kid: String,
+    pub algorithm: String,
+    pub public_jwk: serde_json::Value,
+    /// PKCS#8 (EdDSA) or PKCS#1 (RS256) DER, base64. `None` for keys that
+    /// only need to be verified.
+    pub private_key: Option<String>,
+}
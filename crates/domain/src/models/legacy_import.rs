@@ -22,6 +22,7 @@ pub enum ImportEntityType {
     Supplier,
     CatalogItem,
     InitialStock,
+    FuelCardTransaction,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema, sqlx::FromRow)]
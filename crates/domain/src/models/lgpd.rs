@@ -0,0 +1,110 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::DriverType;
+
+/// Everything held about a data subject (LGPD art. 18, II), found by user ID
+/// and/or CPF
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DataSubjectExport {
+    pub generated_at: DateTime<Utc>,
+    pub user: Option<DataSubjectUser>,
+    pub driver: Option<DataSubjectDriver>,
+}
+
+/// Personal data of a user account, with the records linked to it
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DataSubjectUser {
+    pub id: Uuid,
+    pub username: String,
+    /// Decrypted email
+    pub email: String,
+    pub role: String,
+    pub email_verified: bool,
+    pub mfa_enabled: bool,
+    pub is_banned: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub anonymized_at: Option<DateTime<Utc>>,
+    /// Sessions with IP address and user agent (no token material)
+    pub sessions: Vec<serde_json::Value>,
+    pub login_attempts: Vec<serde_json::Value>,
+    /// Linked external identities (provider and dates)
+    pub identities: Vec<serde_json::Value>,
+    /// Registered passkeys (name and dates, no key material)
+    pub webauthn_credentials: Vec<serde_json::Value>,
+    pub requisitions: Vec<serde_json::Value>,
+    pub trips_requested: Vec<serde_json::Value>,
+    /// Entity changes made by the user, with IP address and user agent
+    pub changes_performed: Vec<serde_json::Value>,
+    /// Entries of the audit log (logs database)
+    pub audit_logs: Vec<serde_json::Value>,
+}
+
+/// Personal data of a driver, with the fleet records linked to it
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DataSubjectDriver {
+    pub id: Uuid,
+    pub driver_type: DriverType,
+    pub full_name: String,
+    pub cpf: String,
+    pub cnh_number: String,
+    pub cnh_category: String,
+    pub cnh_expiration: NaiveDate,
+    pub phone: Option<String>,
+    pub email: Option<String>,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub anonymized_at: Option<DateTime<Utc>>,
+    pub trips: Vec<serde_json::Value>,
+    pub fines: Vec<serde_json::Value>,
+    pub fuelings: Vec<serde_json::Value>,
+    pub fuel_card_transactions: Vec<serde_json::Value>,
+    /// Field-level change history of the driver record
+    pub history: Vec<serde_json::Value>,
+}
+
+/// Random replacements for the personal data of a user account
+#[derive(Debug, Clone)]
+pub struct UserAnonymization {
+    pub user_id: Uuid,
+    pub username: String,
+    /// Plaintext token; encrypted and indexed by the repository
+    pub email: String,
+    /// Hash of a random password nobody knows
+    pub password_hash: String,
+}
+
+/// Random replacements for the personal data of a driver
+#[derive(Debug, Clone)]
+pub struct DriverAnonymization {
+    pub driver_id: Uuid,
+    pub full_name: String,
+    /// 11 characters, never a valid CPF
+    pub cpf: String,
+    pub cnh_number: String,
+}
+
+/// Subjects to anonymize in a single transaction
+#[derive(Debug, Clone)]
+pub struct AnonymizationPlan {
+    pub user: Option<UserAnonymization>,
+    pub driver: Option<DriverAnonymization>,
+}
+
+/// Outcome of an anonymization
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AnonymizationResult {
+    /// User anonymized by this request (`None` if absent or already anonymized)
+    pub user_id: Option<Uuid>,
+    /// Driver anonymized by this request (`None` if absent or already anonymized)
+    pub driver_id: Option<Uuid>,
+    pub anonymized_at: DateTime<Utc>,
+    /// Rows updated or removed, by table
+    pub affected: serde_json::Value,
+    /// Sources kept unchanged, with the legal basis
+    pub retained: Vec<String>,
+}
@@ -0,0 +1,29 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Progressive lockout state of an account
+#[derive(Debug, Clone, Default, PartialEq, FromRow)]
+pub struct AccountLockout {
+    pub user_id: Uuid,
+    pub failed_count: i32,
+    pub lockout_level: i32,
+    pub locked_until: Option<DateTime<Utc>>,
+    pub last_failed_at: Option<DateTime<Utc>>,
+    pub last_failed_ip: Option<String>,
+}
+
+/// Lockout entry as shown to administrators
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, FromRow)]
+pub struct AccountLockoutDto {
+    pub user_id: Uuid,
+    pub username: String,
+    pub failed_count: i32,
+    pub lockout_level: i32,
+    pub locked_until: Option<DateTime<Utc>>,
+    pub last_failed_at: Option<DateTime<Utc>>,
+    pub last_failed_ip: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
@@ -0,0 +1,85 @@
+use crate::value_objects::{Email, Username};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// How an external identity was linked to the local user on first login
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, sqlx::Type, PartialEq)]
+#[sqlx(type_name = "identity_link_method_enum", rename_all = "SCREAMING_SNAKE_CASE")]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum IdentityLinkMethod {
+    Email,
+    Cpf,
+    Jit,
+}
+
+/// External identity (OIDC subject) linked to a local user
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, FromRow)]
+pub struct UserIdentityDto {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub provider: String,
+    pub link_method: IdentityLinkMethod,
+    pub created_at: DateTime<Utc>,
+    pub last_login_at: Option<DateTime<Utc>>,
+}
+
+/// Local account created on first login (just-in-time provisioning),
+/// together with its identity link
+#[derive(Debug, Clone)]
+pub struct NewOidcAccount {
+    pub username: Username,
+    pub email: Email,
+    pub email_verified: bool,
+    pub password_hash: String,
+    pub role: String,
+    /// Stored only when no other account holds it
+    pub cpf: Option<String>,
+    pub provider: String,
+    pub subject: String,
+}
+
+/// Pending authorization request (state, nonce and PKCE verifier)
+#[derive(Debug, Clone, FromRow)]
+pub struct OidcLoginState {
+    pub state: String,
+    pub provider: String,
+    pub nonce: String,
+    pub code_verifier: String,
+    pub redirect_to: Option<String>,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Configured identity provider, as shown on the login page
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct OidcProviderDto {
+    pub name: String,
+    pub display_name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct OidcAuthorizationDto {
+    pub provider: String,
+    pub authorization_url: String,
+    /// Must also be bound to the browser (cookie) and checked on the callback
+    #[serde(skip)]
+    pub state: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct OidcLoginQuery {
+    /// Relative path to return to after login (e.g. "/frota")
+    pub redirect_to: Option<String>,
+}
+
+/// Query string sent by the IdP to the redirect URI
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct OidcCallbackQuery {
+    pub code: Option<String>,
+    pub state: Option<String>,
+    pub error: Option<String>,
+    pub error_description: Option<String>,
+}
@@ -67,6 +67,8 @@ pub struct SyncSummary {
     pub updated: i32,
     pub deleted: i32,
     pub failed: i32,
+    /// Organizations whose changes wait for approval in a preview
+    pub pending_review: i32,
     pub errors: Vec<String>,
 }
 
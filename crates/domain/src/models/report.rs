@@ -1,4 +1,4 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
@@ -78,6 +78,45 @@ pub struct FleetSummaryDto {
     pub monthly_fleet_cost: Option<Decimal>,
 }
 
+// ── Custos por unidade na estrutura vigente em uma data ─────────────────────
+
+/// Documents and cost attributed to one unit (`None` when it had no unit)
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, sqlx::FromRow)]
+pub struct UnitCostDto {
+    pub unit_id: Option<Uuid>,
+    pub document_count: i64,
+    pub total_cost: Decimal,
+}
+
+/// Unit in the as-of structure with its own costs and its subtree totals
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct UnitCostNode {
+    pub unit_id: Uuid,
+    pub name: String,
+    pub acronym: Option<String>,
+    pub is_active: bool,
+    pub direct_count: i64,
+    pub direct_cost: Decimal,
+    pub total_count: i64,
+    pub total_cost: Decimal,
+    #[schema(no_recursion)]
+    pub children: Vec<UnitCostNode>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct UnitCostReport {
+    /// Date of the structure used for the roll-up
+    pub as_of: NaiveDate,
+    pub start_date: Option<NaiveDate>,
+    pub end_date: Option<NaiveDate>,
+    pub total_count: i64,
+    pub total_cost: Decimal,
+    /// Costs whose unit is not part of the structure on `as_of`
+    pub unassigned_count: i64,
+    pub unassigned_cost: Decimal,
+    pub units: Vec<UnitCostNode>,
+}
+
 // ── Filtros de data para relatórios ─────────────────────────────────────────
 
 #[derive(Debug, Clone, Deserialize, ToSchema)]
@@ -2,6 +2,7 @@ use chrono::{DateTime, NaiveDate, Utc};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 // ============================================================================
@@ -34,7 +35,7 @@ pub enum RequisitionPriority {
     Urgent,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema, sqlx::Type)]
 #[sqlx(type_name = "audit_operation_enum", rename_all = "SCREAMING_SNAKE_CASE")]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum AuditOperation {
@@ -70,6 +71,9 @@ pub struct RequisitionDto {
     pub needed_by: Option<NaiveDate>,
     pub approved_by: Option<Uuid>,
     pub approved_at: Option<DateTime<Utc>>,
+    /// Holder of the approval authority when `approved_by` acted as delegate
+    #[sqlx(default)]
+    pub approved_on_behalf_of: Option<Uuid>,
     pub fulfilled_by: Option<Uuid>,
     pub fulfilled_at: Option<DateTime<Utc>>,
     pub rejection_reason: Option<String>,
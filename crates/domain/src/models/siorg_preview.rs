@@ -0,0 +1,131 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema, sqlx::Type)]
+#[sqlx(
+    type_name = "siorg_preview_status_enum",
+    rename_all = "SCREAMING_SNAKE_CASE"
+)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SiorgPreviewStatus {
+    Pending,
+    /// Approved entries were written; the others were rejected
+    Applied,
+    Discarded,
+}
+
+/// What changed in a unit between the local record and SIORG
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SiorgDiffKind {
+    Created,
+    Renamed,
+    /// New parent unit
+    Moved,
+    Deactivated,
+    Reactivated,
+    /// Only contact, address, category or type changed
+    Details,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SiorgDiffDecision {
+    #[default]
+    Pending,
+    Approved,
+    Rejected,
+}
+
+/// Active records that point at a local unit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, ToSchema)]
+pub struct SiorgUnitReferences {
+    pub warehouses: i64,
+    pub vehicles: i64,
+    pub open_requisitions: i64,
+}
+
+impl SiorgUnitReferences {
+    pub fn is_empty(&self) -> bool {
+        self.warehouses == 0 && self.vehicles == 0 && self.open_requisitions == 0
+    }
+}
+
+/// Fields of a local unit compared with SIORG
+#[derive(Debug, Clone, FromRow)]
+pub struct SiorgLocalUnit {
+    pub id: Uuid,
+    pub siorg_code: i32,
+    pub name: String,
+    pub acronym: Option<String>,
+    pub siorg_parent_code: Option<i32>,
+    pub is_active: bool,
+}
+
+/// Change of one unit, as published by SIORG against the local record
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SiorgUnitDiff {
+    pub siorg_code: i32,
+    /// Absent for units created in SIORG
+    pub local_unit_id: Option<Uuid>,
+    pub kinds: Vec<SiorgDiffKind>,
+    pub previous_name: Option<String>,
+    pub name: String,
+    pub previous_acronym: Option<String>,
+    pub acronym: Option<String>,
+    pub previous_parent_code: Option<i32>,
+    pub parent_code: Option<i32>,
+    pub is_active: bool,
+    pub references: SiorgUnitReferences,
+    /// The unit has active warehouses, vehicles or open requisitions
+    pub flagged: bool,
+    #[serde(default)]
+    pub decision: SiorgDiffDecision,
+    /// Unit as published by SIORG, written when the entry is approved
+    #[schema(value_type = Object)]
+    pub upstream: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, FromRow)]
+pub struct SiorgChangePreviewDto {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub org_siorg_code: i32,
+    pub from_version: String,
+    pub to_version: String,
+    pub status: SiorgPreviewStatus,
+    pub total_changes: i32,
+    pub flagged_changes: i32,
+    /// Omitted in listings
+    #[sqlx(json(nullable))]
+    pub entries: Option<Vec<SiorgUnitDiff>>,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub decided_by: Option<Uuid>,
+    pub decided_at: Option<DateTime<Utc>>,
+}
+
+/// Preview computed by the sync, before it is stored
+#[derive(Debug, Clone)]
+pub struct NewSiorgChangePreview {
+    pub organization_id: Uuid,
+    pub org_siorg_code: i32,
+    pub from_version: String,
+    pub to_version: String,
+    pub entries: Vec<SiorgUnitDiff>,
+    pub created_by: Option<Uuid>,
+}
+
+/// Decision over a pending preview. Entries not approved are rejected.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ApplySiorgPreviewPayload {
+    /// Approve every entry
+    #[serde(default)]
+    pub approve_all: bool,
+    /// SIORG codes of the approved entries, when not approving all
+    #[serde(default)]
+    pub siorg_codes: Vec<i32>,
+}
@@ -64,6 +64,9 @@ pub struct VehicleTripDto {
     pub approved_by: Option<Uuid>,
     #[sqlx(rename = "aprovado_em")]
     pub approved_at: Option<DateTime<Utc>>,
+    /// Holder of the review authority when `approved_by` acted as delegate
+    #[sqlx(rename = "aprovado_em_nome_de", default)]
+    pub approved_on_behalf_of: Option<Uuid>,
     #[sqlx(rename = "motivo_rejeicao")]
     pub rejection_reason: Option<String>,
     // Alocação (APROVADA → ALOCADA)
@@ -135,6 +138,9 @@ pub struct ReviewTripPayload {
 pub struct AllocateTripPayload {
     /// Condutor designado para a viagem.
     pub driver_id: Uuid,
+    /// Sugestão do motor de alocação (RF-VIG-05). Quando informada, a viagem
+    /// é alocada ao veículo sugerido em vez do veículo da solicitação.
+    pub suggestion_id: Option<Uuid>,
     pub version: i32,
 }
 
@@ -192,3 +198,146 @@ pub struct TripListFilters {
     pub limit: Option<i64>,
     pub offset: Option<i64>,
 }
+
+// ============================================================
+// RF-VIG-03 — Calendário de reservas e detecção de conflitos
+// ============================================================
+
+/// Origem de um bloqueio na agenda de um veículo ou condutor.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema, sqlx::Type)]
+#[sqlx(type_name = "TEXT", rename_all = "SCREAMING_SNAKE_CASE")]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum BookingKind {
+    #[sqlx(rename = "VIAGEM")]
+    Trip,
+    #[sqlx(rename = "MANUTENCAO")]
+    Maintenance,
+}
+
+/// Intervalo ocupado na agenda — viagem ALOCADA/EM_CURSO ou OS aberta.
+///
+/// Viagens sem `planned_return` ocupam apenas o instante de saída; OS sem
+/// data prevista de conclusão bloqueiam o veículo por tempo indeterminado
+/// (`ends_at = None`).
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, sqlx::FromRow)]
+pub struct TripBookingDto {
+    pub kind: BookingKind,
+    /// ID da viagem ou da ordem de serviço, conforme `kind`.
+    pub reference_id: Uuid,
+    pub vehicle_id: Uuid,
+    pub license_plate: Option<String>,
+    pub driver_id: Option<Uuid>,
+    pub driver_name: Option<String>,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: Option<DateTime<Utc>>,
+    /// Status textual da viagem ou da OS.
+    pub status: String,
+    pub description: Option<String>,
+}
+
+/// Resultado de uma alocação feita sob lock do veículo e do condutor.
+#[derive(Debug, Clone)]
+pub enum TripAllocationOutcome {
+    Allocated(Box<VehicleTripDto>),
+    /// Nada foi gravado: a janela da viagem colide com estes bloqueios.
+    Conflicts(Vec<TripBookingDto>),
+}
+
+/// Janela de consulta da agenda (`start` ≤ `end`).
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TripCalendarQuery {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub vehicle_id: Option<Uuid>,
+    pub driver_id: Option<Uuid>,
+}
+
+/// Agenda de um veículo na janela consultada.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct VehicleScheduleDto {
+    pub vehicle_id: Uuid,
+    pub license_plate: Option<String>,
+    /// `true` quando nenhum bloqueio intersecta a janela.
+    pub is_available: bool,
+    pub bookings: Vec<TripBookingDto>,
+}
+
+/// Agenda de um condutor na janela consultada.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DriverScheduleDto {
+    pub driver_id: Uuid,
+    pub driver_name: Option<String>,
+    pub is_available: bool,
+    pub bookings: Vec<TripBookingDto>,
+}
+
+/// Filtros de busca de veículos livres numa janela.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AvailableVehiclesQuery {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub category_id: Option<Uuid>,
+    /// Capacidade mínima de passageiros do modelo.
+    pub min_seats: Option<i32>,
+}
+
+/// Veículo ATIVO sem viagem ou OS conflitante na janela.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, sqlx::FromRow)]
+pub struct AvailableVehicleDto {
+    pub vehicle_id: Uuid,
+    pub license_plate: String,
+    pub model_id: Uuid,
+    pub model_name: String,
+    pub category_id: Option<Uuid>,
+    pub category_name: Option<String>,
+    pub passenger_capacity: Option<i32>,
+    pub fuel_type_id: Uuid,
+    pub department_id: Option<Uuid>,
+    pub last_odometer_km: Option<i32>,
+}
+
+// ============================================================
+// RF-VIG-05 — Sugestão automática de veículo
+// ============================================================
+
+/// Veículo livre na janela da viagem, com os dados usados no ranking.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, sqlx::FromRow)]
+pub struct AllocationCandidateDto {
+    pub vehicle_id: Uuid,
+    pub license_plate: String,
+    pub model_name: String,
+    pub category_id: Option<Uuid>,
+    pub category_name: Option<String>,
+    pub passenger_capacity: Option<i32>,
+    pub last_odometer_km: Option<i32>,
+    /// Km restantes até a próxima revisão preventiva (negativo = vencida).
+    /// `None` quando o modelo não define intervalo ou não há leitura de hodômetro.
+    pub km_to_preventive: Option<i64>,
+    /// Mesma categoria do veículo informado na solicitação.
+    pub same_category: bool,
+}
+
+/// Sugestão a persistir — gerada pelo motor de ranking.
+#[derive(Debug, Clone)]
+pub struct NewTripSuggestion {
+    pub vehicle_id: Uuid,
+    pub rank: i32,
+    pub score: f64,
+    pub reasons: Vec<String>,
+}
+
+/// Sugestão de alocação persistida (ranking mais recente da viagem).
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, sqlx::FromRow)]
+pub struct TripAllocationSuggestionDto {
+    pub id: Uuid,
+    pub trip_id: Uuid,
+    pub vehicle_id: Uuid,
+    pub license_plate: Option<String>,
+    pub rank: i32,
+    /// Pontuação de 0 a 100.
+    pub score: f64,
+    pub reasons: Vec<String>,
+    pub accepted_at: Option<DateTime<Utc>>,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,111 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema, sqlx::Type)]
+#[sqlx(
+    type_name = "unit_restructuring_kind_enum",
+    rename_all = "SCREAMING_SNAKE_CASE"
+)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum UnitRestructuringKind {
+    /// One or more units replaced by a single successor
+    Merge,
+    /// One unit divided among several successors
+    Split,
+}
+
+/// Record that can be sent to a successor other than the primary one
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum RestructuringReference {
+    Warehouse,
+    Vehicle,
+}
+
+/// Sends one warehouse or vehicle to a specific successor in a split
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RestructuringOverride {
+    pub reference: RestructuringReference,
+    pub id: Uuid,
+    pub successor_id: Uuid,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CreateUnitRestructuringPayload {
+    pub kind: UnitRestructuringKind,
+    /// Units being replaced (exactly one in a split)
+    pub predecessor_ids: Vec<Uuid>,
+    /// Units replacing them (exactly one in a merge)
+    pub successor_ids: Vec<Uuid>,
+    /// Successor that inherits the references in a split
+    pub primary_successor_id: Option<Uuid>,
+    pub effective_date: NaiveDate,
+    pub reason: String,
+    pub documento_sei: Option<String>,
+    #[serde(default)]
+    pub overrides: Vec<RestructuringOverride>,
+    /// Deactivate the predecessors once their references are migrated
+    #[serde(default = "default_true")]
+    pub deactivate_predecessors: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// References moved from the predecessors to the successors
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct RestructuringMigrated {
+    pub warehouses: i64,
+    /// Closed requisitions keep the old unit and are mapped by the lineage
+    pub open_requisitions: i64,
+    /// Moved through department transfers
+    pub vehicles: i64,
+    pub approval_chains: i64,
+    /// Unit roles of users (authorization)
+    pub unit_roles: i64,
+    /// Child units moved under the primary successor
+    pub child_units: i64,
+}
+
+/// Link between a replaced unit and one of its successors
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, FromRow)]
+pub struct UnitLineageDto {
+    pub restructuring_id: Uuid,
+    pub kind: UnitRestructuringKind,
+    pub predecessor_id: Uuid,
+    pub predecessor_name: String,
+    pub successor_id: Uuid,
+    pub successor_name: String,
+    pub is_primary: bool,
+    pub effective_date: NaiveDate,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, FromRow)]
+pub struct UnitRestructuringDto {
+    pub id: Uuid,
+    pub kind: UnitRestructuringKind,
+    pub organization_id: Uuid,
+    pub effective_date: NaiveDate,
+    pub reason: String,
+    pub documento_sei: Option<String>,
+    #[sqlx(json)]
+    pub migrated: RestructuringMigrated,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    #[sqlx(skip)]
+    pub lineage: Vec<UnitLineageDto>,
+}
+
+/// Predecessors and successors of a unit across restructurings
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct UnitLineageResponse {
+    pub unit_id: Uuid,
+    /// Current unit reached through the primary successors
+    pub current_unit_id: Uuid,
+    pub predecessors: Vec<UnitLineageDto>,
+    pub successors: Vec<UnitLineageDto>,
+}
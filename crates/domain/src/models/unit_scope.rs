@@ -0,0 +1,31 @@
+use uuid::Uuid;
+
+/// Organizational units in which the caller may act for the current request.
+///
+/// Resolved by the authorization layer: a global role grants `All`, while
+/// roles assigned within a unit (and optionally its subtree) grant only
+/// those units. Services use it to filter rows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UnitScope {
+    All,
+    Units(Vec<Uuid>),
+}
+
+impl UnitScope {
+    /// Unit filter for queries (`$n::UUID[] IS NULL OR unit_id = ANY($n)`);
+    /// `None` means unrestricted
+    pub fn unit_ids(&self) -> Option<&[Uuid]> {
+        match self {
+            UnitScope::All => None,
+            UnitScope::Units(ids) => Some(ids),
+        }
+    }
+
+    /// Rows not linked to any unit are only visible with a global role
+    pub fn contains(&self, unit_id: Option<Uuid>) -> bool {
+        match self {
+            UnitScope::All => true,
+            UnitScope::Units(ids) => unit_id.is_some_and(|id| ids.contains(&id)),
+        }
+    }
+}
@@ -145,6 +145,8 @@ pub struct VehicleModelDto {
     pub avg_consumption_min: Option<Decimal>,
     pub avg_consumption_max: Option<Decimal>,
     pub avg_consumption_target: Option<Decimal>,
+    /// Intervalo de manutenção preventiva, em km.
+    pub preventive_interval_km: Option<i32>,
     pub is_active: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
@@ -165,6 +167,8 @@ pub struct VehicleModelWithDetailsDto {
     pub avg_consumption_min: Option<Decimal>,
     pub avg_consumption_max: Option<Decimal>,
     pub avg_consumption_target: Option<Decimal>,
+    /// Intervalo de manutenção preventiva, em km.
+    pub preventive_interval_km: Option<i32>,
     pub is_active: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
@@ -182,6 +186,8 @@ pub struct CreateVehicleModelPayload {
     pub avg_consumption_min: Option<Decimal>,
     pub avg_consumption_max: Option<Decimal>,
     pub avg_consumption_target: Option<Decimal>,
+    /// Intervalo de manutenção preventiva, em km.
+    pub preventive_interval_km: Option<i32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -195,6 +201,8 @@ pub struct UpdateVehicleModelPayload {
     pub avg_consumption_min: Option<Decimal>,
     pub avg_consumption_max: Option<Decimal>,
     pub avg_consumption_target: Option<Decimal>,
+    /// Intervalo de manutenção preventiva, em km.
+    pub preventive_interval_km: Option<i32>,
     pub is_active: Option<bool>,
 }
 
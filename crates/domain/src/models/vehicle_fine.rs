@@ -1,4 +1,4 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
@@ -52,6 +52,16 @@ pub enum FineStatus {
     Cancelled,
 }
 
+/// Origem da indicação do condutor infrator.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, sqlx::Type, PartialEq)]
+#[sqlx(type_name = "fine_driver_source_enum", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum FineDriverSource {
+    #[serde(rename = "TRIP")]
+    Trip,
+    #[serde(rename = "MANUAL")]
+    Manual,
+}
+
 // ============================
 // Vehicle Fine Type DTOs
 // ============================
@@ -113,6 +123,12 @@ pub struct VehicleFineDto {
     pub payment_date: Option<DateTime<Utc>>,
     pub status: FineStatus,
     pub notes: Option<String>,
+    pub trip_id: Option<Uuid>,
+    pub driver_source: Option<FineDriverSource>,
+    pub driver_identified_at: Option<DateTime<Utc>>,
+    pub identification_deadline: Option<NaiveDate>,
+    pub defense_deadline: Option<NaiveDate>,
+    pub appeal_deadline: Option<NaiveDate>,
     pub is_deleted: bool,
     pub deleted_at: Option<DateTime<Utc>>,
     pub deleted_by: Option<Uuid>,
@@ -149,6 +165,12 @@ pub struct VehicleFineWithDetailsDto {
     pub payment_date: Option<DateTime<Utc>>,
     pub status: FineStatus,
     pub notes: Option<String>,
+    pub trip_id: Option<Uuid>,
+    pub driver_source: Option<FineDriverSource>,
+    pub driver_identified_at: Option<DateTime<Utc>>,
+    pub identification_deadline: Option<NaiveDate>,
+    pub defense_deadline: Option<NaiveDate>,
+    pub appeal_deadline: Option<NaiveDate>,
     pub is_deleted: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
@@ -210,3 +232,148 @@ pub struct VehicleFineStatusHistoryDto {
     pub changed_by: Option<Uuid>,
     pub created_at: DateTime<Utc>,
 }
+
+// ============================
+// Driver Identification DTOs
+// ============================
+
+/// Indicação do condutor infrator. Sem `driver_id`, o condutor é resolvido
+/// pela viagem em curso no veículo na data da infração.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct IdentifyFineDriverPayload {
+    pub driver_id: Option<Uuid>,
+    pub reason: Option<String>,
+}
+
+/// Viagem em curso no veículo em determinado instante.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, sqlx::FromRow)]
+pub struct FineTripDriverRef {
+    pub trip_id: Uuid,
+    pub driver_id: Uuid,
+}
+
+/// Dados do Formulário de Identificação do Condutor Infrator (FICI).
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, sqlx::FromRow)]
+pub struct FineIdentificationFormDto {
+    pub fine_id: Uuid,
+    pub auto_number: Option<String>,
+    pub fine_date: DateTime<Utc>,
+    pub notification_date: Option<DateTime<Utc>>,
+    pub location: Option<String>,
+    pub infraction_code: String,
+    pub infraction_description: String,
+    pub infraction_points: i32,
+    pub issuing_authority: Option<String>,
+    pub vehicle_license_plate: String,
+    pub vehicle_renavam: String,
+    pub driver_name: String,
+    pub driver_cpf: String,
+    pub driver_cnh_number: String,
+    pub driver_cnh_category: String,
+    pub driver_cnh_expiration: NaiveDate,
+    pub trip_id: Option<Uuid>,
+    pub identification_deadline: Option<NaiveDate>,
+    pub sei_process_number: Option<String>,
+}
+
+// ============================
+// Deadline DTOs
+// ============================
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum FineDeadlineKind {
+    DriverIdentification,
+    PriorDefense,
+    Appeal,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct FineDeadlineDto {
+    pub fine_id: Uuid,
+    pub vehicle_license_plate: Option<String>,
+    pub auto_number: Option<String>,
+    pub status: FineStatus,
+    pub kind: FineDeadlineKind,
+    pub deadline: NaiveDate,
+    /// Negativo quando o prazo já venceu.
+    pub days_remaining: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct FineDeadlineQuery {
+    /// Horizonte em dias (padrão: 15). Prazos vencidos sempre são incluídos.
+    pub within_days: Option<i64>,
+}
+
+// ============================
+// Driver Points DTOs
+// ============================
+
+/// Pontuação bruta do condutor na janela de 12 meses.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, sqlx::FromRow)]
+pub struct DriverPointsTotalsDto {
+    pub driver_id: Uuid,
+    pub driver_name: String,
+    pub cnh_number: String,
+    pub total_points: i64,
+    pub fine_count: i64,
+    pub very_serious_count: i64,
+}
+
+/// `Ok` nunca é gravado: `driver_points_alerts` só guarda os níveis de alerta.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema, sqlx::Type, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[sqlx(type_name = "driver_points_alert_level_enum", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum DriverPointsAlertLevel {
+    Ok,
+    Warning,
+    Suspension,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DriverPointsSummaryDto {
+    pub driver_id: Uuid,
+    pub driver_name: String,
+    pub cnh_number: String,
+    pub total_points: i64,
+    pub fine_count: i64,
+    pub very_serious_count: i64,
+    /// Limite de suspensão da CNH (CTB Art. 261): 20, 30 ou 40 pontos.
+    pub suspension_threshold: i64,
+    pub remaining_points: i64,
+    pub alert_level: DriverPointsAlertLevel,
+    pub window_start: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DriverPointsQuery {
+    /// Retorna apenas condutores em alerta.
+    pub only_alerts: Option<bool>,
+}
+
+/// Alerta de pontuação gerado pela indicação de condutor.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, sqlx::FromRow)]
+pub struct DriverPointsAlertDto {
+    pub id: Uuid,
+    pub driver_id: Uuid,
+    pub driver_name: String,
+    pub cnh_number: String,
+    /// Multa cuja indicação gerou ou atualizou o alerta.
+    pub vehicle_fine_id: Option<Uuid>,
+    pub alert_level: DriverPointsAlertLevel,
+    pub total_points: i64,
+    pub suspension_threshold: i64,
+    pub acknowledged_at: Option<DateTime<Utc>>,
+    pub acknowledged_by: Option<Uuid>,
+    /// Alerta de suspensão que encerrou este alerta de WARNING.
+    pub superseded_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DriverPointsAlertQuery {
+    /// Inclui alertas com ciência já registrada ou substituídos.
+    pub include_acknowledged: Option<bool>,
+}
@@ -453,6 +453,9 @@ pub struct StockTransferDto {
     pub initiated_by: Uuid,
     pub initiated_by_name: Option<String>,
     pub confirmed_by: Option<Uuid>,
+    /// Holder of the confirmation authority when `confirmed_by` acted as delegate
+    #[sqlx(default)]
+    pub confirmed_on_behalf_of: Option<Uuid>,
     pub rejected_by: Option<Uuid>,
     pub cancelled_by: Option<Uuid>,
     pub initiated_at: DateTime<Utc>,
@@ -0,0 +1,101 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+/// Which ceremony a pending WebAuthn challenge belongs to
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, sqlx::Type, PartialEq)]
+#[sqlx(type_name = "webauthn_challenge_kind_enum", rename_all = "SCREAMING_SNAKE_CASE")]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum WebauthnChallengeKind {
+    Registration,
+    Authentication,
+    Discoverable,
+}
+
+/// Registered security key / passkey, as shown to its owner
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, FromRow)]
+pub struct WebauthnCredentialDto {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    /// Credential ID (base64url)
+    pub credential_id: String,
+    pub sign_count: i64,
+    /// Synced passkey (iCloud Keychain, Google Password Manager, ...)
+    pub backup_eligible: bool,
+    pub backup_state: bool,
+    /// Set when the signature counter went backwards (possible cloned key)
+    pub clone_detected_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+/// Stored credential including the serialized public key material
+#[derive(Debug, Clone, FromRow)]
+pub struct StoredWebauthnCredential {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub credential_id: String,
+    pub passkey: serde_json::Value,
+    pub sign_count: i64,
+    pub clone_detected_at: Option<DateTime<Utc>>,
+}
+
+/// Pending ceremony state (single use)
+#[derive(Debug, Clone, FromRow)]
+pub struct WebauthnChallenge {
+    pub id: Uuid,
+    pub user_id: Option<Uuid>,
+    pub kind: WebauthnChallengeKind,
+    pub state: serde_json::Value,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Options to pass to `navigator.credentials.create()` / `.get()`
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct WebauthnChallengeDto {
+    pub challenge_id: Uuid,
+    #[schema(value_type = Object)]
+    pub options: serde_json::Value,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct StartPasskeyRegistrationPayload {
+    #[validate(length(min = 1, max = 100, message = "Nome deve ter entre 1 e 100 caracteres"))]
+    pub name: String,
+}
+
+/// Response of `navigator.credentials.create()` / `.get()`, paired with the
+/// challenge that originated it
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct FinishPasskeyCeremonyPayload {
+    pub challenge_id: Uuid,
+    #[schema(value_type = Object)]
+    pub credential: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct RenamePasskeyPayload {
+    #[validate(length(min = 1, max = 100, message = "Nome deve ter entre 1 e 100 caracteres"))]
+    pub name: String,
+}
+
+/// Starts a passkey assertion for the MFA challenge issued at login
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct PasskeyMfaStartPayload {
+    #[validate(length(min = 1, message = "MFA token não pode estar vazio"))]
+    pub mfa_token: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct PasskeyMfaFinishPayload {
+    #[validate(length(min = 1, message = "MFA token não pode estar vazio"))]
+    pub mfa_token: String,
+    pub challenge_id: Uuid,
+    #[schema(value_type = Object)]
+    pub credential: serde_json::Value,
+}
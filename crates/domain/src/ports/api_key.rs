@@ -0,0 +1,51 @@
+use crate::errors::RepositoryError;
+use crate::models::{ApiKeyDto, NewApiKey, ServicePrincipalDto, StoredApiKey};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// Repository trait for service principals and their API keys.
+#[async_trait]
+pub trait ApiKeyRepositoryPort: Send + Sync {
+    async fn create_principal(
+        &self,
+        name: &str,
+        description: Option<&str>,
+        role: &str,
+        created_by: Option<Uuid>,
+    ) -> Result<ServicePrincipalDto, RepositoryError>;
+
+    async fn find_principal(&self, id: Uuid) -> Result<Option<ServicePrincipalDto>, RepositoryError>;
+
+    async fn exists_principal_by_name(&self, name: &str) -> Result<bool, RepositoryError>;
+
+    async fn list_principals(&self) -> Result<Vec<ServicePrincipalDto>, RepositoryError>;
+
+    async fn update_principal(
+        &self,
+        id: Uuid,
+        description: Option<&str>,
+        role: Option<&str>,
+        is_active: Option<bool>,
+    ) -> Result<Option<ServicePrincipalDto>, RepositoryError>;
+
+    async fn create_key(&self, key: &NewApiKey) -> Result<ApiKeyDto, RepositoryError>;
+
+    /// Creates the replacement key and shortens the old key's expiry in one transaction
+    async fn rotate_key(
+        &self,
+        old_id: Uuid,
+        old_expires_at: DateTime<Utc>,
+        new_key: &NewApiKey,
+    ) -> Result<ApiKeyDto, RepositoryError>;
+
+    async fn find_key(&self, id: Uuid) -> Result<Option<ApiKeyDto>, RepositoryError>;
+
+    async fn list_keys_by_principal(&self, principal_id: Uuid) -> Result<Vec<ApiKeyDto>, RepositoryError>;
+
+    async fn find_stored_by_prefix(&self, prefix: &str) -> Result<Option<StoredApiKey>, RepositoryError>;
+
+    async fn revoke_key(&self, id: Uuid) -> Result<bool, RepositoryError>;
+
+    async fn touch_key(&self, id: Uuid, ip: Option<&str>) -> Result<(), RepositoryError>;
+}
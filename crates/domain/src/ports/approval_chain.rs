@@ -0,0 +1,73 @@
+use crate::errors::RepositoryError;
+use crate::models::{
+    ApprovalChainDto, ApprovalDocumentType, ApprovalStepStatus, CreateApprovalChainPayload,
+    DocumentApprovalStepDto, UpdateApprovalChainPayload,
+};
+use async_trait::async_trait;
+use uuid::Uuid;
+
+/// Repository trait for approval chains and the steps instantiated per document.
+#[async_trait]
+pub trait ApprovalChainRepositoryPort: Send + Sync {
+    async fn create_chain(
+        &self,
+        payload: &CreateApprovalChainPayload,
+        created_by: Uuid,
+    ) -> Result<ApprovalChainDto, RepositoryError>;
+
+    /// Replaces the chain conditions and its steps
+    async fn update_chain(
+        &self,
+        id: Uuid,
+        payload: &UpdateApprovalChainPayload,
+    ) -> Result<ApprovalChainDto, RepositoryError>;
+
+    async fn find_chain(&self, id: Uuid) -> Result<Option<ApprovalChainDto>, RepositoryError>;
+
+    async fn list_chains(
+        &self,
+        document_type: Option<ApprovalDocumentType>,
+    ) -> Result<Vec<ApprovalChainDto>, RepositoryError>;
+
+    /// Active chains of a document type, highest precedence first
+    async fn list_active_chains(
+        &self,
+        document_type: ApprovalDocumentType,
+    ) -> Result<Vec<ApprovalChainDto>, RepositoryError>;
+
+    async fn deactivate_chain(&self, id: Uuid) -> Result<bool, RepositoryError>;
+
+    async fn list_document_steps(
+        &self,
+        document_type: ApprovalDocumentType,
+        document_id: Uuid,
+    ) -> Result<Vec<DocumentApprovalStepDto>, RepositoryError>;
+
+    /// Copies the chain steps to the document unless it already has steps.
+    /// Returns the document steps either way.
+    async fn start_document_chain(
+        &self,
+        document_type: ApprovalDocumentType,
+        document_id: Uuid,
+        chain: &ApprovalChainDto,
+    ) -> Result<Vec<DocumentApprovalStepDto>, RepositoryError>;
+
+    /// Records the decision on a still pending step. Returns false when the
+    /// step was decided concurrently.
+    async fn decide_step(
+        &self,
+        step_id: Uuid,
+        status: ApprovalStepStatus,
+        decided_by: Uuid,
+        on_behalf_of: Option<Uuid>,
+        comments: Option<&str>,
+    ) -> Result<bool, RepositoryError>;
+
+    /// Roles held by the user globally or in `unit_id` (directly or through
+    /// an ancestor unit assigned with its subtree)
+    async fn roles_of(
+        &self,
+        user_id: Uuid,
+        unit_id: Option<Uuid>,
+    ) -> Result<Vec<String>, RepositoryError>;
+}
@@ -0,0 +1,75 @@
+use crate::errors::RepositoryError;
+use crate::models::{
+    AuditArchiveDto, AuditChainEntry, AuditCheckpointDto, AuditRedactionCandidate,
+    NewAuditArchive, NewAuditRedaction,
+};
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use uuid::Uuid;
+
+/// Repository trait for the audit log hash chain (logs database).
+#[async_trait]
+pub trait AuditIntegrityRepositoryPort: Send + Sync {
+    /// Days in `[from, to]` with live entries, checkpoints or archives
+    async fn chain_days(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<NaiveDate>, RepositoryError>;
+
+    /// Live entries of a day ordered by `seq`, with the recomputed hash check
+    async fn chain_entries(&self, day: NaiveDate) -> Result<Vec<AuditChainEntry>, RepositoryError>;
+
+    /// Days before `before` that still have live entries
+    async fn days_with_entries_before(
+        &self,
+        before: NaiveDate,
+    ) -> Result<Vec<NaiveDate>, RepositoryError>;
+
+    /// Days before `before` with live entries and no checkpoint
+    async fn unsealed_days_before(
+        &self,
+        before: NaiveDate,
+    ) -> Result<Vec<NaiveDate>, RepositoryError>;
+
+    async fn find_checkpoint(
+        &self,
+        day: NaiveDate,
+    ) -> Result<Option<AuditCheckpointDto>, RepositoryError>;
+
+    async fn list_checkpoints(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<AuditCheckpointDto>, RepositoryError>;
+
+    /// Stores a checkpoint; returns `false` if the day was already sealed
+    async fn save_checkpoint(
+        &self,
+        checkpoint: &AuditCheckpointDto,
+    ) -> Result<bool, RepositoryError>;
+
+    /// Archives of a day ordered by `first_seq`
+    async fn list_archives(&self, day: NaiveDate) -> Result<Vec<AuditArchiveDto>, RepositoryError>;
+
+    /// Records the archive and removes the archived range from `audit_logs`
+    /// in one transaction
+    async fn archive_range(
+        &self,
+        archive: &NewAuditArchive,
+    ) -> Result<AuditArchiveDto, RepositoryError>;
+
+    /// Live entries of the user that still hold username, IP or user agent
+    async fn redaction_candidates(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<AuditRedactionCandidate>, RepositoryError>;
+
+    /// Erases the personal fields of `entry_ids` and records the signed
+    /// redactions in one transaction. Returns the number of entries erased.
+    async fn redact(
+        &self,
+        entry_ids: &[Uuid],
+        redactions: &[NewAuditRedaction],
+    ) -> Result<u64, RepositoryError>;
+}
@@ -0,0 +1,46 @@
+use crate::errors::RepositoryError;
+use crate::models::{
+    CatalogDuplicateCandidate, CatalogDuplicateFilter, CatalogDuplicatePair,
+    CatalogItemMergeResult, CatalogItemRedirectDto,
+};
+use async_trait::async_trait;
+use uuid::Uuid;
+
+/// Repository trait for finding and merging duplicate CATMAT items.
+#[async_trait]
+pub trait CatalogMergeRepositoryPort: Send + Sync {
+    /// Pairs of items with similar descriptions, most similar first
+    async fn find_duplicate_pairs(
+        &self,
+        filter: &CatalogDuplicateFilter,
+        limit: i64,
+    ) -> Result<Vec<CatalogDuplicatePair>, RepositoryError>;
+
+    async fn find_candidates(
+        &self,
+        ids: &[Uuid],
+    ) -> Result<Vec<CatalogDuplicateCandidate>, RepositoryError>;
+
+    /// Moves every reference of `duplicate_ids` to `survivor_id`, combining
+    /// stock balances, then deactivates the duplicates as merged into it,
+    /// leaving a redirect for each one. All or nothing.
+    async fn merge_items(
+        &self,
+        survivor_id: Uuid,
+        duplicate_ids: &[Uuid],
+        reason: Option<&str>,
+        merged_by: Option<Uuid>,
+    ) -> Result<CatalogItemMergeResult, RepositoryError>;
+
+    async fn find_redirect(
+        &self,
+        old_item_id: Uuid,
+    ) -> Result<Option<CatalogItemRedirectDto>, RepositoryError>;
+
+    async fn list_redirects(
+        &self,
+        new_item_id: Option<Uuid>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<CatalogItemRedirectDto>, i64), RepositoryError>;
+}
@@ -0,0 +1,41 @@
+use crate::errors::RepositoryError;
+use crate::models::{
+    CatalogKind, CatalogMatchKind, CatalogSuggestion, CatmatItemSearchFilter, CatmatItemSearchHit,
+    CatserItemSearchFilter, CatserItemSearchHit,
+};
+use async_trait::async_trait;
+
+/// Repository trait for the ranked text search over catalog items.
+///
+/// `FullText` hits come with the matched terms highlighted; `Fuzzy` hits
+/// carry the plain description in `highlight`.
+#[async_trait]
+pub trait CatalogSearchRepositoryPort: Send + Sync {
+    async fn search_catmat_items(
+        &self,
+        query: &str,
+        kind: CatalogMatchKind,
+        filter: &CatmatItemSearchFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<CatmatItemSearchHit>, i64), RepositoryError>;
+
+    async fn search_catser_items(
+        &self,
+        query: &str,
+        kind: CatalogMatchKind,
+        filter: &CatserItemSearchFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<CatserItemSearchHit>, i64), RepositoryError>;
+
+    /// Active items matching every term of `prefix_query` (a `to_tsquery`
+    /// expression of prefix terms) or whose code starts with `code_prefix`
+    async fn suggest(
+        &self,
+        prefix_query: &str,
+        code_prefix: Option<&str>,
+        catalog: Option<CatalogKind>,
+        limit: i64,
+    ) -> Result<Vec<CatalogSuggestion>, RepositoryError>;
+}
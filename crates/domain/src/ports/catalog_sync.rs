@@ -0,0 +1,54 @@
+use crate::errors::RepositoryError;
+use crate::models::{
+    CatalogKind, CatalogLevel, CatalogNode, CatalogNodeFilter, CatalogSyncPlan, CatalogSyncReport,
+    CatalogSyncRunDto, CatalogSyncScope, CatalogSyncStatus,
+};
+use async_trait::async_trait;
+use uuid::Uuid;
+
+/// Repository trait for the synchronization of the catalog with ComprasGov.
+#[async_trait]
+pub trait CatalogSyncRepositoryPort: Send + Sync {
+    /// Local nodes of a level that carry an official code
+    async fn list_nodes(
+        &self,
+        catalog: CatalogKind,
+        level: CatalogLevel,
+        filter: &CatalogNodeFilter,
+    ) -> Result<Vec<CatalogNode>, RepositoryError>;
+
+    /// Upserts (by official code) and deactivations of the plan in one transaction
+    async fn apply(&self, plan: &CatalogSyncPlan) -> Result<(), RepositoryError>;
+
+    /// Registers a run as running. Fails with `Duplicate` while another
+    /// effective run of the same catalog is running.
+    async fn create_run(
+        &self,
+        catalog: CatalogKind,
+        scope: CatalogSyncScope,
+        scope_code: Option<&str>,
+        dry_run: bool,
+        triggered_by: Option<Uuid>,
+    ) -> Result<CatalogSyncRunDto, RepositoryError>;
+
+    async fn finish_run(
+        &self,
+        id: Uuid,
+        status: CatalogSyncStatus,
+        report: Option<&CatalogSyncReport>,
+        error: Option<&str>,
+    ) -> Result<CatalogSyncRunDto, RepositoryError>;
+
+    /// Marks runs left running by a previous process as failed
+    async fn fail_interrupted_runs(&self, error: &str) -> Result<u64, RepositoryError>;
+
+    async fn find_run(&self, id: Uuid) -> Result<Option<CatalogSyncRunDto>, RepositoryError>;
+
+    /// Runs without the report, most recent first
+    async fn list_runs(
+        &self,
+        catalog: Option<CatalogKind>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<CatalogSyncRunDto>, i64), RepositoryError>;
+}
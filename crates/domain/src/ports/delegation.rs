@@ -0,0 +1,48 @@
+use crate::errors::RepositoryError;
+use crate::models::{ApprovalDelegationDto, DelegationScope};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// Repository trait for approval delegations.
+#[async_trait]
+pub trait ApprovalDelegationRepositoryPort: Send + Sync {
+    #[allow(clippy::too_many_arguments)]
+    async fn create(
+        &self,
+        delegator_id: Uuid,
+        delegate_id: Uuid,
+        scopes: &[DelegationScope],
+        valid_from: DateTime<Utc>,
+        valid_until: DateTime<Utc>,
+        reason: Option<&str>,
+        created_by: Uuid,
+    ) -> Result<ApprovalDelegationDto, RepositoryError>;
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<ApprovalDelegationDto>, RepositoryError>;
+
+    /// Delegations granted or received by `user_id`
+    async fn list_for_user(
+        &self,
+        user_id: Uuid,
+        active_only: bool,
+    ) -> Result<Vec<ApprovalDelegationDto>, RepositoryError>;
+
+    /// Delegations currently in force for `delegate_id` covering `scope`
+    async fn list_active_for_delegate(
+        &self,
+        delegate_id: Uuid,
+        scope: DelegationScope,
+    ) -> Result<Vec<ApprovalDelegationDto>, RepositoryError>;
+
+    /// Delegation currently in force from `delegator_id` to `delegate_id` covering `scope`
+    async fn find_active(
+        &self,
+        delegator_id: Uuid,
+        delegate_id: Uuid,
+        scope: DelegationScope,
+    ) -> Result<Option<ApprovalDelegationDto>, RepositoryError>;
+
+    /// Returns false when the delegation does not exist or was already revoked
+    async fn revoke(&self, id: Uuid, revoked_by: Uuid) -> Result<bool, RepositoryError>;
+}
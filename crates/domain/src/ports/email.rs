@@ -30,4 +30,14 @@ pub trait EmailServicePort: Send + Sync {
     /// Sends a notification that MFA has been enabled.
     async fn send_mfa_enabled_email(&self, to: &Email, username: &Username)
         -> Result<(), EmailError>;
+
+    /// Warns that the account was locked after repeated failed logins,
+    /// with a link to unlock it.
+    async fn send_account_locked_email(
+        &self,
+        to: &Email,
+        username: &Username,
+        locked_until: chrono::DateTime<chrono::Utc>,
+        unlock_token: &str,
+    ) -> Result<(), EmailError>;
 }
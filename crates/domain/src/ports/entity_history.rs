@@ -0,0 +1,26 @@
+use crate::errors::RepositoryError;
+use crate::models::{EntityChangeDto, HistoryEntity, NewEntityChange};
+use async_trait::async_trait;
+use uuid::Uuid;
+
+/// Repository trait for the field-level change history of entities.
+#[async_trait]
+pub trait EntityHistoryRepositoryPort: Send + Sync {
+    /// Changes of one entity, newest first
+    async fn list(
+        &self,
+        entity: HistoryEntity,
+        entity_id: &str,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<EntityChangeDto>, i64), RepositoryError>;
+
+    /// Changes made by one HTTP request, in the order they happened
+    async fn list_by_request(
+        &self,
+        request_id: Uuid,
+    ) -> Result<Vec<EntityChangeDto>, RepositoryError>;
+
+    /// Records a change attributed to the current request context
+    async fn record(&self, change: &NewEntityChange) -> Result<EntityChangeDto, RepositoryError>;
+}
@@ -0,0 +1,65 @@
+use crate::errors::RepositoryError;
+use crate::models::{
+    FieldKeyRotationBatch, FieldKeyRotationDto, FieldKeyRotationStage, FieldKeyRotationStatus,
+};
+use async_trait::async_trait;
+use uuid::Uuid;
+
+/// Repository trait for the re-encryption of field-level encrypted data
+/// after a key rotation.
+#[async_trait]
+pub trait FieldKeyRotationRepositoryPort: Send + Sync {
+    /// Rows holding encrypted fields, across every stage
+    async fn count_rows(&self) -> Result<i64, RepositoryError>;
+
+    /// Re-encrypts with the current key the rows of `stage` after `after`
+    /// (in id order) and rebuilds their blind indexes. Rows changed
+    /// concurrently are left to the writer, which already uses the current key.
+    async fn reencrypt_batch(
+        &self,
+        stage: FieldKeyRotationStage,
+        after: Option<Uuid>,
+        limit: i64,
+    ) -> Result<FieldKeyRotationBatch, RepositoryError>;
+
+    /// Registers a run as running. Fails with `Duplicate` while another run
+    /// is running.
+    async fn create_run(
+        &self,
+        target_key_id: i32,
+        total_rows: i64,
+        triggered_by: Option<Uuid>,
+    ) -> Result<FieldKeyRotationDto, RepositoryError>;
+
+    /// Moves the resume point to `last_processed_id` within `stage` and adds
+    /// the batch counters
+    async fn save_progress(
+        &self,
+        id: Uuid,
+        stage: FieldKeyRotationStage,
+        last_processed_id: Option<Uuid>,
+        batch: &FieldKeyRotationBatch,
+    ) -> Result<FieldKeyRotationDto, RepositoryError>;
+
+    async fn finish_run(
+        &self,
+        id: Uuid,
+        status: FieldKeyRotationStatus,
+        error: Option<&str>,
+    ) -> Result<FieldKeyRotationDto, RepositoryError>;
+
+    /// Puts a failed run back to running, keeping its resume point. Fails
+    /// with `Duplicate` while another run is running.
+    async fn reopen_run(&self, id: Uuid) -> Result<FieldKeyRotationDto, RepositoryError>;
+
+    async fn find_running(&self) -> Result<Option<FieldKeyRotationDto>, RepositoryError>;
+
+    async fn find_run(&self, id: Uuid) -> Result<Option<FieldKeyRotationDto>, RepositoryError>;
+
+    /// Most recent first
+    async fn list_runs(
+        &self,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<FieldKeyRotationDto>, i64), RepositoryError>;
+}
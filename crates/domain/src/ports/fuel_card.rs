@@ -0,0 +1,58 @@
+use crate::errors::RepositoryError;
+use crate::models::fuel_card::*;
+use crate::models::fueling::FuelingDto;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+#[async_trait]
+pub trait FuelCardRepositoryPort: Send + Sync {
+    /// Active vehicle by normalized plate (uppercase, no dash).
+    async fn find_vehicle_by_plate(
+        &self,
+        license_plate: &str,
+    ) -> Result<Option<FuelCardVehicleRef>, RepositoryError>;
+
+    /// Driver ID by CPF digits.
+    async fn find_driver_by_cpf(&self, cpf: &str) -> Result<Option<Uuid>, RepositoryError>;
+
+    /// Fuel type ID by case-insensitive name.
+    async fn find_fuel_type_by_name(&self, name: &str) -> Result<Option<Uuid>, RepositoryError>;
+
+    /// Non-rejected fuelings of a vehicle in `[start, end]` not yet linked to
+    /// any card transaction.
+    async fn find_unlinked_fuelings(
+        &self,
+        vehicle_id: Uuid,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<FuelingDto>, RepositoryError>;
+
+    /// Whether the operator's transaction was already imported.
+    async fn exists_by_external_id(
+        &self,
+        operator: &str,
+        external_id: &str,
+    ) -> Result<bool, RepositoryError>;
+
+    /// Stores the transaction together with its `pending_fueling`, if any,
+    /// in a single database transaction.
+    async fn create_transaction(
+        &self,
+        job_id: Uuid,
+        operator: &str,
+        transaction: &NewFuelCardTransaction,
+    ) -> Result<FuelCardTransactionDto, RepositoryError>;
+
+    async fn list_by_job(
+        &self,
+        job_id: Uuid,
+        match_status: Option<FuelCardMatchStatus>,
+    ) -> Result<Vec<FuelCardTransactionDto>, RepositoryError>;
+
+    /// Number of transactions of the job per match status.
+    async fn count_by_status(
+        &self,
+        job_id: Uuid,
+    ) -> Result<Vec<(FuelCardMatchStatus, i64)>, RepositoryError>;
+}
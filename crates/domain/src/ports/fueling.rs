@@ -22,8 +22,12 @@ pub trait FuelingRepositoryPort: Send + Sync {
         unit_price: Decimal,
         total_cost: Decimal,
         notes: Option<&str>,
+        review_status: FuelingReviewStatus,
+        anomalies: Option<serde_json::Value>,
         created_by: Option<Uuid>,
     ) -> Result<FuelingDto, RepositoryError>;
+    /// `Some(review_status)` re-classifies the fueling: replaces the anomalies
+    /// and clears the previous review. `None` keeps the current review.
     async fn update(
         &self,
         id: Uuid,
@@ -37,6 +41,8 @@ pub trait FuelingRepositoryPort: Send + Sync {
         unit_price: Option<Decimal>,
         total_cost: Option<Decimal>,
         notes: Option<&str>,
+        review_status: Option<FuelingReviewStatus>,
+        anomalies: Option<serde_json::Value>,
         updated_by: Option<Uuid>,
     ) -> Result<FuelingDto, RepositoryError>;
     async fn delete(&self, id: Uuid) -> Result<bool, RepositoryError>;
@@ -47,5 +53,35 @@ pub trait FuelingRepositoryPort: Send + Sync {
         vehicle_id: Option<Uuid>,
         driver_id: Option<Uuid>,
         supplier_id: Option<Uuid>,
+        review_status: Option<FuelingReviewStatus>,
     ) -> Result<(Vec<FuelingWithDetailsDto>, i64), RepositoryError>;
+    /// Most recent non-rejected fuelings of a vehicle strictly before `before`.
+    async fn find_previous_by_vehicle(
+        &self,
+        vehicle_id: Uuid,
+        before: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<FuelingDto>, RepositoryError>;
+    /// Earliest non-rejected fueling of a vehicle strictly after `after`.
+    async fn find_next_by_vehicle(
+        &self,
+        vehicle_id: Uuid,
+        after: DateTime<Utc>,
+    ) -> Result<Option<FuelingDto>, RepositoryError>;
+    /// Median unit price of non-rejected fuelings of a fuel type in `[since, until]`.
+    async fn median_unit_price(
+        &self,
+        fuel_type_id: Uuid,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Result<Option<Decimal>, RepositoryError>;
+    /// QUARENTENA → VALIDADO | REJEITADO. Returns `None` if the fueling is not
+    /// (or no longer) in quarantine.
+    async fn review(
+        &self,
+        id: Uuid,
+        review_status: FuelingReviewStatus,
+        reviewed_by: Uuid,
+        review_notes: Option<&str>,
+    ) -> Result<Option<FuelingDto>, RepositoryError>;
 }
@@ -0,0 +1,28 @@
+use crate::errors::RepositoryError;
+use crate::models::{JwtSigningKeyDto, NewJwtSigningKey};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// Repository trait for the JWT signing keyring.
+#[async_trait]
+pub trait JwtSigningKeyRepositoryPort: Send + Sync {
+    /// Every key ever registered, expired ones included (they still verify
+    /// detached signatures). Most recent first.
+    async fn list_keys(&self) -> Result<Vec<JwtSigningKeyDto>, RepositoryError>;
+
+    /// Active key and its private key (PKCS#8/PKCS#1 DER, base64)
+    async fn find_active(&self) -> Result<Option<(JwtSigningKeyDto, String)>, RepositoryError>;
+
+    /// Retires the active key until `verify_until` and activates `new`.
+    /// Before the first rotation there is no active key: `previous` (the
+    /// configured key) is registered as retired instead. Fails with
+    /// `Duplicate` when another rotation activates a key concurrently.
+    async fn rotate(
+        &self,
+        new: &NewJwtSigningKey,
+        previous: &NewJwtSigningKey,
+        verify_until: DateTime<Utc>,
+        created_by: Option<Uuid>,
+    ) -> Result<JwtSigningKeyDto, RepositoryError>;
+}
@@ -0,0 +1,26 @@
+use crate::errors::RepositoryError;
+use crate::models::{AnonymizationPlan, AnonymizationResult, DataSubjectDriver, DataSubjectUser};
+use async_trait::async_trait;
+use uuid::Uuid;
+
+/// Repository trait for LGPD data-subject requests.
+#[async_trait]
+pub trait LgpdRepositoryPort: Send + Sync {
+    /// User whose CPF (via the blind index) matches
+    async fn find_user_id_by_cpf(&self, cpf: &str) -> Result<Option<Uuid>, RepositoryError>;
+
+    async fn find_driver_id_by_cpf(&self, cpf: &str) -> Result<Option<Uuid>, RepositoryError>;
+
+    /// User account with every record linked to it, audit log included
+    async fn export_user(&self, id: Uuid) -> Result<Option<DataSubjectUser>, RepositoryError>;
+
+    /// Driver with every fleet record linked to it
+    async fn export_driver(&self, id: Uuid) -> Result<Option<DataSubjectDriver>, RepositoryError>;
+
+    /// Replaces the personal data of the plan subjects in one transaction.
+    /// Subjects already anonymized are left untouched.
+    async fn anonymize(
+        &self,
+        plan: &AnonymizationPlan,
+    ) -> Result<AnonymizationResult, RepositoryError>;
+}
@@ -0,0 +1,67 @@
+use crate::errors::RepositoryError;
+use crate::models::{AccountLockout, AccountLockoutDto};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// Repository trait for login attempts and account lockouts.
+#[async_trait]
+pub trait LoginThrottleRepositoryPort: Send + Sync {
+    async fn record_attempt(
+        &self,
+        user_id: Option<Uuid>,
+        identifier: &str,
+        ip_address: Option<&str>,
+        succeeded: bool,
+    ) -> Result<(), RepositoryError>;
+
+    /// Failed attempts whose address falls inside `network` (CIDR) since `since`
+    async fn count_failures_in_network(
+        &self,
+        network: &str,
+        since: DateTime<Utc>,
+    ) -> Result<i64, RepositoryError>;
+
+    /// Failures since `since` for an identifier that matches no account,
+    /// oldest first
+    async fn unknown_identifier_failures(
+        &self,
+        identifier: &str,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<DateTime<Utc>>, RepositoryError>;
+
+    async fn purge_attempts_before(&self, before: DateTime<Utc>) -> Result<u64, RepositoryError>;
+
+    async fn get_lockout(&self, user_id: Uuid) -> Result<Option<AccountLockout>, RepositoryError>;
+
+    /// Atomically counts a failure at `now`, restarting the count when the
+    /// previous failure is older than `window_start`. Returns the new state.
+    async fn increment_failures(
+        &self,
+        user_id: Uuid,
+        ip_address: Option<&str>,
+        now: DateTime<Utc>,
+        window_start: DateTime<Utc>,
+    ) -> Result<AccountLockout, RepositoryError>;
+
+    /// Locks the account and resets its count, only while it still holds at
+    /// least `max_failures` failures. Returns false when a concurrent failure
+    /// already applied the lock.
+    async fn lock_account(
+        &self,
+        lockout: &AccountLockout,
+        max_failures: i32,
+        unlock_token: (&str, DateTime<Utc>),
+    ) -> Result<bool, RepositoryError>;
+
+    async fn clear_lockout(&self, user_id: Uuid) -> Result<bool, RepositoryError>;
+
+    /// User owning a non-expired unlock token hash
+    async fn find_user_by_unlock_token(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<Uuid>, RepositoryError>;
+
+    /// Accounts currently locked or with pending failures
+    async fn list_lockouts(&self) -> Result<Vec<AccountLockoutDto>, RepositoryError>;
+}
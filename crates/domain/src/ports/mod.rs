@@ -1,11 +1,27 @@
 pub mod auth;
 pub mod budget_classifications;
 pub mod catalog;
+pub mod catalog_merge;
+pub mod catalog_search;
+pub mod catalog_sync;
+pub mod field_key_rotation;
+pub mod jwt_signing_key;
+pub mod siorg_preview;
+pub mod unit_restructuring;
 pub mod departments;
 pub mod email;
 pub mod facilities;
 pub mod geo_regions;
 pub mod mfa;
+pub mod oidc;
+pub mod webauthn;
+pub mod login_throttle;
+pub mod api_key;
+pub mod delegation;
+pub mod approval_chain;
+pub mod audit_integrity;
+pub mod entity_history;
+pub mod lgpd;
 pub mod organizational;
 pub mod requisition;
 pub mod session;
@@ -14,6 +30,7 @@ pub mod supplier;
 pub mod vehicle;
 pub mod driver;
 pub mod fueling;
+pub mod fuel_card;
 pub mod vehicle_fine;
 pub mod invoice;
 pub mod invoice_adjustment;
@@ -33,11 +50,27 @@ pub mod legacy_import;
 pub use auth::*;
 pub use budget_classifications::*;
 pub use catalog::*;
+pub use catalog_merge::*;
+pub use catalog_search::*;
+pub use catalog_sync::*;
+pub use field_key_rotation::*;
+pub use jwt_signing_key::*;
+pub use siorg_preview::*;
+pub use unit_restructuring::*;
 pub use departments::*;
 pub use email::*;
 pub use facilities::*;
 pub use geo_regions::*;
 pub use mfa::*;
+pub use oidc::*;
+pub use webauthn::*;
+pub use login_throttle::*;
+pub use api_key::*;
+pub use delegation::*;
+pub use approval_chain::*;
+pub use audit_integrity::*;
+pub use entity_history::*;
+pub use lgpd::*;
 pub use organizational::*;
 pub use requisition::*;
 pub use session::*;
@@ -46,6 +79,7 @@ pub use supplier::*;
 pub use vehicle::*;
 pub use driver::*;
 pub use fueling::*;
+pub use fuel_card::*;
 pub use vehicle_fine::*;
 pub use invoice::*;
 pub use invoice_adjustment::*;
@@ -0,0 +1,44 @@
+use crate::errors::RepositoryError;
+use crate::models::{IdentityLinkMethod, NewOidcAccount, OidcLoginState, UserIdentityDto};
+use async_trait::async_trait;
+use uuid::Uuid;
+
+/// Repository trait for OIDC identities and pending authorizations.
+///
+/// Subjects and CPFs are passed in plain text; implementations store only
+/// their blind index.
+#[async_trait]
+pub trait OidcRepositoryPort: Send + Sync {
+    async fn save_login_state(&self, state: &OidcLoginState) -> Result<(), RepositoryError>;
+
+    /// Removes and returns the pending authorization (single use)
+    async fn take_login_state(&self, state: &str) -> Result<Option<OidcLoginState>, RepositoryError>;
+
+    async fn purge_expired_login_states(&self) -> Result<u64, RepositoryError>;
+
+    async fn find_identity(
+        &self,
+        provider: &str,
+        subject: &str,
+    ) -> Result<Option<UserIdentityDto>, RepositoryError>;
+
+    async fn create_identity(
+        &self,
+        user_id: Uuid,
+        provider: &str,
+        subject: &str,
+        link_method: &IdentityLinkMethod,
+    ) -> Result<UserIdentityDto, RepositoryError>;
+
+    /// Creates the user and links the identity in a single transaction
+    async fn provision_account(
+        &self,
+        account: &NewOidcAccount,
+    ) -> Result<UserIdentityDto, RepositoryError>;
+
+    async fn touch_identity(&self, id: Uuid) -> Result<(), RepositoryError>;
+
+    async fn list_identities_by_user(&self, user_id: Uuid) -> Result<Vec<UserIdentityDto>, RepositoryError>;
+
+    async fn find_user_id_by_cpf(&self, cpf: &str) -> Result<Option<Uuid>, RepositoryError>;
+}
@@ -3,6 +3,7 @@ use std::collections::HashMap;
 use crate::errors::RepositoryError;
 use crate::models::organizational::*;
 use async_trait::async_trait;
+use chrono::NaiveDate;
 use sqlx::{Postgres, Transaction};
 use uuid::Uuid;
 
@@ -193,21 +194,27 @@ pub trait OrganizationalUnitRepositoryPort: Send + Sync {
         offset: i64,
     ) -> Result<(Vec<OrganizationalUnitWithDetailsDto>, i64), RepositoryError>;
 
+    /// Hierarchy in effect on `as_of` (current one when `None`), with name,
+    /// parent and status taken from the unit versions of that date
     async fn get_tree(
         &self,
         organization_id: Option<Uuid>,
+        as_of: Option<NaiveDate>,
     ) -> Result<Vec<OrganizationalUnitTreeNode>, RepositoryError>;
 
     async fn get_children(
         &self,
         parent_id: Uuid,
+        as_of: Option<NaiveDate>,
     ) -> Result<Vec<OrganizationalUnitDto>, RepositoryError>;
 
     async fn has_children(&self, id: Uuid) -> Result<bool, RepositoryError>;
 
+    /// NotFound when the unit did not exist on `as_of`
     async fn get_path_to_root(
         &self,
         id: Uuid,
+        as_of: Option<NaiveDate>,
     ) -> Result<Vec<OrganizationalUnitDto>, RepositoryError>;
 
     async fn create(
@@ -1,7 +1,7 @@
 use crate::errors::RepositoryError;
-use crate::models::report::{FuelConsumptionDto, FleetSummaryDto, VehicleDashboardDto};
+use crate::models::report::{FuelConsumptionDto, FleetSummaryDto, UnitCostDto, VehicleDashboardDto};
 use async_trait::async_trait;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use uuid::Uuid;
 
 #[async_trait]
@@ -29,3 +29,26 @@ pub trait FleetReportRepositoryPort: Send + Sync {
         end_date: Option<DateTime<Utc>>,
     ) -> Result<FleetSummaryDto, RepositoryError>;
 }
+
+#[async_trait]
+pub trait UnitCostReportRepositoryPort: Send + Sync {
+    /// Fulfilled requisition value per destination unit, with each unit mapped
+    /// to the one that answered for it on `as_of`.
+    async fn requisition_costs_by_unit(
+        &self,
+        organization_id: Option<Uuid>,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+        as_of: NaiveDate,
+    ) -> Result<Vec<UnitCostDto>, RepositoryError>;
+
+    /// Fuel and completed maintenance cost per vehicle department at the date
+    /// of each expense, mapped to the unit that answered for it on `as_of`.
+    async fn fleet_costs_by_unit(
+        &self,
+        organization_id: Option<Uuid>,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+        as_of: NaiveDate,
+    ) -> Result<Vec<UnitCostDto>, RepositoryError>;
+}
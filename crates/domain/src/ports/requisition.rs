@@ -38,6 +38,7 @@ pub trait RequisitionRepositoryPort: Send + Sync {
         id: Uuid,
         rejected_by: Uuid,
         reason: &str,
+        on_behalf_of: Option<Uuid>,
     ) -> Result<RequisitionDto, RepositoryError>;
 
     /// Cancel requisition using the database function
@@ -79,6 +80,7 @@ pub trait RequisitionRepositoryPort: Send + Sync {
         status: Option<RequisitionStatus>,
         requester_id: Option<Uuid>,
         warehouse_id: Option<Uuid>,
+        unit_ids: Option<&[Uuid]>,
     ) -> Result<(Vec<RequisitionDto>, i64), RepositoryError>;
 }
 
@@ -0,0 +1,54 @@
+use std::collections::HashMap;
+
+use crate::errors::RepositoryError;
+use crate::models::{
+    NewSiorgChangePreview, SiorgChangePreviewDto, SiorgLocalUnit, SiorgPreviewStatus,
+    SiorgUnitDiff, SiorgUnitReferences,
+};
+use async_trait::async_trait;
+use uuid::Uuid;
+
+/// Repository trait for the previews of SIORG structure changes.
+#[async_trait]
+pub trait SiorgChangePreviewRepositoryPort: Send + Sync {
+    /// Local units of the organization that carry a SIORG code
+    async fn list_local_units(
+        &self,
+        organization_id: Uuid,
+    ) -> Result<Vec<SiorgLocalUnit>, RepositoryError>;
+
+    /// Active warehouses, vehicles and open requisitions of each unit; units
+    /// without references are left out
+    async fn count_references(
+        &self,
+        unit_ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, SiorgUnitReferences>, RepositoryError>;
+
+    /// Stores a pending preview, discarding the pending one of the same
+    /// organization
+    async fn create(
+        &self,
+        preview: &NewSiorgChangePreview,
+    ) -> Result<SiorgChangePreviewDto, RepositoryError>;
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<SiorgChangePreviewDto>, RepositoryError>;
+
+    /// Previews without the entries, most recent first
+    async fn list(
+        &self,
+        organization_id: Option<Uuid>,
+        status: Option<SiorgPreviewStatus>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<SiorgChangePreviewDto>, i64), RepositoryError>;
+
+    /// Closes a pending preview with the decided entries. Fails with
+    /// `OptimisticLockConflict` when it is no longer pending.
+    async fn finish(
+        &self,
+        id: Uuid,
+        status: SiorgPreviewStatus,
+        entries: Option<&[SiorgUnitDiff]>,
+        decided_by: Option<Uuid>,
+    ) -> Result<SiorgChangePreviewDto, RepositoryError>;
+}
@@ -5,6 +5,7 @@ use chrono::DateTime;
 use chrono::Utc;
 use uuid::Uuid;
 
+#[allow(clippy::too_many_arguments)]
 #[async_trait]
 pub trait VehicleTripRepositoryPort: Send + Sync {
     async fn create(
@@ -27,6 +28,7 @@ pub trait VehicleTripRepositoryPort: Send + Sync {
         &self,
         id: Uuid,
         approved_by: Uuid,
+        on_behalf_of: Option<Uuid>,
         version: i32,
     ) -> Result<VehicleTripDto, RepositoryError>;
 
@@ -35,23 +37,28 @@ pub trait VehicleTripRepositoryPort: Send + Sync {
         id: Uuid,
         rejection_reason: &str,
         rejected_by: Uuid,
+        on_behalf_of: Option<Uuid>,
         version: i32,
     ) -> Result<VehicleTripDto, RepositoryError>;
 
     /// Transitions APROVADA → ALOCADA.
     ///
-    /// Acquires a pessimistic row-level lock on the vehicle row
-    /// (`SELECT … FOR UPDATE NOWAIT`) inside a transaction to prevent
-    /// concurrent double-booking. Returns `OptimisticLockConflict` if
-    /// the lock cannot be acquired immediately or if `version` is stale.
+    /// Acquires pessimistic row-level locks on the vehicle and driver rows
+    /// (`SELECT … FOR UPDATE NOWAIT`) and checks the calendar over
+    /// `[start, end)` inside the same transaction, so concurrent allocations
+    /// cannot double-book. Returns `Conflicts` without writing anything if
+    /// the window overlaps another booking, and `OptimisticLockConflict` if
+    /// a lock cannot be acquired immediately or if `version` is stale.
     async fn allocate(
         &self,
         trip_id: Uuid,
         vehicle_id: Uuid,
         driver_id: Uuid,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
         allocated_by: Uuid,
         version: i32,
-    ) -> Result<VehicleTripDto, RepositoryError>;
+    ) -> Result<TripAllocationOutcome, RepositoryError>;
 
     /// Check-out: departure — ALOCADA → EM_CURSO (DRS: checkout = saída).
     async fn checkout(
@@ -106,5 +113,68 @@ pub trait VehicleTripRepositoryPort: Send + Sync {
         status: Option<TripStatus>,
         limit: i64,
         offset: i64,
+        unit_ids: Option<&[Uuid]>,
     ) -> Result<(Vec<VehicleTripDto>, i64), RepositoryError>;
+
+    /// Bookings that intersect `[start, end]`: trips ALOCADA/EM_CURSO and
+    /// maintenance orders ABERTA/EM_EXECUCAO, optionally narrowed to one
+    /// vehicle and/or one driver (maintenance orders carry no driver and are
+    /// therefore only returned when `driver_id` is `None`).
+    async fn list_bookings(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        vehicle_id: Option<Uuid>,
+        driver_id: Option<Uuid>,
+    ) -> Result<Vec<TripBookingDto>, RepositoryError>;
+
+    /// Bookings that would conflict with allocating `vehicle_id`/`driver_id`
+    /// over `[start, end)`, ignoring the trip being allocated.
+    async fn find_conflicts(
+        &self,
+        vehicle_id: Uuid,
+        driver_id: Option<Uuid>,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        exclude_trip_id: Option<Uuid>,
+    ) -> Result<Vec<TripBookingDto>, RepositoryError>;
+
+    /// Active, non-deleted vehicles with no booking intersecting `[start, end)`.
+    async fn find_available_vehicles(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        category_id: Option<Uuid>,
+        min_seats: Option<i32>,
+    ) -> Result<Vec<AvailableVehicleDto>, RepositoryError>;
+
+    /// Free vehicles for the trip window with at least `min_seats` seats,
+    /// flagged by whether they share the category of `reference_vehicle_id`.
+    async fn find_allocation_candidates(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        min_seats: Option<i32>,
+        reference_vehicle_id: Uuid,
+    ) -> Result<Vec<AllocationCandidateDto>, RepositoryError>;
+
+    /// Atomically replaces the trip's suggestions with a fresh ranking.
+    async fn replace_suggestions(
+        &self,
+        trip_id: Uuid,
+        suggestions: &[NewTripSuggestion],
+        created_by: Option<Uuid>,
+    ) -> Result<Vec<TripAllocationSuggestionDto>, RepositoryError>;
+
+    async fn list_suggestions(
+        &self,
+        trip_id: Uuid,
+    ) -> Result<Vec<TripAllocationSuggestionDto>, RepositoryError>;
+
+    async fn find_suggestion(
+        &self,
+        id: Uuid,
+    ) -> Result<Option<TripAllocationSuggestionDto>, RepositoryError>;
+
+    async fn mark_suggestion_accepted(&self, id: Uuid) -> Result<(), RepositoryError>;
 }
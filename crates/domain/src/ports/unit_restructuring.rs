@@ -0,0 +1,35 @@
+use crate::errors::RepositoryError;
+use crate::models::{CreateUnitRestructuringPayload, UnitLineageDto, UnitRestructuringDto};
+use async_trait::async_trait;
+use uuid::Uuid;
+
+/// Repository trait for merges and splits of organizational units.
+#[async_trait]
+pub trait UnitRestructuringRepositoryPort: Send + Sync {
+    /// Records the lineage and migrates every reference of the predecessors
+    /// in a single transaction. Fails with `NotFound` when a unit does not
+    /// exist, `InvalidData` when the units or overrides do not fit together
+    /// and `Duplicate` when a predecessor was already replaced.
+    async fn restructure(
+        &self,
+        payload: &CreateUnitRestructuringPayload,
+        primary_successor_id: Uuid,
+        created_by: Option<Uuid>,
+    ) -> Result<UnitRestructuringDto, RepositoryError>;
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<UnitRestructuringDto>, RepositoryError>;
+
+    /// Most recent first, with their lineage
+    async fn list(
+        &self,
+        organization_id: Option<Uuid>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<UnitRestructuringDto>, i64), RepositoryError>;
+
+    /// Links in which the unit is the predecessor or the successor
+    async fn lineage_of(&self, unit_id: Uuid) -> Result<Vec<UnitLineageDto>, RepositoryError>;
+
+    /// Current unit reached through the primary successors
+    async fn current_unit(&self, unit_id: Uuid) -> Result<Uuid, RepositoryError>;
+}
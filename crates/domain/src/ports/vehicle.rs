@@ -59,6 +59,7 @@ pub trait VehicleModelRepositoryPort: Send + Sync {
         avg_consumption_min: Option<Decimal>,
         avg_consumption_max: Option<Decimal>,
         avg_consumption_target: Option<Decimal>,
+        preventive_interval_km: Option<i32>,
     ) -> Result<VehicleModelDto, RepositoryError>;
 
     async fn update(
@@ -73,6 +74,7 @@ pub trait VehicleModelRepositoryPort: Send + Sync {
         avg_consumption_min: Option<Decimal>,
         avg_consumption_max: Option<Decimal>,
         avg_consumption_target: Option<Decimal>,
+        preventive_interval_km: Option<i32>,
         is_active: Option<bool>,
     ) -> Result<VehicleModelDto, RepositoryError>;
 
@@ -242,9 +244,15 @@ pub trait VehicleRepositoryPort: Send + Sync {
         fuel_type_id: Option<Uuid>,
         department_id: Option<Uuid>,
         include_deleted: bool,
+        unit_ids: Option<&[Uuid]>,
     ) -> Result<(Vec<VehicleWithDetailsDto>, i64), RepositoryError>;
 
-    async fn search_autocomplete(&self, query: &str, limit: i64) -> Result<Vec<VehicleDto>, RepositoryError>;
+    async fn search_autocomplete(
+        &self,
+        query: &str,
+        limit: i64,
+        unit_ids: Option<&[Uuid]>,
+    ) -> Result<Vec<VehicleDto>, RepositoryError>;
 }
 
 // ============================
@@ -1,7 +1,7 @@
 use crate::errors::RepositoryError;
 use crate::models::vehicle_fine::*;
 use async_trait::async_trait;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use rust_decimal::Decimal;
 use uuid::Uuid;
 
@@ -104,6 +104,61 @@ pub trait VehicleFineRepositoryPort: Send + Sync {
         search: Option<String>,
         include_deleted: bool,
     ) -> Result<(Vec<VehicleFineWithDetailsDto>, i64), RepositoryError>;
+
+    // Driver identification
+    async fn find_trip_driver_at(
+        &self,
+        vehicle_id: Uuid,
+        at: DateTime<Utc>,
+    ) -> Result<Option<FineTripDriverRef>, RepositoryError>;
+    async fn assign_driver(
+        &self,
+        id: Uuid,
+        driver_id: Uuid,
+        trip_id: Option<Uuid>,
+        source: &FineDriverSource,
+        updated_by: Option<Uuid>,
+    ) -> Result<VehicleFineDto, RepositoryError>;
+    async fn set_deadlines(
+        &self,
+        id: Uuid,
+        identification_deadline: Option<NaiveDate>,
+        defense_deadline: Option<NaiveDate>,
+        appeal_deadline: Option<NaiveDate>,
+    ) -> Result<VehicleFineDto, RepositoryError>;
+    async fn find_identification_form(
+        &self,
+        id: Uuid,
+    ) -> Result<Option<FineIdentificationFormDto>, RepositoryError>;
+    /// Multas não excluídas com algum prazo até `until` (inclusive vencidos).
+    async fn list_open_deadlines(
+        &self,
+        until: NaiveDate,
+    ) -> Result<Vec<VehicleFineWithDetailsDto>, RepositoryError>;
+    /// Pontos por condutor desde `since`, excluindo multas canceladas ou com defesa deferida.
+    async fn driver_points_totals(
+        &self,
+        since: DateTime<Utc>,
+        driver_id: Option<Uuid>,
+    ) -> Result<Vec<DriverPointsTotalsDto>, RepositoryError>;
+
+    // Driver points alerts
+    /// Grava o alerta ou atualiza a pontuação do alerta aberto do mesmo nível.
+    async fn upsert_points_alert(
+        &self,
+        driver_id: Uuid,
+        vehicle_fine_id: Uuid,
+        alert_level: DriverPointsAlertLevel,
+        total_points: i64,
+        suspension_threshold: i64,
+    ) -> Result<DriverPointsAlertDto, RepositoryError>;
+    async fn find_points_alert_by_id(&self, id: Uuid) -> Result<Option<DriverPointsAlertDto>, RepositoryError>;
+    async fn list_points_alerts(&self, include_acknowledged: bool) -> Result<Vec<DriverPointsAlertDto>, RepositoryError>;
+    async fn acknowledge_points_alert(
+        &self,
+        id: Uuid,
+        acknowledged_by: Uuid,
+    ) -> Result<DriverPointsAlertDto, RepositoryError>;
 }
 
 #[async_trait]
@@ -61,6 +61,7 @@ pub trait WarehouseRepositoryPort: Send + Sync {
         warehouse_type: Option<WarehouseType>,
         city_id: Option<Uuid>,
         is_active: Option<bool>,
+        unit_ids: Option<&[Uuid]>,
     ) -> Result<(Vec<WarehouseWithDetailsDto>, i64), RepositoryError>;
 }
 
@@ -0,0 +1,60 @@
+use crate::errors::RepositoryError;
+use crate::models::{StoredWebauthnCredential, WebauthnChallenge, WebauthnCredentialDto};
+use async_trait::async_trait;
+use uuid::Uuid;
+
+/// Repository trait for WebAuthn credentials and pending ceremonies.
+#[async_trait]
+pub trait WebauthnRepositoryPort: Send + Sync {
+    async fn save_challenge(&self, challenge: &WebauthnChallenge) -> Result<(), RepositoryError>;
+
+    /// Removes and returns the pending challenge (single use)
+    async fn take_challenge(&self, id: Uuid) -> Result<Option<WebauthnChallenge>, RepositoryError>;
+
+    async fn purge_expired_challenges(&self) -> Result<u64, RepositoryError>;
+
+    async fn create_credential(
+        &self,
+        user_id: Uuid,
+        name: &str,
+        credential_id: &str,
+        passkey: &serde_json::Value,
+        backup_eligible: bool,
+        backup_state: bool,
+    ) -> Result<WebauthnCredentialDto, RepositoryError>;
+
+    async fn list_by_user(&self, user_id: Uuid) -> Result<Vec<WebauthnCredentialDto>, RepositoryError>;
+
+    /// Credentials usable for authentication (no clone detected)
+    async fn list_active_stored_by_user(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<StoredWebauthnCredential>, RepositoryError>;
+
+    async fn find_stored_by_credential_id(
+        &self,
+        credential_id: &str,
+    ) -> Result<Option<StoredWebauthnCredential>, RepositoryError>;
+
+    async fn exists_by_credential_id(&self, credential_id: &str) -> Result<bool, RepositoryError>;
+
+    /// Persists the updated credential after a successful assertion
+    async fn record_use(
+        &self,
+        id: Uuid,
+        passkey: &serde_json::Value,
+        sign_count: i64,
+        backup_state: bool,
+    ) -> Result<(), RepositoryError>;
+
+    async fn flag_clone_detected(&self, credential_id: &str) -> Result<(), RepositoryError>;
+
+    async fn rename(
+        &self,
+        user_id: Uuid,
+        id: Uuid,
+        name: &str,
+    ) -> Result<Option<WebauthnCredentialDto>, RepositoryError>;
+
+    async fn delete(&self, user_id: Uuid, id: Uuid) -> Result<bool, RepositoryError>;
+}
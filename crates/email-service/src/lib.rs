@@ -41,6 +41,7 @@ use lettre::{
 };
 use once_cell::sync::Lazy;
 use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
 use tera::{Context as TeraContext, Tera, Value};
 
 // ===================================================================
@@ -119,35 +120,36 @@ pub trait EmailSender: Send + Sync {
 
 #[derive(Clone)]
 pub struct EmailService {
-    transport: AsyncSmtpTransport<Tokio1Executor>,
+    config: Arc<RwLock<EmailConfig>>,
+    transport: Arc<RwLock<AsyncSmtpTransport<Tokio1Executor>>>,
     from_email: String,
 }
 
 impl EmailService {
     /// Inicializa o EmailService com as configurações fornecidas
     pub fn new(config: EmailConfig) -> anyhow::Result<Self> {
-        let creds = Credentials::new(config.smtp_user.clone(), config.smtp_pass.clone());
-
-        let mut transport_builder = AsyncSmtpTransport::<Tokio1Executor>::relay(&config.smtp_host)?
-            .port(config.smtp_port)
-            .credentials(creds);
-
-        // TLS opcional: Desativa TLS se for localhost para facilitar dev local com Mailhog/Mailpit
-        if config.smtp_host != "127.0.0.1" && config.smtp_host != "localhost" {
-            let tls_params = TlsParameters::new(config.smtp_host.clone())?;
-            transport_builder = transport_builder.tls(Tls::Required(tls_params));
-        } else {
-            transport_builder = transport_builder.tls(Tls::None);
-        }
-
-        let transport = transport_builder.build();
+        let transport = build_transport(&config)?;
 
         Ok(Self {
-            transport,
-            from_email: config.from_email,
+            from_email: config.from_email.clone(),
+            config: Arc::new(RwLock::new(config)),
+            transport: Arc::new(RwLock::new(transport)),
         })
     }
 
+    /// Troca as credenciais SMTP (rotação de segredo) sem reiniciar o serviço.
+    /// Envios em andamento terminam com o transporte anterior.
+    pub fn update_credentials(&self, smtp_user: String, smtp_pass: String) -> anyhow::Result<()> {
+        let mut config = self.config.read().unwrap_or_else(|e| e.into_inner()).clone();
+        config.smtp_user = smtp_user;
+        config.smtp_pass = smtp_pass;
+        let transport = build_transport(&config)?;
+
+        *self.transport.write().unwrap_or_else(|e| e.into_inner()) = transport;
+        *self.config.write().unwrap_or_else(|e| e.into_inner()) = config;
+        Ok(())
+    }
+
     async fn send_raw(
         &self,
         to_email: String,
@@ -172,11 +174,30 @@ impl EmailService {
             .header(ContentType::TEXT_HTML)
             .body(html_body)?;
 
-        self.transport.send(email).await?;
+        let transport = self.transport.read().unwrap_or_else(|e| e.into_inner()).clone();
+        transport.send(email).await?;
         Ok(())
     }
 }
 
+fn build_transport(config: &EmailConfig) -> anyhow::Result<AsyncSmtpTransport<Tokio1Executor>> {
+    let creds = Credentials::new(config.smtp_user.clone(), config.smtp_pass.clone());
+
+    let mut transport_builder = AsyncSmtpTransport::<Tokio1Executor>::relay(&config.smtp_host)?
+        .port(config.smtp_port)
+        .credentials(creds);
+
+    // TLS opcional: Desativa TLS se for localhost para facilitar dev local com Mailhog/Mailpit
+    if config.smtp_host != "127.0.0.1" && config.smtp_host != "localhost" {
+        let tls_params = TlsParameters::new(config.smtp_host.clone())?;
+        transport_builder = transport_builder.tls(Tls::Required(tls_params));
+    } else {
+        transport_builder = transport_builder.tls(Tls::None);
+    }
+
+    Ok(transport_builder.build())
+}
+
 #[async_trait]
 impl EmailServicePort for EmailService {
     async fn send_verification_email(
@@ -300,6 +321,44 @@ impl EmailServicePort for EmailService {
         });
         Ok(())
     }
+
+    async fn send_account_locked_email(
+        &self,
+        to: &Email,
+        username: &Username,
+        locked_until: chrono::DateTime<chrono::Utc>,
+        unlock_token: &str,
+    ) -> Result<(), EmailError> {
+        let mut context = TeraContext::new();
+        context.insert("username", username.as_str());
+        context.insert(
+            "locked_until",
+            &locked_until.format("%Y-%m-%d %H:%M:%S UTC").to_string(),
+        );
+
+        let base_url =
+            std::env::var("APP_BASE_URL").unwrap_or_else(|_| "http://localhost:3000".to_string());
+        let link = format!("{}/unlock-account?token={}", base_url, unlock_token);
+        context.insert("unlock_link", &link);
+
+        let service = self.clone();
+        let to_string = to.as_str().to_string();
+
+        tokio::spawn(async move {
+            if let Err(e) = service
+                .send_raw(
+                    to_string,
+                    "Account locked - Waterswamp".into(),
+                    "account_locked.html",
+                    context,
+                )
+                .await
+            {
+                tracing::error!("Account locked email send failed: {:?}", e);
+            }
+        });
+        Ok(())
+    }
 }
 
 // Mantemos a implementação do Trait Legado (EmailSender) para não quebrar handlers antigos
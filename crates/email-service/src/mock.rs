@@ -82,6 +82,31 @@ impl EmailServicePort for MockEmailService {
         EmailSender::send_mfa_enabled_email(self, to.as_str().to_string(), username.as_str());
         Ok(())
     }
+
+    async fn send_account_locked_email(
+        &self,
+        to: &Email,
+        username: &Username,
+        locked_until: chrono::DateTime<chrono::Utc>,
+        unlock_token: &str,
+    ) -> Result<(), EmailError> {
+        let mut ctx = Context::new();
+        ctx.insert("username", username.as_str());
+        ctx.insert("locked_until", &locked_until.to_rfc3339());
+        ctx.insert(
+            "unlock_link",
+            &format!("http://mock.test/unlock-account?token={}", unlock_token),
+        );
+        let _ = self
+            .send_email(
+                to.as_str().to_string(),
+                "Conta bloqueada - Waterswamp".to_string(),
+                "account_locked.html",
+                ctx,
+            )
+            .await;
+        Ok(())
+    }
 }
 
 // --- IMPLEMENTAÇÃO DO TRAIT LEGADO (USANDO TOKIO::SPAWN) ---
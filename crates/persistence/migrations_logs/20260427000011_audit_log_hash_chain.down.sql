@@ -0,0 +1,29 @@
+DROP TRIGGER IF EXISTS trg_audit_log_guard_truncate ON audit_logs;
+DROP TRIGGER IF EXISTS trg_audit_log_guard ON audit_logs;
+DROP TRIGGER IF EXISTS trg_audit_log_chain ON audit_logs;
+DROP FUNCTION IF EXISTS fn_audit_log_guard();
+DROP FUNCTION IF EXISTS fn_audit_log_chain();
+
+ALTER TABLE audit_logs DROP CONSTRAINT IF EXISTS uq_audit_logs_chain;
+DROP FUNCTION IF EXISTS fn_audit_log_hash(audit_logs);
+
+ALTER TABLE audit_logs
+    DROP COLUMN IF EXISTS entry_hash,
+    DROP COLUMN IF EXISTS prev_hash,
+    DROP COLUMN IF EXISTS seq,
+    DROP COLUMN IF EXISTS chain_date;
+
+DROP TABLE IF EXISTS audit_log_archives;
+DROP TABLE IF EXISTS audit_log_checkpoints;
+
+CREATE OR REPLACE FUNCTION cleanup_old_audit_logs(retention_days INTEGER DEFAULT 90)
+RETURNS INTEGER AS $$
+DECLARE
+    deleted_count INTEGER;
+BEGIN
+    DELETE FROM audit_logs
+    WHERE created_at < NOW() - (retention_days || ' days')::INTERVAL;
+    GET DIAGNOSTICS deleted_count = ROW_COUNT;
+    RETURN deleted_count;
+END;
+$$ LANGUAGE plpgsql;
@@ -0,0 +1,165 @@
+-- Cadeia de hashes por dia (UTC) nos registros de auditoria.
+-- Cada entrada guarda o hash da anterior; alterações ou remoções quebram a
+-- cadeia e são detectadas pela verificação contra os checkpoints assinados.
+
+ALTER TABLE audit_logs
+    ADD COLUMN chain_date DATE,
+    ADD COLUMN seq BIGINT,
+    ADD COLUMN prev_hash CHAR(64),
+    ADD COLUMN entry_hash CHAR(64);
+
+-- Checkpoint diário: resumo assinado do último elo de cada dia encerrado
+CREATE TABLE audit_log_checkpoints (
+    chain_date DATE PRIMARY KEY,
+    entry_count BIGINT NOT NULL,
+    last_seq BIGINT NOT NULL,
+    last_hash CHAR(64) NOT NULL,
+    digest CHAR(64) NOT NULL,
+    signature TEXT NOT NULL,
+    signed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Entradas removidas pela retenção, exportadas para arquivo selado
+CREATE TABLE audit_log_archives (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    chain_date DATE NOT NULL,
+    first_seq BIGINT NOT NULL,
+    last_seq BIGINT NOT NULL,
+    entry_count BIGINT NOT NULL,
+    last_hash CHAR(64) NOT NULL,
+    file_path TEXT NOT NULL,
+    file_sha256 CHAR(64) NOT NULL,
+    signature TEXT NOT NULL,
+    archived_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (chain_date, last_seq)
+);
+
+CREATE INDEX idx_audit_log_archives_chain_date ON audit_log_archives(chain_date);
+
+-- Representação canônica de uma entrada para o cálculo do hash
+CREATE OR REPLACE FUNCTION fn_audit_log_hash(e audit_logs)
+RETURNS CHAR(64) AS $$
+    SELECT encode(sha256(convert_to(concat_ws(E'\x1f',
+        e.prev_hash,
+        e.chain_date::TEXT,
+        e.seq::TEXT,
+        e.id::TEXT,
+        COALESCE(e.user_id::TEXT, ''),
+        COALESCE(e.username, ''),
+        e.action,
+        e.resource,
+        COALESCE(e.method, ''),
+        COALESCE(e.status_code::TEXT, ''),
+        COALESCE(e.details::TEXT, ''),
+        COALESCE(host(e.ip_address), ''),
+        COALESCE(e.user_agent, ''),
+        COALESCE(e.request_id::TEXT, ''),
+        COALESCE(e.duration_ms::TEXT, ''),
+        to_char(e.created_at AT TIME ZONE 'UTC', 'YYYY-MM-DD"T"HH24:MI:SS.US')
+    ), 'UTF8')), 'hex')
+$$ LANGUAGE sql STABLE;
+
+-- Encadeia registros já existentes
+DO $$
+DECLARE
+    r audit_logs;
+    cur_date DATE;
+    last_seq BIGINT;
+    last_hash TEXT;
+BEGIN
+    FOR r IN SELECT * FROM audit_logs ORDER BY created_at, id LOOP
+        IF cur_date IS DISTINCT FROM (r.created_at AT TIME ZONE 'UTC')::DATE THEN
+            cur_date := (r.created_at AT TIME ZONE 'UTC')::DATE;
+            last_seq := 0;
+            last_hash := repeat('0', 64);
+        END IF;
+        r.chain_date := cur_date;
+        r.seq := last_seq + 1;
+        r.prev_hash := last_hash;
+        r.entry_hash := fn_audit_log_hash(r);
+        UPDATE audit_logs
+        SET chain_date = r.chain_date, seq = r.seq,
+            prev_hash = r.prev_hash, entry_hash = r.entry_hash
+        WHERE id = r.id;
+        last_seq := r.seq;
+        last_hash := r.entry_hash;
+    END LOOP;
+END;
+$$;
+
+ALTER TABLE audit_logs
+    ALTER COLUMN chain_date SET NOT NULL,
+    ALTER COLUMN seq SET NOT NULL,
+    ALTER COLUMN prev_hash SET NOT NULL,
+    ALTER COLUMN entry_hash SET NOT NULL,
+    ADD CONSTRAINT uq_audit_logs_chain UNIQUE (chain_date, seq);
+
+-- Calcula o próximo elo da cadeia do dia na inserção
+CREATE OR REPLACE FUNCTION fn_audit_log_chain()
+RETURNS TRIGGER AS $$
+DECLARE
+    v_last_seq BIGINT;
+    v_last_hash TEXT;
+BEGIN
+    NEW.created_at := COALESCE(NEW.created_at, NOW());
+    NEW.chain_date := (NEW.created_at AT TIME ZONE 'UTC')::DATE;
+
+    -- Serializa inserções concorrentes no mesmo dia
+    PERFORM pg_advisory_xact_lock(hashtext('audit_chain'), hashtext(NEW.chain_date::TEXT));
+
+    SELECT seq, entry_hash INTO v_last_seq, v_last_hash
+    FROM audit_logs
+    WHERE chain_date = NEW.chain_date
+    ORDER BY seq DESC
+    LIMIT 1;
+
+    -- Dia já arquivado: continua a partir do último elo exportado
+    IF v_last_seq IS NULL THEN
+        SELECT last_seq, last_hash INTO v_last_seq, v_last_hash
+        FROM audit_log_archives
+        WHERE chain_date = NEW.chain_date
+        ORDER BY last_seq DESC
+        LIMIT 1;
+    END IF;
+
+    NEW.seq := COALESCE(v_last_seq, 0) + 1;
+    NEW.prev_hash := COALESCE(v_last_hash, repeat('0', 64));
+    NEW.entry_hash := fn_audit_log_hash(NEW);
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trg_audit_log_chain
+    BEFORE INSERT ON audit_logs
+    FOR EACH ROW EXECUTE FUNCTION fn_audit_log_chain();
+
+-- Registros de auditoria são somente inserção; a única remoção permitida é a
+-- do arquivamento, que habilita `audit.archiving` na própria transação
+CREATE OR REPLACE FUNCTION fn_audit_log_guard()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'DELETE' AND current_setting('audit.archiving', true) = 'on' THEN
+        RETURN OLD;
+    END IF;
+    RAISE EXCEPTION 'audit_logs is append-only (% not allowed)', TG_OP
+        USING ERRCODE = 'insufficient_privilege';
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trg_audit_log_guard
+    BEFORE UPDATE OR DELETE ON audit_logs
+    FOR EACH ROW EXECUTE FUNCTION fn_audit_log_guard();
+
+CREATE TRIGGER trg_audit_log_guard_truncate
+    BEFORE TRUNCATE ON audit_logs
+    FOR EACH STATEMENT EXECUTE FUNCTION fn_audit_log_guard();
+
+-- A retenção agora arquiva em vez de apagar
+DROP FUNCTION IF EXISTS cleanup_old_audit_logs(INTEGER);
+
+COMMENT ON COLUMN audit_logs.chain_date IS 'UTC day whose hash chain this entry belongs to';
+COMMENT ON COLUMN audit_logs.seq IS 'Position of the entry in its daily chain (starting at 1)';
+COMMENT ON COLUMN audit_logs.prev_hash IS 'entry_hash of the previous entry in the chain (zeros for the first)';
+COMMENT ON COLUMN audit_logs.entry_hash IS 'SHA-256 of the canonical entry including prev_hash';
+COMMENT ON TABLE audit_log_checkpoints IS 'Signed daily digests of the audit hash chain';
+COMMENT ON TABLE audit_log_archives IS 'Sealed export files produced by audit log retention';
@@ -0,0 +1,13 @@
+CREATE OR REPLACE FUNCTION fn_audit_log_guard()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'DELETE' AND current_setting('audit.archiving', true) = 'on' THEN
+        RETURN OLD;
+    END IF;
+    RAISE EXCEPTION 'audit_logs is append-only (% not allowed)', TG_OP
+        USING ERRCODE = 'insufficient_privilege';
+END;
+$$ LANGUAGE plpgsql;
+
+DROP FUNCTION IF EXISTS fn_audit_log_redacted_hash(audit_logs);
+DROP TABLE IF EXISTS audit_log_redactions;
@@ -0,0 +1,56 @@
+-- Anonimização LGPD nos registros de auditoria encadeados.
+-- username, ip_address e user_agent do titular são apagados; como o hash da
+-- entrada deixa de ser recalculável, a redação guarda o hash dos demais
+-- campos, assinado no momento em que a entrada ainda estava íntegra.
+
+CREATE TABLE audit_log_redactions (
+    entry_id UUID PRIMARY KEY REFERENCES audit_logs(id) ON DELETE CASCADE,
+    -- entry_hash original, que continua encadeando a entrada seguinte
+    entry_hash CHAR(64) NOT NULL,
+    -- fn_audit_log_redacted_hash da entrada
+    redacted_hash CHAR(64) NOT NULL,
+    signature TEXT NOT NULL,
+    redacted_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Hash canônico sem os campos pessoais (username, ip_address, user_agent)
+CREATE OR REPLACE FUNCTION fn_audit_log_redacted_hash(e audit_logs)
+RETURNS CHAR(64) AS $$
+    SELECT encode(sha256(convert_to(concat_ws(E'\x1f',
+        e.prev_hash,
+        e.chain_date::TEXT,
+        e.seq::TEXT,
+        e.id::TEXT,
+        COALESCE(e.user_id::TEXT, ''),
+        e.action,
+        e.resource,
+        COALESCE(e.method, ''),
+        COALESCE(e.status_code::TEXT, ''),
+        COALESCE(e.details::TEXT, ''),
+        COALESCE(e.request_id::TEXT, ''),
+        COALESCE(e.duration_ms::TEXT, ''),
+        to_char(e.created_at AT TIME ZONE 'UTC', 'YYYY-MM-DD"T"HH24:MI:SS.US'),
+        e.entry_hash
+    ), 'UTF8')), 'hex')
+$$ LANGUAGE sql STABLE;
+
+-- Além do arquivamento, a redação (`audit.redacting`) pode apagar os campos
+-- pessoais; nenhum outro campo pode mudar
+CREATE OR REPLACE FUNCTION fn_audit_log_guard()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'DELETE' AND current_setting('audit.archiving', true) = 'on' THEN
+        RETURN OLD;
+    END IF;
+    IF TG_OP = 'UPDATE' AND current_setting('audit.redacting', true) = 'on'
+        AND NEW.username IS NULL AND NEW.ip_address IS NULL AND NEW.user_agent IS NULL
+        AND fn_audit_log_redacted_hash(NEW) = fn_audit_log_redacted_hash(OLD)
+    THEN
+        RETURN NEW;
+    END IF;
+    RAISE EXCEPTION 'audit_logs is append-only (% not allowed)', TG_OP
+        USING ERRCODE = 'insufficient_privilege';
+END;
+$$ LANGUAGE plpgsql;
+
+COMMENT ON TABLE audit_log_redactions IS 'Signed LGPD redactions of personal fields in hash-chained audit entries';
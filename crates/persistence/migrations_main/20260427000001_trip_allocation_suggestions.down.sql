@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS trip_allocation_suggestions;
+
+ALTER TABLE vehicle_models DROP COLUMN IF EXISTS preventive_interval_km;
@@ -0,0 +1,30 @@
+-- ==========================================================================
+-- RF-VIG-05: Sugestão automática de veículo para viagens aprovadas.
+--
+-- O motor de sugestão classifica os veículos livres na janela da viagem e
+-- persiste o ranking; a alocação pode referenciar uma sugestão pelo ID.
+--
+-- A proximidade da revisão preventiva é calculada a partir do intervalo do
+-- modelo e da última OS PREVENTIVA concluída (km_abertura).
+-- ==========================================================================
+
+ALTER TABLE vehicle_models
+    ADD COLUMN preventive_interval_km INTEGER CHECK (preventive_interval_km > 0);
+
+COMMENT ON COLUMN vehicle_models.preventive_interval_km IS
+    'Intervalo (km) entre revisões preventivas recomendado pelo fabricante';
+
+CREATE TABLE trip_allocation_suggestions (
+    id              UUID             PRIMARY KEY DEFAULT uuid_generate_v4(),
+    trip_id         UUID             NOT NULL REFERENCES vehicle_trips(id) ON DELETE CASCADE,
+    vehicle_id      UUID             NOT NULL REFERENCES vehicles(id) ON DELETE CASCADE,
+    rank            INTEGER          NOT NULL CHECK (rank > 0),
+    score           DOUBLE PRECISION NOT NULL,
+    reasons         TEXT[]           NOT NULL DEFAULT '{}',
+    accepted_at     TIMESTAMPTZ,
+    created_by      UUID,
+    created_at      TIMESTAMPTZ      NOT NULL DEFAULT NOW(),
+    UNIQUE (trip_id, rank)
+);
+
+CREATE INDEX idx_trip_suggestions_vehicle ON trip_allocation_suggestions(vehicle_id);
@@ -0,0 +1,11 @@
+DROP INDEX IF EXISTS idx_fuelings_fuel_type_date;
+DROP INDEX IF EXISTS idx_fuelings_quarantine;
+
+ALTER TABLE fuelings
+    DROP COLUMN IF EXISTS review_notes,
+    DROP COLUMN IF EXISTS reviewed_at,
+    DROP COLUMN IF EXISTS reviewed_by,
+    DROP COLUMN IF EXISTS anomalies,
+    DROP COLUMN IF EXISTS review_status;
+
+DROP TYPE IF EXISTS fueling_review_status_enum;
@@ -0,0 +1,27 @@
+-- ==========================================================================
+-- RF-ABT: Detecção de anomalias e fraude em abastecimentos
+--
+-- Abastecimentos com indícios de irregularidade (litros acima da capacidade
+-- do tanque, consumo fora da média, abastecimentos muito próximos,
+-- combustível incompatível, preço acima da mediana) entram em QUARENTENA e
+-- aguardam revisão do Gestor de Frota — mesmo fluxo de leituras_hodometro.
+-- ==========================================================================
+
+CREATE TYPE fueling_review_status_enum AS ENUM (
+    'VALIDADO',
+    'QUARENTENA',
+    'REJEITADO'
+);
+
+ALTER TABLE fuelings
+    ADD COLUMN review_status fueling_review_status_enum NOT NULL DEFAULT 'VALIDADO',
+    ADD COLUMN anomalies     JSONB,
+    ADD COLUMN reviewed_by   UUID,
+    ADD COLUMN reviewed_at   TIMESTAMPTZ,
+    ADD COLUMN review_notes  TEXT;
+
+-- Fila de revisão do Gestor de Frota
+CREATE INDEX idx_fuelings_quarantine ON fuelings(fueling_date DESC) WHERE review_status = 'QUARENTENA';
+
+-- Mediana de preço por combustível no período
+CREATE INDEX idx_fuelings_fuel_type_date ON fuelings(fuel_type_id, fueling_date DESC);
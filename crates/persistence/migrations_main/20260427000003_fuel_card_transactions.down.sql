@@ -0,0 +1,5 @@
+DROP TABLE IF EXISTS fuel_card_transactions;
+DROP TYPE IF EXISTS fuel_card_match_status_enum;
+
+-- Valores de enum não podem ser removidos; 'FUEL_CARD_TRANSACTION' permanece
+-- em import_entity_type_enum.
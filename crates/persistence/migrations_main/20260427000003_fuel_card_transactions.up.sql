@@ -0,0 +1,50 @@
+-- ==========================================================================
+-- RF-ABT: Importação e conciliação de transações de cartão combustível
+--
+-- As operadoras entregam arquivos mensais (CSV). Cada linha é gravada como
+-- transação em staging e conciliada com os cadastros:
+--   placa → vehicles, CPF → drivers, data/litros/valor → fuelings.
+-- Transações sem abastecimento correspondente geram um abastecimento em
+-- QUARENTENA (pendente de conferência). O job é acompanhado em
+-- legacy_import_jobs, como as demais importações.
+-- ==========================================================================
+
+ALTER TYPE import_entity_type_enum ADD VALUE IF NOT EXISTS 'FUEL_CARD_TRANSACTION';
+
+CREATE TYPE fuel_card_match_status_enum AS ENUM (
+    'CONCILIADO',        -- abastecimento existente confere
+    'DIVERGENTE',        -- abastecimento existente com divergências
+    'PENDENTE',          -- sem abastecimento: gerado abastecimento em quarentena
+    'NAO_IDENTIFICADO'   -- placa ou CPF não cadastrados
+);
+
+CREATE TABLE fuel_card_transactions (
+    id              UUID            PRIMARY KEY DEFAULT uuid_generate_v4(),
+    job_id          UUID            NOT NULL REFERENCES legacy_import_jobs(id) ON DELETE CASCADE,
+    operator        TEXT            NOT NULL,
+    line_number     INTEGER         NOT NULL,
+    external_id     TEXT,
+    transaction_at  TIMESTAMPTZ     NOT NULL,
+    license_plate   TEXT            NOT NULL,
+    driver_cpf      TEXT,
+    liters          NUMERIC(10,3)   NOT NULL,
+    unit_price      NUMERIC(10,4)   NOT NULL,
+    total_amount    NUMERIC(12,2)   NOT NULL,
+    odometer_km     INTEGER,
+    fuel_name       TEXT,
+    merchant        TEXT,
+    -- Conciliação
+    vehicle_id      UUID            REFERENCES vehicles(id) ON DELETE SET NULL,
+    driver_id       UUID            REFERENCES drivers(id) ON DELETE SET NULL,
+    fueling_id      UUID            REFERENCES fuelings(id) ON DELETE SET NULL,
+    match_status    fuel_card_match_status_enum NOT NULL,
+    mismatches      JSONB,
+    created_at      TIMESTAMPTZ     NOT NULL DEFAULT NOW()
+);
+
+-- Reimportar o mesmo arquivo não duplica transações da operadora
+CREATE UNIQUE INDEX idx_fuel_card_tx_external
+    ON fuel_card_transactions(operator, external_id) WHERE external_id IS NOT NULL;
+
+CREATE INDEX idx_fuel_card_tx_job     ON fuel_card_transactions(job_id, match_status);
+CREATE INDEX idx_fuel_card_tx_fueling ON fuel_card_transactions(fueling_id) WHERE fueling_id IS NOT NULL;
@@ -0,0 +1,14 @@
+DROP INDEX IF EXISTS idx_vtrips_vehicle_checkout;
+DROP INDEX IF EXISTS idx_vehicle_fines_driver_date;
+DROP INDEX IF EXISTS idx_vehicle_fines_identification_deadline;
+DROP INDEX IF EXISTS idx_vehicle_fines_trip;
+
+ALTER TABLE vehicle_fines
+    DROP COLUMN IF EXISTS appeal_deadline,
+    DROP COLUMN IF EXISTS defense_deadline,
+    DROP COLUMN IF EXISTS identification_deadline,
+    DROP COLUMN IF EXISTS driver_identified_at,
+    DROP COLUMN IF EXISTS driver_source,
+    DROP COLUMN IF EXISTS trip_id;
+
+DROP TYPE IF EXISTS fine_driver_source_enum;
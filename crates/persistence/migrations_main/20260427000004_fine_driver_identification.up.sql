@@ -0,0 +1,32 @@
+-- =============================================================================
+-- Indicação de condutor infrator e prazos da multa (CTB Art. 257 §7º, 281-A, 285)
+--
+-- O condutor é resolvido a partir da viagem em curso no veículo na data da
+-- infração (checkout_em ≤ fine_date ≤ checkin_em). Os prazos de indicação,
+-- defesa prévia e recurso (JARI) ficam gravados para a fila de vencimentos.
+-- =============================================================================
+
+CREATE TYPE fine_driver_source_enum AS ENUM (
+    'TRIP',     -- Resolvido pela viagem em curso
+    'MANUAL'    -- Indicado manualmente pelo gestor
+);
+
+ALTER TABLE vehicle_fines
+    ADD COLUMN trip_id                 UUID REFERENCES vehicle_trips(id) ON DELETE SET NULL,
+    ADD COLUMN driver_source           fine_driver_source_enum,
+    ADD COLUMN driver_identified_at    TIMESTAMPTZ,
+    ADD COLUMN identification_deadline DATE,
+    ADD COLUMN defense_deadline        DATE,
+    ADD COLUMN appeal_deadline         DATE;
+
+CREATE INDEX idx_vehicle_fines_trip ON vehicle_fines (trip_id) WHERE trip_id IS NOT NULL;
+CREATE INDEX idx_vehicle_fines_identification_deadline ON vehicle_fines (identification_deadline)
+    WHERE is_deleted = false AND identification_deadline IS NOT NULL;
+
+-- Pontuação por condutor nos últimos 12 meses
+CREATE INDEX idx_vehicle_fines_driver_date ON vehicle_fines (driver_id, fine_date DESC)
+    WHERE is_deleted = false AND driver_id IS NOT NULL;
+
+-- Viagem em curso no veículo em determinado instante
+CREATE INDEX idx_vtrips_vehicle_checkout ON vehicle_trips (vehicle_id, checkout_em)
+    WHERE checkout_em IS NOT NULL;
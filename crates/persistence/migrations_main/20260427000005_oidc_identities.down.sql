@@ -0,0 +1,6 @@
+DROP TABLE IF EXISTS oidc_login_states;
+DROP TABLE IF EXISTS user_identities;
+DROP TYPE IF EXISTS identity_link_method_enum;
+
+DROP INDEX IF EXISTS users_cpf_index_unique;
+ALTER TABLE users DROP COLUMN IF EXISTS cpf_index;
@@ -0,0 +1,56 @@
+-- ============================================================================
+-- Migration: OpenID Connect single sign-on (gov.br / IdP institucional)
+-- Description: Vincula identidades externas a usuários locais e guarda o
+--              estado das autorizações em andamento (state/nonce/PKCE).
+-- ============================================================================
+
+-- CPF do usuário como blind index (HMAC), no mesmo esquema de email_index.
+-- Usado para vincular contas gov.br, cujo `sub` é o CPF.
+ALTER TABLE users ADD COLUMN IF NOT EXISTS cpf_index TEXT;
+
+CREATE UNIQUE INDEX IF NOT EXISTS users_cpf_index_unique
+    ON users (cpf_index)
+    WHERE cpf_index IS NOT NULL;
+
+COMMENT ON COLUMN users.cpf_index IS
+    'HMAC-SHA256 blind index of the digits-only CPF. Used to link OIDC identities.';
+
+-- Como a identidade foi vinculada ao usuário local no primeiro login
+CREATE TYPE identity_link_method_enum AS ENUM (
+    'EMAIL',    -- Email verificado pelo IdP igual ao do usuário
+    'CPF',      -- CPF informado pelo IdP igual ao do usuário
+    'JIT'       -- Usuário criado no primeiro login (just-in-time)
+);
+
+CREATE TABLE IF NOT EXISTS user_identities (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+
+    -- Nome do provedor configurado (ex.: "govbr")
+    provider VARCHAR(50) NOT NULL,
+
+    -- HMAC do claim `sub`; no gov.br o `sub` é o próprio CPF
+    subject_index TEXT NOT NULL,
+
+    link_method identity_link_method_enum NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_login_at TIMESTAMPTZ,
+
+    CONSTRAINT uq_user_identities_subject UNIQUE (provider, subject_index),
+    CONSTRAINT uq_user_identities_user_provider UNIQUE (user_id, provider)
+);
+
+CREATE INDEX idx_user_identities_user ON user_identities(user_id);
+
+-- Autorizações em andamento: uso único, expiram em poucos minutos
+CREATE TABLE IF NOT EXISTS oidc_login_states (
+    state VARCHAR(128) PRIMARY KEY,
+    provider VARCHAR(50) NOT NULL,
+    nonce VARCHAR(128) NOT NULL,
+    code_verifier VARCHAR(128) NOT NULL,
+    redirect_to TEXT,
+    expires_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_oidc_login_states_expires_at ON oidc_login_states(expires_at);
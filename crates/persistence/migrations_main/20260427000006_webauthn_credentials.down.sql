@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS webauthn_challenges;
+DROP TYPE IF EXISTS webauthn_challenge_kind_enum;
+DROP TABLE IF EXISTS webauthn_credentials;
@@ -0,0 +1,54 @@
+-- ============================================================================
+-- Migration: WebAuthn / passkeys
+-- Description: Credenciais WebAuthn (chaves de segurança e passkeys) usadas
+--              como segundo fator ou login sem senha, e o estado dos
+--              desafios (cerimônias) em andamento.
+-- ============================================================================
+
+CREATE TABLE IF NOT EXISTS webauthn_credentials (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+
+    -- Credential ID em base64url (único globalmente)
+    credential_id TEXT NOT NULL,
+
+    -- Nome dado pelo usuário (ex.: "YubiKey 5", "iPhone")
+    name VARCHAR(100) NOT NULL,
+
+    -- Credencial serializada (chave pública COSE, contador, flags)
+    passkey JSONB NOT NULL,
+
+    sign_count BIGINT NOT NULL DEFAULT 0,
+    backup_eligible BOOLEAN NOT NULL DEFAULT FALSE,
+    backup_state BOOLEAN NOT NULL DEFAULT FALSE,
+
+    -- Preenchido quando o contador de assinaturas regride (possível clone).
+    -- Credenciais marcadas não são aceitas em novas autenticações.
+    clone_detected_at TIMESTAMPTZ,
+
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_used_at TIMESTAMPTZ,
+
+    CONSTRAINT uq_webauthn_credentials_credential_id UNIQUE (credential_id),
+    CONSTRAINT uq_webauthn_credentials_user_name UNIQUE (user_id, name)
+);
+
+CREATE INDEX idx_webauthn_credentials_user ON webauthn_credentials(user_id);
+
+CREATE TYPE webauthn_challenge_kind_enum AS ENUM (
+    'REGISTRATION',     -- Cadastro de nova credencial
+    'AUTHENTICATION',   -- Segundo fator (desafio MFA do login)
+    'DISCOVERABLE'      -- Login sem senha (credencial descoberta pelo navegador)
+);
+
+-- Desafios em andamento: uso único, expiram em poucos minutos
+CREATE TABLE IF NOT EXISTS webauthn_challenges (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID REFERENCES users(id) ON DELETE CASCADE,
+    kind webauthn_challenge_kind_enum NOT NULL,
+    state JSONB NOT NULL,
+    expires_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_webauthn_challenges_expires_at ON webauthn_challenges(expires_at);
@@ -0,0 +1,50 @@
+-- ============================================================================
+-- Migration: Login throttling / account lockout
+-- Description: Registro das tentativas de login (sucesso e falha) que alimenta
+--              o bloqueio progressivo por conta e os limites por IP e sub-rede.
+-- ============================================================================
+
+CREATE TABLE IF NOT EXISTS login_attempts (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+
+    -- NULL quando o identificador informado não corresponde a nenhum usuário
+    user_id UUID REFERENCES users(id) ON DELETE CASCADE,
+
+    -- Username ou email digitado (normalizado em minúsculas)
+    identifier VARCHAR(255) NOT NULL,
+
+    ip_address INET,
+    succeeded BOOLEAN NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_login_attempts_failed_ip
+    ON login_attempts (ip_address, created_at)
+    WHERE NOT succeeded;
+
+CREATE INDEX IF NOT EXISTS idx_login_attempts_created_at
+    ON login_attempts (created_at);
+
+CREATE TABLE IF NOT EXISTS account_lockouts (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+
+    -- Falhas consecutivas dentro da janela atual
+    failed_count INTEGER NOT NULL DEFAULT 0,
+
+    -- Quantos bloqueios seguidos já ocorreram (duração dobra a cada nível)
+    lockout_level INTEGER NOT NULL DEFAULT 0,
+
+    locked_until TIMESTAMPTZ,
+    last_failed_at TIMESTAMPTZ,
+    last_failed_ip INET,
+
+    -- Token de desbloqueio enviado por email (SHA-256)
+    unlock_token_hash VARCHAR(64),
+    unlock_token_expires_at TIMESTAMPTZ,
+
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_account_lockouts_unlock_token
+    ON account_lockouts (unlock_token_hash)
+    WHERE unlock_token_hash IS NOT NULL;
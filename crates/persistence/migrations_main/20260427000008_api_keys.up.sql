@@ -0,0 +1,51 @@
+-- ============================================================================
+-- Migration: API keys for machine-to-machine integrations
+-- Description: Service principals (identidades não humanas: ponte SIAFI,
+--              ferramentas de BI, importação de cartão combustível) e suas
+--              chaves de API com escopo, validade e lista de IPs permitidos.
+-- ============================================================================
+
+CREATE TABLE IF NOT EXISTS service_principals (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name VARCHAR(100) NOT NULL UNIQUE,
+    description TEXT,
+
+    -- Role do Casbin cujas permissões limitam todas as chaves do principal
+    role VARCHAR(50) NOT NULL,
+
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS api_keys (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    principal_id UUID NOT NULL REFERENCES service_principals(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+
+    -- Parte pública da chave (ex.: wsk_3f9a1c0b7d2e), usada na busca
+    prefix VARCHAR(32) NOT NULL UNIQUE,
+
+    -- SHA-256 (hex) do segredo; o segredo em claro só é exibido na criação
+    secret_hash VARCHAR(64) NOT NULL,
+
+    -- Lista de {obj, act} no formato das políticas do Casbin
+    scopes JSONB NOT NULL DEFAULT '[]',
+
+    -- Vazio = qualquer origem
+    allowed_ips CIDR[] NOT NULL DEFAULT '{}',
+
+    expires_at TIMESTAMPTZ NOT NULL,
+    last_used_at TIMESTAMPTZ,
+    last_used_ip INET,
+
+    -- Chave substituída por esta numa rotação
+    rotated_from_id UUID REFERENCES api_keys(id) ON DELETE SET NULL,
+
+    revoked_at TIMESTAMPTZ,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_api_keys_principal ON api_keys (principal_id);
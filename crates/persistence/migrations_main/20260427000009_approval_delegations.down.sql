@@ -0,0 +1,4 @@
+ALTER TABLE stock_transfers DROP COLUMN IF EXISTS confirmed_on_behalf_of;
+ALTER TABLE vehicle_trips DROP COLUMN IF EXISTS aprovado_em_nome_de;
+ALTER TABLE requisitions DROP COLUMN IF EXISTS approved_on_behalf_of;
+DROP TABLE IF EXISTS approval_delegations;
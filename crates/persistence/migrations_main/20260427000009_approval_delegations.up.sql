@@ -0,0 +1,46 @@
+-- ============================================================================
+-- Migration: Delegação de competência para aprovação
+-- Description: Um aprovador delega a outro usuário, por um período, a
+--              aprovação de requisições, a revisão de viagens e/ou a
+--              confirmação de transferências. Aprovações feitas pelo
+--              delegado registram em nome de quem foram feitas.
+-- ============================================================================
+
+CREATE TABLE IF NOT EXISTS approval_delegations (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+
+    -- Titular da competência e quem passa a exercê-la
+    delegator_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    delegate_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+
+    -- Lista de escopos: REQUISITION, TRIP, STOCK_TRANSFER
+    scopes JSONB NOT NULL DEFAULT '[]',
+
+    -- Vigência; fora dela a delegação expira sem intervenção
+    valid_from TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    valid_until TIMESTAMPTZ NOT NULL,
+
+    reason TEXT,
+
+    revoked_at TIMESTAMPTZ,
+    revoked_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT chk_delegation_distinct_users CHECK (delegator_id <> delegate_id),
+    CONSTRAINT chk_delegation_period CHECK (valid_until > valid_from)
+);
+
+CREATE INDEX IF NOT EXISTS idx_approval_delegations_delegate
+    ON approval_delegations (delegate_id, valid_until)
+    WHERE revoked_at IS NULL;
+CREATE INDEX IF NOT EXISTS idx_approval_delegations_delegator
+    ON approval_delegations (delegator_id);
+
+-- Em nome de quem a decisão foi tomada quando exercida por delegação
+ALTER TABLE requisitions
+    ADD COLUMN IF NOT EXISTS approved_on_behalf_of UUID REFERENCES users(id) ON DELETE SET NULL;
+ALTER TABLE vehicle_trips
+    ADD COLUMN IF NOT EXISTS aprovado_em_nome_de UUID REFERENCES users(id) ON DELETE SET NULL;
+ALTER TABLE stock_transfers
+    ADD COLUMN IF NOT EXISTS confirmed_on_behalf_of UUID REFERENCES users(id) ON DELETE SET NULL;
@@ -0,0 +1,5 @@
+DROP TABLE IF EXISTS document_approval_steps;
+DROP TABLE IF EXISTS approval_chain_steps;
+DROP TABLE IF EXISTS approval_chains;
+DROP TYPE IF EXISTS approval_step_status_enum;
+DROP TYPE IF EXISTS approval_document_type_enum;
@@ -0,0 +1,89 @@
+-- ============================================================================
+-- Migration: Cadeias de aprovação em múltiplos níveis
+-- Description: Requisições, transferências e pedidos de desfazimento passam
+--              a exigir, conforme valor, prioridade, almoxarifado e unidade
+--              de destino, uma sequência configurável de aprovações.
+--              Etapas com a mesma ordem correm em paralelo; ordens maiores
+--              só começam quando as anteriores estiverem aprovadas.
+--              Documentos sem cadeia aplicável mantêm a aprovação única.
+-- ============================================================================
+
+CREATE TYPE approval_document_type_enum AS ENUM (
+    'REQUISITION',
+    'STOCK_TRANSFER',
+    'DISPOSAL_REQUEST'
+);
+
+CREATE TYPE approval_step_status_enum AS ENUM (
+    'PENDING',
+    'APPROVED',
+    'REJECTED'
+);
+
+CREATE TABLE approval_chains (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    document_type approval_document_type_enum NOT NULL,
+    name VARCHAR(200) NOT NULL,
+    description TEXT,
+
+    -- Condições (NULL = qualquer valor); todas precisam ser atendidas
+    min_value NUMERIC(15, 2),
+    priorities JSONB,
+    warehouse_id UUID REFERENCES warehouses(id) ON DELETE CASCADE,
+    destination_unit_id UUID REFERENCES organizational_units(id) ON DELETE CASCADE,
+
+    -- Entre cadeias aplicáveis, vence a de maior precedência
+    precedence INTEGER NOT NULL DEFAULT 0,
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT chk_approval_chain_min_value CHECK (min_value IS NULL OR min_value >= 0)
+);
+
+CREATE INDEX idx_approval_chains_lookup
+    ON approval_chains (document_type, precedence DESC)
+    WHERE is_active;
+
+CREATE TABLE approval_chain_steps (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    chain_id UUID NOT NULL REFERENCES approval_chains(id) ON DELETE CASCADE,
+    step_order INTEGER NOT NULL CHECK (step_order > 0),
+    name VARCHAR(200) NOT NULL,
+
+    -- Quem pode decidir: um papel (global ou da unidade do documento) e/ou um usuário
+    approver_role VARCHAR(100),
+    approver_user_id UUID REFERENCES users(id) ON DELETE CASCADE,
+
+    CONSTRAINT chk_approval_step_approver
+        CHECK (approver_role IS NOT NULL OR approver_user_id IS NOT NULL)
+);
+
+CREATE INDEX idx_approval_chain_steps_chain ON approval_chain_steps (chain_id, step_order);
+
+-- Etapas instanciadas para cada documento (cópia da cadeia no momento em
+-- que o documento entrou em aprovação; alterações posteriores na cadeia não
+-- afetam documentos em andamento)
+CREATE TABLE document_approval_steps (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    document_type approval_document_type_enum NOT NULL,
+    document_id UUID NOT NULL,
+    chain_id UUID REFERENCES approval_chains(id) ON DELETE SET NULL,
+    step_order INTEGER NOT NULL,
+    name VARCHAR(200) NOT NULL,
+    approver_role VARCHAR(100),
+    approver_user_id UUID REFERENCES users(id) ON DELETE SET NULL,
+
+    status approval_step_status_enum NOT NULL DEFAULT 'PENDING',
+    decided_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    decided_on_behalf_of UUID REFERENCES users(id) ON DELETE SET NULL,
+    decided_at TIMESTAMPTZ,
+    comments TEXT,
+
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_document_approval_steps_document
+    ON document_approval_steps (document_type, document_id, step_order);
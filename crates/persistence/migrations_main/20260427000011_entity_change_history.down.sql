@@ -0,0 +1,20 @@
+DROP TRIGGER IF EXISTS trg_organizational_units_history ON organizational_units;
+DROP TRIGGER IF EXISTS trg_catser_items_history ON catser_items;
+DROP TRIGGER IF EXISTS trg_catmat_items_history ON catmat_items;
+DROP TRIGGER IF EXISTS trg_drivers_history ON drivers;
+DROP TRIGGER IF EXISTS trg_suppliers_history ON suppliers;
+DROP TRIGGER IF EXISTS trg_warehouse_stocks_history ON warehouse_stocks;
+DROP TRIGGER IF EXISTS trg_warehouses_history ON warehouses;
+
+DROP FUNCTION IF EXISTS fn_capture_entity_change();
+DROP FUNCTION IF EXISTS fn_get_audit_request_id();
+
+DROP INDEX IF EXISTS idx_changelog_request;
+
+-- Registros de políticas (entity_id não-UUID) não cabem no tipo original
+DELETE FROM entity_changelog WHERE entity_type = 'policy';
+
+ALTER TABLE entity_changelog
+    DROP COLUMN IF EXISTS request_id,
+    DROP COLUMN IF EXISTS diff,
+    ALTER COLUMN entity_id TYPE UUID USING entity_id::UUID;
@@ -0,0 +1,114 @@
+-- Histórico de alterações por entidade (captura genérica via trigger)
+-- Reaproveita entity_changelog e o contexto definido por fn_set_audit_context.
+
+-- Políticas do Casbin são identificadas pelo sujeito (TEXT), não por UUID
+ALTER TABLE entity_changelog
+    ALTER COLUMN entity_id TYPE TEXT USING entity_id::TEXT,
+    ADD COLUMN diff JSONB,
+    ADD COLUMN request_id UUID;
+
+CREATE INDEX idx_changelog_request ON entity_changelog(request_id) WHERE request_id IS NOT NULL;
+
+COMMENT ON COLUMN entity_changelog.diff IS 'Campos alterados no formato {campo: {old, new}}';
+COMMENT ON COLUMN entity_changelog.request_id IS 'Request ID da requisição HTTP (mesmo valor de audit_logs.request_id)';
+
+CREATE OR REPLACE FUNCTION fn_get_audit_request_id()
+RETURNS UUID AS $$
+DECLARE
+    v_request_id TEXT;
+BEGIN
+    v_request_id := current_setting('audit.request_id', TRUE);
+    IF v_request_id IS NULL OR v_request_id = '' THEN
+        RETURN NULL;
+    END IF;
+    RETURN v_request_id::UUID;
+EXCEPTION WHEN OTHERS THEN
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql STABLE;
+
+-- Trigger genérico: TG_ARGV[0] = tipo da entidade, TG_ARGV[1] = coluna chave.
+-- Alterações apenas em colunas de controle (updated_at, version) são ignoradas.
+CREATE OR REPLACE FUNCTION fn_capture_entity_change()
+RETURNS TRIGGER AS $$
+DECLARE
+    v_before JSONB;
+    v_after JSONB;
+    v_changed TEXT[];
+    v_diff JSONB;
+    v_operation audit_operation_enum;
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        v_after := to_jsonb(NEW);
+        v_operation := 'INSERT';
+    ELSIF TG_OP = 'UPDATE' THEN
+        v_before := to_jsonb(OLD);
+        v_after := to_jsonb(NEW);
+        v_operation := 'UPDATE';
+    ELSE
+        v_before := to_jsonb(OLD);
+        v_operation := 'DELETE';
+    END IF;
+
+    v_diff := fn_generate_diff(v_before, v_after) - 'updated_at' - 'version';
+    IF v_diff = '{}'::JSONB THEN
+        RETURN NULL;
+    END IF;
+
+    SELECT array_agg(key ORDER BY key) INTO v_changed FROM jsonb_object_keys(v_diff) AS key;
+
+    INSERT INTO entity_changelog (
+        entity_type, entity_id, operation, data_before, data_after,
+        changed_fields, diff, performed_by, performed_at, ip_address, user_agent, request_id
+    ) VALUES (
+        TG_ARGV[0],
+        COALESCE(v_after, v_before) ->> TG_ARGV[1],
+        v_operation,
+        v_before,
+        v_after,
+        v_changed,
+        v_diff,
+        fn_get_audit_user_id(),
+        -- Hora real da alteração: ordena as mudanças de uma mesma transação
+        clock_timestamp(),
+        fn_get_audit_ip(),
+        NULLIF(current_setting('audit.user_agent', TRUE), ''),
+        fn_get_audit_request_id()
+    );
+
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trg_warehouses_history
+    AFTER INSERT OR UPDATE OR DELETE ON warehouses
+    FOR EACH ROW EXECUTE FUNCTION fn_capture_entity_change('warehouse', 'id');
+
+-- Apenas os parâmetros do estoque; saldos já têm o histórico de movimentações
+CREATE TRIGGER trg_warehouse_stocks_history
+    AFTER UPDATE OF min_stock, max_stock, reorder_point, resupply_days, location,
+                    secondary_location, is_blocked, block_reason
+    ON warehouse_stocks
+    FOR EACH ROW EXECUTE FUNCTION fn_capture_entity_change('warehouse_stock', 'id');
+
+CREATE TRIGGER trg_suppliers_history
+    AFTER INSERT OR UPDATE OR DELETE ON suppliers
+    FOR EACH ROW EXECUTE FUNCTION fn_capture_entity_change('supplier', 'id');
+
+CREATE TRIGGER trg_drivers_history
+    AFTER INSERT OR UPDATE OR DELETE ON drivers
+    FOR EACH ROW EXECUTE FUNCTION fn_capture_entity_change('driver', 'id');
+
+CREATE TRIGGER trg_catmat_items_history
+    AFTER INSERT OR UPDATE OR DELETE ON catmat_items
+    FOR EACH ROW EXECUTE FUNCTION fn_capture_entity_change('catmat_item', 'id');
+
+CREATE TRIGGER trg_catser_items_history
+    AFTER INSERT OR UPDATE OR DELETE ON catser_items
+    FOR EACH ROW EXECUTE FUNCTION fn_capture_entity_change('catser_item', 'id');
+
+CREATE TRIGGER trg_organizational_units_history
+    AFTER INSERT OR UPDATE OR DELETE ON organizational_units
+    FOR EACH ROW EXECUTE FUNCTION fn_capture_entity_change('organizational_unit', 'id');
+
+COMMENT ON FUNCTION fn_capture_entity_change IS 'Registra em entity_changelog o antes/depois de cada alteração (args: tipo, coluna chave)';
@@ -0,0 +1,5 @@
+DROP FUNCTION IF EXISTS fn_lgpd_mask_diff(JSONB, JSONB);
+DROP FUNCTION IF EXISTS fn_lgpd_mask(JSONB, JSONB);
+
+ALTER TABLE drivers DROP COLUMN IF EXISTS anonymized_at;
+ALTER TABLE users DROP COLUMN IF EXISTS anonymized_at;
@@ -0,0 +1,37 @@
+-- ============================================================================
+-- Migration: LGPD data-subject anonymization
+-- Description: Titulares anonimizados continuam na base para preservar as
+--              referências de viagens, multas e requisições; os dados pessoais
+--              são trocados por tokens aleatórios e irreversíveis.
+-- ============================================================================
+
+ALTER TABLE users ADD COLUMN IF NOT EXISTS anonymized_at TIMESTAMPTZ;
+ALTER TABLE drivers ADD COLUMN IF NOT EXISTS anonymized_at TIMESTAMPTZ;
+
+COMMENT ON COLUMN users.anonymized_at IS
+    'Quando os dados pessoais do usuário foram anonimizados (LGPD art. 18, IV)';
+COMMENT ON COLUMN drivers.anonymized_at IS
+    'Quando os dados pessoais do motorista foram anonimizados (LGPD art. 18, IV)';
+
+-- Troca, em um snapshot JSONB, os campos presentes em `tokens` pelo valor
+-- tokenizado; campos ausentes no snapshot não são adicionados
+CREATE OR REPLACE FUNCTION fn_lgpd_mask(doc JSONB, tokens JSONB)
+RETURNS JSONB AS $$
+    SELECT doc || COALESCE(
+        (SELECT jsonb_object_agg(t.key, t.value)
+         FROM jsonb_each(tokens) t
+         WHERE doc ? t.key),
+        '{}'::jsonb
+    )
+$$ LANGUAGE sql IMMUTABLE STRICT;
+
+-- Mesma troca para diffs no formato `{campo: {old, new}}`
+CREATE OR REPLACE FUNCTION fn_lgpd_mask_diff(diff JSONB, tokens JSONB)
+RETURNS JSONB AS $$
+    SELECT diff || COALESCE(
+        (SELECT jsonb_object_agg(t.key, jsonb_build_object('old', t.value, 'new', t.value))
+         FROM jsonb_each(tokens) t
+         WHERE diff ? t.key),
+        '{}'::jsonb
+    )
+$$ LANGUAGE sql IMMUTABLE STRICT;
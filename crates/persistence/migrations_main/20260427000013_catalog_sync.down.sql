@@ -0,0 +1,15 @@
+DELETE FROM system_settings WHERE key IN (
+    'compras_gov.sync_default_unit_symbol',
+    'compras_gov.sync_default_material_classification'
+);
+
+DROP TABLE IF EXISTS catalog_sync_runs;
+DROP TYPE IF EXISTS catalog_sync_status_enum;
+DROP TYPE IF EXISTS catalog_sync_scope_enum;
+DROP TYPE IF EXISTS catalog_kind_enum;
+
+ALTER TABLE catser_divisions DROP CONSTRAINT IF EXISTS catser_divisions_code_key;
+ALTER TABLE catser_divisions DROP COLUMN IF EXISTS code;
+
+ALTER TABLE catser_sections DROP CONSTRAINT IF EXISTS catser_sections_code_key;
+ALTER TABLE catser_sections DROP COLUMN IF EXISTS code;
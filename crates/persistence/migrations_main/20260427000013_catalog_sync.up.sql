@@ -0,0 +1,73 @@
+-- ============================================================================
+-- Migration: Sincronização do catálogo com o ComprasGov
+-- Description: Execuções da sincronização de CATMAT e CATSER com a API de
+--              dados abertos do ComprasGov (completa, por grupo ou por PDM).
+--              Os registros são casados pelo código oficial; itens que
+--              desaparecem do catálogo oficial são inativados, nunca
+--              removidos. Seções e divisões do CATSER passam a guardar o
+--              código oficial, até então ausente.
+-- ============================================================================
+
+ALTER TABLE catser_sections ADD COLUMN code VARCHAR(10);
+ALTER TABLE catser_sections ADD CONSTRAINT catser_sections_code_key UNIQUE (code);
+
+ALTER TABLE catser_divisions ADD COLUMN code VARCHAR(10);
+ALTER TABLE catser_divisions ADD CONSTRAINT catser_divisions_code_key UNIQUE (code);
+
+CREATE TYPE catalog_kind_enum AS ENUM (
+    'CATMAT',
+    'CATSER'
+);
+
+CREATE TYPE catalog_sync_scope_enum AS ENUM (
+    'FULL',
+    'GROUP',
+    'PDM'
+);
+
+CREATE TYPE catalog_sync_status_enum AS ENUM (
+    'RUNNING',
+    'COMPLETED',
+    'FAILED'
+);
+
+CREATE TABLE catalog_sync_runs (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    catalog catalog_kind_enum NOT NULL,
+    scope catalog_sync_scope_enum NOT NULL,
+    -- Código oficial do grupo ou PDM (NULL na sincronização completa)
+    scope_code VARCHAR(20),
+    -- Simulação: calcula o relatório sem gravar no catálogo
+    dry_run BOOLEAN NOT NULL DEFAULT FALSE,
+    status catalog_sync_status_enum NOT NULL DEFAULT 'RUNNING',
+
+    added_count INTEGER NOT NULL DEFAULT 0,
+    changed_count INTEGER NOT NULL DEFAULT 0,
+    deactivated_count INTEGER NOT NULL DEFAULT 0,
+    -- Relatório: { added: [...], changed: [...], deactivated: [...], skipped: [...] }
+    report JSONB,
+    error TEXT,
+
+    triggered_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    started_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    finished_at TIMESTAMPTZ,
+
+    CONSTRAINT chk_catalog_sync_scope_code
+        CHECK ((scope = 'FULL') = (scope_code IS NULL)),
+    CONSTRAINT chk_catalog_sync_pdm_catmat
+        CHECK (scope <> 'PDM' OR catalog = 'CATMAT')
+);
+
+CREATE INDEX idx_catalog_sync_runs_started ON catalog_sync_runs (started_at DESC);
+
+-- Uma sincronização efetiva por catálogo de cada vez
+CREATE UNIQUE INDEX uq_catalog_sync_runs_running
+    ON catalog_sync_runs (catalog)
+    WHERE status = 'RUNNING' AND NOT dry_run;
+
+-- Valores usados nos registros criados pela sincronização, que a API não informa
+INSERT INTO system_settings (key, value, value_type, category, description)
+VALUES
+    ('compras_gov.sync_default_unit_symbol', '"UNID"'::jsonb, 'string', 'compras_gov', 'Símbolo da unidade de medida atribuída aos itens CATMAT/CATSER criados pela sincronização'),
+    ('compras_gov.sync_default_material_classification', '"DIRECT_USE"'::jsonb, 'string', 'compras_gov', 'Classificação (STOCKABLE, PERMANENT ou DIRECT_USE) dos PDMs criados pela sincronização')
+ON CONFLICT (key) DO NOTHING;
@@ -0,0 +1,11 @@
+DROP INDEX IF EXISTS idx_catser_items_code_prefix;
+DROP INDEX IF EXISTS idx_catmat_items_code_prefix;
+DROP INDEX IF EXISTS idx_catser_items_fuzzy;
+DROP INDEX IF EXISTS idx_catmat_items_fuzzy;
+DROP INDEX IF EXISTS idx_catser_items_fts;
+DROP INDEX IF EXISTS idx_catmat_items_fts;
+
+DROP FUNCTION IF EXISTS catser_item_search_vector(TEXT, TEXT, TEXT, TEXT);
+DROP FUNCTION IF EXISTS catmat_item_search_vector(TEXT, TEXT);
+DROP TEXT SEARCH CONFIGURATION IF EXISTS portuguese_unaccent;
+DROP FUNCTION IF EXISTS catalog_unaccent(TEXT);
@@ -0,0 +1,60 @@
+-- ============================================================================
+-- Migration: Busca textual no catálogo
+-- Description: Busca de itens CATMAT e CATSER por texto completo em português
+--              sem acentos, com similaridade por trigramas como alternativa
+--              para erros de digitação. Os vetores de busca são índices de
+--              expressão (e não colunas) para não poluir o histórico de
+--              alterações das tabelas.
+-- ============================================================================
+
+CREATE EXTENSION IF NOT EXISTS unaccent;
+
+-- unaccent() é STABLE; a versão com dicionário explícito pode ser usada em índices
+CREATE OR REPLACE FUNCTION catalog_unaccent(TEXT)
+RETURNS TEXT
+LANGUAGE sql IMMUTABLE PARALLEL SAFE STRICT AS
+$$ SELECT public.unaccent('public.unaccent'::regdictionary, $1) $$;
+
+CREATE TEXT SEARCH CONFIGURATION portuguese_unaccent (COPY = pg_catalog.portuguese);
+ALTER TEXT SEARCH CONFIGURATION portuguese_unaccent
+    ALTER MAPPING FOR hword, hword_part, word
+    WITH unaccent, portuguese_stem;
+
+-- Código com peso A, descrição B e textos complementares C
+CREATE OR REPLACE FUNCTION catmat_item_search_vector(code TEXT, description TEXT)
+RETURNS tsvector
+LANGUAGE sql IMMUTABLE PARALLEL SAFE AS
+$$
+    SELECT setweight(to_tsvector('public.portuguese_unaccent'::regconfig, COALESCE(code, '')), 'A')
+        || setweight(to_tsvector('public.portuguese_unaccent'::regconfig, COALESCE(description, '')), 'B')
+$$;
+
+CREATE OR REPLACE FUNCTION catser_item_search_vector(
+    code TEXT,
+    description TEXT,
+    supplementary_description TEXT,
+    specification TEXT
+)
+RETURNS tsvector
+LANGUAGE sql IMMUTABLE PARALLEL SAFE AS
+$$
+    SELECT setweight(to_tsvector('public.portuguese_unaccent'::regconfig, COALESCE(code, '')), 'A')
+        || setweight(to_tsvector('public.portuguese_unaccent'::regconfig, COALESCE(description, '')), 'B')
+        || setweight(to_tsvector('public.portuguese_unaccent'::regconfig,
+               COALESCE(supplementary_description, '') || ' ' || COALESCE(specification, '')), 'C')
+$$;
+
+CREATE INDEX idx_catmat_items_fts ON catmat_items
+    USING gin (catmat_item_search_vector(code, description));
+CREATE INDEX idx_catser_items_fts ON catser_items
+    USING gin (catser_item_search_vector(code, description, supplementary_description, specification));
+
+-- Alternativa por similaridade (erros de digitação)
+CREATE INDEX idx_catmat_items_fuzzy ON catmat_items
+    USING gin (catalog_unaccent(lower(description)) gin_trgm_ops);
+CREATE INDEX idx_catser_items_fuzzy ON catser_items
+    USING gin (catalog_unaccent(lower(description)) gin_trgm_ops);
+
+-- Autocompletar por prefixo do código
+CREATE INDEX idx_catmat_items_code_prefix ON catmat_items (code text_pattern_ops);
+CREATE INDEX idx_catser_items_code_prefix ON catser_items (code text_pattern_ops);
@@ -0,0 +1,29 @@
+-- ============================================================================
+-- Migration: Fusão de itens duplicados do catálogo
+-- Description: A importação legada deixou itens CATMAT quase duplicados
+--              (mesmo produto, descrições diferentes). A fusão transfere
+--              todas as referências para o item sobrevivente, soma os saldos
+--              e remove o duplicado, deixando aqui o redirecionamento do ID
+--              antigo para quem ainda o tiver guardado.
+-- ============================================================================
+
+CREATE TABLE catalog_item_redirects (
+    -- Sem FK: o item antigo não existe mais
+    old_item_id UUID PRIMARY KEY,
+    new_item_id UUID NOT NULL REFERENCES catmat_items(id) ON DELETE CASCADE,
+
+    -- Cópia do item removido, para consulta
+    old_code VARCHAR(20) NOT NULL,
+    old_description TEXT NOT NULL,
+
+    reason TEXT,
+    merged_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    merged_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT ck_catalog_item_redirects_self CHECK (old_item_id <> new_item_id)
+);
+
+CREATE INDEX idx_catalog_item_redirects_new ON catalog_item_redirects(new_item_id);
+CREATE INDEX idx_catalog_item_redirects_merged_at ON catalog_item_redirects(merged_at DESC);
+
+COMMENT ON TABLE catalog_item_redirects IS 'IDs de itens CATMAT removidos por fusão e o item que os substituiu';
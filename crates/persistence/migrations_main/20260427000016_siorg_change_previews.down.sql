@@ -0,0 +1,4 @@
+DELETE FROM system_settings WHERE key = 'siorg.require_change_approval';
+
+DROP TABLE IF EXISTS siorg_change_previews;
+DROP TYPE IF EXISTS siorg_preview_status_enum;
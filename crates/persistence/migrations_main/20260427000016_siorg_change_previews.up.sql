@@ -0,0 +1,52 @@
+-- ============================================================================
+-- Migration: Prévia das alterações de estrutura do SIORG
+-- Description: Diferença entre a estrutura publicada pelo SIORG e as unidades
+--              locais, calculada sem gravar nada. Cada unidade alterada
+--              (criada, renomeada, movida, desativada) é aprovada ou rejeitada
+--              por um administrador antes de ser aplicada. Com a aprovação
+--              obrigatória ligada, a sincronização agendada gera a prévia em
+--              vez de aplicar as alterações.
+-- ============================================================================
+
+CREATE TYPE siorg_preview_status_enum AS ENUM (
+    'PENDING',
+    'APPLIED',
+    'DISCARDED'
+);
+
+CREATE TABLE siorg_change_previews (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    org_siorg_code INTEGER NOT NULL,
+    -- Versão SIORG sincronizada quando a prévia foi calculada
+    from_version VARCHAR(50) NOT NULL,
+    to_version VARCHAR(50) NOT NULL,
+    status siorg_preview_status_enum NOT NULL DEFAULT 'PENDING',
+
+    total_changes INTEGER NOT NULL DEFAULT 0,
+    -- Alterações em unidades com almoxarifados, veículos ou requisições abertas
+    flagged_changes INTEGER NOT NULL DEFAULT 0,
+    -- Diferença por unidade: [{ siorg_code, kinds, ..., decision, upstream }]
+    entries JSONB NOT NULL DEFAULT '[]'::jsonb,
+
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    decided_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    decided_at TIMESTAMPTZ,
+
+    CONSTRAINT chk_siorg_preview_decided
+        CHECK ((status = 'PENDING') = (decided_at IS NULL))
+);
+
+CREATE INDEX idx_siorg_change_previews_org
+    ON siorg_change_previews (organization_id, created_at DESC);
+
+-- Uma prévia pendente por organização; uma nova substitui a anterior
+CREATE UNIQUE INDEX uq_siorg_change_previews_pending
+    ON siorg_change_previews (organization_id)
+    WHERE status = 'PENDING';
+
+INSERT INTO system_settings (key, value, value_type, category, description)
+VALUES
+    ('siorg.require_change_approval', 'false'::jsonb, 'boolean', 'siorg', 'A sincronização incremental do SIORG gera uma prévia para aprovação em vez de aplicar as alterações')
+ON CONFLICT (key) DO NOTHING;
@@ -0,0 +1,4 @@
+DROP FUNCTION IF EXISTS fn_unit_current(UUID);
+DROP TABLE IF EXISTS organizational_unit_lineage;
+DROP TABLE IF EXISTS organizational_unit_restructurings;
+DROP TYPE IF EXISTS unit_restructuring_kind_enum;
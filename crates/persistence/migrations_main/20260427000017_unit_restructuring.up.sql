@@ -0,0 +1,73 @@
+-- ============================================================================
+-- Migration: Reestruturação de unidades organizacionais (fusão e cisão)
+-- Description: Quando unidades são fundidas ou cindidas (no SIORG, extinção
+--              de códigos e criação de outros), as referências das unidades
+--              antigas migram para as sucessoras: almoxarifados, requisições
+--              em aberto, veículos (por transferência departamental), cadeias
+--              de aprovação e papéis de usuários por unidade. A linhagem
+--              antiga → nova é registrada para que relatórios históricos
+--              agreguem pela estrutura antiga ou pela atual.
+-- ============================================================================
+
+CREATE TYPE unit_restructuring_kind_enum AS ENUM (
+    'MERGE',
+    'SPLIT'
+);
+
+CREATE TABLE organizational_unit_restructurings (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    kind unit_restructuring_kind_enum NOT NULL,
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    effective_date DATE NOT NULL,
+    reason TEXT NOT NULL,
+    documento_sei TEXT,
+    -- Referências migradas: { warehouses, open_requisitions, vehicles, ... }
+    migrated JSONB NOT NULL DEFAULT '{}'::jsonb,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_unit_restructurings_org
+    ON organizational_unit_restructurings (organization_id, effective_date DESC);
+
+CREATE TABLE organizational_unit_lineage (
+    restructuring_id UUID NOT NULL REFERENCES organizational_unit_restructurings(id) ON DELETE CASCADE,
+    predecessor_id UUID NOT NULL REFERENCES organizational_units(id) ON DELETE RESTRICT,
+    successor_id UUID NOT NULL REFERENCES organizational_units(id) ON DELETE RESTRICT,
+    -- Sucessora que herda as referências (a única na fusão)
+    is_primary BOOLEAN NOT NULL DEFAULT FALSE,
+    effective_date DATE NOT NULL,
+
+    PRIMARY KEY (restructuring_id, predecessor_id, successor_id),
+    CONSTRAINT chk_unit_lineage_distinct CHECK (predecessor_id <> successor_id)
+);
+
+CREATE INDEX idx_unit_lineage_predecessor ON organizational_unit_lineage (predecessor_id);
+CREATE INDEX idx_unit_lineage_successor ON organizational_unit_lineage (successor_id);
+
+-- Uma unidade tem no máximo uma sucessora principal
+CREATE UNIQUE INDEX uq_unit_lineage_primary
+    ON organizational_unit_lineage (predecessor_id)
+    WHERE is_primary;
+
+-- ============================================================================
+-- Function: unidade atual de uma unidade histórica
+-- Segue as sucessoras principais até a unidade que não foi reestruturada.
+-- Relatórios agregam pela estrutura atual com fn_unit_current(unit_id).
+-- ============================================================================
+
+CREATE OR REPLACE FUNCTION fn_unit_current(p_unit_id UUID)
+RETURNS UUID AS $$
+    WITH RECURSIVE chain AS (
+        SELECT p_unit_id AS unit_id, 0 AS depth
+        UNION ALL
+        SELECT l.successor_id, c.depth + 1
+        FROM chain c
+        JOIN organizational_unit_lineage l
+          ON l.predecessor_id = c.unit_id AND l.is_primary
+        WHERE c.depth < 20  -- Safety limit
+    )
+    SELECT unit_id FROM chain ORDER BY depth DESC LIMIT 1;
+$$ LANGUAGE sql STABLE;
+
+COMMENT ON FUNCTION fn_unit_current(UUID) IS 'Unidade atual que sucedeu a unidade informada (ela mesma se não foi reestruturada)';
@@ -0,0 +1,6 @@
+DROP FUNCTION IF EXISTS fn_vehicle_department_at(UUID, DATE);
+DROP FUNCTION IF EXISTS fn_unit_as_of(UUID, DATE);
+DROP FUNCTION IF EXISTS fn_unit_structure_as_of(DATE);
+DROP TRIGGER IF EXISTS trg_record_unit_version ON organizational_units;
+DROP FUNCTION IF EXISTS fn_record_unit_version();
+DROP TABLE IF EXISTS organizational_unit_versions;
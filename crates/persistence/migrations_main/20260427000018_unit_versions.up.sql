@@ -0,0 +1,276 @@
+-- ============================================================================
+-- Migration: Versões datadas das unidades organizacionais
+-- Description: Nome, unidade superior e situação de cada unidade passam a ter
+--              vigência (valid_from / valid_to), permitindo consultar a
+--              estrutura como era em uma data e agregar relatórios de
+--              exercícios anteriores pela estrutura vigente na época.
+--              As versões anteriores são reconstruídas a partir do
+--              siorg_history; mudanças futuras são registradas por trigger.
+-- ============================================================================
+
+CREATE TABLE organizational_unit_versions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    unit_id UUID NOT NULL REFERENCES organizational_units(id) ON DELETE CASCADE,
+    -- NULL: vigente desde antes do início dos registros
+    valid_from DATE,
+    -- Exclusivo; NULL: versão atual
+    valid_to DATE,
+    name TEXT NOT NULL,
+    parent_id UUID REFERENCES organizational_units(id) ON DELETE SET NULL,
+    is_active BOOLEAN NOT NULL,
+    -- Entrada do histórico do SIORG que originou a versão (carga inicial)
+    siorg_history_id UUID REFERENCES siorg_history(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT chk_unit_version_period CHECK (
+        valid_from IS NULL OR valid_to IS NULL OR valid_from < valid_to
+    )
+);
+
+-- Uma única versão aberta por unidade
+CREATE UNIQUE INDEX uq_unit_versions_open
+    ON organizational_unit_versions (unit_id)
+    WHERE valid_to IS NULL;
+
+CREATE UNIQUE INDEX uq_unit_versions_start
+    ON organizational_unit_versions (unit_id, COALESCE(valid_from, '-infinity'::DATE));
+
+CREATE INDEX idx_unit_versions_period
+    ON organizational_unit_versions (valid_from, valid_to);
+
+COMMENT ON TABLE organizational_unit_versions IS
+    'Vigência do nome, da unidade superior e da situação de cada unidade organizacional';
+
+-- ============================================================================
+-- Carga inicial a partir do siorg_history
+-- Usa as entradas de UNIT que trazem o estado da unidade (new_data), uma por
+-- dia (a última do dia prevalece).
+-- ============================================================================
+
+CREATE TEMP TABLE tmp_unit_snapshots ON COMMIT DROP AS
+WITH daily AS (
+    SELECT DISTINCT ON (h.local_id, h.created_at::DATE)
+        h.id AS history_id,
+        h.local_id AS unit_id,
+        h.created_at::DATE AS changed_on,
+        h.change_type::TEXT AS change_type,
+        h.previous_data,
+        h.new_data
+    FROM siorg_history h
+    JOIN organizational_units u ON u.id = h.local_id
+    WHERE h.entity_type = 'UNIT'
+      AND h.new_data ? 'name'
+    ORDER BY h.local_id, h.created_at::DATE, h.created_at DESC
+)
+SELECT
+    d.*,
+    ROW_NUMBER() OVER w AS seq,
+    LEAD(d.changed_on) OVER w AS next_change
+FROM daily d
+WINDOW w AS (PARTITION BY d.unit_id ORDER BY d.changed_on);
+
+-- Estado anterior à primeira mudança registrada
+INSERT INTO organizational_unit_versions
+    (unit_id, valid_from, valid_to, name, parent_id, is_active, siorg_history_id)
+SELECT
+    s.unit_id,
+    NULL,
+    s.changed_on,
+    s.previous_data->>'name',
+    p.id,
+    COALESCE((s.previous_data->>'is_active')::BOOLEAN, TRUE),
+    s.history_id
+FROM tmp_unit_snapshots s
+LEFT JOIN organizational_units p ON p.id = (s.previous_data->>'parent_id')::UUID
+WHERE s.seq = 1
+  AND s.change_type <> 'CREATION'
+  AND s.previous_data ? 'name';
+
+INSERT INTO organizational_unit_versions
+    (unit_id, valid_from, valid_to, name, parent_id, is_active, siorg_history_id)
+SELECT
+    s.unit_id,
+    CASE
+        WHEN s.seq = 1
+         AND s.change_type <> 'CREATION'
+         AND NOT COALESCE(s.previous_data ? 'name', FALSE)
+        THEN NULL
+        ELSE s.changed_on
+    END,
+    s.next_change,
+    s.new_data->>'name',
+    p.id,
+    COALESCE((s.new_data->>'is_active')::BOOLEAN, s.change_type <> 'EXTINCTION'),
+    s.history_id
+FROM tmp_unit_snapshots s
+LEFT JOIN organizational_units p ON p.id = (s.new_data->>'parent_id')::UUID;
+
+-- Alinha a versão aberta com o estado atual da unidade
+UPDATE organizational_unit_versions v
+SET name = u.name, parent_id = u.parent_id, is_active = u.is_active
+FROM organizational_units u
+WHERE v.unit_id = u.id
+  AND v.valid_to IS NULL
+  AND v.valid_from = CURRENT_DATE;
+
+UPDATE organizational_unit_versions v
+SET valid_to = CURRENT_DATE
+FROM organizational_units u
+WHERE v.unit_id = u.id
+  AND v.valid_to IS NULL
+  AND (v.name, v.parent_id, v.is_active) IS DISTINCT FROM (u.name, u.parent_id, u.is_active);
+
+-- Unidades sem histórico: uma versão vigente desde sempre
+INSERT INTO organizational_unit_versions (unit_id, valid_from, name, parent_id, is_active)
+SELECT
+    u.id,
+    CASE
+        WHEN EXISTS (SELECT 1 FROM organizational_unit_versions v WHERE v.unit_id = u.id)
+        THEN CURRENT_DATE
+    END,
+    u.name,
+    u.parent_id,
+    u.is_active
+FROM organizational_units u
+WHERE NOT EXISTS (
+    SELECT 1 FROM organizational_unit_versions v
+    WHERE v.unit_id = u.id AND v.valid_to IS NULL
+);
+
+-- ============================================================================
+-- Trigger: registra uma nova versão a cada mudança de nome, unidade superior
+-- ou situação. Mudanças no mesmo dia substituem a versão do dia.
+-- ============================================================================
+
+CREATE OR REPLACE FUNCTION fn_record_unit_version()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'UPDATE'
+       AND NEW.name IS NOT DISTINCT FROM OLD.name
+       AND NEW.parent_id IS NOT DISTINCT FROM OLD.parent_id
+       AND NEW.is_active IS NOT DISTINCT FROM OLD.is_active THEN
+        RETURN NEW;
+    END IF;
+
+    UPDATE organizational_unit_versions
+    SET name = NEW.name, parent_id = NEW.parent_id, is_active = NEW.is_active
+    WHERE unit_id = NEW.id
+      AND valid_to IS NULL
+      AND valid_from = CURRENT_DATE;
+
+    IF NOT FOUND THEN
+        UPDATE organizational_unit_versions
+        SET valid_to = CURRENT_DATE
+        WHERE unit_id = NEW.id AND valid_to IS NULL;
+
+        INSERT INTO organizational_unit_versions (unit_id, valid_from, name, parent_id, is_active)
+        VALUES (NEW.id, CURRENT_DATE, NEW.name, NEW.parent_id, NEW.is_active);
+    END IF;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trg_record_unit_version
+    AFTER INSERT OR UPDATE OF name, parent_id, is_active ON organizational_units
+    FOR EACH ROW EXECUTE PROCEDURE fn_record_unit_version();
+
+-- ============================================================================
+-- Function: estrutura organizacional vigente em uma data
+-- Unidades existentes na data, com nome, superior e situação da versão
+-- vigente; nível e caminho são recalculados sobre essa estrutura. Uma
+-- superior que ainda não existia na data é tratada como raiz.
+-- ============================================================================
+
+CREATE OR REPLACE FUNCTION fn_unit_structure_as_of(p_date DATE)
+RETURNS TABLE (
+    unit_id UUID,
+    name TEXT,
+    parent_id UUID,
+    is_active BOOLEAN,
+    level INTEGER,
+    path_ids UUID[],
+    path_names TEXT
+) AS $$
+    WITH RECURSIVE versions AS (
+        SELECT v.unit_id, v.name, v.parent_id, v.is_active, u.organization_id
+        FROM organizational_unit_versions v
+        JOIN organizational_units u ON u.id = v.unit_id
+        WHERE (v.valid_from IS NULL OR v.valid_from <= p_date)
+          AND (v.valid_to IS NULL OR v.valid_to > p_date)
+    ),
+    tree AS (
+        SELECT
+            v.unit_id,
+            v.name,
+            NULL::UUID AS parent_id,
+            v.is_active,
+            1 AS level,
+            ARRAY[v.unit_id] AS path_ids,
+            COALESCE(o.name, '') || ' > ' || v.name AS path_names
+        FROM versions v
+        LEFT JOIN organizations o ON o.id = v.organization_id
+        WHERE v.parent_id IS NULL
+           OR NOT EXISTS (SELECT 1 FROM versions p WHERE p.unit_id = v.parent_id)
+
+        UNION ALL
+
+        SELECT
+            c.unit_id,
+            c.name,
+            c.parent_id,
+            c.is_active,
+            t.level + 1,
+            t.path_ids || c.unit_id,
+            t.path_names || ' > ' || c.name
+        FROM versions c
+        JOIN tree t ON t.unit_id = c.parent_id
+        WHERE NOT c.unit_id = ANY(t.path_ids)
+    )
+    SELECT unit_id, name, parent_id, is_active, level, path_ids, path_names
+    FROM tree;
+$$ LANGUAGE sql STABLE;
+
+-- ============================================================================
+-- Function: unidade que respondia por uma unidade em uma data
+-- Segue as sucessoras principais cujas reestruturações já vigoravam na data.
+-- ============================================================================
+
+CREATE OR REPLACE FUNCTION fn_unit_as_of(p_unit_id UUID, p_date DATE)
+RETURNS UUID AS $$
+    WITH RECURSIVE chain AS (
+        SELECT p_unit_id AS unit_id, 0 AS depth
+        UNION ALL
+        SELECT l.successor_id, c.depth + 1
+        FROM chain c
+        JOIN organizational_unit_lineage l
+          ON l.predecessor_id = c.unit_id
+         AND l.is_primary
+         AND l.effective_date <= p_date
+        WHERE c.depth < 50
+    )
+    SELECT unit_id FROM chain ORDER BY depth DESC LIMIT 1;
+$$ LANGUAGE sql STABLE;
+
+-- ============================================================================
+-- Function: departamento de um veículo em uma data
+-- Última transferência até a data; antes da primeira, o departamento de
+-- origem dela; sem transferências, o departamento atual.
+-- ============================================================================
+
+CREATE OR REPLACE FUNCTION fn_vehicle_department_at(p_vehicle_id UUID, p_date DATE)
+RETURNS UUID AS $$
+    SELECT COALESCE(
+        (SELECT t.dept_destino_id
+         FROM vehicle_department_transfers t
+         WHERE t.vehicle_id = p_vehicle_id AND t.data_efetiva <= p_date
+         ORDER BY t.data_efetiva DESC, t.created_at DESC
+         LIMIT 1),
+        (SELECT t.dept_origem_id
+         FROM vehicle_department_transfers t
+         WHERE t.vehicle_id = p_vehicle_id AND t.data_efetiva > p_date
+         ORDER BY t.data_efetiva, t.created_at
+         LIMIT 1),
+        (SELECT v.department_id FROM vehicles v WHERE v.id = p_vehicle_id)
+    );
+$$ LANGUAGE sql STABLE;
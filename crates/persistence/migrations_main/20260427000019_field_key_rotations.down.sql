@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS field_key_rotations;
+DROP TYPE IF EXISTS field_key_rotation_stage_enum;
+DROP TYPE IF EXISTS field_key_rotation_status_enum;
@@ -0,0 +1,55 @@
+-- ============================================================================
+-- Migration: Rotação da chave de criptografia de campos
+-- Description: Execuções do job que recriptografa com a chave atual os campos
+--              cifrados (email e segredo MFA dos usuários, segredos de
+--              cadastro de MFA pendentes) e reconstrói o blind index do
+--              email. O progresso é gravado a cada lote: uma execução
+--              interrompida retoma do último registro processado.
+--              Os blind indexes sem valor recuperável (users.cpf_index,
+--              user_identities.subject_index) são regravados com a chave
+--              atual no próximo uso.
+-- ============================================================================
+
+CREATE TYPE field_key_rotation_status_enum AS ENUM (
+    'RUNNING',
+    'COMPLETED',
+    'FAILED'
+);
+
+CREATE TYPE field_key_rotation_stage_enum AS ENUM (
+    'USERS',
+    'MFA_SETUP_TOKENS'
+);
+
+CREATE TABLE field_key_rotations (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    -- Versão da chave (WS_FIELD_ENCRYPTION_KEY_ID) usada na recriptografia
+    target_key_id INTEGER NOT NULL,
+    status field_key_rotation_status_enum NOT NULL DEFAULT 'RUNNING',
+
+    -- Ponto de retomada: tabela em processamento e último id concluído
+    stage field_key_rotation_stage_enum NOT NULL DEFAULT 'USERS',
+    last_processed_id UUID,
+
+    total_rows BIGINT NOT NULL DEFAULT 0,
+    processed_rows BIGINT NOT NULL DEFAULT 0,
+    reencrypted_rows BIGINT NOT NULL DEFAULT 0,
+    -- Registros que nenhuma chave do keyring consegue abrir
+    unreadable_rows BIGINT NOT NULL DEFAULT 0,
+    error TEXT,
+
+    triggered_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    started_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    finished_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_field_key_rotations_started ON field_key_rotations (started_at DESC);
+
+-- Uma rotação em execução de cada vez
+CREATE UNIQUE INDEX uq_field_key_rotations_running
+    ON field_key_rotations ((TRUE))
+    WHERE status = 'RUNNING';
+
+COMMENT ON TABLE field_key_rotations IS
+    'Execuções da recriptografia dos campos cifrados com a chave atual';
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS jwt_signing_keys;
+DROP TYPE IF EXISTS jwt_signing_key_status_enum;
@@ -0,0 +1,46 @@
+-- ============================================================================
+-- Migration: Keyring de assinatura dos JWTs
+-- Description: Pares de chaves que assinam os tokens, identificados pelo
+--              header `kid`. Uma rotação gera uma nova chave ativa e aposenta
+--              a anterior, que continua verificando tokens até o fim do
+--              período de carência (verify_until). Chaves expiradas não são
+--              removidas: ainda verificam as assinaturas dos checkpoints de
+--              auditoria. Antes da primeira rotação assina a chave
+--              configurada (WS_JWT_PRIVATE_KEY / private.pem).
+-- ============================================================================
+
+CREATE TYPE jwt_signing_key_status_enum AS ENUM (
+    'ACTIVE',
+    'RETIRED'
+);
+
+CREATE TABLE jwt_signing_keys (
+    -- Thumbprint RFC 7638 da chave pública
+    kid VARCHAR(64) PRIMARY KEY,
+    algorithm VARCHAR(10) NOT NULL CHECK (algorithm IN ('EdDSA', 'RS256')),
+    status jwt_signing_key_status_enum NOT NULL,
+
+    public_jwk JSONB NOT NULL,
+    -- DER da chave privada, cifrado com a chave de campos; nulo para a chave
+    -- configurada, que não sai do ambiente
+    private_key TEXT,
+
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    retired_at TIMESTAMPTZ,
+    verify_until TIMESTAMPTZ,
+
+    CONSTRAINT chk_jwt_signing_keys_retired CHECK (
+        status = 'ACTIVE' OR (retired_at IS NOT NULL AND verify_until IS NOT NULL)
+    )
+);
+
+CREATE INDEX idx_jwt_signing_keys_created ON jwt_signing_keys (created_at DESC);
+
+-- Uma chave ativa de cada vez
+CREATE UNIQUE INDEX uq_jwt_signing_keys_active
+    ON jwt_signing_keys ((TRUE))
+    WHERE status = 'ACTIVE';
+
+COMMENT ON TABLE jwt_signing_keys IS
+    'Chaves de assinatura dos JWTs publicadas em /.well-known/jwks.json';
@@ -0,0 +1,10 @@
+-- ============================================================================
+-- Migration: Índice das falhas de login por identificador inexistente
+-- Description: Identificadores sem conta recebem o mesmo bloqueio progressivo
+--              das contas reais, calculado a partir de login_attempts, para
+--              que o 429 não revele quais contas existem.
+-- ============================================================================
+
+CREATE INDEX IF NOT EXISTS idx_login_attempts_unknown_identifier
+    ON login_attempts (identifier, created_at)
+    WHERE user_id IS NULL AND NOT succeeded;
@@ -0,0 +1,5 @@
+DROP INDEX IF EXISTS idx_catmat_items_merged_into;
+ALTER TABLE catmat_items
+    DROP CONSTRAINT IF EXISTS ck_catmat_items_merged_self,
+    DROP CONSTRAINT IF EXISTS ck_catmat_items_merged_inactive,
+    DROP COLUMN IF EXISTS merged_into_id;
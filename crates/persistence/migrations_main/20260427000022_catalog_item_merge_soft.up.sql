@@ -0,0 +1,20 @@
+-- ============================================================================
+-- Migration: Itens fundidos permanecem no catálogo
+-- Description: Em vez de removidos, os itens CATMAT fundidos ficam inativos e
+--              apontam para o sobrevivente. Assim o código oficial continua
+--              ocupado localmente e nem a sincronização com o ComprasGov nem a
+--              importação por código voltam a criá-lo ou reativá-lo.
+-- ============================================================================
+
+ALTER TABLE catmat_items
+    ADD COLUMN merged_into_id UUID REFERENCES catmat_items(id) ON DELETE SET NULL,
+    ADD CONSTRAINT ck_catmat_items_merged_inactive
+        CHECK (merged_into_id IS NULL OR NOT is_active),
+    ADD CONSTRAINT ck_catmat_items_merged_self
+        CHECK (merged_into_id <> id);
+
+CREATE INDEX idx_catmat_items_merged_into ON catmat_items(merged_into_id)
+    WHERE merged_into_id IS NOT NULL;
+
+COMMENT ON COLUMN catmat_items.merged_into_id IS 'Item sobrevivente quando este foi fundido; o item fica inativo';
+COMMENT ON TABLE catalog_item_redirects IS 'IDs de itens CATMAT fundidos e o item que os substituiu';
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS driver_points_alerts;
+DROP TYPE IF EXISTS driver_points_alert_level_enum;
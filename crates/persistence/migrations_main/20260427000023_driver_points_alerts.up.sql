@@ -0,0 +1,35 @@
+-- =============================================================================
+-- Alertas de pontuação da CNH
+--
+-- Gravados quando a indicação de condutor leva o condutor ao nível de alerta
+-- ou de suspensão (CTB Art. 261). Fica no máximo um alerta aberto por
+-- condutor e nível; novas multas atualizam a pontuação do alerta aberto até
+-- que o gestor dê ciência.
+-- =============================================================================
+
+CREATE TYPE driver_points_alert_level_enum AS ENUM (
+    'WARNING',      -- Pontuação a partir de 75% do limite
+    'SUSPENSION'    -- Limite de suspensão atingido
+);
+
+CREATE TABLE driver_points_alerts (
+    id                   UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    driver_id            UUID NOT NULL REFERENCES drivers(id) ON DELETE CASCADE,
+    vehicle_fine_id      UUID REFERENCES vehicle_fines(id) ON DELETE SET NULL,
+    alert_level          driver_points_alert_level_enum NOT NULL,
+    total_points         BIGINT NOT NULL,
+    suspension_threshold BIGINT NOT NULL,
+    acknowledged_at      TIMESTAMPTZ,
+    acknowledged_by      UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at           TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at           TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX uq_driver_points_alerts_open ON driver_points_alerts (driver_id, alert_level)
+    WHERE acknowledged_at IS NULL;
+CREATE INDEX idx_driver_points_alerts_created ON driver_points_alerts (created_at DESC);
+
+CREATE TRIGGER set_timestamp_driver_points_alerts
+BEFORE UPDATE ON driver_points_alerts
+FOR EACH ROW
+EXECUTE FUNCTION update_updated_at_column();
@@ -0,0 +1,4 @@
+DROP INDEX IF EXISTS uq_driver_points_alerts_open;
+ALTER TABLE driver_points_alerts DROP COLUMN IF EXISTS superseded_by;
+CREATE UNIQUE INDEX uq_driver_points_alerts_open ON driver_points_alerts (driver_id, alert_level)
+    WHERE acknowledged_at IS NULL;
@@ -0,0 +1,14 @@
+-- =============================================================================
+-- Alerta de pontuação substituído
+--
+-- Quando o condutor passa ao nível de suspensão, o alerta de WARNING ainda
+-- aberto é encerrado e aponta para o alerta de SUSPENSION que o substituiu.
+-- Aberto passa a ser: sem ciência e não substituído.
+-- =============================================================================
+
+ALTER TABLE driver_points_alerts
+    ADD COLUMN superseded_by UUID REFERENCES driver_points_alerts(id) ON DELETE SET NULL;
+
+DROP INDEX uq_driver_points_alerts_open;
+CREATE UNIQUE INDEX uq_driver_points_alerts_open ON driver_points_alerts (driver_id, alert_level)
+    WHERE acknowledged_at IS NULL AND superseded_by IS NULL;
@@ -0,0 +1,5 @@
+DROP INDEX IF EXISTS uq_jwt_signing_keys_id;
+ALTER TABLE jwt_signing_keys DROP COLUMN IF EXISTS id;
+
+-- Valores de enum não podem ser removidos; 'JWT_SIGNING_KEYS' permanece
+-- em field_key_rotation_stage_enum.
@@ -0,0 +1,15 @@
+-- ============================================================================
+-- Migration: Chaves de assinatura JWT na rotação da chave de campos
+-- Description: A chave privada das chaves de assinatura geradas por rotação
+--              é cifrada com a chave de campos e passa a ser recriptografada
+--              pelo job, depois dos segredos de MFA. O job retoma pelo último
+--              id (UUID) processado: a tabela, identificada pelo kid, ganha
+--              um id para servir de cursor.
+-- ============================================================================
+
+ALTER TYPE field_key_rotation_stage_enum ADD VALUE IF NOT EXISTS 'JWT_SIGNING_KEYS';
+
+ALTER TABLE jwt_signing_keys
+    ADD COLUMN id UUID NOT NULL DEFAULT gen_random_uuid();
+
+CREATE UNIQUE INDEX uq_jwt_signing_keys_id ON jwt_signing_keys (id);
@@ -0,0 +1,4 @@
+ALTER TABLE service_principals DROP CONSTRAINT IF EXISTS fk_service_principals_user;
+
+-- Os usuários de apoio permanecem: podem ser referenciados por registros
+-- criados com as chaves de API.
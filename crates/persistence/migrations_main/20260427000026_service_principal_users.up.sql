@@ -0,0 +1,16 @@
+-- ============================================================================
+-- Migration: Usuário de apoio para service principals
+-- Description: Requisições feitas com chave de API têm o principal como ator,
+--              e o id do ator é gravado em colunas que referenciam users(id)
+--              (created_by, submitted_by, ...). Cada principal passa a ter um
+--              registro em users com o mesmo id, sem senha utilizável.
+-- ============================================================================
+
+INSERT INTO users (id, username, email, password_hash)
+SELECT id, 'svc:' || id, 'svc-' || id || '@service-principals.invalid', '!'
+FROM service_principals
+ON CONFLICT (id) DO NOTHING;
+
+ALTER TABLE service_principals
+    ADD CONSTRAINT fk_service_principals_user
+    FOREIGN KEY (id) REFERENCES users(id) ON DELETE RESTRICT;
@@ -2,7 +2,9 @@
 //!
 //! This module provides shared utilities to avoid code duplication across repositories.
 
+use core_services::request_context;
 use domain::errors::RepositoryError;
+use sqlx::{PgPool, Postgres, Transaction};
 
 /// PostgreSQL error codes for constraint violations.
 pub mod pg_error_codes {
@@ -140,6 +142,48 @@ impl<T> SqlxResultExt<T> for Result<T, sqlx::Error> {
     }
 }
 
+/// Begins a transaction carrying the current request's audit context
+/// (actor, IP, user agent and request ID) so the change-capture triggers can
+/// attribute the write. Outside a request the context is left empty.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use persistence::db_utils::begin_audited;
+///
+/// async fn update(&self, ...) -> Result<SupplierDto, RepositoryError> {
+///     let mut tx = begin_audited(&self.pool).await?;
+///     let supplier = sqlx::query_as(...).fetch_one(&mut *tx).await.map_repo_err()?;
+///     tx.commit().await.map_repo_err()?;
+///     Ok(supplier)
+/// }
+/// ```
+pub async fn begin_audited(
+    pool: &PgPool,
+) -> Result<Transaction<'static, Postgres>, RepositoryError> {
+    let mut tx = pool.begin().await.map_err(map_db_error)?;
+
+    if let Some(ctx) = request_context::current() {
+        sqlx::query(
+            r#"
+            SELECT set_config('audit.current_user_id', COALESCE($1::TEXT, ''), TRUE),
+                   set_config('audit.ip_address', COALESCE($2, ''), TRUE),
+                   set_config('audit.user_agent', COALESCE($3, ''), TRUE),
+                   set_config('audit.request_id', $4::TEXT, TRUE)
+            "#,
+        )
+        .bind(ctx.actor())
+        .bind(ctx.ip_address.as_deref())
+        .bind(ctx.user_agent.as_deref())
+        .bind(ctx.request_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(map_db_error)?;
+    }
+
+    Ok(tx)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,250 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use domain::errors::RepositoryError;
+use domain::models::{ApiKeyDto, NewApiKey, ServicePrincipalDto, StoredApiKey};
+use domain::ports::ApiKeyRepositoryPort;
+use sqlx::types::Json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::db_utils::map_db_error;
+
+const PRINCIPAL_COLUMNS: &str =
+    "id, name, description, role, is_active, created_by, created_at, updated_at";
+
+const KEY_COLUMNS: &str = "id, principal_id, name, prefix, scopes, \
+     allowed_ips::TEXT[] AS allowed_ips, expires_at, last_used_at, \
+     host(last_used_ip) AS last_used_ip, rotated_from_id, revoked_at, created_by, created_at";
+
+#[derive(Clone)]
+pub struct ApiKeyRepository {
+    pool: PgPool,
+}
+
+impl ApiKeyRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+async fn insert_key<'e, E>(executor: E, key: &NewApiKey) -> Result<ApiKeyDto, RepositoryError>
+where
+    E: sqlx::PgExecutor<'e>,
+{
+    sqlx::query_as::<_, ApiKeyDto>(&format!(
+        r#"
+        INSERT INTO api_keys (
+            principal_id, name, prefix, secret_hash, scopes, allowed_ips,
+            expires_at, rotated_from_id, created_by
+        )
+        VALUES ($1, $2, $3, $4, $5, $6::CIDR[], $7, $8, $9)
+        RETURNING {}
+        "#,
+        KEY_COLUMNS
+    ))
+    .bind(key.principal_id)
+    .bind(&key.name)
+    .bind(&key.prefix)
+    .bind(&key.secret_hash)
+    .bind(Json(&key.scopes))
+    .bind(&key.allowed_ips)
+    .bind(key.expires_at)
+    .bind(key.rotated_from_id)
+    .bind(key.created_by)
+    .fetch_one(executor)
+    .await
+    .map_err(map_db_error)
+}
+
+#[async_trait]
+impl ApiKeyRepositoryPort for ApiKeyRepository {
+    async fn create_principal(
+        &self,
+        name: &str,
+        description: Option<&str>,
+        role: &str,
+        created_by: Option<Uuid>,
+    ) -> Result<ServicePrincipalDto, RepositoryError> {
+        let mut tx = self.pool.begin().await.map_err(map_db_error)?;
+
+        // Usuário de apoio com o mesmo id: o principal é o ator das
+        // requisições feitas com suas chaves
+        let id = Uuid::new_v4();
+        sqlx::query(
+            r#"
+            INSERT INTO users (id, username, email, password_hash)
+            VALUES ($1, 'svc:' || $1::TEXT, 'svc-' || $1::TEXT || '@service-principals.invalid', '!')
+            "#,
+        )
+        .bind(id)
+        .execute(&mut *tx)
+        .await
+        .map_err(map_db_error)?;
+
+        let principal = sqlx::query_as::<_, ServicePrincipalDto>(&format!(
+            r#"
+            INSERT INTO service_principals (id, name, description, role, created_by)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING {}
+            "#,
+            PRINCIPAL_COLUMNS
+        ))
+        .bind(id)
+        .bind(name)
+        .bind(description)
+        .bind(role)
+        .bind(created_by)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(map_db_error)?;
+
+        tx.commit().await.map_err(map_db_error)?;
+        Ok(principal)
+    }
+
+    async fn find_principal(&self, id: Uuid) -> Result<Option<ServicePrincipalDto>, RepositoryError> {
+        sqlx::query_as::<_, ServicePrincipalDto>(&format!(
+            "SELECT {} FROM service_principals WHERE id = $1",
+            PRINCIPAL_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn exists_principal_by_name(&self, name: &str) -> Result<bool, RepositoryError> {
+        sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM service_principals WHERE LOWER(name) = LOWER($1))",
+        )
+        .bind(name)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn list_principals(&self) -> Result<Vec<ServicePrincipalDto>, RepositoryError> {
+        sqlx::query_as::<_, ServicePrincipalDto>(&format!(
+            "SELECT {} FROM service_principals ORDER BY name",
+            PRINCIPAL_COLUMNS
+        ))
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn update_principal(
+        &self,
+        id: Uuid,
+        description: Option<&str>,
+        role: Option<&str>,
+        is_active: Option<bool>,
+    ) -> Result<Option<ServicePrincipalDto>, RepositoryError> {
+        sqlx::query_as::<_, ServicePrincipalDto>(&format!(
+            r#"
+            UPDATE service_principals SET
+                description = COALESCE($2, description),
+                role = COALESCE($3, role),
+                is_active = COALESCE($4, is_active),
+                updated_at = NOW()
+            WHERE id = $1
+            RETURNING {}
+            "#,
+            PRINCIPAL_COLUMNS
+        ))
+        .bind(id)
+        .bind(description)
+        .bind(role)
+        .bind(is_active)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn create_key(&self, key: &NewApiKey) -> Result<ApiKeyDto, RepositoryError> {
+        insert_key(&self.pool, key).await
+    }
+
+    async fn rotate_key(
+        &self,
+        old_id: Uuid,
+        old_expires_at: DateTime<Utc>,
+        new_key: &NewApiKey,
+    ) -> Result<ApiKeyDto, RepositoryError> {
+        let mut tx = self.pool.begin().await.map_err(map_db_error)?;
+
+        sqlx::query("UPDATE api_keys SET expires_at = LEAST(expires_at, $2) WHERE id = $1")
+            .bind(old_id)
+            .bind(old_expires_at)
+            .execute(&mut *tx)
+            .await
+            .map_err(map_db_error)?;
+
+        let created = insert_key(&mut *tx, new_key).await?;
+
+        tx.commit().await.map_err(map_db_error)?;
+        Ok(created)
+    }
+
+    async fn find_key(&self, id: Uuid) -> Result<Option<ApiKeyDto>, RepositoryError> {
+        sqlx::query_as::<_, ApiKeyDto>(&format!(
+            "SELECT {} FROM api_keys WHERE id = $1",
+            KEY_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn list_keys_by_principal(&self, principal_id: Uuid) -> Result<Vec<ApiKeyDto>, RepositoryError> {
+        sqlx::query_as::<_, ApiKeyDto>(&format!(
+            "SELECT {} FROM api_keys WHERE principal_id = $1 ORDER BY created_at DESC",
+            KEY_COLUMNS
+        ))
+        .bind(principal_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn find_stored_by_prefix(&self, prefix: &str) -> Result<Option<StoredApiKey>, RepositoryError> {
+        sqlx::query_as::<_, StoredApiKey>(
+            r#"
+            SELECT k.id, k.principal_id, p.name AS principal_name, p.role AS principal_role,
+                   p.is_active AS principal_active, k.prefix, k.secret_hash, k.scopes,
+                   k.allowed_ips::TEXT[] AS allowed_ips, k.expires_at, k.revoked_at
+            FROM api_keys k
+            JOIN service_principals p ON p.id = k.principal_id
+            WHERE k.prefix = $1
+            "#,
+        )
+        .bind(prefix)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn revoke_key(&self, id: Uuid) -> Result<bool, RepositoryError> {
+        let result = sqlx::query(
+            "UPDATE api_keys SET revoked_at = NOW() WHERE id = $1 AND revoked_at IS NULL",
+        )
+        .bind(id)
+        .execute(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn touch_key(&self, id: Uuid, ip: Option<&str>) -> Result<(), RepositoryError> {
+        sqlx::query(
+            "UPDATE api_keys SET last_used_at = NOW(), last_used_ip = $2::INET WHERE id = $1",
+        )
+        .bind(id)
+        .bind(ip)
+        .execute(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,373 @@
+use async_trait::async_trait;
+use domain::errors::RepositoryError;
+use domain::models::{
+    ApprovalChainDto, ApprovalChainStepDto, ApprovalChainStepPayload, ApprovalDocumentType,
+    ApprovalStepStatus, CreateApprovalChainPayload, DocumentApprovalStepDto,
+    UpdateApprovalChainPayload,
+};
+use domain::ports::ApprovalChainRepositoryPort;
+use sqlx::types::Json;
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::db_utils::map_db_error;
+
+const CHAIN_SELECT: &str = r#"
+    SELECT id, document_type, name, description, min_value, priorities,
+           warehouse_id, destination_unit_id, precedence, is_active,
+           created_by, created_at, updated_at
+    FROM approval_chains
+"#;
+
+const DOCUMENT_STEP_SELECT: &str = r#"
+    SELECT s.id, s.document_type, s.document_id, s.chain_id, s.step_order, s.name,
+           s.approver_role, s.approver_user_id, s.status,
+           s.decided_by, u.username AS decided_by_username,
+           s.decided_on_behalf_of, s.decided_at, s.comments, s.created_at
+    FROM document_approval_steps s
+    LEFT JOIN users u ON u.id = s.decided_by
+"#;
+
+#[derive(Clone)]
+pub struct ApprovalChainRepository {
+    pool: PgPool,
+}
+
+impl ApprovalChainRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    async fn replace_steps(
+        tx: &mut Transaction<'_, Postgres>,
+        chain_id: Uuid,
+        steps: &[ApprovalChainStepPayload],
+    ) -> Result<(), RepositoryError> {
+        sqlx::query("DELETE FROM approval_chain_steps WHERE chain_id = $1")
+            .bind(chain_id)
+            .execute(&mut **tx)
+            .await
+            .map_err(map_db_error)?;
+
+        for step in steps {
+            sqlx::query(
+                r#"
+                INSERT INTO approval_chain_steps (
+                    chain_id, step_order, name, approver_role, approver_user_id
+                )
+                VALUES ($1, $2, $3, $4, $5)
+                "#,
+            )
+            .bind(chain_id)
+            .bind(step.step_order)
+            .bind(&step.name)
+            .bind(step.approver_role.as_deref())
+            .bind(step.approver_user_id)
+            .execute(&mut **tx)
+            .await
+            .map_err(map_db_error)?;
+        }
+        Ok(())
+    }
+
+    async fn with_steps(
+        &self,
+        mut chains: Vec<ApprovalChainDto>,
+    ) -> Result<Vec<ApprovalChainDto>, RepositoryError> {
+        let ids: Vec<Uuid> = chains.iter().map(|c| c.id).collect();
+        let steps = sqlx::query_as::<_, ApprovalChainStepDto>(
+            r#"
+            SELECT id, chain_id, step_order, name, approver_role, approver_user_id
+            FROM approval_chain_steps
+            WHERE chain_id = ANY($1)
+            ORDER BY step_order, name
+            "#,
+        )
+        .bind(&ids)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        for chain in &mut chains {
+            chain.steps = steps
+                .iter()
+                .filter(|s| s.chain_id == chain.id)
+                .cloned()
+                .collect();
+        }
+        Ok(chains)
+    }
+}
+
+#[async_trait]
+impl ApprovalChainRepositoryPort for ApprovalChainRepository {
+    async fn create_chain(
+        &self,
+        payload: &CreateApprovalChainPayload,
+        created_by: Uuid,
+    ) -> Result<ApprovalChainDto, RepositoryError> {
+        let mut tx = self.pool.begin().await.map_err(map_db_error)?;
+
+        let id: Uuid = sqlx::query_scalar(
+            r#"
+            INSERT INTO approval_chains (
+                document_type, name, description, min_value, priorities,
+                warehouse_id, destination_unit_id, precedence, created_by
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            RETURNING id
+            "#,
+        )
+        .bind(payload.document_type)
+        .bind(&payload.name)
+        .bind(payload.description.as_deref())
+        .bind(payload.min_value)
+        .bind(payload.priorities.as_ref().map(Json))
+        .bind(payload.warehouse_id)
+        .bind(payload.destination_unit_id)
+        .bind(payload.precedence)
+        .bind(created_by)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(map_db_error)?;
+
+        Self::replace_steps(&mut tx, id, &payload.steps).await?;
+        tx.commit().await.map_err(map_db_error)?;
+
+        self.find_chain(id).await?.ok_or(RepositoryError::NotFound)
+    }
+
+    async fn update_chain(
+        &self,
+        id: Uuid,
+        payload: &UpdateApprovalChainPayload,
+    ) -> Result<ApprovalChainDto, RepositoryError> {
+        let mut tx = self.pool.begin().await.map_err(map_db_error)?;
+
+        let result = sqlx::query(
+            r#"
+            UPDATE approval_chains SET
+                name = $2,
+                description = $3,
+                min_value = $4,
+                priorities = $5,
+                warehouse_id = $6,
+                destination_unit_id = $7,
+                precedence = $8,
+                is_active = $9,
+                updated_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(&payload.name)
+        .bind(payload.description.as_deref())
+        .bind(payload.min_value)
+        .bind(payload.priorities.as_ref().map(Json))
+        .bind(payload.warehouse_id)
+        .bind(payload.destination_unit_id)
+        .bind(payload.precedence)
+        .bind(payload.is_active)
+        .execute(&mut *tx)
+        .await
+        .map_err(map_db_error)?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+
+        Self::replace_steps(&mut tx, id, &payload.steps).await?;
+        tx.commit().await.map_err(map_db_error)?;
+
+        self.find_chain(id).await?.ok_or(RepositoryError::NotFound)
+    }
+
+    async fn find_chain(&self, id: Uuid) -> Result<Option<ApprovalChainDto>, RepositoryError> {
+        let chain =
+            sqlx::query_as::<_, ApprovalChainDto>(&format!("{} WHERE id = $1", CHAIN_SELECT))
+                .bind(id)
+                .fetch_optional(&self.pool)
+                .await
+                .map_err(map_db_error)?;
+
+        match chain {
+            Some(chain) => Ok(self.with_steps(vec![chain]).await?.pop()),
+            None => Ok(None),
+        }
+    }
+
+    async fn list_chains(
+        &self,
+        document_type: Option<ApprovalDocumentType>,
+    ) -> Result<Vec<ApprovalChainDto>, RepositoryError> {
+        let chains = sqlx::query_as::<_, ApprovalChainDto>(&format!(
+            r#"{}
+            WHERE ($1::approval_document_type_enum IS NULL OR document_type = $1)
+            ORDER BY document_type, precedence DESC, name"#,
+            CHAIN_SELECT
+        ))
+        .bind(document_type)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        self.with_steps(chains).await
+    }
+
+    async fn list_active_chains(
+        &self,
+        document_type: ApprovalDocumentType,
+    ) -> Result<Vec<ApprovalChainDto>, RepositoryError> {
+        let chains = sqlx::query_as::<_, ApprovalChainDto>(&format!(
+            r#"{}
+            WHERE document_type = $1 AND is_active
+            ORDER BY precedence DESC, created_at"#,
+            CHAIN_SELECT
+        ))
+        .bind(document_type)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        self.with_steps(chains).await
+    }
+
+    async fn deactivate_chain(&self, id: Uuid) -> Result<bool, RepositoryError> {
+        let result = sqlx::query(
+            "UPDATE approval_chains SET is_active = FALSE, updated_at = NOW() WHERE id = $1 AND is_active",
+        )
+        .bind(id)
+        .execute(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn list_document_steps(
+        &self,
+        document_type: ApprovalDocumentType,
+        document_id: Uuid,
+    ) -> Result<Vec<DocumentApprovalStepDto>, RepositoryError> {
+        sqlx::query_as::<_, DocumentApprovalStepDto>(&format!(
+            r#"{}
+            WHERE s.document_type = $1 AND s.document_id = $2
+            ORDER BY s.step_order, s.name"#,
+            DOCUMENT_STEP_SELECT
+        ))
+        .bind(document_type)
+        .bind(document_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn start_document_chain(
+        &self,
+        document_type: ApprovalDocumentType,
+        document_id: Uuid,
+        chain: &ApprovalChainDto,
+    ) -> Result<Vec<DocumentApprovalStepDto>, RepositoryError> {
+        let mut tx = self.pool.begin().await.map_err(map_db_error)?;
+
+        // Serializa o início da cadeia do mesmo documento
+        sqlx::query("SELECT pg_advisory_xact_lock(hashtext($1))")
+            .bind(format!("approval:{}", document_id))
+            .execute(&mut *tx)
+            .await
+            .map_err(map_db_error)?;
+
+        let started: bool = sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM document_approval_steps WHERE document_type = $1 AND document_id = $2)",
+        )
+        .bind(document_type)
+        .bind(document_id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(map_db_error)?;
+
+        if !started {
+            sqlx::query(
+                r#"
+                INSERT INTO document_approval_steps (
+                    document_type, document_id, chain_id, step_order, name,
+                    approver_role, approver_user_id
+                )
+                SELECT $1, $2, chain_id, step_order, name, approver_role, approver_user_id
+                FROM approval_chain_steps
+                WHERE chain_id = $3
+                "#,
+            )
+            .bind(document_type)
+            .bind(document_id)
+            .bind(chain.id)
+            .execute(&mut *tx)
+            .await
+            .map_err(map_db_error)?;
+        }
+
+        tx.commit().await.map_err(map_db_error)?;
+        self.list_document_steps(document_type, document_id).await
+    }
+
+    async fn decide_step(
+        &self,
+        step_id: Uuid,
+        status: ApprovalStepStatus,
+        decided_by: Uuid,
+        on_behalf_of: Option<Uuid>,
+        comments: Option<&str>,
+    ) -> Result<bool, RepositoryError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE document_approval_steps SET
+                status = $2,
+                decided_by = $3,
+                decided_on_behalf_of = $4,
+                decided_at = NOW(),
+                comments = $5
+            WHERE id = $1 AND status = 'PENDING'
+            "#,
+        )
+        .bind(step_id)
+        .bind(status)
+        .bind(decided_by)
+        .bind(on_behalf_of)
+        .bind(comments)
+        .execute(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn roles_of(
+        &self,
+        user_id: Uuid,
+        unit_id: Option<Uuid>,
+    ) -> Result<Vec<String>, RepositoryError> {
+        // Papéis globais (g) e papéis por unidade (g2) cujo domínio é a
+        // própria unidade ou um ancestral atribuído com a subárvore ("<id>/*")
+        sqlx::query_scalar(
+            r#"
+            WITH RECURSIVE ancestors AS (
+                SELECT id, parent_id FROM organizational_units WHERE id = $2
+                UNION ALL
+                SELECT u.id, u.parent_id
+                FROM organizational_units u
+                JOIN ancestors a ON u.id = a.parent_id
+            )
+            SELECT v1 FROM casbin_rule WHERE ptype = 'g' AND v0 = $1
+            UNION
+            SELECT v1 FROM casbin_rule
+            WHERE ptype = 'g2' AND v0 = $1
+              AND (v2 = $2::TEXT OR v2 IN (SELECT id::TEXT || '/*' FROM ancestors))
+            "#,
+        )
+        .bind(user_id.to_string())
+        .bind(unit_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+}
@@ -0,0 +1,315 @@
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use domain::errors::RepositoryError;
+use domain::models::{
+    AuditArchiveDto, AuditChainEntry, AuditCheckpointDto, AuditRedactionCandidate,
+    NewAuditArchive, NewAuditRedaction,
+};
+use domain::ports::AuditIntegrityRepositoryPort;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::db_utils::map_db_error;
+
+const CHECKPOINT_SELECT: &str = r#"
+    SELECT chain_date, entry_count, last_seq, last_hash, digest, signature, signed_at
+    FROM audit_log_checkpoints
+"#;
+
+const ARCHIVE_SELECT: &str = r#"
+    SELECT id, chain_date, first_seq, last_seq, entry_count, last_hash,
+           file_path, file_sha256, signature, archived_at
+    FROM audit_log_archives
+"#;
+
+/// Hash chain of the audit logs.
+/// Note: This connects to db_pool_logs, NOT db_pool_auth
+#[derive(Clone)]
+pub struct AuditIntegrityRepository {
+    pool: PgPool,
+}
+
+impl AuditIntegrityRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl AuditIntegrityRepositoryPort for AuditIntegrityRepository {
+    async fn chain_days(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<NaiveDate>, RepositoryError> {
+        sqlx::query_scalar(
+            r#"
+            SELECT chain_date FROM audit_logs WHERE chain_date BETWEEN $1 AND $2
+            UNION
+            SELECT chain_date FROM audit_log_checkpoints WHERE chain_date BETWEEN $1 AND $2
+            UNION
+            SELECT chain_date FROM audit_log_archives WHERE chain_date BETWEEN $1 AND $2
+            ORDER BY 1
+            "#,
+        )
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn chain_entries(&self, day: NaiveDate) -> Result<Vec<AuditChainEntry>, RepositoryError> {
+        sqlx::query_as::<_, AuditChainEntry>(
+            r#"
+            SELECT
+                a.id, a.user_id, a.username, a.action, a.resource, a.method,
+                a.status_code, a.details, a.ip_address::TEXT AS ip_address,
+                a.user_agent, a.request_id, a.duration_ms, a.created_at,
+                a.chain_date, a.seq, a.prev_hash::TEXT AS prev_hash,
+                a.entry_hash::TEXT AS entry_hash,
+                COALESCE(
+                    a.entry_hash = fn_audit_log_hash(a)
+                    OR (r.entry_hash = a.entry_hash
+                        AND r.redacted_hash = fn_audit_log_redacted_hash(a)),
+                    FALSE
+                ) AS hash_valid,
+                r.redacted_hash::TEXT AS redacted_hash,
+                r.signature AS redaction_signature
+            FROM audit_logs a
+            LEFT JOIN audit_log_redactions r ON r.entry_id = a.id
+            WHERE a.chain_date = $1
+            ORDER BY a.seq
+            "#,
+        )
+        .bind(day)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn days_with_entries_before(
+        &self,
+        before: NaiveDate,
+    ) -> Result<Vec<NaiveDate>, RepositoryError> {
+        sqlx::query_scalar(
+            "SELECT DISTINCT chain_date FROM audit_logs WHERE chain_date < $1 ORDER BY chain_date",
+        )
+        .bind(before)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn unsealed_days_before(
+        &self,
+        before: NaiveDate,
+    ) -> Result<Vec<NaiveDate>, RepositoryError> {
+        sqlx::query_scalar(
+            r#"
+            SELECT DISTINCT a.chain_date
+            FROM audit_logs a
+            WHERE a.chain_date < $1
+              AND NOT EXISTS (
+                  SELECT 1 FROM audit_log_checkpoints c WHERE c.chain_date = a.chain_date
+              )
+            ORDER BY a.chain_date
+            "#,
+        )
+        .bind(before)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn find_checkpoint(
+        &self,
+        day: NaiveDate,
+    ) -> Result<Option<AuditCheckpointDto>, RepositoryError> {
+        sqlx::query_as::<_, AuditCheckpointDto>(&format!(
+            "{} WHERE chain_date = $1",
+            CHECKPOINT_SELECT
+        ))
+        .bind(day)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn list_checkpoints(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<AuditCheckpointDto>, RepositoryError> {
+        sqlx::query_as::<_, AuditCheckpointDto>(&format!(
+            "{} WHERE chain_date BETWEEN $1 AND $2 ORDER BY chain_date",
+            CHECKPOINT_SELECT
+        ))
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn save_checkpoint(
+        &self,
+        checkpoint: &AuditCheckpointDto,
+    ) -> Result<bool, RepositoryError> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO audit_log_checkpoints (
+                chain_date, entry_count, last_seq, last_hash, digest, signature, signed_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (chain_date) DO NOTHING
+            "#,
+        )
+        .bind(checkpoint.chain_date)
+        .bind(checkpoint.entry_count)
+        .bind(checkpoint.last_seq)
+        .bind(&checkpoint.last_hash)
+        .bind(&checkpoint.digest)
+        .bind(&checkpoint.signature)
+        .bind(checkpoint.signed_at)
+        .execute(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn list_archives(&self, day: NaiveDate) -> Result<Vec<AuditArchiveDto>, RepositoryError> {
+        sqlx::query_as::<_, AuditArchiveDto>(&format!(
+            "{} WHERE chain_date = $1 ORDER BY first_seq",
+            ARCHIVE_SELECT
+        ))
+        .bind(day)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn archive_range(
+        &self,
+        archive: &NewAuditArchive,
+    ) -> Result<AuditArchiveDto, RepositoryError> {
+        let mut tx = self.pool.begin().await.map_err(map_db_error)?;
+
+        // Libera o guard de somente inserção apenas nesta transação
+        sqlx::query("SET LOCAL audit.archiving = 'on'")
+            .execute(&mut *tx)
+            .await
+            .map_err(map_db_error)?;
+
+        let deleted =
+            sqlx::query("DELETE FROM audit_logs WHERE chain_date = $1 AND seq BETWEEN $2 AND $3")
+                .bind(archive.chain_date)
+                .bind(archive.first_seq)
+                .bind(archive.last_seq)
+                .execute(&mut *tx)
+                .await
+                .map_err(map_db_error)?;
+
+        if deleted.rows_affected() as i64 != archive.entry_count {
+            return Err(RepositoryError::Transaction(format!(
+                "Expected {} audit entries for {} but found {}",
+                archive.entry_count,
+                archive.chain_date,
+                deleted.rows_affected()
+            )));
+        }
+
+        let saved = sqlx::query_as::<_, AuditArchiveDto>(
+            r#"
+            INSERT INTO audit_log_archives (
+                chain_date, first_seq, last_seq, entry_count, last_hash,
+                file_path, file_sha256, signature
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING id, chain_date, first_seq, last_seq, entry_count, last_hash,
+                      file_path, file_sha256, signature, archived_at
+            "#,
+        )
+        .bind(archive.chain_date)
+        .bind(archive.first_seq)
+        .bind(archive.last_seq)
+        .bind(archive.entry_count)
+        .bind(&archive.last_hash)
+        .bind(&archive.file_path)
+        .bind(&archive.file_sha256)
+        .bind(&archive.signature)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(map_db_error)?;
+
+        tx.commit().await.map_err(map_db_error)?;
+        Ok(saved)
+    }
+
+    async fn redaction_candidates(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<AuditRedactionCandidate>, RepositoryError> {
+        sqlx::query_as::<_, AuditRedactionCandidate>(
+            r#"
+            SELECT
+                a.id, a.entry_hash::TEXT AS entry_hash,
+                fn_audit_log_redacted_hash(a)::TEXT AS redacted_hash,
+                a.entry_hash = fn_audit_log_hash(a) AS hash_valid
+            FROM audit_logs a
+            WHERE a.user_id = $1
+              AND (a.username IS NOT NULL OR a.ip_address IS NOT NULL OR a.user_agent IS NOT NULL)
+            ORDER BY a.chain_date, a.seq
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn redact(
+        &self,
+        entry_ids: &[Uuid],
+        redactions: &[NewAuditRedaction],
+    ) -> Result<u64, RepositoryError> {
+        let mut tx = self.pool.begin().await.map_err(map_db_error)?;
+
+        // Libera o guard apenas para apagar os campos pessoais
+        sqlx::query("SET LOCAL audit.redacting = 'on'")
+            .execute(&mut *tx)
+            .await
+            .map_err(map_db_error)?;
+
+        for redaction in redactions {
+            sqlx::query(
+                r#"
+                INSERT INTO audit_log_redactions (entry_id, entry_hash, redacted_hash, signature)
+                VALUES ($1, $2, $3, $4)
+                ON CONFLICT (entry_id) DO NOTHING
+                "#,
+            )
+            .bind(redaction.entry_id)
+            .bind(&redaction.entry_hash)
+            .bind(&redaction.redacted_hash)
+            .bind(&redaction.signature)
+            .execute(&mut *tx)
+            .await
+            .map_err(map_db_error)?;
+        }
+
+        let erased = sqlx::query(
+            "UPDATE audit_logs SET username = NULL, ip_address = NULL, user_agent = NULL
+             WHERE id = ANY($1)",
+        )
+        .bind(entry_ids)
+        .execute(&mut *tx)
+        .await
+        .map_err(map_db_error)?
+        .rows_affected();
+
+        tx.commit().await.map_err(map_db_error)?;
+        Ok(erased)
+    }
+}
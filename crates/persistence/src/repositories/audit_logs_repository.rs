@@ -480,21 +480,6 @@ impl<'a> AuditLogRepository<'a> {
         .fetch_all(self.pool)
         .await
     }
-
-    /// Cleans up old audit logs based on retention policy.
-    pub async fn cleanup_old_logs(&self, retention_days: i64) -> Result<u64, sqlx::Error> {
-        let result = sqlx::query(
-            r#"
-            DELETE FROM audit_logs 
-            WHERE created_at < NOW() - ($1 * INTERVAL '1 day')
-        "#,
-        )
-        .bind(retention_days as i32)
-        .execute(self.pool)
-        .await?;
-
-        Ok(result.rows_affected())
-    }
 }
 
 // =============================================================================
@@ -0,0 +1,485 @@
+use async_trait::async_trait;
+use domain::errors::RepositoryError;
+use domain::models::{
+    CatalogDuplicateCandidate, CatalogDuplicateFilter, CatalogDuplicatePair,
+    CatalogItemMergeResult, CatalogItemRedirectDto, CatalogMergeReference,
+};
+use domain::ports::CatalogMergeRepositoryPort;
+use sqlx::{PgPool, Postgres, Row, Transaction};
+use uuid::Uuid;
+
+use crate::db_utils::{begin_audited, map_db_error};
+
+/// Tables whose rows are only repointed to the surviving item, and the
+/// column holding the item
+const REPOINTED: &[(&str, &str)] = &[
+    ("stock_movements", "catalog_item_id"),
+    ("stock_reservations", "catalog_item_id"),
+    ("requisition_items", "catalog_item_id"),
+    ("invoice_items", "catalog_item_id"),
+    ("stock_transfer_items", "catalog_item_id"),
+    ("inventory_session_items", "catalog_item_id"),
+    ("disposal_request_items", "catalog_item_id"),
+    ("batch_quality_occurrences", "catalog_item_id"),
+    ("stock_alerts", "catalog_item_id"),
+    ("abc_analysis_results", "catalog_item_id"),
+    ("unit_conversions", "catmat_id"),
+    ("fleet_fuel_catalog", "catmat_item_id"),
+];
+
+/// Tables that allow each item only once per parent document
+const ONE_PER_PARENT: &[(&str, &str, &str)] = &[
+    ("requisition_items", "requisition_id", "A requisição"),
+    ("stock_transfer_items", "transfer_id", "A transferência"),
+    (
+        "inventory_session_items",
+        "session_id",
+        "A sessão de inventário",
+    ),
+];
+
+const REDIRECT_COLUMNS: &str =
+    "old_item_id, new_item_id, old_code, old_description, reason, merged_by, merged_at";
+
+pub struct CatalogMergeRepository {
+    pool: PgPool,
+}
+
+impl CatalogMergeRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Adds the duplicates' warehouse balances to the survivor's, creating
+    /// the survivor's balance where only a duplicate had one. The unit value
+    /// becomes the average weighted by quantity. Returns how many balances
+    /// were added to an existing one.
+    async fn combine_stocks(
+        tx: &mut Transaction<'_, Postgres>,
+        survivor_id: Uuid,
+        duplicate_ids: &[Uuid],
+    ) -> Result<i64, RepositoryError> {
+        let combined = sqlx::query_scalar::<_, i64>(
+            r#"SELECT COUNT(*) FROM warehouse_stocks d
+            WHERE d.catalog_item_id = ANY($2)
+              AND EXISTS (SELECT 1 FROM warehouse_stocks s
+                          WHERE s.catalog_item_id = $1 AND s.warehouse_id = d.warehouse_id)"#,
+        )
+        .bind(survivor_id)
+        .bind(duplicate_ids)
+        .fetch_one(&mut **tx)
+        .await
+        .map_err(map_db_error)?;
+
+        sqlx::query(
+            r#"INSERT INTO warehouse_stocks (
+                warehouse_id, catalog_item_id, min_stock, max_stock, reorder_point,
+                resupply_days, location, secondary_location
+            )
+            SELECT DISTINCT ON (warehouse_id) warehouse_id, $1, min_stock, max_stock,
+                reorder_point, resupply_days, location, secondary_location
+            FROM warehouse_stocks
+            WHERE catalog_item_id = ANY($2)
+            ORDER BY warehouse_id, quantity DESC
+            ON CONFLICT (warehouse_id, catalog_item_id) DO NOTHING"#,
+        )
+        .bind(survivor_id)
+        .bind(duplicate_ids)
+        .execute(&mut **tx)
+        .await
+        .map_err(map_db_error)?;
+
+        sqlx::query(
+            r#"WITH d AS (
+                SELECT warehouse_id,
+                    SUM(quantity) AS quantity,
+                    SUM(reserved_quantity) AS reserved_quantity,
+                    SUM(quantity * average_unit_value) AS total_value,
+                    BOOL_OR(is_blocked) AS is_blocked,
+                    (ARRAY_AGG(block_reason) FILTER (WHERE is_blocked))[1] AS block_reason,
+                    (ARRAY_AGG(blocked_at) FILTER (WHERE is_blocked))[1] AS blocked_at,
+                    (ARRAY_AGG(blocked_by) FILTER (WHERE is_blocked))[1] AS blocked_by,
+                    MAX(last_entry_at) AS last_entry_at,
+                    MAX(last_exit_at) AS last_exit_at,
+                    MAX(last_inventory_at) AS last_inventory_at
+                FROM warehouse_stocks
+                WHERE catalog_item_id = ANY($2)
+                GROUP BY warehouse_id
+            )
+            UPDATE warehouse_stocks s SET
+                average_unit_value = CASE
+                    WHEN s.quantity + d.quantity > 0
+                    THEN ROUND((s.quantity * s.average_unit_value + d.total_value)
+                               / (s.quantity + d.quantity), 4)
+                    ELSE s.average_unit_value
+                END,
+                quantity = s.quantity + d.quantity,
+                reserved_quantity = s.reserved_quantity + d.reserved_quantity,
+                is_blocked = s.is_blocked OR d.is_blocked,
+                block_reason = CASE WHEN s.is_blocked THEN s.block_reason ELSE d.block_reason END,
+                blocked_at = CASE WHEN s.is_blocked THEN s.blocked_at ELSE d.blocked_at END,
+                blocked_by = CASE WHEN s.is_blocked THEN s.blocked_by ELSE d.blocked_by END,
+                last_entry_at = GREATEST(s.last_entry_at, d.last_entry_at),
+                last_exit_at = GREATEST(s.last_exit_at, d.last_exit_at),
+                last_inventory_at = GREATEST(s.last_inventory_at, d.last_inventory_at)
+            FROM d
+            WHERE s.catalog_item_id = $1 AND s.warehouse_id = d.warehouse_id"#,
+        )
+        .bind(survivor_id)
+        .bind(duplicate_ids)
+        .execute(&mut **tx)
+        .await
+        .map_err(map_db_error)?;
+
+        sqlx::query("DELETE FROM warehouse_stocks WHERE catalog_item_id = ANY($1)")
+            .bind(duplicate_ids)
+            .execute(&mut **tx)
+            .await
+            .map_err(map_db_error)?;
+
+        Ok(combined)
+    }
+
+    /// Same as [`Self::combine_stocks`] for the balances per batch, with the
+    /// unit cost weighted by quantity
+    async fn combine_batches(
+        tx: &mut Transaction<'_, Postgres>,
+        survivor_id: Uuid,
+        duplicate_ids: &[Uuid],
+    ) -> Result<i64, RepositoryError> {
+        let combined = sqlx::query_scalar::<_, i64>(
+            r#"SELECT COUNT(*) FROM warehouse_batch_stocks d
+            WHERE d.catalog_item_id = ANY($2)
+              AND EXISTS (SELECT 1 FROM warehouse_batch_stocks s
+                          WHERE s.catalog_item_id = $1 AND s.warehouse_id = d.warehouse_id
+                            AND s.batch_number = d.batch_number)"#,
+        )
+        .bind(survivor_id)
+        .bind(duplicate_ids)
+        .fetch_one(&mut **tx)
+        .await
+        .map_err(map_db_error)?;
+
+        sqlx::query(
+            r#"INSERT INTO warehouse_batch_stocks (
+                warehouse_id, catalog_item_id, batch_number, expiration_date
+            )
+            SELECT DISTINCT ON (warehouse_id, batch_number) warehouse_id, $1, batch_number,
+                expiration_date
+            FROM warehouse_batch_stocks
+            WHERE catalog_item_id = ANY($2)
+            ORDER BY warehouse_id, batch_number, quantity DESC
+            ON CONFLICT (warehouse_id, catalog_item_id, batch_number) DO NOTHING"#,
+        )
+        .bind(survivor_id)
+        .bind(duplicate_ids)
+        .execute(&mut **tx)
+        .await
+        .map_err(map_db_error)?;
+
+        sqlx::query(
+            r#"WITH d AS (
+                SELECT warehouse_id, batch_number,
+                    SUM(quantity) AS quantity,
+                    SUM(quantity * unit_cost) AS total_cost,
+                    MIN(expiration_date) AS expiration_date,
+                    BOOL_OR(is_quarantined) AS is_quarantined,
+                    (ARRAY_AGG(quarantine_reason) FILTER (WHERE is_quarantined))[1] AS quarantine_reason,
+                    (ARRAY_AGG(quarantined_at) FILTER (WHERE is_quarantined))[1] AS quarantined_at,
+                    (ARRAY_AGG(quarantined_by) FILTER (WHERE is_quarantined))[1] AS quarantined_by
+                FROM warehouse_batch_stocks
+                WHERE catalog_item_id = ANY($2)
+                GROUP BY warehouse_id, batch_number
+            )
+            UPDATE warehouse_batch_stocks s SET
+                unit_cost = CASE
+                    WHEN s.quantity + d.quantity > 0
+                    THEN ROUND((s.quantity * s.unit_cost + d.total_cost)
+                               / (s.quantity + d.quantity), 4)
+                    ELSE s.unit_cost
+                END,
+                quantity = s.quantity + d.quantity,
+                expiration_date = LEAST(s.expiration_date, d.expiration_date),
+                is_quarantined = s.is_quarantined OR d.is_quarantined,
+                quarantine_reason = CASE WHEN s.is_quarantined THEN s.quarantine_reason ELSE d.quarantine_reason END,
+                quarantined_at = CASE WHEN s.is_quarantined THEN s.quarantined_at ELSE d.quarantined_at END,
+                quarantined_by = CASE WHEN s.is_quarantined THEN s.quarantined_by ELSE d.quarantined_by END
+            FROM d
+            WHERE s.catalog_item_id = $1
+              AND s.warehouse_id = d.warehouse_id
+              AND s.batch_number = d.batch_number"#,
+        )
+        .bind(survivor_id)
+        .bind(duplicate_ids)
+        .execute(&mut **tx)
+        .await
+        .map_err(map_db_error)?;
+
+        sqlx::query("DELETE FROM warehouse_batch_stocks WHERE catalog_item_id = ANY($1)")
+            .bind(duplicate_ids)
+            .execute(&mut **tx)
+            .await
+            .map_err(map_db_error)?;
+
+        Ok(combined)
+    }
+}
+
+#[async_trait]
+impl CatalogMergeRepositoryPort for CatalogMergeRepository {
+    async fn find_duplicate_pairs(
+        &self,
+        filter: &CatalogDuplicateFilter,
+        limit: i64,
+    ) -> Result<Vec<CatalogDuplicatePair>, RepositoryError> {
+        let mut tx = self.pool.begin().await.map_err(map_db_error)?;
+
+        // `%` usa o limiar da sessão; assim o índice trigram já filtra pela similaridade pedida
+        sqlx::query("SELECT set_config('pg_trgm.similarity_threshold', $1::TEXT, TRUE)")
+            .bind(filter.min_similarity.to_string())
+            .execute(&mut *tx)
+            .await
+            .map_err(map_db_error)?;
+
+        let rows = sqlx::query(
+            r#"SELECT a.id AS item_id, b.id AS other_id,
+                similarity(catalog_unaccent(lower(a.description)),
+                           catalog_unaccent(lower(b.description))) AS similarity
+            FROM catmat_items a
+            JOIN catmat_items b
+              ON a.id < b.id
+             AND catalog_unaccent(lower(a.description)) % catalog_unaccent(lower(b.description))
+            WHERE a.merged_into_id IS NULL AND b.merged_into_id IS NULL
+              AND ($1 OR (a.is_active AND b.is_active))
+              AND (NOT $2 OR a.pdm_id = b.pdm_id)
+              AND (NOT $3 OR a.unit_of_measure_id = b.unit_of_measure_id)
+              AND ($4::UUID IS NULL OR a.pdm_id = $4 OR b.pdm_id = $4)
+            ORDER BY similarity DESC, a.id, b.id
+            LIMIT $5"#,
+        )
+        .bind(filter.include_inactive)
+        .bind(filter.same_pdm)
+        .bind(filter.same_unit)
+        .bind(filter.pdm_id)
+        .bind(limit)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(map_db_error)?;
+
+        tx.commit().await.map_err(map_db_error)?;
+
+        Ok(rows
+            .iter()
+            .map(|r| CatalogDuplicatePair {
+                item_id: r.get("item_id"),
+                other_id: r.get("other_id"),
+                similarity: r.get("similarity"),
+            })
+            .collect())
+    }
+
+    async fn find_candidates(
+        &self,
+        ids: &[Uuid],
+    ) -> Result<Vec<CatalogDuplicateCandidate>, RepositoryError> {
+        let rows = sqlx::query(
+            r#"SELECT i.id, i.code, i.description, i.pdm_id,
+                p.code AS pdm_code, p.description AS pdm_description,
+                i.unit_of_measure_id, u.symbol AS unit_symbol,
+                i.verification_status, i.is_active, i.created_at,
+                COALESCE((SELECT SUM(ws.quantity) FROM warehouse_stocks ws
+                          WHERE ws.catalog_item_id = i.id), 0) AS stock_quantity,
+                (SELECT COUNT(*) FROM stock_movements sm
+                 WHERE sm.catalog_item_id = i.id) AS movement_count
+            FROM catmat_items i
+            JOIN catmat_pdms p ON i.pdm_id = p.id
+            JOIN units_of_measure u ON i.unit_of_measure_id = u.id
+            WHERE i.id = ANY($1)"#,
+        )
+        .bind(ids)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        Ok(rows
+            .iter()
+            .map(|r| CatalogDuplicateCandidate {
+                id: r.get("id"),
+                code: r.get("code"),
+                description: r.get("description"),
+                pdm_id: r.get("pdm_id"),
+                pdm_code: r.get("pdm_code"),
+                pdm_description: r.get("pdm_description"),
+                unit_of_measure_id: r.get("unit_of_measure_id"),
+                unit_symbol: r.get("unit_symbol"),
+                verification_status: r.get("verification_status"),
+                is_active: r.get("is_active"),
+                stock_quantity: r.get("stock_quantity"),
+                movement_count: r.get("movement_count"),
+                created_at: r.get("created_at"),
+            })
+            .collect())
+    }
+
+    async fn merge_items(
+        &self,
+        survivor_id: Uuid,
+        duplicate_ids: &[Uuid],
+        reason: Option<&str>,
+        merged_by: Option<Uuid>,
+    ) -> Result<CatalogItemMergeResult, RepositoryError> {
+        let mut all_ids = vec![survivor_id];
+        all_ids.extend_from_slice(duplicate_ids);
+
+        let mut tx = begin_audited(&self.pool).await?;
+
+        let locked: Vec<(Uuid, Uuid, Option<Uuid>)> = sqlx::query_as(
+            r#"SELECT id, unit_of_measure_id, merged_into_id FROM catmat_items
+            WHERE id = ANY($1) ORDER BY id FOR UPDATE"#,
+        )
+        .bind(&all_ids)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(map_db_error)?;
+        if locked.len() != all_ids.len() {
+            return Err(RepositoryError::NotFound);
+        }
+        if locked.iter().any(|(_, _, merged_into)| merged_into.is_some()) {
+            return Err(RepositoryError::InvalidData(
+                "Item já fundido em outro não pode participar de nova fusão".to_string(),
+            ));
+        }
+        // Saldos e movimentos são somados como estão: só itens na mesma unidade
+        if locked.iter().any(|(_, unit, _)| *unit != locked[0].1) {
+            return Err(RepositoryError::InvalidData(
+                "Itens com unidades de medida diferentes não podem ser fundidos".to_string(),
+            ));
+        }
+
+        for (table, parent, label) in ONE_PER_PARENT {
+            let shared: Option<Uuid> = sqlx::query_scalar(&format!(
+                r#"SELECT {parent} FROM {table} WHERE catalog_item_id = ANY($1)
+                GROUP BY {parent} HAVING COUNT(*) > 1 LIMIT 1"#,
+            ))
+            .bind(&all_ids)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(map_db_error)?;
+            if let Some(parent_id) = shared {
+                return Err(RepositoryError::Duplicate(format!(
+                    "{} {} contém mais de um dos itens a fundir; ajuste-a antes da fusão",
+                    label, parent_id
+                )));
+            }
+        }
+
+        let combined_stocks = Self::combine_stocks(&mut tx, survivor_id, duplicate_ids).await?;
+        let combined_batches = Self::combine_batches(&mut tx, survivor_id, duplicate_ids).await?;
+
+        let mut references = Vec::with_capacity(REPOINTED.len());
+        for (table, column) in REPOINTED {
+            let rows = sqlx::query(&format!(
+                "UPDATE {table} SET {column} = $1 WHERE {column} = ANY($2)"
+            ))
+            .bind(survivor_id)
+            .bind(duplicate_ids)
+            .execute(&mut *tx)
+            .await
+            .map_err(map_db_error)?
+            .rows_affected() as i64;
+            if rows > 0 {
+                references.push(CatalogMergeReference {
+                    table: table.to_string(),
+                    rows,
+                });
+            }
+        }
+
+        // Redirecionamentos para os duplicados passam a apontar direto para o sobrevivente
+        sqlx::query(
+            "UPDATE catalog_item_redirects SET new_item_id = $1 WHERE new_item_id = ANY($2)",
+        )
+        .bind(survivor_id)
+        .bind(duplicate_ids)
+        .execute(&mut *tx)
+        .await
+        .map_err(map_db_error)?;
+
+        sqlx::query(
+            r#"INSERT INTO catalog_item_redirects
+                (old_item_id, new_item_id, old_code, old_description, reason, merged_by)
+            SELECT id, $1, code, description, $3, $4
+            FROM catmat_items WHERE id = ANY($2)"#,
+        )
+        .bind(survivor_id)
+        .bind(duplicate_ids)
+        .bind(reason)
+        .bind(merged_by)
+        .execute(&mut *tx)
+        .await
+        .map_err(map_db_error)?;
+
+        // O item fundido permanece, inativo, ocupando o código oficial
+        sqlx::query(
+            "UPDATE catmat_items SET is_active = FALSE, merged_into_id = $1 WHERE id = ANY($2)",
+        )
+        .bind(survivor_id)
+        .bind(duplicate_ids)
+        .execute(&mut *tx)
+        .await
+        .map_err(map_db_error)?;
+
+        tx.commit().await.map_err(map_db_error)?;
+
+        Ok(CatalogItemMergeResult {
+            survivor_id,
+            merged_item_ids: duplicate_ids.to_vec(),
+            combined_stocks,
+            combined_batches,
+            references,
+        })
+    }
+
+    async fn find_redirect(
+        &self,
+        old_item_id: Uuid,
+    ) -> Result<Option<CatalogItemRedirectDto>, RepositoryError> {
+        sqlx::query_as::<_, CatalogItemRedirectDto>(&format!(
+            "SELECT {REDIRECT_COLUMNS} FROM catalog_item_redirects WHERE old_item_id = $1"
+        ))
+        .bind(old_item_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn list_redirects(
+        &self,
+        new_item_id: Option<Uuid>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<CatalogItemRedirectDto>, i64), RepositoryError> {
+        let items = sqlx::query_as::<_, CatalogItemRedirectDto>(&format!(
+            r#"SELECT {REDIRECT_COLUMNS} FROM catalog_item_redirects
+            WHERE ($1::UUID IS NULL OR new_item_id = $1)
+            ORDER BY merged_at DESC, old_code
+            LIMIT $2 OFFSET $3"#
+        ))
+        .bind(new_item_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        let total: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM catalog_item_redirects WHERE ($1::UUID IS NULL OR new_item_id = $1)",
+        )
+        .bind(new_item_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        Ok((items, total))
+    }
+}
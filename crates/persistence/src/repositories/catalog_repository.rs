@@ -7,7 +7,7 @@ use async_trait::async_trait;
 use sqlx::{PgPool, Row};
 use uuid::Uuid;
 
-use crate::db_utils::map_db_error;
+use crate::db_utils::{begin_audited, map_db_error};
 
 // ============================
 // Unit of Measure Repository
@@ -818,7 +818,8 @@ impl CatmatItemRepositoryPort for CatmatItemRepository {
         code: &str, description: &str,
         is_sustainable: bool, code_ncm: Option<&str>, is_active: bool, verification_status: &str,
     ) -> Result<CatmatItemDto, RepositoryError> {
-        sqlx::query_as::<_, CatmatItemDto>(
+        let mut tx = begin_audited(&self.pool).await?;
+        let saved = sqlx::query_as::<_, CatmatItemDto>(
             r#"INSERT INTO catmat_items (pdm_id, unit_of_measure_id, budget_classification_id, code, description,
                 is_sustainable, code_ncm, is_active, verification_status)
             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9) RETURNING *"#,
@@ -832,9 +833,12 @@ impl CatmatItemRepositoryPort for CatmatItemRepository {
         .bind(code_ncm)
         .bind(is_active)
         .bind(verification_status)
-        .fetch_one(&self.pool)
+        .fetch_one(&mut *tx)
         .await
-        .map_err(map_db_error)
+        .map_err(map_db_error)?;
+
+        tx.commit().await.map_err(map_db_error)?;
+        Ok(saved)
     }
 
     async fn update(
@@ -885,29 +889,37 @@ impl CatmatItemRepositoryPort for CatmatItemRepository {
         builder.push_bind(id);
         builder.push(" RETURNING *");
 
-        builder
+        let mut tx = begin_audited(&self.pool).await?;
+        let saved = builder
             .build_query_as::<CatmatItemDto>()
-            .fetch_one(&self.pool)
+            .fetch_one(&mut *tx)
             .await
-            .map_err(map_db_error)
+            .map_err(map_db_error)?;
+
+        tx.commit().await.map_err(map_db_error)?;
+        Ok(saved)
     }
 
     async fn update_verification_status(&self, id: Uuid, verification_status: &str) -> Result<(), RepositoryError> {
+        let mut tx = begin_audited(&self.pool).await?;
         sqlx::query("UPDATE catmat_items SET verification_status = $2, updated_at = NOW() WHERE id = $1")
             .bind(id)
             .bind(verification_status)
-            .execute(&self.pool)
+            .execute(&mut *tx)
             .await
             .map_err(map_db_error)?;
+        tx.commit().await.map_err(map_db_error)?;
         Ok(())
     }
 
     async fn delete(&self, id: Uuid) -> Result<bool, RepositoryError> {
+        let mut tx = begin_audited(&self.pool).await?;
         let result = sqlx::query("DELETE FROM catmat_items WHERE id = $1")
             .bind(id)
-            .execute(&self.pool)
+            .execute(&mut *tx)
             .await
             .map_err(map_db_error)?;
+        tx.commit().await.map_err(map_db_error)?;
         Ok(result.rows_affected() > 0)
     }
 
@@ -1463,7 +1475,8 @@ impl CatserItemRepositoryPort for CatserItemRepository {
         supplementary_description: Option<&str>, specification: Option<&str>,
         search_links: Option<&str>, is_active: bool, verification_status: &str,
     ) -> Result<CatserItemDto, RepositoryError> {
-        sqlx::query_as::<_, CatserItemDto>(
+        let mut tx = begin_audited(&self.pool).await?;
+        let saved = sqlx::query_as::<_, CatserItemDto>(
             r#"INSERT INTO catser_items (class_id, unit_of_measure_id, budget_classification_id, code, code_cpc, description,
                 supplementary_description, specification, search_links, is_active, verification_status)
             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11) RETURNING *"#,
@@ -1479,9 +1492,12 @@ impl CatserItemRepositoryPort for CatserItemRepository {
         .bind(search_links)
         .bind(is_active)
         .bind(verification_status)
-        .fetch_one(&self.pool)
+        .fetch_one(&mut *tx)
         .await
-        .map_err(map_db_error)
+        .map_err(map_db_error)?;
+
+        tx.commit().await.map_err(map_db_error)?;
+        Ok(saved)
     }
 
     async fn update(
@@ -1491,7 +1507,8 @@ impl CatserItemRepositoryPort for CatserItemRepository {
         supplementary_description: Option<&str>,
         specification: Option<&str>, search_links: Option<&str>, is_active: Option<bool>,
     ) -> Result<CatserItemDto, RepositoryError> {
-        sqlx::query_as::<_, CatserItemDto>(
+        let mut tx = begin_audited(&self.pool).await?;
+        let saved = sqlx::query_as::<_, CatserItemDto>(
             r#"UPDATE catser_items SET
                 class_id = COALESCE($2, class_id), unit_of_measure_id = COALESCE($3, unit_of_measure_id),
                 budget_classification_id = CASE WHEN $4::UUID IS NOT NULL THEN $4 ELSE budget_classification_id END,
@@ -1515,27 +1532,34 @@ impl CatserItemRepositoryPort for CatserItemRepository {
         .bind(specification)
         .bind(search_links)
         .bind(is_active)
-        .fetch_one(&self.pool)
+        .fetch_one(&mut *tx)
         .await
-        .map_err(map_db_error)
+        .map_err(map_db_error)?;
+
+        tx.commit().await.map_err(map_db_error)?;
+        Ok(saved)
     }
 
     async fn update_verification_status(&self, id: Uuid, verification_status: &str) -> Result<(), RepositoryError> {
+        let mut tx = begin_audited(&self.pool).await?;
         sqlx::query("UPDATE catser_items SET verification_status = $2, updated_at = NOW() WHERE id = $1")
             .bind(id)
             .bind(verification_status)
-            .execute(&self.pool)
+            .execute(&mut *tx)
             .await
             .map_err(map_db_error)?;
+        tx.commit().await.map_err(map_db_error)?;
         Ok(())
     }
 
     async fn delete(&self, id: Uuid) -> Result<bool, RepositoryError> {
+        let mut tx = begin_audited(&self.pool).await?;
         let result = sqlx::query("DELETE FROM catser_items WHERE id = $1")
             .bind(id)
-            .execute(&self.pool)
+            .execute(&mut *tx)
             .await
             .map_err(map_db_error)?;
+        tx.commit().await.map_err(map_db_error)?;
         Ok(result.rows_affected() > 0)
     }
 
@@ -0,0 +1,318 @@
+use async_trait::async_trait;
+use domain::errors::RepositoryError;
+use domain::models::{
+    CatalogKind, CatalogMatchKind, CatalogSuggestion, CatmatItemSearchFilter, CatmatItemSearchHit,
+    CatmatItemWithDetailsDto, CatserItemSearchFilter, CatserItemSearchHit,
+    CatserItemWithDetailsDto,
+};
+use domain::ports::CatalogSearchRepositoryPort;
+use sqlx::{postgres::PgRow, PgPool, Row};
+
+use crate::db_utils::map_db_error;
+
+const HEADLINE_OPTIONS: &str = "StartSel=<mark>, StopSel=</mark>, HighlightAll=true";
+
+/// Match condition, rank and highlight expressions of a search over `i`.
+/// The vector and trigram expressions must match the indexes of the
+/// `catalog_search` migration.
+struct MatchSql {
+    condition: String,
+    rank: String,
+    highlight: String,
+}
+
+fn match_sql(kind: CatalogMatchKind, vector: &str) -> MatchSql {
+    match kind {
+        CatalogMatchKind::FullText => {
+            let query = "websearch_to_tsquery('portuguese_unaccent', $1)";
+            MatchSql {
+                condition: format!("{vector} @@ {query}"),
+                rank: format!("ts_rank_cd({vector}, {query})"),
+                highlight: format!(
+                    "ts_headline('portuguese_unaccent', i.description, {query}, '{HEADLINE_OPTIONS}')"
+                ),
+            }
+        }
+        CatalogMatchKind::Fuzzy => {
+            let (query, text) = (
+                "catalog_unaccent(lower($1))",
+                "catalog_unaccent(lower(i.description))",
+            );
+            MatchSql {
+                condition: format!("{query} <% {text}"),
+                rank: format!("word_similarity({query}, {text})"),
+                highlight: "i.description".to_string(),
+            }
+        }
+    }
+}
+
+fn catmat_hit(r: &PgRow, kind: CatalogMatchKind) -> CatmatItemSearchHit {
+    CatmatItemSearchHit {
+        item: CatmatItemWithDetailsDto {
+            id: r.get("id"),
+            pdm_id: r.get("pdm_id"),
+            pdm_description: r.get("pdm_description"),
+            pdm_code: r.get("pdm_code"),
+            pdm_material_classification: r.get("pdm_material_classification"),
+            class_id: r.get("class_id"),
+            class_name: r.get("class_name"),
+            class_code: r.get("class_code"),
+            group_id: r.get("group_id"),
+            group_name: r.get("group_name"),
+            group_code: r.get("group_code"),
+            unit_of_measure_id: r.get("unit_of_measure_id"),
+            unit_name: r.get("unit_name"),
+            unit_symbol: r.get("unit_symbol"),
+            budget_classification_id: r.get("budget_classification_id"),
+            budget_classification_name: r.try_get("bc_name").ok().flatten(),
+            budget_classification_full_code: r.try_get("bc_full_code").ok().flatten(),
+            code: r.get("code"),
+            description: r.get("description"),
+            is_sustainable: r.get("is_sustainable"),
+            code_ncm: r.get("code_ncm"),
+            is_active: r.get("is_active"),
+            verification_status: r.get("verification_status"),
+            created_at: r.get("created_at"),
+            updated_at: r.get("updated_at"),
+        },
+        rank: r.get("rank"),
+        highlight: r.get("highlight"),
+        match_kind: kind,
+    }
+}
+
+fn catser_hit(r: &PgRow, kind: CatalogMatchKind) -> CatserItemSearchHit {
+    CatserItemSearchHit {
+        item: CatserItemWithDetailsDto {
+            id: r.get("id"),
+            class_id: r.get("class_id"),
+            class_name: r.get("class_name"),
+            class_code: r.get("class_code"),
+            group_id: r.get("group_id"),
+            group_name: r.get("group_name"),
+            group_code: r.get("group_code"),
+            unit_of_measure_id: r.get("unit_of_measure_id"),
+            unit_name: r.get("unit_name"),
+            unit_symbol: r.get("unit_symbol"),
+            budget_classification_id: r.get("budget_classification_id"),
+            budget_classification_name: r.try_get("bc_name").ok().flatten(),
+            budget_classification_full_code: r.try_get("bc_full_code").ok().flatten(),
+            code: r.get("code"),
+            code_cpc: r.get("code_cpc"),
+            description: r.get("description"),
+            supplementary_description: r.get("supplementary_description"),
+            specification: r.get("specification"),
+            search_links: r.get("search_links"),
+            is_active: r.get("is_active"),
+            verification_status: r.get("verification_status"),
+            created_at: r.get("created_at"),
+            updated_at: r.get("updated_at"),
+        },
+        rank: r.get("rank"),
+        highlight: r.get("highlight"),
+        match_kind: kind,
+    }
+}
+
+pub struct CatalogSearchRepository {
+    pool: PgPool,
+}
+
+impl CatalogSearchRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl CatalogSearchRepositoryPort for CatalogSearchRepository {
+    async fn search_catmat_items(
+        &self,
+        query: &str,
+        kind: CatalogMatchKind,
+        filter: &CatmatItemSearchFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<CatmatItemSearchHit>, i64), RepositoryError> {
+        let m = match_sql(kind, "catmat_item_search_vector(i.code, i.description)");
+        let filters = r#"
+              AND ($2::material_classification_enum IS NULL OR p.material_classification = $2)
+              AND ($3::TEXT IS NULL OR i.code_ncm = $3)
+              AND ($4::BOOLEAN IS NULL OR i.is_sustainable = $4)
+              AND ($5::UUID IS NULL OR i.budget_classification_id = $5)
+              AND ($6::BOOLEAN IS NULL OR i.is_active = $6)"#;
+
+        let sql = format!(
+            r#"SELECT i.*, p.description AS pdm_description, p.code AS pdm_code,
+                p.material_classification AS pdm_material_classification,
+                cc.id AS class_id, cc.name AS class_name, cc.code AS class_code,
+                cg.id AS group_id, cg.name AS group_name, cg.code AS group_code,
+                u.name AS unit_name, u.symbol AS unit_symbol,
+                bc.name AS bc_name, bc.full_code AS bc_full_code,
+                {rank} AS rank, {highlight} AS highlight
+            FROM catmat_items i
+            JOIN catmat_pdms p ON i.pdm_id = p.id
+            JOIN catmat_classes cc ON p.class_id = cc.id
+            JOIN catmat_groups cg ON cc.group_id = cg.id
+            JOIN units_of_measure u ON i.unit_of_measure_id = u.id
+            LEFT JOIN budget_classifications bc ON i.budget_classification_id = bc.id
+            WHERE {condition}{filters}
+            ORDER BY rank DESC, i.code LIMIT $7 OFFSET $8"#,
+            rank = m.rank,
+            highlight = m.highlight,
+            condition = m.condition,
+        );
+        let records = sqlx::query(&sql)
+            .bind(query)
+            .bind(filter.material_classification.clone())
+            .bind(filter.code_ncm.as_deref())
+            .bind(filter.is_sustainable)
+            .bind(filter.budget_classification_id)
+            .bind(filter.is_active)
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(map_db_error)?;
+
+        let count_sql = format!(
+            r#"SELECT COUNT(*) FROM catmat_items i
+            JOIN catmat_pdms p ON i.pdm_id = p.id
+            WHERE {condition}{filters}"#,
+            condition = m.condition,
+        );
+        let total: i64 = sqlx::query_scalar(&count_sql)
+            .bind(query)
+            .bind(filter.material_classification.clone())
+            .bind(filter.code_ncm.as_deref())
+            .bind(filter.is_sustainable)
+            .bind(filter.budget_classification_id)
+            .bind(filter.is_active)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(map_db_error)?;
+
+        Ok((records.iter().map(|r| catmat_hit(r, kind)).collect(), total))
+    }
+
+    async fn search_catser_items(
+        &self,
+        query: &str,
+        kind: CatalogMatchKind,
+        filter: &CatserItemSearchFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<CatserItemSearchHit>, i64), RepositoryError> {
+        let m = match_sql(
+            kind,
+            "catser_item_search_vector(i.code, i.description, i.supplementary_description, i.specification)",
+        );
+        let filters = r#"
+              AND ($2::TEXT IS NULL OR i.code_cpc = $2)
+              AND ($3::UUID IS NULL OR i.budget_classification_id = $3)
+              AND ($4::BOOLEAN IS NULL OR i.is_active = $4)"#;
+
+        let sql = format!(
+            r#"SELECT i.*, c.name AS class_name, c.code AS class_code,
+                g.id AS group_id, g.name AS group_name, g.code AS group_code,
+                u.name AS unit_name, u.symbol AS unit_symbol,
+                bc.name AS bc_name, bc.full_code AS bc_full_code,
+                {rank} AS rank, {highlight} AS highlight
+            FROM catser_items i
+            JOIN catser_classes c ON i.class_id = c.id
+            JOIN catser_groups g ON c.group_id = g.id
+            JOIN units_of_measure u ON i.unit_of_measure_id = u.id
+            LEFT JOIN budget_classifications bc ON i.budget_classification_id = bc.id
+            WHERE {condition}{filters}
+            ORDER BY rank DESC, i.code LIMIT $5 OFFSET $6"#,
+            rank = m.rank,
+            highlight = m.highlight,
+            condition = m.condition,
+        );
+        let records = sqlx::query(&sql)
+            .bind(query)
+            .bind(filter.code_cpc.as_deref())
+            .bind(filter.budget_classification_id)
+            .bind(filter.is_active)
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(map_db_error)?;
+
+        let count_sql = format!(
+            "SELECT COUNT(*) FROM catser_items i WHERE {condition}{filters}",
+            condition = m.condition,
+        );
+        let total: i64 = sqlx::query_scalar(&count_sql)
+            .bind(query)
+            .bind(filter.code_cpc.as_deref())
+            .bind(filter.budget_classification_id)
+            .bind(filter.is_active)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(map_db_error)?;
+
+        Ok((records.iter().map(|r| catser_hit(r, kind)).collect(), total))
+    }
+
+    async fn suggest(
+        &self,
+        prefix_query: &str,
+        code_prefix: Option<&str>,
+        catalog: Option<CatalogKind>,
+        limit: i64,
+    ) -> Result<Vec<CatalogSuggestion>, RepositoryError> {
+        // Each catalog is cut to `limit` before merging so the ranking only
+        // runs over the index matches of one side at a time
+        let sql = format!(
+            r#"WITH q AS (SELECT to_tsquery('portuguese_unaccent', $1) AS query)
+            SELECT s.catalog, s.id, s.code, s.description,
+                ts_headline('portuguese_unaccent', s.description, q.query, '{HEADLINE_OPTIONS}') AS highlight
+            FROM (
+                (SELECT 'CATMAT'::catalog_kind_enum AS catalog, i.id, i.code, i.description,
+                    ts_rank_cd(catmat_item_search_vector(i.code, i.description), q.query)
+                        + CASE WHEN i.code LIKE $2::TEXT || '%' THEN 1 ELSE 0 END AS rank
+                FROM catmat_items i, q
+                WHERE i.is_active
+                  AND ($3::catalog_kind_enum IS NULL OR $3 = 'CATMAT')
+                  AND (catmat_item_search_vector(i.code, i.description) @@ q.query
+                       OR i.code LIKE $2::TEXT || '%')
+                ORDER BY rank DESC, i.code LIMIT $4)
+                UNION ALL
+                (SELECT 'CATSER'::catalog_kind_enum AS catalog, i.id, i.code, i.description,
+                    ts_rank_cd(catser_item_search_vector(i.code, i.description, i.supplementary_description, i.specification), q.query)
+                        + CASE WHEN i.code LIKE $2::TEXT || '%' THEN 1 ELSE 0 END AS rank
+                FROM catser_items i, q
+                WHERE i.is_active
+                  AND ($3::catalog_kind_enum IS NULL OR $3 = 'CATSER')
+                  AND (catser_item_search_vector(i.code, i.description, i.supplementary_description, i.specification) @@ q.query
+                       OR i.code LIKE $2::TEXT || '%')
+                ORDER BY rank DESC, i.code LIMIT $4)
+            ) s, q
+            ORDER BY s.rank DESC, s.code
+            LIMIT $4"#,
+        );
+
+        let records = sqlx::query(&sql)
+            .bind(prefix_query)
+            .bind(code_prefix)
+            .bind(catalog)
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(map_db_error)?;
+
+        Ok(records
+            .into_iter()
+            .map(|r| CatalogSuggestion {
+                catalog: r.get("catalog"),
+                id: r.get("id"),
+                code: r.get("code"),
+                description: r.get("description"),
+                highlight: r.get("highlight"),
+            })
+            .collect())
+    }
+}
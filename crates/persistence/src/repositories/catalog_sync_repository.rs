@@ -0,0 +1,442 @@
+use async_trait::async_trait;
+use domain::errors::RepositoryError;
+use domain::models::{
+    CatalogKind, CatalogLevel, CatalogNode, CatalogNodeFilter, CatalogSyncPlan, CatalogSyncReport,
+    CatalogSyncRunDto, CatalogSyncScope, CatalogSyncStatus,
+};
+use domain::ports::CatalogSyncRepositoryPort;
+use sqlx::{types::Json, PgPool, Postgres, Row, Transaction};
+use uuid::Uuid;
+
+use crate::db_utils::{begin_audited, map_db_error};
+
+/// Upserts are sent in batches of this many rows
+const UPSERT_CHUNK: usize = 1000;
+
+const RUN_COLUMNS: &str = r#"id, catalog, scope, scope_code, dry_run, status,
+    added_count, changed_count, deactivated_count, report, error,
+    triggered_by, started_at, finished_at"#;
+
+/// Table backing a level of a catalog
+struct LevelTable {
+    table: &'static str,
+    /// `name`, or `description` for PDMs and items
+    name_column: &'static str,
+    /// Parent table and foreign key column
+    parent: Option<(&'static str, &'static str)>,
+    /// Whether the parent may be absent (CATSER groups without a division)
+    parent_optional: bool,
+}
+
+fn level_table(catalog: CatalogKind, level: CatalogLevel) -> Result<LevelTable, RepositoryError> {
+    use CatalogLevel::*;
+
+    let (table, name_column, parent, parent_optional) = match (catalog, level) {
+        (CatalogKind::Catmat, Group) => ("catmat_groups", "name", None, false),
+        (CatalogKind::Catmat, Class) => (
+            "catmat_classes",
+            "name",
+            Some(("catmat_groups", "group_id")),
+            false,
+        ),
+        (CatalogKind::Catmat, Pdm) => (
+            "catmat_pdms",
+            "description",
+            Some(("catmat_classes", "class_id")),
+            false,
+        ),
+        (CatalogKind::Catmat, Item) => (
+            "catmat_items",
+            "description",
+            Some(("catmat_pdms", "pdm_id")),
+            false,
+        ),
+        (CatalogKind::Catser, Section) => ("catser_sections", "name", None, false),
+        (CatalogKind::Catser, Division) => (
+            "catser_divisions",
+            "name",
+            Some(("catser_sections", "section_id")),
+            false,
+        ),
+        (CatalogKind::Catser, Group) => (
+            "catser_groups",
+            "name",
+            Some(("catser_divisions", "division_id")),
+            true,
+        ),
+        (CatalogKind::Catser, Class) => (
+            "catser_classes",
+            "name",
+            Some(("catser_groups", "group_id")),
+            false,
+        ),
+        (CatalogKind::Catser, Item) => (
+            "catser_items",
+            "description",
+            Some(("catser_classes", "class_id")),
+            false,
+        ),
+        _ => {
+            return Err(RepositoryError::InvalidData(format!(
+                "Nível {:?} não existe no {:?}",
+                level, catalog
+            )))
+        }
+    };
+
+    Ok(LevelTable {
+        table,
+        name_column,
+        parent,
+        parent_optional,
+    })
+}
+
+pub struct CatalogSyncRepository {
+    pool: PgPool,
+}
+
+impl CatalogSyncRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Sections and divisions created by hand have no code: the first one
+    /// with the same name takes the official code instead of being duplicated
+    async fn adopt_uncoded(
+        tx: &mut Transaction<'_, Postgres>,
+        table: &str,
+        nodes: &[&CatalogNode],
+    ) -> Result<(), RepositoryError> {
+        let codes: Vec<&str> = nodes.iter().map(|n| n.code.as_str()).collect();
+        let names: Vec<&str> = nodes.iter().map(|n| n.name.as_str()).collect();
+
+        sqlx::query(&format!(
+            r#"UPDATE {table} t SET code = n.code
+            FROM UNNEST($1::text[], $2::text[]) AS n(code, name)
+            WHERE t.id = (
+                SELECT u.id FROM {table} u
+                WHERE u.code IS NULL AND u.name = n.name
+                ORDER BY u.created_at LIMIT 1
+            )
+            AND NOT EXISTS (SELECT 1 FROM {table} c WHERE c.code = n.code)"#,
+        ))
+        .bind(&codes)
+        .bind(&names)
+        .execute(&mut **tx)
+        .await
+        .map_err(map_db_error)?;
+
+        Ok(())
+    }
+
+    async fn upsert_level(
+        tx: &mut Transaction<'_, Postgres>,
+        plan: &CatalogSyncPlan,
+        level: CatalogLevel,
+        nodes: &[&CatalogNode],
+    ) -> Result<(), RepositoryError> {
+        let lt = level_table(plan.catalog, level)?;
+        let name = lt.name_column;
+
+        if lt.table == "catser_sections" || lt.table == "catser_divisions" {
+            Self::adopt_uncoded(tx, lt.table, nodes).await?;
+        }
+
+        let mut columns = format!("code, {name}, is_active, verification_status");
+        let mut values = "n.code, n.name, n.is_active, 'verified'".to_string();
+        let mut updates = format!(
+            "{name} = EXCLUDED.{name}, is_active = EXCLUDED.is_active, verification_status = 'verified'"
+        );
+        let mut from =
+            "UNNEST($1::text[], $2::text[], $3::text[], $4::bool[], $5::bool[], $6::text[])
+                AS n(code, parent_code, name, is_active, is_sustainable, code_cpc)"
+                .to_string();
+
+        if let Some((parent_table, fk)) = lt.parent {
+            columns.push_str(&format!(", {fk}"));
+            values.push_str(", p.id");
+            updates.push_str(&format!(", {fk} = EXCLUDED.{fk}"));
+            from.push_str(&format!(
+                " {} JOIN {parent_table} p ON p.code = n.parent_code",
+                if lt.parent_optional { "LEFT" } else { "" }
+            ));
+        }
+
+        let extra_bind = match (plan.catalog, level) {
+            (CatalogKind::Catmat, CatalogLevel::Pdm) => {
+                columns.push_str(", material_classification");
+                values.push_str(", $7");
+                true
+            }
+            (CatalogKind::Catmat, CatalogLevel::Item) => {
+                columns.push_str(", unit_of_measure_id, is_sustainable");
+                values.push_str(", $7, COALESCE(n.is_sustainable, FALSE)");
+                updates.push_str(", is_sustainable = EXCLUDED.is_sustainable");
+                true
+            }
+            (CatalogKind::Catser, CatalogLevel::Item) => {
+                columns.push_str(", unit_of_measure_id, code_cpc");
+                values.push_str(", $7, n.code_cpc");
+                updates.push_str(", code_cpc = EXCLUDED.code_cpc");
+                true
+            }
+            _ => false,
+        };
+
+        let sql = format!(
+            "INSERT INTO {table} ({columns}) SELECT {values} FROM {from}
+            ON CONFLICT (code) DO UPDATE SET {updates}",
+            table = lt.table,
+        );
+
+        for chunk in nodes.chunks(UPSERT_CHUNK) {
+            let codes: Vec<&str> = chunk.iter().map(|n| n.code.as_str()).collect();
+            let parents: Vec<Option<&str>> =
+                chunk.iter().map(|n| n.parent_code.as_deref()).collect();
+            let names: Vec<&str> = chunk.iter().map(|n| n.name.as_str()).collect();
+            let active: Vec<bool> = chunk.iter().map(|n| n.is_active).collect();
+            let sustainable: Vec<Option<bool>> = chunk.iter().map(|n| n.is_sustainable).collect();
+            let cpc: Vec<Option<&str>> = chunk.iter().map(|n| n.code_cpc.as_deref()).collect();
+
+            let mut query = sqlx::query(&sql)
+                .bind(&codes)
+                .bind(&parents)
+                .bind(&names)
+                .bind(&active)
+                .bind(&sustainable)
+                .bind(&cpc);
+            if extra_bind {
+                query = match level {
+                    CatalogLevel::Pdm => query.bind(plan.default_material_classification.clone()),
+                    _ => query.bind(plan.default_unit_id),
+                };
+            }
+
+            let result = query.execute(&mut **tx).await.map_err(map_db_error)?;
+            if result.rows_affected() < chunk.len() as u64 {
+                return Err(RepositoryError::InvalidData(format!(
+                    "{} registro(s) de {} sem o registro pai no catálogo local",
+                    chunk.len() as u64 - result.rows_affected(),
+                    lt.table
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl CatalogSyncRepositoryPort for CatalogSyncRepository {
+    async fn list_nodes(
+        &self,
+        catalog: CatalogKind,
+        level: CatalogLevel,
+        filter: &CatalogNodeFilter,
+    ) -> Result<Vec<CatalogNode>, RepositoryError> {
+        let lt = level_table(catalog, level)?;
+
+        let (parent_select, parent_join, parent_filter) = match lt.parent {
+            Some((parent_table, fk)) => (
+                "p.code",
+                format!("LEFT JOIN {parent_table} p ON p.id = t.{fk}"),
+                "p.code = ANY($2)",
+            ),
+            None => {
+                if matches!(filter, CatalogNodeFilter::ParentCodes(_)) {
+                    return Ok(Vec::new());
+                }
+                ("NULL::text", String::new(), "FALSE")
+            }
+        };
+        let (sustainable, merged) = if lt.table == "catmat_items" {
+            ("t.is_sustainable", "t.merged_into_id IS NOT NULL")
+        } else {
+            ("NULL::boolean", "FALSE")
+        };
+        let code_cpc = if lt.table == "catser_items" {
+            "t.code_cpc"
+        } else {
+            "NULL::text"
+        };
+
+        let (codes, parent_codes) = match filter {
+            CatalogNodeFilter::All => (None, None),
+            CatalogNodeFilter::Codes(codes) => (Some(codes), None),
+            CatalogNodeFilter::ParentCodes(codes) => (None, Some(codes)),
+        };
+
+        let rows = sqlx::query(&format!(
+            r#"SELECT t.code, {parent_select} AS parent_code, t.{name} AS name, t.is_active,
+                {sustainable} AS is_sustainable, {code_cpc} AS code_cpc, {merged} AS merged
+            FROM {table} t
+            {parent_join}
+            WHERE t.code IS NOT NULL
+              AND ($1::text[] IS NULL OR t.code = ANY($1))
+              AND ($2::text[] IS NULL OR {parent_filter})"#,
+            name = lt.name_column,
+            table = lt.table,
+        ))
+        .bind(codes)
+        .bind(parent_codes)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| CatalogNode {
+                level,
+                code: r.get("code"),
+                parent_code: r.get("parent_code"),
+                name: r.get("name"),
+                is_active: r.get("is_active"),
+                is_sustainable: r.get("is_sustainable"),
+                code_cpc: r.get("code_cpc"),
+                merged: r.get("merged"),
+            })
+            .collect())
+    }
+
+    async fn apply(&self, plan: &CatalogSyncPlan) -> Result<(), RepositoryError> {
+        use CatalogLevel::*;
+
+        let mut tx = begin_audited(&self.pool).await?;
+
+        for level in [Section, Division, Group, Class, Pdm, Item] {
+            let nodes: Vec<&CatalogNode> =
+                plan.upserts.iter().filter(|n| n.level == level).collect();
+            if !nodes.is_empty() {
+                Self::upsert_level(&mut tx, plan, level, &nodes).await?;
+            }
+
+            let codes: Vec<&str> = plan
+                .deactivations
+                .iter()
+                .filter(|(l, _)| *l == level)
+                .map(|(_, code)| code.as_str())
+                .collect();
+            if !codes.is_empty() {
+                let lt = level_table(plan.catalog, level)?;
+                sqlx::query(&format!(
+                    "UPDATE {} SET is_active = FALSE WHERE code = ANY($1) AND is_active",
+                    lt.table
+                ))
+                .bind(&codes)
+                .execute(&mut *tx)
+                .await
+                .map_err(map_db_error)?;
+            }
+        }
+
+        tx.commit().await.map_err(map_db_error)?;
+        Ok(())
+    }
+
+    async fn create_run(
+        &self,
+        catalog: CatalogKind,
+        scope: CatalogSyncScope,
+        scope_code: Option<&str>,
+        dry_run: bool,
+        triggered_by: Option<Uuid>,
+    ) -> Result<CatalogSyncRunDto, RepositoryError> {
+        sqlx::query_as::<_, CatalogSyncRunDto>(&format!(
+            r#"INSERT INTO catalog_sync_runs (catalog, scope, scope_code, dry_run, triggered_by)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING {RUN_COLUMNS}"#
+        ))
+        .bind(catalog)
+        .bind(scope)
+        .bind(scope_code)
+        .bind(dry_run)
+        .bind(triggered_by)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn finish_run(
+        &self,
+        id: Uuid,
+        status: CatalogSyncStatus,
+        report: Option<&CatalogSyncReport>,
+        error: Option<&str>,
+    ) -> Result<CatalogSyncRunDto, RepositoryError> {
+        let count = |f: fn(&CatalogSyncReport) -> usize| report.map(f).unwrap_or(0) as i32;
+
+        sqlx::query_as::<_, CatalogSyncRunDto>(&format!(
+            r#"UPDATE catalog_sync_runs
+            SET status = $2, report = $3, error = $4,
+                added_count = $5, changed_count = $6, deactivated_count = $7,
+                finished_at = NOW()
+            WHERE id = $1
+            RETURNING {RUN_COLUMNS}"#
+        ))
+        .bind(id)
+        .bind(status)
+        .bind(report.map(Json))
+        .bind(error)
+        .bind(count(|r| r.added.len()))
+        .bind(count(|r| r.changed.len()))
+        .bind(count(|r| r.deactivated.len()))
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_db_error)?
+        .ok_or(RepositoryError::NotFound)
+    }
+
+    async fn fail_interrupted_runs(&self, error: &str) -> Result<u64, RepositoryError> {
+        let result = sqlx::query(
+            "UPDATE catalog_sync_runs SET status = 'FAILED', error = $1, finished_at = NOW() WHERE status = 'RUNNING'",
+        )
+        .bind(error)
+        .execute(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn find_run(&self, id: Uuid) -> Result<Option<CatalogSyncRunDto>, RepositoryError> {
+        sqlx::query_as::<_, CatalogSyncRunDto>(&format!(
+            "SELECT {RUN_COLUMNS} FROM catalog_sync_runs WHERE id = $1"
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn list_runs(
+        &self,
+        catalog: Option<CatalogKind>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<CatalogSyncRunDto>, i64), RepositoryError> {
+        let runs = sqlx::query_as::<_, CatalogSyncRunDto>(&format!(
+            r#"SELECT {} FROM catalog_sync_runs
+            WHERE ($1::catalog_kind_enum IS NULL OR catalog = $1)
+            ORDER BY started_at DESC
+            LIMIT $2 OFFSET $3"#,
+            RUN_COLUMNS.replace("report,", "NULL::jsonb AS report,")
+        ))
+        .bind(catalog)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        let total: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM catalog_sync_runs WHERE ($1::catalog_kind_enum IS NULL OR catalog = $1)",
+        )
+        .bind(catalog)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        Ok((runs, total))
+    }
+}
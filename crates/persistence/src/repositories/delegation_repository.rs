@@ -0,0 +1,156 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use domain::errors::RepositoryError;
+use domain::models::{ApprovalDelegationDto, DelegationScope};
+use domain::ports::ApprovalDelegationRepositoryPort;
+use sqlx::types::Json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::db_utils::map_db_error;
+
+const DELEGATION_SELECT: &str = r#"
+    SELECT d.id, d.delegator_id, dr.username AS delegator_username,
+           d.delegate_id, de.username AS delegate_username,
+           d.scopes, d.valid_from, d.valid_until, d.reason,
+           (d.revoked_at IS NULL AND d.valid_from <= NOW() AND d.valid_until > NOW()) AS is_active,
+           d.revoked_at, d.revoked_by, d.created_by, d.created_at
+    FROM approval_delegations d
+    LEFT JOIN users dr ON dr.id = d.delegator_id
+    LEFT JOIN users de ON de.id = d.delegate_id
+"#;
+
+/// Predicate for delegations in force right now
+const ACTIVE: &str =
+    "d.revoked_at IS NULL AND d.valid_from <= NOW() AND d.valid_until > NOW()";
+
+#[derive(Clone)]
+pub struct ApprovalDelegationRepository {
+    pool: PgPool,
+}
+
+impl ApprovalDelegationRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ApprovalDelegationRepositoryPort for ApprovalDelegationRepository {
+    #[allow(clippy::too_many_arguments)]
+    async fn create(
+        &self,
+        delegator_id: Uuid,
+        delegate_id: Uuid,
+        scopes: &[DelegationScope],
+        valid_from: DateTime<Utc>,
+        valid_until: DateTime<Utc>,
+        reason: Option<&str>,
+        created_by: Uuid,
+    ) -> Result<ApprovalDelegationDto, RepositoryError> {
+        let id: Uuid = sqlx::query_scalar(
+            r#"
+            INSERT INTO approval_delegations (
+                delegator_id, delegate_id, scopes, valid_from, valid_until, reason, created_by
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING id
+            "#,
+        )
+        .bind(delegator_id)
+        .bind(delegate_id)
+        .bind(Json(scopes))
+        .bind(valid_from)
+        .bind(valid_until)
+        .bind(reason)
+        .bind(created_by)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        self.find_by_id(id).await?.ok_or(RepositoryError::NotFound)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<ApprovalDelegationDto>, RepositoryError> {
+        sqlx::query_as::<_, ApprovalDelegationDto>(&format!("{} WHERE d.id = $1", DELEGATION_SELECT))
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(map_db_error)
+    }
+
+    async fn list_for_user(
+        &self,
+        user_id: Uuid,
+        active_only: bool,
+    ) -> Result<Vec<ApprovalDelegationDto>, RepositoryError> {
+        sqlx::query_as::<_, ApprovalDelegationDto>(&format!(
+            r#"{}
+            WHERE (d.delegator_id = $1 OR d.delegate_id = $1)
+              AND (NOT $2 OR ({}))
+            ORDER BY d.valid_from DESC"#,
+            DELEGATION_SELECT, ACTIVE
+        ))
+        .bind(user_id)
+        .bind(active_only)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn list_active_for_delegate(
+        &self,
+        delegate_id: Uuid,
+        scope: DelegationScope,
+    ) -> Result<Vec<ApprovalDelegationDto>, RepositoryError> {
+        sqlx::query_as::<_, ApprovalDelegationDto>(&format!(
+            r#"{}
+            WHERE d.delegate_id = $1 AND d.scopes ? $2 AND {}
+            ORDER BY d.valid_from"#,
+            DELEGATION_SELECT, ACTIVE
+        ))
+        .bind(delegate_id)
+        .bind(scope.as_str())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn find_active(
+        &self,
+        delegator_id: Uuid,
+        delegate_id: Uuid,
+        scope: DelegationScope,
+    ) -> Result<Option<ApprovalDelegationDto>, RepositoryError> {
+        sqlx::query_as::<_, ApprovalDelegationDto>(&format!(
+            r#"{}
+            WHERE d.delegator_id = $1 AND d.delegate_id = $2 AND d.scopes ? $3 AND {}
+            ORDER BY d.valid_from
+            LIMIT 1"#,
+            DELEGATION_SELECT, ACTIVE
+        ))
+        .bind(delegator_id)
+        .bind(delegate_id)
+        .bind(scope.as_str())
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn revoke(&self, id: Uuid, revoked_by: Uuid) -> Result<bool, RepositoryError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE approval_delegations
+            SET revoked_at = NOW(), revoked_by = $2
+            WHERE id = $1 AND revoked_at IS NULL
+            "#,
+        )
+        .bind(id)
+        .bind(revoked_by)
+        .execute(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
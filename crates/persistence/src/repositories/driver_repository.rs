@@ -8,7 +8,7 @@ use domain::{
 use sqlx::{PgPool, Row};
 use uuid::Uuid;
 
-use crate::db_utils::map_db_error;
+use crate::db_utils::{begin_audited, map_db_error};
 
 pub struct DriverRepository {
     pool: PgPool,
@@ -80,7 +80,8 @@ impl DriverRepositoryPort for DriverRepository {
         email: Option<&str>,
         created_by: Option<Uuid>,
     ) -> Result<DriverDto, RepositoryError> {
-        sqlx::query_as::<_, DriverDto>(
+        let mut tx = begin_audited(&self.pool).await?;
+        let saved = sqlx::query_as::<_, DriverDto>(
             r#"INSERT INTO drivers (driver_type, full_name, cpf, cnh_number, cnh_category,
                                     cnh_expiration, phone, email, created_by, updated_by)
                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $9)
@@ -95,9 +96,12 @@ impl DriverRepositoryPort for DriverRepository {
         .bind(phone)
         .bind(email)
         .bind(created_by)
-        .fetch_one(&self.pool)
+        .fetch_one(&mut *tx)
         .await
-        .map_err(map_db_error)
+        .map_err(map_db_error)?;
+
+        tx.commit().await.map_err(map_db_error)?;
+        Ok(saved)
     }
 
     async fn update(
@@ -114,7 +118,8 @@ impl DriverRepositoryPort for DriverRepository {
         is_active: Option<bool>,
         updated_by: Option<Uuid>,
     ) -> Result<DriverDto, RepositoryError> {
-        sqlx::query_as::<_, DriverDto>(
+        let mut tx = begin_audited(&self.pool).await?;
+        let saved = sqlx::query_as::<_, DriverDto>(
             r#"UPDATE drivers SET
                 driver_type = COALESCE($2, driver_type),
                 full_name = COALESCE($3, full_name),
@@ -140,17 +145,22 @@ impl DriverRepositoryPort for DriverRepository {
         .bind(email)
         .bind(is_active)
         .bind(updated_by)
-        .fetch_one(&self.pool)
+        .fetch_one(&mut *tx)
         .await
-        .map_err(map_db_error)
+        .map_err(map_db_error)?;
+
+        tx.commit().await.map_err(map_db_error)?;
+        Ok(saved)
     }
 
     async fn delete(&self, id: Uuid) -> Result<bool, RepositoryError> {
+        let mut tx = begin_audited(&self.pool).await?;
         let result = sqlx::query("DELETE FROM drivers WHERE id = $1")
             .bind(id)
-            .execute(&self.pool)
+            .execute(&mut *tx)
             .await
             .map_err(map_db_error)?;
+        tx.commit().await.map_err(map_db_error)?;
         Ok(result.rows_affected() > 0)
     }
 
@@ -0,0 +1,124 @@
+use async_trait::async_trait;
+use domain::errors::RepositoryError;
+use domain::models::{EntityChangeDto, HistoryEntity, NewEntityChange};
+use domain::ports::EntityHistoryRepositoryPort;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::db_utils::{begin_audited, map_db_error};
+
+const CHANGE_SELECT: &str = r#"
+    SELECT c.id, c.entity_type, c.entity_id, c.operation,
+           COALESCE(c.changed_fields, '{}') AS changed_fields,
+           c.diff, c.data_before, c.data_after,
+           c.performed_by, u.username AS performed_by_username, c.performed_at,
+           host(c.ip_address) AS ip_address, c.user_agent, c.request_id
+    FROM entity_changelog c
+    LEFT JOIN users u ON u.id = c.performed_by
+"#;
+
+#[derive(Clone)]
+pub struct EntityHistoryRepository {
+    pool: PgPool,
+}
+
+impl EntityHistoryRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl EntityHistoryRepositoryPort for EntityHistoryRepository {
+    async fn list(
+        &self,
+        entity: HistoryEntity,
+        entity_id: &str,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<EntityChangeDto>, i64), RepositoryError> {
+        let changes = sqlx::query_as::<_, EntityChangeDto>(&format!(
+            "{} WHERE c.entity_type = $1 AND c.entity_id = $2
+               ORDER BY c.performed_at DESC, c.id
+               LIMIT $3 OFFSET $4",
+            CHANGE_SELECT
+        ))
+        .bind(entity.as_str())
+        .bind(entity_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        let total: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM entity_changelog WHERE entity_type = $1 AND entity_id = $2",
+        )
+        .bind(entity.as_str())
+        .bind(entity_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        Ok((changes, total))
+    }
+
+    async fn list_by_request(
+        &self,
+        request_id: Uuid,
+    ) -> Result<Vec<EntityChangeDto>, RepositoryError> {
+        sqlx::query_as::<_, EntityChangeDto>(&format!(
+            "{} WHERE c.request_id = $1 ORDER BY c.performed_at, c.id",
+            CHANGE_SELECT
+        ))
+        .bind(request_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn record(&self, change: &NewEntityChange) -> Result<EntityChangeDto, RepositoryError> {
+        let mut tx = begin_audited(&self.pool).await?;
+
+        // Mesmo formato das linhas gravadas por fn_capture_entity_change
+        let id: Uuid = sqlx::query_scalar(
+            r#"
+            WITH d AS (SELECT fn_generate_diff($4, $5) AS diff)
+            INSERT INTO entity_changelog (
+                entity_type, entity_id, operation, data_before, data_after,
+                changed_fields, diff, performed_by, performed_at, ip_address, user_agent,
+                request_id
+            )
+            SELECT
+                $1, $2, $3, $4, $5,
+                ARRAY(SELECT jsonb_object_keys(d.diff) ORDER BY 1),
+                d.diff,
+                fn_get_audit_user_id(),
+                clock_timestamp(),
+                fn_get_audit_ip(),
+                NULLIF(current_setting('audit.user_agent', TRUE), ''),
+                fn_get_audit_request_id()
+            FROM d
+            RETURNING id
+            "#,
+        )
+        .bind(change.entity.as_str())
+        .bind(&change.entity_id)
+        .bind(change.operation)
+        .bind(&change.data_before)
+        .bind(&change.data_after)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(map_db_error)?;
+
+        let saved =
+            sqlx::query_as::<_, EntityChangeDto>(&format!("{} WHERE c.id = $1", CHANGE_SELECT))
+                .bind(id)
+                .fetch_one(&mut *tx)
+                .await
+                .map_err(map_db_error)?;
+
+        tx.commit().await.map_err(map_db_error)?;
+        Ok(saved)
+    }
+}
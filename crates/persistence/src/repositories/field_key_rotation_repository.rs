@@ -0,0 +1,411 @@
+use async_trait::async_trait;
+use core_services::field_encryption::FieldKeyring;
+use domain::errors::RepositoryError;
+use domain::models::{
+    FieldKeyRotationBatch, FieldKeyRotationDto, FieldKeyRotationStage, FieldKeyRotationStatus,
+};
+use domain::ports::FieldKeyRotationRepositoryPort;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::db_utils::map_db_error;
+
+const RUN_COLUMNS: &str = r#"id, target_key_id, status, stage, last_processed_id,
+    total_rows, processed_rows, reencrypted_rows, unreadable_rows, error,
+    triggered_by, started_at, updated_at, finished_at"#;
+
+#[derive(sqlx::FromRow)]
+struct UserSecretsRow {
+    id: Uuid,
+    email: String,
+    email_index: Option<String>,
+    mfa_secret: Option<String>,
+}
+
+#[derive(sqlx::FromRow)]
+struct SetupTokenRow {
+    id: Uuid,
+    secret: String,
+}
+
+#[derive(sqlx::FromRow)]
+struct SigningKeyRow {
+    id: Uuid,
+    private_key: String,
+}
+
+/// Outcome of re-sealing a ciphertext with the current key
+enum Resealed {
+    Current,
+    Changed(String),
+    Unreadable,
+}
+
+#[derive(Clone)]
+pub struct FieldKeyRotationRepository {
+    pool: PgPool,
+    keyring: FieldKeyring,
+}
+
+impl FieldKeyRotationRepository {
+    pub fn new(pool: PgPool, keyring: FieldKeyring) -> Self {
+        Self { pool, keyring }
+    }
+
+    fn reseal(&self, value: &str) -> Result<Resealed, RepositoryError> {
+        if self.keyring.is_current(value) {
+            return Ok(Resealed::Current);
+        }
+        let Ok(plain) = self.keyring.decrypt(value) else {
+            return Ok(Resealed::Unreadable);
+        };
+        self.keyring
+            .encrypt(&plain)
+            .map(Resealed::Changed)
+            .map_err(|e| RepositoryError::InvalidData(e.to_string()))
+    }
+
+    /// Email and its blind index under the current key. Emails that no key
+    /// opens are legacy plain text when they look like an address, as in
+    /// `UserRepository`.
+    fn reseal_email(&self, raw: &str) -> Result<Option<(String, String)>, RepositoryError> {
+        let plain = match self.keyring.decrypt(raw) {
+            Ok(plain) => plain,
+            Err(_) if raw.contains('@') => raw.to_string(),
+            Err(_) => return Ok(None),
+        };
+        let sealed = if self.keyring.is_current(raw) {
+            raw.to_string()
+        } else {
+            self.keyring
+                .encrypt(&plain)
+                .map_err(|e| RepositoryError::InvalidData(e.to_string()))?
+        };
+        Ok(Some((sealed, self.keyring.blind_index(&plain))))
+    }
+
+    async fn reencrypt_users(
+        &self,
+        after: Option<Uuid>,
+        limit: i64,
+    ) -> Result<FieldKeyRotationBatch, RepositoryError> {
+        let rows = sqlx::query_as::<_, UserSecretsRow>(
+            r#"SELECT id, email, email_index, mfa_secret FROM users
+            WHERE ($1::UUID IS NULL OR id > $1)
+            ORDER BY id
+            LIMIT $2"#,
+        )
+        .bind(after)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        let mut batch = FieldKeyRotationBatch {
+            last_id: rows.last().map(|r| r.id),
+            processed: rows.len() as i64,
+            ..Default::default()
+        };
+        let mut tx = self.pool.begin().await.map_err(map_db_error)?;
+
+        for row in rows {
+            let resealed_email = self.reseal_email(&row.email)?;
+            let resealed_secret = row
+                .mfa_secret
+                .as_deref()
+                .map(|s| self.reseal(s))
+                .transpose()?;
+            if resealed_email.is_none() || matches!(resealed_secret, Some(Resealed::Unreadable)) {
+                batch.unreadable += 1;
+            }
+
+            let (email, email_index) = match resealed_email {
+                Some((email, index)) => (email, Some(index)),
+                None => (row.email.clone(), row.email_index.clone()),
+            };
+            let mfa_secret = match resealed_secret {
+                Some(Resealed::Changed(sealed)) => Some(sealed),
+                _ => row.mfa_secret.clone(),
+            };
+            if email == row.email && email_index == row.email_index && mfa_secret == row.mfa_secret
+            {
+                continue;
+            }
+
+            // Só grava se o registro não mudou desde a leitura
+            let updated = sqlx::query(
+                r#"UPDATE users SET email = $2, email_index = $3, mfa_secret = $4
+                WHERE id = $1 AND email = $5 AND mfa_secret IS NOT DISTINCT FROM $6"#,
+            )
+            .bind(row.id)
+            .bind(&email)
+            .bind(&email_index)
+            .bind(&mfa_secret)
+            .bind(&row.email)
+            .bind(&row.mfa_secret)
+            .execute(&mut *tx)
+            .await
+            .map_err(map_db_error)?;
+            batch.reencrypted += updated.rows_affected() as i64;
+        }
+
+        tx.commit().await.map_err(map_db_error)?;
+        Ok(batch)
+    }
+
+    async fn reencrypt_setup_tokens(
+        &self,
+        after: Option<Uuid>,
+        limit: i64,
+    ) -> Result<FieldKeyRotationBatch, RepositoryError> {
+        let rows = sqlx::query_as::<_, SetupTokenRow>(
+            r#"SELECT id, secret FROM mfa_setup_tokens
+            WHERE ($1::UUID IS NULL OR id > $1)
+            ORDER BY id
+            LIMIT $2"#,
+        )
+        .bind(after)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        let mut batch = FieldKeyRotationBatch {
+            last_id: rows.last().map(|r| r.id),
+            processed: rows.len() as i64,
+            ..Default::default()
+        };
+        let mut tx = self.pool.begin().await.map_err(map_db_error)?;
+
+        for row in rows {
+            let sealed = match self.reseal(&row.secret)? {
+                Resealed::Changed(sealed) => sealed,
+                Resealed::Current => continue,
+                Resealed::Unreadable => {
+                    batch.unreadable += 1;
+                    continue;
+                }
+            };
+            let updated = sqlx::query(
+                "UPDATE mfa_setup_tokens SET secret = $2 WHERE id = $1 AND secret = $3",
+            )
+            .bind(row.id)
+            .bind(&sealed)
+            .bind(&row.secret)
+            .execute(&mut *tx)
+            .await
+            .map_err(map_db_error)?;
+            batch.reencrypted += updated.rows_affected() as i64;
+        }
+
+        tx.commit().await.map_err(map_db_error)?;
+        Ok(batch)
+    }
+
+    /// Chave configurada (sem chave privada no banco) fica de fora
+    async fn reencrypt_signing_keys(
+        &self,
+        after: Option<Uuid>,
+        limit: i64,
+    ) -> Result<FieldKeyRotationBatch, RepositoryError> {
+        let rows = sqlx::query_as::<_, SigningKeyRow>(
+            r#"SELECT id, private_key FROM jwt_signing_keys
+            WHERE private_key IS NOT NULL AND ($1::UUID IS NULL OR id > $1)
+            ORDER BY id
+            LIMIT $2"#,
+        )
+        .bind(after)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        let mut batch = FieldKeyRotationBatch {
+            last_id: rows.last().map(|r| r.id),
+            processed: rows.len() as i64,
+            ..Default::default()
+        };
+        let mut tx = self.pool.begin().await.map_err(map_db_error)?;
+
+        for row in rows {
+            let sealed = match self.reseal(&row.private_key)? {
+                Resealed::Changed(sealed) => sealed,
+                Resealed::Current => continue,
+                Resealed::Unreadable => {
+                    batch.unreadable += 1;
+                    continue;
+                }
+            };
+            let updated = sqlx::query(
+                "UPDATE jwt_signing_keys SET private_key = $2 WHERE id = $1 AND private_key = $3",
+            )
+            .bind(row.id)
+            .bind(&sealed)
+            .bind(&row.private_key)
+            .execute(&mut *tx)
+            .await
+            .map_err(map_db_error)?;
+            batch.reencrypted += updated.rows_affected() as i64;
+        }
+
+        tx.commit().await.map_err(map_db_error)?;
+        Ok(batch)
+    }
+}
+
+#[async_trait]
+impl FieldKeyRotationRepositoryPort for FieldKeyRotationRepository {
+    async fn count_rows(&self) -> Result<i64, RepositoryError> {
+        sqlx::query_scalar(
+            r#"SELECT (SELECT COUNT(*) FROM users)
+                + (SELECT COUNT(*) FROM mfa_setup_tokens)
+                + (SELECT COUNT(*) FROM jwt_signing_keys WHERE private_key IS NOT NULL)"#,
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn reencrypt_batch(
+        &self,
+        stage: FieldKeyRotationStage,
+        after: Option<Uuid>,
+        limit: i64,
+    ) -> Result<FieldKeyRotationBatch, RepositoryError> {
+        match stage {
+            FieldKeyRotationStage::Users => self.reencrypt_users(after, limit).await,
+            FieldKeyRotationStage::MfaSetupTokens => {
+                self.reencrypt_setup_tokens(after, limit).await
+            }
+            FieldKeyRotationStage::JwtSigningKeys => {
+                self.reencrypt_signing_keys(after, limit).await
+            }
+        }
+    }
+
+    async fn create_run(
+        &self,
+        target_key_id: i32,
+        total_rows: i64,
+        triggered_by: Option<Uuid>,
+    ) -> Result<FieldKeyRotationDto, RepositoryError> {
+        sqlx::query_as::<_, FieldKeyRotationDto>(&format!(
+            r#"INSERT INTO field_key_rotations (target_key_id, total_rows, triggered_by)
+            VALUES ($1, $2, $3)
+            RETURNING {RUN_COLUMNS}"#
+        ))
+        .bind(target_key_id)
+        .bind(total_rows)
+        .bind(triggered_by)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn save_progress(
+        &self,
+        id: Uuid,
+        stage: FieldKeyRotationStage,
+        last_processed_id: Option<Uuid>,
+        batch: &FieldKeyRotationBatch,
+    ) -> Result<FieldKeyRotationDto, RepositoryError> {
+        sqlx::query_as::<_, FieldKeyRotationDto>(&format!(
+            r#"UPDATE field_key_rotations
+            SET stage = $2, last_processed_id = $3,
+                processed_rows = processed_rows + $4,
+                reencrypted_rows = reencrypted_rows + $5,
+                unreadable_rows = unreadable_rows + $6,
+                updated_at = NOW()
+            WHERE id = $1
+            RETURNING {RUN_COLUMNS}"#
+        ))
+        .bind(id)
+        .bind(stage)
+        .bind(last_processed_id)
+        .bind(batch.processed)
+        .bind(batch.reencrypted)
+        .bind(batch.unreadable)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_db_error)?
+        .ok_or(RepositoryError::NotFound)
+    }
+
+    async fn finish_run(
+        &self,
+        id: Uuid,
+        status: FieldKeyRotationStatus,
+        error: Option<&str>,
+    ) -> Result<FieldKeyRotationDto, RepositoryError> {
+        sqlx::query_as::<_, FieldKeyRotationDto>(&format!(
+            r#"UPDATE field_key_rotations
+            SET status = $2, error = $3, updated_at = NOW(), finished_at = NOW()
+            WHERE id = $1
+            RETURNING {RUN_COLUMNS}"#
+        ))
+        .bind(id)
+        .bind(status)
+        .bind(error)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_db_error)?
+        .ok_or(RepositoryError::NotFound)
+    }
+
+    async fn reopen_run(&self, id: Uuid) -> Result<FieldKeyRotationDto, RepositoryError> {
+        sqlx::query_as::<_, FieldKeyRotationDto>(&format!(
+            r#"UPDATE field_key_rotations
+            SET status = 'RUNNING', error = NULL, updated_at = NOW(), finished_at = NULL
+            WHERE id = $1 AND status = 'FAILED'
+            RETURNING {RUN_COLUMNS}"#
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_db_error)?
+        .ok_or(RepositoryError::NotFound)
+    }
+
+    async fn find_running(&self) -> Result<Option<FieldKeyRotationDto>, RepositoryError> {
+        sqlx::query_as::<_, FieldKeyRotationDto>(&format!(
+            "SELECT {RUN_COLUMNS} FROM field_key_rotations WHERE status = 'RUNNING'"
+        ))
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn find_run(&self, id: Uuid) -> Result<Option<FieldKeyRotationDto>, RepositoryError> {
+        sqlx::query_as::<_, FieldKeyRotationDto>(&format!(
+            "SELECT {RUN_COLUMNS} FROM field_key_rotations WHERE id = $1"
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn list_runs(
+        &self,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<FieldKeyRotationDto>, i64), RepositoryError> {
+        let runs = sqlx::query_as::<_, FieldKeyRotationDto>(&format!(
+            r#"SELECT {RUN_COLUMNS} FROM field_key_rotations
+            ORDER BY started_at DESC
+            LIMIT $1 OFFSET $2"#
+        ))
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        let total: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM field_key_rotations")
+            .fetch_one(&self.pool)
+            .await
+            .map_err(map_db_error)?;
+
+        Ok((runs, total))
+    }
+}
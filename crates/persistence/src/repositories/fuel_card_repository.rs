@@ -0,0 +1,218 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use domain::{
+    errors::RepositoryError,
+    models::fuel_card::*,
+    models::fueling::FuelingDto,
+    ports::fuel_card::FuelCardRepositoryPort,
+};
+use sqlx::{PgPool, Row};
+use uuid::Uuid;
+
+use crate::db_utils::map_db_error;
+
+pub struct FuelCardRepository {
+    pool: PgPool,
+}
+
+impl FuelCardRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl FuelCardRepositoryPort for FuelCardRepository {
+    async fn find_vehicle_by_plate(
+        &self,
+        license_plate: &str,
+    ) -> Result<Option<FuelCardVehicleRef>, RepositoryError> {
+        sqlx::query_as::<_, FuelCardVehicleRef>(
+            r#"SELECT id, fuel_type_id, last_odometer_km
+               FROM vehicles
+               WHERE REPLACE(UPPER(license_plate), '-', '') = $1 AND is_deleted = false
+               LIMIT 1"#,
+        )
+        .bind(license_plate)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn find_driver_by_cpf(&self, cpf: &str) -> Result<Option<Uuid>, RepositoryError> {
+        sqlx::query_scalar::<_, Uuid>("SELECT id FROM drivers WHERE cpf = $1")
+            .bind(cpf)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(map_db_error)
+    }
+
+    async fn find_fuel_type_by_name(&self, name: &str) -> Result<Option<Uuid>, RepositoryError> {
+        sqlx::query_scalar::<_, Uuid>(
+            "SELECT id FROM vehicle_fuel_types WHERE UPPER(name) = UPPER($1) LIMIT 1",
+        )
+        .bind(name)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn find_unlinked_fuelings(
+        &self,
+        vehicle_id: Uuid,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<FuelingDto>, RepositoryError> {
+        sqlx::query_as::<_, FuelingDto>(
+            r#"SELECT f.* FROM fuelings f
+               WHERE f.vehicle_id = $1
+                 AND f.fueling_date BETWEEN $2 AND $3
+                 AND f.review_status <> 'REJEITADO'
+                 AND NOT EXISTS (
+                     SELECT 1 FROM fuel_card_transactions t WHERE t.fueling_id = f.id
+                 )
+               ORDER BY f.fueling_date"#,
+        )
+        .bind(vehicle_id)
+        .bind(start)
+        .bind(end)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn exists_by_external_id(
+        &self,
+        operator: &str,
+        external_id: &str,
+    ) -> Result<bool, RepositoryError> {
+        sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS(SELECT 1 FROM fuel_card_transactions WHERE operator = $1 AND external_id = $2)",
+        )
+        .bind(operator)
+        .bind(external_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn create_transaction(
+        &self,
+        job_id: Uuid,
+        operator: &str,
+        transaction: &NewFuelCardTransaction,
+    ) -> Result<FuelCardTransactionDto, RepositoryError> {
+        let record = &transaction.record;
+        let mismatches = if transaction.mismatches.is_empty() {
+            None
+        } else {
+            Some(serde_json::json!(transaction.mismatches))
+        };
+
+        let mut tx = self.pool.begin().await.map_err(map_db_error)?;
+
+        // Abastecimento gerado e transação gravados juntos: uma falha na
+        // transação não deixa abastecimento órfão na fila de revisão
+        let fueling_id = match &transaction.pending_fueling {
+            Some(fueling) => Some(
+                sqlx::query_scalar::<_, Uuid>(
+                    r#"INSERT INTO fuelings (vehicle_id, driver_id, fuel_type_id,
+                                             fueling_date, odometer_km, quantity_liters,
+                                             unit_price, total_cost, notes,
+                                             review_status, anomalies,
+                                             created_by, updated_by)
+                       VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, 'QUARENTENA', $10, $11, $11)
+                       RETURNING id"#,
+                )
+                .bind(fueling.vehicle_id)
+                .bind(fueling.driver_id)
+                .bind(fueling.fuel_type_id)
+                .bind(record.transaction_at)
+                .bind(fueling.odometer_km)
+                .bind(record.liters)
+                .bind(record.unit_price)
+                .bind(record.total_amount)
+                .bind(&fueling.notes)
+                .bind(&fueling.anomalies)
+                .bind(fueling.created_by)
+                .fetch_one(&mut *tx)
+                .await
+                .map_err(map_db_error)?,
+            ),
+            None => transaction.fueling_id,
+        };
+
+        let created = sqlx::query_as::<_, FuelCardTransactionDto>(
+            r#"INSERT INTO fuel_card_transactions
+                (job_id, operator, line_number, external_id, transaction_at,
+                 license_plate, driver_cpf, liters, unit_price, total_amount,
+                 odometer_km, fuel_name, merchant,
+                 vehicle_id, driver_id, fueling_id, match_status, mismatches)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13,
+                       $14, $15, $16, $17, $18)
+               RETURNING *"#,
+        )
+        .bind(job_id)
+        .bind(operator)
+        .bind(record.line_number)
+        .bind(record.external_id.as_deref())
+        .bind(record.transaction_at)
+        .bind(&record.license_plate)
+        .bind(record.driver_cpf.as_deref())
+        .bind(record.liters)
+        .bind(record.unit_price)
+        .bind(record.total_amount)
+        .bind(record.odometer_km)
+        .bind(record.fuel_name.as_deref())
+        .bind(record.merchant.as_deref())
+        .bind(transaction.vehicle_id)
+        .bind(transaction.driver_id)
+        .bind(fueling_id)
+        .bind(&transaction.match_status)
+        .bind(mismatches)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(map_db_error)?;
+
+        tx.commit().await.map_err(map_db_error)?;
+        Ok(created)
+    }
+
+    async fn list_by_job(
+        &self,
+        job_id: Uuid,
+        match_status: Option<FuelCardMatchStatus>,
+    ) -> Result<Vec<FuelCardTransactionDto>, RepositoryError> {
+        sqlx::query_as::<_, FuelCardTransactionDto>(
+            r#"SELECT * FROM fuel_card_transactions
+               WHERE job_id = $1 AND ($2::fuel_card_match_status_enum IS NULL OR match_status = $2)
+               ORDER BY line_number"#,
+        )
+        .bind(job_id)
+        .bind(match_status)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn count_by_status(
+        &self,
+        job_id: Uuid,
+    ) -> Result<Vec<(FuelCardMatchStatus, i64)>, RepositoryError> {
+        let rows = sqlx::query(
+            r#"SELECT match_status, COUNT(*) AS total
+               FROM fuel_card_transactions
+               WHERE job_id = $1
+               GROUP BY match_status"#,
+        )
+        .bind(job_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        Ok(rows
+            .iter()
+            .map(|r| (r.get("match_status"), r.get("total")))
+            .collect())
+    }
+}
@@ -39,6 +39,7 @@ impl FuelingRepositoryPort for FuelingRepository {
                       f.fuel_type_id, ft.name AS fuel_type_name,
                       f.fueling_date, f.odometer_km, f.quantity_liters,
                       f.unit_price, f.total_cost, f.notes,
+                      f.review_status, f.anomalies,
                       f.created_at, f.updated_at
                FROM fuelings f
                LEFT JOIN vehicles v ON v.id = f.vehicle_id
@@ -65,14 +66,17 @@ impl FuelingRepositoryPort for FuelingRepository {
         unit_price: Decimal,
         total_cost: Decimal,
         notes: Option<&str>,
+        review_status: FuelingReviewStatus,
+        anomalies: Option<serde_json::Value>,
         created_by: Option<Uuid>,
     ) -> Result<FuelingDto, RepositoryError> {
         sqlx::query_as::<_, FuelingDto>(
             r#"INSERT INTO fuelings (vehicle_id, driver_id, supplier_id, fuel_type_id,
                                      fueling_date, odometer_km, quantity_liters,
                                      unit_price, total_cost, notes,
+                                     review_status, anomalies,
                                      created_by, updated_by)
-               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $11)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $13)
                RETURNING *"#,
         )
         .bind(vehicle_id)
@@ -85,6 +89,8 @@ impl FuelingRepositoryPort for FuelingRepository {
         .bind(unit_price)
         .bind(total_cost)
         .bind(notes)
+        .bind(review_status)
+        .bind(anomalies)
         .bind(created_by)
         .fetch_one(&self.pool)
         .await
@@ -104,6 +110,8 @@ impl FuelingRepositoryPort for FuelingRepository {
         unit_price: Option<Decimal>,
         total_cost: Option<Decimal>,
         notes: Option<&str>,
+        review_status: Option<FuelingReviewStatus>,
+        anomalies: Option<serde_json::Value>,
         updated_by: Option<Uuid>,
     ) -> Result<FuelingDto, RepositoryError> {
         sqlx::query_as::<_, FuelingDto>(
@@ -118,7 +126,12 @@ impl FuelingRepositoryPort for FuelingRepository {
                 unit_price = COALESCE($9, unit_price),
                 total_cost = COALESCE($10, total_cost),
                 notes = COALESCE($11, notes),
-                updated_by = COALESCE($12, updated_by)
+                updated_by = COALESCE($14, updated_by),
+                review_status = COALESCE($12, review_status),
+                anomalies = CASE WHEN $12 IS NULL THEN anomalies ELSE $13 END,
+                reviewed_by = CASE WHEN $12 IS NULL THEN reviewed_by END,
+                reviewed_at = CASE WHEN $12 IS NULL THEN reviewed_at END,
+                review_notes = CASE WHEN $12 IS NULL THEN review_notes END
                WHERE id = $1
                RETURNING *"#,
         )
@@ -133,6 +146,8 @@ impl FuelingRepositoryPort for FuelingRepository {
         .bind(unit_price)
         .bind(total_cost)
         .bind(notes)
+        .bind(review_status)
+        .bind(anomalies)
         .bind(updated_by)
         .fetch_one(&self.pool)
         .await
@@ -155,6 +170,7 @@ impl FuelingRepositoryPort for FuelingRepository {
         vehicle_id: Option<Uuid>,
         driver_id: Option<Uuid>,
         supplier_id: Option<Uuid>,
+        review_status: Option<FuelingReviewStatus>,
     ) -> Result<(Vec<FuelingWithDetailsDto>, i64), RepositoryError> {
         let mut where_clauses = Vec::new();
         let mut param_index = 1u32;
@@ -171,6 +187,10 @@ impl FuelingRepositoryPort for FuelingRepository {
             where_clauses.push(format!("f.supplier_id = ${}", param_index));
             param_index += 1;
         }
+        if review_status.is_some() {
+            where_clauses.push(format!("f.review_status = ${}", param_index));
+            param_index += 1;
+        }
 
         let where_sql = if where_clauses.is_empty() {
             String::new()
@@ -186,6 +206,7 @@ impl FuelingRepositoryPort for FuelingRepository {
                       f.fuel_type_id, ft.name AS fuel_type_name,
                       f.fueling_date, f.odometer_km, f.quantity_liters,
                       f.unit_price, f.total_cost, f.notes,
+                      f.review_status, f.anomalies,
                       f.created_at, f.updated_at
                FROM fuelings f
                LEFT JOIN vehicles v ON v.id = f.vehicle_id
@@ -213,6 +234,10 @@ impl FuelingRepositoryPort for FuelingRepository {
             count_query = count_query.bind(sid);
             list_query = list_query.bind(sid);
         }
+        if let Some(status) = review_status {
+            count_query = count_query.bind(status.clone());
+            list_query = list_query.bind(status);
+        }
 
         count_query = count_query.bind(limit);
         list_query = list_query.bind(limit).bind(offset);
@@ -230,4 +255,93 @@ impl FuelingRepositoryPort for FuelingRepository {
 
         Ok((items, total))
     }
+
+    async fn find_previous_by_vehicle(
+        &self,
+        vehicle_id: Uuid,
+        before: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<FuelingDto>, RepositoryError> {
+        sqlx::query_as::<_, FuelingDto>(
+            r#"SELECT * FROM fuelings
+               WHERE vehicle_id = $1
+                 AND fueling_date < $2
+                 AND review_status <> 'REJEITADO'
+               ORDER BY fueling_date DESC
+               LIMIT $3"#,
+        )
+        .bind(vehicle_id)
+        .bind(before)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn find_next_by_vehicle(
+        &self,
+        vehicle_id: Uuid,
+        after: DateTime<Utc>,
+    ) -> Result<Option<FuelingDto>, RepositoryError> {
+        sqlx::query_as::<_, FuelingDto>(
+            r#"SELECT * FROM fuelings
+               WHERE vehicle_id = $1
+                 AND fueling_date > $2
+                 AND review_status <> 'REJEITADO'
+               ORDER BY fueling_date
+               LIMIT 1"#,
+        )
+        .bind(vehicle_id)
+        .bind(after)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn median_unit_price(
+        &self,
+        fuel_type_id: Uuid,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Result<Option<Decimal>, RepositoryError> {
+        sqlx::query_scalar::<_, Option<Decimal>>(
+            r#"SELECT (PERCENTILE_CONT(0.5) WITHIN GROUP (ORDER BY unit_price))::NUMERIC
+               FROM fuelings
+               WHERE fuel_type_id = $1
+                 AND fueling_date BETWEEN $2 AND $3
+                 AND review_status <> 'REJEITADO'"#,
+        )
+        .bind(fuel_type_id)
+        .bind(since)
+        .bind(until)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn review(
+        &self,
+        id: Uuid,
+        review_status: FuelingReviewStatus,
+        reviewed_by: Uuid,
+        review_notes: Option<&str>,
+    ) -> Result<Option<FuelingDto>, RepositoryError> {
+        sqlx::query_as::<_, FuelingDto>(
+            r#"UPDATE fuelings SET
+                review_status = $2,
+                reviewed_by = $3,
+                reviewed_at = NOW(),
+                review_notes = $4,
+                updated_by = $3
+               WHERE id = $1 AND review_status = 'QUARENTENA'
+               RETURNING *"#,
+        )
+        .bind(id)
+        .bind(review_status)
+        .bind(reviewed_by)
+        .bind(review_notes)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
 }
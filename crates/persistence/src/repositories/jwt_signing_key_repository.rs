@@ -0,0 +1,135 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use core_services::field_encryption::FieldKeyring;
+use domain::errors::RepositoryError;
+use domain::models::{JwtSigningKeyDto, NewJwtSigningKey};
+use domain::ports::JwtSigningKeyRepositoryPort;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::db_utils::map_db_error;
+
+const KEY_COLUMNS: &str = r#"kid, algorithm, status, public_jwk, created_by, created_at,
+    retired_at, verify_until"#;
+
+#[derive(sqlx::FromRow)]
+struct ActiveKeyRow {
+    #[sqlx(flatten)]
+    key: JwtSigningKeyDto,
+    private_key: Option<String>,
+}
+
+/// Private keys are stored sealed with the field encryption keyring.
+#[derive(Clone)]
+pub struct JwtSigningKeyRepository {
+    pool: PgPool,
+    keyring: FieldKeyring,
+}
+
+impl JwtSigningKeyRepository {
+    pub fn new(pool: PgPool, keyring: FieldKeyring) -> Self {
+        Self { pool, keyring }
+    }
+
+    fn seal(&self, private_key: Option<&str>) -> Result<Option<String>, RepositoryError> {
+        private_key
+            .map(|key| self.keyring.encrypt(key))
+            .transpose()
+            .map_err(|e| RepositoryError::InvalidData(e.to_string()))
+    }
+}
+
+#[async_trait]
+impl JwtSigningKeyRepositoryPort for JwtSigningKeyRepository {
+    async fn list_keys(&self) -> Result<Vec<JwtSigningKeyDto>, RepositoryError> {
+        sqlx::query_as::<_, JwtSigningKeyDto>(&format!(
+            "SELECT {KEY_COLUMNS} FROM jwt_signing_keys ORDER BY created_at DESC"
+        ))
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn find_active(&self) -> Result<Option<(JwtSigningKeyDto, String)>, RepositoryError> {
+        let row = sqlx::query_as::<_, ActiveKeyRow>(&format!(
+            "SELECT {KEY_COLUMNS}, private_key FROM jwt_signing_keys WHERE status = 'ACTIVE'"
+        ))
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+        let sealed = row.private_key.ok_or_else(|| {
+            RepositoryError::InvalidData(format!("Chave JWT ativa {} sem chave privada", row.key.kid))
+        })?;
+        let private_key = self.keyring.decrypt(&sealed).map_err(|e| {
+            RepositoryError::InvalidData(format!(
+                "Falha ao decifrar a chave JWT {}: {}",
+                row.key.kid, e
+            ))
+        })?;
+        Ok(Some((row.key, private_key)))
+    }
+
+    async fn rotate(
+        &self,
+        new: &NewJwtSigningKey,
+        previous: &NewJwtSigningKey,
+        verify_until: DateTime<Utc>,
+        created_by: Option<Uuid>,
+    ) -> Result<JwtSigningKeyDto, RepositoryError> {
+        let new_private_key = self.seal(new.private_key.as_deref())?;
+        let previous_private_key = self.seal(previous.private_key.as_deref())?;
+
+        let mut tx = self.pool.begin().await.map_err(map_db_error)?;
+
+        let retired = sqlx::query(
+            r#"UPDATE jwt_signing_keys
+            SET status = 'RETIRED', retired_at = NOW(), verify_until = $1
+            WHERE status = 'ACTIVE'"#,
+        )
+        .bind(verify_until)
+        .execute(&mut *tx)
+        .await
+        .map_err(map_db_error)?
+        .rows_affected();
+
+        // Primeira rotação: a chave configurada passa a constar como aposentada
+        if retired == 0 {
+            sqlx::query(
+                r#"INSERT INTO jwt_signing_keys
+                    (kid, algorithm, status, public_jwk, private_key, retired_at, verify_until)
+                VALUES ($1, $2, 'RETIRED', $3, $4, NOW(), $5)
+                ON CONFLICT (kid) DO NOTHING"#,
+            )
+            .bind(&previous.kid)
+            .bind(&previous.algorithm)
+            .bind(&previous.public_jwk)
+            .bind(previous_private_key)
+            .bind(verify_until)
+            .execute(&mut *tx)
+            .await
+            .map_err(map_db_error)?;
+        }
+
+        let key = sqlx::query_as::<_, JwtSigningKeyDto>(&format!(
+            r#"INSERT INTO jwt_signing_keys
+                (kid, algorithm, status, public_jwk, private_key, created_by)
+            VALUES ($1, $2, 'ACTIVE', $3, $4, $5)
+            RETURNING {KEY_COLUMNS}"#
+        ))
+        .bind(&new.kid)
+        .bind(&new.algorithm)
+        .bind(&new.public_jwk)
+        .bind(new_private_key)
+        .bind(created_by)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(map_db_error)?;
+
+        tx.commit().await.map_err(map_db_error)?;
+        Ok(key)
+    }
+}
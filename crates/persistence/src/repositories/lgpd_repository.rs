@@ -0,0 +1,553 @@
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use core_services::field_encryption::FieldKeyring;
+use domain::errors::RepositoryError;
+use domain::models::{
+    AnonymizationPlan, AnonymizationResult, DataSubjectDriver, DataSubjectUser,
+    DriverAnonymization, DriverType, UserAnonymization,
+};
+use domain::ports::LgpdRepositoryPort;
+use serde_json::{json, Map, Value};
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::db_utils::{begin_audited, map_db_error};
+
+// ---------------------------------------------------------------------------
+// Consultas da exportação — uma linha JSON por registro, sem material de
+// credenciais (hashes de token, chaves de passkey, índices cegos)
+// ---------------------------------------------------------------------------
+
+const USER_SESSIONS: &str = r#"
+    SELECT to_jsonb(s) - 'session_token_hash' - 'access_token_encrypted'
+    FROM sessions s WHERE s.user_id = $1 ORDER BY s.created_at DESC
+"#;
+
+const USER_LOGIN_ATTEMPTS: &str = r#"
+    SELECT to_jsonb(a) FROM login_attempts a WHERE a.user_id = $1 ORDER BY a.created_at DESC
+"#;
+
+const USER_IDENTITIES: &str = r#"
+    SELECT to_jsonb(i) - 'subject_index'
+    FROM user_identities i WHERE i.user_id = $1 ORDER BY i.created_at
+"#;
+
+const USER_WEBAUTHN: &str = r#"
+    SELECT jsonb_build_object(
+        'id', w.id, 'name', w.name, 'created_at', w.created_at, 'last_used_at', w.last_used_at
+    )
+    FROM webauthn_credentials w WHERE w.user_id = $1 ORDER BY w.created_at
+"#;
+
+const USER_REQUISITIONS: &str = r#"
+    SELECT to_jsonb(r) FROM requisitions r WHERE r.requester_id = $1 ORDER BY r.created_at DESC
+"#;
+
+const USER_TRIPS: &str = r#"
+    SELECT to_jsonb(t) FROM vehicle_trips t WHERE t.requester_id = $1 ORDER BY t.created_at DESC
+"#;
+
+const USER_CHANGES: &str = r#"
+    SELECT jsonb_build_object(
+        'id', c.id, 'entity_type', c.entity_type, 'entity_id', c.entity_id,
+        'operation', c.operation, 'changed_fields', c.changed_fields,
+        'performed_at', c.performed_at, 'ip_address', host(c.ip_address),
+        'user_agent', c.user_agent, 'request_id', c.request_id
+    )
+    FROM entity_changelog c WHERE c.performed_by = $1 ORDER BY c.performed_at DESC
+"#;
+
+/// Logs database
+const USER_AUDIT_LOGS: &str = r#"
+    SELECT jsonb_build_object(
+        'id', a.id, 'action', a.action, 'resource', a.resource, 'method', a.method,
+        'status_code', a.status_code, 'ip_address', host(a.ip_address),
+        'user_agent', a.user_agent, 'request_id', a.request_id, 'created_at', a.created_at
+    )
+    FROM audit_logs a WHERE a.user_id = $1 ORDER BY a.created_at DESC
+"#;
+
+const DRIVER_TRIPS: &str = r#"
+    SELECT to_jsonb(t) FROM vehicle_trips t WHERE t.driver_id = $1 ORDER BY t.created_at DESC
+"#;
+
+const DRIVER_FINES: &str = r#"
+    SELECT to_jsonb(f) FROM vehicle_fines f WHERE f.driver_id = $1 ORDER BY f.created_at DESC
+"#;
+
+const DRIVER_FUELINGS: &str = r#"
+    SELECT to_jsonb(f) FROM fuelings f WHERE f.driver_id = $1 ORDER BY f.created_at DESC
+"#;
+
+const DRIVER_HISTORY: &str = r#"
+    SELECT to_jsonb(c) FROM entity_changelog c
+    WHERE c.entity_type = 'driver' AND c.entity_id = $1::TEXT
+    ORDER BY c.performed_at DESC
+"#;
+
+// ---------------------------------------------------------------------------
+// Anonimização — registros de acesso e credenciais do usuário ($1 = user_id)
+// ---------------------------------------------------------------------------
+
+const USER_CLEANUP: &[(&str, &str)] = &[
+    ("sessions", "DELETE FROM sessions WHERE user_id = $1"),
+    (
+        "refresh_tokens",
+        "DELETE FROM refresh_tokens WHERE user_id = $1",
+    ),
+    (
+        "user_identities",
+        "DELETE FROM user_identities WHERE user_id = $1",
+    ),
+    (
+        "webauthn_credentials",
+        "DELETE FROM webauthn_credentials WHERE user_id = $1",
+    ),
+    (
+        "mfa_setup_tokens",
+        "DELETE FROM mfa_setup_tokens WHERE user_id = $1",
+    ),
+    (
+        "mfa_backup_codes",
+        "DELETE FROM mfa_backup_codes WHERE user_id = $1",
+    ),
+    (
+        "mfa_backup_code_usage",
+        "UPDATE mfa_backup_code_usage SET ip_address = NULL
+         WHERE user_id = $1 AND ip_address IS NOT NULL",
+    ),
+    (
+        "email_verification_tokens",
+        "DELETE FROM email_verification_tokens WHERE user_id = $1",
+    ),
+    (
+        "login_attempts",
+        "DELETE FROM login_attempts WHERE user_id = $1",
+    ),
+    (
+        "account_lockouts",
+        "DELETE FROM account_lockouts WHERE user_id = $1",
+    ),
+    (
+        "entity_changelog",
+        "UPDATE entity_changelog SET ip_address = NULL, user_agent = NULL
+         WHERE performed_by = $1 AND (ip_address IS NOT NULL OR user_agent IS NOT NULL)",
+    ),
+    (
+        "requisition_history",
+        "UPDATE requisition_history SET ip_address = NULL, user_agent = NULL,
+                performed_by_name = NULL
+         WHERE performed_by = $1
+           AND (ip_address IS NOT NULL OR user_agent IS NOT NULL OR performed_by_name IS NOT NULL)",
+    ),
+    (
+        "requisition_item_history",
+        "UPDATE requisition_item_history SET ip_address = NULL
+         WHERE performed_by = $1 AND ip_address IS NOT NULL",
+    ),
+    (
+        "invoice_history",
+        "UPDATE invoice_history SET ip_address = NULL, performed_by_name = NULL
+         WHERE performed_by = $1 AND (ip_address IS NOT NULL OR performed_by_name IS NOT NULL)",
+    ),
+    (
+        "invoice_item_history",
+        "UPDATE invoice_item_history SET ip_address = NULL
+         WHERE performed_by = $1 AND ip_address IS NOT NULL",
+    ),
+];
+
+#[derive(sqlx::FromRow)]
+struct RawSubjectUserRow {
+    id: Uuid,
+    username: String,
+    email: String,
+    role: String,
+    email_verified: bool,
+    mfa_enabled: bool,
+    is_banned: bool,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    anonymized_at: Option<DateTime<Utc>>,
+}
+
+#[derive(sqlx::FromRow)]
+struct SubjectDriverRow {
+    id: Uuid,
+    driver_type: DriverType,
+    full_name: String,
+    cpf: String,
+    cnh_number: String,
+    cnh_category: String,
+    cnh_expiration: NaiveDate,
+    phone: Option<String>,
+    email: Option<String>,
+    is_active: bool,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    anonymized_at: Option<DateTime<Utc>>,
+}
+
+/// Data-subject export and anonymization.
+/// Reads the audit log from db_pool_logs; everything else is in db_pool_auth.
+#[derive(Clone)]
+pub struct LgpdRepository {
+    pool: PgPool,
+    pool_logs: PgPool,
+    keyring: FieldKeyring,
+}
+
+impl LgpdRepository {
+    pub fn new(pool: PgPool, pool_logs: PgPool, keyring: FieldKeyring) -> Self {
+        Self {
+            pool,
+            pool_logs,
+            keyring,
+        }
+    }
+
+    async fn json_rows(pool: &PgPool, sql: &str, id: Uuid) -> Result<Vec<Value>, RepositoryError> {
+        sqlx::query_scalar::<_, Value>(sql)
+            .bind(id)
+            .fetch_all(pool)
+            .await
+            .map_err(map_db_error)
+    }
+
+    async fn anonymize_user(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        plan: &UserAnonymization,
+        anonymized_at: DateTime<Utc>,
+        affected: &mut Map<String, Value>,
+    ) -> Result<bool, RepositoryError> {
+        let email = self
+            .keyring
+            .encrypt(&plan.email)
+            .map_err(|e| RepositoryError::InvalidData(e.to_string()))?;
+
+        // A conta fica bloqueada: nenhuma credencial antiga continua válida
+        let updated = sqlx::query(
+            r#"
+            UPDATE users SET
+                username = $2,
+                email = $3,
+                email_index = $4,
+                cpf_index = NULL,
+                password_hash = $5,
+                mfa_enabled = FALSE,
+                mfa_secret = NULL,
+                mfa_backup_codes = NULL,
+                password_reset_token = NULL,
+                password_reset_expires = NULL,
+                email_verification_token = NULL,
+                email_verification_expires = NULL,
+                is_banned = TRUE,
+                banned_at = COALESCE(banned_at, $6),
+                banned_reason = 'Titular anonimizado (LGPD)',
+                anonymized_at = $6,
+                updated_at = NOW()
+            WHERE id = $1 AND anonymized_at IS NULL
+            "#,
+        )
+        .bind(plan.user_id)
+        .bind(&plan.username)
+        .bind(&email)
+        .bind(self.keyring.blind_index(&plan.email))
+        .bind(&plan.password_hash)
+        .bind(anonymized_at)
+        .execute(&mut **tx)
+        .await
+        .map_err(map_db_error)?
+        .rows_affected();
+
+        if updated == 0 {
+            return Ok(false);
+        }
+        count(affected, "users", updated);
+
+        for (table, sql) in USER_CLEANUP {
+            let n = sqlx::query(sql)
+                .bind(plan.user_id)
+                .execute(&mut **tx)
+                .await
+                .map_err(map_db_error)?
+                .rows_affected();
+            count(affected, table, n);
+        }
+
+        // Nome em cache nas requisições: a linha e o histórico da requisição
+        // mantêm o requester_id, só o nome é trocado
+        let n = sqlx::query(
+            "UPDATE requisitions SET requester_name = $2
+             WHERE requester_id = $1 AND requester_name IS NOT NULL",
+        )
+        .bind(plan.user_id)
+        .bind(&plan.username)
+        .execute(&mut **tx)
+        .await
+        .map_err(map_db_error)?
+        .rows_affected();
+        count(affected, "requisitions", n);
+
+        let n = sqlx::query(
+            r#"
+            UPDATE requisition_history SET
+                data_before = fn_lgpd_mask(data_before, $2),
+                data_after = fn_lgpd_mask(data_after, $2),
+                changes_diff = fn_lgpd_mask_diff(changes_diff, $2)
+            WHERE requisition_id IN (SELECT id FROM requisitions WHERE requester_id = $1)
+            "#,
+        )
+        .bind(plan.user_id)
+        .bind(json!({ "requester_name": plan.username }))
+        .execute(&mut **tx)
+        .await
+        .map_err(map_db_error)?
+        .rows_affected();
+        count(affected, "requisition_history", n);
+
+        Ok(true)
+    }
+
+    async fn anonymize_driver(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        plan: &DriverAnonymization,
+        anonymized_at: DateTime<Utc>,
+        affected: &mut Map<String, Value>,
+    ) -> Result<bool, RepositoryError> {
+        let old_cpf: Option<String> = sqlx::query_scalar(
+            r#"
+            UPDATE drivers d SET
+                full_name = $2,
+                cpf = $3,
+                cnh_number = $4,
+                phone = NULL,
+                email = NULL,
+                is_active = FALSE,
+                anonymized_at = $5,
+                version = d.version + 1,
+                updated_at = NOW()
+            FROM (SELECT id, cpf FROM drivers WHERE id = $1 FOR UPDATE) old
+            WHERE d.id = old.id AND d.anonymized_at IS NULL
+            RETURNING old.cpf
+            "#,
+        )
+        .bind(plan.driver_id)
+        .bind(&plan.full_name)
+        .bind(&plan.cpf)
+        .bind(&plan.cnh_number)
+        .bind(anonymized_at)
+        .fetch_optional(&mut **tx)
+        .await
+        .map_err(map_db_error)?;
+
+        let Some(old_cpf) = old_cpf else {
+            return Ok(false);
+        };
+        count(affected, "drivers", 1);
+
+        let n = sqlx::query(
+            "UPDATE fuel_card_transactions SET driver_cpf = $2
+             WHERE driver_id = $1 OR driver_cpf = $3",
+        )
+        .bind(plan.driver_id)
+        .bind(&plan.cpf)
+        .bind(&old_cpf)
+        .execute(&mut **tx)
+        .await
+        .map_err(map_db_error)?
+        .rows_affected();
+        count(affected, "fuel_card_transactions", n);
+
+        // Inclui a linha que o trigger acabou de gravar para esta alteração
+        let tokens = json!({
+            "full_name": plan.full_name,
+            "cpf": plan.cpf,
+            "cnh_number": plan.cnh_number,
+            "phone": null,
+            "email": null,
+        });
+        let n = sqlx::query(
+            r#"
+            UPDATE entity_changelog SET
+                data_before = fn_lgpd_mask(data_before, $2),
+                data_after = fn_lgpd_mask(data_after, $2),
+                diff = fn_lgpd_mask_diff(diff, $2)
+            WHERE entity_type = 'driver' AND entity_id = $1::TEXT
+            "#,
+        )
+        .bind(plan.driver_id)
+        .bind(&tokens)
+        .execute(&mut **tx)
+        .await
+        .map_err(map_db_error)?
+        .rows_affected();
+        count(affected, "entity_changelog", n);
+
+        Ok(true)
+    }
+}
+
+/// Accumulates affected rows per table
+fn count(affected: &mut Map<String, Value>, table: &str, n: u64) {
+    if n == 0 {
+        return;
+    }
+    let total = affected.get(table).and_then(Value::as_u64).unwrap_or(0) + n;
+    affected.insert(table.to_string(), json!(total));
+}
+
+#[async_trait]
+impl LgpdRepositoryPort for LgpdRepository {
+    async fn find_user_id_by_cpf(&self, cpf: &str) -> Result<Option<Uuid>, RepositoryError> {
+        sqlx::query_scalar::<_, Uuid>("SELECT id FROM users WHERE cpf_index = ANY($1)")
+            .bind(self.keyring.blind_indexes(cpf))
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(map_db_error)
+    }
+
+    async fn find_driver_id_by_cpf(&self, cpf: &str) -> Result<Option<Uuid>, RepositoryError> {
+        sqlx::query_scalar::<_, Uuid>("SELECT id FROM drivers WHERE cpf = $1")
+            .bind(cpf)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(map_db_error)
+    }
+
+    async fn export_user(&self, id: Uuid) -> Result<Option<DataSubjectUser>, RepositoryError> {
+        let row = sqlx::query_as::<_, RawSubjectUserRow>(
+            r#"
+            SELECT id, username, email, role, email_verified,
+                   COALESCE(mfa_enabled, FALSE) AS mfa_enabled, is_banned,
+                   created_at, updated_at, anonymized_at
+            FROM users WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+
+        // Linhas antigas podem ainda ter o email em texto puro
+        let email = self.keyring.decrypt(&row.email).unwrap_or(row.email);
+
+        Ok(Some(DataSubjectUser {
+            id: row.id,
+            username: row.username,
+            email,
+            role: row.role,
+            email_verified: row.email_verified,
+            mfa_enabled: row.mfa_enabled,
+            is_banned: row.is_banned,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+            anonymized_at: row.anonymized_at,
+            sessions: Self::json_rows(&self.pool, USER_SESSIONS, id).await?,
+            login_attempts: Self::json_rows(&self.pool, USER_LOGIN_ATTEMPTS, id).await?,
+            identities: Self::json_rows(&self.pool, USER_IDENTITIES, id).await?,
+            webauthn_credentials: Self::json_rows(&self.pool, USER_WEBAUTHN, id).await?,
+            requisitions: Self::json_rows(&self.pool, USER_REQUISITIONS, id).await?,
+            trips_requested: Self::json_rows(&self.pool, USER_TRIPS, id).await?,
+            changes_performed: Self::json_rows(&self.pool, USER_CHANGES, id).await?,
+            audit_logs: Self::json_rows(&self.pool_logs, USER_AUDIT_LOGS, id).await?,
+        }))
+    }
+
+    async fn export_driver(&self, id: Uuid) -> Result<Option<DataSubjectDriver>, RepositoryError> {
+        let row = sqlx::query_as::<_, SubjectDriverRow>(
+            r#"
+            SELECT id, driver_type, full_name, cpf, cnh_number, cnh_category, cnh_expiration,
+                   phone, email, is_active, created_at, updated_at, anonymized_at
+            FROM drivers WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+
+        let fuel_card_transactions = sqlx::query_scalar::<_, Value>(
+            r#"
+            SELECT to_jsonb(t) FROM fuel_card_transactions t
+            WHERE t.driver_id = $1 OR t.driver_cpf = $2
+            ORDER BY t.created_at DESC
+            "#,
+        )
+        .bind(id)
+        .bind(&row.cpf)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        Ok(Some(DataSubjectDriver {
+            id: row.id,
+            driver_type: row.driver_type,
+            full_name: row.full_name,
+            cpf: row.cpf,
+            cnh_number: row.cnh_number,
+            cnh_category: row.cnh_category,
+            cnh_expiration: row.cnh_expiration,
+            phone: row.phone,
+            email: row.email,
+            is_active: row.is_active,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+            anonymized_at: row.anonymized_at,
+            trips: Self::json_rows(&self.pool, DRIVER_TRIPS, id).await?,
+            fines: Self::json_rows(&self.pool, DRIVER_FINES, id).await?,
+            fuelings: Self::json_rows(&self.pool, DRIVER_FUELINGS, id).await?,
+            fuel_card_transactions,
+            history: Self::json_rows(&self.pool, DRIVER_HISTORY, id).await?,
+        }))
+    }
+
+    async fn anonymize(
+        &self,
+        plan: &AnonymizationPlan,
+    ) -> Result<AnonymizationResult, RepositoryError> {
+        let mut tx = begin_audited(&self.pool).await?;
+        let anonymized_at = Utc::now();
+        let mut affected = Map::new();
+
+        let mut user_id = None;
+        if let Some(user) = &plan.user {
+            if self
+                .anonymize_user(&mut tx, user, anonymized_at, &mut affected)
+                .await?
+            {
+                user_id = Some(user.user_id);
+            }
+        }
+
+        let mut driver_id = None;
+        if let Some(driver) = &plan.driver {
+            if self
+                .anonymize_driver(&mut tx, driver, anonymized_at, &mut affected)
+                .await?
+            {
+                driver_id = Some(driver.driver_id);
+            }
+        }
+
+        tx.commit().await.map_err(map_db_error)?;
+
+        Ok(AnonymizationResult {
+            user_id,
+            driver_id,
+            anonymized_at,
+            affected: Value::Object(affected),
+            retained: Vec::new(),
+        })
+    }
+}
@@ -0,0 +1,217 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use domain::errors::RepositoryError;
+use domain::models::{AccountLockout, AccountLockoutDto};
+use domain::ports::LoginThrottleRepositoryPort;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::db_utils::map_db_error;
+
+#[derive(Clone)]
+pub struct LoginThrottleRepository {
+    pool: PgPool,
+}
+
+impl LoginThrottleRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl LoginThrottleRepositoryPort for LoginThrottleRepository {
+    async fn record_attempt(
+        &self,
+        user_id: Option<Uuid>,
+        identifier: &str,
+        ip_address: Option<&str>,
+        succeeded: bool,
+    ) -> Result<(), RepositoryError> {
+        sqlx::query(
+            r#"
+            INSERT INTO login_attempts (user_id, identifier, ip_address, succeeded)
+            VALUES ($1, LOWER($2), $3::INET, $4)
+            "#,
+        )
+        .bind(user_id)
+        .bind(identifier)
+        .bind(ip_address)
+        .bind(succeeded)
+        .execute(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+        Ok(())
+    }
+
+    async fn count_failures_in_network(
+        &self,
+        network: &str,
+        since: DateTime<Utc>,
+    ) -> Result<i64, RepositoryError> {
+        sqlx::query_scalar(
+            r#"
+            SELECT COUNT(*) FROM login_attempts
+            WHERE NOT succeeded
+              AND ip_address <<= $1::CIDR
+              AND created_at >= $2
+            "#,
+        )
+        .bind(network)
+        .bind(since)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn unknown_identifier_failures(
+        &self,
+        identifier: &str,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<DateTime<Utc>>, RepositoryError> {
+        sqlx::query_scalar(
+            r#"
+            SELECT created_at FROM login_attempts
+            WHERE user_id IS NULL
+              AND NOT succeeded
+              AND identifier = LOWER($1)
+              AND created_at >= $2
+            ORDER BY created_at
+            "#,
+        )
+        .bind(identifier)
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn purge_attempts_before(&self, before: DateTime<Utc>) -> Result<u64, RepositoryError> {
+        let result = sqlx::query("DELETE FROM login_attempts WHERE created_at < $1")
+            .bind(before)
+            .execute(&self.pool)
+            .await
+            .map_err(map_db_error)?;
+        Ok(result.rows_affected())
+    }
+
+    async fn get_lockout(&self, user_id: Uuid) -> Result<Option<AccountLockout>, RepositoryError> {
+        sqlx::query_as::<_, AccountLockout>(
+            r#"
+            SELECT user_id, failed_count, lockout_level, locked_until, last_failed_at,
+                   host(last_failed_ip) AS last_failed_ip
+            FROM account_lockouts
+            WHERE user_id = $1
+            "#,
+        )
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn increment_failures(
+        &self,
+        user_id: Uuid,
+        ip_address: Option<&str>,
+        now: DateTime<Utc>,
+        window_start: DateTime<Utc>,
+    ) -> Result<AccountLockout, RepositoryError> {
+        sqlx::query_as::<_, AccountLockout>(
+            r#"
+            INSERT INTO account_lockouts (user_id, failed_count, last_failed_at, last_failed_ip)
+            VALUES ($1, 1, $3, $2::INET)
+            ON CONFLICT (user_id) DO UPDATE SET
+                failed_count = CASE
+                    WHEN account_lockouts.last_failed_at >= $4 THEN account_lockouts.failed_count + 1
+                    ELSE 1
+                END,
+                last_failed_at = EXCLUDED.last_failed_at,
+                last_failed_ip = EXCLUDED.last_failed_ip,
+                updated_at = NOW()
+            RETURNING user_id, failed_count, lockout_level, locked_until, last_failed_at,
+                      host(last_failed_ip) AS last_failed_ip
+            "#,
+        )
+        .bind(user_id)
+        .bind(ip_address)
+        .bind(now)
+        .bind(window_start)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn lock_account(
+        &self,
+        lockout: &AccountLockout,
+        max_failures: i32,
+        unlock_token: (&str, DateTime<Utc>),
+    ) -> Result<bool, RepositoryError> {
+        let (token_hash, token_expires_at) = unlock_token;
+        let result = sqlx::query(
+            r#"
+            UPDATE account_lockouts SET
+                failed_count = 0,
+                lockout_level = $2,
+                locked_until = $3,
+                unlock_token_hash = $4,
+                unlock_token_expires_at = $5,
+                updated_at = NOW()
+            WHERE user_id = $1 AND failed_count >= $6
+            "#,
+        )
+        .bind(lockout.user_id)
+        .bind(lockout.lockout_level)
+        .bind(lockout.locked_until)
+        .bind(token_hash)
+        .bind(token_expires_at)
+        .bind(max_failures)
+        .execute(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn clear_lockout(&self, user_id: Uuid) -> Result<bool, RepositoryError> {
+        let result = sqlx::query("DELETE FROM account_lockouts WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&self.pool)
+            .await
+            .map_err(map_db_error)?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn find_user_by_unlock_token(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<Uuid>, RepositoryError> {
+        sqlx::query_scalar(
+            r#"
+            SELECT user_id FROM account_lockouts
+            WHERE unlock_token_hash = $1
+              AND unlock_token_expires_at > NOW()
+            "#,
+        )
+        .bind(token_hash)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn list_lockouts(&self) -> Result<Vec<AccountLockoutDto>, RepositoryError> {
+        sqlx::query_as::<_, AccountLockoutDto>(
+            r#"
+            SELECT l.user_id, u.username, l.failed_count, l.lockout_level, l.locked_until,
+                   l.last_failed_at, host(l.last_failed_ip) AS last_failed_ip, l.updated_at
+            FROM account_lockouts l
+            JOIN users u ON u.id = l.user_id
+            WHERE l.locked_until > NOW() OR l.failed_count > 0
+            ORDER BY l.locked_until DESC NULLS LAST, l.last_failed_at DESC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+}
@@ -1,6 +1,6 @@
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
-use core_services::field_encryption;
+use core_services::field_encryption::FieldKeyring;
 use domain::errors::RepositoryError;
 use domain::ports::MfaRepositoryPort;
 use sqlx::PgPool;
@@ -11,24 +11,23 @@ use crate::db_utils::map_db_error;
 #[derive(Clone)]
 pub struct MfaRepository {
     pool: PgPool,
-    encryption_key: [u8; 32],
+    keyring: FieldKeyring,
 }
 
 impl MfaRepository {
-    pub fn new(pool: PgPool, encryption_key: [u8; 32]) -> Self {
-        Self {
-            pool,
-            encryption_key,
-        }
+    pub fn new(pool: PgPool, keyring: FieldKeyring) -> Self {
+        Self { pool, keyring }
     }
 
     fn encrypt(&self, plaintext: &str) -> Result<String, RepositoryError> {
-        field_encryption::encrypt_field(plaintext, &self.encryption_key)
+        self.keyring
+            .encrypt(plaintext)
             .map_err(|e| RepositoryError::InvalidData(e.to_string()))
     }
 
     fn decrypt(&self, ciphertext: &str) -> Result<String, RepositoryError> {
-        field_encryption::decrypt_field(ciphertext, &self.encryption_key)
+        self.keyring
+            .decrypt(ciphertext)
             .map_err(|e| RepositoryError::InvalidData(e.to_string()))
     }
 }
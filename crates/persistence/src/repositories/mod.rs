@@ -1,12 +1,28 @@
 pub mod audit_logs_repository;
+pub mod audit_integrity_repository;
+pub mod entity_history_repository;
+pub mod lgpd_repository;
 pub mod auth_repository;
 pub mod budget_classifications_repository;
 pub mod catalog_repository;
+pub mod catalog_merge_repository;
+pub mod catalog_search_repository;
+pub mod catalog_sync_repository;
+pub mod field_key_rotation_repository;
+pub mod jwt_signing_key_repository;
+pub mod siorg_preview_repository;
+pub mod unit_restructuring_repository;
 pub mod departments_repository;
 pub mod email_verification_repository;
 pub mod facilities_repository;
 pub mod geo_regions_repository;
 pub mod mfa_repository;
+pub mod oidc_repository;
+pub mod webauthn_repository;
+pub mod login_throttle_repository;
+pub mod api_key_repository;
+pub mod delegation_repository;
+pub mod approval_chain_repository;
 pub mod organizational_repository;
 pub mod requisition_repository;
 pub mod session_repository;
@@ -16,6 +32,7 @@ pub mod supplier_repository;
 pub mod vehicle_repository;
 pub mod driver_repository;
 pub mod fueling_repository;
+pub mod fuel_card_repository;
 pub mod vehicle_fine_repository;
 pub mod invoice_repository;
 pub mod invoice_adjustment_repository;
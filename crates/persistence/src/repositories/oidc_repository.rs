@@ -0,0 +1,236 @@
+use async_trait::async_trait;
+use core_services::field_encryption::FieldKeyring;
+use domain::errors::RepositoryError;
+use domain::models::{IdentityLinkMethod, NewOidcAccount, OidcLoginState, UserIdentityDto};
+use domain::ports::OidcRepositoryPort;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::db_utils::map_db_error;
+
+/// OIDC identities and pending authorizations.
+///
+/// The `sub` claim (the CPF, for gov.br) and user CPFs are never stored in
+/// plain text — only their HMAC blind index, as done for `users.email_index`.
+/// Since the value cannot be recovered, an index computed with a previous key
+/// is rewritten with the current one the next time it matches.
+#[derive(Clone)]
+pub struct OidcRepository {
+    pool: PgPool,
+    keyring: FieldKeyring,
+}
+
+impl OidcRepository {
+    pub fn new(pool: PgPool, keyring: FieldKeyring) -> Self {
+        Self { pool, keyring }
+    }
+
+    fn blind_index(&self, value: &str) -> String {
+        self.keyring.blind_index(value)
+    }
+}
+
+#[async_trait]
+impl OidcRepositoryPort for OidcRepository {
+    async fn save_login_state(&self, state: &OidcLoginState) -> Result<(), RepositoryError> {
+        sqlx::query(
+            r#"
+            INSERT INTO oidc_login_states (state, provider, nonce, code_verifier, redirect_to, expires_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+        )
+        .bind(&state.state)
+        .bind(&state.provider)
+        .bind(&state.nonce)
+        .bind(&state.code_verifier)
+        .bind(&state.redirect_to)
+        .bind(state.expires_at)
+        .execute(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+        Ok(())
+    }
+
+    async fn take_login_state(&self, state: &str) -> Result<Option<OidcLoginState>, RepositoryError> {
+        sqlx::query_as::<_, OidcLoginState>(
+            r#"
+            DELETE FROM oidc_login_states
+            WHERE state = $1
+            RETURNING state, provider, nonce, code_verifier, redirect_to, expires_at
+            "#,
+        )
+        .bind(state)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn purge_expired_login_states(&self) -> Result<u64, RepositoryError> {
+        let result = sqlx::query("DELETE FROM oidc_login_states WHERE expires_at < NOW()")
+            .execute(&self.pool)
+            .await
+            .map_err(map_db_error)?;
+        Ok(result.rows_affected())
+    }
+
+    async fn find_identity(
+        &self,
+        provider: &str,
+        subject: &str,
+    ) -> Result<Option<UserIdentityDto>, RepositoryError> {
+        sqlx::query_as::<_, UserIdentityDto>(
+            r#"
+            WITH found AS (
+                SELECT id, user_id, provider, link_method, created_at, last_login_at, subject_index
+                FROM user_identities
+                WHERE provider = $1 AND subject_index = ANY($2)
+                LIMIT 1
+            ),
+            reindexed AS (
+                UPDATE user_identities i SET subject_index = $3
+                FROM found f
+                WHERE i.id = f.id AND f.subject_index <> $3
+            )
+            SELECT id, user_id, provider, link_method, created_at, last_login_at
+            FROM found
+            "#,
+        )
+        .bind(provider)
+        .bind(self.keyring.blind_indexes(subject))
+        .bind(self.blind_index(subject))
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn create_identity(
+        &self,
+        user_id: Uuid,
+        provider: &str,
+        subject: &str,
+        link_method: &IdentityLinkMethod,
+    ) -> Result<UserIdentityDto, RepositoryError> {
+        sqlx::query_as::<_, UserIdentityDto>(
+            r#"
+            INSERT INTO user_identities (user_id, provider, subject_index, link_method, last_login_at)
+            VALUES ($1, $2, $3, $4, NOW())
+            RETURNING id, user_id, provider, link_method, created_at, last_login_at
+            "#,
+        )
+        .bind(user_id)
+        .bind(provider)
+        .bind(self.blind_index(subject))
+        .bind(link_method)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn provision_account(
+        &self,
+        account: &NewOidcAccount,
+    ) -> Result<UserIdentityDto, RepositoryError> {
+        let email = account.email.as_str();
+        let email_encrypted = self
+            .keyring
+            .encrypt(email)
+            .map_err(|e| RepositoryError::InvalidData(e.to_string()))?;
+
+        let mut tx = self.pool.begin().await.map_err(map_db_error)?;
+
+        // CPF já pertencente a outra conta não é gravado
+        let user_id: Uuid = sqlx::query_scalar(
+            r#"
+            INSERT INTO users (
+                username, email, email_index, password_hash, role,
+                email_verified, email_verified_at, cpf_index
+            )
+            VALUES (
+                $1, $2, $3, $4, $5, $6, CASE WHEN $6 THEN NOW() END,
+                CASE WHEN NOT EXISTS (SELECT 1 FROM users WHERE cpf_index = ANY($8)) THEN $7 END
+            )
+            RETURNING id
+            "#,
+        )
+        .bind(account.username.as_str())
+        .bind(&email_encrypted)
+        .bind(self.blind_index(email))
+        .bind(&account.password_hash)
+        .bind(&account.role)
+        .bind(account.email_verified)
+        .bind(account.cpf.as_deref().map(|cpf| self.blind_index(cpf)))
+        .bind(
+            account
+                .cpf
+                .as_deref()
+                .map(|cpf| self.keyring.blind_indexes(cpf))
+                .unwrap_or_default(),
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(map_db_error)?;
+
+        let identity = sqlx::query_as::<_, UserIdentityDto>(
+            r#"
+            INSERT INTO user_identities (user_id, provider, subject_index, link_method, last_login_at)
+            VALUES ($1, $2, $3, $4, NOW())
+            RETURNING id, user_id, provider, link_method, created_at, last_login_at
+            "#,
+        )
+        .bind(user_id)
+        .bind(&account.provider)
+        .bind(self.blind_index(&account.subject))
+        .bind(IdentityLinkMethod::Jit)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(map_db_error)?;
+
+        tx.commit().await.map_err(map_db_error)?;
+        Ok(identity)
+    }
+
+    async fn touch_identity(&self, id: Uuid) -> Result<(), RepositoryError> {
+        sqlx::query("UPDATE user_identities SET last_login_at = NOW() WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(map_db_error)?;
+        Ok(())
+    }
+
+    async fn list_identities_by_user(&self, user_id: Uuid) -> Result<Vec<UserIdentityDto>, RepositoryError> {
+        sqlx::query_as::<_, UserIdentityDto>(
+            r#"
+            SELECT id, user_id, provider, link_method, created_at, last_login_at
+            FROM user_identities
+            WHERE user_id = $1
+            ORDER BY created_at
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn find_user_id_by_cpf(&self, cpf: &str) -> Result<Option<Uuid>, RepositoryError> {
+        sqlx::query_scalar::<_, Uuid>(
+            r#"
+            WITH found AS (
+                SELECT id, cpf_index FROM users WHERE cpf_index = ANY($1) LIMIT 1
+            ),
+            reindexed AS (
+                UPDATE users u SET cpf_index = $2
+                FROM found f
+                WHERE u.id = f.id AND f.cpf_index <> $2
+            )
+            SELECT id FROM found
+            "#,
+        )
+        .bind(self.keyring.blind_indexes(cpf))
+        .bind(self.blind_index(cpf))
+        .fetch_optional(&self.pool)
+            .await
+            .map_err(map_db_error)
+    }
+}
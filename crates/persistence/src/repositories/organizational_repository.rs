@@ -1,3 +1,4 @@
+use chrono::NaiveDate;
 use domain::errors::RepositoryError;
 use domain::models::{
     ActivityArea, ContactInfo, CreateOrganizationPayload, CreateOrganizationalUnitCategoryPayload,
@@ -22,6 +23,8 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use uuid::Uuid;
 
+use crate::db_utils::begin_audited;
+
 #[derive(sqlx::FromRow)]
 struct SiorgMapRow {
     siorg_code: Option<i32>,
@@ -846,6 +849,21 @@ impl OrganizationalUnitTypeRepositoryPort for OrganizationalUnitTypeRepository {
 // Organizational Unit Repository
 // ============================================================================
 
+/// Unit columns with name, parent, status and path taken from the structure
+/// in effect on a date (`s` = `fn_unit_structure_as_of`)
+const UNIT_AS_OF_COLUMNS: &str = r#"
+    u.id, u.organization_id, s.parent_id, u.category_id, u.unit_type_id,
+    s.name, u.formal_name, u.acronym,
+    u.siorg_code, u.siorg_parent_code, u.siorg_url, u.siorg_last_version, u.is_siorg_managed,
+    u.activity_area,
+    u.contact_info,
+    s.level, s.path_ids, s.path_names, s.is_active,
+    CASE WHEN s.is_active THEN NULL ELSE u.deactivated_at END AS deactivated_at,
+    CASE WHEN s.is_active THEN NULL ELSE u.deactivation_reason END AS deactivation_reason,
+    u.siorg_synced_at, u.siorg_sync_status,
+    u.siorg_raw_data, u.created_at, u.updated_at
+"#;
+
 pub struct OrganizationalUnitRepository {
     pool: Arc<PgPool>,
 }
@@ -1125,26 +1143,48 @@ impl OrganizationalUnitRepositoryPort for OrganizationalUnitRepository {
     async fn get_tree(
         &self,
         organization_id: Option<Uuid>,
+        as_of: Option<NaiveDate>,
     ) -> Result<Vec<OrganizationalUnitTreeNode>, RepositoryError> {
-        let all_units = sqlx::query_as::<_, OrganizationalUnitDto>(
-            r#"
-            SELECT
-                id, organization_id, parent_id, category_id, unit_type_id,
-                name, formal_name, acronym,
-                siorg_code, siorg_parent_code, siorg_url, siorg_last_version, is_siorg_managed,
-                activity_area,
-                contact_info,
-                level, path_ids, path_names, is_active, deactivated_at, deactivation_reason,
-                siorg_synced_at, siorg_sync_status,
-                siorg_raw_data, created_at, updated_at
-            FROM organizational_units
-            WHERE ($1::UUID IS NULL OR organization_id = $1)
-            ORDER BY level, name
-            "#,
-        )
-        .bind(organization_id)
-        .fetch_all(&*self.pool)
-        .await
+        let all_units = match as_of {
+            Some(date) => {
+                let query = format!(
+                    r#"
+                    SELECT {}
+                    FROM fn_unit_structure_as_of($2) s
+                    JOIN organizational_units u ON u.id = s.unit_id
+                    WHERE ($1::UUID IS NULL OR u.organization_id = $1)
+                    ORDER BY s.level, s.name
+                    "#,
+                    UNIT_AS_OF_COLUMNS
+                );
+                sqlx::query_as::<_, OrganizationalUnitDto>(&query)
+                    .bind(organization_id)
+                    .bind(date)
+                    .fetch_all(&*self.pool)
+                    .await
+            }
+            None => {
+                sqlx::query_as::<_, OrganizationalUnitDto>(
+                    r#"
+                    SELECT
+                        id, organization_id, parent_id, category_id, unit_type_id,
+                        name, formal_name, acronym,
+                        siorg_code, siorg_parent_code, siorg_url, siorg_last_version, is_siorg_managed,
+                        activity_area,
+                        contact_info,
+                        level, path_ids, path_names, is_active, deactivated_at, deactivation_reason,
+                        siorg_synced_at, siorg_sync_status,
+                        siorg_raw_data, created_at, updated_at
+                    FROM organizational_units
+                    WHERE ($1::UUID IS NULL OR organization_id = $1)
+                    ORDER BY level, name
+                    "#,
+                )
+                    .bind(organization_id)
+                    .fetch_all(&*self.pool)
+                    .await
+            }
+        }
         .map_err(|e| RepositoryError::Database(e.to_string()))?;
 
         let nodes: Vec<OrganizationalUnitTreeNode> = all_units
@@ -1174,7 +1214,27 @@ impl OrganizationalUnitRepositoryPort for OrganizationalUnitRepository {
     async fn get_children(
         &self,
         parent_id: Uuid,
+        as_of: Option<NaiveDate>,
     ) -> Result<Vec<OrganizationalUnitDto>, RepositoryError> {
+        if let Some(date) = as_of {
+            let query = format!(
+                r#"
+                SELECT {}
+                FROM fn_unit_structure_as_of($2) s
+                JOIN organizational_units u ON u.id = s.unit_id
+                WHERE s.parent_id = $1
+                ORDER BY s.name
+                "#,
+                UNIT_AS_OF_COLUMNS
+            );
+            return sqlx::query_as::<_, OrganizationalUnitDto>(&query)
+                .bind(parent_id)
+                .bind(date)
+                .fetch_all(&*self.pool)
+                .await
+                .map_err(|e| RepositoryError::Database(e.to_string()));
+        }
+
         let result = sqlx::query_as::<_, OrganizationalUnitDto>(
             r#"
             SELECT
@@ -1213,7 +1273,34 @@ impl OrganizationalUnitRepositoryPort for OrganizationalUnitRepository {
     async fn get_path_to_root(
         &self,
         id: Uuid,
+        as_of: Option<NaiveDate>,
     ) -> Result<Vec<OrganizationalUnitDto>, RepositoryError> {
+        if let Some(date) = as_of {
+            let query = format!(
+                r#"
+                WITH structure AS (SELECT * FROM fn_unit_structure_as_of($2))
+                SELECT {}
+                FROM structure s
+                JOIN structure target ON target.unit_id = $1
+                JOIN organizational_units u ON u.id = s.unit_id
+                WHERE s.unit_id = ANY(target.path_ids)
+                ORDER BY s.level
+                "#,
+                UNIT_AS_OF_COLUMNS
+            );
+            let path = sqlx::query_as::<_, OrganizationalUnitDto>(&query)
+                .bind(id)
+                .bind(date)
+                .fetch_all(&*self.pool)
+                .await
+                .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+            if path.is_empty() {
+                return Err(RepositoryError::NotFound);
+            }
+            return Ok(path);
+        }
+
         let unit = self.find_by_id(id).await?;
 
         let unit = match unit {
@@ -1235,6 +1322,7 @@ impl OrganizationalUnitRepositoryPort for OrganizationalUnitRepository {
         &self,
         payload: CreateOrganizationalUnitPayload,
     ) -> Result<OrganizationalUnitDto, RepositoryError> {
+        let mut tx = begin_audited(&self.pool).await?;
         let result = sqlx::query_as::<_, OrganizationalUnitDto>(
             r#"
             INSERT INTO organizational_units (
@@ -1264,9 +1352,10 @@ impl OrganizationalUnitRepositoryPort for OrganizationalUnitRepository {
         .bind(payload.activity_area as ActivityArea)
         .bind(serde_json::to_value(&payload.contact_info).unwrap())
         .bind(payload.is_active)
-        .fetch_one(&*self.pool)
+        .fetch_one(&mut *tx)
         .await
         .map_err(|e| RepositoryError::Database(e.to_string()))?;
+        tx.commit().await.map_err(|e| RepositoryError::Database(e.to_string()))?;
 
         Ok(result)
     }
@@ -1281,6 +1370,7 @@ impl OrganizationalUnitRepositoryPort for OrganizationalUnitRepository {
             .as_ref()
             .map(|c| serde_json::to_value(c).unwrap());
 
+        let mut tx = begin_audited(&self.pool).await?;
         let result = sqlx::query_as::<_, OrganizationalUnitDto>(
             r#"
             UPDATE organizational_units
@@ -1319,19 +1409,22 @@ impl OrganizationalUnitRepositoryPort for OrganizationalUnitRepository {
         .bind(contact_info_json)
         .bind(payload.is_active)
         .bind(payload.deactivation_reason)
-        .fetch_one(&*self.pool)
+        .fetch_one(&mut *tx)
         .await
         .map_err(|e| RepositoryError::Database(e.to_string()))?;
+        tx.commit().await.map_err(|e| RepositoryError::Database(e.to_string()))?;
 
         Ok(result)
     }
 
     async fn delete(&self, id: Uuid) -> Result<(), RepositoryError> {
+        let mut tx = begin_audited(&self.pool).await?;
         let result = sqlx::query("DELETE FROM organizational_units WHERE id = $1")
             .bind(id)
-            .execute(&*self.pool)
+            .execute(&mut *tx)
             .await
             .map_err(|e| RepositoryError::Database(e.to_string()))?;
+        tx.commit().await.map_err(|e| RepositoryError::Database(e.to_string()))?;
 
         if result.rows_affected() == 0 {
             return Err(RepositoryError::NotFound);
@@ -1341,6 +1434,7 @@ impl OrganizationalUnitRepositoryPort for OrganizationalUnitRepository {
     }
 
     async fn deactivate(&self, id: Uuid, reason: Option<String>) -> Result<(), RepositoryError> {
+        let mut tx = begin_audited(&self.pool).await?;
         let result = sqlx::query(
             r#"
             UPDATE organizational_units
@@ -1350,9 +1444,10 @@ impl OrganizationalUnitRepositoryPort for OrganizationalUnitRepository {
         )
         .bind(id)
         .bind(reason)
-        .execute(&*self.pool)
+        .execute(&mut *tx)
         .await
         .map_err(|e| RepositoryError::Database(e.to_string()))?;
+        tx.commit().await.map_err(|e| RepositoryError::Database(e.to_string()))?;
 
         if result.rows_affected() == 0 {
             return Err(RepositoryError::NotFound);
@@ -1362,6 +1457,7 @@ impl OrganizationalUnitRepositoryPort for OrganizationalUnitRepository {
     }
 
     async fn activate(&self, id: Uuid) -> Result<(), RepositoryError> {
+        let mut tx = begin_audited(&self.pool).await?;
         let result = sqlx::query(
             r#"
             UPDATE organizational_units
@@ -1370,9 +1466,10 @@ impl OrganizationalUnitRepositoryPort for OrganizationalUnitRepository {
             "#,
         )
         .bind(id)
-        .execute(&*self.pool)
+        .execute(&mut *tx)
         .await
         .map_err(|e| RepositoryError::Database(e.to_string()))?;
+        tx.commit().await.map_err(|e| RepositoryError::Database(e.to_string()))?;
 
         if result.rows_affected() == 0 {
             return Err(RepositoryError::NotFound);
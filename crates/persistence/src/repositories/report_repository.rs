@@ -1,9 +1,9 @@
 use async_trait::async_trait;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use domain::{
     errors::RepositoryError,
-    models::report::{FuelConsumptionDto, FleetSummaryDto, VehicleDashboardDto},
-    ports::report::FleetReportRepositoryPort,
+    models::report::{FuelConsumptionDto, FleetSummaryDto, UnitCostDto, VehicleDashboardDto},
+    ports::report::{FleetReportRepositoryPort, UnitCostReportRepositoryPort},
 };
 use sqlx::PgPool;
 use uuid::Uuid;
@@ -46,6 +46,7 @@ impl FleetReportRepositoryPort for FleetReportRepository {
                 SUM(NULLIF(f.odometer_km - f.km_anterior, 0))::BIGINT      AS total_km_driven
             FROM vehicles v
             LEFT JOIN fuelings f ON f.vehicle_id = v.id
+                AND f.review_status <> 'REJEITADO'
                 AND ($2::TIMESTAMPTZ IS NULL OR f.fueling_date >= $2)
                 AND ($3::TIMESTAMPTZ IS NULL OR f.fueling_date <= $3)
             WHERE v.is_deleted = false
@@ -103,7 +104,7 @@ impl FleetReportRepositoryPort for FleetReportRepository {
             LEFT JOIN LATERAL (
                 SELECT SUM(total_cost) AS total_fuel_cost
                 FROM fuelings
-                WHERE vehicle_id = v.id
+                WHERE vehicle_id = v.id AND review_status <> 'REJEITADO'
                   AND ($2::TIMESTAMPTZ IS NULL OR fueling_date >= $2)
                   AND ($3::TIMESTAMPTZ IS NULL OR fueling_date <= $3)
             ) fuel_stats ON true
@@ -161,7 +162,8 @@ impl FleetReportRepositoryPort for FleetReportRepository {
                    AND ($1::TIMESTAMPTZ IS NULL OR created_at >= $1)
                    AND ($2::TIMESTAMPTZ IS NULL OR created_at <= $2))   AS monthly_km,
                 (SELECT SUM(total_cost) FROM fuelings
-                 WHERE ($1::TIMESTAMPTZ IS NULL OR fueling_date >= $1)
+                 WHERE review_status <> 'REJEITADO'
+                   AND ($1::TIMESTAMPTZ IS NULL OR fueling_date >= $1)
                    AND ($2::TIMESTAMPTZ IS NULL OR fueling_date <= $2)) AS monthly_fuel_cost,
                 (SELECT SUM(custo_real) FROM vehicle_maintenance_orders
                  WHERE status = 'CONCLUIDA'
@@ -169,7 +171,8 @@ impl FleetReportRepositoryPort for FleetReportRepository {
                    AND ($2::TIMESTAMPTZ IS NULL OR created_at <= $2))   AS monthly_maintenance_cost,
                 (
                     COALESCE((SELECT SUM(total_cost) FROM fuelings
-                     WHERE ($1::TIMESTAMPTZ IS NULL OR fueling_date >= $1)
+                     WHERE review_status <> 'REJEITADO'
+                       AND ($1::TIMESTAMPTZ IS NULL OR fueling_date >= $1)
                        AND ($2::TIMESTAMPTZ IS NULL OR fueling_date <= $2)), 0)
                   + COALESCE((SELECT SUM(custo_real) FROM vehicle_maintenance_orders
                      WHERE status = 'CONCLUIDA'
@@ -186,3 +189,92 @@ impl FleetReportRepositoryPort for FleetReportRepository {
         .map_err(map_db_error)
     }
 }
+
+pub struct UnitCostReportRepository {
+    pool: PgPool,
+}
+
+impl UnitCostReportRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl UnitCostReportRepositoryPort for UnitCostReportRepository {
+    async fn requisition_costs_by_unit(
+        &self,
+        organization_id: Option<Uuid>,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+        as_of: NaiveDate,
+    ) -> Result<Vec<UnitCostDto>, RepositoryError> {
+        sqlx::query_as::<_, UnitCostDto>(
+            r#"
+            SELECT
+                fn_unit_as_of(r.destination_unit_id, $4)                    AS unit_id,
+                COUNT(*)                                                    AS document_count,
+                COALESCE(SUM(r.total_value), 0)                             AS total_cost
+            FROM requisitions r
+            JOIN organizational_units u ON u.id = r.destination_unit_id
+            WHERE r.status IN ('FULFILLED', 'PARTIALLY_FULFILLED')
+              AND ($1::UUID IS NULL OR u.organization_id = $1)
+              AND ($2::DATE IS NULL OR COALESCE(r.fulfilled_at, r.request_date)::DATE >= $2)
+              AND ($3::DATE IS NULL OR COALESCE(r.fulfilled_at, r.request_date)::DATE <= $3)
+            GROUP BY 1
+            "#,
+        )
+        .bind(organization_id)
+        .bind(start_date)
+        .bind(end_date)
+        .bind(as_of)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn fleet_costs_by_unit(
+        &self,
+        organization_id: Option<Uuid>,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+        as_of: NaiveDate,
+    ) -> Result<Vec<UnitCostDto>, RepositoryError> {
+        sqlx::query_as::<_, UnitCostDto>(
+            r#"
+            WITH expenses AS (
+                SELECT f.vehicle_id, f.fueling_date::DATE AS spent_on, f.total_cost AS cost
+                FROM fuelings f
+                WHERE f.review_status <> 'REJEITADO'
+                UNION ALL
+                SELECT m.vehicle_id, COALESCE(m.data_conclusao, m.created_at::DATE), m.custo_real
+                FROM vehicle_maintenance_orders m
+                WHERE m.status = 'CONCLUIDA' AND m.custo_real IS NOT NULL
+            ),
+            attributed AS (
+                SELECT
+                    fn_unit_as_of(fn_vehicle_department_at(e.vehicle_id, e.spent_on), $4) AS unit_id,
+                    e.cost
+                FROM expenses e
+                WHERE ($2::DATE IS NULL OR e.spent_on >= $2)
+                  AND ($3::DATE IS NULL OR e.spent_on <= $3)
+            )
+            SELECT
+                a.unit_id,
+                COUNT(*)                                                    AS document_count,
+                COALESCE(SUM(a.cost), 0)                                    AS total_cost
+            FROM attributed a
+            LEFT JOIN organizational_units u ON u.id = a.unit_id
+            WHERE $1::UUID IS NULL OR u.organization_id = $1
+            GROUP BY a.unit_id
+            "#,
+        )
+        .bind(organization_id)
+        .bind(start_date)
+        .bind(end_date)
+        .bind(as_of)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+}
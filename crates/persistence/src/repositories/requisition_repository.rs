@@ -91,6 +91,7 @@ impl RequisitionRepositoryPort for RequisitionRepository {
         id: Uuid,
         rejected_by: Uuid,
         reason: &str,
+        on_behalf_of: Option<Uuid>,
     ) -> Result<RequisitionDto, RepositoryError> {
         sqlx::query_as::<_, RequisitionDto>(
             r#"
@@ -99,6 +100,7 @@ impl RequisitionRepositoryPort for RequisitionRepository {
                 approved_by = $2,
                 approved_at = NOW(),
                 rejection_reason = $3,
+                approved_on_behalf_of = $4,
                 updated_at = NOW()
             WHERE id = $1
             RETURNING *
@@ -107,6 +109,7 @@ impl RequisitionRepositoryPort for RequisitionRepository {
         .bind(id)
         .bind(rejected_by)
         .bind(reason)
+        .bind(on_behalf_of)
         .fetch_one(&self.pool)
         .await
         .map_err(map_db_error)
@@ -234,6 +237,7 @@ impl RequisitionRepositoryPort for RequisitionRepository {
         status: Option<RequisitionStatus>,
         requester_id: Option<Uuid>,
         warehouse_id: Option<Uuid>,
+        unit_ids: Option<&[Uuid]>,
     ) -> Result<(Vec<RequisitionDto>, i64), RepositoryError> {
         let requisitions = sqlx::query_as::<_, RequisitionDto>(
             r#"
@@ -241,6 +245,7 @@ impl RequisitionRepositoryPort for RequisitionRepository {
             WHERE ($1::requisition_status_enum IS NULL OR status = $1)
               AND ($2::UUID IS NULL OR requester_id = $2)
               AND ($3::UUID IS NULL OR warehouse_id = $3)
+              AND ($6::UUID[] IS NULL OR destination_unit_id = ANY($6))
             ORDER BY created_at DESC
             LIMIT $4 OFFSET $5
             "#,
@@ -250,6 +255,7 @@ impl RequisitionRepositoryPort for RequisitionRepository {
         .bind(warehouse_id)
         .bind(limit)
         .bind(offset)
+        .bind(unit_ids)
         .fetch_all(&self.pool)
         .await
         .map_err(map_db_error)?;
@@ -260,11 +266,13 @@ impl RequisitionRepositoryPort for RequisitionRepository {
             WHERE ($1::requisition_status_enum IS NULL OR status = $1)
               AND ($2::UUID IS NULL OR requester_id = $2)
               AND ($3::UUID IS NULL OR warehouse_id = $3)
+              AND ($4::UUID[] IS NULL OR destination_unit_id = ANY($4))
             "#,
         )
         .bind(status)
         .bind(requester_id)
         .bind(warehouse_id)
+        .bind(unit_ids)
         .fetch_one(&self.pool)
         .await
         .map_err(map_db_error)?;
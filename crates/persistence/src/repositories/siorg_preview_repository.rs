@@ -0,0 +1,206 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use domain::errors::RepositoryError;
+use domain::models::{
+    NewSiorgChangePreview, SiorgChangePreviewDto, SiorgLocalUnit, SiorgPreviewStatus,
+    SiorgUnitDiff, SiorgUnitReferences,
+};
+use domain::ports::SiorgChangePreviewRepositoryPort;
+use sqlx::{types::Json, PgPool, Row};
+use uuid::Uuid;
+
+use crate::db_utils::{begin_audited, map_db_error};
+
+const PREVIEW_COLUMNS: &str = r#"id, organization_id, org_siorg_code, from_version, to_version,
+    status, total_changes, flagged_changes, entries,
+    created_by, created_at, decided_by, decided_at"#;
+
+pub struct SiorgChangePreviewRepository {
+    pool: PgPool,
+}
+
+impl SiorgChangePreviewRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl SiorgChangePreviewRepositoryPort for SiorgChangePreviewRepository {
+    async fn list_local_units(
+        &self,
+        organization_id: Uuid,
+    ) -> Result<Vec<SiorgLocalUnit>, RepositoryError> {
+        sqlx::query_as::<_, SiorgLocalUnit>(
+            r#"SELECT id, siorg_code, name, acronym, siorg_parent_code, is_active
+            FROM organizational_units
+            WHERE organization_id = $1 AND siorg_code IS NOT NULL"#,
+        )
+        .bind(organization_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn count_references(
+        &self,
+        unit_ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, SiorgUnitReferences>, RepositoryError> {
+        if unit_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let rows = sqlx::query(
+            r#"SELECT u.id,
+                (SELECT COUNT(*) FROM warehouses w
+                 WHERE w.responsible_unit_id = u.id AND w.is_active) AS warehouses,
+                (SELECT COUNT(*) FROM vehicles v
+                 WHERE v.department_id = u.id AND NOT v.is_deleted) AS vehicles,
+                (SELECT COUNT(*) FROM requisitions r
+                 WHERE r.destination_unit_id = u.id
+                   AND r.status NOT IN ('REJECTED', 'FULFILLED', 'CANCELLED')) AS open_requisitions
+            FROM UNNEST($1::uuid[]) AS u(id)"#,
+        )
+        .bind(unit_ids)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                (
+                    row.get::<Uuid, _>("id"),
+                    SiorgUnitReferences {
+                        warehouses: row.get("warehouses"),
+                        vehicles: row.get("vehicles"),
+                        open_requisitions: row.get("open_requisitions"),
+                    },
+                )
+            })
+            .filter(|(_, refs)| !refs.is_empty())
+            .collect())
+    }
+
+    async fn create(
+        &self,
+        preview: &NewSiorgChangePreview,
+    ) -> Result<SiorgChangePreviewDto, RepositoryError> {
+        let mut tx = begin_audited(&self.pool).await?;
+
+        sqlx::query(
+            r#"UPDATE siorg_change_previews
+            SET status = 'DISCARDED', decided_by = $2, decided_at = NOW()
+            WHERE organization_id = $1 AND status = 'PENDING'"#,
+        )
+        .bind(preview.organization_id)
+        .bind(preview.created_by)
+        .execute(&mut *tx)
+        .await
+        .map_err(map_db_error)?;
+
+        let flagged = preview.entries.iter().filter(|e| e.flagged).count() as i32;
+        let created = sqlx::query_as::<_, SiorgChangePreviewDto>(&format!(
+            r#"INSERT INTO siorg_change_previews
+                (organization_id, org_siorg_code, from_version, to_version,
+                 total_changes, flagged_changes, entries, created_by)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING {PREVIEW_COLUMNS}"#
+        ))
+        .bind(preview.organization_id)
+        .bind(preview.org_siorg_code)
+        .bind(&preview.from_version)
+        .bind(&preview.to_version)
+        .bind(preview.entries.len() as i32)
+        .bind(flagged)
+        .bind(Json(&preview.entries))
+        .bind(preview.created_by)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(map_db_error)?;
+
+        tx.commit().await.map_err(map_db_error)?;
+        Ok(created)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<SiorgChangePreviewDto>, RepositoryError> {
+        sqlx::query_as::<_, SiorgChangePreviewDto>(&format!(
+            "SELECT {PREVIEW_COLUMNS} FROM siorg_change_previews WHERE id = $1"
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn list(
+        &self,
+        organization_id: Option<Uuid>,
+        status: Option<SiorgPreviewStatus>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<SiorgChangePreviewDto>, i64), RepositoryError> {
+        let previews = sqlx::query_as::<_, SiorgChangePreviewDto>(&format!(
+            r#"SELECT {} FROM siorg_change_previews
+            WHERE ($1::uuid IS NULL OR organization_id = $1)
+              AND ($2::siorg_preview_status_enum IS NULL OR status = $2)
+            ORDER BY created_at DESC
+            LIMIT $3 OFFSET $4"#,
+            PREVIEW_COLUMNS.replace("entries,", "NULL::jsonb AS entries,")
+        ))
+        .bind(organization_id)
+        .bind(status)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        let total: i64 = sqlx::query_scalar(
+            r#"SELECT COUNT(*) FROM siorg_change_previews
+            WHERE ($1::uuid IS NULL OR organization_id = $1)
+              AND ($2::siorg_preview_status_enum IS NULL OR status = $2)"#,
+        )
+        .bind(organization_id)
+        .bind(status)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        Ok((previews, total))
+    }
+
+    async fn finish(
+        &self,
+        id: Uuid,
+        status: SiorgPreviewStatus,
+        entries: Option<&[SiorgUnitDiff]>,
+        decided_by: Option<Uuid>,
+    ) -> Result<SiorgChangePreviewDto, RepositoryError> {
+        let updated = sqlx::query_as::<_, SiorgChangePreviewDto>(&format!(
+            r#"UPDATE siorg_change_previews
+            SET status = $2, entries = COALESCE($3, entries),
+                decided_by = $4, decided_at = NOW()
+            WHERE id = $1 AND status = 'PENDING'
+            RETURNING {PREVIEW_COLUMNS}"#
+        ))
+        .bind(id)
+        .bind(status)
+        .bind(entries.map(Json))
+        .bind(decided_by)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        match updated {
+            Some(preview) => Ok(preview),
+            None => match self.find_by_id(id).await? {
+                Some(_) => Err(RepositoryError::OptimisticLockConflict(
+                    "A prévia já foi aplicada ou descartada".to_string(),
+                )),
+                None => Err(RepositoryError::NotFound),
+            },
+        }
+    }
+}
@@ -8,7 +8,7 @@ use rust_decimal::Decimal;
 use sqlx::{PgPool, Row};
 use uuid::Uuid;
 
-use crate::db_utils::map_db_error;
+use crate::db_utils::{begin_audited, map_db_error};
 
 pub struct SupplierRepository {
     pool: PgPool,
@@ -81,7 +81,8 @@ impl SupplierRepositoryPort for SupplierRepository {
         phone: Option<&str>,
         created_by: Option<Uuid>,
     ) -> Result<SupplierDto, RepositoryError> {
-        sqlx::query_as::<_, SupplierDto>(
+        let mut tx = begin_audited(&self.pool).await?;
+        let saved = sqlx::query_as::<_, SupplierDto>(
             r#"INSERT INTO suppliers (legal_name, trade_name, document_number,
                                       representative_name, address, neighborhood,
                                       city_id, zip_code, email, phone, created_by, updated_by)
@@ -99,9 +100,12 @@ impl SupplierRepositoryPort for SupplierRepository {
         .bind(email)
         .bind(phone)
         .bind(created_by)
-        .fetch_one(&self.pool)
+        .fetch_one(&mut *tx)
         .await
-        .map_err(map_db_error)
+        .map_err(map_db_error)?;
+
+        tx.commit().await.map_err(map_db_error)?;
+        Ok(saved)
     }
 
     async fn update(
@@ -120,7 +124,8 @@ impl SupplierRepositoryPort for SupplierRepository {
         is_active: Option<bool>,
         updated_by: Option<Uuid>,
     ) -> Result<SupplierDto, RepositoryError> {
-        sqlx::query_as::<_, SupplierDto>(
+        let mut tx = begin_audited(&self.pool).await?;
+        let saved = sqlx::query_as::<_, SupplierDto>(
             r#"UPDATE suppliers SET
                 legal_name = COALESCE($2, legal_name),
                 trade_name = COALESCE($3, trade_name),
@@ -150,17 +155,22 @@ impl SupplierRepositoryPort for SupplierRepository {
         .bind(phone)
         .bind(is_active)
         .bind(updated_by)
-        .fetch_one(&self.pool)
+        .fetch_one(&mut *tx)
         .await
-        .map_err(map_db_error)
+        .map_err(map_db_error)?;
+
+        tx.commit().await.map_err(map_db_error)?;
+        Ok(saved)
     }
 
     async fn delete(&self, id: Uuid) -> Result<bool, RepositoryError> {
+        let mut tx = begin_audited(&self.pool).await?;
         let result = sqlx::query("DELETE FROM suppliers WHERE id = $1")
             .bind(id)
-            .execute(&self.pool)
+            .execute(&mut *tx)
             .await
             .map_err(map_db_error)?;
+        tx.commit().await.map_err(map_db_error)?;
         Ok(result.rows_affected() > 0)
     }
 
@@ -240,14 +250,16 @@ impl SupplierRepositoryPort for SupplierRepository {
     }
 
     async fn update_quality_score(&self, id: Uuid, score: Decimal) -> Result<(), RepositoryError> {
+        let mut tx = begin_audited(&self.pool).await?;
         sqlx::query(
             "UPDATE suppliers SET quality_score = $2, updated_at = NOW() WHERE id = $1",
         )
         .bind(id)
         .bind(score)
-        .execute(&self.pool)
+        .execute(&mut *tx)
         .await
         .map_err(map_db_error)?;
+        tx.commit().await.map_err(map_db_error)?;
         Ok(())
     }
 }
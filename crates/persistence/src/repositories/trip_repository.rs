@@ -10,6 +10,78 @@ use uuid::Uuid;
 
 use crate::db_utils::map_db_error;
 
+/// Unified view of everything that occupies a vehicle or driver: allocated or
+/// in-progress trips and open maintenance orders. `range_end` is the instant
+/// the booking stops blocking the calendar — a trip without planned return
+/// blocks only its departure instant (or until now, if already under way),
+/// and a maintenance order without expected completion blocks indefinitely.
+///
+/// Bookings are half-open `[starts_at, range_end)`: a trip returning at the
+/// instant another departs does not conflict with it. Two bookings starting
+/// at the same instant always do, even when one of them is a single instant.
+const BOOKINGS_CTE: &str = r#"
+    WITH bookings AS (
+        SELECT
+            'VIAGEM'::TEXT                  AS kind,
+            t.id                            AS reference_id,
+            t.vehicle_id,
+            v.license_plate::TEXT           AS license_plate,
+            t.driver_id,
+            d.full_name::TEXT               AS driver_name,
+            t.data_saida_prevista           AS starts_at,
+            t.data_retorno_prevista         AS ends_at,
+            t.status::TEXT                  AS status,
+            t.destino                       AS description,
+            CASE
+                WHEN t.status = 'EM_CURSO'
+                THEN GREATEST(COALESCE(t.data_retorno_prevista, t.data_saida_prevista), NOW())
+                ELSE COALESCE(t.data_retorno_prevista, t.data_saida_prevista)
+            END                             AS range_end
+        FROM vehicle_trips t
+        JOIN vehicles v     ON v.id = t.vehicle_id
+        LEFT JOIN drivers d ON d.id = t.driver_id
+        WHERE t.status IN ('ALOCADA', 'EM_CURSO')
+        UNION ALL
+        SELECT
+            'MANUTENCAO'::TEXT,
+            o.id,
+            o.vehicle_id,
+            v.license_plate::TEXT,
+            NULL::UUID,
+            NULL::TEXT,
+            o.data_abertura::TIMESTAMPTZ,
+            (o.data_prevista_conclusao + 1)::TIMESTAMPTZ,
+            o.status::TEXT,
+            o.titulo,
+            COALESCE((o.data_prevista_conclusao + 1)::TIMESTAMPTZ, 'infinity'::TIMESTAMPTZ)
+        FROM vehicle_maintenance_orders o
+        JOIN vehicles v ON v.id = o.vehicle_id
+        WHERE o.status IN ('ABERTA', 'EM_EXECUCAO')
+    )
+"#;
+
+const BOOKING_COLUMNS: &str =
+    "kind, reference_id, vehicle_id, license_plate, driver_id, driver_name, starts_at, ends_at, status, description";
+
+/// `b` overlaps the window `[$1, $2)`.
+const OVERLAPS: &str = "((b.starts_at < $2 AND b.range_end > $1) OR b.starts_at = $1)";
+
+/// Bookings of vehicle `$3` or driver `$4` overlapping `[$1, $2)`, except
+/// trip `$5`.
+fn conflicts_sql() -> String {
+    format!(
+        r#"
+        {BOOKINGS_CTE}
+        SELECT {BOOKING_COLUMNS}
+        FROM bookings b
+        WHERE {OVERLAPS}
+          AND (b.vehicle_id = $3 OR ($4::UUID IS NOT NULL AND b.driver_id = $4))
+          AND ($5::UUID IS NULL OR b.kind <> 'VIAGEM' OR b.reference_id <> $5)
+        ORDER BY b.starts_at
+        "#
+    )
+}
+
 pub struct VehicleTripRepository {
     pool: PgPool,
 }
@@ -72,17 +144,19 @@ impl VehicleTripRepositoryPort for VehicleTripRepository {
         &self,
         id: Uuid,
         approved_by: Uuid,
+        on_behalf_of: Option<Uuid>,
         version: i32,
     ) -> Result<VehicleTripDto, RepositoryError> {
         let result = sqlx::query_as::<_, VehicleTripDto>(
             r#"
             UPDATE vehicle_trips
-            SET status       = 'APROVADA',
-                aprovado_por = $2,
-                aprovado_em  = NOW(),
-                version      = version + 1,
-                updated_by   = $2,
-                updated_at   = NOW()
+            SET status              = 'APROVADA',
+                aprovado_por        = $2,
+                aprovado_em         = NOW(),
+                aprovado_em_nome_de = $4,
+                version             = version + 1,
+                updated_by          = $2,
+                updated_at          = NOW()
             WHERE id = $1 AND version = $3 AND status = 'SOLICITADA'
             RETURNING *
             "#,
@@ -90,6 +164,7 @@ impl VehicleTripRepositoryPort for VehicleTripRepository {
         .bind(id)
         .bind(approved_by)
         .bind(version)
+        .bind(on_behalf_of)
         .fetch_optional(&self.pool)
         .await
         .map_err(map_db_error)?;
@@ -102,18 +177,20 @@ impl VehicleTripRepositoryPort for VehicleTripRepository {
         id: Uuid,
         rejection_reason: &str,
         rejected_by: Uuid,
+        on_behalf_of: Option<Uuid>,
         version: i32,
     ) -> Result<VehicleTripDto, RepositoryError> {
         let result = sqlx::query_as::<_, VehicleTripDto>(
             r#"
             UPDATE vehicle_trips
-            SET status          = 'REJEITADA',
-                motivo_rejeicao = $2,
-                aprovado_por    = $3,
-                aprovado_em     = NOW(),
-                version         = version + 1,
-                updated_by      = $3,
-                updated_at      = NOW()
+            SET status              = 'REJEITADA',
+                motivo_rejeicao     = $2,
+                aprovado_por        = $3,
+                aprovado_em         = NOW(),
+                aprovado_em_nome_de = $5,
+                version             = version + 1,
+                updated_by          = $3,
+                updated_at          = NOW()
             WHERE id = $1 AND version = $4 AND status = 'SOLICITADA'
             RETURNING *
             "#,
@@ -122,6 +199,7 @@ impl VehicleTripRepositoryPort for VehicleTripRepository {
         .bind(rejection_reason)
         .bind(rejected_by)
         .bind(version)
+        .bind(on_behalf_of)
         .fetch_optional(&self.pool)
         .await
         .map_err(map_db_error)?;
@@ -134,30 +212,48 @@ impl VehicleTripRepositoryPort for VehicleTripRepository {
         trip_id: Uuid,
         vehicle_id: Uuid,
         driver_id: Uuid,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
         allocated_by: Uuid,
         version: i32,
-    ) -> Result<VehicleTripDto, RepositoryError> {
+    ) -> Result<TripAllocationOutcome, RepositoryError> {
         let mut tx = self.pool.begin().await.map_err(map_db_error)?;
 
-        // Pessimistic lock: fail immediately if another transaction holds the lock.
-        let lock_ok = sqlx::query(
-            "SELECT id FROM vehicles WHERE id = $1 FOR UPDATE NOWAIT",
-        )
-        .bind(vehicle_id)
-        .execute(&mut *tx)
-        .await;
+        // Pessimistic lock: fail immediately if another transaction holds the
+        // vehicle or the driver.
+        for (lock_sql, entity, id) in [
+            ("SELECT id FROM vehicles WHERE id = $1 FOR UPDATE NOWAIT", "vehicle", vehicle_id),
+            ("SELECT id FROM drivers WHERE id = $1 FOR UPDATE NOWAIT", "driver", driver_id),
+        ] {
+            if sqlx::query(lock_sql).bind(id).execute(&mut *tx).await.is_err() {
+                let _ = tx.rollback().await;
+                return Err(RepositoryError::OptimisticLockConflict(
+                    format!("{}:{} is locked by a concurrent allocation", entity, id),
+                ));
+            }
+        }
 
-        if lock_ok.is_err() {
+        // Conflict check under the locks: any allocation committed before we
+        // got them is visible here, and later ones wait for our commit.
+        let conflicts = sqlx::query_as::<_, TripBookingDto>(&conflicts_sql())
+            .bind(start)
+            .bind(end)
+            .bind(vehicle_id)
+            .bind(driver_id)
+            .bind(trip_id)
+            .fetch_all(&mut *tx)
+            .await
+            .map_err(map_db_error)?;
+        if !conflicts.is_empty() {
             let _ = tx.rollback().await;
-            return Err(RepositoryError::OptimisticLockConflict(
-                format!("vehicle:{} is locked by a concurrent allocation", vehicle_id),
-            ));
+            return Ok(TripAllocationOutcome::Conflicts(conflicts));
         }
 
         let update_result = sqlx::query_as::<_, VehicleTripDto>(
             r#"
             UPDATE vehicle_trips
             SET status       = 'ALOCADA',
+                vehicle_id   = $2,
                 driver_id    = $3,
                 allocated_at = NOW(),
                 allocated_by = $4,
@@ -187,9 +283,11 @@ impl VehicleTripRepositoryPort for VehicleTripRepository {
 
         tx.commit().await.map_err(map_db_error)?;
 
-        result.ok_or_else(|| {
-            RepositoryError::OptimisticLockConflict(format!("vehicle_trip:{}", trip_id))
-        })
+        result
+            .map(|trip| TripAllocationOutcome::Allocated(Box::new(trip)))
+            .ok_or_else(|| {
+                RepositoryError::OptimisticLockConflict(format!("vehicle_trip:{}", trip_id))
+            })
     }
 
     async fn checkout(
@@ -366,6 +464,7 @@ impl VehicleTripRepositoryPort for VehicleTripRepository {
         status: Option<TripStatus>,
         limit: i64,
         offset: i64,
+        unit_ids: Option<&[Uuid]>,
     ) -> Result<(Vec<VehicleTripDto>, i64), RepositoryError> {
         let rows = sqlx::query_as::<_, VehicleTripDto>(
             r#"
@@ -373,6 +472,9 @@ impl VehicleTripRepositoryPort for VehicleTripRepository {
             WHERE ($1::UUID IS NULL OR vehicle_id = $1)
               AND ($2::UUID IS NULL OR driver_id = $2)
               AND ($3::trip_status_enum IS NULL OR status = $3)
+              AND ($6::UUID[] IS NULL OR vehicle_id IN (
+                  SELECT id FROM vehicles WHERE department_id = ANY($6)
+              ))
             ORDER BY data_saida_prevista DESC
             LIMIT $4 OFFSET $5
             "#,
@@ -382,6 +484,7 @@ impl VehicleTripRepositoryPort for VehicleTripRepository {
         .bind(&status)
         .bind(limit)
         .bind(offset)
+        .bind(unit_ids)
         .fetch_all(&self.pool)
         .await
         .map_err(map_db_error)?;
@@ -392,15 +495,266 @@ impl VehicleTripRepositoryPort for VehicleTripRepository {
             WHERE ($1::UUID IS NULL OR vehicle_id = $1)
               AND ($2::UUID IS NULL OR driver_id = $2)
               AND ($3::trip_status_enum IS NULL OR status = $3)
+              AND ($4::UUID[] IS NULL OR vehicle_id IN (
+                  SELECT id FROM vehicles WHERE department_id = ANY($4)
+              ))
             "#,
         )
         .bind(vehicle_id)
         .bind(driver_id)
         .bind(&status)
+        .bind(unit_ids)
         .fetch_one(&self.pool)
         .await
         .map_err(map_db_error)?;
 
         Ok((rows, total))
     }
+
+    async fn list_bookings(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        vehicle_id: Option<Uuid>,
+        driver_id: Option<Uuid>,
+    ) -> Result<Vec<TripBookingDto>, RepositoryError> {
+        let sql = format!(
+            r#"
+            {BOOKINGS_CTE}
+            SELECT {BOOKING_COLUMNS}
+            FROM bookings
+            WHERE starts_at <= $2 AND range_end >= $1
+              AND ($3::UUID IS NULL OR vehicle_id = $3)
+              AND ($4::UUID IS NULL OR driver_id = $4)
+            ORDER BY starts_at, license_plate
+            "#
+        );
+
+        sqlx::query_as::<_, TripBookingDto>(&sql)
+            .bind(start)
+            .bind(end)
+            .bind(vehicle_id)
+            .bind(driver_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(map_db_error)
+    }
+
+    async fn find_conflicts(
+        &self,
+        vehicle_id: Uuid,
+        driver_id: Option<Uuid>,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        exclude_trip_id: Option<Uuid>,
+    ) -> Result<Vec<TripBookingDto>, RepositoryError> {
+        sqlx::query_as::<_, TripBookingDto>(&conflicts_sql())
+            .bind(start)
+            .bind(end)
+            .bind(vehicle_id)
+            .bind(driver_id)
+            .bind(exclude_trip_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(map_db_error)
+    }
+
+    async fn find_available_vehicles(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        category_id: Option<Uuid>,
+        min_seats: Option<i32>,
+    ) -> Result<Vec<AvailableVehicleDto>, RepositoryError> {
+        let sql = format!(
+            r#"
+            {BOOKINGS_CTE}
+            SELECT
+                v.id                    AS vehicle_id,
+                v.license_plate::TEXT   AS license_plate,
+                vm.id                   AS model_id,
+                vm.name::TEXT           AS model_name,
+                vm.category_id,
+                vc.name::TEXT           AS category_name,
+                vm.passenger_capacity,
+                v.fuel_type_id,
+                v.department_id,
+                v.last_odometer_km
+            FROM vehicles v
+            JOIN vehicle_models vm          ON vm.id = v.model_id
+            LEFT JOIN vehicle_categories vc ON vc.id = vm.category_id
+            WHERE v.is_deleted = false
+              AND v.operational_status = 'ATIVO'
+              AND ($3::UUID IS NULL OR vm.category_id = $3)
+              AND ($4::INT IS NULL OR vm.passenger_capacity >= $4)
+              AND NOT EXISTS (
+                  SELECT 1 FROM bookings b
+                  WHERE b.vehicle_id = v.id
+                    AND {OVERLAPS}
+              )
+            ORDER BY vm.passenger_capacity NULLS LAST, v.license_plate
+            "#
+        );
+
+        sqlx::query_as::<_, AvailableVehicleDto>(&sql)
+            .bind(start)
+            .bind(end)
+            .bind(category_id)
+            .bind(min_seats)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(map_db_error)
+    }
+
+    async fn find_allocation_candidates(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        min_seats: Option<i32>,
+        reference_vehicle_id: Uuid,
+    ) -> Result<Vec<AllocationCandidateDto>, RepositoryError> {
+        // Next preventive maintenance: last completed PREVENTIVA order odometer
+        // plus the model interval; without any such order, the next multiple
+        // of the interval.
+        let sql = format!(
+            r#"
+            {BOOKINGS_CTE}
+            SELECT
+                v.id                    AS vehicle_id,
+                v.license_plate::TEXT   AS license_plate,
+                vm.name::TEXT           AS model_name,
+                vm.category_id,
+                vc.name::TEXT           AS category_name,
+                vm.passenger_capacity,
+                v.last_odometer_km,
+                CASE
+                    WHEN vm.preventive_interval_km IS NULL OR v.last_odometer_km IS NULL THEN NULL
+                    WHEN lp.km IS NULL THEN
+                        (vm.preventive_interval_km - v.last_odometer_km % vm.preventive_interval_km)::BIGINT
+                    ELSE lp.km + vm.preventive_interval_km - v.last_odometer_km
+                END                     AS km_to_preventive,
+                COALESCE(vm.category_id = ref.category_id, false) AS same_category
+            FROM vehicles v
+            JOIN vehicle_models vm          ON vm.id = v.model_id
+            LEFT JOIN vehicle_categories vc ON vc.id = vm.category_id
+            LEFT JOIN LATERAL (
+                SELECT MAX(mo.km_abertura) AS km
+                FROM vehicle_maintenance_orders mo
+                WHERE mo.vehicle_id = v.id
+                  AND mo.tipo = 'PREVENTIVA'
+                  AND mo.status = 'CONCLUIDA'
+            ) lp ON TRUE
+            LEFT JOIN LATERAL (
+                SELECT rvm.category_id
+                FROM vehicles rv
+                JOIN vehicle_models rvm ON rvm.id = rv.model_id
+                WHERE rv.id = $4
+            ) ref ON TRUE
+            WHERE v.is_deleted = false
+              AND v.operational_status = 'ATIVO'
+              AND ($3::INT IS NULL OR vm.passenger_capacity IS NULL OR vm.passenger_capacity >= $3)
+              AND NOT EXISTS (
+                  SELECT 1 FROM bookings b
+                  WHERE b.vehicle_id = v.id
+                    AND {OVERLAPS}
+              )
+            ORDER BY v.license_plate
+            "#
+        );
+
+        sqlx::query_as::<_, AllocationCandidateDto>(&sql)
+            .bind(start)
+            .bind(end)
+            .bind(min_seats)
+            .bind(reference_vehicle_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(map_db_error)
+    }
+
+    async fn replace_suggestions(
+        &self,
+        trip_id: Uuid,
+        suggestions: &[NewTripSuggestion],
+        created_by: Option<Uuid>,
+    ) -> Result<Vec<TripAllocationSuggestionDto>, RepositoryError> {
+        let mut tx = self.pool.begin().await.map_err(map_db_error)?;
+
+        sqlx::query("DELETE FROM trip_allocation_suggestions WHERE trip_id = $1")
+            .bind(trip_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(map_db_error)?;
+
+        for s in suggestions {
+            sqlx::query(
+                r#"
+                INSERT INTO trip_allocation_suggestions
+                    (trip_id, vehicle_id, rank, score, reasons, created_by)
+                VALUES ($1, $2, $3, $4, $5, $6)
+                "#,
+            )
+            .bind(trip_id)
+            .bind(s.vehicle_id)
+            .bind(s.rank)
+            .bind(s.score)
+            .bind(&s.reasons)
+            .bind(created_by)
+            .execute(&mut *tx)
+            .await
+            .map_err(map_db_error)?;
+        }
+
+        tx.commit().await.map_err(map_db_error)?;
+
+        self.list_suggestions(trip_id).await
+    }
+
+    async fn list_suggestions(
+        &self,
+        trip_id: Uuid,
+    ) -> Result<Vec<TripAllocationSuggestionDto>, RepositoryError> {
+        sqlx::query_as::<_, TripAllocationSuggestionDto>(
+            r#"
+            SELECT s.id, s.trip_id, s.vehicle_id, v.license_plate::TEXT AS license_plate,
+                   s.rank, s.score, s.reasons, s.accepted_at, s.created_by, s.created_at
+            FROM trip_allocation_suggestions s
+            LEFT JOIN vehicles v ON v.id = s.vehicle_id
+            WHERE s.trip_id = $1
+            ORDER BY s.rank
+            "#,
+        )
+        .bind(trip_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn find_suggestion(
+        &self,
+        id: Uuid,
+    ) -> Result<Option<TripAllocationSuggestionDto>, RepositoryError> {
+        sqlx::query_as::<_, TripAllocationSuggestionDto>(
+            r#"
+            SELECT s.id, s.trip_id, s.vehicle_id, v.license_plate::TEXT AS license_plate,
+                   s.rank, s.score, s.reasons, s.accepted_at, s.created_by, s.created_at
+            FROM trip_allocation_suggestions s
+            LEFT JOIN vehicles v ON v.id = s.vehicle_id
+            WHERE s.id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn mark_suggestion_accepted(&self, id: Uuid) -> Result<(), RepositoryError> {
+        sqlx::query("UPDATE trip_allocation_suggestions SET accepted_at = NOW() WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(map_db_error)?;
+        Ok(())
+    }
 }
@@ -0,0 +1,445 @@
+use std::collections::{HashMap, HashSet};
+
+use async_trait::async_trait;
+use domain::errors::RepositoryError;
+use domain::models::{
+    CreateUnitRestructuringPayload, RestructuringMigrated, RestructuringReference, UnitLineageDto,
+    UnitRestructuringDto,
+};
+use domain::ports::UnitRestructuringRepositoryPort;
+use sqlx::{types::Json, PgPool, Postgres, Row, Transaction};
+use uuid::Uuid;
+
+use crate::db_utils::{begin_audited, map_db_error};
+
+const RESTRUCTURING_COLUMNS: &str = r#"id, kind, organization_id, effective_date, reason,
+    documento_sei, migrated, created_by, created_at"#;
+
+const LINEAGE_SELECT: &str = r#"SELECT l.restructuring_id, r.kind,
+        l.predecessor_id, p.name AS predecessor_name,
+        l.successor_id, s.name AS successor_name,
+        l.is_primary, l.effective_date
+    FROM organizational_unit_lineage l
+    JOIN organizational_unit_restructurings r ON r.id = l.restructuring_id
+    JOIN organizational_units p ON p.id = l.predecessor_id
+    JOIN organizational_units s ON s.id = l.successor_id"#;
+
+/// Requisitions still moving through the workflow
+const OPEN_REQUISITION_FILTER: &str = "status NOT IN ('REJECTED', 'FULFILLED', 'CANCELLED')";
+
+/// Casbin grouping type of the unit roles
+const UNIT_ROLE_PTYPE: &str = "g2";
+
+pub struct UnitRestructuringRepository {
+    pool: PgPool,
+}
+
+impl UnitRestructuringRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    async fn lineage_for(
+        &self,
+        restructuring_ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, Vec<UnitLineageDto>>, RepositoryError> {
+        let links = sqlx::query_as::<_, UnitLineageDto>(&format!(
+            "{LINEAGE_SELECT} WHERE l.restructuring_id = ANY($1) ORDER BY p.name, s.name"
+        ))
+        .bind(restructuring_ids)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        let mut by_restructuring: HashMap<Uuid, Vec<UnitLineageDto>> = HashMap::new();
+        for link in links {
+            by_restructuring
+                .entry(link.restructuring_id)
+                .or_default()
+                .push(link);
+        }
+        Ok(by_restructuring)
+    }
+}
+
+/// Locks the involved units and checks that they can be restructured together
+async fn lock_units(
+    tx: &mut Transaction<'_, Postgres>,
+    payload: &CreateUnitRestructuringPayload,
+) -> Result<Uuid, RepositoryError> {
+    let unit_ids: Vec<Uuid> = payload
+        .predecessor_ids
+        .iter()
+        .chain(&payload.successor_ids)
+        .copied()
+        .collect();
+
+    let rows = sqlx::query(
+        r#"SELECT id, organization_id, is_active
+        FROM organizational_units
+        WHERE id = ANY($1)
+        FOR UPDATE"#,
+    )
+    .bind(&unit_ids)
+    .fetch_all(&mut **tx)
+    .await
+    .map_err(map_db_error)?;
+
+    if rows.len() != unit_ids.len() {
+        return Err(RepositoryError::NotFound);
+    }
+
+    let organizations: HashSet<Uuid> = rows.iter().map(|r| r.get("organization_id")).collect();
+    if organizations.len() > 1 {
+        return Err(RepositoryError::InvalidData(
+            "As unidades reestruturadas devem pertencer à mesma organização".to_string(),
+        ));
+    }
+
+    let inactive_successor = rows.iter().any(|r| {
+        payload.successor_ids.contains(&r.get::<Uuid, _>("id")) && !r.get::<bool, _>("is_active")
+    });
+    if inactive_successor {
+        return Err(RepositoryError::InvalidData(
+            "As unidades sucessoras devem estar ativas".to_string(),
+        ));
+    }
+
+    let already_replaced: bool = sqlx::query_scalar(
+        r#"SELECT EXISTS(
+            SELECT 1 FROM organizational_unit_lineage
+            WHERE predecessor_id = ANY($1) AND is_primary
+        )"#,
+    )
+    .bind(&payload.predecessor_ids)
+    .fetch_one(&mut **tx)
+    .await
+    .map_err(map_db_error)?;
+    if already_replaced {
+        return Err(RepositoryError::Duplicate(
+            "Uma das unidades sucedidas já foi reestruturada".to_string(),
+        ));
+    }
+
+    Ok(rows[0].get("organization_id"))
+}
+
+/// Overrides of one reference type as parallel `(ids, successor_ids)` arrays
+fn overrides_of(
+    payload: &CreateUnitRestructuringPayload,
+    reference: RestructuringReference,
+) -> (Vec<Uuid>, Vec<Uuid>) {
+    payload
+        .overrides
+        .iter()
+        .filter(|o| o.reference == reference)
+        .map(|o| (o.id, o.successor_id))
+        .unzip()
+}
+
+#[async_trait]
+impl UnitRestructuringRepositoryPort for UnitRestructuringRepository {
+    async fn restructure(
+        &self,
+        payload: &CreateUnitRestructuringPayload,
+        primary_successor_id: Uuid,
+        created_by: Option<Uuid>,
+    ) -> Result<UnitRestructuringDto, RepositoryError> {
+        let mut tx = begin_audited(&self.pool).await?;
+        let organization_id = lock_units(&mut tx, payload).await?;
+        let predecessors = &payload.predecessor_ids;
+        let mut migrated = RestructuringMigrated::default();
+
+        let id: Uuid = sqlx::query_scalar(
+            r#"INSERT INTO organizational_unit_restructurings
+                (kind, organization_id, effective_date, reason, documento_sei, created_by)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING id"#,
+        )
+        .bind(payload.kind)
+        .bind(organization_id)
+        .bind(payload.effective_date)
+        .bind(&payload.reason)
+        .bind(payload.documento_sei.as_deref())
+        .bind(created_by)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(map_db_error)?;
+
+        sqlx::query(
+            r#"INSERT INTO organizational_unit_lineage
+                (restructuring_id, predecessor_id, successor_id, is_primary, effective_date)
+            SELECT $1, p.id, s.id, s.id = $4, $5
+            FROM UNNEST($2::uuid[]) AS p(id)
+            CROSS JOIN UNNEST($3::uuid[]) AS s(id)"#,
+        )
+        .bind(id)
+        .bind(predecessors)
+        .bind(&payload.successor_ids)
+        .bind(primary_successor_id)
+        .bind(payload.effective_date)
+        .execute(&mut *tx)
+        .await
+        .map_err(map_db_error)?;
+
+        // Almoxarifados: primeiro os direcionados, depois o restante para a principal
+        let (warehouse_ids, warehouse_targets) =
+            overrides_of(payload, RestructuringReference::Warehouse);
+        if !warehouse_ids.is_empty() {
+            let moved = sqlx::query(
+                r#"UPDATE warehouses w SET responsible_unit_id = o.successor_id
+                FROM UNNEST($1::uuid[], $2::uuid[]) AS o(id, successor_id)
+                WHERE w.id = o.id AND w.responsible_unit_id = ANY($3)"#,
+            )
+            .bind(&warehouse_ids)
+            .bind(&warehouse_targets)
+            .bind(predecessors)
+            .execute(&mut *tx)
+            .await
+            .map_err(map_db_error)?
+            .rows_affected();
+            if moved as usize != warehouse_ids.len() {
+                return Err(RepositoryError::InvalidData(
+                    "Almoxarifado direcionado não pertence à unidade sucedida".to_string(),
+                ));
+            }
+            migrated.warehouses += moved as i64;
+        }
+
+        migrated.warehouses += sqlx::query(
+            "UPDATE warehouses SET responsible_unit_id = $1 WHERE responsible_unit_id = ANY($2)",
+        )
+        .bind(primary_successor_id)
+        .bind(predecessors)
+        .execute(&mut *tx)
+        .await
+        .map_err(map_db_error)?
+        .rows_affected() as i64;
+
+        // Requisições encerradas permanecem na unidade antiga (mapeadas pela linhagem)
+        migrated.open_requisitions = sqlx::query(&format!(
+            r#"UPDATE requisitions SET destination_unit_id = $1
+            WHERE destination_unit_id = ANY($2) AND {OPEN_REQUISITION_FILTER}"#
+        ))
+        .bind(primary_successor_id)
+        .bind(predecessors)
+        .execute(&mut *tx)
+        .await
+        .map_err(map_db_error)?
+        .rows_affected() as i64;
+
+        // Veículos: transferência departamental registrada para cada um
+        let (vehicle_ids, vehicle_targets) = overrides_of(payload, RestructuringReference::Vehicle);
+        if !vehicle_ids.is_empty() {
+            let owned: i64 = sqlx::query_scalar(
+                r#"SELECT COUNT(*) FROM vehicles
+                WHERE id = ANY($1) AND department_id = ANY($2) AND NOT is_deleted"#,
+            )
+            .bind(&vehicle_ids)
+            .bind(predecessors)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(map_db_error)?;
+            if owned as usize != vehicle_ids.len() {
+                return Err(RepositoryError::InvalidData(
+                    "Veículo direcionado não pertence à unidade sucedida".to_string(),
+                ));
+            }
+        }
+
+        migrated.vehicles = sqlx::query(
+            r#"WITH moved AS (
+                INSERT INTO vehicle_department_transfers
+                    (vehicle_id, dept_origem_id, dept_destino_id, data_efetiva,
+                     motivo, documento_sei, created_by)
+                SELECT v.id, v.department_id, COALESCE(o.successor_id, $3), $5, $6, $7, $8
+                FROM vehicles v
+                LEFT JOIN UNNEST($1::uuid[], $2::uuid[]) AS o(id, successor_id) ON o.id = v.id
+                WHERE v.department_id = ANY($4) AND NOT v.is_deleted
+                RETURNING vehicle_id, dept_destino_id
+            )
+            UPDATE vehicles v
+            SET department_id = m.dept_destino_id, version = v.version + 1
+            FROM moved m
+            WHERE v.id = m.vehicle_id"#,
+        )
+        .bind(&vehicle_ids)
+        .bind(&vehicle_targets)
+        .bind(primary_successor_id)
+        .bind(predecessors)
+        .bind(payload.effective_date)
+        .bind(&payload.reason)
+        .bind(payload.documento_sei.as_deref())
+        .bind(created_by)
+        .execute(&mut *tx)
+        .await
+        .map_err(map_db_error)?
+        .rows_affected() as i64;
+
+        migrated.approval_chains = sqlx::query(
+            r#"UPDATE approval_chains SET destination_unit_id = $1, updated_at = NOW()
+            WHERE destination_unit_id = ANY($2)"#,
+        )
+        .bind(primary_successor_id)
+        .bind(predecessors)
+        .execute(&mut *tx)
+        .await
+        .map_err(map_db_error)?
+        .rows_affected() as i64;
+
+        // Papéis por unidade (Casbin): o domínio "<unidade>" ou "<unidade>/*"
+        // passa para a principal, preservando a abrangência da subárvore
+        let domains: Vec<String> = predecessors.iter().map(Uuid::to_string).collect();
+        let subtree_domains: Vec<String> = domains.iter().map(|d| format!("{d}/*")).collect();
+        migrated.unit_roles = sqlx::query_scalar(
+            r#"WITH removed AS (
+                DELETE FROM casbin_rule
+                WHERE ptype = $1 AND (v2 = ANY($2) OR v2 = ANY($3))
+                RETURNING ptype, v0, v1, v2, v3, v4, v5
+            ),
+            inserted AS (
+                INSERT INTO casbin_rule (ptype, v0, v1, v2, v3, v4, v5)
+                SELECT ptype, v0, v1,
+                       CASE WHEN v2 LIKE '%/*' THEN $4 || '/*' ELSE $4 END,
+                       v3, v4, v5
+                FROM removed
+                ON CONFLICT ON CONSTRAINT unique_key DO NOTHING
+            )
+            SELECT COUNT(*) FROM removed"#,
+        )
+        .bind(UNIT_ROLE_PTYPE)
+        .bind(&domains)
+        .bind(&subtree_domains)
+        .bind(primary_successor_id.to_string())
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(map_db_error)?;
+
+        let involved: Vec<Uuid> = predecessors
+            .iter()
+            .chain(&payload.successor_ids)
+            .copied()
+            .collect();
+        let children: Vec<Uuid> = sqlx::query_scalar(
+            r#"UPDATE organizational_units SET parent_id = $1
+            WHERE parent_id = ANY($2) AND NOT (id = ANY($3))
+            RETURNING id"#,
+        )
+        .bind(primary_successor_id)
+        .bind(predecessors)
+        .bind(&involved)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(map_db_error)?;
+        for child in &children {
+            sqlx::query("SELECT fn_update_descendant_paths($1)")
+                .bind(child)
+                .execute(&mut *tx)
+                .await
+                .map_err(map_db_error)?;
+        }
+        migrated.child_units = children.len() as i64;
+
+        if payload.deactivate_predecessors {
+            sqlx::query(
+                r#"UPDATE organizational_units
+                SET is_active = FALSE, deactivated_at = NOW(), deactivation_reason = $2
+                WHERE id = ANY($1) AND is_active"#,
+            )
+            .bind(predecessors)
+            .bind(&payload.reason)
+            .execute(&mut *tx)
+            .await
+            .map_err(map_db_error)?;
+        }
+
+        sqlx::query("UPDATE organizational_unit_restructurings SET migrated = $2 WHERE id = $1")
+            .bind(id)
+            .bind(Json(migrated))
+            .execute(&mut *tx)
+            .await
+            .map_err(map_db_error)?;
+
+        tx.commit().await.map_err(map_db_error)?;
+
+        self.find_by_id(id).await?.ok_or(RepositoryError::NotFound)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<UnitRestructuringDto>, RepositoryError> {
+        let restructuring = sqlx::query_as::<_, UnitRestructuringDto>(&format!(
+            "SELECT {RESTRUCTURING_COLUMNS} FROM organizational_unit_restructurings WHERE id = $1"
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        let Some(mut restructuring) = restructuring else {
+            return Ok(None);
+        };
+        restructuring.lineage = self
+            .lineage_for(&[id])
+            .await?
+            .remove(&id)
+            .unwrap_or_default();
+        Ok(Some(restructuring))
+    }
+
+    async fn list(
+        &self,
+        organization_id: Option<Uuid>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<UnitRestructuringDto>, i64), RepositoryError> {
+        let mut restructurings = sqlx::query_as::<_, UnitRestructuringDto>(&format!(
+            r#"SELECT {RESTRUCTURING_COLUMNS} FROM organizational_unit_restructurings
+            WHERE ($1::uuid IS NULL OR organization_id = $1)
+            ORDER BY effective_date DESC, created_at DESC
+            LIMIT $2 OFFSET $3"#
+        ))
+        .bind(organization_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        let total: i64 = sqlx::query_scalar(
+            r#"SELECT COUNT(*) FROM organizational_unit_restructurings
+            WHERE ($1::uuid IS NULL OR organization_id = $1)"#,
+        )
+        .bind(organization_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        let ids: Vec<Uuid> = restructurings.iter().map(|r| r.id).collect();
+        let mut lineage = self.lineage_for(&ids).await?;
+        for restructuring in &mut restructurings {
+            restructuring.lineage = lineage.remove(&restructuring.id).unwrap_or_default();
+        }
+
+        Ok((restructurings, total))
+    }
+
+    async fn lineage_of(&self, unit_id: Uuid) -> Result<Vec<UnitLineageDto>, RepositoryError> {
+        sqlx::query_as::<_, UnitLineageDto>(&format!(
+            r#"{LINEAGE_SELECT}
+            WHERE l.predecessor_id = $1 OR l.successor_id = $1
+            ORDER BY l.effective_date, r.created_at"#
+        ))
+        .bind(unit_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn current_unit(&self, unit_id: Uuid) -> Result<Uuid, RepositoryError> {
+        sqlx::query_scalar("SELECT fn_unit_current(id) FROM organizational_units WHERE id = $1")
+            .bind(unit_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(map_db_error)?
+            .ok_or(RepositoryError::NotFound)
+    }
+}
@@ -1,5 +1,5 @@
 use async_trait::async_trait;
-use core_services::field_encryption;
+use core_services::field_encryption::FieldKeyring;
 use domain::errors::RepositoryError;
 use domain::models::{UserDto, UserDtoExtended, UserLoginInfo};
 use domain::ports::UserRepositoryPort;
@@ -45,15 +45,12 @@ struct RawUserExtendedRow {
 #[derive(Clone)]
 pub struct UserRepository {
     pool: PgPool,
-    encryption_key: [u8; 32],
+    keyring: FieldKeyring,
 }
 
 impl UserRepository {
-    pub fn new(pool: PgPool, encryption_key: [u8; 32]) -> Self {
-        Self {
-            pool,
-            encryption_key,
-        }
+    pub fn new(pool: PgPool, keyring: FieldKeyring) -> Self {
+        Self { pool, keyring }
     }
 
     // -----------------------------------------------------------------------
@@ -61,14 +58,15 @@ impl UserRepository {
     // -----------------------------------------------------------------------
 
     fn encrypt_email(&self, email: &str) -> Result<String, RepositoryError> {
-        field_encryption::encrypt_field(email, &self.encryption_key)
+        self.keyring
+            .encrypt(email)
             .map_err(|e| RepositoryError::InvalidData(e.to_string()))
     }
 
     /// Decrypts email ciphertext. Falls back to returning the raw value during
     /// the migration period when rows may still contain plaintext.
     fn decrypt_email(&self, raw: &str) -> Result<String, RepositoryError> {
-        match field_encryption::decrypt_field(raw, &self.encryption_key) {
+        match self.keyring.decrypt(raw) {
             Ok(plain) => Ok(plain),
             // If decryption fails the value is likely a legacy plaintext email.
             Err(_) => Ok(raw.to_string()),
@@ -76,7 +74,13 @@ impl UserRepository {
     }
 
     fn email_index(&self, email: &str) -> String {
-        field_encryption::blind_index(email, &self.encryption_key)
+        self.keyring.blind_index(email)
+    }
+
+    /// Indexes under every key in the ring, so rows not yet re-indexed by a
+    /// key rotation still match.
+    fn email_indexes(&self, email: &str) -> Vec<String> {
+        self.keyring.blind_indexes(email)
     }
 
     fn to_user_dto(&self, row: RawUserRow) -> Result<UserDto, RepositoryError> {
@@ -141,7 +145,12 @@ impl UserRepositoryPort for UserRepository {
             r#"
             SELECT
                 id, username, email, role,
-                email_verified, email_verified_at, mfa_enabled,
+                email_verified, email_verified_at,
+                -- Segundo fator exigido se houver TOTP ou passkey utilizável
+                (mfa_enabled OR EXISTS (
+                    SELECT 1 FROM webauthn_credentials c
+                    WHERE c.user_id = users.id AND c.clone_detected_at IS NULL
+                )) AS mfa_enabled,
                 is_banned, banned_at, banned_reason,
                 created_at, updated_at
             FROM users
@@ -191,10 +200,10 @@ impl UserRepositoryPort for UserRepository {
     }
 
     async fn find_by_email(&self, email: &Email) -> Result<Option<UserDto>, RepositoryError> {
-        let idx = self.email_index(email.as_str());
+        let idx = self.email_indexes(email.as_str());
         let row: Option<RawUserRow> = sqlx::query_as(
             "SELECT id, username, email, created_at, updated_at \
-             FROM users WHERE email_index = $1",
+             FROM users WHERE email_index = ANY($1)",
         )
         .bind(&idx)
         .fetch_optional(&self.pool)
@@ -206,10 +215,12 @@ impl UserRepositoryPort for UserRepository {
 
     async fn find_for_login(&self, identifier: &str) -> Result<Option<UserLoginInfo>, RepositoryError> {
         // Compute the email blind index so we can match encrypted emails.
-        let idx = self.email_index(identifier);
+        let idx = self.email_indexes(identifier);
         sqlx::query_as::<_, UserLoginInfo>(
-            "SELECT id, username, password_hash, mfa_enabled \
-             FROM users WHERE username = $1 OR email_index = $2",
+            "SELECT id, username, password_hash, \
+                    (mfa_enabled OR EXISTS (SELECT 1 FROM webauthn_credentials c \
+                     WHERE c.user_id = users.id AND c.clone_detected_at IS NULL)) AS mfa_enabled \
+             FROM users WHERE username = $1 OR email_index = ANY($2)",
         )
         .bind(identifier)
         .bind(&idx)
@@ -219,9 +230,9 @@ impl UserRepositoryPort for UserRepository {
     }
 
     async fn exists_by_email(&self, email: &Email) -> Result<bool, RepositoryError> {
-        let idx = self.email_index(email.as_str());
+        let idx = self.email_indexes(email.as_str());
         sqlx::query_scalar(
-            "SELECT EXISTS(SELECT 1 FROM users WHERE email_index = $1)",
+            "SELECT EXISTS(SELECT 1 FROM users WHERE email_index = ANY($1))",
         )
         .bind(&idx)
         .fetch_one(&self.pool)
@@ -242,9 +253,9 @@ impl UserRepositoryPort for UserRepository {
         email: &Email,
         exclude_id: Uuid,
     ) -> Result<bool, RepositoryError> {
-        let idx = self.email_index(email.as_str());
+        let idx = self.email_indexes(email.as_str());
         sqlx::query_scalar(
-            "SELECT EXISTS(SELECT 1 FROM users WHERE email_index = $1 AND id != $2)",
+            "SELECT EXISTS(SELECT 1 FROM users WHERE email_index = ANY($1) AND id != $2)",
         )
         .bind(&idx)
         .bind(exclude_id)
@@ -1,5 +1,5 @@
 use async_trait::async_trait;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use domain::{
     errors::RepositoryError,
     models::vehicle_fine::*,
@@ -206,8 +206,10 @@ impl VehicleFineRepositoryPort for VehicleFineRepository {
                       vf.auto_number, vf.fine_date, vf.notification_date, vf.due_date,
                       vf.location, vf.sei_process_number,
                       vf.fine_amount, vf.discount_amount, vf.paid_amount, vf.payment_date,
-                      vf.status, vf.notes, vf.is_deleted,
-                      vf.created_at, vf.updated_at
+                      vf.status, vf.notes,
+                      vf.trip_id, vf.driver_source, vf.driver_identified_at,
+                      vf.identification_deadline, vf.defense_deadline, vf.appeal_deadline,
+                      vf.is_deleted, vf.created_at, vf.updated_at
                FROM vehicle_fines vf
                LEFT JOIN vehicles v ON v.id = vf.vehicle_id
                LEFT JOIN vehicle_fine_types ft ON ft.id = vf.fine_type_id
@@ -396,8 +398,10 @@ impl VehicleFineRepositoryPort for VehicleFineRepository {
                       vf.auto_number, vf.fine_date, vf.notification_date, vf.due_date,
                       vf.location, vf.sei_process_number,
                       vf.fine_amount, vf.discount_amount, vf.paid_amount, vf.payment_date,
-                      vf.status, vf.notes, vf.is_deleted,
-                      vf.created_at, vf.updated_at
+                      vf.status, vf.notes,
+                      vf.trip_id, vf.driver_source, vf.driver_identified_at,
+                      vf.identification_deadline, vf.defense_deadline, vf.appeal_deadline,
+                      vf.is_deleted, vf.created_at, vf.updated_at
                FROM vehicle_fines vf
                LEFT JOIN vehicles v ON v.id = vf.vehicle_id
                LEFT JOIN vehicle_fine_types ft ON ft.id = vf.fine_type_id
@@ -450,8 +454,268 @@ impl VehicleFineRepositoryPort for VehicleFineRepository {
 
         Ok((items, total))
     }
+
+    async fn find_trip_driver_at(
+        &self,
+        vehicle_id: Uuid,
+        at: DateTime<Utc>,
+    ) -> Result<Option<FineTripDriverRef>, RepositoryError> {
+        sqlx::query_as::<_, FineTripDriverRef>(
+            r#"SELECT id AS trip_id, driver_id
+               FROM vehicle_trips
+               WHERE vehicle_id = $1
+                 AND driver_id IS NOT NULL
+                 AND checkout_em IS NOT NULL
+                 AND checkout_em <= $2
+                 -- Sem check-in, a viagem só cobre até o retorno previsto
+                 AND COALESCE(checkin_em, data_retorno_prevista) >= $2
+               ORDER BY checkout_em DESC
+               LIMIT 1"#,
+        )
+        .bind(vehicle_id)
+        .bind(at)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn assign_driver(
+        &self,
+        id: Uuid,
+        driver_id: Uuid,
+        trip_id: Option<Uuid>,
+        source: &FineDriverSource,
+        updated_by: Option<Uuid>,
+    ) -> Result<VehicleFineDto, RepositoryError> {
+        sqlx::query_as::<_, VehicleFineDto>(
+            r#"UPDATE vehicle_fines SET
+                driver_id = $2,
+                trip_id = $3,
+                driver_source = $4,
+                driver_identified_at = NOW(),
+                updated_by = COALESCE($5, updated_by)
+               WHERE id = $1
+               RETURNING *"#,
+        )
+        .bind(id)
+        .bind(driver_id)
+        .bind(trip_id)
+        .bind(source)
+        .bind(updated_by)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn set_deadlines(
+        &self,
+        id: Uuid,
+        identification_deadline: Option<NaiveDate>,
+        defense_deadline: Option<NaiveDate>,
+        appeal_deadline: Option<NaiveDate>,
+    ) -> Result<VehicleFineDto, RepositoryError> {
+        sqlx::query_as::<_, VehicleFineDto>(
+            r#"UPDATE vehicle_fines SET
+                identification_deadline = $2,
+                defense_deadline = $3,
+                appeal_deadline = $4
+               WHERE id = $1
+               RETURNING *"#,
+        )
+        .bind(id)
+        .bind(identification_deadline)
+        .bind(defense_deadline)
+        .bind(appeal_deadline)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn find_identification_form(
+        &self,
+        id: Uuid,
+    ) -> Result<Option<FineIdentificationFormDto>, RepositoryError> {
+        sqlx::query_as::<_, FineIdentificationFormDto>(
+            r#"SELECT vf.id AS fine_id, vf.auto_number, vf.fine_date, vf.notification_date, vf.location,
+                      ft.code AS infraction_code, ft.description AS infraction_description,
+                      ft.points AS infraction_points,
+                      s.legal_name AS issuing_authority,
+                      v.license_plate AS vehicle_license_plate, v.renavam AS vehicle_renavam,
+                      d.full_name AS driver_name, d.cpf AS driver_cpf,
+                      d.cnh_number AS driver_cnh_number, d.cnh_category AS driver_cnh_category,
+                      d.cnh_expiration AS driver_cnh_expiration,
+                      vf.trip_id, vf.identification_deadline, vf.sei_process_number
+               FROM vehicle_fines vf
+               JOIN vehicles v ON v.id = vf.vehicle_id
+               JOIN vehicle_fine_types ft ON ft.id = vf.fine_type_id
+               JOIN drivers d ON d.id = vf.driver_id
+               LEFT JOIN suppliers s ON s.id = vf.supplier_id
+               WHERE vf.id = $1"#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn list_open_deadlines(
+        &self,
+        until: NaiveDate,
+    ) -> Result<Vec<VehicleFineWithDetailsDto>, RepositoryError> {
+        sqlx::query_as::<_, VehicleFineWithDetailsDto>(
+            r#"SELECT vf.id, vf.vehicle_id, v.license_plate AS vehicle_license_plate,
+                      vf.fine_type_id, ft.code AS fine_type_code, ft.description AS fine_type_description,
+                      ft.severity AS fine_type_severity, ft.points AS fine_type_points,
+                      vf.supplier_id, s.legal_name AS supplier_name,
+                      vf.driver_id, d.full_name AS driver_name,
+                      vf.auto_number, vf.fine_date, vf.notification_date, vf.due_date,
+                      vf.location, vf.sei_process_number,
+                      vf.fine_amount, vf.discount_amount, vf.paid_amount, vf.payment_date,
+                      vf.status, vf.notes,
+                      vf.trip_id, vf.driver_source, vf.driver_identified_at,
+                      vf.identification_deadline, vf.defense_deadline, vf.appeal_deadline,
+                      vf.is_deleted, vf.created_at, vf.updated_at
+               FROM vehicle_fines vf
+               LEFT JOIN vehicles v ON v.id = vf.vehicle_id
+               LEFT JOIN vehicle_fine_types ft ON ft.id = vf.fine_type_id
+               LEFT JOIN suppliers s ON s.id = vf.supplier_id
+               LEFT JOIN drivers d ON d.id = vf.driver_id
+               WHERE vf.is_deleted = false
+                 AND vf.status NOT IN ('PAID', 'CANCELLED', 'DEFENSE_ACCEPTED')
+                 AND (vf.identification_deadline <= $1
+                      OR vf.defense_deadline <= $1
+                      OR vf.appeal_deadline <= $1)
+               ORDER BY LEAST(vf.identification_deadline, vf.defense_deadline, vf.appeal_deadline)"#,
+        )
+        .bind(until)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn driver_points_totals(
+        &self,
+        since: DateTime<Utc>,
+        driver_id: Option<Uuid>,
+    ) -> Result<Vec<DriverPointsTotalsDto>, RepositoryError> {
+        sqlx::query_as::<_, DriverPointsTotalsDto>(
+            r#"SELECT d.id AS driver_id, d.full_name AS driver_name, d.cnh_number,
+                      COALESCE(SUM(ft.points), 0)::BIGINT AS total_points,
+                      COUNT(vf.id) AS fine_count,
+                      COUNT(vf.id) FILTER (WHERE ft.severity = 'VERY_SERIOUS') AS very_serious_count
+               FROM vehicle_fines vf
+               JOIN drivers d ON d.id = vf.driver_id
+               JOIN vehicle_fine_types ft ON ft.id = vf.fine_type_id
+               WHERE vf.is_deleted = false
+                 AND vf.status NOT IN ('CANCELLED', 'DEFENSE_ACCEPTED')
+                 AND vf.fine_date >= $1
+                 AND ($2::UUID IS NULL OR vf.driver_id = $2)
+               GROUP BY d.id, d.full_name, d.cnh_number
+               ORDER BY total_points DESC, d.full_name"#,
+        )
+        .bind(since)
+        .bind(driver_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn upsert_points_alert(
+        &self,
+        driver_id: Uuid,
+        vehicle_fine_id: Uuid,
+        alert_level: DriverPointsAlertLevel,
+        total_points: i64,
+        suspension_threshold: i64,
+    ) -> Result<DriverPointsAlertDto, RepositoryError> {
+        let mut tx = self.pool.begin().await.map_err(map_db_error)?;
+
+        let id: Uuid = sqlx::query_scalar(
+            r#"INSERT INTO driver_points_alerts
+                   (driver_id, vehicle_fine_id, alert_level, total_points, suspension_threshold)
+               VALUES ($1, $2, $3, $4, $5)
+               ON CONFLICT (driver_id, alert_level) WHERE acknowledged_at IS NULL AND superseded_by IS NULL
+               DO UPDATE SET vehicle_fine_id = EXCLUDED.vehicle_fine_id,
+                             total_points = EXCLUDED.total_points,
+                             suspension_threshold = EXCLUDED.suspension_threshold
+               RETURNING id"#,
+        )
+        .bind(driver_id)
+        .bind(vehicle_fine_id)
+        .bind(alert_level)
+        .bind(total_points)
+        .bind(suspension_threshold)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(map_db_error)?;
+
+        // A suspensão encerra o alerta de WARNING ainda aberto
+        if alert_level == DriverPointsAlertLevel::Suspension {
+            sqlx::query(
+                "UPDATE driver_points_alerts SET superseded_by = $2
+                 WHERE driver_id = $1 AND alert_level = 'WARNING'
+                   AND acknowledged_at IS NULL AND superseded_by IS NULL",
+            )
+            .bind(driver_id)
+            .bind(id)
+            .execute(&mut *tx)
+            .await
+            .map_err(map_db_error)?;
+        }
+
+        tx.commit().await.map_err(map_db_error)?;
+
+        self.find_points_alert_by_id(id)
+            .await?
+            .ok_or(RepositoryError::NotFound)
+    }
+
+    async fn find_points_alert_by_id(&self, id: Uuid) -> Result<Option<DriverPointsAlertDto>, RepositoryError> {
+        sqlx::query_as::<_, DriverPointsAlertDto>(&format!("{} WHERE a.id = $1", POINTS_ALERT_SELECT))
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(map_db_error)
+    }
+
+    async fn list_points_alerts(&self, include_acknowledged: bool) -> Result<Vec<DriverPointsAlertDto>, RepositoryError> {
+        sqlx::query_as::<_, DriverPointsAlertDto>(&format!(
+            "{} WHERE $1 OR (a.acknowledged_at IS NULL AND a.superseded_by IS NULL) ORDER BY a.updated_at DESC",
+            POINTS_ALERT_SELECT
+        ))
+        .bind(include_acknowledged)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn acknowledge_points_alert(
+        &self,
+        id: Uuid,
+        acknowledged_by: Uuid,
+    ) -> Result<DriverPointsAlertDto, RepositoryError> {
+        sqlx::query(
+            "UPDATE driver_points_alerts SET acknowledged_at = NOW(), acknowledged_by = $2
+             WHERE id = $1 AND acknowledged_at IS NULL",
+        )
+        .bind(id)
+        .bind(acknowledged_by)
+        .execute(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        self.find_points_alert_by_id(id)
+            .await?
+            .ok_or(RepositoryError::NotFound)
+    }
 }
 
+const POINTS_ALERT_SELECT: &str = r#"SELECT a.id, a.driver_id, d.full_name AS driver_name, d.cnh_number,
+       a.vehicle_fine_id, a.alert_level, a.total_points, a.suspension_threshold,
+       a.acknowledged_at, a.acknowledged_by, a.superseded_by, a.created_at, a.updated_at
+FROM driver_points_alerts a
+JOIN drivers d ON d.id = a.driver_id"#;
+
 // ============================
 // Vehicle Fine Status History Repository
 // ============================
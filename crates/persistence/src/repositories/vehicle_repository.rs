@@ -269,6 +269,7 @@ impl VehicleModelRepositoryPort for VehicleModelRepository {
                 m.passenger_capacity, m.engine_displacement, m.horsepower,
                 m.load_capacity,
                 m.avg_consumption_min, m.avg_consumption_max, m.avg_consumption_target,
+                m.preventive_interval_km,
                 m.is_active, m.created_at, m.updated_at
             FROM vehicle_models m
             JOIN vehicle_makes mk ON m.make_id = mk.id
@@ -315,9 +316,10 @@ impl VehicleModelRepositoryPort for VehicleModelRepository {
         avg_consumption_min: Option<Decimal>,
         avg_consumption_max: Option<Decimal>,
         avg_consumption_target: Option<Decimal>,
+        preventive_interval_km: Option<i32>,
     ) -> Result<VehicleModelDto, RepositoryError> {
         sqlx::query_as::<_, VehicleModelDto>(
-            "INSERT INTO vehicle_models (make_id, category_id, name, passenger_capacity, engine_displacement, horsepower, load_capacity, avg_consumption_min, avg_consumption_max, avg_consumption_target) VALUES ($1,$2,$3,$4,$5,$6,$7,$8,$9,$10) RETURNING *"
+            "INSERT INTO vehicle_models (make_id, category_id, name, passenger_capacity, engine_displacement, horsepower, load_capacity, avg_consumption_min, avg_consumption_max, avg_consumption_target, preventive_interval_km) VALUES ($1,$2,$3,$4,$5,$6,$7,$8,$9,$10,$11) RETURNING *"
         )
         .bind(make_id)
         .bind(category_id)
@@ -329,6 +331,7 @@ impl VehicleModelRepositoryPort for VehicleModelRepository {
         .bind(avg_consumption_min)
         .bind(avg_consumption_max)
         .bind(avg_consumption_target)
+        .bind(preventive_interval_km)
         .fetch_one(&self.pool)
         .await
         .map_err(map_db_error)
@@ -346,6 +349,7 @@ impl VehicleModelRepositoryPort for VehicleModelRepository {
         avg_consumption_min: Option<Decimal>,
         avg_consumption_max: Option<Decimal>,
         avg_consumption_target: Option<Decimal>,
+        preventive_interval_km: Option<i32>,
         is_active: Option<bool>,
     ) -> Result<VehicleModelDto, RepositoryError> {
         sqlx::query_as::<_, VehicleModelDto>(
@@ -360,7 +364,8 @@ impl VehicleModelRepositoryPort for VehicleModelRepository {
                 avg_consumption_min = CASE WHEN $8::NUMERIC IS NOT NULL THEN $8 ELSE avg_consumption_min END,
                 avg_consumption_max = CASE WHEN $9::NUMERIC IS NOT NULL THEN $9 ELSE avg_consumption_max END,
                 avg_consumption_target = CASE WHEN $10::NUMERIC IS NOT NULL THEN $10 ELSE avg_consumption_target END,
-                is_active = COALESCE($11, is_active),
+                preventive_interval_km = CASE WHEN $11::INT IS NOT NULL THEN $11 ELSE preventive_interval_km END,
+                is_active = COALESCE($12, is_active),
                 updated_at = NOW()
             WHERE id = $1
             RETURNING *
@@ -376,6 +381,7 @@ impl VehicleModelRepositoryPort for VehicleModelRepository {
         .bind(avg_consumption_min)
         .bind(avg_consumption_max)
         .bind(avg_consumption_target)
+        .bind(preventive_interval_km)
         .bind(is_active)
         .fetch_one(&self.pool)
         .await
@@ -402,6 +408,7 @@ impl VehicleModelRepositoryPort for VehicleModelRepository {
                 m.passenger_capacity, m.engine_displacement, m.horsepower,
                 m.load_capacity,
                 m.avg_consumption_min, m.avg_consumption_max, m.avg_consumption_target,
+                m.preventive_interval_km,
                 m.is_active, m.created_at, m.updated_at
             FROM vehicle_models m
             JOIN vehicle_makes mk ON m.make_id = mk.id
@@ -1202,6 +1209,7 @@ impl VehicleRepositoryPort for VehicleRepository {
         fuel_type_id: Option<Uuid>,
         department_id: Option<Uuid>,
         include_deleted: bool,
+        unit_ids: Option<&[Uuid]>,
     ) -> Result<(Vec<VehicleWithDetailsDto>, i64), RepositoryError> {
         let search_pattern = search.map(|s| format!("%{}%", s));
 
@@ -1236,6 +1244,7 @@ impl VehicleRepositoryPort for VehicleRepository {
               AND ($4::UUID IS NULL OR v.model_id = $4)
               AND ($5::UUID IS NULL OR v.fuel_type_id = $5)
               AND ($6::UUID IS NULL OR v.department_id = $6)
+              AND ($9::UUID[] IS NULL OR v.department_id = ANY($9))
             ORDER BY v.updated_at DESC
             LIMIT $7 OFFSET $8
             "#
@@ -1248,6 +1257,7 @@ impl VehicleRepositoryPort for VehicleRepository {
         .bind(department_id)
         .bind(limit)
         .bind(offset)
+        .bind(unit_ids)
         .fetch_all(&self.pool)
         .await
         .map_err(map_db_error)?;
@@ -1304,6 +1314,7 @@ impl VehicleRepositoryPort for VehicleRepository {
               AND ($4::UUID IS NULL OR v.model_id = $4)
               AND ($5::UUID IS NULL OR v.fuel_type_id = $5)
               AND ($6::UUID IS NULL OR v.department_id = $6)
+              AND ($7::UUID[] IS NULL OR v.department_id = ANY($7))
             "#
         )
         .bind(include_deleted)
@@ -1312,6 +1323,7 @@ impl VehicleRepositoryPort for VehicleRepository {
         .bind(model_id)
         .bind(fuel_type_id)
         .bind(department_id)
+        .bind(unit_ids)
         .fetch_one(&self.pool)
         .await
         .map_err(map_db_error)?;
@@ -1319,7 +1331,12 @@ impl VehicleRepositoryPort for VehicleRepository {
         Ok((vehicles, total))
     }
 
-    async fn search_autocomplete(&self, query: &str, limit: i64) -> Result<Vec<VehicleDto>, RepositoryError> {
+    async fn search_autocomplete(
+        &self,
+        query: &str,
+        limit: i64,
+        unit_ids: Option<&[Uuid]>,
+    ) -> Result<Vec<VehicleDto>, RepositoryError> {
         let pattern = format!("%{}%", query);
         sqlx::query_as::<_, VehicleDto>(
             r#"
@@ -1328,12 +1345,14 @@ impl VehicleRepositoryPort for VehicleRepository {
             JOIN vehicle_makes mk ON m.make_id = mk.id
             WHERE v.is_deleted = false
               AND (v.license_plate ILIKE $1 OR v.chassis_number ILIKE $1 OR v.renavam ILIKE $1 OR v.patrimony_number ILIKE $1 OR mk.name ILIKE $1 OR m.name ILIKE $1)
+              AND ($3::UUID[] IS NULL OR v.department_id = ANY($3))
             ORDER BY v.license_plate
             LIMIT $2
             "#
         )
         .bind(pattern)
         .bind(limit)
+        .bind(unit_ids)
         .fetch_all(&self.pool)
         .await
         .map_err(map_db_error)
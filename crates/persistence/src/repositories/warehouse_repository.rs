@@ -8,7 +8,7 @@ use rust_decimal::Decimal;
 use sqlx::{PgPool, Row};
 use uuid::Uuid;
 
-use crate::db_utils::map_db_error;
+use crate::db_utils::{begin_audited, map_db_error};
 
 // ============================
 // Warehouse Repository
@@ -97,7 +97,8 @@ impl WarehouseRepositoryPort for WarehouseRepository {
         phone: Option<&str>,
         email: Option<&str>,
     ) -> Result<WarehouseDto, RepositoryError> {
-        sqlx::query_as::<_, WarehouseDto>(
+        let mut tx = begin_audited(&self.pool).await?;
+        let saved = sqlx::query_as::<_, WarehouseDto>(
             r#"INSERT INTO warehouses (
                 name, code, warehouse_type, city_id,
                 responsible_user_id, responsible_unit_id,
@@ -117,9 +118,12 @@ impl WarehouseRepositoryPort for WarehouseRepository {
         .bind(address)
         .bind(phone)
         .bind(email)
-        .fetch_one(&self.pool)
+        .fetch_one(&mut *tx)
         .await
-        .map_err(map_db_error)
+        .map_err(map_db_error)?;
+
+        tx.commit().await.map_err(map_db_error)?;
+        Ok(saved)
     }
 
     async fn update(
@@ -138,7 +142,8 @@ impl WarehouseRepositoryPort for WarehouseRepository {
         email: Option<&str>,
         is_active: Option<bool>,
     ) -> Result<WarehouseDto, RepositoryError> {
-        sqlx::query_as::<_, WarehouseDto>(
+        let mut tx = begin_audited(&self.pool).await?;
+        let saved = sqlx::query_as::<_, WarehouseDto>(
             r#"UPDATE warehouses SET
                 name = COALESCE($2, name),
                 code = COALESCE($3, code),
@@ -168,17 +173,22 @@ impl WarehouseRepositoryPort for WarehouseRepository {
         .bind(phone)
         .bind(email)
         .bind(is_active)
-        .fetch_one(&self.pool)
+        .fetch_one(&mut *tx)
         .await
-        .map_err(map_db_error)
+        .map_err(map_db_error)?;
+
+        tx.commit().await.map_err(map_db_error)?;
+        Ok(saved)
     }
 
     async fn delete(&self, id: Uuid) -> Result<bool, RepositoryError> {
+        let mut tx = begin_audited(&self.pool).await?;
         let result = sqlx::query("DELETE FROM warehouses WHERE id = $1")
             .bind(id)
-            .execute(&self.pool)
+            .execute(&mut *tx)
             .await
             .map_err(map_db_error)?;
+        tx.commit().await.map_err(map_db_error)?;
         Ok(result.rows_affected() > 0)
     }
 
@@ -190,6 +200,7 @@ impl WarehouseRepositoryPort for WarehouseRepository {
         warehouse_type: Option<WarehouseType>,
         city_id: Option<Uuid>,
         is_active: Option<bool>,
+        unit_ids: Option<&[Uuid]>,
     ) -> Result<(Vec<WarehouseWithDetailsDto>, i64), RepositoryError> {
         let mut where_clauses = Vec::new();
         let mut param_index = 1u32;
@@ -213,6 +224,10 @@ impl WarehouseRepositoryPort for WarehouseRepository {
             where_clauses.push(format!("w.is_active = ${}", param_index));
             param_index += 1;
         }
+        if unit_ids.is_some() {
+            where_clauses.push(format!("w.responsible_unit_id = ANY(${})", param_index));
+            param_index += 1;
+        }
 
         let where_sql = if where_clauses.is_empty() {
             String::new()
@@ -262,6 +277,10 @@ impl WarehouseRepositoryPort for WarehouseRepository {
             count_query = count_query.bind(active);
             list_query = list_query.bind(active);
         }
+        if let Some(ids) = unit_ids {
+            count_query = count_query.bind(ids);
+            list_query = list_query.bind(ids);
+        }
 
         count_query = count_query.bind(limit);
         list_query = list_query.bind(limit).bind(offset);
@@ -450,7 +469,8 @@ impl WarehouseStockRepositoryPort for WarehouseStockRepository {
         location: Option<&str>,
         secondary_location: Option<&str>,
     ) -> Result<WarehouseStockDto, RepositoryError> {
-        sqlx::query_as::<_, WarehouseStockDto>(
+        let mut tx = begin_audited(&self.pool).await?;
+        let saved = sqlx::query_as::<_, WarehouseStockDto>(
             r#"UPDATE warehouse_stocks SET
                 min_stock = COALESCE($2, min_stock),
                 max_stock = COALESCE($3, max_stock),
@@ -468,9 +488,12 @@ impl WarehouseStockRepositoryPort for WarehouseStockRepository {
         .bind(resupply_days)
         .bind(location)
         .bind(secondary_location)
-        .fetch_one(&self.pool)
+        .fetch_one(&mut *tx)
         .await
-        .map_err(map_db_error)
+        .map_err(map_db_error)?;
+
+        tx.commit().await.map_err(map_db_error)?;
+        Ok(saved)
     }
 
     async fn block(
@@ -479,7 +502,8 @@ impl WarehouseStockRepositoryPort for WarehouseStockRepository {
         block_reason: &str,
         blocked_by: Uuid,
     ) -> Result<WarehouseStockDto, RepositoryError> {
-        sqlx::query_as::<_, WarehouseStockDto>(
+        let mut tx = begin_audited(&self.pool).await?;
+        let saved = sqlx::query_as::<_, WarehouseStockDto>(
             r#"UPDATE warehouse_stocks SET
                 is_blocked = TRUE,
                 block_reason = $2,
@@ -491,13 +515,17 @@ impl WarehouseStockRepositoryPort for WarehouseStockRepository {
         .bind(id)
         .bind(block_reason)
         .bind(blocked_by)
-        .fetch_one(&self.pool)
+        .fetch_one(&mut *tx)
         .await
-        .map_err(map_db_error)
+        .map_err(map_db_error)?;
+
+        tx.commit().await.map_err(map_db_error)?;
+        Ok(saved)
     }
 
     async fn unblock(&self, id: Uuid) -> Result<WarehouseStockDto, RepositoryError> {
-        sqlx::query_as::<_, WarehouseStockDto>(
+        let mut tx = begin_audited(&self.pool).await?;
+        let saved = sqlx::query_as::<_, WarehouseStockDto>(
             r#"UPDATE warehouse_stocks SET
                 is_blocked = FALSE,
                 block_reason = NULL,
@@ -507,8 +535,11 @@ impl WarehouseStockRepositoryPort for WarehouseStockRepository {
                RETURNING *"#,
         )
         .bind(id)
-        .fetch_one(&self.pool)
+        .fetch_one(&mut *tx)
         .await
-        .map_err(map_db_error)
+        .map_err(map_db_error)?;
+
+        tx.commit().await.map_err(map_db_error)?;
+        Ok(saved)
     }
 }
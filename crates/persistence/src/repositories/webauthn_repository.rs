@@ -0,0 +1,210 @@
+use async_trait::async_trait;
+use domain::errors::RepositoryError;
+use domain::models::{StoredWebauthnCredential, WebauthnChallenge, WebauthnCredentialDto};
+use domain::ports::WebauthnRepositoryPort;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::db_utils::map_db_error;
+
+const CREDENTIAL_COLUMNS: &str = "id, user_id, name, credential_id, sign_count, backup_eligible, \
+     backup_state, clone_detected_at, created_at, last_used_at";
+
+const STORED_COLUMNS: &str = "id, user_id, credential_id, passkey, sign_count, clone_detected_at";
+
+#[derive(Clone)]
+pub struct WebauthnRepository {
+    pool: PgPool,
+}
+
+impl WebauthnRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl WebauthnRepositoryPort for WebauthnRepository {
+    async fn save_challenge(&self, challenge: &WebauthnChallenge) -> Result<(), RepositoryError> {
+        sqlx::query(
+            r#"
+            INSERT INTO webauthn_challenges (id, user_id, kind, state, expires_at)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+        )
+        .bind(challenge.id)
+        .bind(challenge.user_id)
+        .bind(&challenge.kind)
+        .bind(&challenge.state)
+        .bind(challenge.expires_at)
+        .execute(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+        Ok(())
+    }
+
+    async fn take_challenge(&self, id: Uuid) -> Result<Option<WebauthnChallenge>, RepositoryError> {
+        sqlx::query_as::<_, WebauthnChallenge>(
+            r#"
+            DELETE FROM webauthn_challenges
+            WHERE id = $1
+            RETURNING id, user_id, kind, state, expires_at
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn purge_expired_challenges(&self) -> Result<u64, RepositoryError> {
+        let result = sqlx::query("DELETE FROM webauthn_challenges WHERE expires_at < NOW()")
+            .execute(&self.pool)
+            .await
+            .map_err(map_db_error)?;
+        Ok(result.rows_affected())
+    }
+
+    async fn create_credential(
+        &self,
+        user_id: Uuid,
+        name: &str,
+        credential_id: &str,
+        passkey: &serde_json::Value,
+        backup_eligible: bool,
+        backup_state: bool,
+    ) -> Result<WebauthnCredentialDto, RepositoryError> {
+        sqlx::query_as::<_, WebauthnCredentialDto>(&format!(
+            r#"
+            INSERT INTO webauthn_credentials
+                (user_id, name, credential_id, passkey, backup_eligible, backup_state)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING {}
+            "#,
+            CREDENTIAL_COLUMNS
+        ))
+        .bind(user_id)
+        .bind(name)
+        .bind(credential_id)
+        .bind(passkey)
+        .bind(backup_eligible)
+        .bind(backup_state)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn list_by_user(&self, user_id: Uuid) -> Result<Vec<WebauthnCredentialDto>, RepositoryError> {
+        sqlx::query_as::<_, WebauthnCredentialDto>(&format!(
+            "SELECT {} FROM webauthn_credentials WHERE user_id = $1 ORDER BY created_at",
+            CREDENTIAL_COLUMNS
+        ))
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn list_active_stored_by_user(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<StoredWebauthnCredential>, RepositoryError> {
+        sqlx::query_as::<_, StoredWebauthnCredential>(&format!(
+            "SELECT {} FROM webauthn_credentials \
+             WHERE user_id = $1 AND clone_detected_at IS NULL ORDER BY created_at",
+            STORED_COLUMNS
+        ))
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn find_stored_by_credential_id(
+        &self,
+        credential_id: &str,
+    ) -> Result<Option<StoredWebauthnCredential>, RepositoryError> {
+        sqlx::query_as::<_, StoredWebauthnCredential>(&format!(
+            "SELECT {} FROM webauthn_credentials WHERE credential_id = $1",
+            STORED_COLUMNS
+        ))
+        .bind(credential_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn exists_by_credential_id(&self, credential_id: &str) -> Result<bool, RepositoryError> {
+        sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM webauthn_credentials WHERE credential_id = $1)",
+        )
+        .bind(credential_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn record_use(
+        &self,
+        id: Uuid,
+        passkey: &serde_json::Value,
+        sign_count: i64,
+        backup_state: bool,
+    ) -> Result<(), RepositoryError> {
+        sqlx::query(
+            r#"
+            UPDATE webauthn_credentials
+            SET passkey = $2, sign_count = $3, backup_state = $4, last_used_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(passkey)
+        .bind(sign_count)
+        .bind(backup_state)
+        .execute(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+        Ok(())
+    }
+
+    async fn flag_clone_detected(&self, credential_id: &str) -> Result<(), RepositoryError> {
+        sqlx::query(
+            "UPDATE webauthn_credentials SET clone_detected_at = COALESCE(clone_detected_at, NOW()) \
+             WHERE credential_id = $1",
+        )
+        .bind(credential_id)
+        .execute(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+        Ok(())
+    }
+
+    async fn rename(
+        &self,
+        user_id: Uuid,
+        id: Uuid,
+        name: &str,
+    ) -> Result<Option<WebauthnCredentialDto>, RepositoryError> {
+        sqlx::query_as::<_, WebauthnCredentialDto>(&format!(
+            "UPDATE webauthn_credentials SET name = $3 WHERE id = $1 AND user_id = $2 RETURNING {}",
+            CREDENTIAL_COLUMNS
+        ))
+        .bind(id)
+        .bind(user_id)
+        .bind(name)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_db_error)
+    }
+
+    async fn delete(&self, user_id: Uuid, id: Uuid) -> Result<bool, RepositoryError> {
+        let result = sqlx::query("DELETE FROM webauthn_credentials WHERE id = $1 AND user_id = $2")
+            .bind(id)
+            .bind(user_id)
+            .execute(&self.pool)
+            .await
+            .map_err(map_db_error)?;
+        Ok(result.rows_affected() > 0)
+    }
+}